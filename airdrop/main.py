@@ -0,0 +1,281 @@
+import yaml
+import sys
+import json
+import time
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+from Crypto.Hash import keccak
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'airdrop.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'airdrop-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_program(payer: Keypair, decimals: int) -> Token:
+    token = Token.create_mint(
+        conn=client,
+        payer=payer,
+        mint_authority=payer.public_key,
+        decimals=decimals,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    print(f"Address of token program: {token.pubkey}")
+    return token
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def mint_token(token: Token, minter: Keypair, account: PublicKey, amount: int):
+    print(f"Minting {amount} tokens to {account}")
+    token.mint_to(
+        dest=account,
+        mint_authority=minter,
+        amount=amount,
+        opts=TxOpts(skip_confirmation=False)
+    )
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+
+def keccak256(*parts: bytes) -> bytes:
+    return keccak.new(data=b"".join(parts), digest_bits=256).digest()
+
+def leaf(index: int, claimant: PublicKey, amount: int) -> bytes:
+    return keccak256(b"\x00", index.to_bytes(8, byteorder='little'), bytes(claimant), amount.to_bytes(8, byteorder='little'))
+
+def node(a: bytes, b: bytes) -> bytes:
+    return keccak256(b"\x01", min(a, b), max(a, b))
+
+# Builds all levels of the tree, the last level only contains the root. A node without a sibling is moved up as it is.
+def build_tree(leaves: list) -> list:
+    levels = [leaves]
+    while len(levels[-1]) > 1:
+        level = levels[-1]
+        levels.append([node(level[i], level[i + 1]) if i + 1 < len(level) else level[i] for i in range(0, len(level), 2)])
+    return levels
+
+def get_proof(levels: list, index: int) -> list:
+    proof = []
+    for level in levels[:-1]:
+        sibling = index ^ 1
+        if sibling < len(level):
+            proof.append(level[sibling])
+        index //= 2
+    return proof
+
+def get_airdrop_address(authority: PublicKey, mint: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("airdrop", encoding="utf8"), bytes(authority), bytes(mint)],
+        program_id
+    )[0]
+
+def get_claim_address(airdrop: PublicKey, index: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("claim", encoding="utf8"), bytes(airdrop), index.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def init_airdrop(authority: Keypair, token: Token, merkle_root: bytes) -> PublicKey:
+    airdrop = get_airdrop_address(authority.public_key, token.pubkey)
+    # the vault is a normal token account owned by the airdrop PDA
+    vault = create_token_account(token, airdrop)
+    print(f"\nCreating airdrop {airdrop} with merkle root {merkle_root.hex()}")
+    init_ix = TransactionInstruction(
+        data=(0).to_bytes(1, byteorder='little') + merkle_root,
+        keys=[
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=airdrop, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(init_ix), authority)
+    client.confirm_transaction(tx["result"])
+    return vault
+
+def claim(claimant: Keypair, airdrop: PublicKey, vault: PublicKey, destination: PublicKey, index: int, amount: int, proof: list):
+    print(f"\n{claimant.public_key} claims {amount} tokens with a proof of {len(proof)} hashes")
+    claim_ix = TransactionInstruction(
+        data=(
+            (1).to_bytes(1, byteorder='little')
+            + index.to_bytes(8, byteorder='little')
+            + amount.to_bytes(8, byteorder='little')
+            + b"".join(proof)
+        ),
+        keys=[
+            AccountMeta(pubkey=claimant.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=airdrop, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_claim_address(airdrop, index), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(claim_ix), claimant)
+    client.confirm_transaction(tx["result"])
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+
+    # everybody on the list with the amount they may claim, only the root goes on-chain
+    recipients = [(alice.public_key, 100), (bob.public_key, 250), (Keypair().public_key, 50)]
+    levels = build_tree([leaf(index, claimant, amount) for index, (claimant, amount) in enumerate(recipients)])
+    merkle_root = levels[-1][0]
+
+    token = create_token_program(payer, decimals=0)
+    vault = init_airdrop(payer, token, merkle_root)
+    mint_token(token, payer, vault, sum(amount for _, amount in recipients))
+    airdrop = get_airdrop_address(payer.public_key, token.pubkey)
+
+    alice_account = create_token_account(token, alice.public_key)
+    claim(alice, airdrop, vault, alice_account, 0, 100, get_proof(levels, 0))
+    print(f"Alice token balance: {token_balance(token, alice_account)}")
+
+    try:
+        claim(alice, airdrop, vault, alice_account, 0, 100, get_proof(levels, 0))
+    except Exception as e:
+        print(f"Claiming twice fails as expected: {e}")
+
+    bob_account = create_token_account(token, bob.public_key)
+    try:
+        claim(bob, airdrop, vault, bob_account, 1, 1000, get_proof(levels, 1))
+    except Exception as e:
+        print(f"Claiming more than listed fails as expected: {e}")
+    claim(bob, airdrop, vault, bob_account, 1, 250, get_proof(levels, 1))
+    print(f"Bob token balance: {token_balance(token, bob_account)}")
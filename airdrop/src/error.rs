@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum AirdropError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Airdrop Address")]
+    InvalidAirdropAddress,
+    #[error("Invalid Claim Address")]
+    InvalidClaimAddress,
+    #[error("Invalid Vault")]
+    InvalidVault,
+    #[error("Invalid Proof")]
+    InvalidProof,
+    #[error("Already Claimed")]
+    AlreadyClaimed,
+}
+
+impl From<AirdropError> for ProgramError {
+    fn from(e: AirdropError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,84 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::AirdropError::InvalidInstruction;
+
+pub enum AirdropInstruction {
+
+    // Creates an airdrop in a PDA derived from ["airdrop", authority, mint]. The vault has to be created prior to
+    // this instruction and owned by the airdrop PDA, the authority funds it with a normal token transfer.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The authority
+    // 1. `[writable]` The airdrop PDA
+    // 2. `[]` The vault, a token account owned by the airdrop PDA
+    // 3. `[]` The system program
+    InitAirdrop {
+        merkle_root: [u8; 32],
+    },
+
+    // Claims the amount of a leaf. The proof is the list of sibling hashes from the leaf up to the root.
+    // The claim is recorded in a PDA derived from ["claim", airdrop, index].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The claimant
+    // 1. `[]` The airdrop PDA
+    // 2. `[writable]` The claim PDA
+    // 3. `[writable]` The vault
+    // 4. `[writable]` The token account receiving the tokens
+    // 5. `[]` The system program
+    // 6. `[]` The token program
+    Claim {
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    },
+}
+
+impl AirdropInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitAirdrop {
+                merkle_root: Self::unpack_hash(rest)?,
+            },
+            1 => {
+                let index = Self::unpack_u64(rest, 0)?;
+                let amount = Self::unpack_u64(rest, 8)?;
+                // everything after the two numbers are the 32 byte hashes of the proof
+                let proof_bytes = &rest[16..];
+                if proof_bytes.len() % 32 != 0 {
+                    return Err(InvalidInstruction.into());
+                }
+                let proof = proof_bytes
+                    .chunks_exact(32)
+                    .map(Self::unpack_hash)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Self::Claim { index, amount, proof }
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+
+    fn unpack_hash(input: &[u8]) -> Result<[u8; 32], ProgramError> {
+        let hash = input
+            .get(..32)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(InvalidInstruction)?;
+        Ok(hash)
+    }
+}
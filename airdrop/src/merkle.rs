@@ -0,0 +1,22 @@
+use solana_program::{keccak::hashv, pubkey::Pubkey};
+
+// Leaves and inner nodes are hashed with different prefixes. Otherwise an inner node, which is just 64 bytes,
+// could be passed off as a leaf.
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn leaf(index: u64, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, &index.to_le_bytes(), claimant.as_ref(), &amount.to_le_bytes()]).to_bytes()
+}
+
+// The two children are sorted before hashing, so a proof doesn't need to tell whether a sibling is on the
+// left or on the right.
+fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |hash, sibling| node(&hash, sibling));
+    computed == *root
+}
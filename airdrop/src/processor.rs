@@ -0,0 +1,234 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::Account as TokenAccount;
+
+use crate::{
+    instruction::AirdropInstruction,
+    error::AirdropError,
+    merkle,
+    state::{Airdrop, ClaimStatus},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = AirdropInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            AirdropInstruction::InitAirdrop { merkle_root } => {
+                msg!("Instruction: InitAirdrop");
+                Self::process_init_airdrop(accounts, merkle_root, program_id)
+            },
+            AirdropInstruction::Claim { index, amount, proof } => {
+                msg!("Instruction: Claim");
+                Self::process_claim(accounts, index, amount, proof, program_id)
+            }
+        }
+    }
+
+    fn process_init_airdrop(
+        accounts: &[AccountInfo],
+        merkle_root: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let airdrop_account = next_account_info(account_info_iter)?;
+        let vault = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        if *vault.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let vault_info = TokenAccount::unpack(&vault.try_borrow_data()?)?;
+
+        let (airdrop_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"airdrop", authority.key.as_ref(), vault_info.mint.as_ref()],
+            program_id,
+        );
+        if airdrop_pda != *airdrop_account.key {
+            return Err(AirdropError::InvalidAirdropAddress.into());
+        }
+        if vault_info.owner != airdrop_pda {
+            return Err(AirdropError::InvalidVault.into());
+        }
+
+        if !airdrop_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating airdrop account...");
+        Self::create_pda_account(
+            authority,
+            airdrop_account,
+            system_program,
+            Airdrop::LEN,
+            program_id,
+            &[b"airdrop", authority.key.as_ref(), vault_info.mint.as_ref(), &[bump_seed]],
+        )?;
+
+        let airdrop_info = Airdrop {
+            is_initialized: true,
+            authority: *authority.key,
+            mint: vault_info.mint,
+            vault: *vault.key,
+            merkle_root,
+            bump_seed,
+        };
+        airdrop_info.serialize(&mut &mut airdrop_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_claim(
+        accounts: &[AccountInfo],
+        index: u64,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let claimant = next_account_info(account_info_iter)?;
+
+        if !claimant.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let airdrop_account = next_account_info(account_info_iter)?;
+        if airdrop_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let airdrop_info = Airdrop::try_from_slice(&airdrop_account.data.borrow())?;
+        if !airdrop_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        // the claimant is part of the leaf, so nobody can claim somebody else's tokens with their proof
+        let leaf = merkle::leaf(index, claimant.key, amount);
+        if !merkle::verify(&proof, &airdrop_info.merkle_root, leaf) {
+            return Err(AirdropError::InvalidProof.into());
+        }
+
+        let claim_account = next_account_info(account_info_iter)?;
+        let index_bytes = index.to_le_bytes();
+        let (claim_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"claim", airdrop_account.key.as_ref(), &index_bytes],
+            program_id,
+        );
+        if claim_pda != *claim_account.key {
+            return Err(AirdropError::InvalidClaimAddress.into());
+        }
+
+        // the claim PDA can only be created once, that's what prevents a leaf from being claimed twice
+        if !claim_account.data_is_empty() {
+            return Err(AirdropError::AlreadyClaimed.into());
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != airdrop_info.vault {
+            return Err(AirdropError::InvalidVault.into());
+        }
+
+        let destination = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        msg!("Creating claim account...");
+        Self::create_pda_account(
+            claimant,
+            claim_account,
+            system_program,
+            ClaimStatus::LEN,
+            program_id,
+            &[b"claim", airdrop_account.key.as_ref(), &index_bytes, &[bump_seed]],
+        )?;
+
+        let claim_info = ClaimStatus {
+            airdrop: *airdrop_account.key,
+            index,
+            claimant: *claimant.key,
+            amount,
+        };
+        claim_info.serialize(&mut &mut claim_account.data.borrow_mut()[..])?;
+
+        let transfer_ix = spl_token::instruction::transfer(
+            token_program.key,
+            vault.key,
+            destination.key,
+            airdrop_account.key,
+            &[airdrop_account.key],
+            amount,
+        )?;
+
+        msg!("Calling the token program to transfer {} tokens out of the vault...", amount);
+        invoke_signed(
+            &transfer_ix,
+            &[
+                vault.clone(),
+                destination.clone(),
+                airdrop_account.clone(),
+                token_program.clone(),
+            ],
+            &[&[
+                b"airdrop",
+                airdrop_info.authority.as_ref(),
+                airdrop_info.mint.as_ref(),
+                &[airdrop_info.bump_seed],
+            ]],
+        )?;
+
+        Ok(())
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
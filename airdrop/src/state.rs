@@ -0,0 +1,31 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Airdrop {
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    // token account owned by the airdrop PDA, funded by the authority
+    pub vault: Pubkey,
+    // root of the merkle tree over all (index, claimant, amount) leaves
+    pub merkle_root: [u8; 32],
+    pub bump_seed: u8,
+}
+
+impl Airdrop {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 32 + 1;
+}
+
+// The mere existence of this account marks the leaf as claimed, the data is only there for explorers.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ClaimStatus {
+    pub airdrop: Pubkey,
+    pub index: u64,
+    pub claimant: Pubkey,
+    pub amount: u64,
+}
+
+impl ClaimStatus {
+    pub const LEN: usize = 32 + 8 + 32 + 8;
+}
@@ -0,0 +1,247 @@
+import yaml
+import sys
+import json
+import time
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+ALLOWANCE_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'funder': types.fixed_array(types.u8, 32),
+    'recipient': types.fixed_array(types.u8, 32),
+    'limit_per_epoch': types.u64,
+    'epoch': types.u64,
+    'withdrawn_in_epoch': types.u64,
+    'bump_seed': types.u8
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'allowance.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'allowance-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_allowance_address(funder: PublicKey, recipient: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("allowance", encoding="utf8"), bytes(funder), bytes(recipient)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def create_allowance(funder: Keypair, recipient: PublicKey, limit_per_epoch: int, deposit: int) -> PublicKey:
+    allowance = get_allowance_address(funder.public_key, recipient)
+    print(f"\nCreating allowance {allowance} with {deposit} lamports, at most {limit_per_epoch} lamports per epoch")
+    send(
+        funder,
+        (0).to_bytes(1, byteorder='little') + limit_per_epoch.to_bytes(8, byteorder='little') + deposit.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=funder.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=recipient, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=allowance, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return allowance
+
+def top_up(funder: Keypair, allowance: PublicKey, amount: int):
+    print(f"\nTopping up the allowance with {amount} lamports")
+    send(
+        funder,
+        (1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=funder.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=allowance, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def withdraw(recipient: Keypair, allowance: PublicKey, amount: int):
+    print(f"\nWithdrawing {amount} lamports")
+    send(
+        recipient,
+        (2).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=recipient.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=allowance, is_signer=False, is_writable=True),
+        ],
+    )
+
+def revoke(funder: Keypair, allowance: PublicKey):
+    print("\nRevoking the allowance")
+    send(
+        funder,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=funder.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=allowance, is_signer=False, is_writable=True),
+        ],
+    )
+
+def current_epoch() -> int:
+    return client.get_epoch_info()["result"]["epoch"]
+
+def wait_for_next_epoch():
+    epoch = current_epoch()
+    print(f"\nWaiting for epoch {epoch + 1}, start the validator with --slots-per-epoch to keep this short...")
+    while current_epoch() == epoch:
+        time.sleep(2)
+
+def show_allowance(allowance: PublicKey):
+    account = client.get_account_info(allowance)["result"]["value"]
+    if account is None:
+        print(f"Allowance {allowance} is closed")
+        return
+    allowance_info = borsh.deserialize(ALLOWANCE_SCHEMA, base64.b64decode(account["data"][0]))
+    print(f"Allowance {allowance}: {account['lamports']} lamports, {allowance_info['withdrawn_in_epoch']} of {allowance_info['limit_per_epoch']} withdrawn in epoch {allowance_info['epoch']}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    recipient = setup_user_keypair()
+
+    limit = LAMPORTS_PER_SOL // 10
+    allowance = create_allowance(payer, recipient.public_key, limit, LAMPORTS_PER_SOL // 4)
+    withdraw(recipient, allowance, limit // 2)
+    withdraw(recipient, allowance, limit // 2)
+    show_allowance(allowance)
+
+    try:
+        withdraw(recipient, allowance, 1)
+    except Exception as e:
+        print(f"Withdrawing more than the limit in one epoch fails as expected: {e}")
+
+    wait_for_next_epoch()
+    withdraw(recipient, allowance, limit)
+    show_allowance(allowance)
+
+    top_up(payer, allowance, LAMPORTS_PER_SOL // 10)
+    show_allowance(allowance)
+
+    revoke(payer, allowance)
+    show_allowance(allowance)
@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum AllowanceError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Allowance Address")]
+    InvalidAllowanceAddress,
+    #[error("Invalid Limit")]
+    InvalidLimit,
+    #[error("Not Funder")]
+    NotFunder,
+    #[error("Not Recipient")]
+    NotRecipient,
+    #[error("Limit Exceeded")]
+    LimitExceeded,
+    #[error("Insufficient Funds")]
+    InsufficientFunds,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<AllowanceError> for ProgramError {
+    fn from(e: AllowanceError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,84 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::AllowanceError::InvalidInstruction;
+
+pub enum AllowanceInstruction {
+
+    // Creates an allowance in a PDA derived from ["allowance", funder, recipient]. The allowance account holds
+    // the deposit on top of its rent.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The funder
+    // 1. `[]` The recipient
+    // 2. `[writable]` The allowance PDA
+    // 3. `[]` The system program
+    CreateAllowance {
+        limit_per_epoch: u64,
+        deposit: u64,
+    },
+
+    // Adds lamports to the allowance.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The funder
+    // 1. `[writable]` The allowance PDA
+    // 2. `[]` The system program
+    TopUp {
+        amount: u64,
+    },
+
+    // Withdraws lamports, at most limit_per_epoch within the current epoch.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The recipient
+    // 1. `[writable]` The allowance PDA
+    Withdraw {
+        amount: u64,
+    },
+
+    // Closes the allowance, everything left goes back to the funder.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The funder
+    // 1. `[writable]` The allowance PDA
+    Revoke,
+}
+
+impl AllowanceInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateAllowance {
+                limit_per_epoch: Self::unpack_u64(rest, 0)?,
+                deposit: Self::unpack_u64(rest, 8)?,
+            },
+            1 => Self::TopUp {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::Withdraw {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            3 => Self::Revoke,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
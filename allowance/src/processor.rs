@@ -0,0 +1,234 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{instruction::AllowanceInstruction, error::AllowanceError, state::Allowance};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = AllowanceInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            AllowanceInstruction::CreateAllowance { limit_per_epoch, deposit } => {
+                msg!("Instruction: CreateAllowance");
+                Self::process_create_allowance(accounts, limit_per_epoch, deposit, program_id)
+            },
+            AllowanceInstruction::TopUp { amount } => {
+                msg!("Instruction: TopUp");
+                Self::process_top_up(accounts, amount, program_id)
+            },
+            AllowanceInstruction::Withdraw { amount } => {
+                msg!("Instruction: Withdraw");
+                Self::process_withdraw(accounts, amount, program_id)
+            },
+            AllowanceInstruction::Revoke => {
+                msg!("Instruction: Revoke");
+                Self::process_revoke(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_allowance(
+        accounts: &[AccountInfo],
+        limit_per_epoch: u64,
+        deposit: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if limit_per_epoch == 0 {
+            return Err(AllowanceError::InvalidLimit.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let funder = next_account_info(account_info_iter)?;
+
+        if !funder.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let recipient = next_account_info(account_info_iter)?;
+        let allowance_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (allowance_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"allowance", funder.key.as_ref(), recipient.key.as_ref()],
+            program_id,
+        );
+        if allowance_pda != *allowance_account.key {
+            return Err(AllowanceError::InvalidAllowanceAddress.into());
+        }
+
+        if !allowance_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the deposit is simply added on top of the rent when the account is created
+        let lamports = Rent::get()?.minimum_balance(Allowance::LEN)
+            .checked_add(deposit)
+            .ok_or(AllowanceError::AmountOverflow)?;
+
+        msg!("Creating allowance account holding {} lamports...", deposit);
+        invoke_signed(
+            &system_instruction::create_account(
+                funder.key,
+                allowance_account.key,
+                lamports,
+                Allowance::LEN as u64,
+                program_id,
+            ),
+            &[
+                funder.clone(),
+                allowance_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"allowance", funder.key.as_ref(), recipient.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        let allowance_info = Allowance {
+            is_initialized: true,
+            funder: *funder.key,
+            recipient: *recipient.key,
+            limit_per_epoch,
+            epoch: Clock::get()?.epoch,
+            withdrawn_in_epoch: 0,
+            bump_seed,
+        };
+        allowance_info.serialize(&mut &mut allowance_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_top_up(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let funder = next_account_info(account_info_iter)?;
+
+        if !funder.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let allowance_account = next_account_info(account_info_iter)?;
+        let allowance_info = Self::unpack_allowance(allowance_account, program_id)?;
+        if allowance_info.funder != *funder.key {
+            return Err(AllowanceError::NotFunder.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to add {} lamports...", amount);
+        invoke(
+            &system_instruction::transfer(funder.key, allowance_account.key, amount),
+            &[
+                funder.clone(),
+                allowance_account.clone(),
+                system_program.clone(),
+            ],
+        )
+    }
+
+    fn process_withdraw(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let recipient = next_account_info(account_info_iter)?;
+
+        if !recipient.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let allowance_account = next_account_info(account_info_iter)?;
+        let mut allowance_info = Self::unpack_allowance(allowance_account, program_id)?;
+        if allowance_info.recipient != *recipient.key {
+            return Err(AllowanceError::NotRecipient.into());
+        }
+
+        // An epoch is a fixed number of slots, roughly two days on mainnet. The limit isn't carried over,
+        // whatever the recipient didn't withdraw in an epoch is simply not available on top in the next one.
+        let epoch = Clock::get()?.epoch;
+        let available = allowance_info.available(epoch);
+        if amount > available {
+            msg!("{} lamports are left to withdraw in epoch {}", available, epoch);
+            return Err(AllowanceError::LimitExceeded.into());
+        }
+        if amount > Self::funds(allowance_account)? {
+            return Err(AllowanceError::InsufficientFunds.into());
+        }
+
+        // the allowance account is owned by the program, so lamports are paid out by moving them directly
+        msg!("Withdrawing {} lamports...", amount);
+        **allowance_account.lamports.borrow_mut() -= amount;
+        **recipient.lamports.borrow_mut() = recipient.lamports()
+            .checked_add(amount)
+            .ok_or(AllowanceError::AmountOverflow)?;
+
+        if epoch != allowance_info.epoch {
+            allowance_info.epoch = epoch;
+            allowance_info.withdrawn_in_epoch = 0;
+        }
+        allowance_info.withdrawn_in_epoch += amount;
+        allowance_info.serialize(&mut &mut allowance_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_revoke(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let funder = next_account_info(account_info_iter)?;
+
+        if !funder.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let allowance_account = next_account_info(account_info_iter)?;
+        let allowance_info = Self::unpack_allowance(allowance_account, program_id)?;
+        if allowance_info.funder != *funder.key {
+            return Err(AllowanceError::NotFunder.into());
+        }
+
+        msg!("Closing the allowance account, {} lamports go back to the funder...", allowance_account.lamports());
+        **funder.lamports.borrow_mut() = funder.lamports()
+            .checked_add(allowance_account.lamports())
+            .ok_or(AllowanceError::AmountOverflow)?;
+        **allowance_account.lamports.borrow_mut() = 0;
+        allowance_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_allowance(allowance_account: &AccountInfo, program_id: &Pubkey) -> Result<Allowance, ProgramError> {
+        if allowance_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let allowance_info = Allowance::try_from_slice(&allowance_account.data.borrow())?;
+        if !allowance_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(allowance_info)
+    }
+
+    // lamports held by the allowance on top of its rent
+    fn funds(allowance_account: &AccountInfo) -> Result<u64, ProgramError> {
+        let rent_lamports = Rent::get()?.minimum_balance(allowance_account.data_len());
+        Ok(allowance_account.lamports().saturating_sub(rent_lamports))
+    }
+}
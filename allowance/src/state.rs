@@ -0,0 +1,27 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Allowance {
+    pub is_initialized: bool,
+    pub funder: Pubkey,
+    pub recipient: Pubkey,
+    // the most the recipient may withdraw within one epoch, in lamports
+    pub limit_per_epoch: u64,
+    // the epoch of the last withdrawal and how much was withdrawn in it
+    pub epoch: u64,
+    pub withdrawn_in_epoch: u64,
+    pub bump_seed: u8,
+}
+
+impl Allowance {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 + 1;
+
+    // what is left of the limit in the given epoch, a new epoch starts with the full limit
+    pub fn available(&self, epoch: u64) -> u64 {
+        if epoch != self.epoch {
+            return self.limit_per_epoch;
+        }
+        self.limit_per_epoch.saturating_sub(self.withdrawn_in_epoch)
+    }
+}
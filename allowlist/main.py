@@ -0,0 +1,194 @@
+import yaml
+import sys
+import json
+import base64
+import struct
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'allowlist.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'allowlist-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+
+def get_allowlist_address(admin: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("allowlist", encoding="utf8"), bytes(admin)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def admin_keys(admin: Keypair) -> list:
+    return [
+        AccountMeta(pubkey=admin.public_key, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=get_allowlist_address(admin.public_key), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+    ]
+
+def init_allowlist(admin: Keypair):
+    print(f"\nCreating allowlist {get_allowlist_address(admin.public_key)}")
+    send(admin, (0).to_bytes(1, byteorder='little'), admin_keys(admin))
+
+def add_members(admin: Keypair, members: list):
+    print(f"\nAdding {len(members)} members")
+    send(admin, (1).to_bytes(1, byteorder='little') + b"".join(bytes(member) for member in members), admin_keys(admin))
+
+def remove_members(admin: Keypair, members: list):
+    print(f"\nRemoving {len(members)} members")
+    send(admin, (2).to_bytes(1, byteorder='little') + b"".join(bytes(member) for member in members), admin_keys(admin))
+
+def check_member(caller: Keypair, allowlist: PublicKey, member: PublicKey):
+    send(
+        caller,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=allowlist, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=member, is_signer=False, is_writable=False),
+        ],
+    )
+
+def get_members(allowlist: PublicKey) -> list:
+    data = base64.b64decode(client.get_account_info(allowlist)['result']['value']['data'][0])
+    # is_initialized, admin and bump_seed come before the u32 length of the members
+    offset = 1 + 32 + 1
+    (count,) = struct.unpack_from("<I", data, offset)
+    offset += 4
+    return [PublicKey(data[offset + i * 32:offset + (i + 1) * 32]) for i in range(count)]
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+
+    allowlist = get_allowlist_address(payer.public_key)
+    if client.get_account_info(allowlist)['result']['value'] is None:
+        init_allowlist(payer)
+
+    members = [Keypair().public_key for _ in range(5)]
+    add_members(payer, [payer.public_key] + members)
+    remove_members(payer, members[:2])
+    print(f"Members: {[str(member) for member in get_members(allowlist)]}")
+
+    check_member(payer, allowlist, payer.public_key)
+    print(f"{payer.public_key} is a member")
+    try:
+        check_member(payer, allowlist, members[0])
+    except Exception as e:
+        print(f"Checking a removed member fails as expected: {e}")
+
+    print(f"\nTo gate the greeting program, build it with ALLOWLIST_ADDRESS={allowlist} ALLOWLIST_PROGRAM_ID={program_id}")
@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum AllowlistError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Allowlist Address")]
+    InvalidAllowlistAddress,
+    #[error("Not Admin")]
+    NotAdmin,
+    #[error("Allowlist Full")]
+    AllowlistFull,
+    #[error("Not A Member")]
+    NotAMember,
+}
+
+impl From<AllowlistError> for ProgramError {
+    fn from(e: AllowlistError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,98 @@
+use std::convert::TryInto;
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::AllowlistError::InvalidInstruction;
+
+pub enum AllowlistInstruction {
+
+    // Creates an empty allowlist in a PDA derived from ["allowlist", admin].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The admin
+    // 1. `[writable]` The allowlist PDA
+    // 2. `[]` The system program
+    InitAllowlist,
+
+    // Adds a batch of members, members which are already on the list are skipped.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The admin, pays for the bigger account
+    // 1. `[writable]` The allowlist PDA
+    // 2. `[]` The system program
+    AddMembers {
+        members: Vec<Pubkey>,
+    },
+
+    // Removes a batch of members, pubkeys which are not on the list are skipped.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The admin, receives the rent of the smaller account
+    // 1. `[writable]` The allowlist PDA
+    // 2. `[]` The system program
+    RemoveMembers {
+        members: Vec<Pubkey>,
+    },
+
+    // Fails with NotAMember unless the member is on the list. Meant to be called by other programs through a CPI.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[]` The allowlist PDA
+    // 1. `[]` The member to check
+    CheckMember,
+}
+
+impl AllowlistInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitAllowlist,
+            1 => Self::AddMembers {
+                members: Self::unpack_pubkeys(rest)?,
+            },
+            2 => Self::RemoveMembers {
+                members: Self::unpack_pubkeys(rest)?,
+            },
+            3 => Self::CheckMember,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    // the pubkeys are simply concatenated, the number of members follows from the length
+    fn unpack_pubkeys(input: &[u8]) -> Result<Vec<Pubkey>, ProgramError> {
+        if input.is_empty() || !input.len().is_multiple_of(32) {
+            return Err(InvalidInstruction.into());
+        }
+
+        let members = input
+            .chunks_exact(32)
+            .map(|chunk| chunk.try_into().map(Pubkey::new_from_array))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| InvalidInstruction)?;
+        Ok(members)
+    }
+}
+
+// Builds a CheckMember instruction, for programs which want to consult an allowlist through a CPI
+pub fn check_member(allowlist_program_id: &Pubkey, allowlist: &Pubkey, member: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *allowlist_program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*allowlist, false),
+            AccountMeta::new_readonly(*member, false),
+        ],
+        data: vec![3],
+    }
+}
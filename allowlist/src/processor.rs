@@ -0,0 +1,212 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::BorshSerialize;
+
+use crate::{
+    instruction::AllowlistInstruction,
+    error::AllowlistError,
+    state::{Allowlist, MAX_MEMBERS},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = AllowlistInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            AllowlistInstruction::InitAllowlist => {
+                msg!("Instruction: InitAllowlist");
+                Self::process_init_allowlist(accounts, program_id)
+            },
+            AllowlistInstruction::AddMembers { members } => {
+                msg!("Instruction: AddMembers");
+                Self::process_add_members(accounts, members, program_id)
+            },
+            AllowlistInstruction::RemoveMembers { members } => {
+                msg!("Instruction: RemoveMembers");
+                Self::process_remove_members(accounts, members, program_id)
+            },
+            AllowlistInstruction::CheckMember => {
+                msg!("Instruction: CheckMember");
+                Self::process_check_member(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_init_allowlist(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+
+        if !admin.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let allowlist_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (allowlist_pda, bump_seed) = Pubkey::find_program_address(&[b"allowlist", admin.key.as_ref()], program_id);
+        if allowlist_pda != *allowlist_account.key {
+            return Err(AllowlistError::InvalidAllowlistAddress.into());
+        }
+
+        if !allowlist_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let allowlist_info = Allowlist {
+            is_initialized: true,
+            admin: *admin.key,
+            bump_seed,
+            members: Vec::new(),
+        };
+        let data = allowlist_info.try_to_vec()?;
+
+        msg!("Creating allowlist account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                admin.key,
+                allowlist_account.key,
+                Rent::get()?.minimum_balance(data.len()),
+                data.len() as u64,
+                program_id,
+            ),
+            &[
+                admin.clone(),
+                allowlist_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"allowlist", admin.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        allowlist_account.data.borrow_mut().copy_from_slice(&data);
+
+        Ok(())
+    }
+
+    fn process_add_members(
+        accounts: &[AccountInfo],
+        members: Vec<Pubkey>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let allowlist_account = next_account_info(account_info_iter)?;
+        let mut allowlist_info = Self::unpack_as_admin(admin, allowlist_account, program_id)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        for member in members {
+            if !allowlist_info.contains(&member) {
+                allowlist_info.members.push(member);
+            }
+        }
+
+        if allowlist_info.members.len() > MAX_MEMBERS {
+            return Err(AllowlistError::AllowlistFull.into());
+        }
+
+        msg!("Allowlist has {} members", allowlist_info.members.len());
+        Self::store_allowlist(&allowlist_info, admin, allowlist_account, system_program)
+    }
+
+    fn process_remove_members(
+        accounts: &[AccountInfo],
+        members: Vec<Pubkey>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+        let allowlist_account = next_account_info(account_info_iter)?;
+        let mut allowlist_info = Self::unpack_as_admin(admin, allowlist_account, program_id)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        allowlist_info.members.retain(|member| !members.contains(member));
+
+        msg!("Allowlist has {} members", allowlist_info.members.len());
+        Self::store_allowlist(&allowlist_info, admin, allowlist_account, system_program)
+    }
+
+    fn process_check_member(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let allowlist_account = next_account_info(account_info_iter)?;
+        let member = next_account_info(account_info_iter)?;
+
+        let allowlist_info = Allowlist::from_account_info(allowlist_account, program_id)?;
+        if !allowlist_info.contains(member.key) {
+            return Err(AllowlistError::NotAMember.into());
+        }
+
+        msg!("{} is a member", member.key);
+        Ok(())
+    }
+
+    fn unpack_as_admin(
+        admin: &AccountInfo,
+        allowlist_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<Allowlist, ProgramError> {
+        if !admin.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let allowlist_info = Allowlist::from_account_info(allowlist_account, program_id)?;
+        if allowlist_info.admin != *admin.key {
+            return Err(AllowlistError::NotAdmin.into());
+        }
+
+        Ok(allowlist_info)
+    }
+
+    // Writes the allowlist back and resizes the account to the serialized length first. A bigger account needs
+    // more lamports to stay rent exempt, which the admin pays through the system program. A smaller one
+    // gives the surplus back to the admin.
+    fn store_allowlist<'a>(
+        allowlist_info: &Allowlist,
+        admin: &AccountInfo<'a>,
+        allowlist_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let data = allowlist_info.try_to_vec()?;
+
+        let required_lamports = Rent::get()?.minimum_balance(data.len());
+        let current_lamports = allowlist_account.lamports();
+
+        if required_lamports > current_lamports {
+            invoke(
+                &system_instruction::transfer(admin.key, allowlist_account.key, required_lamports - current_lamports),
+                &[
+                    admin.clone(),
+                    allowlist_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        } else if current_lamports > required_lamports {
+            let surplus = current_lamports - required_lamports;
+            **allowlist_account.lamports.borrow_mut() = required_lamports;
+            **admin.lamports.borrow_mut() = admin.lamports()
+                .checked_add(surplus)
+                .ok_or(ProgramError::InvalidAccountData)?;
+        }
+
+        msg!("Resizing allowlist from {} to {} bytes...", allowlist_account.data_len(), data.len());
+        allowlist_account.realloc(data.len(), false)?;
+        allowlist_account.data.borrow_mut().copy_from_slice(&data);
+
+        Ok(())
+    }
+}
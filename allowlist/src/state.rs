@@ -0,0 +1,34 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+// Most members a single list can hold
+pub const MAX_MEMBERS: usize = 200;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Allowlist {
+    pub is_initialized: bool,
+    pub admin: Pubkey,
+    pub bump_seed: u8,
+    pub members: Vec<Pubkey>,
+}
+
+impl Allowlist {
+    pub fn contains(&self, member: &Pubkey) -> bool {
+        self.members.contains(member)
+    }
+
+    // Reads an allowlist from an account which has been passed to another program. Anybody can create an
+    // account with the same layout, so the owner has to be checked against the allowlist program id first.
+    pub fn from_account_info(account: &AccountInfo, allowlist_program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if account.owner != allowlist_program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let allowlist = Self::try_from_slice(&account.data.borrow())?;
+        if !allowlist.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(allowlist)
+    }
+}
@@ -0,0 +1,287 @@
+import yaml
+import sys
+import json
+import base64
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'amm.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'amm-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_program(payer: Keypair, decimals: int) -> Token:
+    token = Token.create_mint(
+        conn=client,
+        payer=payer,
+        mint_authority=payer.public_key,
+        decimals=decimals,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    print(f"Address of token program: {token.pubkey}")
+    return token
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def mint_token(token: Token, minter: Keypair, account: PublicKey, amount: int):
+    print(f"Minting {amount} tokens to {account}")
+    token.mint_to(
+        dest=account,
+        mint_authority=minter,
+        amount=amount,
+        opts=TxOpts(skip_confirmation=False)
+    )
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+def get_pool_address(mint_a: PublicKey, mint_b: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("amm", encoding="utf8"), bytes(mint_a), bytes(mint_b)], program_id)[0]
+
+def u64(value: int) -> bytes:
+    return value.to_bytes(8, byteorder='little')
+
+def init_pool(creator: Keypair, token_a: Token, token_b: Token, fee_bps: int) -> dict:
+    pool_address = get_pool_address(token_a.pubkey, token_b.pubkey)
+    vault_a = create_token_account(token_a, pool_address)
+    vault_b = create_token_account(token_b, pool_address)
+    # LP tokens can only be minted by the pool
+    lp_token = Token.create_mint(
+        conn=client,
+        payer=creator,
+        mint_authority=pool_address,
+        decimals=0,
+        program_id=TOKEN_PROGRAM_ID
+    )
+
+    print(f"\nCreating pool {pool_address} with a fee of {fee_bps} bps")
+    init_ix = TransactionInstruction(
+        data=bytes(1) + fee_bps.to_bytes(2, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=creator.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=pool_address, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault_a, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=vault_b, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=lp_token.pubkey, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(init_ix), creator)
+    client.confirm_transaction(tx["result"])
+    return {'pool': pool_address, 'vault_a': vault_a, 'vault_b': vault_b, 'lp_token': lp_token}
+
+def liquidity_keys(provider: Keypair, pool: dict, account_a: PublicKey, account_b: PublicKey, account_lp: PublicKey) -> list:
+    return [
+        AccountMeta(pubkey=provider.public_key, is_signer=True, is_writable=False),
+        AccountMeta(pubkey=pool['pool'], is_signer=False, is_writable=False),
+        AccountMeta(pubkey=pool['vault_a'], is_signer=False, is_writable=True),
+        AccountMeta(pubkey=pool['vault_b'], is_signer=False, is_writable=True),
+        AccountMeta(pubkey=pool['lp_token'].pubkey, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=account_a, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=account_b, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=account_lp, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+    ]
+
+def add_liquidity(provider: Keypair, pool: dict, account_a: PublicKey, account_b: PublicKey, account_lp: PublicKey, max_a: int, max_b: int, min_lp: int):
+    print(f"\nAdding liquidity, at most {max_a} A and {max_b} B")
+    add_ix = TransactionInstruction(
+        data=(1).to_bytes(1, byteorder='little') + u64(max_a) + u64(max_b) + u64(min_lp),
+        keys=liquidity_keys(provider, pool, account_a, account_b, account_lp),
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(add_ix), provider)
+    client.confirm_transaction(tx["result"])
+
+def remove_liquidity(provider: Keypair, pool: dict, account_a: PublicKey, account_b: PublicKey, account_lp: PublicKey, lp_amount: int, min_a: int, min_b: int):
+    print(f"\nRemoving liquidity, burning {lp_amount} LP tokens")
+    remove_ix = TransactionInstruction(
+        data=(2).to_bytes(1, byteorder='little') + u64(lp_amount) + u64(min_a) + u64(min_b),
+        keys=liquidity_keys(provider, pool, account_a, account_b, account_lp),
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(remove_ix), provider)
+    client.confirm_transaction(tx["result"])
+
+def quote(amount_in: int, reserve_in: int, reserve_out: int, fee_bps: int) -> int:
+    # same formula as swap_output in curve.rs
+    in_after_fee = amount_in * (10_000 - fee_bps) // 10_000
+    return in_after_fee * reserve_out // (reserve_in + in_after_fee)
+
+def swap(trader: Keypair, pool: dict, vault_in: PublicKey, vault_out: PublicKey, source: PublicKey, destination: PublicKey, amount_in: int, min_out: int):
+    print(f"\nSwapping {amount_in} tokens, expecting at least {min_out}")
+    swap_ix = TransactionInstruction(
+        data=(3).to_bytes(1, byteorder='little') + u64(amount_in) + u64(min_out),
+        keys=[
+            AccountMeta(pubkey=trader.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=pool['pool'], is_signer=False, is_writable=False),
+            AccountMeta(pubkey=vault_in, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault_out, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=source, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(swap_ix), trader)
+    client.confirm_transaction(tx["result"])
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    fee_bps = 30
+
+    token_a = create_token_program(payer, decimals=0)
+    token_b = create_token_program(payer, decimals=0)
+    pool = init_pool(payer, token_a, token_b, fee_bps)
+
+    provider_a = create_token_account(token_a, payer.public_key)
+    provider_b = create_token_account(token_b, payer.public_key)
+    provider_lp = create_token_account(pool['lp_token'], payer.public_key)
+    mint_token(token_a, payer, provider_a, 100_000)
+    mint_token(token_b, payer, provider_b, 400_000)
+    # the first deposit sets the price: 1 A = 4 B
+    add_liquidity(payer, pool, provider_a, provider_b, provider_lp, 10_000, 40_000, 0)
+    print(f"LP tokens: {token_balance(pool['lp_token'], provider_lp)}")
+
+    trader = setup_user_keypair()
+    trader_a = create_token_account(token_a, trader.public_key)
+    trader_b = create_token_account(token_b, trader.public_key)
+    mint_token(token_a, payer, trader_a, 1_000)
+    expected = quote(1_000, token_balance(token_a, pool['vault_a']), token_balance(token_b, pool['vault_b']), fee_bps)
+    # accept 1% less than the quote, in case someone else trades first
+    swap(trader, pool, pool['vault_a'], pool['vault_b'], trader_a, trader_b, 1_000, expected * 99 // 100)
+    print(f"Trader received {token_balance(token_b, trader_b)} B for 1000 A")
+
+    remove_liquidity(payer, pool, provider_a, provider_b, provider_lp, token_balance(pool['lp_token'], provider_lp), 0, 0)
+    print(f"Provider holds {token_balance(token_a, provider_a)} A and {token_balance(token_b, provider_b)} B")
@@ -0,0 +1,76 @@
+// Constant product math. The pool keeps reserve_a * reserve_b = k constant on swaps (minus fees,
+// which stay in the pool and make k grow). All intermediate values are u128 so products of two
+// u64 reserves can't overflow.
+
+use crate::error::AmmError;
+
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+// Tokens a trader receives for amount_in. The fee is taken from the input before it hits the curve:
+// (reserve_in + in_after_fee) * (reserve_out - out) = reserve_in * reserve_out
+pub fn swap_output(amount_in: u64, reserve_in: u64, reserve_out: u64, fee_bps: u16) -> Result<u64, AmmError> {
+    let in_after_fee = amount_in as u128 * (BPS_DENOMINATOR - fee_bps as u128) / BPS_DENOMINATOR;
+    let numerator = in_after_fee
+        .checked_mul(reserve_out as u128)
+        .ok_or(AmmError::MathOverflow)?;
+    let denominator = (reserve_in as u128)
+        .checked_add(in_after_fee)
+        .ok_or(AmmError::MathOverflow)?;
+    if denominator == 0 {
+        return Err(AmmError::ZeroAmount);
+    }
+    u64::try_from(numerator / denominator).map_err(|_| AmmError::MathOverflow)
+}
+
+// LP tokens minted for the first deposit, the geometric mean of both amounts
+pub fn initial_liquidity(amount_a: u64, amount_b: u64) -> u64 {
+    // the square root of a product of two u64 always fits into a u64
+    sqrt(amount_a as u128 * amount_b as u128) as u64
+}
+
+// LP tokens minted for a deposit into a pool that already has liquidity. The share of the
+// side with the smaller ratio wins, anything above that ratio isn't taken from the depositor.
+pub fn liquidity_for_deposit(
+    max_a: u64,
+    max_b: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+) -> Result<u64, AmmError> {
+    if reserve_a == 0 || reserve_b == 0 {
+        return Err(AmmError::ZeroAmount);
+    }
+    let lp_a = max_a as u128 * lp_supply as u128 / reserve_a as u128;
+    let lp_b = max_b as u128 * lp_supply as u128 / reserve_b as u128;
+    u64::try_from(lp_a.min(lp_b)).map_err(|_| AmmError::MathOverflow)
+}
+
+// Tokens needed from one side to mint lp_amount, rounded up so the pool never loses on rounding
+pub fn deposit_amount(lp_amount: u64, reserve: u64, lp_supply: u64) -> Result<u64, AmmError> {
+    let numerator = lp_amount as u128 * reserve as u128;
+    let amount = numerator.div_ceil(lp_supply as u128);
+    u64::try_from(amount).map_err(|_| AmmError::MathOverflow)
+}
+
+// Tokens paid out from one side for burning lp_amount, rounded down
+pub fn withdraw_amount(lp_amount: u64, reserve: u64, lp_supply: u64) -> Result<u64, AmmError> {
+    if lp_supply == 0 {
+        return Err(AmmError::ZeroAmount);
+    }
+    let amount = lp_amount as u128 * reserve as u128 / lp_supply as u128;
+    u64::try_from(amount).map_err(|_| AmmError::MathOverflow)
+}
+
+// Integer square root (Newton's method), rounded down
+fn sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
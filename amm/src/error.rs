@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum AmmError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Pool Address")]
+    InvalidPoolAddress,
+    #[error("Invalid Vault")]
+    InvalidVault,
+    #[error("Invalid LP Mint")]
+    InvalidLpMint,
+    #[error("Invalid Fee")]
+    InvalidFee,
+    #[error("Zero Amount")]
+    ZeroAmount,
+    #[error("Slippage Exceeded")]
+    SlippageExceeded,
+    #[error("Math Overflow")]
+    MathOverflow,
+}
+
+impl From<AmmError> for ProgramError {
+    fn from(e: AmmError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,126 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::AmmError::InvalidInstruction;
+
+pub enum AmmInstruction {
+
+    // Creates a pool for two tokens in a PDA derived from ["amm", mint A, mint B]. Both vaults and the LP mint
+    // have to be created prior to this instruction. The vaults must be empty token accounts owned by the pool PDA,
+    // the LP mint must have the pool PDA as mint authority and no supply.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The creator of the pool, pays for the pool account
+    // 1. `[writable]` The pool PDA
+    // 2. `[]` The vault of token A
+    // 3. `[]` The vault of token B
+    // 4. `[]` The LP mint
+    // 5. `[]` The system program
+    InitPool {
+        fee_bps: u16,
+    },
+
+    // Deposits both tokens at the current ratio of the pool and mints LP tokens for them.
+    // The first deposit sets the initial price.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The liquidity provider
+    // 1. `[]` The pool PDA
+    // 2. `[writable]` The vault of token A
+    // 3. `[writable]` The vault of token B
+    // 4. `[writable]` The LP mint
+    // 5. `[writable]` The provider's token account for token A
+    // 6. `[writable]` The provider's token account for token B
+    // 7. `[writable]` The provider's token account for the LP token
+    // 8. `[]` The token program
+    AddLiquidity {
+        // most the provider is willing to deposit of each token
+        max_amount_a: u64,
+        max_amount_b: u64,
+        // fewest LP tokens the provider accepts
+        min_lp_amount: u64,
+    },
+
+    // Burns LP tokens and pays out the matching share of both reserves
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The liquidity provider
+    // 1. `[]` The pool PDA
+    // 2. `[writable]` The vault of token A
+    // 3. `[writable]` The vault of token B
+    // 4. `[writable]` The LP mint
+    // 5. `[writable]` The provider's token account for token A
+    // 6. `[writable]` The provider's token account for token B
+    // 7. `[writable]` The provider's token account for the LP token
+    // 8. `[]` The token program
+    RemoveLiquidity {
+        lp_amount: u64,
+        // fewest tokens of each side the provider accepts
+        min_amount_a: u64,
+        min_amount_b: u64,
+    },
+
+    // Swaps one token for the other. The direction is given by the order of the vaults.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The trader
+    // 1. `[]` The pool PDA
+    // 2. `[writable]` The vault of the token being sold
+    // 3. `[writable]` The vault of the token being bought
+    // 4. `[writable]` The trader's token account to take the sold tokens from
+    // 5. `[writable]` The trader's token account to receive the bought tokens
+    // 6. `[]` The token program
+    Swap {
+        amount_in: u64,
+        // fewest tokens the trader accepts, protects against the price moving before the transaction lands
+        min_amount_out: u64,
+    },
+}
+
+impl AmmInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitPool {
+                fee_bps: rest
+                    .get(..2)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u16::from_le_bytes)
+                    .ok_or(InvalidInstruction)?,
+            },
+            1 => Self::AddLiquidity {
+                max_amount_a: Self::unpack_amount(rest, 0)?,
+                max_amount_b: Self::unpack_amount(rest, 8)?,
+                min_lp_amount: Self::unpack_amount(rest, 16)?,
+            },
+            2 => Self::RemoveLiquidity {
+                lp_amount: Self::unpack_amount(rest, 0)?,
+                min_amount_a: Self::unpack_amount(rest, 8)?,
+                min_amount_b: Self::unpack_amount(rest, 16)?,
+            },
+            3 => Self::Swap {
+                amount_in: Self::unpack_amount(rest, 0)?,
+                min_amount_out: Self::unpack_amount(rest, 8)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_amount(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let amount = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(amount)
+    }
+}
@@ -0,0 +1,7 @@
+#[cfg(not(feature = "no-entrypoint"))]
+pub mod entrypoint;
+pub mod instruction;
+pub mod error;
+pub mod processor;
+pub mod curve;
+pub mod state;
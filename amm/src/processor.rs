@@ -0,0 +1,434 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::{Account as TokenAccount, Mint};
+
+use crate::{
+    instruction::AmmInstruction,
+    error::AmmError,
+    state::Pool,
+    curve,
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = AmmInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            AmmInstruction::InitPool { fee_bps } => {
+                msg!("Instruction: InitPool");
+                Self::process_init_pool(accounts, fee_bps, program_id)
+            },
+            AmmInstruction::AddLiquidity { max_amount_a, max_amount_b, min_lp_amount } => {
+                msg!("Instruction: AddLiquidity");
+                Self::process_add_liquidity(accounts, max_amount_a, max_amount_b, min_lp_amount, program_id)
+            },
+            AmmInstruction::RemoveLiquidity { lp_amount, min_amount_a, min_amount_b } => {
+                msg!("Instruction: RemoveLiquidity");
+                Self::process_remove_liquidity(accounts, lp_amount, min_amount_a, min_amount_b, program_id)
+            },
+            AmmInstruction::Swap { amount_in, min_amount_out } => {
+                msg!("Instruction: Swap");
+                Self::process_swap(accounts, amount_in, min_amount_out, program_id)
+            }
+        }
+    }
+
+    fn process_init_pool(
+        accounts: &[AccountInfo],
+        fee_bps: u16,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if fee_bps as u128 >= curve::BPS_DENOMINATOR {
+            return Err(AmmError::InvalidFee.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let vault_a = next_account_info(account_info_iter)?;
+        let vault_b = next_account_info(account_info_iter)?;
+        let lp_mint = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let vault_a_info = Self::unpack_token_account(vault_a)?;
+        let vault_b_info = Self::unpack_token_account(vault_b)?;
+
+        let (pool_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"amm", vault_a_info.mint.as_ref(), vault_b_info.mint.as_ref()],
+            program_id,
+        );
+        if pool_pda != *pool_account.key {
+            return Err(AmmError::InvalidPoolAddress.into());
+        }
+
+        if !pool_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the pool must own the vaults and they must start empty, otherwise the first price would be off
+        if vault_a_info.mint == vault_b_info.mint
+            || vault_a_info.owner != pool_pda
+            || vault_b_info.owner != pool_pda
+            || vault_a_info.amount != 0
+            || vault_b_info.amount != 0
+        {
+            return Err(AmmError::InvalidVault.into());
+        }
+
+        let lp_mint_info = Self::unpack_mint(lp_mint)?;
+        if lp_mint_info.mint_authority != Some(pool_pda).into() || lp_mint_info.supply != 0 {
+            return Err(AmmError::InvalidLpMint.into());
+        }
+
+        msg!("Creating pool account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                creator.key,
+                pool_account.key,
+                Rent::get()?.minimum_balance(Pool::LEN),
+                Pool::LEN as u64,
+                program_id,
+            ),
+            &[
+                creator.clone(),
+                pool_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"amm", vault_a_info.mint.as_ref(), vault_b_info.mint.as_ref(), &[bump_seed]]],
+        )?;
+
+        let pool_info = Pool {
+            is_initialized: true,
+            mint_a: vault_a_info.mint,
+            mint_b: vault_b_info.mint,
+            vault_a: *vault_a.key,
+            vault_b: *vault_b.key,
+            lp_mint: *lp_mint.key,
+            fee_bps,
+            bump_seed,
+        };
+        pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_add_liquidity(
+        accounts: &[AccountInfo],
+        max_amount_a: u64,
+        max_amount_b: u64,
+        min_lp_amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let provider = next_account_info(account_info_iter)?;
+
+        if !provider.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let pool_info = Self::unpack_pool(pool_account, program_id)?;
+
+        let vault_a = next_account_info(account_info_iter)?;
+        let vault_b = next_account_info(account_info_iter)?;
+        let lp_mint = next_account_info(account_info_iter)?;
+        Self::check_pool_accounts(&pool_info, vault_a, vault_b, lp_mint)?;
+
+        let provider_a = next_account_info(account_info_iter)?;
+        let provider_b = next_account_info(account_info_iter)?;
+        let provider_lp = next_account_info(account_info_iter)?;
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let reserve_a = Self::unpack_token_account(vault_a)?.amount;
+        let reserve_b = Self::unpack_token_account(vault_b)?.amount;
+        let lp_supply = Self::unpack_mint(lp_mint)?.supply;
+
+        let (amount_a, amount_b, lp_amount) = if lp_supply == 0 {
+            (max_amount_a, max_amount_b, curve::initial_liquidity(max_amount_a, max_amount_b))
+        } else {
+            let lp_amount = curve::liquidity_for_deposit(max_amount_a, max_amount_b, reserve_a, reserve_b, lp_supply)?;
+            (
+                curve::deposit_amount(lp_amount, reserve_a, lp_supply)?,
+                curve::deposit_amount(lp_amount, reserve_b, lp_supply)?,
+                lp_amount,
+            )
+        };
+
+        if lp_amount == 0 {
+            return Err(AmmError::ZeroAmount.into());
+        }
+        if lp_amount < min_lp_amount || amount_a > max_amount_a || amount_b > max_amount_b {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        msg!("Depositing {} A and {} B for {} LP tokens", amount_a, amount_b, lp_amount);
+        Self::transfer_to_vault(token_program, provider_a, vault_a, provider, amount_a)?;
+        Self::transfer_to_vault(token_program, provider_b, vault_b, provider, amount_b)?;
+
+        msg!("Calling the token program to mint LP tokens...");
+        invoke_signed(
+            &spl_token::instruction::mint_to(
+                token_program.key,
+                lp_mint.key,
+                provider_lp.key,
+                pool_account.key,
+                &[pool_account.key],
+                lp_amount,
+            )?,
+            &[
+                lp_mint.clone(),
+                provider_lp.clone(),
+                pool_account.clone(),
+                token_program.clone(),
+            ],
+            &[&[b"amm", pool_info.mint_a.as_ref(), pool_info.mint_b.as_ref(), &[pool_info.bump_seed]]],
+        )?;
+
+        Ok(())
+    }
+
+    fn process_remove_liquidity(
+        accounts: &[AccountInfo],
+        lp_amount: u64,
+        min_amount_a: u64,
+        min_amount_b: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if lp_amount == 0 {
+            return Err(AmmError::ZeroAmount.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let provider = next_account_info(account_info_iter)?;
+
+        if !provider.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let pool_info = Self::unpack_pool(pool_account, program_id)?;
+
+        let vault_a = next_account_info(account_info_iter)?;
+        let vault_b = next_account_info(account_info_iter)?;
+        let lp_mint = next_account_info(account_info_iter)?;
+        Self::check_pool_accounts(&pool_info, vault_a, vault_b, lp_mint)?;
+
+        let provider_a = next_account_info(account_info_iter)?;
+        let provider_b = next_account_info(account_info_iter)?;
+        let provider_lp = next_account_info(account_info_iter)?;
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let reserve_a = Self::unpack_token_account(vault_a)?.amount;
+        let reserve_b = Self::unpack_token_account(vault_b)?.amount;
+        let lp_supply = Self::unpack_mint(lp_mint)?.supply;
+
+        let amount_a = curve::withdraw_amount(lp_amount, reserve_a, lp_supply)?;
+        let amount_b = curve::withdraw_amount(lp_amount, reserve_b, lp_supply)?;
+        if amount_a < min_amount_a || amount_b < min_amount_b {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        // the provider owns the LP tokens and signed the transaction, so they can burn them
+        msg!("Calling the token program to burn {} LP tokens...", lp_amount);
+        invoke(
+            &spl_token::instruction::burn(
+                token_program.key,
+                provider_lp.key,
+                lp_mint.key,
+                provider.key,
+                &[provider.key],
+                lp_amount,
+            )?,
+            &[
+                provider_lp.clone(),
+                lp_mint.clone(),
+                provider.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        msg!("Withdrawing {} A and {} B", amount_a, amount_b);
+        Self::transfer_from_vault(token_program, &pool_info, pool_account, vault_a, provider_a, amount_a)?;
+        Self::transfer_from_vault(token_program, &pool_info, pool_account, vault_b, provider_b, amount_b)?;
+
+        Ok(())
+    }
+
+    fn process_swap(
+        accounts: &[AccountInfo],
+        amount_in: u64,
+        min_amount_out: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if amount_in == 0 {
+            return Err(AmmError::ZeroAmount.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let trader = next_account_info(account_info_iter)?;
+
+        if !trader.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let pool_info = Self::unpack_pool(pool_account, program_id)?;
+
+        let vault_in = next_account_info(account_info_iter)?;
+        let vault_out = next_account_info(account_info_iter)?;
+        let is_a_to_b = *vault_in.key == pool_info.vault_a && *vault_out.key == pool_info.vault_b;
+        let is_b_to_a = *vault_in.key == pool_info.vault_b && *vault_out.key == pool_info.vault_a;
+        if !is_a_to_b && !is_b_to_a {
+            return Err(AmmError::InvalidVault.into());
+        }
+
+        let trader_in = next_account_info(account_info_iter)?;
+        let trader_out = next_account_info(account_info_iter)?;
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let reserve_in = Self::unpack_token_account(vault_in)?.amount;
+        let reserve_out = Self::unpack_token_account(vault_out)?.amount;
+
+        let amount_out = curve::swap_output(amount_in, reserve_in, reserve_out, pool_info.fee_bps)?;
+        if amount_out == 0 {
+            return Err(AmmError::ZeroAmount.into());
+        }
+        if amount_out < min_amount_out {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        msg!("Swapping {} for {}", amount_in, amount_out);
+        Self::transfer_to_vault(token_program, trader_in, vault_in, trader, amount_in)?;
+        Self::transfer_from_vault(token_program, &pool_info, pool_account, vault_out, trader_out, amount_out)?;
+
+        Ok(())
+    }
+
+    fn unpack_pool(pool_account: &AccountInfo, program_id: &Pubkey) -> Result<Pool, ProgramError> {
+        if pool_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let pool_info = Pool::try_from_slice(&pool_account.data.borrow())?;
+        if !pool_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(pool_info)
+    }
+
+    fn check_pool_accounts(
+        pool_info: &Pool,
+        vault_a: &AccountInfo,
+        vault_b: &AccountInfo,
+        lp_mint: &AccountInfo,
+    ) -> ProgramResult {
+        if pool_info.vault_a != *vault_a.key || pool_info.vault_b != *vault_b.key {
+            return Err(AmmError::InvalidVault.into());
+        }
+        if pool_info.lp_mint != *lp_mint.key {
+            return Err(AmmError::InvalidLpMint.into());
+        }
+        Ok(())
+    }
+
+    fn unpack_token_account(account: &AccountInfo) -> Result<TokenAccount, ProgramError> {
+        if *account.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        TokenAccount::unpack(&account.try_borrow_data()?)
+    }
+
+    fn unpack_mint(account: &AccountInfo) -> Result<Mint, ProgramError> {
+        if *account.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Mint::unpack(&account.try_borrow_data()?)
+    }
+
+    fn transfer_to_vault<'a>(
+        token_program: &AccountInfo<'a>,
+        source: &AccountInfo<'a>,
+        vault: &AccountInfo<'a>,
+        owner: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        msg!("Calling the token program to transfer tokens into the vault...");
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                source.key,
+                vault.key,
+                owner.key,
+                &[owner.key],
+                amount,
+            )?,
+            &[
+                source.clone(),
+                vault.clone(),
+                owner.clone(),
+                token_program.clone(),
+            ],
+        )
+    }
+
+    // The pool PDA owns the vaults, so the program signs the transfer with the pool's seeds
+    fn transfer_from_vault<'a>(
+        token_program: &AccountInfo<'a>,
+        pool_info: &Pool,
+        pool_account: &AccountInfo<'a>,
+        vault: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        msg!("Calling the token program to transfer tokens out of the vault...");
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                vault.key,
+                destination.key,
+                pool_account.key,
+                &[pool_account.key],
+                amount,
+            )?,
+            &[
+                vault.clone(),
+                destination.clone(),
+                pool_account.clone(),
+                token_program.clone(),
+            ],
+            &[&[b"amm", pool_info.mint_a.as_ref(), pool_info.mint_b.as_ref(), &[pool_info.bump_seed]]],
+        )
+    }
+}
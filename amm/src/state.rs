@@ -0,0 +1,21 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Pool {
+    pub is_initialized: bool,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    // token accounts owned by the pool PDA, their balances are the reserves
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    // mint of the liquidity provider shares, its mint authority is the pool PDA
+    pub lp_mint: Pubkey,
+    // fee taken from the input of every swap, in basis points (1/100 of a percent)
+    pub fee_bps: u16,
+    pub bump_seed: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 1 + 32 * 5 + 2 + 1;
+}
@@ -0,0 +1,216 @@
+import yaml
+import sys
+import json
+import time
+import base64
+import hashlib
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'attest.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'attest-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def claim_hash(claim: str) -> bytes:
+    # only the hash of the claim goes on-chain, the issuer and the subject know the claim itself
+    return hashlib.sha256(bytes(claim, encoding="utf8")).digest()
+
+def get_attestation_address(issuer: PublicKey, subject: PublicKey, claim: str) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("attestation", encoding="utf8"), bytes(issuer), bytes(subject), claim_hash(claim)],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def attest(issuer: Keypair, subject: PublicKey, claim: str, expires_at: int) -> PublicKey:
+    attestation = get_attestation_address(issuer.public_key, subject, claim)
+    print(f"\n{issuer.public_key} attests '{claim}' for {subject}")
+    send(
+        issuer,
+        (0).to_bytes(1, byteorder='little') + claim_hash(claim) + expires_at.to_bytes(8, byteorder='little', signed=True),
+        [
+            AccountMeta(pubkey=issuer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=subject, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=attestation, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return attestation
+
+def revoke(issuer: Keypair, attestation: PublicKey):
+    print(f"\nRevoking attestation {attestation}")
+    send(
+        issuer,
+        (1).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=issuer.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=attestation, is_signer=False, is_writable=True),
+        ],
+    )
+
+def verify(signer: Keypair, issuer: PublicKey, subject: PublicKey, claim: str) -> bool:
+    # Verify doesn't change anything, any signer can pay for it. Another program would do the
+    # same through a CPI, or read the account itself after checking its owner
+    try:
+        send(
+            signer,
+            (2).to_bytes(1, byteorder='little') + claim_hash(claim),
+            [
+                AccountMeta(pubkey=get_attestation_address(issuer, subject, claim), is_signer=False, is_writable=False),
+                AccountMeta(pubkey=issuer, is_signer=False, is_writable=False),
+                AccountMeta(pubkey=subject, is_signer=False, is_writable=False),
+            ],
+        )
+        print(f"'{claim}' holds for {subject}")
+        return True
+    except Exception as e:
+        print(f"'{claim}' doesn't hold for {subject}: {e}")
+        return False
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    issuer = setup_user_keypair()
+    subject = Keypair().public_key
+
+    kyc = attest(issuer, subject, "kyc-passed", 0)
+    attest(issuer, subject, "member-2022", int(time.time()) + 10)
+    verify(payer, issuer.public_key, subject, "kyc-passed")
+    verify(payer, issuer.public_key, subject, "member-2022")
+    verify(payer, issuer.public_key, subject, "admin")
+
+    print("\nWaiting for the membership to expire...")
+    time.sleep(15)
+    verify(payer, issuer.public_key, subject, "member-2022")
+
+    revoke(issuer, kyc)
+    verify(payer, issuer.public_key, subject, "kyc-passed")
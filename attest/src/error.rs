@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum AttestError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Attestation Address")]
+    InvalidAttestationAddress,
+    #[error("Expiry In The Past")]
+    ExpiryInPast,
+    #[error("Not The Issuer")]
+    NotTheIssuer,
+    #[error("Attestation Mismatch")]
+    AttestationMismatch,
+    #[error("Attestation Revoked")]
+    AttestationRevoked,
+    #[error("Attestation Expired")]
+    AttestationExpired,
+}
+
+impl From<AttestError> for ProgramError {
+    fn from(e: AttestError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,101 @@
+use std::convert::TryInto;
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::AttestError::InvalidInstruction;
+
+pub enum AttestInstruction {
+
+    // The issuer attests a claim about a subject. The attestation is stored in a PDA derived from
+    // ["attestation", issuer, subject, claim_hash], so there is exactly one per issuer, subject and claim.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The issuer, pays for the attestation account
+    // 1. `[]` The subject
+    // 2. `[writable]` The attestation PDA
+    // 3. `[]` The system program
+    Attest {
+        claim_hash: [u8; 32],
+        // unix timestamp, 0 for an attestation which doesn't expire
+        expires_at: i64,
+    },
+
+    // Revokes an attestation. The account is kept, so verifiers see that it has been revoked.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The issuer
+    // 1. `[writable]` The attestation PDA
+    Revoke,
+
+    // Fails unless the attestation has been issued by the issuer for the subject and claim, and is neither
+    // revoked nor expired. Meant to be called by other programs through a CPI.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[]` The attestation PDA
+    // 1. `[]` The issuer
+    // 2. `[]` The subject
+    Verify {
+        claim_hash: [u8; 32],
+    },
+}
+
+impl AttestInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::Attest {
+                claim_hash: Self::unpack_hash(rest, 0)?,
+                expires_at: rest
+                    .get(32..40)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(i64::from_le_bytes)
+                    .ok_or(InvalidInstruction)?,
+            },
+            1 => Self::Revoke,
+            2 => Self::Verify {
+                claim_hash: Self::unpack_hash(rest, 0)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_hash(input: &[u8], offset: usize) -> Result<[u8; 32], ProgramError> {
+        let hash = input
+            .get(offset..offset + 32)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(InvalidInstruction)?;
+        Ok(hash)
+    }
+}
+
+// Builds a Verify instruction, for programs which want to check an attestation through a CPI
+pub fn verify(
+    attest_program_id: &Pubkey,
+    attestation: &Pubkey,
+    issuer: &Pubkey,
+    subject: &Pubkey,
+    claim_hash: &[u8; 32],
+) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(claim_hash);
+
+    Instruction {
+        program_id: *attest_program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*attestation, false),
+            AccountMeta::new_readonly(*issuer, false),
+            AccountMeta::new_readonly(*subject, false),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,147 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::BorshSerialize;
+
+use crate::{instruction::AttestInstruction, error::AttestError, state::Attestation};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = AttestInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            AttestInstruction::Attest { claim_hash, expires_at } => {
+                msg!("Instruction: Attest");
+                Self::process_attest(accounts, claim_hash, expires_at, program_id)
+            },
+            AttestInstruction::Revoke => {
+                msg!("Instruction: Revoke");
+                Self::process_revoke(accounts, program_id)
+            },
+            AttestInstruction::Verify { claim_hash } => {
+                msg!("Instruction: Verify");
+                Self::process_verify(accounts, claim_hash, program_id)
+            }
+        }
+    }
+
+    fn process_attest(
+        accounts: &[AccountInfo],
+        claim_hash: [u8; 32],
+        expires_at: i64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let now = Clock::get()?.unix_timestamp;
+        if expires_at != 0 && expires_at <= now {
+            return Err(AttestError::ExpiryInPast.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let issuer = next_account_info(account_info_iter)?;
+
+        if !issuer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let subject = next_account_info(account_info_iter)?;
+        let attestation_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (attestation_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"attestation", issuer.key.as_ref(), subject.key.as_ref(), &claim_hash],
+            program_id,
+        );
+        if attestation_pda != *attestation_account.key {
+            return Err(AttestError::InvalidAttestationAddress.into());
+        }
+
+        if !attestation_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating attestation account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                issuer.key,
+                attestation_account.key,
+                Rent::get()?.minimum_balance(Attestation::LEN),
+                Attestation::LEN as u64,
+                program_id,
+            ),
+            &[
+                issuer.clone(),
+                attestation_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"attestation", issuer.key.as_ref(), subject.key.as_ref(), &claim_hash, &[bump_seed]]],
+        )?;
+
+        let attestation_info = Attestation {
+            is_initialized: true,
+            issuer: *issuer.key,
+            subject: *subject.key,
+            claim_hash,
+            issued_at: now,
+            expires_at,
+            revoked_at: 0,
+            bump_seed,
+        };
+        attestation_info.serialize(&mut &mut attestation_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_revoke(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let issuer = next_account_info(account_info_iter)?;
+
+        if !issuer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let attestation_account = next_account_info(account_info_iter)?;
+        let mut attestation_info = Attestation::from_account_info(attestation_account, program_id)?;
+        if attestation_info.issuer != *issuer.key {
+            return Err(AttestError::NotTheIssuer.into());
+        }
+        if attestation_info.revoked_at != 0 {
+            return Err(AttestError::AttestationRevoked.into());
+        }
+
+        attestation_info.revoked_at = Clock::get()?.unix_timestamp;
+        attestation_info.serialize(&mut &mut attestation_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_verify(
+        accounts: &[AccountInfo],
+        claim_hash: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let attestation_account = next_account_info(account_info_iter)?;
+        let issuer = next_account_info(account_info_iter)?;
+        let subject = next_account_info(account_info_iter)?;
+
+        // the owner check in from_account_info makes sure the fields have been written by this program
+        let attestation_info = Attestation::from_account_info(attestation_account, program_id)?;
+        attestation_info.check(issuer.key, subject.key, &claim_hash, Clock::get()?.unix_timestamp)?;
+
+        msg!("{} attested the claim for {}", issuer.key, subject.key);
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::error::AttestError;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Attestation {
+    pub is_initialized: bool,
+    pub issuer: Pubkey,
+    pub subject: Pubkey,
+    // hash of whatever the issuer vouches for, e.g. sha256 of "kyc-passed". The claim itself stays off-chain
+    pub claim_hash: [u8; 32],
+    // unix timestamps, an expiry of 0 never expires and a revocation time of 0 means not revoked
+    pub issued_at: i64,
+    pub expires_at: i64,
+    pub revoked_at: i64,
+    pub bump_seed: u8,
+}
+
+impl Attestation {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 8 + 1;
+
+    // Checks that the attestation says what the caller expects and still holds at the given time
+    pub fn check(&self, issuer: &Pubkey, subject: &Pubkey, claim_hash: &[u8; 32], now: i64) -> Result<(), AttestError> {
+        if self.issuer != *issuer || self.subject != *subject || self.claim_hash != *claim_hash {
+            return Err(AttestError::AttestationMismatch);
+        }
+        if self.revoked_at != 0 {
+            return Err(AttestError::AttestationRevoked);
+        }
+        if self.expires_at != 0 && now >= self.expires_at {
+            return Err(AttestError::AttestationExpired);
+        }
+
+        Ok(())
+    }
+
+    // Reads an attestation from an account which has been passed to another program. Anybody can create an
+    // account with the same layout, so the owner has to be checked against the attest program id first.
+    pub fn from_account_info(account: &AccountInfo, attest_program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if account.owner != attest_program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let attestation = Self::try_from_slice(&account.data.borrow())?;
+        if !attestation.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(attestation)
+    }
+}
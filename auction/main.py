@@ -0,0 +1,267 @@
+import yaml
+import sys
+import json
+import time
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'auction.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'auction-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_program(payer: Keypair, decimals: int) -> Token:
+    token = Token.create_mint(
+        conn=client,
+        payer=payer,
+        mint_authority=payer.public_key,
+        decimals=decimals,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    print(f"Address of token program: {token.pubkey}")
+    return token
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def mint_token(token: Token, minter: Keypair, account: PublicKey, amount: int):
+    print(f"Minting {amount} tokens to {account}")
+    token.mint_to(
+        dest=account,
+        mint_authority=minter,
+        amount=amount,
+        opts=TxOpts(skip_confirmation=False)
+    )
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+
+def get_auction_address(seller: PublicKey, mint: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("auction", encoding="utf8"), bytes(seller), bytes(mint)],
+        program_id
+    )[0]
+
+def create_auction(seller: Keypair, token: Token, source: PublicKey, token_amount: int, min_bid: int, end_slot: int) -> PublicKey:
+    auction = get_auction_address(seller.public_key, token.pubkey)
+    # the vault is a normal token account owned by the auction PDA
+    vault = create_token_account(token, auction)
+    print(f"\nAuctioning {token_amount} tokens, minimum bid {min_bid} lamports, ending at slot {end_slot}")
+    create_ix = TransactionInstruction(
+        data=(
+            (0).to_bytes(1, byteorder='little')
+            + token_amount.to_bytes(8, byteorder='little')
+            + min_bid.to_bytes(8, byteorder='little')
+            + end_slot.to_bytes(8, byteorder='little')
+        ),
+        keys=[
+            AccountMeta(pubkey=seller.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=auction, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=source, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(create_ix), seller)
+    client.confirm_transaction(tx["result"])
+    return vault
+
+def bid(bidder: Keypair, auction: PublicKey, previous_bidder: PublicKey, amount: int):
+    print(f"\n{bidder.public_key} bids {amount} lamports")
+    bid_ix = TransactionInstruction(
+        data=(1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=bidder.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=auction, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=previous_bidder, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(bid_ix), bidder)
+    client.confirm_transaction(tx["result"])
+
+def settle(caller: Keypair, auction: PublicKey, seller: PublicKey, vault: PublicKey, destination: PublicKey):
+    print("\nSettling the auction")
+    settle_ix = TransactionInstruction(
+        data=(2).to_bytes(1, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=auction, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=seller, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(settle_ix), caller)
+    client.confirm_transaction(tx["result"])
+
+def balance(account: PublicKey) -> int:
+    return client.get_balance(account)['result']['value']
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+
+    token = create_token_program(payer, decimals=0)
+    seller_account = create_token_account(token, payer.public_key)
+    mint_token(token, payer, seller_account, 10)
+
+    end_slot = client.get_slot()['result'] + 50
+    vault = create_auction(payer, token, seller_account, 10, LAMPORTS_PER_SOL // 10, end_slot)
+    auction = get_auction_address(payer.public_key, token.pubkey)
+
+    # there is no previous bidder for the first bid, any account can be passed
+    bid(alice, auction, alice.public_key, LAMPORTS_PER_SOL // 5)
+    print(f"Alice balance: {balance(alice.public_key)} lamports")
+    bid(bob, auction, alice.public_key, LAMPORTS_PER_SOL // 2)
+    print(f"Alice balance after being outbid: {balance(alice.public_key)} lamports")
+
+    try:
+        bid(alice, auction, bob.public_key, LAMPORTS_PER_SOL // 4)
+    except Exception as e:
+        print(f"Bidding below the highest bid fails as expected: {e}")
+
+    bob_account = create_token_account(token, bob.public_key)
+    print("Waiting for the end slot...")
+    while client.get_slot()['result'] < end_slot:
+        time.sleep(1)
+
+    seller_balance = balance(payer.public_key)
+    settle(alice, auction, payer.public_key, vault, bob_account)
+    print(f"Bob token balance: {token_balance(token, bob_account)}")
+    print(f"Seller received: {balance(payer.public_key) - seller_balance} lamports")
@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum AuctionError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Auction Address")]
+    InvalidAuctionAddress,
+    #[error("Invalid Vault")]
+    InvalidVault,
+    #[error("End Slot In The Past")]
+    EndSlotInPast,
+    #[error("Auction Ended")]
+    AuctionEnded,
+    #[error("Auction Still Running")]
+    AuctionStillRunning,
+    #[error("Bid Too Low")]
+    BidTooLow,
+    #[error("Wrong Previous Bidder")]
+    WrongPreviousBidder,
+    #[error("Wrong Recipient")]
+    WrongRecipient,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<AuctionError> for ProgramError {
+    fn from(e: AuctionError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,79 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::AuctionError::InvalidInstruction;
+
+pub enum AuctionInstruction {
+
+    // Lists tokens for auction. The auction lives in a PDA derived from ["auction", seller, mint], the vault has to be
+    // created prior to this instruction and owned by the auction PDA.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The seller, pays for the auction account
+    // 1. `[writable]` The auction PDA
+    // 2. `[writable]` The seller's token account to take the tokens from
+    // 3. `[writable]` The vault, a token account owned by the auction PDA
+    // 4. `[]` The system program
+    // 5. `[]` The token program
+    CreateAuction {
+        token_amount: u64,
+        min_bid: u64,
+        end_slot: u64,
+    },
+
+    // Places a bid. The lamports are escrowed in the auction account and the previous highest bid is refunded.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The bidder
+    // 1. `[writable]` The auction PDA
+    // 2. `[writable]` The current highest bidder to refund, any account if there is no bid yet
+    // 3. `[]` The system program
+    Bid {
+        amount: u64,
+    },
+
+    // Ends the auction once the end slot is reached. Anyone may call it. The tokens go to the highest bidder
+    // (back to the seller without bids), the highest bid and all rent go to the seller.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The auction PDA
+    // 1. `[writable]` The seller
+    // 2. `[writable]` The vault
+    // 3. `[writable]` The token account of the highest bidder, or of the seller without bids
+    // 4. `[]` The token program
+    Settle,
+}
+
+impl AuctionInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateAuction {
+                token_amount: Self::unpack_u64(rest, 0)?,
+                min_bid: Self::unpack_u64(rest, 8)?,
+                end_slot: Self::unpack_u64(rest, 16)?,
+            },
+            1 => Self::Bid {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::Settle,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
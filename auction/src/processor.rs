@@ -0,0 +1,299 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::Account as TokenAccount;
+
+use crate::{instruction::AuctionInstruction, error::AuctionError, state::Auction};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = AuctionInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            AuctionInstruction::CreateAuction { token_amount, min_bid, end_slot } => {
+                msg!("Instruction: CreateAuction");
+                Self::process_create_auction(accounts, token_amount, min_bid, end_slot, program_id)
+            },
+            AuctionInstruction::Bid { amount } => {
+                msg!("Instruction: Bid");
+                Self::process_bid(accounts, amount, program_id)
+            },
+            AuctionInstruction::Settle => {
+                msg!("Instruction: Settle");
+                Self::process_settle(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_auction(
+        accounts: &[AccountInfo],
+        token_amount: u64,
+        min_bid: u64,
+        end_slot: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if end_slot <= Clock::get()?.slot {
+            return Err(AuctionError::EndSlotInPast.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let seller = next_account_info(account_info_iter)?;
+
+        if !seller.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let auction_account = next_account_info(account_info_iter)?;
+        let seller_token_account = next_account_info(account_info_iter)?;
+        let vault = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let vault_info = TokenAccount::unpack(&vault.try_borrow_data()?)?;
+        let (auction_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"auction", seller.key.as_ref(), vault_info.mint.as_ref()],
+            program_id,
+        );
+        if auction_pda != *auction_account.key {
+            return Err(AuctionError::InvalidAuctionAddress.into());
+        }
+        if vault_info.owner != auction_pda {
+            return Err(AuctionError::InvalidVault.into());
+        }
+
+        if !auction_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating auction account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                seller.key,
+                auction_account.key,
+                Rent::get()?.minimum_balance(Auction::LEN),
+                Auction::LEN as u64,
+                program_id,
+            ),
+            &[
+                seller.clone(),
+                auction_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"auction", seller.key.as_ref(), vault_info.mint.as_ref(), &[bump_seed]]],
+        )?;
+
+        msg!("Calling the token program to transfer the tokens into the vault...");
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                seller_token_account.key,
+                vault.key,
+                seller.key,
+                &[seller.key],
+                token_amount,
+            )?,
+            &[
+                seller_token_account.clone(),
+                vault.clone(),
+                seller.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        let auction_info = Auction {
+            is_initialized: true,
+            seller: *seller.key,
+            mint: vault_info.mint,
+            vault: *vault.key,
+            token_amount,
+            min_bid,
+            end_slot,
+            highest_bidder: Pubkey::default(),
+            highest_bid: 0,
+            bump_seed,
+        };
+        auction_info.serialize(&mut &mut auction_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_bid(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let bidder = next_account_info(account_info_iter)?;
+
+        if !bidder.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let auction_account = next_account_info(account_info_iter)?;
+        let mut auction_info = Self::unpack_auction(auction_account, program_id)?;
+
+        if Clock::get()?.slot >= auction_info.end_slot {
+            return Err(AuctionError::AuctionEnded.into());
+        }
+
+        if amount < auction_info.min_bid || amount <= auction_info.highest_bid {
+            return Err(AuctionError::BidTooLow.into());
+        }
+
+        let previous_bidder = next_account_info(account_info_iter)?;
+        let has_previous_bid = auction_info.highest_bid > 0;
+        if has_previous_bid && *previous_bidder.key != auction_info.highest_bidder {
+            return Err(AuctionError::WrongPreviousBidder.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        // the bidder is a system account, only the system program can take lamports from it
+        msg!("Calling the system program to escrow {} lamports...", amount);
+        invoke(
+            &system_instruction::transfer(bidder.key, auction_account.key, amount),
+            &[
+                bidder.clone(),
+                auction_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        // the auction account is owned by this program, so the refund is a plain lamport move
+        if has_previous_bid {
+            msg!("Refunding {} lamports to {}...", auction_info.highest_bid, previous_bidder.key);
+            **auction_account.lamports.borrow_mut() -= auction_info.highest_bid;
+            **previous_bidder.lamports.borrow_mut() = previous_bidder.lamports()
+                .checked_add(auction_info.highest_bid)
+                .ok_or(AuctionError::AmountOverflow)?;
+        }
+
+        auction_info.highest_bidder = *bidder.key;
+        auction_info.highest_bid = amount;
+        auction_info.serialize(&mut &mut auction_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_settle(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let auction_account = next_account_info(account_info_iter)?;
+        let auction_info = Self::unpack_auction(auction_account, program_id)?;
+
+        if Clock::get()?.slot < auction_info.end_slot {
+            return Err(AuctionError::AuctionStillRunning.into());
+        }
+
+        let seller = next_account_info(account_info_iter)?;
+        if *seller.key != auction_info.seller {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != auction_info.vault {
+            return Err(AuctionError::InvalidVault.into());
+        }
+
+        // without any bid the tokens simply go back to the seller
+        let recipient = if auction_info.highest_bid > 0 {
+            auction_info.highest_bidder
+        } else {
+            auction_info.seller
+        };
+        let recipient_token_account = next_account_info(account_info_iter)?;
+        if TokenAccount::unpack(&recipient_token_account.try_borrow_data()?)?.owner != recipient {
+            return Err(AuctionError::WrongRecipient.into());
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let auction_seeds: &[&[u8]] = &[
+            b"auction",
+            auction_info.seller.as_ref(),
+            auction_info.mint.as_ref(),
+            &[auction_info.bump_seed],
+        ];
+
+        msg!("Calling the token program to transfer the tokens to {}...", recipient);
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                vault.key,
+                recipient_token_account.key,
+                auction_account.key,
+                &[auction_account.key],
+                auction_info.token_amount,
+            )?,
+            &[
+                vault.clone(),
+                recipient_token_account.clone(),
+                auction_account.clone(),
+                token_program.clone(),
+            ],
+            &[auction_seeds],
+        )?;
+
+        msg!("Calling the token program to close the vault...");
+        invoke_signed(
+            &spl_token::instruction::close_account(
+                token_program.key,
+                vault.key,
+                seller.key,
+                auction_account.key,
+                &[auction_account.key],
+            )?,
+            &[
+                vault.clone(),
+                seller.clone(),
+                auction_account.clone(),
+                token_program.clone(),
+            ],
+            &[auction_seeds],
+        )?;
+
+        // the auction account holds the rent plus the highest bid, both go to the seller
+        msg!("Closing the auction account...");
+        **seller.lamports.borrow_mut() = seller.lamports()
+            .checked_add(auction_account.lamports())
+            .ok_or(AuctionError::AmountOverflow)?;
+        **auction_account.lamports.borrow_mut() = 0;
+        auction_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_auction(auction_account: &AccountInfo, program_id: &Pubkey) -> Result<Auction, ProgramError> {
+        if auction_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let auction_info = Auction::try_from_slice(&auction_account.data.borrow())?;
+        if !auction_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(auction_info)
+    }
+}
@@ -0,0 +1,25 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Auction {
+    pub is_initialized: bool,
+    pub seller: Pubkey,
+    pub mint: Pubkey,
+    // token account owned by the auction PDA, holding the tokens for sale
+    pub vault: Pubkey,
+    pub token_amount: u64,
+    // lowest first bid in lamports
+    pub min_bid: u64,
+    // bids are accepted until this slot, settlement is possible from then on
+    pub end_slot: u64,
+    // Pubkey::default() as long as nobody has bid
+    pub highest_bidder: Pubkey,
+    // lamports held by the auction account on top of its rent
+    pub highest_bid: u64,
+    pub bump_seed: u8,
+}
+
+impl Auction {
+    pub const LEN: usize = 1 + 32 * 3 + 8 * 3 + 32 + 8 + 1;
+}
@@ -0,0 +1,277 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+BOARD_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'owner': types.fixed_array(types.u8, 32),
+    'post_count': types.u64,
+    'bump_seed': types.u8
+})
+
+POST_SCHEMA = borsh.schema({
+    'board': types.fixed_array(types.u8, 32),
+    'author': types.fixed_array(types.u8, 32),
+    'index': types.u64,
+    'created_ts': types.i64,
+    'edited_ts': types.i64,
+    'deleted': types.u8,
+    'body': types.string
+})
+
+PAGE_SIZE = 3
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'board.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'board-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def get_board_address(owner: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("board", encoding="utf8"), bytes(owner)], program_id)[0]
+
+def get_post_address(board: PublicKey, index: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("post", encoding="utf8"), bytes(board), index.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def init_board(owner: Keypair) -> PublicKey:
+    board = get_board_address(owner.public_key)
+    if client.get_account_info(board)["result"]["value"] is not None:
+        print(f"Board {board} already exists")
+        return board
+
+    print(f"\nCreating board {board}")
+    send(
+        owner,
+        bytes(1),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=board, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return board
+
+def get_board(board: PublicKey) -> dict:
+    account_info = client.get_account_info(board)["result"]["value"]
+    return borsh.deserialize(BOARD_SCHEMA, base64.b64decode(account_info["data"][0]))
+
+def create_post(author: Keypair, board: PublicKey, body: str) -> int:
+    # the post is created at the current post count, so we have to read the head first
+    index = get_board(board)['post_count']
+    print(f"\n{author.public_key} writes post {index}")
+    send(
+        author,
+        (1).to_bytes(1, byteorder='little') + bytes(body, encoding="utf8"),
+        [
+            AccountMeta(pubkey=author.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=board, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_post_address(board, index), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return index
+
+def edit_post(author: Keypair, board: PublicKey, index: int, body: str):
+    print(f"\n{author.public_key} edits post {index}")
+    send(
+        author,
+        (2).to_bytes(1, byteorder='little') + bytes(body, encoding="utf8"),
+        [
+            AccountMeta(pubkey=author.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_post_address(board, index), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def delete_post(signer: Keypair, board: PublicKey, index: int, author: PublicKey):
+    print(f"\n{signer.public_key} deletes post {index}")
+    send(
+        signer,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=signer.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=board, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_post_address(board, index), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=author, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def get_page(board: PublicKey, page: int) -> list:
+    # Pages are counted from the newest post. Since the addresses only depend on the board and the index,
+    # a page is a single getMultipleAccounts call and always contains the same posts, deleted ones are
+    # still there as tombstones
+    post_count = get_board(board)['post_count']
+    last = post_count - page * PAGE_SIZE
+    indexes = range(last - 1, max(last - PAGE_SIZE, 0) - 1, -1)
+    result = client.get_multiple_accounts([get_post_address(board, index) for index in indexes])["result"]["value"]
+    return [borsh.deserialize(POST_SCHEMA, base64.b64decode(account_info["data"][0])) for account_info in result]
+
+def print_posts(board: PublicKey):
+    post_count = get_board(board)['post_count']
+    pages = (post_count + PAGE_SIZE - 1) // PAGE_SIZE
+    print(f"\nBoard {board} has {post_count} posts on {pages} pages")
+    for page in range(pages):
+        print(f"Page {page}")
+        for post in get_page(board, page):
+            if post['deleted']:
+                print(f"  #{post['index']} [deleted]")
+                continue
+            edited = f" (edited at {post['edited_ts']})" if post['edited_ts'] else ""
+            print(f"  #{post['index']} {PublicKey(post['author'])} at {post['created_ts']}{edited}: {post['body']}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    board = init_board(payer)
+    author = setup_user_keypair()
+
+    create_post(payer, board, "Welcome to the board, be nice")
+    first = create_post(author, board, "Hello everyone!")
+    create_post(author, board, "Does anyone know how PDAs work?")
+    spam = create_post(author, board, "Buy my token")
+    create_post(author, board, "Never mind, I read the docs")
+    print_posts(board)
+
+    edit_post(author, board, first, "Hello everyone, I am new here!")
+    # the board owner moderates the board and removes the spam, the rent goes back to the author anyway
+    delete_post(payer, board, spam, author.public_key)
+    print_posts(board)
+
+    try:
+        edit_post(author, board, spam, "Buy it anyway")
+    except Exception as e:
+        print(f"Editing a deleted post fails as expected: {e}")
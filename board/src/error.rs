@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum BoardError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Post Too Long")]
+    PostTooLong,
+    #[error("Invalid Board Address")]
+    InvalidBoardAddress,
+    #[error("Invalid Post Address")]
+    InvalidPostAddress,
+    #[error("Post Count Overflow")]
+    PostCountOverflow,
+    #[error("Not The Author")]
+    NotTheAuthor,
+    #[error("Post Deleted")]
+    PostDeleted,
+}
+
+impl From<BoardError> for ProgramError {
+    fn from(e: BoardError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,83 @@
+use solana_program::program_error::ProgramError;
+
+use crate::error::BoardError::InvalidInstruction;
+
+pub enum BoardInstruction {
+
+    // Creates the head account of a board. The head is a PDA derived from ["board", owner]
+    // and keeps track of how many posts have been written so far.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the board, pays for the head account
+    // 1. `[writable]` The board head PDA
+    // 2. `[]` The system program
+    InitBoard,
+
+    // Writes a new post PDA derived from ["post", board, index], where index is the
+    // post count stored in the head account before this instruction.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The author, pays for the post account
+    // 1. `[writable]` The board head PDA
+    // 2. `[writable]` The post PDA for the next index
+    // 3. `[]` The system program
+    CreatePost {
+        // The body of the post, everything after the tag byte, utf-8 encoded
+        body: String,
+    },
+
+    // Replaces the body of a post. The account is resized to the new body, the author pays
+    // for a longer one and gets the rent back for a shorter one.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The author
+    // 1. `[writable]` The post PDA
+    // 2. `[]` The system program
+    EditPost {
+        // The new body, everything after the tag byte, utf-8 encoded
+        body: String,
+    },
+
+    // Turns a post into a tombstone, the body is dropped and the freed rent goes back to the author.
+    // Either the author or the owner of the board may delete a post.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The author or the board owner
+    // 1. `[]` The board head PDA
+    // 2. `[writable]` The post PDA
+    // 3. `[writable]` The author, receives the freed rent
+    // 4. `[]` The system program
+    DeletePost,
+}
+
+impl BoardInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitBoard,
+            1 => Self::CreatePost {
+                body: Self::unpack_body(rest)?,
+            },
+            2 => Self::EditPost {
+                body: Self::unpack_body(rest)?,
+            },
+            3 => Self::DeletePost,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_body(input: &[u8]) -> Result<String, ProgramError> {
+        let body = std::str::from_utf8(input)
+            .map_err(|_| InvalidInstruction)?;
+        Ok(body.to_string())
+    }
+}
@@ -0,0 +1,331 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::BoardInstruction,
+    error::BoardError,
+    state::{Board, Post, MAX_POST_LEN},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = BoardInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            BoardInstruction::InitBoard => {
+                msg!("Instruction: InitBoard");
+                Self::process_init_board(accounts, program_id)
+            },
+            BoardInstruction::CreatePost { body } => {
+                msg!("Instruction: CreatePost");
+                Self::process_create_post(accounts, body, program_id)
+            },
+            BoardInstruction::EditPost { body } => {
+                msg!("Instruction: EditPost");
+                Self::process_edit_post(accounts, body, program_id)
+            },
+            BoardInstruction::DeletePost => {
+                msg!("Instruction: DeletePost");
+                Self::process_delete_post(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_init_board(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let board_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (board_pda, bump_seed) = Pubkey::find_program_address(&[b"board", owner.key.as_ref()], program_id);
+        if board_pda != *board_account.key {
+            return Err(BoardError::InvalidBoardAddress.into());
+        }
+
+        if !board_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating board head account...");
+        Self::create_pda_account(
+            owner,
+            board_account,
+            system_program,
+            Board::LEN,
+            program_id,
+            &[b"board", owner.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let board_info = Board {
+            is_initialized: true,
+            owner: *owner.key,
+            post_count: 0,
+            bump_seed,
+        };
+        board_info.serialize(&mut &mut board_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_create_post(
+        accounts: &[AccountInfo],
+        body: String,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if body.len() > MAX_POST_LEN {
+            return Err(BoardError::PostTooLong.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let author = next_account_info(account_info_iter)?;
+
+        if !author.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let board_account = next_account_info(account_info_iter)?;
+        let mut board_info = Self::unpack_board(board_account, program_id)?;
+
+        // the post address is fully determined by the board and the current count, so a client can
+        // fetch any page of posts by deriving the addresses for its range of indexes
+        let post_account = next_account_info(account_info_iter)?;
+        let index_bytes = board_info.post_count.to_le_bytes();
+        let (post_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"post", board_account.key.as_ref(), &index_bytes],
+            program_id,
+        );
+        if post_pda != *post_account.key {
+            return Err(BoardError::InvalidPostAddress.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Creating post {}...", board_info.post_count);
+        Self::create_pda_account(
+            author,
+            post_account,
+            system_program,
+            Post::space(body.len()),
+            program_id,
+            &[b"post", board_account.key.as_ref(), &index_bytes, &[bump_seed]],
+        )?;
+
+        let post_info = Post {
+            board: *board_account.key,
+            author: *author.key,
+            index: board_info.post_count,
+            created_ts: Clock::get()?.unix_timestamp,
+            edited_ts: 0,
+            deleted: false,
+            body,
+        };
+        post_info.serialize(&mut &mut post_account.data.borrow_mut()[..])?;
+
+        board_info.post_count = board_info.post_count
+            .checked_add(1)
+            .ok_or(BoardError::PostCountOverflow)?;
+        board_info.serialize(&mut &mut board_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_edit_post(
+        accounts: &[AccountInfo],
+        body: String,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if body.len() > MAX_POST_LEN {
+            return Err(BoardError::PostTooLong.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let author = next_account_info(account_info_iter)?;
+
+        if !author.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let post_account = next_account_info(account_info_iter)?;
+        let mut post_info = Self::unpack_post(post_account, program_id)?;
+        if post_info.author != *author.key {
+            return Err(BoardError::NotTheAuthor.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        post_info.body = body;
+        post_info.edited_ts = Clock::get()?.unix_timestamp;
+        Self::store_post(&post_info, author, post_account, system_program)
+    }
+
+    fn process_delete_post(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let signer = next_account_info(account_info_iter)?;
+
+        if !signer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let board_account = next_account_info(account_info_iter)?;
+        let board_info = Self::unpack_board(board_account, program_id)?;
+
+        let post_account = next_account_info(account_info_iter)?;
+        let mut post_info = Self::unpack_post(post_account, program_id)?;
+        if post_info.board != *board_account.key {
+            return Err(BoardError::InvalidPostAddress.into());
+        }
+
+        // the board owner acts as moderator, besides them only the author may remove a post
+        if *signer.key != post_info.author && *signer.key != board_info.owner {
+            return Err(BoardError::NotTheAuthor.into());
+        }
+
+        // the author paid for the post, so the freed rent goes back to them, even if the moderator deleted it
+        let author = next_account_info(account_info_iter)?;
+        if *author.key != post_info.author {
+            return Err(BoardError::NotTheAuthor.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Deleting post {}...", post_info.index);
+        post_info.body.clear();
+        post_info.deleted = true;
+        Self::store_post(&post_info, author, post_account, system_program)
+    }
+
+    fn unpack_board(board_account: &AccountInfo, program_id: &Pubkey) -> Result<Board, ProgramError> {
+        if board_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let board_info = Board::try_from_slice(&board_account.data.borrow())?;
+        if !board_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(board_info)
+    }
+
+    fn unpack_post(post_account: &AccountInfo, program_id: &Pubkey) -> Result<Post, ProgramError> {
+        if post_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let post_info = Post::try_from_slice(&post_account.data.borrow())?;
+        if post_info.deleted {
+            return Err(BoardError::PostDeleted.into());
+        }
+
+        Ok(post_info)
+    }
+
+    // Writes the post back and resizes the account to the serialized length first. A bigger account needs
+    // more lamports to stay rent exempt, which the author pays through the system program. A smaller one
+    // gives the surplus back to the author.
+    fn store_post<'a>(
+        post_info: &Post,
+        author: &AccountInfo<'a>,
+        post_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let data = post_info.try_to_vec()?;
+
+        let required_lamports = Rent::get()?.minimum_balance(data.len());
+        let current_lamports = post_account.lamports();
+
+        if required_lamports > current_lamports {
+            invoke(
+                &system_instruction::transfer(author.key, post_account.key, required_lamports - current_lamports),
+                &[
+                    author.clone(),
+                    post_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        } else if current_lamports > required_lamports {
+            let surplus = current_lamports - required_lamports;
+            **post_account.lamports.borrow_mut() = required_lamports;
+            **author.lamports.borrow_mut() = author.lamports()
+                .checked_add(surplus)
+                .ok_or(ProgramError::InvalidAccountData)?;
+        }
+
+        msg!("Resizing post from {} to {} bytes...", post_account.data_len(), data.len());
+        post_account.realloc(data.len(), false)?;
+        post_account.data.borrow_mut().copy_from_slice(&data);
+
+        Ok(())
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    //
+    // The address of the next post is known to anyone, and anyone can send lamports to it. create_account refuses
+    // an account which holds lamports already, a single lamport would block every later post of the board. Such an
+    // account is topped up to the rent instead, then allocated and assigned, which only needs the signature of the PDA.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let accounts = [
+            payer.clone(),
+            new_account.clone(),
+            system_program.clone(),
+        ];
+
+        if new_account.lamports() == 0 {
+            let create_account_ix = system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            );
+            return invoke_signed(&create_account_ix, &accounts, &[signer_seeds]);
+        }
+
+        let shortfall = lamports.saturating_sub(new_account.lamports());
+        if shortfall > 0 {
+            invoke(&system_instruction::transfer(payer.key, new_account.key, shortfall), &accounts)?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(new_account.key, space as u64),
+            &accounts,
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(new_account.key, program_id),
+            &accounts,
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,39 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Longest post an author is allowed to write, in bytes
+pub const MAX_POST_LEN: usize = 512;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Board {
+    pub is_initialized: bool,
+    // the owner moderates the board, they may delete any post
+    pub owner: Pubkey,
+    // number of posts written so far, deleted ones included. The next post is created at this index
+    pub post_count: u64,
+    pub bump_seed: u8,
+}
+
+impl Board {
+    pub const LEN: usize = 1 + 32 + 8 + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Post {
+    pub board: Pubkey,
+    pub author: Pubkey,
+    pub index: u64,
+    // unix timestamps taken from the Clock sysvar, edited_ts stays 0 until the post is edited
+    pub created_ts: i64,
+    pub edited_ts: i64,
+    // a deleted post keeps its account as a tombstone with an empty body, so the indexes never shift
+    pub deleted: bool,
+    pub body: String,
+}
+
+impl Post {
+    // Posts are sized exactly to their body, borsh stores a string as a u32 length followed by its bytes
+    pub fn space(body_len: usize) -> usize {
+        32 + 32 + 8 + 8 + 8 + 1 + 4 + body_len
+    }
+}
@@ -0,0 +1,117 @@
+// Runs CreatePost in a local bank with solana-program-test, the board program is called natively
+use borsh::BorshDeserialize;
+use board::{
+    processor::Processor,
+    state::{Board, Post},
+};
+use solana_program_test::{processor, BanksClient, ProgramTest, ProgramTestBanksClientExt};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+struct Test {
+    banks_client: BanksClient,
+    payer: Keypair,
+    recent_blockhash: Hash,
+    program_id: Pubkey,
+    board: Pubkey,
+}
+
+impl Test {
+    // An empty board owned by the payer, who writes the posts too
+    async fn start() -> Self {
+        let program_id = Pubkey::new_unique();
+        let program_test = ProgramTest::new("board", program_id, processor!(Processor::process));
+        let (banks_client, payer, recent_blockhash) = program_test.start().await;
+        let board = Pubkey::find_program_address(&[b"board", payer.pubkey().as_ref()], &program_id).0;
+        let mut test = Self { banks_client, payer, recent_blockhash, program_id, board };
+
+        let init_board = Instruction::new_with_bytes(
+            program_id,
+            &[0],
+            vec![
+                AccountMeta::new(test.payer.pubkey(), true),
+                AccountMeta::new(board, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        test.send(init_board).await.unwrap();
+        test
+    }
+
+    // Every transaction uses a new blockhash, otherwise the same instruction twice would be a duplicate transaction
+    async fn send(&mut self, instruction: Instruction) -> Result<(), TransactionError> {
+        self.recent_blockhash = self.banks_client.get_new_latest_blockhash(&self.recent_blockhash).await.unwrap();
+        let payer = self.payer.pubkey();
+        let transaction =
+            Transaction::new_signed_with_payer(&[instruction], Some(&payer), &[&self.payer], self.recent_blockhash);
+        self.banks_client.process_transaction(transaction).await.map_err(|e| e.unwrap())
+    }
+
+    fn post_address(&self, index: u64) -> Pubkey {
+        let seeds: &[&[u8]] = &[b"post", self.board.as_ref(), &index.to_le_bytes()];
+        Pubkey::find_program_address(seeds, &self.program_id).0
+    }
+
+    // Writes the post at the post count of the board
+    async fn create_post(&mut self, body: &str) -> Result<(), TransactionError> {
+        let board = self.banks_client.get_account(self.board).await.unwrap().unwrap();
+        let post_count = Board::try_from_slice(&board.data).unwrap().post_count;
+        let mut data = vec![1];
+        data.extend_from_slice(body.as_bytes());
+        let create_post = Instruction::new_with_bytes(
+            self.program_id,
+            &data,
+            vec![
+                AccountMeta::new(self.payer.pubkey(), true),
+                AccountMeta::new(self.board, false),
+                AccountMeta::new(self.post_address(post_count), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        self.send(create_post).await
+    }
+
+    async fn post(&mut self, index: u64) -> Post {
+        let account = self.banks_client.get_account(self.post_address(index)).await.unwrap().unwrap();
+        assert_eq!(account.owner, self.program_id);
+        Post::try_from_slice(&account.data).unwrap()
+    }
+}
+
+#[tokio::test]
+async fn create_post_writes_the_next_index() {
+    let mut test = Test::start().await;
+    test.create_post("hello").await.unwrap();
+    test.create_post("world").await.unwrap();
+
+    assert_eq!(test.post(0).await.body, "hello");
+    let post = test.post(1).await;
+    assert_eq!((post.index, post.body.as_str(), post.deleted), (1, "world", false));
+}
+
+#[tokio::test]
+async fn prefunded_post_is_created_anyway() {
+    let mut test = Test::start().await;
+
+    // anybody can send lamports to the address of the next post, the bank wants them rent exempt for no data
+    let payer = test.payer.pubkey();
+    let next_post = test.post_address(0);
+    let lamports = test.banks_client.get_rent().await.unwrap().minimum_balance(0);
+    test.send(system_instruction::transfer(&payer, &next_post, lamports)).await.unwrap();
+
+    test.create_post("hello").await.unwrap();
+    let post = test.post(0).await;
+    assert_eq!((post.author, post.body.as_str()), (payer, "hello"));
+    let rent = test.banks_client.get_rent().await.unwrap().minimum_balance(Post::space("hello".len()));
+    assert_eq!(test.banks_client.get_balance(next_post).await.unwrap(), rent);
+
+    // the later posts of the board aren't blocked
+    test.create_post("world").await.unwrap();
+    assert_eq!(test.post(1).await.body, "world");
+}
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum BountyError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Bounty Address")]
+    InvalidBountyAddress,
+    #[error("Invalid Submission Address")]
+    InvalidSubmissionAddress,
+    #[error("Deadline In The Past")]
+    DeadlineInPast,
+    #[error("Bounty Expired")]
+    BountyExpired,
+    #[error("Bounty Not Expired")]
+    BountyNotExpired,
+    #[error("Not The Poster")]
+    NotThePoster,
+    #[error("Not The Hunter")]
+    NotTheHunter,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<BountyError> for ProgramError {
+    fn from(e: BountyError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
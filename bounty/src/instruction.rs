@@ -0,0 +1,98 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::BountyError::InvalidInstruction;
+
+pub enum BountyInstruction {
+
+    // Posts a bounty in a PDA derived from ["bounty", poster, id]. The reward is escrowed in the bounty account.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The poster
+    // 1. `[writable]` The bounty PDA
+    // 2. `[]` The system program
+    PostBounty {
+        id: u64,
+        reward: u64,
+        deadline: i64,
+    },
+
+    // Submits work for a bounty before its deadline. Every hunter submits once, the submission is stored in a
+    // PDA derived from ["submission", bounty, hunter].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The hunter, pays for the submission account
+    // 1. `[writable]` The bounty PDA
+    // 2. `[writable]` The submission PDA
+    // 3. `[]` The system program
+    Submit {
+        deliverable_hash: [u8; 32],
+    },
+
+    // The poster accepts a submission. The reward goes to the hunter, the rent of the submission goes back to
+    // the hunter and the rent of the bounty goes back to the poster.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The poster
+    // 1. `[writable]` The bounty PDA
+    // 2. `[writable]` The submission PDA
+    // 3. `[writable]` The hunter
+    Approve,
+
+    // Returns the reward to the poster once the deadline has passed without an approval. Anyone may call it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The bounty PDA
+    // 1. `[writable]` The poster
+    Expire,
+
+    // Closes a submission which hasn't been approved, the hunter gets the rent back.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The hunter
+    // 1. `[writable]` The submission PDA
+    CloseSubmission,
+}
+
+impl BountyInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::PostBounty {
+                id: Self::unpack_u64(rest, 0)?,
+                reward: Self::unpack_u64(rest, 8)?,
+                deadline: Self::unpack_u64(rest, 16)? as i64,
+            },
+            1 => Self::Submit {
+                deliverable_hash: rest
+                    .get(..32)
+                    .and_then(|slice| slice.try_into().ok())
+                    .ok_or(InvalidInstruction)?,
+            },
+            2 => Self::Approve,
+            3 => Self::Expire,
+            4 => Self::CloseSubmission,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
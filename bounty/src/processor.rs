@@ -0,0 +1,329 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::BountyInstruction,
+    error::BountyError,
+    state::{Bounty, Submission},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = BountyInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            BountyInstruction::PostBounty { id, reward, deadline } => {
+                msg!("Instruction: PostBounty");
+                Self::process_post_bounty(accounts, id, reward, deadline, program_id)
+            },
+            BountyInstruction::Submit { deliverable_hash } => {
+                msg!("Instruction: Submit");
+                Self::process_submit(accounts, deliverable_hash, program_id)
+            },
+            BountyInstruction::Approve => {
+                msg!("Instruction: Approve");
+                Self::process_approve(accounts, program_id)
+            },
+            BountyInstruction::Expire => {
+                msg!("Instruction: Expire");
+                Self::process_expire(accounts, program_id)
+            },
+            BountyInstruction::CloseSubmission => {
+                msg!("Instruction: CloseSubmission");
+                Self::process_close_submission(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_post_bounty(
+        accounts: &[AccountInfo],
+        id: u64,
+        reward: u64,
+        deadline: i64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if deadline <= Clock::get()?.unix_timestamp {
+            return Err(BountyError::DeadlineInPast.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let poster = next_account_info(account_info_iter)?;
+
+        if !poster.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let bounty_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (bounty_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"bounty", poster.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if bounty_pda != *bounty_account.key {
+            return Err(BountyError::InvalidBountyAddress.into());
+        }
+
+        if !bounty_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the reward is simply added on top of the rent when the account is created
+        let lamports = Rent::get()?.minimum_balance(Bounty::LEN)
+            .checked_add(reward)
+            .ok_or(BountyError::AmountOverflow)?;
+
+        msg!("Creating bounty account holding a reward of {} lamports...", reward);
+        invoke_signed(
+            &system_instruction::create_account(
+                poster.key,
+                bounty_account.key,
+                lamports,
+                Bounty::LEN as u64,
+                program_id,
+            ),
+            &[
+                poster.clone(),
+                bounty_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"bounty", poster.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        let bounty_info = Bounty {
+            is_initialized: true,
+            poster: *poster.key,
+            id,
+            reward,
+            deadline,
+            submission_count: 0,
+            bump_seed,
+        };
+        bounty_info.serialize(&mut &mut bounty_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_submit(
+        accounts: &[AccountInfo],
+        deliverable_hash: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let hunter = next_account_info(account_info_iter)?;
+
+        if !hunter.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let bounty_account = next_account_info(account_info_iter)?;
+        let mut bounty_info = Self::unpack_bounty(bounty_account, program_id)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        if now >= bounty_info.deadline {
+            return Err(BountyError::BountyExpired.into());
+        }
+
+        let submission_account = next_account_info(account_info_iter)?;
+        let (submission_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"submission", bounty_account.key.as_ref(), hunter.key.as_ref()],
+            program_id,
+        );
+        if submission_pda != *submission_account.key {
+            return Err(BountyError::InvalidSubmissionAddress.into());
+        }
+
+        if !submission_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Creating submission account...");
+        Self::create_pda_account(
+            hunter,
+            submission_account,
+            system_program,
+            Submission::LEN,
+            program_id,
+            &[b"submission", bounty_account.key.as_ref(), hunter.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let submission_info = Submission {
+            is_initialized: true,
+            bounty: *bounty_account.key,
+            hunter: *hunter.key,
+            deliverable_hash,
+            submitted_at: now,
+            bump_seed,
+        };
+        submission_info.serialize(&mut &mut submission_account.data.borrow_mut()[..])?;
+
+        bounty_info.submission_count += 1;
+        bounty_info.serialize(&mut &mut bounty_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_approve(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let poster = next_account_info(account_info_iter)?;
+
+        if !poster.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let bounty_account = next_account_info(account_info_iter)?;
+        let bounty_info = Self::unpack_bounty(bounty_account, program_id)?;
+        if bounty_info.poster != *poster.key {
+            return Err(BountyError::NotThePoster.into());
+        }
+
+        let submission_account = next_account_info(account_info_iter)?;
+        let submission_info = Self::unpack_submission(submission_account, program_id)?;
+        if submission_info.bounty != *bounty_account.key {
+            return Err(BountyError::InvalidSubmissionAddress.into());
+        }
+
+        let hunter = next_account_info(account_info_iter)?;
+        if submission_info.hunter != *hunter.key {
+            return Err(BountyError::NotTheHunter.into());
+        }
+
+        // the bounty account is owned by the program, so the reward is paid by moving lamports directly
+        msg!("Paying the reward of {} lamports to {}...", bounty_info.reward, hunter.key);
+        **bounty_account.lamports.borrow_mut() -= bounty_info.reward;
+        **hunter.lamports.borrow_mut() = hunter.lamports()
+            .checked_add(bounty_info.reward)
+            .ok_or(BountyError::AmountOverflow)?;
+
+        msg!("Closing the submission and bounty accounts...");
+        Self::close_account(submission_account, hunter)?;
+        Self::close_account(bounty_account, poster)
+    }
+
+    fn process_expire(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let bounty_account = next_account_info(account_info_iter)?;
+        let bounty_info = Self::unpack_bounty(bounty_account, program_id)?;
+
+        if Clock::get()?.unix_timestamp < bounty_info.deadline {
+            return Err(BountyError::BountyNotExpired.into());
+        }
+
+        let poster = next_account_info(account_info_iter)?;
+        if bounty_info.poster != *poster.key {
+            return Err(BountyError::NotThePoster.into());
+        }
+
+        // the reward and the rent both go back, so the whole account is closed in one go
+        msg!("Returning the reward of {} lamports to the poster...", bounty_info.reward);
+        Self::close_account(bounty_account, poster)
+    }
+
+    fn process_close_submission(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let hunter = next_account_info(account_info_iter)?;
+
+        if !hunter.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let submission_account = next_account_info(account_info_iter)?;
+        let submission_info = Self::unpack_submission(submission_account, program_id)?;
+        if submission_info.hunter != *hunter.key {
+            return Err(BountyError::NotTheHunter.into());
+        }
+
+        msg!("Closing the submission account...");
+        Self::close_account(submission_account, hunter)
+    }
+
+    fn unpack_bounty(bounty_account: &AccountInfo, program_id: &Pubkey) -> Result<Bounty, ProgramError> {
+        if bounty_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let bounty_info = Bounty::try_from_slice(&bounty_account.data.borrow())?;
+        if !bounty_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(bounty_info)
+    }
+
+    fn unpack_submission(submission_account: &AccountInfo, program_id: &Pubkey) -> Result<Submission, ProgramError> {
+        if submission_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let submission_info = Submission::try_from_slice(&submission_account.data.borrow())?;
+        if !submission_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(submission_info)
+    }
+
+    fn close_account<'a>(account: &AccountInfo<'a>, destination: &AccountInfo<'a>) -> ProgramResult {
+        **destination.lamports.borrow_mut() = destination.lamports()
+            .checked_add(account.lamports())
+            .ok_or(BountyError::AmountOverflow)?;
+        **account.lamports.borrow_mut() = 0;
+        account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
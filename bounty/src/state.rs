@@ -0,0 +1,34 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Bounty {
+    pub is_initialized: bool,
+    pub poster: Pubkey,
+    pub id: u64,
+    // the reward in lamports, held by the bounty account on top of its rent
+    pub reward: u64,
+    // work can be submitted until this unix timestamp, afterwards the reward can go back to the poster
+    pub deadline: i64,
+    pub submission_count: u64,
+    pub bump_seed: u8,
+}
+
+impl Bounty {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 8 + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Submission {
+    pub is_initialized: bool,
+    pub bounty: Pubkey,
+    pub hunter: Pubkey,
+    // hash of the deliverable, the work itself is handed over off-chain
+    pub deliverable_hash: [u8; 32],
+    pub submitted_at: i64,
+    pub bump_seed: u8,
+}
+
+impl Submission {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 1;
+}
@@ -0,0 +1,286 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+GAME_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'white': types.fixed_array(types.u8, 32),
+    'black': types.option(types.fixed_array(types.u8, 32)),
+    'id': types.u64,
+    'squares': types.fixed_array(types.u8, 32),
+    'turn': types.u8,
+    'castling': types.u8,
+    'en_passant': types.u8,
+    'move_count': types.u16,
+    'status': types.u8,
+    'bump_seed': types.u8
+})
+
+GAME_STATUS = ["WaitingForPlayer", "InProgress", "WhiteWon", "BlackWon", "Stalemate"]
+PIECES = ".PNBRQK"
+PROMOTIONS = {"n": 2, "b": 3, "r": 4, "q": 5}
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'chess.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'chess-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_game_address(white: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("chess", encoding="utf8"), bytes(white), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+# "e2" -> 12, squares are numbered from a1 to h8
+def square(name: str) -> int:
+    return (int(name[1]) - 1) * 8 + ord(name[0]) - ord('a')
+
+def create_game(white: Keypair, id: int) -> PublicKey:
+    game = get_game_address(white.public_key, id)
+    print(f"\nCreating game {game}")
+    send(
+        white,
+        (0).to_bytes(1, byteorder='little') + id.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=white.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=game, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return game
+
+def join_game(black: Keypair, game: PublicKey):
+    print(f"\n{black.public_key} joins as black")
+    send(
+        black,
+        (1).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=black.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=game, is_signer=False, is_writable=True),
+        ],
+    )
+
+# moves are written like "e2e4", a promotion adds the piece, "e7e8q"
+def play_move(player: Keypair, game: PublicKey, move: str):
+    print(f"Playing {move}")
+    promotion = PROMOTIONS[move[4]] if len(move) == 5 else 0
+    send(
+        player,
+        (2).to_bytes(1, byteorder='little') + bytes([square(move[0:2]), square(move[2:4]), promotion]),
+        [
+            AccountMeta(pubkey=player.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=game, is_signer=False, is_writable=True),
+        ],
+    )
+
+def resign(player: Keypair, game: PublicKey):
+    print(f"\n{player.public_key} resigns")
+    send(
+        player,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=player.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=game, is_signer=False, is_writable=True),
+        ],
+    )
+
+def close_game(white: Keypair, game: PublicKey):
+    print(f"\nClosing game {game}")
+    send(
+        white,
+        (4).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=white.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=game, is_signer=False, is_writable=True),
+        ],
+    )
+
+# every square takes half a byte, white pieces are printed in upper case and black ones in lower case
+def show_game(game: PublicKey):
+    data = base64.b64decode(client.get_account_info(game)["result"]["value"]["data"][0])
+    game_info = borsh.deserialize(GAME_SCHEMA, data)
+    for rank in range(7, -1, -1):
+        row = ""
+        for file in range(8):
+            index = rank * 8 + file
+            nibble = (game_info['squares'][index // 2] >> (index % 2 * 4)) & 0x0f
+            piece = PIECES[nibble & 0x07]
+            row += piece.lower() if nibble & 0x08 else piece
+        print(f"{rank + 1} {row}")
+    print("  abcdefgh")
+    print(f"Moves: {game_info['move_count']}, status: {GAME_STATUS[game_info['status']]}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    black = setup_user_keypair()
+
+    game = create_game(payer, 0)
+    join_game(black, game)
+
+    try:
+        play_move(black, game, "e7e5")
+    except Exception as e:
+        print(f"Moving out of turn fails as expected: {e}")
+    try:
+        play_move(payer, game, "e2e5")
+    except Exception as e:
+        print(f"An illegal move fails as expected: {e}")
+
+    # the fool's mate, the shortest possible game
+    play_move(payer, game, "f2f3")
+    play_move(black, game, "e7e5")
+    play_move(payer, game, "g2g4")
+    play_move(black, game, "d8h4")
+    show_game(game)
+    close_game(payer, game)
+
+    # a second game that shows castling and en passant before white gives up
+    game = create_game(payer, 1)
+    join_game(black, game)
+    for player, move in [
+        (payer, "e2e4"), (black, "g8f6"),
+        (payer, "e4e5"), (black, "d7d5"),
+        (payer, "e5d6"), (black, "e7e6"),
+        (payer, "g1f3"), (black, "f8d6"),
+        (payer, "f1c4"), (black, "e8g8"),
+        (payer, "e1g1"),
+    ]:
+        play_move(player, game, move)
+    show_game(game)
+    resign(payer, game)
+    show_game(game)
+    close_game(payer, game)
@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum ChessError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Game Address")]
+    InvalidGameAddress,
+    #[error("Game Already Joined")]
+    GameAlreadyJoined,
+    #[error("Game Not In Progress")]
+    GameNotInProgress,
+    #[error("Game In Progress")]
+    GameInProgress,
+    #[error("Not A Player")]
+    NotAPlayer,
+    #[error("Not Your Turn")]
+    NotYourTurn,
+    #[error("Illegal Move")]
+    IllegalMove,
+}
+
+impl From<ChessError> for ProgramError {
+    fn from(e: ChessError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
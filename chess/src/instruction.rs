@@ -0,0 +1,96 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::{error::ChessError::InvalidInstruction, rules::{Kind, Move}};
+
+pub enum ChessInstruction {
+
+    // Creates a game in a PDA derived from ["chess", white, id] with the pieces in their starting position.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The white player
+    // 1. `[writable]` The game PDA
+    // 2. `[]` The system program
+    CreateGame {
+        id: u64,
+    },
+
+    // Joins a game as the black player.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The black player
+    // 1. `[writable]` The game PDA
+    JoinGame,
+
+    // Moves a piece from one square to another, squares are numbered from 0 (a1) to 63 (h8).
+    // Castling is played as a king move of two squares. A pawn reaching the last rank has to name
+    // the piece it turns into in the third byte, 0 for any other move, 2 for a knight, 3 for a bishop, 4 for a rook and 5 for a queen.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The player whose turn it is
+    // 1. `[writable]` The game PDA
+    PlayMove {
+        mv: Move,
+    },
+
+    // Gives up the game, the opponent wins.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` One of the players
+    // 1. `[writable]` The game PDA
+    Resign,
+
+    // Closes a finished game, or a game nobody joined, and returns the rent to white.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The white player
+    // 1. `[writable]` The game PDA
+    CloseGame,
+}
+
+impl ChessInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateGame {
+                id: Self::unpack_u64(rest, 0)?,
+            },
+            1 => Self::JoinGame,
+            2 => {
+                let (from, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                let (to, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                let promotion = rest.first().ok_or(InvalidInstruction)?;
+                Self::PlayMove {
+                    mv: Move {
+                        from: *from,
+                        to: *to,
+                        promotion: Kind::promotion_from_u8(*promotion).ok_or(InvalidInstruction)?,
+                    },
+                }
+            },
+            3 => Self::Resign,
+            4 => Self::CloseGame,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,276 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::ChessInstruction,
+    error::ChessError,
+    rules::{Color, Move, Position},
+    state::{Game, GameStatus},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = ChessInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            ChessInstruction::CreateGame { id } => {
+                msg!("Instruction: CreateGame");
+                Self::process_create_game(accounts, id, program_id)
+            },
+            ChessInstruction::JoinGame => {
+                msg!("Instruction: JoinGame");
+                Self::process_join_game(accounts, program_id)
+            },
+            ChessInstruction::PlayMove { mv } => {
+                msg!("Instruction: PlayMove");
+                Self::process_play_move(accounts, mv, program_id)
+            },
+            ChessInstruction::Resign => {
+                msg!("Instruction: Resign");
+                Self::process_resign(accounts, program_id)
+            },
+            ChessInstruction::CloseGame => {
+                msg!("Instruction: CloseGame");
+                Self::process_close_game(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_game(
+        accounts: &[AccountInfo],
+        id: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let white = next_account_info(account_info_iter)?;
+
+        if !white.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (game_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"chess", white.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if game_pda != *game_account.key {
+            return Err(ChessError::InvalidGameAddress.into());
+        }
+
+        if !game_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating game account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                white.key,
+                game_account.key,
+                Rent::get()?.minimum_balance(Game::LEN),
+                Game::LEN as u64,
+                program_id,
+            ),
+            &[
+                white.clone(),
+                game_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"chess", white.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        let game_info = Game {
+            is_initialized: true,
+            white: *white.key,
+            black: None,
+            id,
+            position: Position::new(),
+            move_count: 0,
+            status: GameStatus::WaitingForPlayer,
+            bump_seed,
+        };
+        game_info.serialize(&mut &mut game_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_join_game(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let black = next_account_info(account_info_iter)?;
+
+        if !black.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let mut game_info = Self::unpack_game(game_account, program_id)?;
+
+        if game_info.status != GameStatus::WaitingForPlayer {
+            return Err(ChessError::GameAlreadyJoined.into());
+        }
+
+        game_info.black = Some(*black.key);
+        game_info.status = GameStatus::InProgress;
+        game_info.serialize(&mut &mut game_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_play_move(
+        accounts: &[AccountInfo],
+        mv: Move,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let player = next_account_info(account_info_iter)?;
+
+        if !player.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let mut game_info = Self::unpack_game(game_account, program_id)?;
+
+        if game_info.status != GameStatus::InProgress {
+            return Err(ChessError::GameNotInProgress.into());
+        }
+
+        let black_key = game_info.black.ok_or(ChessError::GameNotInProgress)?;
+        let expected_player = match game_info.position.turn {
+            Color::White => game_info.white,
+            Color::Black => black_key,
+        };
+        if *player.key != expected_player {
+            return if *player.key == game_info.white || *player.key == black_key {
+                Err(ChessError::NotYourTurn.into())
+            } else {
+                Err(ChessError::NotAPlayer.into())
+            };
+        }
+
+        if !game_info.position.is_legal(&mv) {
+            return Err(ChessError::IllegalMove.into());
+        }
+
+        let mover = game_info.position.turn;
+        game_info.position.apply(&mv);
+        game_info.move_count += 1;
+
+        // the opponent is to move now, without a legal move the game is over
+        let opponent = game_info.position.turn;
+        if !game_info.position.has_legal_move() {
+            game_info.status = if game_info.position.in_check(opponent) {
+                msg!("Checkmate");
+                match mover {
+                    Color::White => GameStatus::WhiteWon,
+                    Color::Black => GameStatus::BlackWon,
+                }
+            } else {
+                msg!("Stalemate");
+                GameStatus::Stalemate
+            };
+        } else if game_info.position.in_check(opponent) {
+            msg!("Check");
+        }
+
+        msg!("{:?} moved from {} to {}, game status: {:?}", mover, mv.from, mv.to, game_info.status);
+        game_info.serialize(&mut &mut game_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_resign(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let player = next_account_info(account_info_iter)?;
+
+        if !player.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let mut game_info = Self::unpack_game(game_account, program_id)?;
+
+        if game_info.status != GameStatus::InProgress {
+            return Err(ChessError::GameNotInProgress.into());
+        }
+
+        // resigning is allowed at any time, not only when it's the player's turn
+        game_info.status = if *player.key == game_info.white {
+            GameStatus::BlackWon
+        } else if Some(*player.key) == game_info.black {
+            GameStatus::WhiteWon
+        } else {
+            return Err(ChessError::NotAPlayer.into());
+        };
+
+        msg!("{} resigned, game status: {:?}", player.key, game_info.status);
+        game_info.serialize(&mut &mut game_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_close_game(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let white = next_account_info(account_info_iter)?;
+
+        if !white.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let game_info = Self::unpack_game(game_account, program_id)?;
+
+        if game_info.white != *white.key {
+            return Err(ChessError::NotAPlayer.into());
+        }
+        if game_info.status == GameStatus::InProgress {
+            return Err(ChessError::GameInProgress.into());
+        }
+
+        msg!("Closing the game account after {} moves...", game_info.move_count);
+        **white.lamports.borrow_mut() = white.lamports()
+            .checked_add(game_account.lamports())
+            .ok_or(ProgramError::InvalidAccountData)?;
+        **game_account.lamports.borrow_mut() = 0;
+        game_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_game(game_account: &AccountInfo, program_id: &Pubkey) -> Result<Game, ProgramError> {
+        if game_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // black is None until somebody joins, the game is shorter than its account until then
+        let game_info = Game::deserialize(&mut &game_account.data.borrow()[..])?;
+        if !game_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(game_info)
+    }
+}
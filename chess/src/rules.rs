@@ -0,0 +1,348 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+// Squares are numbered from 0 (a1) to 63 (h8), rank by rank. The file is square % 8, the rank square / 8.
+pub const NO_SQUARE: u8 = 64;
+
+// castling rights, one bit each
+pub const WHITE_KINGSIDE: u8 = 1;
+pub const WHITE_QUEENSIDE: u8 = 2;
+pub const BLACK_KINGSIDE: u8 = 4;
+pub const BLACK_QUEENSIDE: u8 = 8;
+
+const KNIGHT_STEPS: [(i8, i8); 8] = [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)];
+const KING_STEPS: [(i8, i8); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+const DIAGONALS: [(i8, i8); 4] = [(1, 1), (-1, 1), (-1, -1), (1, -1)];
+const LINES: [(i8, i8); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    pub fn opponent(self) -> Self {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+
+    // the direction pawns of this color move in
+    fn forward(self) -> i8 {
+        match self {
+            Color::White => 1,
+            Color::Black => -1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Pawn = 1,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+impl Kind {
+    fn from_u8(value: u8) -> Option<Self> {
+        Some(match value {
+            1 => Kind::Pawn,
+            2 => Kind::Knight,
+            3 => Kind::Bishop,
+            4 => Kind::Rook,
+            5 => Kind::Queen,
+            6 => Kind::King,
+            _ => return None,
+        })
+    }
+
+    // what a pawn may turn into on the last rank, 0 means no promotion
+    pub fn promotion_from_u8(value: u8) -> Option<Option<Self>> {
+        match value {
+            0 => Some(None),
+            2..=5 => Kind::from_u8(value).map(Some),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub from: u8,
+    pub to: u8,
+    pub promotion: Option<Kind>,
+}
+
+// Everything needed to decide whether a move is legal. The 64 squares take half a byte each,
+// the lower three bits are the kind of the piece (0 for an empty square) and the fourth bit is set for black.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub squares: [u8; 32],
+    pub turn: Color,
+    pub castling: u8,
+    // the square a pawn skipped with its double step in the previous move, NO_SQUARE otherwise
+    pub en_passant: u8,
+}
+
+impl Position {
+    pub const LEN: usize = 32 + 1 + 1 + 1;
+
+    pub fn new() -> Self {
+        let mut position = Position {
+            squares: [0; 32],
+            turn: Color::White,
+            castling: WHITE_KINGSIDE | WHITE_QUEENSIDE | BLACK_KINGSIDE | BLACK_QUEENSIDE,
+            en_passant: NO_SQUARE,
+        };
+        let back_rank = [Kind::Rook, Kind::Knight, Kind::Bishop, Kind::Queen, Kind::King, Kind::Bishop, Kind::Knight, Kind::Rook];
+        for (file, kind) in back_rank.iter().enumerate() {
+            let file = file as u8;
+            position.set(file, Some((Color::White, *kind)));
+            position.set(8 + file, Some((Color::White, Kind::Pawn)));
+            position.set(48 + file, Some((Color::Black, Kind::Pawn)));
+            position.set(56 + file, Some((Color::Black, *kind)));
+        }
+        position
+    }
+
+    pub fn piece(&self, square: u8) -> Option<(Color, Kind)> {
+        let nibble = (self.squares[square as usize / 2] >> (square % 2 * 4)) & 0x0f;
+        let color = if nibble & 0x08 == 0 { Color::White } else { Color::Black };
+        Kind::from_u8(nibble & 0x07).map(|kind| (color, kind))
+    }
+
+    fn set(&mut self, square: u8, piece: Option<(Color, Kind)>) {
+        let nibble = match piece {
+            None => 0,
+            Some((Color::White, kind)) => kind as u8,
+            Some((Color::Black, kind)) => kind as u8 | 0x08,
+        };
+        let shift = square % 2 * 4;
+        let byte = &mut self.squares[square as usize / 2];
+        *byte = (*byte & !(0x0f << shift)) | (nibble << shift);
+    }
+
+    // The move is legal if the piece can get there and the own king isn't in check afterwards.
+    // A pawn reaching the last rank has to be promoted, no other move may carry a promotion.
+    pub fn is_legal(&self, mv: &Move) -> bool {
+        if mv.from >= 64 || mv.to >= 64 {
+            return false;
+        }
+        let kind = match self.piece(mv.from) {
+            Some((color, kind)) if color == self.turn => kind,
+            _ => return false,
+        };
+        let last_rank = if self.turn == Color::White { 7 } else { 0 };
+        if mv.promotion.is_some() != (kind == Kind::Pawn && mv.to / 8 == last_rank) {
+            return false;
+        }
+        if !self.targets(mv.from).contains(&mv.to) {
+            return false;
+        }
+
+        let mut after = *self;
+        after.apply(mv);
+        !after.in_check(self.turn)
+    }
+
+    // Plays a move without checking it, is_legal has to be called first. Hands the turn to the opponent.
+    pub fn apply(&mut self, mv: &Move) {
+        let (color, kind) = match self.piece(mv.from) {
+            Some(piece) => piece,
+            None => return,
+        };
+
+        // en passant captures the pawn next to the moving one, not on the target square
+        if kind == Kind::Pawn && mv.to == self.en_passant && mv.from % 8 != mv.to % 8 {
+            self.set(mv.from / 8 * 8 + mv.to % 8, None);
+        }
+        // castling is a king move of two files, the rook jumps over the king
+        if kind == Kind::King && (mv.to % 8).abs_diff(mv.from % 8) == 2 {
+            let rank = mv.from / 8 * 8;
+            let (rook_from, rook_to) = if mv.to % 8 == 6 { (rank + 7, rank + 5) } else { (rank, rank + 3) };
+            self.set(rook_from, None);
+            self.set(rook_to, Some((color, Kind::Rook)));
+        }
+
+        self.set(mv.to, Some((color, mv.promotion.unwrap_or(kind))));
+        self.set(mv.from, None);
+
+        // moving the king or a rook, or capturing a rook, gives up the castling rights on that side
+        if kind == Kind::King {
+            self.castling &= match color {
+                Color::White => !(WHITE_KINGSIDE | WHITE_QUEENSIDE),
+                Color::Black => !(BLACK_KINGSIDE | BLACK_QUEENSIDE),
+            };
+        }
+        for (corner, right) in [(0, WHITE_QUEENSIDE), (7, WHITE_KINGSIDE), (56, BLACK_QUEENSIDE), (63, BLACK_KINGSIDE)] {
+            if mv.from == corner || mv.to == corner {
+                self.castling &= !right;
+            }
+        }
+
+        self.en_passant = if kind == Kind::Pawn && mv.to.abs_diff(mv.from) == 16 {
+            (mv.from + mv.to) / 2
+        } else {
+            NO_SQUARE
+        };
+        self.turn = color.opponent();
+    }
+
+    pub fn in_check(&self, color: Color) -> bool {
+        (0..64)
+            .find(|square| self.piece(*square) == Some((color, Kind::King)))
+            .is_some_and(|king| self.is_attacked(king, color.opponent()))
+    }
+
+    // Checkmate and stalemate are both the lack of a legal move, the difference is whether the king is in check
+    pub fn has_legal_move(&self) -> bool {
+        (0..64).any(|from| {
+            matches!(self.piece(from), Some((color, _)) if color == self.turn)
+                && self.targets(from).into_iter().any(|to| {
+                    [None, Some(Kind::Queen)]
+                        .into_iter()
+                        .any(|promotion| self.is_legal(&Move { from, to, promotion }))
+                })
+        })
+    }
+
+    pub fn is_attacked(&self, square: u8, by: Color) -> bool {
+        let is = |target: Option<u8>, kinds: &[Kind]| {
+            target
+                .and_then(|target| self.piece(target))
+                .is_some_and(|(color, kind)| color == by && kinds.contains(&kind))
+        };
+
+        // pawns attack diagonally forward, so look one rank back from their point of view
+        if [-1, 1].iter().any(|file| is(step(square, *file, -by.forward()), &[Kind::Pawn])) {
+            return true;
+        }
+        if KNIGHT_STEPS.iter().any(|(file, rank)| is(step(square, *file, *rank), &[Kind::Knight])) {
+            return true;
+        }
+        if KING_STEPS.iter().any(|(file, rank)| is(step(square, *file, *rank), &[Kind::King])) {
+            return true;
+        }
+        DIAGONALS.iter().any(|direction| is(self.first_piece(square, *direction), &[Kind::Bishop, Kind::Queen]))
+            || LINES.iter().any(|direction| is(self.first_piece(square, *direction), &[Kind::Rook, Kind::Queen]))
+    }
+
+    // the first occupied square in a direction, if there is one before the edge of the board
+    fn first_piece(&self, from: u8, (file, rank): (i8, i8)) -> Option<u8> {
+        let mut square = from;
+        loop {
+            square = step(square, file, rank)?;
+            if self.piece(square).is_some() {
+                return Some(square);
+            }
+        }
+    }
+
+    // Squares the piece on `from` can move to, ignoring whether its own king is left in check
+    fn targets(&self, from: u8) -> Vec<u8> {
+        let (color, kind) = match self.piece(from) {
+            Some(piece) => piece,
+            None => return Vec::new(),
+        };
+        let free_or_enemy = |square: &u8| !matches!(self.piece(*square), Some((other, _)) if other == color);
+
+        match kind {
+            Kind::Pawn => self.pawn_targets(from, color),
+            Kind::Knight => KNIGHT_STEPS.iter().filter_map(|(file, rank)| step(from, *file, *rank)).filter(free_or_enemy).collect(),
+            Kind::Bishop => self.slide(from, color, &DIAGONALS),
+            Kind::Rook => self.slide(from, color, &LINES),
+            Kind::Queen => [self.slide(from, color, &DIAGONALS), self.slide(from, color, &LINES)].concat(),
+            Kind::King => {
+                let mut targets: Vec<u8> = KING_STEPS.iter().filter_map(|(file, rank)| step(from, *file, *rank)).filter(free_or_enemy).collect();
+                targets.extend(self.castling_targets(from, color));
+                targets
+            },
+        }
+    }
+
+    fn pawn_targets(&self, from: u8, color: Color) -> Vec<u8> {
+        let mut targets = Vec::new();
+        let forward = color.forward();
+        let start_rank = if color == Color::White { 1 } else { 6 };
+
+        if let Some(one) = step(from, 0, forward).filter(|square| self.piece(*square).is_none()) {
+            targets.push(one);
+            if from / 8 == start_rank {
+                if let Some(two) = step(one, 0, forward).filter(|square| self.piece(*square).is_none()) {
+                    targets.push(two);
+                }
+            }
+        }
+        for file in [-1, 1] {
+            if let Some(square) = step(from, file, forward) {
+                let captures = matches!(self.piece(square), Some((other, _)) if other != color);
+                if captures || square == self.en_passant {
+                    targets.push(square);
+                }
+            }
+        }
+        targets
+    }
+
+    fn slide(&self, from: u8, color: Color, directions: &[(i8, i8)]) -> Vec<u8> {
+        let mut targets = Vec::new();
+        for (file, rank) in directions {
+            let mut square = from;
+            while let Some(next) = step(square, *file, *rank) {
+                match self.piece(next) {
+                    None => targets.push(next),
+                    Some((other, _)) => {
+                        if other != color {
+                            targets.push(next);
+                        }
+                        break;
+                    }
+                }
+                square = next;
+            }
+        }
+        targets
+    }
+
+    // The king may castle if neither it nor the rook has moved, the squares between them are empty and the king
+    // doesn't start in, pass through or end up in check. The last one is checked by is_legal like for any move.
+    fn castling_targets(&self, from: u8, color: Color) -> Vec<u8> {
+        let (rank, kingside, queenside) = match color {
+            Color::White => (0, WHITE_KINGSIDE, WHITE_QUEENSIDE),
+            Color::Black => (56, BLACK_KINGSIDE, BLACK_QUEENSIDE),
+        };
+        if from != rank + 4 || self.is_attacked(from, color.opponent()) {
+            return Vec::new();
+        }
+
+        let mut targets = Vec::new();
+        let empty = |files: &[u8]| files.iter().all(|file| self.piece(rank + file).is_none());
+        if self.castling & kingside != 0 && empty(&[5, 6]) && !self.is_attacked(rank + 5, color.opponent()) {
+            targets.push(rank + 6);
+        }
+        if self.castling & queenside != 0 && empty(&[1, 2, 3]) && !self.is_attacked(rank + 3, color.opponent()) {
+            targets.push(rank + 2);
+        }
+        targets
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn step(square: u8, file: i8, rank: i8) -> Option<u8> {
+    let file = (square % 8) as i8 + file;
+    let rank = (square / 8) as i8 + rank;
+    if (0..8).contains(&file) && (0..8).contains(&rank) {
+        Some((rank * 8 + file) as u8)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,31 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::rules::Position;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    WaitingForPlayer,
+    InProgress,
+    WhiteWon,
+    BlackWon,
+    Stalemate,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Game {
+    pub is_initialized: bool,
+    // white creates the game and moves first
+    pub white: Pubkey,
+    pub black: Option<Pubkey>,
+    pub id: u64,
+    pub position: Position,
+    // moves played by both players together
+    pub move_count: u16,
+    pub status: GameStatus,
+    pub bump_seed: u8,
+}
+
+impl Game {
+    pub const LEN: usize = 1 + 32 + (1 + 32) + 8 + Position::LEN + 2 + 1 + 1;
+}
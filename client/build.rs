@@ -0,0 +1,180 @@
+// Generates the instruction builders from the instruction enums of the programs. Every variant becomes a function
+// `<variant>_instruction(program_id, <accounts>, <fields>)`, with the accounts taken from the #[account(...)]
+// attributes shank reads for the IDL, in the order of their index. A new variant or account in a program shows up in
+// the client with the next build.
+use std::{env, fs, path::Path};
+
+use quote::ToTokens;
+use syn::{punctuated::Punctuated, Expr, Fields, ItemEnum, Lit, Token};
+
+struct Program {
+    source: &'static str,
+    enum_name: &'static str,
+    // the path of the enum as the client sees it
+    enum_path: &'static str,
+    out: &'static str,
+}
+
+const PROGRAMS: &[Program] = &[
+    Program {
+        source: "../function_calls/src/instruction.rs",
+        enum_name: "Instruction",
+        enum_path: "function_calls::instruction::Instruction",
+        out: "calculator_instructions.rs",
+    },
+    Program {
+        source: "../from_scratch/src/lib.rs",
+        enum_name: "GreetingInstruction",
+        enum_path: "helloworld::GreetingInstruction",
+        out: "greeting_instructions.rs",
+    },
+];
+
+struct Account {
+    index: u64,
+    name: String,
+    writable: bool,
+    signer: bool,
+    desc: Option<String>,
+}
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    for program in PROGRAMS {
+        println!("cargo:rerun-if-changed={}", program.source);
+        let source =
+            fs::read_to_string(program.source).unwrap_or_else(|e| panic!("Failed to read {}: {}", program.source, e));
+        let file = syn::parse_file(&source).unwrap_or_else(|e| panic!("Failed to parse {}: {}", program.source, e));
+        let instruction_enum = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                syn::Item::Enum(item) if item.ident == program.enum_name => Some(item),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("{} has no enum {}", program.source, program.enum_name));
+
+        fs::write(Path::new(&out_dir).join(program.out), generate(program, instruction_enum)).unwrap();
+    }
+}
+
+fn generate(program: &Program, instruction_enum: &ItemEnum) -> String {
+    let mut code = format!("// Generated by build.rs from {} in {}\n", program.enum_name, program.source);
+
+    for variant in &instruction_enum.variants {
+        let mut accounts: Vec<Account> = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("account"))
+            .map(|attr| parse_account(attr, &variant.ident))
+            .collect();
+        accounts.sort_by_key(|account| account.index);
+
+        let fields: Vec<(String, String)> = match &variant.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .map(|field| (field.ident.as_ref().unwrap().to_string(), field.ty.to_token_stream().to_string()))
+                .collect(),
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(_) => panic!("{}::{} has unnamed fields, name them", program.enum_name, variant.ident),
+        };
+
+        code.push_str(&format!("\n// {}::{}, accounts:\n", program.enum_name, variant.ident));
+        for account in &accounts {
+            let flags: Vec<&str> = [(account.writable, "writable"), (account.signer, "signer")]
+                .into_iter()
+                .filter_map(|(set, flag)| set.then_some(flag))
+                .collect();
+            code.push_str(&format!("//  {}. {}", account.index, account.name));
+            if !flags.is_empty() {
+                code.push_str(&format!(" ({})", flags.join(", ")));
+            }
+            if let Some(desc) = &account.desc {
+                code.push_str(&format!(": {}", desc));
+            }
+            code.push('\n');
+        }
+
+        let mut parameters = vec!["program_id: &::solana_sdk::pubkey::Pubkey".to_string()];
+        parameters.extend(accounts.iter().map(|account| format!("{}: &::solana_sdk::pubkey::Pubkey", account.name)));
+        parameters.extend(fields.iter().map(|(name, ty)| format!("{}: {}", name, ty)));
+        code.push_str(&format!(
+            "pub fn {}_instruction({}) -> ::solana_sdk::instruction::Instruction {{\n",
+            snake_case(&variant.ident.to_string()),
+            parameters.join(", ")
+        ));
+
+        let metas: Vec<String> = accounts
+            .iter()
+            .map(|account| {
+                let constructor = if account.writable { "new" } else { "new_readonly" };
+                let (name, signer) = (&account.name, account.signer);
+                format!("::solana_sdk::instruction::AccountMeta::{}(*{}, {})", constructor, name, signer)
+            })
+            .collect();
+        // the program crates pack their enums themselves, with `pack(&self) -> Vec<u8>`
+        let names: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+        let data = if names.is_empty() {
+            format!("{}::{}.pack()", program.enum_path, variant.ident)
+        } else {
+            format!("{}::{} {{ {} }}.pack()", program.enum_path, variant.ident, names.join(", "))
+        };
+        code.push_str(&format!(
+            "    ::solana_sdk::instruction::Instruction::new_with_bytes(*program_id, &{}, vec![{}])\n}}\n",
+            data,
+            metas.join(", ")
+        ));
+    }
+
+    code
+}
+
+// #[account(0, writable, signer, name = "result_account", desc = "...")]
+fn parse_account(attr: &syn::Attribute, variant: &syn::Ident) -> Account {
+    let arguments = attr
+        .parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+        .unwrap_or_else(|e| panic!("Invalid account attribute on {}: {}", variant, e));
+
+    let mut account = Account { index: 0, name: String::new(), writable: false, signer: false, desc: None };
+    for argument in arguments {
+        match argument {
+            Expr::Lit(index) => match index.lit {
+                Lit::Int(index) => account.index = index.base10_parse().unwrap(),
+                _ => panic!("The index of an account of {} must be an integer", variant),
+            },
+            Expr::Path(flag) if flag.path.is_ident("writable") => account.writable = true,
+            Expr::Path(flag) if flag.path.is_ident("signer") => account.signer = true,
+            Expr::Assign(assign) => {
+                let key = assign.left.to_token_stream().to_string();
+                let value = match *assign.right {
+                    Expr::Lit(syn::ExprLit { lit: Lit::Str(value), .. }) => value.value(),
+                    _ => panic!("{} of an account of {} must be a string", key, variant),
+                };
+                match key.as_str() {
+                    "name" => account.name = value,
+                    "desc" => account.desc = Some(value),
+                    _ => {},
+                }
+            },
+            // shank knows a few more flags, like optional, they don't change the account meta
+            _ => {},
+        }
+    }
+
+    if account.name.is_empty() {
+        panic!("An account of {} has no name", variant);
+    }
+    account
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
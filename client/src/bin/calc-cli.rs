@@ -0,0 +1,224 @@
+// Calls the calculator program from the command line, for example `calc-cli add 3 5 --account <PUBKEY>`
+use std::error::Error;
+
+use clap::{Parser, Subcommand};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer, system_instruction};
+
+use client::{
+    bootstrap, calculator, cli, fees, logs, memo,
+    profile::{self, Profile},
+};
+
+#[derive(Parser)]
+#[command(about = "Add or subtract with the calculator program, or use its vault")]
+struct Args {
+    #[command(subcommand)]
+    operation: Operation,
+    /// Result account, derived from the payer and the program id like main.py does if it isn't given
+    #[arg(long, global = true)]
+    account: Option<Pubkey>,
+    /// Calculator program, taken from the cluster profile or read from the owner of the result account if it isn't
+    /// given
+    #[arg(long, global = true)]
+    program_id: Option<Pubkey>,
+    /// Let the program write a memo of the calculation, which explorers show
+    #[arg(long, global = true)]
+    memo: bool,
+    /// Pass the accounts of the caller check, needed by a program built with the caller-allowlist feature
+    #[arg(long, global = true)]
+    caller_allowlist: bool,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long, global = true)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Keypair of the payer, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    keypair: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Operation {
+    /// Creates the rent exempt result account of the payer, needs --program-id or a profile with the program
+    CreateAccount,
+    /// Stores a + b in the result account
+    Add { a: u64, b: u64 },
+    /// Stores a - b in the result account
+    Sub { a: u64, b: u64 },
+    /// The vault of the payer, a PDA holding lamports only the program can sign for
+    #[command(subcommand)]
+    Vault(VaultOperation),
+    /// The caller config, the programs allowed to call add and sub through a CPI
+    #[command(subcommand)]
+    Config(ConfigOperation),
+}
+
+#[derive(Subcommand)]
+enum VaultOperation {
+    /// Sets up the vault of the payer
+    Init,
+    /// Transfers lamports from the payer into its vault
+    Deposit { lamports: u64 },
+    /// Has the program transfer lamports out of the vault of the payer
+    Withdraw {
+        lamports: u64,
+        /// Recipient of the lamports, the payer if it isn't given
+        #[arg(long)]
+        to: Option<Pubkey>,
+    },
+    /// Shows the vault of the payer and its balance
+    Show,
+}
+
+#[derive(Subcommand)]
+enum ConfigOperation {
+    /// Creates the caller config with the payer as its admin
+    Init,
+    /// Replaces the allowed callers, the payer has to be the admin
+    SetCallers { callers: Vec<Pubkey> },
+    /// Shows the admin and the allowed callers
+    Show,
+}
+
+// add and sub only differ in the function building the instruction and the operator printed
+type Calculate = fn(&Pubkey, &Pubkey, u64, u64) -> Instruction;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+    let (rpc, payer) = cli::connect(&profile, args.url, args.keypair)?;
+    let program_id = args.program_id.or_else(|| profile.program_id(profile::CALCULATOR_PROGRAM));
+
+    let (calculate, a, b, operator): (Calculate, _, _, _) = match args.operation {
+        Operation::CreateAccount => return create_account(&rpc, &*payer, program_id),
+        Operation::Vault(operation) => return vault(&rpc, &*payer, program_id, operation),
+        Operation::Config(operation) => return config(&rpc, &*payer, program_id, operation),
+        Operation::Add { a, b } => (calculator::add_instruction, a, b, '+'),
+        Operation::Sub { a, b } => (calculator::sub_instruction, a, b, '-'),
+    };
+
+    let (program_id, account) = cli::program_and_account(&rpc, program_id, args.account, |program_id| {
+        calculator::calculator_address(&payer.pubkey(), program_id)
+    })?;
+
+    let mut instruction = calculate(&program_id, &account, a, b);
+    if args.caller_allowlist {
+        instruction = calculator::with_caller_accounts(instruction);
+    }
+    if args.memo {
+        instruction = memo::with_memo(instruction);
+    }
+    let instructions = [instruction];
+    fees::estimate_fees(&rpc, &payer.pubkey(), &instructions, &[])?.print();
+
+    let signature = client::send_instructions(&rpc, &*payer, &instructions)?;
+    println!("Transaction {} confirmed", signature);
+    for memo in memo::memos(&logs::transaction_logs(&rpc, &signature)?) {
+        println!("Memo: {}", memo);
+    }
+
+    let result = calculator::get_result(&rpc, &account)?;
+    println!("{} {} {} = {}", result.a, operator, result.b, result.result);
+
+    Ok(())
+}
+
+fn create_account(rpc: &RpcClient, payer: &dyn Signer, program_id: Option<Pubkey>) -> Result<(), Box<dyn Error>> {
+    let program_id = program_id.ok_or("create-account needs --program-id or a profile with the program")?;
+    let account = calculator::calculator_address(&payer.pubkey(), &program_id);
+    if rpc.get_account(&account).is_ok() {
+        println!("Result account {} exists already", account);
+        return Ok(());
+    }
+
+    // the lamports don't change the fee
+    let size = calculator::calculator_result_size();
+    let seed = calculator::CALCULATOR_SEED;
+    let instruction = bootstrap::create_state_account_instruction(&payer.pubkey(), &program_id, seed, size, 0)?;
+    fees::estimate_fees(rpc, &payer.pubkey(), &[instruction], &[size])?.print();
+
+    calculator::create_result_account(rpc, payer, &program_id)?;
+    println!("Created result account {}", account);
+    Ok(())
+}
+
+fn vault(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    program_id: Option<Pubkey>,
+    operation: VaultOperation,
+) -> Result<(), Box<dyn Error>> {
+    let program_id = program_id.ok_or("The vault needs --program-id or a profile with the program")?;
+    let (vault, _) = calculator::vault_address(&payer.pubkey(), &program_id);
+    match operation {
+        VaultOperation::Init => {
+            let (vault_state, _) = calculator::vault_state_address(&payer.pubkey(), &program_id);
+            if rpc.get_account(&vault_state).is_ok() {
+                println!("Vault {} is set up already", vault);
+                return Ok(());
+            }
+            let instruction = calculator::init_vault_pda_instruction(&payer.pubkey(), &program_id);
+            let size = bootstrap::packed_size(&calculator::VaultState { authority: payer.pubkey(), bump: 0 });
+            fees::estimate_fees(rpc, &payer.pubkey(), &[instruction], &[size])?.print();
+
+            calculator::init_vault(rpc, payer, &program_id)?;
+            println!("Vault {} is set up, deposit with `calc-cli vault deposit <LAMPORTS>`", vault);
+        },
+        VaultOperation::Deposit { lamports } => {
+            let transfer = system_instruction::transfer(&payer.pubkey(), &vault, lamports);
+            let signature = client::send_instructions(rpc, payer, &[transfer])?;
+            println!("Transaction {} confirmed", signature);
+            println!("Vault {} holds {} lamports", vault, rpc.get_balance(&vault)?);
+        },
+        VaultOperation::Withdraw { lamports, to } => {
+            let recipient = to.unwrap_or_else(|| payer.pubkey());
+            let signature = calculator::withdraw_from_vault(rpc, payer, &program_id, &recipient, lamports)?;
+            println!("Transaction {} confirmed", signature);
+            println!("Vault {} holds {} lamports", vault, rpc.get_balance(&vault)?);
+        },
+        VaultOperation::Show => println!("Vault {} holds {} lamports", vault, rpc.get_balance(&vault)?),
+    }
+    Ok(())
+}
+
+fn config(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    program_id: Option<Pubkey>,
+    operation: ConfigOperation,
+) -> Result<(), Box<dyn Error>> {
+    let program_id = program_id.ok_or("The config needs --program-id or a profile with the program")?;
+    let (config, _) = calculator::config_address(&program_id);
+    let instruction = match operation {
+        ConfigOperation::Init => {
+            if rpc.get_account(&config).is_ok() {
+                println!("Config {} exists already", config);
+                return Ok(());
+            }
+            let instruction = calculator::init_config_pda_instruction(&payer.pubkey(), &program_id);
+            let size = calculator::CallerConfig::SIZE;
+            fees::estimate_fees(rpc, &payer.pubkey(), std::slice::from_ref(&instruction), &[size])?.print();
+            instruction
+        },
+        ConfigOperation::SetCallers { callers } => {
+            if callers.len() > calculator::MAX_CALLERS {
+                return Err(format!("At most {} callers fit into the config", calculator::MAX_CALLERS).into());
+            }
+            calculator::set_callers_pda_instruction(&payer.pubkey(), &program_id, callers)
+        },
+        ConfigOperation::Show => {
+            let state: calculator::CallerConfig = client::get_state(rpc, &config)?;
+            println!("Config {} of admin {}", config, state.admin);
+            for caller in state.callers {
+                println!("  {}", caller);
+            }
+            return Ok(());
+        },
+    };
+
+    let signature = client::send_instructions(rpc, payer, &[instruction])?;
+    println!("Transaction {} confirmed", signature);
+    Ok(())
+}
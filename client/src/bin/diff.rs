@@ -0,0 +1,106 @@
+// Sends an instruction of the example programs and shows what it changed, for example
+// `diff greet --account <PUBKEY>` prints "counter: 4 -> 5". Unlike simulate the transaction is really sent, the
+// accounts are read right before and after it is confirmed. `--json` prints the changes for scripts.
+use std::error::Error;
+
+use clap::Parser;
+use serde_json::{json, Value};
+use solana_sdk::{message::Message, pubkey::Pubkey};
+
+use client::{
+    cli::{self, Call},
+    profile::Profile,
+    send_instructions,
+    simulate::{AccountChange, Change},
+};
+
+#[derive(Parser)]
+#[command(about = "Send an instruction of the greeting or the calculator program and show what it changed")]
+struct Args {
+    #[command(subcommand)]
+    call: Call,
+    /// More accounts to compare, besides the ones the instruction writes to
+    #[arg(long, global = true)]
+    watch: Vec<Pubkey>,
+    /// Print the changes as JSON
+    #[arg(long, global = true)]
+    json: bool,
+    /// State account, derived from the payer and the program id like main.py does if it isn't given
+    #[arg(long, global = true)]
+    account: Option<Pubkey>,
+    /// Program, taken from the cluster profile or read from the owner of the state account if it isn't given
+    #[arg(long, global = true)]
+    program_id: Option<Pubkey>,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long, global = true)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Keypair of the payer, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    keypair: Option<String>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+    let (rpc, payer) = cli::connect(&profile, args.url, args.keypair)?;
+
+    let instructions = [args.call.instruction(&rpc, &profile, &payer.pubkey(), args.program_id, args.account)?];
+
+    // the accounts the transaction writes to, the payer included, and the watched ones
+    let message = Message::new(&instructions, Some(&payer.pubkey()));
+    let mut addresses: Vec<Pubkey> = message
+        .account_keys
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| message.is_writable(*index))
+        .map(|(_, address)| *address)
+        .collect();
+    for address in args.watch {
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+
+    let before = rpc.get_multiple_accounts(&addresses)?;
+    let signature = send_instructions(&rpc, &*payer, &instructions)?;
+    let after = rpc.get_multiple_accounts(&addresses)?;
+    let accounts: Vec<AccountChange> = addresses
+        .into_iter()
+        .zip(before.into_iter().zip(after))
+        .map(|(address, (before, after))| AccountChange { address, before, after })
+        .collect();
+
+    if args.json {
+        let accounts: Vec<Value> = accounts
+            .iter()
+            .map(|account| {
+                let changes: Vec<Value> = account.changes().iter().map(change_json).collect();
+                json!({ "address": account.address.to_string(), "changes": changes })
+            })
+            .collect();
+        println!("{}", json!({ "signature": signature.to_string(), "accounts": accounts }));
+        return Ok(());
+    }
+
+    println!("Signature: {}", signature);
+    println!("Accounts:");
+    for account in &accounts {
+        account.print();
+    }
+    Ok(())
+}
+
+fn change_json(change: &Change) -> Value {
+    match change {
+        Change::Created => json!({ "change": "created" }),
+        Change::Closed => json!({ "change": "closed" }),
+        Change::Lamports { before, after } => json!({ "change": "lamports", "before": before, "after": after }),
+        Change::Field { state, name, before, after } => {
+            json!({ "change": "field", "state": state, "field": name, "before": before, "after": after })
+        },
+        Change::Data { before, after } => json!({ "change": "data", "before": before, "after": after }),
+    }
+}
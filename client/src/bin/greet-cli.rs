@@ -0,0 +1,169 @@
+// Creates a greeting account, greets it and shows its counter from the command line
+use std::error::Error;
+
+use clap::{Parser, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+
+use client::{
+    calculator, cli, fees, greeting, logs, memo,
+    profile::{self, Profile},
+};
+
+#[derive(Parser)]
+#[command(about = "Greet with the greeting program of from_scratch")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+    /// Greeting account, derived from the payer and the program id like main.py does if it isn't given
+    #[arg(long, global = true)]
+    account: Option<Pubkey>,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long, global = true)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Keypair of the payer, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    keypair: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates the rent exempt greeting account of the payer
+    CreateAccount {
+        /// Greeting program, taken from the cluster profile if it isn't given
+        #[arg(long)]
+        program_id: Option<Pubkey>,
+    },
+    /// Lets the program create the greeting PDA of the payer, use it with --account afterwards
+    Initialize {
+        /// Greeting program, taken from the cluster profile if it isn't given
+        #[arg(long)]
+        program_id: Option<Pubkey>,
+    },
+    /// Greets the account, which increments its counter
+    Greet {
+        /// Greeting program, taken from the cluster profile or read from the owner of the account if it isn't given
+        #[arg(long)]
+        program_id: Option<Pubkey>,
+        /// Let the program write a memo of the greeting, which explorers show
+        #[arg(long)]
+        memo: bool,
+        /// Only greet if the calculator stored a result above this threshold
+        #[arg(long)]
+        above: Option<u64>,
+        /// Result account of the calculator for --above, derived from the payer and the calculator program of the
+        /// cluster profile if it isn't given
+        #[arg(long, requires = "above")]
+        result_account: Option<Pubkey>,
+    },
+    /// Lists every greeting account of the program with its counter
+    List {
+        /// Greeting program, taken from the cluster profile if it isn't given
+        #[arg(long)]
+        program_id: Option<Pubkey>,
+    },
+    /// Shows how often the account has been greeted
+    Show {
+        /// Only needed to derive the account if --account isn't given
+        #[arg(long)]
+        program_id: Option<Pubkey>,
+    },
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+    let (rpc, payer) = cli::connect(&profile, args.url, args.keypair)?;
+    let program_or_profile =
+        |program_id: Option<Pubkey>| program_id.or_else(|| profile.program_id(profile::GREETING_PROGRAM));
+    let required = |program_id: Option<Pubkey>| -> Result<Pubkey, Box<dyn Error>> {
+        Ok(program_or_profile(program_id).ok_or("--program-id or a profile with the program is needed")?)
+    };
+
+    match args.command {
+        Command::CreateAccount { program_id } => {
+            let program_id = required(program_id)?;
+            let account = greeting::greeting_address(&payer.pubkey(), &program_id);
+            if rpc.get_account(&account).is_ok() {
+                println!("Greeting account {} exists already", account);
+                return Ok(());
+            }
+            // the lamports don't change the fee
+            let instruction = greeting::create_greeting_account_instruction(&payer.pubkey(), &program_id, 0);
+            fees::estimate_fees(&rpc, &payer.pubkey(), &[instruction], &[greeting::greeting_account_size()])?.print();
+
+            greeting::create_greeting_account(&rpc, &*payer, &program_id)?;
+            println!("Created greeting account {}", account);
+        },
+        Command::Initialize { program_id } => {
+            let program_id = required(program_id)?;
+            let (account, _) = greeting::greeting_pda(&payer.pubkey(), &program_id);
+            if rpc.get_account(&account).is_ok() {
+                println!("Greeting account {} exists already", account);
+                return Ok(());
+            }
+            // the program pays the rent out of the payer, which the estimate includes
+            let instruction = greeting::initialize_pda_instruction(&payer.pubkey(), &program_id);
+            fees::estimate_fees(&rpc, &payer.pubkey(), &[instruction], &[greeting::greeting_account_size()])?.print();
+
+            greeting::initialize_greeting(&rpc, &*payer, &program_id)?;
+            println!("Created greeting account {}", account);
+        },
+        Command::Greet { program_id, memo, above, result_account } => {
+            let program_id = program_or_profile(program_id);
+            let (program_id, account) = cli::program_and_account(&rpc, program_id, args.account, |program_id| {
+                greeting::greeting_address(&payer.pubkey(), program_id)
+            })?;
+            let mut instruction = match above {
+                Some(threshold) => {
+                    let result_account = result_account
+                        .or_else(|| {
+                            let calculator = profile.program_id(profile::CALCULATOR_PROGRAM)?;
+                            Some(calculator::calculator_address(&payer.pubkey(), &calculator))
+                        })
+                        .ok_or("--above needs --result-account or a profile with the calculator program")?;
+                    greeting::greet_above_instruction(&program_id, &account, &result_account, threshold)
+                },
+                None => greeting::greet_instruction(&program_id, &account),
+            };
+            if memo {
+                instruction = memo::with_memo(instruction);
+            }
+            let instructions = [instruction];
+            fees::estimate_fees(&rpc, &payer.pubkey(), &instructions, &[])?.print();
+
+            let signature = client::send_instructions(&rpc, &*payer, &instructions)?;
+            println!("Transaction {} confirmed", signature);
+            let transaction_logs = logs::transaction_logs(&rpc, &signature)?;
+            // the counter the program logged is the one right after this greeting, the account may be ahead already
+            for event in logs::greeting_events(&transaction_logs, &program_id) {
+                if let logs::GreetingEvent::Greeted { counter } = event {
+                    println!("{} has been greeted {} time(s)", account, counter);
+                }
+            }
+            for memo in memo::memos(&transaction_logs) {
+                println!("Memo: {}", memo);
+            }
+        },
+        Command::List { program_id } => {
+            let program_id = required(program_id)?;
+            let accounts = greeting::fetch_all_greeting_accounts(&rpc, &program_id)?;
+            for (account, greeting) in &accounts {
+                println!("{} has been greeted {} time(s)", account, greeting.counter);
+            }
+            println!("{} greeting account(s)", accounts.len());
+        },
+        Command::Show { program_id } => {
+            let account = match (program_or_profile(program_id), args.account) {
+                (_, Some(account)) => account,
+                (Some(program_id), None) => greeting::greeting_address(&payer.pubkey(), &program_id),
+                (None, None) => return Err("Either --account or --program-id is needed".into()),
+            };
+            println!("{} has been greeted {} time(s)", account, greeting::get_greeting(&rpc, &account)?.counter);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+// Watches a greeting account over the websocket of the cluster and prints the counter whenever it changes
+use std::error::Error;
+
+use borsh::BorshDeserialize;
+use clap::Parser;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{pubsub_client::PubsubClient, rpc_config::RpcAccountInfoConfig};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+
+use client::{
+    cli, greeting,
+    profile::{self, Profile},
+};
+
+#[derive(Parser)]
+#[command(about = "Print the counter of a greeting account every time it is greeted")]
+struct Args {
+    /// Greeting account, derived from the payer and the program id like main.py does if it isn't given
+    #[arg(long)]
+    account: Option<Pubkey>,
+    /// Only needed to derive the account if --account isn't given, taken from the cluster profile if it isn't given
+    #[arg(long)]
+    program_id: Option<Pubkey>,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long)]
+    cluster: Option<String>,
+    /// RPC url, the websocket url is derived from it. Both are taken from the cluster profile or the solana cli
+    /// config if it isn't given
+    #[arg(long)]
+    url: Option<String>,
+    /// Keypair of the payer, only needed to derive the account
+    #[arg(long)]
+    keypair: Option<String>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+    let program_id = args.program_id.or_else(|| profile.program_id(profile::GREETING_PROGRAM));
+    let account = match (args.account, program_id) {
+        (Some(account), _) => account,
+        (None, Some(program_id)) => {
+            let (_, payer) = cli::connect(&profile, args.url.clone(), args.keypair)?;
+            greeting::greeting_address(&payer.pubkey(), &program_id)
+        },
+        (None, None) => return Err("Either --account or --program-id is needed".into()),
+    };
+
+    let websocket_url = cli::websocket_url(&profile, args.url);
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        ..RpcAccountInfoConfig::default()
+    };
+    // the subscription is closed when it is dropped, so it has to live as long as the loop below
+    let (_subscription, notifications) = PubsubClient::account_subscribe(&websocket_url, &account, Some(config))?;
+    println!("Watching {} on {}, press Ctrl-C to stop", account, websocket_url);
+
+    // the node only notifies about changes, every greeting is one notification
+    for notification in notifications {
+        let data = notification.value.data.decode().ok_or("Account data isn't base64 encoded")?;
+        match greeting::GreetingAccount::try_from_slice(&data) {
+            Ok(greeting) => println!("Slot {}: greeted {} time(s)", notification.context.slot, greeting.counter),
+            Err(e) => println!("Slot {}: account data isn't a GreetingAccount: {}", notification.context.slot, e),
+        }
+    }
+
+    Err("Subscription closed by the node".into())
+}
@@ -0,0 +1,88 @@
+// Prints what happened to a state account, oldest transaction first, for example `history <PUBKEY>`:
+//
+//   Slot 1234 <signature>: success
+//     Add { a: 3, b: 5 }
+use std::error::Error;
+
+use clap::{Parser, ValueEnum};
+use solana_sdk::pubkey::Pubkey;
+
+use client::{
+    cli,
+    history::{self, Program},
+    profile::Profile,
+    state::ProgramState,
+};
+
+#[derive(Parser)]
+#[command(about = "Show the transactions of a greeting account or a calculator result account")]
+struct Args {
+    /// State account
+    account: Pubkey,
+    /// How many of the last transactions are shown
+    #[arg(long, default_value_t = 100)]
+    limit: usize,
+    /// Program owning the account, the owner of the account if it isn't given
+    #[arg(long)]
+    program_id: Option<Pubkey>,
+    /// Decode the instructions as the ones of this program, detected from the size of the account if it isn't given
+    #[arg(long = "as", value_enum)]
+    kind: Option<Kind>,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long)]
+    url: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Kind {
+    Greeting,
+    Calculator,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let rpc = cli::rpc_client(&Profile::load(args.cluster)?, args.url);
+
+    // a closed account has neither an owner nor data left, --program-id and --as are needed for it
+    let (program_id, program) = match (args.program_id, args.kind) {
+        (Some(program_id), Some(kind)) => (program_id, program_of(kind)),
+        (program_id, kind) => {
+            let account = rpc.get_account(&args.account)?;
+            let program = match kind {
+                Some(kind) => program_of(kind),
+                None => ProgramState::detect(&account.data)
+                    .map(|state| Program::of_state(&state))
+                    .ok_or("The account is neither a greeting nor a result account, --as picks the program")?,
+            };
+            (program_id.unwrap_or(account.owner), program)
+        },
+    };
+
+    let entries = history::account_history(&rpc, &args.account, &program_id, program, args.limit)?;
+    for entry in &entries {
+        let result = match &entry.err {
+            Some(e) => format!("failed, {}", e),
+            None => "success".to_string(),
+        };
+        match entry.block_time {
+            Some(time) => println!("Slot {} at {} {}: {}", entry.slot, time, entry.signature, result),
+            None => println!("Slot {} {}: {}", entry.slot, entry.signature, result),
+        }
+        for call in &entry.calls {
+            println!("  {:?}", call);
+        }
+    }
+    println!("{} transaction(s)", entries.len());
+
+    Ok(())
+}
+
+fn program_of(kind: Kind) -> Program {
+    match kind {
+        Kind::Greeting => Program::Greeting,
+        Kind::Calculator => Program::Calculator,
+    }
+}
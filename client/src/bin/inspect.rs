@@ -0,0 +1,64 @@
+// Fetches an account and prints it, with the data decoded if it is the state of one of the programs
+use std::error::Error;
+
+use clap::{Parser, ValueEnum};
+use solana_sdk::pubkey::Pubkey;
+
+use client::{cli, profile::Profile, state::ProgramState};
+
+#[derive(Parser)]
+#[command(about = "Show an account and decode the state of the greeting or the calculator program")]
+struct Args {
+    /// Account to show
+    account: Pubkey,
+    /// Decode the data as this state, detected from the size of the data if it isn't given
+    #[arg(long = "as", value_enum)]
+    kind: Option<Kind>,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long)]
+    url: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Kind {
+    Greeting,
+    Calculator,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let rpc = cli::rpc_client(&Profile::load(args.cluster)?, args.url);
+
+    let account = rpc.get_account(&args.account)?;
+    let rent_exempt_minimum = rpc.get_minimum_balance_for_rent_exemption(account.data.len())?;
+
+    println!("Account:  {}", args.account);
+    println!("Owner:    {}", account.owner);
+    println!("Lamports: {}", account.lamports);
+    if account.lamports >= rent_exempt_minimum {
+        println!("Rent:     exempt, the minimum is {} lamports", rent_exempt_minimum);
+    } else {
+        println!("Rent:     not exempt, {} lamports are missing", rent_exempt_minimum - account.lamports);
+    }
+    println!("Size:     {} bytes", account.data.len());
+
+    let state = match args.kind {
+        Some(Kind::Greeting) => Some(ProgramState::greeting(&account.data)?),
+        Some(Kind::Calculator) => Some(ProgramState::calculator(&account.data)?),
+        None => ProgramState::detect(&account.data),
+    };
+    match state {
+        Some(state) => {
+            println!("{}", state.name());
+            for (name, value) in state.fields() {
+                println!("  {:<8}{}", format!("{}:", name), value);
+            }
+        },
+        None => println!("Data:     {:?}", account.data),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+// A keeper bot for the streaming program. It looks for streams which can be cranked every few seconds, cranks them
+// and collects the reward, one line per crank:
+//
+//   Cranked <stream>: 600 tokens to <token account>, <signature>
+use std::{error::Error, thread, time::Duration};
+
+use clap::Parser;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+use client::{
+    cli,
+    profile::{self, Profile},
+    send_instructions,
+    streaming::{self, Stream, CRANK_REWARD},
+};
+
+#[derive(Parser)]
+#[command(about = "Crank the streams of the streaming program and collect the rewards")]
+struct Args {
+    /// Streaming program, taken from the cluster profile if it isn't given
+    #[arg(long)]
+    program_id: Option<Pubkey>,
+    /// Seconds between two looks for streams to crank
+    #[arg(long, default_value_t = 30)]
+    interval: u64,
+    /// Crank what is there and stop
+    #[arg(long)]
+    once: bool,
+    /// Also crank streams whose sender didn't fund the reward, the keeper pays the fee for nothing
+    #[arg(long)]
+    unpaid: bool,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long)]
+    url: Option<String>,
+    /// Keypair of the keeper, it pays the fees and gets the rewards. Taken from the cluster profile or the solana cli
+    /// config if it isn't given
+    #[arg(long)]
+    keypair: Option<String>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+    let program_id = args
+        .program_id
+        .or_else(|| profile.program_id(profile::STREAMING_PROGRAM))
+        .ok_or("--program-id is needed")?;
+    let (rpc, keeper) = cli::connect(&profile, args.url, args.keypair)?;
+
+    println!("Keeping the streams of {} as {}", program_id, keeper.pubkey());
+    loop {
+        crank_all(&rpc, keeper.as_ref(), &program_id, args.unpaid)?;
+        if args.once {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_secs(args.interval));
+    }
+}
+
+// Cranks every stream which can be cranked now. A failing crank is only printed, another keeper may have been faster.
+fn crank_all(rpc: &RpcClient, keeper: &dyn Signer, program_id: &Pubkey, unpaid: bool) -> Result<(), Box<dyn Error>> {
+    let now = streaming::cluster_time(rpc)?;
+    let rent = rpc.get_minimum_balance_for_rent_exemption(Stream::LEN)?;
+
+    for (address, stream) in streaming::streams(rpc, program_id)? {
+        if !stream.crankable(now) {
+            continue;
+        }
+        // the reward comes out of the lamports of the stream account above its rent
+        if !unpaid && rpc.get_balance(&address)?.saturating_sub(rent) < CRANK_REWARD {
+            continue;
+        }
+        let Some(destination) = streaming::recipient_token_account(rpc, &stream)? else {
+            println!("Skipping {}: the recipient {} has no account for the mint {}", address, stream.recipient, stream.mint);
+            continue;
+        };
+
+        let amount = stream.withdrawable(now);
+        let instruction = streaming::crank_instruction(program_id, &keeper.pubkey(), &address, &stream, &destination);
+        match send_instructions(rpc, keeper, &[instruction]) {
+            Ok(signature) => println!("Cranked {}: {} tokens to {}, {}", address, amount, destination, signature),
+            Err(e) => println!("Cranking {} failed: {}", address, e),
+        }
+    }
+    Ok(())
+}
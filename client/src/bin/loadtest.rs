@@ -0,0 +1,216 @@
+// Sends many transactions of the example programs at once and reports how the cluster coped, for example
+// `loadtest greet --account <PUBKEY> --count 200 --concurrency 20 --rate 50`. Every transaction writes to the same
+// state account, so the validator has to process them one after the other, which is part of what the numbers show.
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    sync::{mpsc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use solana_client::{rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::Transaction,
+};
+use solana_transaction_status::{option_serializer::OptionSerializer, UiTransactionEncoding};
+
+use client::{
+    cli::{self, Call},
+    profile::Profile,
+};
+
+#[derive(Parser)]
+#[command(about = "Load test the greeting or the calculator program")]
+struct Args {
+    #[command(subcommand)]
+    call: Call,
+    /// How many transactions are sent
+    #[arg(long, default_value_t = 100, global = true)]
+    count: usize,
+    /// How many transactions are in flight at the same time
+    #[arg(long, default_value_t = 10, global = true)]
+    concurrency: usize,
+    /// Transactions sent per second at most, as fast as the concurrency allows if it isn't given
+    #[arg(long, global = true)]
+    rate: Option<f64>,
+    /// Seconds a transaction has to be confirmed in before it counts as failed
+    #[arg(long, default_value_t = 60, global = true)]
+    timeout: u64,
+    /// State account, derived from the payer and the program id like main.py does if it isn't given
+    #[arg(long, global = true)]
+    account: Option<Pubkey>,
+    /// Program, taken from the cluster profile or read from the owner of the state account if it isn't given
+    #[arg(long, global = true)]
+    program_id: Option<Pubkey>,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long, global = true)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Keypair of the payer, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    keypair: Option<String>,
+}
+
+// how often the status of a transaction is asked for
+const POLL_INTERVAL: Duration = Duration::from_millis(400);
+
+// a blockhash is valid for about a minute, a fresh one is fetched every few seconds
+const BLOCKHASH_REFRESH: Duration = Duration::from_secs(5);
+
+// the compute unit limit of the first transaction, every next one gets one more. Transactions with the same
+// instructions and blockhash have the same signature and the node would drop all but one of them, the limit makes
+// each one different. It is far above what the programs need.
+const BASE_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+
+#[derive(Default)]
+struct Report {
+    // confirmation latencies of the transactions that succeeded
+    latencies: Vec<Duration>,
+    compute_units: Vec<u64>,
+    // how often each error happened
+    failures: BTreeMap<String, usize>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    if args.count == 0 || args.concurrency == 0 {
+        return Err("--count and --concurrency have to be at least 1".into());
+    }
+    let profile = Profile::load(args.cluster.clone())?;
+    let (rpc, payer) = cli::connect(&profile, args.url.clone(), args.keypair.clone())?;
+    let instruction = args.call.instruction(&rpc, &profile, &payer.pubkey(), args.program_id, args.account)?;
+    let timeout = Duration::from_secs(args.timeout);
+
+    println!("Sending {} transactions, {} at a time", args.count, args.concurrency);
+    let report = Mutex::new(Report::default());
+    // the signer can't be shared between threads, so the main thread signs and the workers send. The channel has no
+    // buffer, a transaction is only signed once a worker is free to send it.
+    let (sender, receiver) = mpsc::sync_channel::<Transaction>(0);
+    let receiver = Mutex::new(receiver);
+    let start = Instant::now();
+
+    thread::scope(|scope| -> Result<(), Box<dyn Error>> {
+        // the workers stop once the sender is dropped at the end of this closure
+        let sender = sender;
+        for _ in 0..args.concurrency.min(args.count) {
+            scope.spawn(|| loop {
+                let next = receiver.lock().unwrap().recv();
+                let Ok(transaction) = next else {
+                    break;
+                };
+                let result = send_one(&rpc, &transaction, timeout);
+                let mut report = report.lock().unwrap();
+                match result {
+                    Ok((latency, units)) => {
+                        report.latencies.push(latency);
+                        report.compute_units.extend(units);
+                    },
+                    Err(e) => *report.failures.entry(e).or_default() += 1,
+                }
+            });
+        }
+
+        let mut blockhash: Option<(Hash, Instant)> = None;
+        for index in 0..args.count {
+            // the rate limit is a schedule, transaction n is never sent before n / rate seconds
+            if let Some(rate) = args.rate {
+                let due = start + Duration::from_secs_f64(index as f64 / rate);
+                thread::sleep(due.saturating_duration_since(Instant::now()));
+            }
+            let hash = match blockhash {
+                Some((hash, fetched)) if fetched.elapsed() < BLOCKHASH_REFRESH => hash,
+                _ => {
+                    let hash = rpc.get_latest_blockhash()?;
+                    blockhash = Some((hash, Instant::now()));
+                    hash
+                },
+            };
+            let limit = ComputeBudgetInstruction::set_compute_unit_limit(BASE_COMPUTE_UNIT_LIMIT + index as u32);
+            let instructions = [limit, instruction.clone()];
+            sender.send(Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[&*payer], hash))?;
+        }
+        Ok(())
+    })?;
+
+    let elapsed = start.elapsed();
+    let mut report = report.into_inner()?;
+    let failed: usize = report.failures.values().sum();
+    println!(
+        "Sent:          {} in {:.1}s, {:.1} transactions per second",
+        args.count,
+        elapsed.as_secs_f64(),
+        args.count as f64 / elapsed.as_secs_f64()
+    );
+    println!("Confirmed:     {}", report.latencies.len());
+    println!("Failed:        {}", failed);
+    for (reason, count) in &report.failures {
+        println!("  {}x {}", count, reason);
+    }
+
+    report.latencies.sort();
+    if !report.latencies.is_empty() {
+        println!(
+            "Latency:       p50 {:?}, p90 {:?}, p99 {:?}, max {:?}",
+            percentile(&report.latencies, 50),
+            percentile(&report.latencies, 90),
+            percentile(&report.latencies, 99),
+            report.latencies[report.latencies.len() - 1]
+        );
+    }
+    if !report.compute_units.is_empty() {
+        let units = &report.compute_units;
+        println!(
+            "Compute units: min {}, average {}, max {}",
+            units.iter().min().unwrap(),
+            units.iter().sum::<u64>() / units.len() as u64,
+            units.iter().max().unwrap()
+        );
+    }
+    Ok(())
+}
+
+// Sends the transaction and waits for its confirmation. Returns the time that took and the compute units it used,
+// or why it failed.
+fn send_one(rpc: &RpcClient, transaction: &Transaction, timeout: Duration) -> Result<(Duration, Option<u64>), String> {
+    // no preflight, the node would simulate every transaction first and the test would measure that
+    let config = RpcSendTransactionConfig { skip_preflight: true, ..RpcSendTransactionConfig::default() };
+    let sent = Instant::now();
+    let signature = rpc.send_transaction_with_config(transaction, config).map_err(|e| e.to_string())?;
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let status = rpc
+            .get_signature_status_with_commitment(&signature, CommitmentConfig::confirmed())
+            .map_err(|e| format!("Getting the status failed: {}", e))?;
+        match status {
+            Some(Ok(())) => return Ok((sent.elapsed(), compute_units(rpc, &signature))),
+            Some(Err(e)) => return Err(e.to_string()),
+            None if sent.elapsed() >= timeout => return Err(format!("Not confirmed within {:?}", timeout)),
+            None => {},
+        }
+    }
+}
+
+// None if the node doesn't report them
+fn compute_units(rpc: &RpcClient, signature: &Signature) -> Option<u64> {
+    let transaction = rpc.get_transaction(signature, UiTransactionEncoding::Base64).ok()?;
+    match transaction.transaction.meta?.compute_units_consumed {
+        OptionSerializer::Some(units) => Some(units),
+        _ => None,
+    }
+}
+
+// the nearest-rank percentile of the sorted values
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    let rank = (sorted.len() * percent).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
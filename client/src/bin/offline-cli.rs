@@ -0,0 +1,153 @@
+// Prepares a call of the example programs on an online machine, signs it on offline ones and broadcasts it later,
+// for example:
+//
+//   offline-cli create-nonce
+//   offline-cli prepare --nonce <NONCE> --fee-payer <PUBKEY> --out add.json add 3 5 --account <PUBKEY>
+//   offline-cli sign add.json --keypair usb://ledger        (on the air-gapped machine)
+//   offline-cli broadcast add.json
+use std::{error::Error, path::PathBuf};
+
+use clap::{Parser, Subcommand};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use client::{
+    cli::{self, Call},
+    keypair,
+    offline::{self, OfflineTransaction},
+    profile::Profile,
+};
+
+#[derive(Parser)]
+#[command(about = "Sign calls of the greeting or the calculator program offline, with a durable nonce")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long, global = true)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Keypair of the payer or the signer, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    keypair: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates a nonce account paid by the payer and prints its address
+    CreateNonce {
+        /// Key that advances the nonce and has to sign every transaction using it, the payer if it isn't given
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Writes an unsigned transaction using the nonce to a file
+    Prepare {
+        #[command(subcommand)]
+        call: Call,
+        #[arg(long)]
+        nonce: Pubkey,
+        /// Pays the fee and signs first, the payer if it isn't given
+        #[arg(long)]
+        fee_payer: Option<Pubkey>,
+        #[arg(long)]
+        out: PathBuf,
+        /// State account, derived from the fee payer and the program id like main.py does if it isn't given
+        #[arg(long, global = true)]
+        account: Option<Pubkey>,
+        /// Program, taken from the cluster profile or read from the owner of the state account if it isn't given
+        #[arg(long, global = true)]
+        program_id: Option<Pubkey>,
+    },
+    /// Signs the transaction in the file with the keypair and adds the signature to it, needs no network
+    Sign { file: PathBuf },
+    /// Adds signatures made elsewhere, as <PUBKEY>=<SIGNATURE> like sign prints them
+    AddSignature {
+        file: PathBuf,
+        #[arg(required = true)]
+        signatures: Vec<String>,
+    },
+    /// Shows the signers and the signatures missing
+    Show { file: PathBuf },
+    /// Sends the signed transaction in the file
+    Broadcast { file: PathBuf },
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+
+    match args.command {
+        Command::CreateNonce { authority } => {
+            let (rpc, payer) = cli::connect(&profile, args.url, args.keypair)?;
+            let authority = authority.unwrap_or_else(|| payer.pubkey());
+            let nonce = offline::create_nonce_account(&rpc, &*payer, &authority)?;
+            println!("Nonce account: {}", nonce);
+        },
+        Command::Prepare { call, nonce, fee_payer, out, account, program_id } => {
+            let (rpc, fee_payer) = match fee_payer {
+                Some(fee_payer) => (cli::rpc_client(&profile, args.url), fee_payer),
+                None => {
+                    let (rpc, payer) = cli::connect(&profile, args.url, args.keypair)?;
+                    (rpc, payer.pubkey())
+                },
+            };
+            let instruction = call.instruction(&rpc, &profile, &fee_payer, program_id, account)?;
+            let transaction = offline::prepare(&rpc, &fee_payer, &nonce, &[instruction])?;
+            transaction.save(&out)?;
+            println!("Wrote {}", out.display());
+            print_signers(&transaction);
+        },
+        Command::Sign { file } => {
+            let mut transaction = OfflineTransaction::load(&file)?;
+            let keypair = args.keypair.ok_or("sign needs --keypair, the config isn't read offline")?;
+            let signer = keypair::signer_from_path(&keypair)?;
+            let signature = transaction.sign(&*signer)?;
+            transaction.save(&file)?;
+            println!("{}={}", signer.pubkey(), signature);
+            print_signers(&transaction);
+        },
+        Command::AddSignature { file, signatures } => {
+            let mut transaction = OfflineTransaction::load(&file)?;
+            for signature in signatures {
+                let (pubkey, signature) = parse_signature(&signature)?;
+                transaction.add_signature(pubkey, signature)?;
+            }
+            transaction.save(&file)?;
+            print_signers(&transaction);
+        },
+        Command::Show { file } => {
+            let transaction = OfflineTransaction::load(&file)?;
+            if let Some(nonce) = transaction.nonce_account() {
+                println!("Nonce account: {}", nonce);
+            }
+            println!("Nonce:         {}", transaction.message.recent_blockhash);
+            print_signers(&transaction);
+        },
+        Command::Broadcast { file } => {
+            let transaction = OfflineTransaction::load(&file)?;
+            let rpc = cli::rpc_client(&profile, args.url);
+            let signature = offline::broadcast(&rpc, &transaction)?;
+            println!("Signature: {}", signature);
+        },
+    }
+
+    Ok(())
+}
+
+fn parse_signature(text: &str) -> Result<(Pubkey, Signature), Box<dyn Error>> {
+    let (pubkey, signature) = text.split_once('=').ok_or_else(|| format!("{} isn't <PUBKEY>=<SIGNATURE>", text))?;
+    let pubkey = pubkey.parse().map_err(|e| format!("Invalid pubkey {}: {}", pubkey, e))?;
+    let signature = signature.parse().map_err(|e| format!("Invalid signature {}: {}", signature, e))?;
+    Ok((pubkey, signature))
+}
+
+fn print_signers(transaction: &OfflineTransaction) {
+    println!("Signers:");
+    for signer in transaction.required_signers() {
+        match transaction.signatures().get(signer) {
+            Some(_) => println!("  {} signed", signer),
+            None => println!("  {} missing", signer),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// Picks up the messages of the messenger program and hands them on, the off-chain half of the cross-chain messaging.
+// A message is only relayed once it is finalized, and every message is printed as one JSON line, for whatever
+// submits it to the other chain:
+//
+//   {"address":"<pubkey>","emitter":"<pubkey>","sequence":0,"nonce":42,"payload":"<base64>",
+//    "payload_hash":"<hash>","slot":1234,"timestamp":1650000000}
+//
+// The next sequence number of every emitter is kept in a state file, a restarted relayer continues where it stopped.
+use std::{collections::BTreeMap, error::Error, fs, path::Path, thread, time::Duration};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::Parser;
+use serde_json::json;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey};
+
+use client::{
+    cli, messenger,
+    profile::{self, Profile},
+};
+
+#[derive(Parser)]
+#[command(about = "Relay the finalized messages of the messenger program as JSON lines")]
+struct Args {
+    /// Messenger program, taken from the cluster profile if it isn't given
+    #[arg(long)]
+    program_id: Option<Pubkey>,
+    /// Where the next sequence number of every emitter is kept
+    #[arg(long, default_value = "relayer-state.json")]
+    state: String,
+    /// Seconds between two looks for new messages
+    #[arg(long, default_value_t = 10)]
+    interval: u64,
+    /// Relay what is there and stop
+    #[arg(long)]
+    once: bool,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long)]
+    url: Option<String>,
+}
+
+// the next sequence number of every emitter, by address
+type RelayerState = BTreeMap<String, u64>;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+    let program_id = args
+        .program_id
+        .or_else(|| profile.program_id(profile::MESSENGER_PROGRAM))
+        .ok_or("--program-id is needed")?;
+    // a message of a confirmed block may still be rolled back, the other chain would act on something that never
+    // happened. Finalized blocks can't be.
+    let rpc = RpcClient::new_with_commitment(cli::rpc_client(&profile, args.url).url(), CommitmentConfig::finalized());
+
+    let mut state = load_state(&args.state)?;
+    eprintln!("Relaying the messages of {} on {}", program_id, rpc.url());
+    loop {
+        relay(&rpc, &program_id, &mut state, &args.state)?;
+        if args.once {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_secs(args.interval));
+    }
+}
+
+// Relays the new messages of every emitter in the order of their sequence numbers. A gap, or a payload which can't
+// be found, stops the emitter until the next round, a message is never skipped.
+fn relay(rpc: &RpcClient, program_id: &Pubkey, state: &mut RelayerState, state_path: &str) -> Result<(), Box<dyn Error>> {
+    let mut stopped = Vec::new();
+    for (address, message) in messenger::messages(rpc, program_id)? {
+        let emitter = message.emitter.to_string();
+        let next = state.get(&emitter).copied().unwrap_or(0);
+        if message.sequence < next || stopped.contains(&message.emitter) {
+            continue;
+        }
+        if message.sequence > next {
+            eprintln!("Message {} of {} is missing, waiting for it", next, emitter);
+            stopped.push(message.emitter);
+            continue;
+        }
+
+        let Some(payload) = messenger::message_payload(rpc, program_id, &address, &message)? else {
+            eprintln!("The payload of message {} of {} isn't in the logs, waiting for it", next, emitter);
+            stopped.push(message.emitter);
+            continue;
+        };
+
+        let line = json!({
+            "address": address.to_string(),
+            "emitter": emitter,
+            "sequence": message.sequence,
+            "nonce": message.nonce,
+            "payload": STANDARD.encode(&payload),
+            "payload_hash": Hash::new_from_array(message.payload_hash).to_string(),
+            "slot": message.slot,
+            "timestamp": message.timestamp,
+        });
+        println!("{}", line);
+
+        // saved after every message, a crash relays at most the last message again, never one less
+        state.insert(emitter, message.sequence + 1);
+        fs::write(state_path, serde_json::to_string_pretty(state)?)?;
+    }
+    Ok(())
+}
+
+fn load_state(path: &str) -> Result<RelayerState, Box<dyn Error>> {
+    if !Path::new(path).exists() {
+        return Ok(RelayerState::new());
+    }
+    let data = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    Ok(serde_json::from_str(&data).map_err(|e| format!("Invalid state file {}: {}", path, e))?)
+}
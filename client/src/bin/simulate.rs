@@ -0,0 +1,70 @@
+// Simulates an instruction of the example programs and shows what it would do, for example
+// `simulate add 3 5 --account <PUBKEY>`. Nothing is sent, so it costs nothing and changes nothing.
+use std::error::Error;
+
+use clap::Parser;
+use solana_sdk::pubkey::Pubkey;
+
+use client::{
+    cli::{self, Call},
+    profile::Profile,
+    simulate,
+};
+
+#[derive(Parser)]
+#[command(about = "Simulate an instruction of the greeting or the calculator program")]
+struct Args {
+    #[command(subcommand)]
+    call: Call,
+    /// State account, derived from the payer and the program id like main.py does if it isn't given
+    #[arg(long, global = true)]
+    account: Option<Pubkey>,
+    /// Program, taken from the cluster profile or read from the owner of the state account if it isn't given
+    #[arg(long, global = true)]
+    program_id: Option<Pubkey>,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long, global = true)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Keypair of the payer, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    keypair: Option<String>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+    let (rpc, payer) = cli::connect(&profile, args.url, args.keypair)?;
+    let payer = payer.pubkey();
+
+    let instruction = args.call.instruction(&rpc, &profile, &payer, args.program_id, args.account)?;
+
+    let simulation = simulate::simulate(&rpc, &payer, &[instruction])?;
+
+    println!("Logs:");
+    for line in &simulation.logs {
+        println!("  {}", line);
+    }
+    match &simulation.err {
+        Some(err) => println!("Result:        failed, {}", err),
+        None => println!("Result:        success"),
+    }
+    if let Some(units) = simulation.units_consumed {
+        println!("Compute units: {}", units);
+    }
+    if let Some((program_id, data)) = &simulation.return_data {
+        println!("Return data:   {:?} from {}", data, program_id);
+        if let Ok(text) = std::str::from_utf8(data) {
+            println!("  as text:     {}", text);
+        }
+    }
+
+    println!("Accounts:");
+    for change in &simulation.accounts {
+        change.print();
+    }
+
+    Ok(())
+}
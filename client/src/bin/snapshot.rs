@@ -0,0 +1,82 @@
+// Saves the state accounts of the example programs to a JSON file and turns it into accounts for a test validator:
+//
+//   snapshot export --out demo.json
+//   snapshot account-files demo.json --dir demo-accounts
+//   solana-test-validator --bpf-program ... <printed --account arguments>
+use std::{error::Error, path::PathBuf};
+
+use clap::{Parser, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+
+use client::{
+    cli,
+    profile::{self, Profile},
+    snapshot::{self, Snapshot},
+};
+
+#[derive(Parser)]
+#[command(about = "Snapshot the accounts of the greeting and the calculator program")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long, global = true)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    url: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Writes every account of the programs to a snapshot file
+    Export {
+        #[arg(long)]
+        out: PathBuf,
+        /// Greeting program, taken from the cluster profile if it isn't given
+        #[arg(long)]
+        greeting: Option<Pubkey>,
+        /// Calculator program, taken from the cluster profile if it isn't given
+        #[arg(long)]
+        calculator: Option<Pubkey>,
+    },
+    /// Writes the accounts of a snapshot as files for solana-test-validator --account and prints the arguments
+    AccountFiles {
+        snapshot: PathBuf,
+        #[arg(long)]
+        dir: PathBuf,
+    },
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+
+    match args.command {
+        Command::Export { out, greeting, calculator } => {
+            let programs: Vec<(&str, Pubkey)> = [
+                (profile::GREETING_PROGRAM, greeting.or_else(|| profile.program_id(profile::GREETING_PROGRAM))),
+                (profile::CALCULATOR_PROGRAM, calculator.or_else(|| profile.program_id(profile::CALCULATOR_PROGRAM))),
+            ]
+            .into_iter()
+            .filter_map(|(program, id)| Some((program, id?)))
+            .collect();
+            if programs.is_empty() {
+                return Err("Either --greeting or --calculator is needed".into());
+            }
+
+            let rpc = cli::rpc_client(&profile, args.url);
+            let snapshot = snapshot::take_snapshot(&rpc, &programs)?;
+            snapshot.save(&out)?;
+            println!("Wrote {} accounts of slot {} to {}", snapshot.accounts.len(), snapshot.slot, out.display());
+        },
+        Command::AccountFiles { snapshot, dir } => {
+            let files = Snapshot::load(&snapshot)?.write_account_files(&dir)?;
+            let arguments: Vec<String> =
+                files.iter().map(|(address, path)| format!("--account {} {}", address, path.display())).collect();
+            println!("{}", arguments.join(" "));
+        },
+    }
+
+    Ok(())
+}
@@ -0,0 +1,191 @@
+// Follows the transactions of the example programs over the websocket of the cluster with logsSubscribe and prints
+// the events they log, one line per transaction. `--json` prints JSON lines instead, for piping into jq or similar:
+//
+//   {"slot":1234,"signature":"<signature>","program":"helloworld","program_id":"<id>","err":null,
+//    "events":[{"event":"greeted","counter":5}]}
+use std::{error::Error, sync::mpsc, thread};
+
+use clap::Parser;
+use serde_json::{json, Value};
+use solana_client::{
+    pubsub_client::PubsubClient,
+    rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+};
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+
+use client::{
+    cli,
+    logs::{self, CalcEvent, GreetingEvent},
+    profile::{self, Profile},
+};
+
+#[derive(Parser)]
+#[command(about = "Print the events of every transaction of the greeting and the calculator program")]
+struct Args {
+    /// Greeting program, taken from the cluster profile if it isn't given
+    #[arg(long)]
+    greeting: Option<Pubkey>,
+    /// Calculator program, taken from the cluster profile if it isn't given
+    #[arg(long)]
+    calculator: Option<Pubkey>,
+    /// Print one JSON object per transaction
+    #[arg(long)]
+    json: bool,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long)]
+    cluster: Option<String>,
+    /// RPC url, the websocket url is derived from it. Both are taken from the cluster profile or the solana cli
+    /// config if it isn't given
+    #[arg(long)]
+    url: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+enum Program {
+    Greeting,
+    Calculator,
+}
+
+impl Program {
+    fn name(self) -> &'static str {
+        match self {
+            Program::Greeting => profile::GREETING_PROGRAM,
+            Program::Calculator => profile::CALCULATOR_PROGRAM,
+        }
+    }
+
+    // the events of the transaction, as Debug for people and as JSON for tools
+    fn events(self, logs: &[String], program_id: &Pubkey) -> Vec<(String, Value)> {
+        match self {
+            Program::Greeting => logs::greeting_events(logs, program_id)
+                .into_iter()
+                .map(|event| (format!("{:?}", event), greeting_json(&event)))
+                .collect(),
+            Program::Calculator => logs::calc_events(logs, program_id)
+                .into_iter()
+                .map(|event| (format!("{:?}", event), calc_json(&event)))
+                .collect(),
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+    let programs: Vec<(Program, Pubkey)> = [
+        (Program::Greeting, args.greeting.or_else(|| profile.program_id(profile::GREETING_PROGRAM))),
+        (Program::Calculator, args.calculator.or_else(|| profile.program_id(profile::CALCULATOR_PROGRAM))),
+    ]
+    .into_iter()
+    .filter_map(|(program, id)| Some((program, id?)))
+    .collect();
+    if programs.is_empty() {
+        return Err("Either --greeting or --calculator is needed".into());
+    }
+
+    let websocket_url = cli::websocket_url(&profile, args.url);
+    let config = RpcTransactionLogsConfig { commitment: Some(CommitmentConfig::confirmed()) };
+
+    // a subscription only mentions a single address, so there is one per program, all forwarding to one channel
+    let (sender, receiver) = mpsc::channel();
+    let mut subscriptions = Vec::new();
+    for (program, program_id) in programs {
+        let filter = RpcTransactionLogsFilter::Mentions(vec![program_id.to_string()]);
+        let (subscription, notifications) = PubsubClient::logs_subscribe(&websocket_url, filter, config.clone())?;
+        // the subscription is closed when it is dropped, so it has to live as long as the loop below
+        subscriptions.push(subscription);
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for notification in notifications {
+                if sender.send((program, program_id, notification)).is_err() {
+                    break;
+                }
+            }
+        });
+        if !args.json {
+            println!("Tailing {} {} on {}", program.name(), program_id, websocket_url);
+        }
+    }
+    // the channel closes once every subscription did
+    drop(sender);
+
+    for (program, program_id, notification) in receiver {
+        let slot = notification.context.slot;
+        let transaction = notification.value;
+        let events = program.events(&transaction.logs, &program_id);
+
+        if args.json {
+            let line = json!({
+                "slot": slot,
+                "signature": transaction.signature,
+                "program": program.name(),
+                "program_id": program_id.to_string(),
+                "err": transaction.err.as_ref().map(|e| e.to_string()),
+                "events": events.into_iter().map(|(_, event)| event).collect::<Vec<_>>(),
+            });
+            println!("{}", line);
+            continue;
+        }
+
+        let result = match &transaction.err {
+            Some(e) => format!("failed, {}", e),
+            None => "success".to_string(),
+        };
+        println!("Slot {} {} {}: {}", slot, transaction.signature, program.name(), result);
+        for (event, _) in events {
+            println!("  {}", event);
+        }
+    }
+
+    Err("Subscriptions closed by the node".into())
+}
+
+fn greeting_json(event: &GreetingEvent) -> Value {
+    match event {
+        GreetingEvent::Entrypoint => json!({ "event": "entrypoint" }),
+        GreetingEvent::Greeted { counter } => json!({ "event": "greeted", "counter": counter }),
+        GreetingEvent::Initialized { account } => {
+            json!({ "event": "initialized", "account": account.to_string() })
+        },
+        GreetingEvent::PayerNotSigner => json!({ "event": "payer_not_signer" }),
+        GreetingEvent::WrongSystemProgram => json!({ "event": "wrong_system_program" }),
+        GreetingEvent::WrongGreetingAccount => json!({ "event": "wrong_greeting_account" }),
+        GreetingEvent::IncorrectProgramId => json!({ "event": "incorrect_program_id" }),
+        GreetingEvent::WrongMemoProgram => json!({ "event": "wrong_memo_program" }),
+        GreetingEvent::NotAbove { result, threshold } => {
+            json!({ "event": "not_above", "result": result, "threshold": threshold })
+        },
+        GreetingEvent::WrongCalculatorOwner => json!({ "event": "wrong_calculator_owner" }),
+        GreetingEvent::GreeterNotSigner => json!({ "event": "greeter_not_signer" }),
+        GreetingEvent::WrongAllowlist => json!({ "event": "wrong_allowlist" }),
+        GreetingEvent::NotOnAllowlist { greeter } => {
+            json!({ "event": "not_on_allowlist", "greeter": greeter.to_string() })
+        },
+    }
+}
+
+fn calc_json(event: &CalcEvent) -> Value {
+    match event {
+        CalcEvent::Invoked { program_id, accounts, data } => json!({
+            "event": "invoked",
+            "program_id": program_id.to_string(),
+            "accounts": accounts,
+            "data": data,
+        }),
+        CalcEvent::Add { a, b } => json!({ "event": "add", "a": a, "b": b }),
+        CalcEvent::Sub { a, b } => json!({ "event": "sub", "a": a, "b": b }),
+        CalcEvent::InitVault { vault, authority } => {
+            json!({ "event": "init_vault", "vault": vault.to_string(), "authority": authority.to_string() })
+        },
+        CalcEvent::Withdraw { amount } => json!({ "event": "withdraw", "amount": amount }),
+        CalcEvent::InitConfig => json!({ "event": "init_config" }),
+        CalcEvent::SetCallers { callers } => json!({ "event": "set_callers", "callers": callers }),
+        CalcEvent::IncorrectProgramId => json!({ "event": "incorrect_program_id" }),
+        CalcEvent::WrongMemoProgram => json!({ "event": "wrong_memo_program" }),
+        CalcEvent::NotAdmin { admin } => json!({ "event": "not_admin", "admin": admin.to_string() }),
+        CalcEvent::NestedCall => json!({ "event": "nested_call" }),
+        CalcEvent::CallerNotAllowed { caller } => {
+            json!({ "event": "caller_not_allowed", "caller": caller.to_string() })
+        },
+    }
+}
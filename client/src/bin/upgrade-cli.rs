@@ -0,0 +1,104 @@
+// Upgrades a deployed program from the command line: write a buffer, swap it in, manage the authority, clean up buffers
+use std::{error::Error, fs, path::PathBuf};
+
+use clap::{Parser, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+
+use client::{cli, profile::Profile, upgrade};
+
+#[derive(Parser)]
+#[command(about = "Upgrade programs deployed with the upgradeable loader")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+    /// Cluster profile, like devnet, see the README. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long, global = true)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Keypair of the payer and authority, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    keypair: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Writes the program into a new buffer and prints its address
+    WriteBuffer {
+        /// The .so file built by cargo build-sbf
+        program: PathBuf,
+    },
+    /// Replaces the program with the one in the buffer, writes a new buffer if a .so file is given instead
+    Upgrade {
+        #[arg(long)]
+        program_id: Pubkey,
+        #[arg(long, conflicts_with = "program", required_unless_present = "program")]
+        buffer: Option<Pubkey>,
+        /// The .so file built by cargo build-sbf
+        #[arg(long)]
+        program: Option<PathBuf>,
+    },
+    /// Hands the upgrade authority of the program to another key, or revokes it with --final
+    SetAuthority {
+        #[arg(long)]
+        program_id: Pubkey,
+        #[arg(long, conflicts_with = "final_", required_unless_present = "final_")]
+        new_authority: Option<Pubkey>,
+        /// Makes the program immutable, this can't be undone
+        #[arg(long = "final")]
+        final_: bool,
+    },
+    /// Closes a buffer, or every buffer of the payer with --all, and returns the lamports to the payer
+    CloseBuffer {
+        #[arg(conflicts_with = "all", required_unless_present = "all")]
+        buffer: Option<Pubkey>,
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+    let (rpc, payer) = cli::connect(&profile, args.url, args.keypair)?;
+
+    match args.command {
+        Command::WriteBuffer { program } => {
+            let buffer = upgrade::write_buffer(&rpc, &*payer, &fs::read(program)?)?;
+            println!("Buffer: {}", buffer);
+        },
+        Command::Upgrade { program_id, buffer, program } => {
+            let buffer = match (buffer, program) {
+                (Some(buffer), _) => buffer,
+                (None, Some(program)) => upgrade::write_buffer(&rpc, &*payer, &fs::read(program)?)?,
+                // clap requires one of them
+                (None, None) => unreachable!(),
+            };
+            let signature = upgrade::upgrade(&rpc, &*payer, &program_id, &buffer, &payer.pubkey())?;
+            println!("Upgraded {} with buffer {}, transaction {}", program_id, buffer, signature);
+        },
+        Command::SetAuthority { program_id, new_authority, .. } => {
+            upgrade::set_upgrade_authority(&rpc, &*payer, &program_id, new_authority.as_ref())?;
+            match new_authority {
+                Some(new_authority) => println!("Upgrade authority of {} is {}", program_id, new_authority),
+                None => println!("{} is immutable now", program_id),
+            }
+        },
+        Command::CloseBuffer { buffer, .. } => {
+            let buffers = match buffer {
+                Some(buffer) => vec![buffer],
+                None => upgrade::buffers(&rpc, &payer.pubkey())?.into_iter().map(|(buffer, _)| buffer).collect(),
+            };
+            if buffers.is_empty() {
+                println!("{} has no buffers", payer.pubkey());
+            }
+            for buffer in buffers {
+                upgrade::close_buffer(&rpc, &*payer, &buffer, &payer.pubkey())?;
+                println!("Closed buffer {}", buffer);
+            }
+        },
+    }
+
+    Ok(())
+}
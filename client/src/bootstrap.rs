@@ -0,0 +1,118 @@
+// The setup main.py does before it can call a program: the payer needs lamports, and the program needs a state
+// account it owns, big enough for its state struct and rent exempt.
+#[cfg(feature = "rpc")]
+use std::{thread, time::Duration};
+
+use borsh::BorshSerialize;
+#[cfg(feature = "rpc")]
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_client::RpcClient,
+};
+use solana_sdk::{
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::{Pubkey, PubkeyError},
+    system_instruction,
+};
+#[cfg(feature = "rpc")]
+use solana_sdk::{
+    signature::Signature,
+    signer::Signer,
+};
+
+// main.py requests 2 SOL whenever the balance is too low, so does fund_payer
+pub const AIRDROP_LAMPORTS: u64 = 2 * LAMPORTS_PER_SOL;
+
+// devnet often refuses airdrops when it is busy or the rate limit is hit, trying again a bit later usually works
+pub const AIRDROP_ATTEMPTS: u32 = 5;
+
+// what the payer keeps on top of the rent for the fees, plenty for a few transactions
+pub const FEE_RESERVE: u64 = LAMPORTS_PER_SOL / 100;
+
+// The size of the state struct once borsh packed it, which is the size the programs expect their accounts to have.
+// The state has no variable sized fields, so any value of it gives the size.
+pub fn packed_size<T: BorshSerialize>(state: &T) -> usize {
+    // serializing into a Vec can't fail
+    state.try_to_vec().unwrap().len()
+}
+
+// Requests an airdrop and waits until it is confirmed. A failed request is retried, waiting one second longer
+// after every attempt.
+#[cfg(feature = "rpc")]
+pub fn airdrop(rpc: &RpcClient, to: &Pubkey, lamports: u64) -> ClientResult<Signature> {
+    let mut attempt = 1;
+    loop {
+        match rpc.request_airdrop(to, lamports).and_then(|signature| {
+            rpc.poll_for_signature(&signature)?;
+            Ok(signature)
+        }) {
+            Ok(signature) => return Ok(signature),
+            Err(e) if attempt < AIRDROP_ATTEMPTS => {
+                eprintln!("Airdrop attempt {} failed: {}", attempt, e);
+                thread::sleep(Duration::from_secs(attempt as u64));
+                attempt += 1;
+            },
+            Err(e) => {
+                return Err(ClientError::from(ClientErrorKind::Custom(format!(
+                    "Airdrop failed {} times, last error: {}",
+                    AIRDROP_ATTEMPTS, e
+                ))))
+            }
+        }
+    }
+}
+
+// Airdrops AIRDROP_LAMPORTS to the payer if its balance is below the given lamports, like establishPayer of main.py
+#[cfg(feature = "rpc")]
+pub fn fund_payer(rpc: &RpcClient, payer: &Pubkey, lamports: u64) -> ClientResult<()> {
+    let balance = rpc.get_balance(payer)?;
+    if balance < lamports {
+        eprintln!("Balance of {} is {} lamports, requesting an airdrop", payer, balance);
+        airdrop(rpc, payer, AIRDROP_LAMPORTS.max(lamports - balance))?;
+    }
+    Ok(())
+}
+
+// Creates the account at the address derived from the payer and the seed, the way main.py derives them, and assigns
+// it to the program in the same instruction, so the program may write to it right away. The payer funds it with the
+// given lamports. Fails if the seed is longer than 32 bytes.
+pub fn create_state_account_instruction(
+    payer: &Pubkey,
+    program_id: &Pubkey,
+    seed: &str,
+    size: usize,
+    lamports: u64,
+) -> Result<Instruction, PubkeyError> {
+    let account = Pubkey::create_with_seed(payer, seed, program_id)?;
+    Ok(system_instruction::create_account_with_seed(
+        payer,
+        &account,
+        payer,
+        seed,
+        lamports,
+        size as u64,
+        program_id,
+    ))
+}
+
+// Funds the payer if needed, then creates the rent exempt state account in a single transaction and returns its
+// address
+#[cfg(feature = "rpc")]
+pub fn create_state_account(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    program_id: &Pubkey,
+    seed: &str,
+    size: usize,
+) -> ClientResult<Pubkey> {
+    let lamports = rpc.get_minimum_balance_for_rent_exemption(size)?;
+    fund_payer(rpc, &payer.pubkey(), lamports + FEE_RESERVE)?;
+
+    let instruction = create_state_account_instruction(&payer.pubkey(), program_id, seed, size, lamports)
+        .map_err(|e| crate::send::custom_error(format!("Invalid seed {}: {}", seed, e)))?;
+    crate::send_instructions(rpc, payer, &[instruction])?;
+
+    // the instruction above derived the same address already, so this can't fail
+    Ok(Pubkey::create_with_seed(&payer.pubkey(), seed, program_id).unwrap())
+}
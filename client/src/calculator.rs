@@ -0,0 +1,156 @@
+// Client of the calculator program in function_calls
+#[cfg(feature = "rpc")]
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_client::RpcClient,
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+#[cfg(feature = "rpc")]
+use solana_sdk::{signature::Signature, signer::Signer};
+
+pub use function_calls::processor::{
+    config_address, vault_address, vault_state_address, CalculatorResult, CallerConfig, VaultState, MAX_CALLERS,
+};
+
+use crate::bootstrap;
+
+// main.py derives the result account from the payer with this seed
+pub const CALCULATOR_SEED: &str = "calculator_program_seed";
+
+pub fn calculator_address(payer: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    // the seed is short enough and the program id no PDA marker, so this can't fail
+    Pubkey::create_with_seed(payer, CALCULATOR_SEED, program_id).unwrap()
+}
+
+// The program expects the account to be exactly as big as a borsh packed CalculatorResult
+pub fn calculator_result_size() -> usize {
+    bootstrap::packed_size(&CalculatorResult { result: 0, a: 0, b: 0 })
+}
+
+// Creates the rent exempt result account at the address main.py uses, after an airdrop if the payer can't pay for it,
+// and returns its address
+#[cfg(feature = "rpc")]
+pub fn create_result_account(rpc: &RpcClient, payer: &dyn Signer, program_id: &Pubkey) -> ClientResult<Pubkey> {
+    bootstrap::create_state_account(rpc, payer, program_id, CALCULATOR_SEED, calculator_result_size())
+}
+
+// add_instruction, sub_instruction, init_vault_instruction, withdraw_instruction, init_config_instruction and
+// set_callers_instruction, generated by build.rs from the Instruction enum of function_calls. The data is packed by
+// the program crate itself, so it always matches what Instruction::unpack reads.
+include!(concat!(env!("OUT_DIR"), "/calculator_instructions.rs"));
+
+// Creates the vault state of the authority, which lets the program sign for the vault of the authority
+pub fn init_vault_pda_instruction(authority: &Pubkey, program_id: &Pubkey) -> Instruction {
+    let (vault_state, _) = vault_state_address(authority, program_id);
+    init_vault_instruction(program_id, authority, &vault_state, &system_program::id())
+}
+
+// Transfers lamports out of the vault of the authority, the authority has to sign
+pub fn withdraw_from_vault_instruction(
+    authority: &Pubkey,
+    program_id: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (vault_state, _) = vault_state_address(authority, program_id);
+    let (vault, _) = vault_address(authority, program_id);
+    withdraw_instruction(program_id, authority, &vault_state, &vault, recipient, &system_program::id(), amount)
+}
+
+// Creates the caller config of the program with the admin as the one who may change the callers
+pub fn init_config_pda_instruction(admin: &Pubkey, program_id: &Pubkey) -> Instruction {
+    let (config, _) = config_address(program_id);
+    init_config_instruction(program_id, admin, &config, &system_program::id())
+}
+
+// Replaces the programs a build with the caller-allowlist feature accepts add and sub from through a CPI
+pub fn set_callers_pda_instruction(admin: &Pubkey, program_id: &Pubkey, callers: Vec<Pubkey>) -> Instruction {
+    let (config, _) = config_address(program_id);
+    set_callers_instruction(program_id, admin, &config, callers)
+}
+
+// The add or sub instruction with the accounts a build with the caller-allowlist feature reads, the config and the
+// Instructions sysvar right after the result account. Apply it before with_memo, the memo program comes last.
+pub fn with_caller_accounts(mut instruction: Instruction) -> Instruction {
+    let (config, _) = config_address(&instruction.program_id);
+    instruction.accounts.insert(1, AccountMeta::new_readonly(config, false));
+    instruction.accounts.insert(2, AccountMeta::new_readonly(sysvar::instructions::id(), false));
+    instruction
+}
+
+// Sets up the vault of the payer and returns its address. Lamports are deposited with a plain transfer to it.
+#[cfg(feature = "rpc")]
+pub fn init_vault(rpc: &RpcClient, payer: &dyn Signer, program_id: &Pubkey) -> ClientResult<Pubkey> {
+    crate::send_instructions(rpc, payer, &[init_vault_pda_instruction(&payer.pubkey(), program_id)])?;
+    Ok(vault_address(&payer.pubkey(), program_id).0)
+}
+
+#[cfg(feature = "rpc")]
+pub fn withdraw_from_vault(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    program_id: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+) -> ClientResult<Signature> {
+    let instruction = withdraw_from_vault_instruction(&payer.pubkey(), program_id, recipient, amount);
+    crate::send_instructions(rpc, payer, &[instruction])
+}
+
+#[cfg(feature = "rpc")]
+pub fn calculator_add(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    program_id: &Pubkey,
+    result_account: &Pubkey,
+    a: u64,
+    b: u64,
+) -> ClientResult<Signature> {
+    crate::send_instructions(rpc, payer, &[add_instruction(program_id, result_account, a, b)])
+}
+
+#[cfg(feature = "rpc")]
+pub fn calculator_sub(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    program_id: &Pubkey,
+    result_account: &Pubkey,
+    a: u64,
+    b: u64,
+) -> ClientResult<Signature> {
+    crate::send_instructions(rpc, payer, &[sub_instruction(program_id, result_account, a, b)])
+}
+
+#[cfg(feature = "rpc")]
+pub fn get_result(rpc: &RpcClient, result_account: &Pubkey) -> ClientResult<CalculatorResult> {
+    crate::get_state(rpc, result_account)
+}
+
+// Every result account of the program
+#[cfg(feature = "rpc")]
+pub fn fetch_all_results(rpc: &RpcClient, program_id: &Pubkey) -> ClientResult<Vec<(Pubkey, CalculatorResult)>> {
+    crate::get_program_states(rpc, program_id, calculator_result_size(), Vec::new())
+}
+
+// The result accounts whose last calculation used these operands. borsh packs the fields in order, result at
+// offset 0, a at 8 and b at 16, each 8 bytes little endian.
+#[cfg(feature = "rpc")]
+pub fn fetch_results_with_operands(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    a: Option<u64>,
+    b: Option<u64>,
+) -> ClientResult<Vec<(Pubkey, CalculatorResult)>> {
+    let filters = [(8, a), (16, b)]
+        .into_iter()
+        .filter_map(|(offset, operand)| {
+            operand.map(|operand| RpcFilterType::Memcmp(Memcmp::new_raw_bytes(offset, operand.to_le_bytes().to_vec())))
+        })
+        .collect();
+    crate::get_program_states(rpc, program_id, calculator_result_size(), filters)
+}
@@ -0,0 +1,130 @@
+// Shared by the binaries. They find the cluster and the payer the same way main.py does, in the config of the
+// solana cli, unless they are given on the command line or by the cluster profile, see profile.rs.
+use std::error::Error;
+
+use clap::Subcommand;
+use solana_cli_config::{Config, CONFIG_FILE};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::Signer,
+};
+
+use crate::{
+    calculator, greeting, keypair,
+    profile::{self, Profile},
+};
+
+pub fn connect(
+    profile: &Profile,
+    url: Option<String>,
+    keypair_path: Option<String>,
+) -> Result<(RpcClient, Box<dyn Signer>), Box<dyn Error>> {
+    // a key file, a Ledger or anything else keypair::signer_from_path knows
+    let keypair_path = keypair_path
+        .or_else(|| profile.keypair.clone())
+        .unwrap_or_else(|| load_config().keypair_path);
+    let payer = keypair::signer_from_path(&keypair_path)?;
+
+    Ok((rpc_client(profile, url), payer))
+}
+
+// For the binaries that only read accounts and don't need a payer
+pub fn rpc_client(profile: &Profile, url: Option<String>) -> RpcClient {
+    let url = url.or_else(|| profile.url.clone()).unwrap_or_else(|| load_config().json_rpc_url);
+    RpcClient::new_with_commitment(url, CommitmentConfig::confirmed())
+}
+
+// The websocket of the cluster, for subscriptions. Like the solana cli it is derived from the RPC url, unless the
+// profile or the config sets one.
+pub fn websocket_url(profile: &Profile, url: Option<String>) -> String {
+    if let Some(url) = url {
+        return Config::compute_websocket_url(&url);
+    }
+    match (&profile.websocket_url, &profile.url) {
+        (Some(websocket_url), _) => return websocket_url.clone(),
+        (None, Some(url)) => return Config::compute_websocket_url(url),
+        (None, None) => {},
+    }
+    let config = load_config();
+    if config.websocket_url.is_empty() {
+        Config::compute_websocket_url(&config.json_rpc_url)
+    } else {
+        config.websocket_url
+    }
+}
+
+fn load_config() -> Config {
+    // without a config file the defaults are localhost and ~/.config/solana/id.json
+    CONFIG_FILE
+        .as_ref()
+        .and_then(|path| Config::load(path).ok())
+        .unwrap_or_default()
+}
+
+// The binaries need the program id and the state account. Either can be found with the other one: the program owns
+// the account, and the account is derived from the program id and the payer.
+pub fn program_and_account(
+    rpc: &RpcClient,
+    program_id: Option<Pubkey>,
+    account: Option<Pubkey>,
+    derive_account: impl Fn(&Pubkey) -> Pubkey,
+) -> Result<(Pubkey, Pubkey), Box<dyn Error>> {
+    Ok(match (program_id, account) {
+        (Some(program_id), Some(account)) => (program_id, account),
+        (Some(program_id), None) => (program_id, derive_account(&program_id)),
+        (None, Some(account)) => (rpc.get_account(&account)?.owner, account),
+        (None, None) => return Err("Either --account or --program-id is needed".into()),
+    })
+}
+
+// The calls of the example programs, for the binaries which take any of them as subcommand
+#[derive(Subcommand, Clone, Copy)]
+pub enum Call {
+    /// Greets the greeting account
+    Greet,
+    /// Stores a + b in the result account of the calculator
+    Add { a: u64, b: u64 },
+    /// Stores a - b in the result account of the calculator
+    Sub { a: u64, b: u64 },
+}
+
+// add and sub only differ in the builder
+type Calculate = fn(&Pubkey, &Pubkey, u64, u64) -> Instruction;
+
+impl Call {
+    // The instruction of the call. The program id falls back to the profile, the account is derived from the payer
+    // like main.py does, see program_and_account.
+    pub fn instruction(
+        self,
+        rpc: &RpcClient,
+        profile: &Profile,
+        payer: &Pubkey,
+        program_id: Option<Pubkey>,
+        account: Option<Pubkey>,
+    ) -> Result<Instruction, Box<dyn Error>> {
+        let calculation: Option<(Calculate, _, _)> = match self {
+            Call::Greet => None,
+            Call::Add { a, b } => Some((calculator::add_instruction, a, b)),
+            Call::Sub { a, b } => Some((calculator::sub_instruction, a, b)),
+        };
+        Ok(match calculation {
+            None => {
+                let program_id = program_id.or_else(|| profile.program_id(profile::GREETING_PROGRAM));
+                let (program_id, account) = program_and_account(rpc, program_id, account, |program_id| {
+                    greeting::greeting_address(payer, program_id)
+                })?;
+                greeting::greet_instruction(&program_id, &account)
+            },
+            Some((calculate, a, b)) => {
+                let program_id = program_id.or_else(|| profile.program_id(profile::CALCULATOR_PROGRAM));
+                let (program_id, account) = program_and_account(rpc, program_id, account, |program_id| {
+                    calculator::calculator_address(payer, program_id)
+                })?;
+                calculate(&program_id, &account, a, b)
+            },
+        })
+    }
+}
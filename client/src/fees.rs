@@ -0,0 +1,123 @@
+// What a transaction will cost before it is sent. The cost has three parts:
+//  * the base fee, 5000 lamports per signature, from getFeeForMessage
+//  * the priority fee, compute unit price times compute unit limit. Nobody has to pay one, but when blocks are full
+//  validators pick the transactions paying more. getRecentPrioritizationFees tells what recent transactions writing
+//  to the same accounts paid
+//  * the rent of new accounts, which isn't a fee, closing an account returns it
+use solana_client::{client_error::Result as ClientResult, rpc_client::RpcClient};
+use solana_sdk::{
+    compute_budget, instruction::Instruction, message::Message, native_token::lamports_to_sol, pubkey::Pubkey,
+};
+
+use crate::send::MAX_COMPUTE_UNIT_LIMIT;
+
+// the units the runtime allows an instruction without a SetComputeUnitLimit instruction
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u32 = 200_000;
+
+// the percentiles of the recent priority fees the estimate shows
+pub const PRIORITY_FEE_PERCENTILES: &[u8] = &[25, 50, 75, 90];
+
+#[derive(Debug)]
+pub struct FeeEstimate {
+    pub base_fee: u64,
+    // the units the priority fee is paid for, the limit the transaction sets or the default one
+    pub compute_unit_limit: u32,
+    // compute unit prices in micro lamports, by percentile, of the recent blocks. Zero if nobody paid one.
+    pub priority_fees: Vec<(u8, u64)>,
+    // lamports the new accounts need to be rent exempt
+    pub rent: u64,
+}
+
+impl FeeEstimate {
+    // The priority fee in lamports at the price in micro lamports per unit
+    pub fn priority_fee(&self, micro_lamports: u64) -> u64 {
+        (micro_lamports as u128 * self.compute_unit_limit as u128).div_ceil(1_000_000) as u64
+    }
+
+    // Everything the payer needs, the rent included
+    pub fn total(&self, micro_lamports: u64) -> u64 {
+        self.base_fee + self.priority_fee(micro_lamports) + self.rent
+    }
+
+    pub fn print(&self) {
+        println!("Estimated cost:");
+        println!("  base fee:     {} lamports", self.base_fee);
+        for (percentile, micro_lamports) in &self.priority_fees {
+            println!(
+                "  priority p{}: {} lamports, {} micro lamports per unit for {} units",
+                percentile,
+                self.priority_fee(*micro_lamports),
+                micro_lamports,
+                self.compute_unit_limit
+            );
+        }
+        if self.rent > 0 {
+            println!("  rent:         {} lamports, returned when the accounts are closed", self.rent);
+        }
+        let total = self.total(0);
+        println!("  total:        {} lamports ({} SOL) without a priority fee", total, lamports_to_sol(total));
+    }
+}
+
+// Estimates the cost of sending the instructions in a transaction paid by the payer, which creates accounts of the
+// given sizes. The instructions aren't sent or simulated.
+pub fn estimate_fees(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    new_account_sizes: &[usize],
+) -> ClientResult<FeeEstimate> {
+    let mut message = Message::new(instructions, Some(payer));
+    message.recent_blockhash = rpc.get_latest_blockhash()?;
+    let base_fee = rpc.get_fee_for_message(&message)?;
+
+    // the fees paid for writing to the same accounts, the ones the transaction locks for writing
+    let writable: Vec<Pubkey> = message
+        .account_keys
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| message.is_writable(*index))
+        .map(|(_, address)| *address)
+        .collect();
+    let mut recent: Vec<u64> =
+        rpc.get_recent_prioritization_fees(&writable)?.iter().map(|fee| fee.prioritization_fee).collect();
+    recent.sort_unstable();
+    let priority_fees =
+        PRIORITY_FEE_PERCENTILES.iter().map(|percentile| (*percentile, percentile_of(&recent, *percentile))).collect();
+
+    let mut rent = 0;
+    for size in new_account_sizes {
+        rent += rpc.get_minimum_balance_for_rent_exemption(*size)?;
+    }
+
+    Ok(FeeEstimate { base_fee, compute_unit_limit: compute_unit_limit(instructions), priority_fees, rent })
+}
+
+// The limit of the SetComputeUnitLimit instruction, else what the runtime allows the other instructions
+fn compute_unit_limit(instructions: &[Instruction]) -> u32 {
+    let mut limit = None;
+    let mut others = 0;
+    for instruction in instructions {
+        if !compute_budget::check_id(&instruction.program_id) {
+            others += 1;
+            continue;
+        }
+        // SetComputeUnitLimit is variant 2, followed by the units as u32
+        if let [2, units @ ..] = instruction.data.as_slice() {
+            if let Ok(units) = <[u8; 4]>::try_from(units) {
+                limit = Some(u32::from_le_bytes(units));
+            }
+        }
+    }
+    limit.unwrap_or(others * DEFAULT_INSTRUCTION_COMPUTE_UNITS).min(MAX_COMPUTE_UNIT_LIMIT)
+}
+
+// the nearest-rank percentile of the sorted values, zero without values
+fn percentile_of(sorted: &[u64], percentile: u8) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (sorted.len() * percentile as usize).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
@@ -0,0 +1,95 @@
+// Client of the greeting program in from_scratch
+#[cfg(feature = "rpc")]
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_client::RpcClient,
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+#[cfg(feature = "rpc")]
+use solana_sdk::{signature::Signature, signer::Signer};
+
+use crate::bootstrap;
+
+pub use helloworld::{greeting_pda, GreetingAccount};
+
+// main.py derives the greeting account from the payer with this seed
+pub const GREETING_SEED: &str = "hello";
+
+pub fn greeting_address(payer: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    // the seed is short enough and the program id no PDA marker, so this can't fail
+    Pubkey::create_with_seed(payer, GREETING_SEED, program_id).unwrap()
+}
+
+// The program expects the account to be exactly as big as a borsh packed GreetingAccount
+pub fn greeting_account_size() -> usize {
+    bootstrap::packed_size(&GreetingAccount { counter: 0 })
+}
+
+// Creates the greeting account at the address main.py uses, owned by the program, so the program may write to it.
+// The payer funds it with the given lamports.
+pub fn create_greeting_account_instruction(payer: &Pubkey, program_id: &Pubkey, lamports: u64) -> Instruction {
+    // GREETING_SEED is a valid seed, so this can't fail
+    bootstrap::create_state_account_instruction(payer, program_id, GREETING_SEED, greeting_account_size(), lamports)
+        .unwrap()
+}
+
+// Creates the rent exempt greeting account, after an airdrop if the payer can't pay for it, and returns its address
+#[cfg(feature = "rpc")]
+pub fn create_greeting_account(rpc: &RpcClient, payer: &dyn Signer, program_id: &Pubkey) -> ClientResult<Pubkey> {
+    bootstrap::create_state_account(rpc, payer, program_id, GREETING_SEED, greeting_account_size())
+}
+
+// greet_instruction, initialize_instruction and greet_above_instruction, generated by build.rs from GreetingInstruction
+include!(concat!(env!("OUT_DIR"), "/greeting_instructions.rs"));
+
+// Lets the program create the greeting PDA of the payer, which works out the size and the rent itself
+pub fn initialize_pda_instruction(payer: &Pubkey, program_id: &Pubkey) -> Instruction {
+    let (account, _) = greeting_pda(payer, program_id);
+    initialize_instruction(program_id, payer, &account, &solana_sdk::system_program::id())
+}
+
+// Creates the greeting PDA of the payer through the program and returns its address
+#[cfg(feature = "rpc")]
+pub fn initialize_greeting(rpc: &RpcClient, payer: &dyn Signer, program_id: &Pubkey) -> ClientResult<Pubkey> {
+    crate::send_instructions(rpc, payer, &[initialize_pda_instruction(&payer.pubkey(), program_id)])?;
+    Ok(greeting_pda(&payer.pubkey(), program_id).0)
+}
+
+#[cfg(feature = "rpc")]
+pub fn greet(rpc: &RpcClient, payer: &dyn Signer, program_id: &Pubkey, greeting_account: &Pubkey) -> ClientResult<Signature> {
+    crate::send_instructions(rpc, payer, &[greet_instruction(program_id, greeting_account)])
+}
+
+#[cfg(feature = "rpc")]
+pub fn get_greeting(rpc: &RpcClient, greeting_account: &Pubkey) -> ClientResult<GreetingAccount> {
+    crate::get_state(rpc, greeting_account)
+}
+
+// Every greeting account of the program, no matter who created it
+#[cfg(feature = "rpc")]
+pub fn fetch_all_greeting_accounts(rpc: &RpcClient, program_id: &Pubkey) -> ClientResult<Vec<(Pubkey, GreetingAccount)>> {
+    crate::get_program_states(rpc, program_id, greeting_account_size(), Vec::new())
+}
+
+// The greeting accounts greeted exactly that often, the counter is the only field, at offset 0
+#[cfg(feature = "rpc")]
+pub fn fetch_greeting_accounts_with_counter(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    counter: u32,
+) -> ClientResult<Vec<(Pubkey, GreetingAccount)>> {
+    let filter = RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, counter.to_le_bytes().to_vec()));
+    crate::get_program_states(rpc, program_id, greeting_account_size(), vec![filter])
+}
+
+// The counter of the account a user created at the address main.py uses. The account doesn't store its creator,
+// so no filter can find it, it is derived like main.py does. None if the user has no greeting account yet.
+#[cfg(feature = "rpc")]
+pub fn fetch_user_counter(rpc: &RpcClient, program_id: &Pubkey, user: &Pubkey) -> ClientResult<Option<u32>> {
+    let account = greeting_address(user, program_id);
+    match rpc.get_account_with_commitment(&account, rpc.commitment())?.value {
+        Some(_) => Ok(Some(get_greeting(rpc, &account)?.counter)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,177 @@
+// The audit trail of a state account, rebuilt from the chain. getSignaturesForAddress lists every transaction that
+// used the account, newest first, and each transaction is fetched and its instructions to the program decoded. Only
+// the instructions of the transaction itself are decoded, a program calling the example programs through a CPI
+// shows up without calls.
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
+    rpc_config::RpcTransactionConfig,
+};
+use solana_sdk::{
+    clock::{Slot, UnixTimestamp},
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::TransactionError,
+};
+use solana_transaction_status::{option_serializer::OptionSerializer, UiTransactionEncoding};
+
+use crate::{send, state::ProgramState};
+
+// the most signatures getSignaturesForAddress returns at once
+const SIGNATURES_PER_PAGE: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Program {
+    Greeting,
+    Calculator,
+}
+
+impl Program {
+    // The program a state account belongs to
+    pub fn of_state(state: &ProgramState) -> Self {
+        match state {
+            ProgramState::Greeting(_) => Program::Greeting,
+            ProgramState::Calculator(_) => Program::Calculator,
+        }
+    }
+}
+
+// An instruction of one of the example programs
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProgramCall {
+    Greet,
+    // the greeting program created the account
+    Initialize,
+    GreetAbove { threshold: u64 },
+    Add { a: u64, b: u64 },
+    Sub { a: u64, b: u64 },
+    InitVault,
+    Withdraw { amount: u64 },
+    InitConfig,
+    SetCallers { callers: Vec<Pubkey> },
+    // data the program can't unpack, the transaction failed
+    Invalid(Vec<u8>),
+}
+
+impl ProgramCall {
+    pub fn decode(program: Program, data: &[u8]) -> Self {
+        use function_calls::instruction::Instruction;
+        use helloworld::GreetingInstruction;
+
+        match program {
+            // any data the greeting program doesn't know greets
+            Program::Greeting => match GreetingInstruction::unpack(data) {
+                Ok(GreetingInstruction::Greet) => ProgramCall::Greet,
+                Ok(GreetingInstruction::Initialize) => ProgramCall::Initialize,
+                Ok(GreetingInstruction::GreetAbove { threshold }) => ProgramCall::GreetAbove { threshold },
+                Err(_) => ProgramCall::Invalid(data.to_vec()),
+            },
+            Program::Calculator => match Instruction::unpack(data) {
+                Ok(Instruction::Add { a, b }) => ProgramCall::Add { a, b },
+                Ok(Instruction::Sub { a, b }) => ProgramCall::Sub { a, b },
+                Ok(Instruction::InitVault) => ProgramCall::InitVault,
+                Ok(Instruction::Withdraw { amount }) => ProgramCall::Withdraw { amount },
+                Ok(Instruction::InitConfig) => ProgramCall::InitConfig,
+                Ok(Instruction::SetCallers { callers }) => ProgramCall::SetCallers { callers },
+                Err(_) => ProgramCall::Invalid(data.to_vec()),
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HistoryEntry {
+    pub signature: Signature,
+    pub slot: Slot,
+    pub block_time: Option<UnixTimestamp>,
+    pub err: Option<TransactionError>,
+    // the instructions of the program to the account, in the order of the transaction
+    pub calls: Vec<ProgramCall>,
+}
+
+// The last limit transactions of the account, oldest first. The program is the one owning the account.
+pub fn account_history(
+    rpc: &RpcClient,
+    account: &Pubkey,
+    program_id: &Pubkey,
+    program: Program,
+    limit: usize,
+) -> ClientResult<Vec<HistoryEntry>> {
+    let mut statuses = Vec::new();
+    let mut before = None;
+    while statuses.len() < limit {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            limit: Some((limit - statuses.len()).min(SIGNATURES_PER_PAGE)),
+            commitment: Some(CommitmentConfig::confirmed()),
+            ..GetConfirmedSignaturesForAddress2Config::default()
+        };
+        let page = rpc.get_signatures_for_address_with_config(account, config)?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = Some(parse_signature(&last.signature)?);
+        statuses.extend(page);
+    }
+
+    let mut entries = Vec::new();
+    for status in statuses.into_iter().rev() {
+        let signature = parse_signature(&status.signature)?;
+        entries.push(HistoryEntry {
+            signature,
+            slot: status.slot,
+            block_time: status.block_time,
+            err: status.err,
+            calls: transaction_calls(rpc, &signature, account, program_id, program)?,
+        });
+    }
+    Ok(entries)
+}
+
+// The instructions of the transaction to the program which include the account
+fn transaction_calls(
+    rpc: &RpcClient,
+    signature: &Signature,
+    account: &Pubkey,
+    program_id: &Pubkey,
+    program: Program,
+) -> ClientResult<Vec<ProgramCall>> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Base64),
+        commitment: Some(CommitmentConfig::confirmed()),
+        // v0 transactions, like the ones of lookup_table, are refused without it
+        max_supported_transaction_version: Some(0),
+    };
+    let fetched = rpc.get_transaction_with_config(signature, config)?;
+    let transaction = fetched
+        .transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| send::custom_error(format!("Transaction {} can't be decoded", signature)))?;
+
+    // the accounts of a v0 transaction continue with the ones loaded from lookup tables
+    let mut keys = transaction.message.static_account_keys().to_vec();
+    if let Some(OptionSerializer::Some(loaded)) = fetched.transaction.meta.map(|meta| meta.loaded_addresses) {
+        for address in loaded.writable.iter().chain(&loaded.readonly) {
+            let address = address
+                .parse()
+                .map_err(|e| send::custom_error(format!("Invalid loaded address {}: {}", address, e)))?;
+            keys.push(address);
+        }
+    }
+
+    let key = |index: u8| keys.get(index as usize);
+    Ok(transaction
+        .message
+        .instructions()
+        .iter()
+        .filter(|instruction| key(instruction.program_id_index) == Some(program_id))
+        .filter(|instruction| instruction.accounts.iter().any(|index| key(*index) == Some(account)))
+        .map(|instruction| ProgramCall::decode(program, &instruction.data))
+        .collect())
+}
+
+fn parse_signature(signature: &str) -> ClientResult<Signature> {
+    signature.parse().map_err(|e| send::custom_error(format!("Invalid signature {}: {}", signature, e)))
+}
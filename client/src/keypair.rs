@@ -0,0 +1,105 @@
+// Where the binaries and tests get their keys from. The solana cli and main.py only read JSON key files, these
+// helpers also take seed phrases, keys in environment variables and Ledger hardware wallets. Everything that only
+// signs, instead of exposing the secret key, is returned as a Signer, which is all the client needs.
+use std::{env, error::Error};
+
+use solana_clap_utils::keypair::keypair_from_seed_phrase as prompt_seed_phrase;
+use solana_remote_wallet::{locator::Locator, remote_keypair::generate_remote_keypair, remote_wallet};
+use solana_sdk::{
+    bs58,
+    derivation_path::DerivationPath,
+    signer::{
+        keypair::{
+            generate_seed_from_seed_phrase_and_passphrase, keypair_from_seed, keypair_from_seed_and_derivation_path,
+            keypair_from_seed_phrase_and_passphrase, read_keypair, read_keypair_file, Keypair,
+        },
+        Signer,
+    },
+};
+
+// the name the prompts and the wallet show for the key
+const KEYPAIR_NAME: &str = "payer";
+
+// A JSON key file written by solana-keygen, an array of the 64 bytes of the secret and the public key
+pub fn keypair_from_file(path: &str) -> Result<Keypair, Box<dyn Error>> {
+    read_keypair_file(path).map_err(|e| format!("Failed to read keypair from {}: {}", path, e).into())
+}
+
+// The key of a seed phrase. Without a derivation path it is the key `solana-keygen recover` prints, wallets like
+// Phantom derive theirs with m/44'/501'/0'/0', DerivationPath::new_bip44(Some(0), Some(0)). The words aren't checked
+// against the BIP39 word list.
+pub fn keypair_from_seed_phrase(
+    seed_phrase: &str,
+    passphrase: &str,
+    derivation_path: Option<DerivationPath>,
+) -> Result<Keypair, Box<dyn Error>> {
+    match derivation_path {
+        Some(derivation_path) => {
+            let seed = generate_seed_from_seed_phrase_and_passphrase(seed_phrase, passphrase);
+            keypair_from_seed_and_derivation_path(&seed, Some(derivation_path))
+        },
+        None => keypair_from_seed_phrase_and_passphrase(seed_phrase, passphrase),
+    }
+}
+
+// A key kept in an environment variable, the way CI hands out secrets. Either the content of a JSON key file or the
+// base58 encoded 64 bytes wallets export.
+pub fn keypair_from_env(variable: &str) -> Result<Keypair, Box<dyn Error>> {
+    let value = env::var(variable).map_err(|e| format!("Failed to read {}: {}", variable, e))?;
+    let value = value.trim();
+    if value.starts_with('[') {
+        return read_keypair(&mut value.as_bytes()).map_err(|e| format!("Invalid keypair in {}: {}", variable, e).into());
+    }
+    let bytes = bs58::decode(value).into_vec().map_err(|e| format!("Invalid base58 in {}: {}", variable, e))?;
+    Keypair::from_bytes(&bytes).map_err(|e| format!("Invalid keypair in {}: {}", variable, e).into())
+}
+
+// A new random key, for tests and demos which need a fresh payer or account and throw it away afterwards
+pub fn throwaway_keypair() -> Keypair {
+    Keypair::new()
+}
+
+// The same key for the same seed every run, for tests which need stable addresses. Never fund such a key on a real
+// cluster, anybody can derive it.
+pub fn test_keypair(seed: u8) -> Keypair {
+    // 32 bytes are a valid seed, so this can't fail
+    keypair_from_seed(&[seed; 32]).unwrap()
+}
+
+// Resolves a keypair argument of the binaries, like the solana cli does:
+//  * usb://ledger, usb://ledger?key=1 or usb://ledger/<wallet>?key=0/1, a key of a Ledger, needs the ledger feature
+//  * prompt://, asks for a seed phrase, prompt://?key=0/0 derives the key like a wallet
+//  * env://<VARIABLE>, a key in an environment variable, see keypair_from_env
+//  * anything else is a path of a JSON key file
+pub fn signer_from_path(path: &str) -> Result<Box<dyn Signer>, Box<dyn Error>> {
+    if path.starts_with("usb://") {
+        return remote_signer(path);
+    }
+    if let Some(query) = path.strip_prefix("prompt://") {
+        let derivation_path = match query.strip_prefix("?key=") {
+            Some(key) => Some(DerivationPath::from_key_str(key)?),
+            None if query.is_empty() => None,
+            None => return Err(format!("Invalid query in {}, only ?key= is supported", path).into()),
+        };
+        // a legacy key unless a derivation path is given, like solana-keygen recover
+        let keypair = prompt_seed_phrase(KEYPAIR_NAME, false, true, derivation_path.clone(), derivation_path.is_none())?;
+        return Ok(Box::new(keypair));
+    }
+    if let Some(variable) = path.strip_prefix("env://") {
+        return Ok(Box::new(keypair_from_env(variable)?));
+    }
+    Ok(Box::new(keypair_from_file(path)?))
+}
+
+// The Ledger signs every transaction after the user confirmed it on the device, the key never leaves it
+fn remote_signer(path: &str) -> Result<Box<dyn Signer>, Box<dyn Error>> {
+    if cfg!(not(feature = "ledger")) {
+        return Err(format!("{} needs a build with the ledger feature", path).into());
+    }
+    let uri = uriparse::URIReference::try_from(path)?;
+    let locator = Locator::new_from_uri(&uri)?;
+    let derivation_path = DerivationPath::from_uri_key_query(&uri)?.unwrap_or_default();
+    let wallet_manager = remote_wallet::maybe_wallet_manager()?.ok_or("No hardware wallet found, is it connected?")?;
+    let keypair = generate_remote_keypair(locator, derivation_path, &wallet_manager, false, KEYPAIR_NAME)?;
+    Ok(Box::new(keypair))
+}
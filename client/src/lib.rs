@@ -0,0 +1,100 @@
+// Rust counterpart of the main.py scripts. Every program gets a module with functions that build its
+// instructions, with the accounts in the order the program expects them, and functions that send them.
+
+// Without the rpc feature only the builders, the state structs and the log parsing are compiled, they don't need a
+// node and build for wasm32-unknown-unknown as well.
+
+// every RpcClient call returns the large ClientError, there is no point in boxing it here
+#![allow(clippy::result_large_err)]
+
+pub mod bootstrap;
+pub mod calculator;
+#[cfg(feature = "rpc")]
+pub mod cli;
+#[cfg(feature = "rpc")]
+pub mod fees;
+pub mod greeting;
+#[cfg(feature = "rpc")]
+pub mod history;
+#[cfg(feature = "rpc")]
+pub mod keypair;
+#[cfg(feature = "rpc")]
+pub mod localnet;
+pub mod logs;
+pub mod memo;
+pub mod messenger;
+#[cfg(feature = "rpc")]
+pub mod offline;
+#[cfg(feature = "rpc")]
+pub mod lookup_table;
+#[cfg(feature = "rpc")]
+pub mod profile;
+#[cfg(feature = "rpc")]
+pub mod send;
+#[cfg(feature = "rpc")]
+pub mod simulate;
+#[cfg(feature = "rpc")]
+pub mod snapshot;
+pub mod state;
+pub mod streaming;
+pub mod tx_builder;
+#[cfg(feature = "rpc")]
+pub mod upgrade;
+
+#[cfg(feature = "rpc")]
+use solana_account_decoder::UiAccountEncoding;
+#[cfg(feature = "rpc")]
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::RpcFilterType,
+};
+#[cfg(feature = "rpc")]
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+};
+
+// Sends the instructions in a single transaction paid and signed by the payer, and waits for the confirmation. An
+// expired transaction is sent again with a new blockhash, see send::SendOptions.
+#[cfg(feature = "rpc")]
+pub fn send_instructions(rpc: &RpcClient, payer: &dyn Signer, instructions: &[Instruction]) -> ClientResult<Signature> {
+    send::send_with_options(rpc, payer, instructions, &send::SendOptions::default())
+}
+
+// Reads an account and deserializes its data with borsh. A missing account is an error.
+#[cfg(feature = "rpc")]
+pub fn get_state<T: borsh::BorshDeserialize>(rpc: &RpcClient, account: &Pubkey) -> ClientResult<T> {
+    let data = rpc.get_account_data(account)?;
+    Ok(T::try_from_slice(&data)?)
+}
+
+// Every account of the program of the given size, deserialized with borsh. The state structs have no discriminator,
+// so the size is what tells them apart, memcmp filters can narrow the accounts down further. The node does the
+// filtering, only matching accounts are sent.
+#[cfg(feature = "rpc")]
+pub fn get_program_states<T: borsh::BorshDeserialize>(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    size: usize,
+    filters: Vec<RpcFilterType>,
+) -> ClientResult<Vec<(Pubkey, T)>> {
+    let mut all_filters = vec![RpcFilterType::DataSize(size as u64)];
+    all_filters.extend(filters);
+    let config = RpcProgramAccountsConfig {
+        filters: Some(all_filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    rpc.get_program_accounts_with_config(program_id, config)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, T::try_from_slice(&account.data)?)))
+        .collect()
+}
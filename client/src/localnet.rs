@@ -0,0 +1,140 @@
+// Starts a solana-test-validator with the programs of this repository loaded, for tests and demos against a real
+// validator. The validator is stopped and its ledger removed when the Localnet is dropped.
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+};
+
+use crate::bootstrap;
+
+// the validator needs a few seconds to start, a cold start with a new ledger even longer
+pub const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub struct LocalProgram {
+    pub name: &'static str,
+    pub program_id: Pubkey,
+    pub so_path: PathBuf,
+}
+
+impl LocalProgram {
+    // A program built with `cargo build-sbf` in the crate directory, like `cargo xtask build` does. The program id is
+    // the address of the keypair build-sbf generated next to the .so, so it is the same as after a deploy.
+    pub fn built(crate_dir: &Path, lib_name: &'static str) -> Result<Self, Box<dyn Error>> {
+        let deploy_dir = crate_dir.join("target").join("deploy");
+        let so_path = deploy_dir.join(format!("{}.so", lib_name));
+        if !so_path.exists() {
+            return Err(format!("{} is missing, build it with cargo xtask build", so_path.display()).into());
+        }
+        let keypair_path = deploy_dir.join(format!("{}-keypair.json", lib_name));
+        let program_id = read_keypair_file(&keypair_path)
+            .map(|keypair| keypair.pubkey())
+            .unwrap_or_else(|_| Keypair::new().pubkey());
+
+        Ok(Self { name: lib_name, program_id, so_path })
+    }
+
+    // The greeting program of from_scratch and the calculator of function_calls, the ones the client talks to
+    pub fn repo_programs() -> Result<Vec<Self>, Box<dyn Error>> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        Ok(vec![
+            Self::built(&root.join("from_scratch"), "helloworld")?,
+            Self::built(&root.join("function_calls"), "function_calls")?,
+        ])
+    }
+}
+
+pub struct Localnet {
+    validator: Child,
+    ledger: PathBuf,
+    pub rpc_url: String,
+    pub websocket_url: String,
+    pub programs: Vec<LocalProgram>,
+}
+
+impl Localnet {
+    // Starts the validator with the programs and waits until it is healthy. The RPC port is rpc_port, the websocket
+    // the next one, the faucet faucet_port. Another validator using the same ports makes the start fail, which is
+    // also why only one Localnet should run at a time.
+    pub fn start(programs: Vec<LocalProgram>, rpc_port: u16, faucet_port: u16) -> Result<Self, Box<dyn Error>> {
+        let ledger = std::env::temp_dir().join(format!("localnet-{}-{}", std::process::id(), rpc_port));
+
+        let mut command = Command::new("solana-test-validator");
+        command
+            .arg("--ledger")
+            .arg(&ledger)
+            .arg("--reset")
+            .arg("--quiet")
+            .arg("--rpc-port")
+            .arg(rpc_port.to_string())
+            .arg("--faucet-port")
+            .arg(faucet_port.to_string())
+            .stdout(Stdio::null());
+        for program in &programs {
+            command.arg("--bpf-program").arg(program.program_id.to_string()).arg(&program.so_path);
+        }
+        let validator = command.spawn().map_err(|e| format!("Failed to start solana-test-validator: {}", e))?;
+
+        let localnet = Self {
+            validator,
+            ledger,
+            rpc_url: format!("http://127.0.0.1:{}", rpc_port),
+            websocket_url: format!("ws://127.0.0.1:{}", rpc_port + 1),
+            programs,
+        };
+        localnet.wait_until_healthy()?;
+        Ok(localnet)
+    }
+
+    // The validator with the programs of repo_programs on the default ports of solana-test-validator
+    pub fn start_default() -> Result<Self, Box<dyn Error>> {
+        Self::start(LocalProgram::repo_programs()?, 8899, 9900)
+    }
+
+    pub fn rpc(&self) -> RpcClient {
+        RpcClient::new_with_commitment(self.rpc_url.clone(), CommitmentConfig::confirmed())
+    }
+
+    pub fn program_id(&self, name: &str) -> Option<Pubkey> {
+        self.programs.iter().find(|program| program.name == name).map(|program| program.program_id)
+    }
+
+    // A new keypair with an airdrop from the faucet of the validator, enough to create the state accounts
+    pub fn funded_payer(&self) -> Result<Keypair, Box<dyn Error>> {
+        let payer = Keypair::new();
+        bootstrap::airdrop(&self.rpc(), &payer.pubkey(), bootstrap::AIRDROP_LAMPORTS)?;
+        Ok(payer)
+    }
+
+    fn wait_until_healthy(&self) -> Result<(), Box<dyn Error>> {
+        let rpc = self.rpc();
+        let started = Instant::now();
+        while rpc.get_health().is_err() {
+            if started.elapsed() > STARTUP_TIMEOUT {
+                return Err(format!("solana-test-validator wasn't healthy after {:?}", STARTUP_TIMEOUT).into());
+            }
+            thread::sleep(Duration::from_millis(500));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Localnet {
+    fn drop(&mut self) {
+        // the validator may have exited on its own already, there is nothing left to do then
+        let _ = self.validator.kill();
+        let _ = self.validator.wait();
+        let _ = fs::remove_dir_all(&self.ledger);
+    }
+}
@@ -0,0 +1,262 @@
+// Turns the log messages of a transaction into events of the programs. The runtime prefixes everything a program
+// logs: `msg!` becomes "Program log: ..." and `sol_log_data` becomes "Program data: ..." with every slice base64
+// encoded. Which program logged a line follows from the "invoke" and "success" or "failed" lines around it.
+use std::str::FromStr;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+#[cfg(feature = "rpc")]
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_client::RpcClient,
+};
+use solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "rpc")]
+use solana_sdk::signature::Signature;
+#[cfg(feature = "rpc")]
+use solana_transaction_status::{option_serializer::OptionSerializer, UiTransactionEncoding};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProgramLog<'a> {
+    // logged with msg!
+    Message(&'a str),
+    // logged with sol_log_data, one entry per slice
+    Data(Vec<Vec<u8>>),
+}
+
+// Everything the program logged itself, in order, also when it was invoked by another program. Lines of the
+// programs it invoked are left out.
+pub fn program_logs<'a>(logs: &'a [String], program_id: &Pubkey) -> Vec<ProgramLog<'a>> {
+    let program = program_id.to_string();
+    // the programs currently running, the last one is the one logging
+    let mut stack: Vec<&str> = Vec::new();
+    let mut program_logs = Vec::new();
+
+    for line in logs {
+        let logging = stack.last() == Some(&program.as_str());
+        if let Some(message) = line.strip_prefix("Program log: ") {
+            if logging {
+                program_logs.push(ProgramLog::Message(message));
+            }
+        } else if let Some(data) = line.strip_prefix("Program data: ") {
+            if logging {
+                // a slice that isn't valid base64 can't come from sol_log_data, the line is skipped
+                if let Ok(data) = data.split(' ').map(|slice| STANDARD.decode(slice)).collect() {
+                    program_logs.push(ProgramLog::Data(data));
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            match (words.next(), words.next()) {
+                (Some(id), Some("invoke")) => stack.push(id),
+                (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                    stack.pop();
+                },
+                _ => {},
+            }
+        }
+    }
+
+    program_logs
+}
+
+// Fetches the log messages of a confirmed transaction
+#[cfg(feature = "rpc")]
+pub fn transaction_logs(rpc: &RpcClient, signature: &Signature) -> ClientResult<Vec<String>> {
+    let transaction = rpc.get_transaction(signature, UiTransactionEncoding::Json)?;
+    match transaction.transaction.meta.map(|meta| meta.log_messages) {
+        Some(OptionSerializer::Some(logs)) => Ok(logs),
+        _ => Err(ClientError::from(ClientErrorKind::Custom(format!("Transaction {} has no log messages", signature)))),
+    }
+}
+
+// What the greeting program of from_scratch logs
+#[derive(Debug, PartialEq, Eq)]
+pub enum GreetingEvent {
+    Entrypoint,
+    Greeted { counter: u32 },
+    // Initialize created the greeting account
+    Initialized { account: Pubkey },
+    // Initialize refused to create it
+    PayerNotSigner,
+    WrongSystemProgram,
+    WrongGreetingAccount,
+    IncorrectProgramId,
+    // a memo was asked for, but the last account isn't the memo program
+    WrongMemoProgram,
+    // GreetAbove refused to greet
+    NotAbove { result: u64, threshold: u64 },
+    WrongCalculatorOwner,
+    // only logged by a build with the allowlist feature
+    GreeterNotSigner,
+    WrongAllowlist,
+    NotOnAllowlist { greeter: Pubkey },
+}
+
+impl GreetingEvent {
+    // The messages of the msg! calls in from_scratch/src/lib.rs, None for any other message
+    pub fn parse(message: &str) -> Option<Self> {
+        Some(match message {
+            "Hello World Rust program entrypoint" => Self::Entrypoint,
+            "Greeted account does not have the correct program id" => Self::IncorrectProgramId,
+            "Last account is not the memo program" => Self::WrongMemoProgram,
+            "Result account is not owned by the calculator" => Self::WrongCalculatorOwner,
+            "Payer has to sign the transaction" => Self::PayerNotSigner,
+            "Wrong system program" => Self::WrongSystemProgram,
+            "Greeting account is neither a signer nor the greeting PDA of the payer" => Self::WrongGreetingAccount,
+            "Greeter has to sign the transaction" => Self::GreeterNotSigner,
+            "Wrong allowlist account" => Self::WrongAllowlist,
+            _ => {
+                if let Some(counter) = message.strip_prefix("Greeted ").and_then(|m| m.strip_suffix(" time(s)!")) {
+                    Self::Greeted { counter: counter.parse().ok()? }
+                } else if let Some(account) = message.strip_prefix("Created greeting account ") {
+                    Self::Initialized { account: Pubkey::from_str(account).ok()? }
+                } else if let Some((result, threshold)) =
+                    message.strip_prefix("Result ").and_then(|m| m.split_once(" is not above "))
+                {
+                    Self::NotAbove { result: result.parse().ok()?, threshold: threshold.parse().ok()? }
+                } else {
+                    let greeter = message.strip_suffix(" is not on the allowlist")?;
+                    Self::NotOnAllowlist { greeter: Pubkey::from_str(greeter).ok()? }
+                }
+            }
+        })
+    }
+}
+
+// What the calculator program of function_calls logs
+#[derive(Debug, PartialEq, Eq)]
+pub enum CalcEvent {
+    Invoked { program_id: Pubkey, accounts: usize, data: Vec<u8> },
+    Add { a: u64, b: u64 },
+    Sub { a: u64, b: u64 },
+    // InitVault created the vault state of the authority
+    InitVault { vault: Pubkey, authority: Pubkey },
+    Withdraw { amount: u64 },
+    InitConfig,
+    // SetCallers replaced the allowlist with this many callers
+    SetCallers { callers: usize },
+    IncorrectProgramId,
+    // a memo was asked for, but the last account isn't the memo program
+    WrongMemoProgram,
+    NotAdmin { admin: Pubkey },
+    // only logged by a build with the caller-allowlist feature
+    NestedCall,
+    CallerNotAllowed { caller: Pubkey },
+}
+
+impl CalcEvent {
+    // The messages of the msg! calls in function_calls/src, None for any other message
+    pub fn parse(message: &str) -> Option<Self> {
+        match message {
+            "Greeted account does not have the correct program id" => return Some(Self::IncorrectProgramId),
+            "Last account is not the memo program" => return Some(Self::WrongMemoProgram),
+            "Instruction: InitConfig" => return Some(Self::InitConfig),
+            "Calls nested deeper than one CPI are refused" => return Some(Self::NestedCall),
+            _ => {},
+        }
+        if let Some(operands) = message.strip_prefix("Instruction: Add ") {
+            let (a, b) = Self::parse_operands(operands)?;
+            return Some(Self::Add { a, b });
+        }
+        if let Some(operands) = message.strip_prefix("Instruction: Sub ") {
+            let (a, b) = Self::parse_operands(operands)?;
+            return Some(Self::Sub { a, b });
+        }
+        if let Some(amount) = message.strip_prefix("Instruction: Withdraw ") {
+            return Some(Self::Withdraw { amount: amount.parse().ok()? });
+        }
+        if let Some(callers) = message.strip_prefix("Instruction: SetCallers ") {
+            return Some(Self::SetCallers { callers: callers.parse().ok()? });
+        }
+        if let Some(admin) = message.strip_suffix(" is not the admin of the config") {
+            return Some(Self::NotAdmin { admin: Pubkey::from_str(admin).ok()? });
+        }
+        if let Some(caller) = message.strip_suffix(" may not call the calculator") {
+            return Some(Self::CallerNotAllowed { caller: Pubkey::from_str(caller).ok()? });
+        }
+        if let Some((vault, authority)) = message.strip_prefix("Vault ").and_then(|m| m.split_once(" of ")) {
+            let (vault, authority) = (Pubkey::from_str(vault).ok()?, Pubkey::from_str(authority).ok()?);
+            return Some(Self::InitVault { vault, authority });
+        }
+
+        // "process_instruction: <program id>: <n> accounts, data=[1, 2, 3]"
+        let rest = message.strip_prefix("process_instruction: ")?;
+        let (program_id, rest) = rest.split_once(": ")?;
+        let (accounts, data) = rest.split_once(" accounts, data=")?;
+        let data = data.strip_prefix('[')?.strip_suffix(']')?;
+        Some(Self::Invoked {
+            program_id: Pubkey::from_str(program_id).ok()?,
+            accounts: accounts.parse().ok()?,
+            data: if data.is_empty() {
+                Vec::new()
+            } else {
+                data.split(", ").map(|byte| byte.parse().ok()).collect::<Option<_>>()?
+            },
+        })
+    }
+
+    fn parse_operands(operands: &str) -> Option<(u64, u64)> {
+        let (a, b) = operands.split_once(' ')?;
+        Some((a.parse().ok()?, b.parse().ok()?))
+    }
+}
+
+// The events of the greeting program in the logs of a transaction
+pub fn greeting_events(logs: &[String], program_id: &Pubkey) -> Vec<GreetingEvent> {
+    messages(logs, program_id).filter_map(GreetingEvent::parse).collect()
+}
+
+// The events of the calculator program in the logs of a transaction
+pub fn calc_events(logs: &[String], program_id: &Pubkey) -> Vec<CalcEvent> {
+    messages(logs, program_id).filter_map(CalcEvent::parse).collect()
+}
+
+fn messages<'a>(logs: &'a [String], program_id: &Pubkey) -> impl Iterator<Item = &'a str> {
+    program_logs(logs, program_id).into_iter().filter_map(|log| match log {
+        ProgramLog::Message(message) => Some(message),
+        ProgramLog::Data(_) => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrong_memo_program_of_both_programs() {
+        let message = "Last account is not the memo program";
+        assert_eq!(GreetingEvent::parse(message), Some(GreetingEvent::WrongMemoProgram));
+        assert_eq!(CalcEvent::parse(message), Some(CalcEvent::WrongMemoProgram));
+    }
+
+    #[test]
+    fn greet_above_refusals() {
+        assert_eq!(
+            GreetingEvent::parse("Result 8 is not above 10"),
+            Some(GreetingEvent::NotAbove { result: 8, threshold: 10 })
+        );
+        assert_eq!(
+            GreetingEvent::parse("Result account is not owned by the calculator"),
+            Some(GreetingEvent::WrongCalculatorOwner)
+        );
+        assert_eq!(GreetingEvent::parse("Result 8 is not above ten"), None);
+    }
+
+    #[test]
+    fn caller_config_of_the_calculator() {
+        let key = Pubkey::new_unique();
+        assert_eq!(CalcEvent::parse("Instruction: InitConfig"), Some(CalcEvent::InitConfig));
+        assert_eq!(CalcEvent::parse("Instruction: SetCallers 3"), Some(CalcEvent::SetCallers { callers: 3 }));
+        assert_eq!(
+            CalcEvent::parse(&format!("{} is not the admin of the config", key)),
+            Some(CalcEvent::NotAdmin { admin: key })
+        );
+        assert_eq!(CalcEvent::parse("Calls nested deeper than one CPI are refused"), Some(CalcEvent::NestedCall));
+        assert_eq!(
+            CalcEvent::parse(&format!("{} may not call the calculator", key)),
+            Some(CalcEvent::CallerNotAllowed { caller: key })
+        );
+        // anything but a number is no count
+        assert_eq!(CalcEvent::parse("Instruction: SetCallers many"), None);
+    }
+}
@@ -0,0 +1,96 @@
+// A legacy transaction lists every account with its 32 byte address and has to fit into 1232 bytes, which limits it
+// to about 35 accounts. A v0 transaction can reference accounts by a one byte index into an address lookup table
+// instead, a list of addresses stored on chain. These functions create and extend such tables and send v0
+// transactions using them.
+use std::{thread, time::Duration};
+
+use solana_client::{client_error::Result as ClientResult, rpc_client::RpcClient};
+use solana_sdk::{
+    address_lookup_table::{instruction, state::AddressLookupTable, AddressLookupTableAccount},
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+    transaction::VersionedTransaction,
+};
+
+use crate::send::{self, SendOptions};
+
+// an extend instruction with more addresses doesn't fit into a legacy transaction anymore
+pub const ADDRESSES_PER_EXTEND: usize = 20;
+
+// Creates a lookup table with the payer as authority and returns its address. The address is derived from the
+// authority and a recent slot, so every call creates a new table.
+pub fn create_lookup_table(rpc: &RpcClient, payer: &dyn Signer) -> ClientResult<Pubkey> {
+    // the slot has to be in the SlotHashes sysvar of the validator processing the transaction, a finalized slot is
+    // there on every validator
+    let recent_slot = rpc.get_slot_with_commitment(CommitmentConfig::finalized())?;
+    let (create, table) = instruction::create_lookup_table(payer.pubkey(), payer.pubkey(), recent_slot);
+    crate::send_instructions(rpc, payer, &[create])?;
+    Ok(table)
+}
+
+// Adds the addresses to the table, the payer pays for the bigger account. Long lists are split over several
+// transactions, a table holds up to 256 addresses.
+pub fn extend_lookup_table(rpc: &RpcClient, payer: &dyn Signer, table: &Pubkey, addresses: &[Pubkey]) -> ClientResult<()> {
+    for chunk in addresses.chunks(ADDRESSES_PER_EXTEND) {
+        let extend = instruction::extend_lookup_table(*table, payer.pubkey(), Some(payer.pubkey()), chunk.to_vec());
+        crate::send_instructions(rpc, payer, &[extend])?;
+    }
+
+    // addresses added in a slot can only be looked up from the next slot on
+    let extended_in = rpc.get_slot()?;
+    while rpc.get_slot()? <= extended_in {
+        thread::sleep(Duration::from_millis(400));
+    }
+    Ok(())
+}
+
+// Reads the table, it is needed to compile a v0 message with it
+pub fn get_lookup_table(rpc: &RpcClient, table: &Pubkey) -> ClientResult<AddressLookupTableAccount> {
+    let data = rpc.get_account_data(table)?;
+    let lookup_table = AddressLookupTable::deserialize(&data)
+        .map_err(|e| send::custom_error(format!("{} is no lookup table: {}", table, e)))?;
+    Ok(AddressLookupTableAccount {
+        key: *table,
+        addresses: lookup_table.addresses.to_vec(),
+    })
+}
+
+// Sends the instructions in a single v0 transaction signed by the payer. Every account found in one of the tables is
+// referenced by its index, except signers, which always need their full address.
+pub fn send_with_lookup_tables(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    options: &SendOptions,
+) -> ClientResult<Signature> {
+    let instructions = send::with_compute_budget_for(rpc, instructions, options, |instructions| {
+        let message = compile(payer, instructions, lookup_tables, Hash::default())?;
+        // the simulation doesn't check signatures, the empty ones only have to be there
+        Ok(VersionedTransaction {
+            signatures: vec![Signature::default(); message.header().num_required_signatures as usize],
+            message,
+        })
+    })?;
+
+    send::send_with(rpc, options, |blockhash| {
+        let message = compile(payer, &instructions, lookup_tables, blockhash)?;
+        Ok(VersionedTransaction::try_new(message, &[payer])?)
+    })
+}
+
+fn compile(
+    payer: &dyn Signer,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> ClientResult<VersionedMessage> {
+    let message = v0::Message::try_compile(&payer.pubkey(), instructions, lookup_tables, blockhash)
+        .map_err(|e| send::custom_error(format!("Failed to compile the v0 message: {}", e)))?;
+    Ok(VersionedMessage::V0(message))
+}
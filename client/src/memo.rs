@@ -0,0 +1,33 @@
+// The greeting and the calculator program write a memo of what they did into the transaction when the SPL Memo
+// program follows the accounts of greet, add or sub. Explorers show it next to the transaction. The memo program isn't
+// part of the generated builders, with_memo appends it to an instruction they built.
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+use crate::logs::{self, ProgramLog};
+
+pub fn memo_program_id() -> Pubkey {
+    spl_memo::id()
+}
+
+// The instruction with the memo program as its last account
+pub fn with_memo(mut instruction: Instruction) -> Instruction {
+    instruction.accounts.push(AccountMeta::new_readonly(memo_program_id(), false));
+    instruction
+}
+
+// The memos written in a transaction, the memo program logs each as `Memo (len 9): "3 + 5 = 8"`
+pub fn memos(logs: &[String]) -> Vec<String> {
+    logs::program_logs(logs, &memo_program_id())
+        .into_iter()
+        .filter_map(|log| match log {
+            ProgramLog::Message(message) => {
+                let (_, memo) = message.strip_prefix("Memo (len ")?.split_once("): ")?;
+                Some(memo.strip_prefix('"')?.strip_suffix('"')?.replace("\\\"", "\""))
+            },
+            ProgramLog::Data(_) => None,
+        })
+        .collect()
+}
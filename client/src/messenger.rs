@@ -0,0 +1,53 @@
+// Client of the messenger program, the side a relayer needs: the published messages and their payloads
+#[cfg(feature = "rpc")]
+use solana_client::{client_error::Result as ClientResult, rpc_client::RpcClient};
+use solana_sdk::{hash::hash, pubkey::Pubkey};
+
+pub use messenger::{
+    processor::{config_address, message_address, sequence_address},
+    state::{Config, Message, Sequence, MAX_PAYLOAD_LEN},
+};
+
+use crate::logs::{self, ProgramLog};
+
+// The payload of the message in the logs of the transaction which posted it. The program logs every payload with
+// sol_log_data, a transaction may post more than one message, the hash tells which one belongs to this message.
+pub fn payload_from_logs(logs: &[String], program_id: &Pubkey, message: &Message) -> Option<Vec<u8>> {
+    logs::program_logs(logs, program_id)
+        .into_iter()
+        .filter_map(|log| match log {
+            ProgramLog::Data(mut data) if data.len() == 1 => data.pop(),
+            _ => None,
+        })
+        .find(|payload| hash(payload).to_bytes() == message.payload_hash)
+}
+
+// Every message of the program with its address, in the order of the emitters and their sequence numbers
+#[cfg(feature = "rpc")]
+pub fn messages(rpc: &RpcClient, program_id: &Pubkey) -> ClientResult<Vec<(Pubkey, Message)>> {
+    let mut messages: Vec<(Pubkey, Message)> = crate::get_program_states(rpc, program_id, Message::LEN, vec![])?;
+    messages.sort_by_key(|(_, message)| (message.emitter, message.sequence));
+    Ok(messages)
+}
+
+// The payload of a message. The message account is never written again, but anybody may mention it in a later
+// transaction, so the payload is looked for in the oldest transaction of the account, the one creating it.
+// None if that transaction is older than the 1000 signatures the node returns at once.
+#[cfg(feature = "rpc")]
+pub fn message_payload(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    address: &Pubkey,
+    message: &Message,
+) -> ClientResult<Option<Vec<u8>>> {
+    let signatures = rpc.get_signatures_for_address(address)?;
+    let Some(oldest) = signatures.last() else {
+        return Ok(None);
+    };
+    let signature = oldest
+        .signature
+        .parse()
+        .map_err(|e| crate::send::custom_error(format!("Invalid signature {}: {}", oldest.signature, e)))?;
+    let logs = logs::transaction_logs(rpc, &signature)?;
+    Ok(payload_from_logs(&logs, program_id, message))
+}
@@ -0,0 +1,223 @@
+// Offline and multi-party signing. A transaction expires about a minute after its blockhash, too soon to carry it to
+// an air-gapped machine or to collect the signatures of several people. A nonce account stores a blockhash which
+// stays valid until a transaction using it advances the nonce, so a transaction built on it can be prepared online,
+// signed offline by every key it needs, one after the other, and broadcast whenever all signatures are there.
+//
+// The transaction travels as a JSON file holding the message and the signatures collected so far:
+//
+//   {"version": 1, "message": "<base64 of the message>", "signatures": {"<pubkey>": "<signature>"}}
+use std::{collections::BTreeMap, error::Error, fs, path::Path};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use solana_client::{
+    client_error::Result as ClientResult, nonce_utils, rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
+    nonce::State as NonceState,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    system_instruction::{self, SystemInstruction},
+    system_program,
+    transaction::Transaction,
+};
+
+use crate::send::{self, SendOptions};
+
+// the version of the payload file, raised when its format changes
+pub const PAYLOAD_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PayloadFile {
+    version: u32,
+    message: String,
+    #[serde(default)]
+    signatures: BTreeMap<String, String>,
+}
+
+// A transaction waiting for its signatures. Every signature is checked against the message when it is added, a
+// wrong one is never carried along to the next signer.
+#[derive(Clone, Debug)]
+pub struct OfflineTransaction {
+    pub message: Message,
+    signatures: BTreeMap<Pubkey, Signature>,
+}
+
+impl OfflineTransaction {
+    pub fn new(message: Message) -> Self {
+        Self { message, signatures: BTreeMap::new() }
+    }
+
+    // The keys that have to sign, the fee payer first
+    pub fn required_signers(&self) -> &[Pubkey] {
+        &self.message.account_keys[..self.message.header.num_required_signatures as usize]
+    }
+
+    pub fn missing_signers(&self) -> Vec<Pubkey> {
+        self.required_signers().iter().filter(|key| !self.signatures.contains_key(key)).copied().collect()
+    }
+
+    // The nonce account, if the transaction starts with advancing a nonce like the prepared ones do
+    pub fn nonce_account(&self) -> Option<Pubkey> {
+        let instruction = self.message.instructions.first()?;
+        let program_id = self.message.account_keys.get(instruction.program_id_index as usize)?;
+        let advance = bincode::deserialize(&instruction.data).ok() == Some(SystemInstruction::AdvanceNonceAccount);
+        if !system_program::check_id(program_id) || !advance {
+            return None;
+        }
+        self.message.account_keys.get(*instruction.accounts.first()? as usize).copied()
+    }
+
+    pub fn signatures(&self) -> &BTreeMap<Pubkey, Signature> {
+        &self.signatures
+    }
+
+    // Signs the message with a key it needs, a Ledger shows the message before it signs
+    pub fn sign(&mut self, signer: &dyn Signer) -> Result<Signature, Box<dyn Error>> {
+        let pubkey = signer.try_pubkey()?;
+        if !self.required_signers().contains(&pubkey) {
+            return Err(format!("{} doesn't have to sign this transaction", pubkey).into());
+        }
+        let signature = signer.try_sign_message(&self.message.serialize())?;
+        self.signatures.insert(pubkey, signature);
+        Ok(signature)
+    }
+
+    // Adds a signature made elsewhere, like the output of the sign command on another machine
+    pub fn add_signature(&mut self, pubkey: Pubkey, signature: Signature) -> Result<(), Box<dyn Error>> {
+        if !self.required_signers().contains(&pubkey) {
+            return Err(format!("{} doesn't have to sign this transaction", pubkey).into());
+        }
+        if !signature.verify(pubkey.as_ref(), &self.message.serialize()) {
+            return Err(format!("{} isn't a signature of {} for this transaction", signature, pubkey).into());
+        }
+        self.signatures.insert(pubkey, signature);
+        Ok(())
+    }
+
+    // The signed transaction, once every key signed
+    pub fn to_transaction(&self) -> Result<Transaction, Box<dyn Error>> {
+        let missing = self.missing_signers();
+        if !missing.is_empty() {
+            let missing: Vec<String> = missing.iter().map(Pubkey::to_string).collect();
+            return Err(format!("Signatures of {} are missing", missing.join(", ")).into());
+        }
+        // the signatures are in the order of the signing keys in the message
+        let signatures = self.required_signers().iter().map(|key| self.signatures[key]).collect();
+        Ok(Transaction { signatures, message: self.message.clone() })
+    }
+
+    pub fn to_json(&self) -> String {
+        let payload = PayloadFile {
+            version: PAYLOAD_VERSION,
+            message: STANDARD.encode(self.message.serialize()),
+            signatures: self
+                .signatures
+                .iter().map(|(key, signature)| (key.to_string(), signature.to_string())).collect(),
+        };
+        // a struct of strings always serializes
+        serde_json::to_string_pretty(&payload).unwrap()
+    }
+
+    // Reads a payload and checks every signature in it
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        let payload: PayloadFile = serde_json::from_str(json)?;
+        if payload.version != PAYLOAD_VERSION {
+            return Err(format!("Payload version {} isn't supported, only {}", payload.version, PAYLOAD_VERSION).into());
+        }
+        let message: Message = bincode::deserialize(&STANDARD.decode(&payload.message)?)?;
+
+        let mut transaction = Self::new(message);
+        for (key, signature) in payload.signatures {
+            let key = key.parse().map_err(|e| format!("Invalid signer {}: {}", key, e))?;
+            let signature = signature.parse().map_err(|e| format!("Invalid signature {}: {}", signature, e))?;
+            transaction.add_signature(key, signature)?;
+        }
+        Ok(transaction)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_json()).map_err(|e| format!("Failed to write {}: {}", path.display(), e).into())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let json = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_json(&json).map_err(|e| format!("Invalid payload {}: {}", path.display(), e).into())
+    }
+}
+
+// Creates a nonce account controlled by the authority and returns its address. It holds enough lamports to be rent
+// exempt, they go back with system_instruction::withdraw_nonce_account.
+pub fn create_nonce_account(rpc: &RpcClient, payer: &dyn Signer, authority: &Pubkey) -> ClientResult<Pubkey> {
+    let nonce = Keypair::new();
+    let lamports = rpc.get_minimum_balance_for_rent_exemption(NonceState::size())?;
+    let instructions = system_instruction::create_nonce_account(&payer.pubkey(), &nonce.pubkey(), authority, lamports);
+
+    send::send_with(rpc, &SendOptions::default(), |blockhash| {
+        Ok(Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[payer, &nonce], blockhash))
+    })?;
+    Ok(nonce.pubkey())
+}
+
+// The blockhash stored in the nonce account and the authority which has to sign for advancing it
+pub fn nonce_blockhash(rpc: &RpcClient, nonce_account: &Pubkey) -> ClientResult<(Hash, Pubkey)> {
+    let account = nonce_utils::get_account_with_commitment(rpc, nonce_account, CommitmentConfig::confirmed())
+        .map_err(|e| send::custom_error(format!("Failed to read nonce account {}: {}", nonce_account, e)))?;
+    let data = nonce_utils::data_from_account(&account)
+        .map_err(|e| send::custom_error(format!("{} isn't a nonce account: {}", nonce_account, e)))?;
+    Ok((data.blockhash(), data.authority))
+}
+
+// An unsigned transaction of the instructions, with the nonce of the account as blockhash. The runtime advances the
+// nonce with the first instruction, so the transaction can only be processed once.
+pub fn prepare(
+    rpc: &RpcClient,
+    fee_payer: &Pubkey,
+    nonce_account: &Pubkey,
+    instructions: &[Instruction],
+) -> ClientResult<OfflineTransaction> {
+    let (nonce, authority) = nonce_blockhash(rpc, nonce_account)?;
+    Ok(prepare_with_nonce(fee_payer, nonce_account, &authority, nonce, instructions))
+}
+
+// prepare without a node, for a nonce read beforehand
+pub fn prepare_with_nonce(
+    fee_payer: &Pubkey,
+    nonce_account: &Pubkey,
+    nonce_authority: &Pubkey,
+    nonce: Hash,
+    instructions: &[Instruction],
+) -> OfflineTransaction {
+    let mut message = Message::new_with_nonce(instructions.to_vec(), Some(fee_payer), nonce_account, nonce_authority);
+    message.recent_blockhash = nonce;
+    OfflineTransaction::new(message)
+}
+
+// Sends the fully signed transaction and waits for the confirmation. The nonce never expires, but once anything
+// advanced it, the transaction is void and has to be prepared again.
+pub fn broadcast(rpc: &RpcClient, transaction: &OfflineTransaction) -> ClientResult<Signature> {
+    let signed = transaction.to_transaction().map_err(|e| send::custom_error(e.to_string()))?;
+
+    // an advanced nonce would only fail with BlockhashNotFound
+    if let Some(nonce_account) = transaction.nonce_account() {
+        let (nonce, _) = nonce_blockhash(rpc, &nonce_account)?;
+        if nonce != transaction.message.recent_blockhash {
+            return Err(send::custom_error(format!(
+                "The nonce of {} was advanced since the transaction was prepared, prepare it again",
+                nonce_account
+            )));
+        }
+    }
+
+    let config = RpcSendTransactionConfig {
+        preflight_commitment: Some(CommitmentConfig::confirmed().commitment),
+        ..RpcSendTransactionConfig::default()
+    };
+    rpc.send_and_confirm_transaction_with_spinner_and_config(&signed, CommitmentConfig::confirmed(), config)
+}
@@ -0,0 +1,140 @@
+// Cluster profiles for the binaries, so the url, the payer and the program ids don't have to be given on every
+// command. The profiles are read from a TOML file, client.toml in the current directory or the file CLIENT_CONFIG
+// points to, see client.example.toml:
+//
+//   cluster = "devnet"
+//
+//   [clusters.devnet]
+//   keypair = "~/.config/solana/devnet.json"
+//
+//   [clusters.devnet.programs]
+//   helloworld = "<program id>"
+//
+// Arguments on the command line come first, then the environment, then the chosen profile and last the config of
+// the solana cli.
+use std::{collections::BTreeMap, env, error::Error, fs, path::PathBuf};
+
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+
+pub const CONFIG_ENV: &str = "CLIENT_CONFIG";
+pub const CONFIG_FILE: &str = "client.toml";
+// overrides of the profile
+pub const CLUSTER_ENV: &str = "CLIENT_CLUSTER";
+pub const URL_ENV: &str = "CLIENT_URL";
+pub const KEYPAIR_ENV: &str = "CLIENT_KEYPAIR";
+// one program id per program, like CLIENT_PROGRAM_HELLOWORLD
+pub const PROGRAM_ENV_PREFIX: &str = "CLIENT_PROGRAM_";
+
+// the names of the programs in the profiles, the names of their libraries, like cargo xtask and localnet use them
+pub const GREETING_PROGRAM: &str = "helloworld";
+pub const CALCULATOR_PROGRAM: &str = "function_calls";
+pub const MESSENGER_PROGRAM: &str = "messenger";
+pub const STREAMING_PROGRAM: &str = "streaming";
+
+// the clusters a profile doesn't have to give the url of
+const KNOWN_CLUSTERS: &[(&str, &str)] = &[
+    ("localnet", "http://127.0.0.1:8899"),
+    ("devnet", "https://api.devnet.solana.com"),
+    ("testnet", "https://api.testnet.solana.com"),
+    ("mainnet", "https://api.mainnet-beta.solana.com"),
+];
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    // the profile used if none is chosen
+    cluster: Option<String>,
+    #[serde(default)]
+    clusters: BTreeMap<String, ClusterConfig>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct ClusterConfig {
+    url: Option<String>,
+    websocket_url: Option<String>,
+    keypair: Option<String>,
+    #[serde(default)]
+    programs: BTreeMap<String, String>,
+}
+
+// Everything left unset falls back to the solana cli config
+#[derive(Debug, Default)]
+pub struct Profile {
+    pub cluster: Option<String>,
+    pub url: Option<String>,
+    pub websocket_url: Option<String>,
+    pub keypair: Option<String>,
+    pub programs: BTreeMap<String, Pubkey>,
+}
+
+impl Profile {
+    // Loads the given profile, the one of CLIENT_CLUSTER or the default one of the config file. Without a config
+    // file only the known clusters can be chosen. An empty profile if no cluster is chosen at all.
+    pub fn load(cluster: Option<String>) -> Result<Self, Box<dyn Error>> {
+        let config = read_config_file()?;
+        let cluster = cluster.or_else(|| env::var(CLUSTER_ENV).ok()).or(config.cluster);
+
+        let mut profile = Profile::default();
+        if let Some(name) = &cluster {
+            let known_url = KNOWN_CLUSTERS.iter().find(|(known, _)| known == name).map(|(_, url)| url.to_string());
+            let mut clusters = config.clusters;
+            let cluster_config = match clusters.remove(name) {
+                Some(cluster_config) => cluster_config,
+                None if known_url.is_some() => ClusterConfig::default(),
+                None => return Err(format!("Unknown cluster {}, it has no profile in the config", name).into()),
+            };
+
+            profile.url = cluster_config.url.or(known_url);
+            profile.websocket_url = cluster_config.websocket_url;
+            profile.keypair = cluster_config.keypair.map(|keypair| expand_home(&keypair));
+            for (program, id) in cluster_config.programs {
+                let id = id.parse().map_err(|e| format!("Invalid id {} of {} in {}: {}", id, program, name, e))?;
+                profile.programs.insert(program, id);
+            }
+        }
+        profile.cluster = cluster;
+
+        if let Ok(url) = env::var(URL_ENV) {
+            profile.url = Some(url);
+        }
+        if let Ok(keypair) = env::var(KEYPAIR_ENV) {
+            profile.keypair = Some(expand_home(&keypair));
+        }
+        for (key, id) in env::vars() {
+            if let Some(program) = key.strip_prefix(PROGRAM_ENV_PREFIX) {
+                let id = id.parse().map_err(|e| format!("Invalid program id in {}: {}", key, e))?;
+                profile.programs.insert(program.to_lowercase(), id);
+            }
+        }
+
+        Ok(profile)
+    }
+
+    // The id of the program on the cluster of the profile, if the profile knows it
+    pub fn program_id(&self, program: &str) -> Option<Pubkey> {
+        self.programs.get(program).copied()
+    }
+}
+
+// A missing client.toml is fine, a missing file CLIENT_CONFIG points to is a mistake
+fn read_config_file() -> Result<ConfigFile, Box<dyn Error>> {
+    let (path, required) = match env::var(CONFIG_ENV) {
+        Ok(path) => (PathBuf::from(path), true),
+        Err(_) => (PathBuf::from(CONFIG_FILE), false),
+    };
+    match fs::read_to_string(&path) {
+        Ok(content) => Ok(toml::from_str(&content).map_err(|e| format!("Invalid config {}: {}", path.display(), e))?),
+        Err(_) if !required => Ok(ConfigFile::default()),
+        Err(e) => Err(format!("Failed to read config {}: {}", path.display(), e).into()),
+    }
+}
+
+// only shells expand ~, so a path from the config or the environment would be taken literally
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home, rest),
+        _ => path.to_string(),
+    }
+}
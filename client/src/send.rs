@@ -0,0 +1,232 @@
+// Sending a transaction with send_and_confirm_transaction gives up as soon as its blockhash expires, which happens
+// a lot on a busy devnet. send_with_options signs the transaction again with a fresh blockhash instead, a bounded
+// number of times and within a timeout.
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    rpc_client::{RpcClient, SerializableTransaction},
+    rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig},
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::Instruction,
+    signature::Signature,
+    signer::Signer,
+    transaction::Transaction,
+};
+
+// the runtime refuses a bigger compute unit limit for a transaction
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+// what the limit of ComputeUnitLimit::Auto adds on top of the simulated units, the state of the accounts may change
+// until the transaction is processed and take a few more
+pub const AUTO_COMPUTE_UNIT_MARGIN_PERCENT: u64 = 10;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub enum ComputeUnitLimit {
+    // no SetComputeUnitLimit instruction, the runtime allows 200k units per instruction
+    #[default]
+    Default,
+    Fixed(u32),
+    // simulates the transaction first and sets the limit to the units it used plus a margin. A priority fee is paid
+    // per requested unit, so a tight limit makes it cheaper.
+    Auto,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SendOptions {
+    // the transaction counts as sent once it reached this commitment
+    pub commitment: CommitmentConfig,
+    // how often the transaction is signed and sent, every attempt after the first one has a new blockhash
+    pub max_attempts: u32,
+    // the node drops transactions it can't forward, so the same transaction is sent again after this interval
+    // while waiting for its confirmation
+    pub resend_interval: Duration,
+    // how often the status of the transaction is asked for
+    pub poll_interval: Duration,
+    // all attempts together give up after this time
+    pub timeout: Duration,
+    pub compute_unit_limit: ComputeUnitLimit,
+    // priority fee in micro lamports per compute unit, validators prefer transactions paying one when blocks are full
+    pub compute_unit_price: Option<u64>,
+}
+
+impl Default for SendOptions {
+    fn default() -> Self {
+        Self {
+            commitment: CommitmentConfig::confirmed(),
+            max_attempts: 3,
+            resend_interval: Duration::from_secs(2),
+            poll_interval: Duration::from_millis(500),
+            timeout: Duration::from_secs(90),
+            compute_unit_limit: ComputeUnitLimit::Default,
+            compute_unit_price: None,
+        }
+    }
+}
+
+// Sends the instructions in a single transaction paid and signed by the payer and waits for the commitment of the
+// options. A transaction which fails, like a program error, is returned right away, only lost or expired ones are
+// sent again.
+pub fn send_with_options(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    instructions: &[Instruction],
+    options: &SendOptions,
+) -> ClientResult<Signature> {
+    let instructions = with_compute_budget(rpc, payer, instructions, options)?;
+    send_with(rpc, options, |blockhash| {
+        Ok(Transaction::new_signed_with_payer(&instructions, Some(&payer.pubkey()), &[payer], blockhash))
+    })
+}
+
+// The resends of send_with_options for any kind of transaction, sign returns it signed with the given blockhash
+pub fn send_with<T: SerializableTransaction>(
+    rpc: &RpcClient,
+    options: &SendOptions,
+    sign: impl Fn(Hash) -> ClientResult<T>,
+) -> ClientResult<Signature> {
+    let deadline = Instant::now() + options.timeout;
+    let config = RpcSendTransactionConfig {
+        preflight_commitment: Some(options.commitment.commitment),
+        ..RpcSendTransactionConfig::default()
+    };
+    // the simulation of a transaction, which has been processed already, fails, so only the first send simulates it
+    let resend_config = RpcSendTransactionConfig { skip_preflight: true, ..config };
+
+    for attempt in 1..=options.max_attempts {
+        let (blockhash, last_valid_block_height) = rpc.get_latest_blockhash_with_commitment(options.commitment)?;
+        let transaction = sign(blockhash)?;
+        let signature = *transaction.get_signature();
+
+        let mut last_sent: Option<Instant> = None;
+        loop {
+            let send_config = match last_sent {
+                None => Some(config),
+                Some(sent) if sent.elapsed() >= options.resend_interval => Some(resend_config),
+                Some(_) => None,
+            };
+            if let Some(config) = send_config {
+                match rpc.send_transaction_with_config(&transaction, config) {
+                    Ok(_) => {},
+                    // a failed simulation fails again with the next blockhash
+                    Err(e) if e.get_transaction_error().is_some() => return Err(e),
+                    // the node may be overloaded, waiting and sending again is the best there is
+                    Err(e) => eprintln!("Sending {} failed: {}", signature, e),
+                }
+                last_sent = Some(Instant::now());
+            }
+
+            thread::sleep(options.poll_interval);
+
+            match rpc.get_signature_status_with_commitment(&signature, options.commitment)? {
+                Some(Ok(())) => return Ok(signature),
+                Some(Err(e)) => return Err(e.into()),
+                None => {},
+            }
+
+            if Instant::now() >= deadline {
+                return Err(custom_error(format!(
+                    "Transaction {} wasn't confirmed within {:?}",
+                    signature, options.timeout
+                )));
+            }
+            // no validator accepts the transaction anymore, it needs a new blockhash
+            if rpc.get_block_height_with_commitment(options.commitment)? > last_valid_block_height {
+                eprintln!("Blockhash of {} expired after attempt {}", signature, attempt);
+                break;
+            }
+        }
+    }
+
+    Err(custom_error(format!("Transaction wasn't confirmed after {} attempts", options.max_attempts)))
+}
+
+// Prepends the ComputeBudget instructions the options ask for. The instructions are returned as they are if the
+// options don't ask for any.
+pub fn with_compute_budget(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    instructions: &[Instruction],
+    options: &SendOptions,
+) -> ClientResult<Vec<Instruction>> {
+    with_compute_budget_for(rpc, instructions, options, |instructions| {
+        Ok(Transaction::new_with_payer(instructions, Some(&payer.pubkey())))
+    })
+}
+
+// with_compute_budget for any kind of transaction, unsigned builds the one ComputeUnitLimit::Auto simulates. It is
+// simulated with the highest limit, so it doesn't run out of units, and with the price, because the ComputeBudget
+// instructions take units as well.
+pub fn with_compute_budget_for<T: SerializableTransaction>(
+    rpc: &RpcClient,
+    instructions: &[Instruction],
+    options: &SendOptions,
+    unsigned: impl Fn(&[Instruction]) -> ClientResult<T>,
+) -> ClientResult<Vec<Instruction>> {
+    let limit = match options.compute_unit_limit {
+        ComputeUnitLimit::Default => None,
+        ComputeUnitLimit::Fixed(units) => Some(units),
+        ComputeUnitLimit::Auto => {
+            let simulated = budget_instructions(Some(MAX_COMPUTE_UNIT_LIMIT), options, instructions);
+            Some(simulate_compute_units(rpc, &unsigned(&simulated)?, options)?)
+        },
+    };
+    Ok(budget_instructions(limit, options, instructions))
+}
+
+// The ComputeBudget instructions the options add, with the highest limit, for reserving room in a transaction before
+// the limit is known
+pub(crate) fn budget_placeholder(options: &SendOptions) -> Vec<Instruction> {
+    let limit = match options.compute_unit_limit {
+        ComputeUnitLimit::Default => None,
+        ComputeUnitLimit::Fixed(_) | ComputeUnitLimit::Auto => Some(MAX_COMPUTE_UNIT_LIMIT),
+    };
+    budget_instructions(limit, options, &[])
+}
+
+fn budget_instructions(limit: Option<u32>, options: &SendOptions, instructions: &[Instruction]) -> Vec<Instruction> {
+    let mut budget = Vec::new();
+    if let Some(units) = limit {
+        budget.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+    }
+    if let Some(micro_lamports) = options.compute_unit_price {
+        budget.push(ComputeBudgetInstruction::set_compute_unit_price(micro_lamports));
+    }
+
+    budget.extend_from_slice(instructions);
+    budget
+}
+
+// The units the transaction takes, plus the margin
+fn simulate_compute_units<T: SerializableTransaction>(
+    rpc: &RpcClient,
+    transaction: &T,
+    options: &SendOptions,
+) -> ClientResult<u32> {
+    // the node replaces the blockhash and doesn't check the signature, so the transaction can stay unsigned
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(options.commitment),
+        ..RpcSimulateTransactionConfig::default()
+    };
+
+    let result = rpc.simulate_transaction_with_config(transaction, config)?.value;
+    if let Some(e) = result.err {
+        return Err(e.into());
+    }
+    let units = result.units_consumed.ok_or_else(|| custom_error("Simulation returned no compute units".to_string()))?;
+    let units = units + units * AUTO_COMPUTE_UNIT_MARGIN_PERCENT / 100;
+    Ok(units.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32)
+}
+
+pub(crate) fn custom_error(message: String) -> ClientError {
+    ClientError::from(ClientErrorKind::Custom(message))
+}
@@ -0,0 +1,172 @@
+// Runs instructions through simulateTransaction, which executes them on the node without committing anything, so it
+// costs no fees and needs no signature. The node returns the logs, the compute units, the return data and the state
+// of the accounts after the transaction. Comparing that state with the current one shows what the transaction would
+// change.
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_client::RpcClient,
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+};
+use solana_sdk::{
+    account::Account,
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    transaction::{Transaction, TransactionError},
+};
+
+use crate::{send, state::ProgramState};
+
+// An account written by the transaction, None where it doesn't exist
+pub struct AccountChange {
+    pub address: Pubkey,
+    pub before: Option<Account>,
+    pub after: Option<Account>,
+}
+
+// One difference between an account before and after a transaction
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change {
+    Created,
+    Closed,
+    Lamports { before: u64, after: u64 },
+    // a field of a GreetingAccount or a CalculatorResult
+    Field { state: &'static str, name: &'static str, before: u64, after: u64 },
+    // data which isn't the state of one of the programs
+    Data { before: Vec<u8>, after: Vec<u8> },
+}
+
+impl AccountChange {
+    // Empty if nothing changed, also if the account doesn't exist before and after
+    pub fn changes(&self) -> Vec<Change> {
+        let (before, after) = match (&self.before, &self.after) {
+            (None, None) => return Vec::new(),
+            (None, Some(_)) => return vec![Change::Created],
+            (Some(_), None) => return vec![Change::Closed],
+            (Some(before), Some(after)) => (before, after),
+        };
+
+        let mut changes = Vec::new();
+        if before.lamports != after.lamports {
+            changes.push(Change::Lamports { before: before.lamports, after: after.lamports });
+        }
+        if before.data != after.data {
+            match (ProgramState::detect(&before.data), ProgramState::detect(&after.data)) {
+                (Some(old), Some(new)) if old.name() == new.name() => {
+                    for ((name, before), (_, after)) in old.fields().into_iter().zip(new.fields()) {
+                        if before != after {
+                            changes.push(Change::Field { state: new.name(), name, before, after });
+                        }
+                    }
+                },
+                _ => changes.push(Change::Data { before: before.data.clone(), after: after.data.clone() }),
+            }
+        }
+        changes
+    }
+
+    // The changes indented under the address, the way the simulate and the diff binaries show them:
+    //   <result account>
+    //     CalculatorResult
+    //       result: 2 -> 8
+    pub fn print(&self) {
+        println!("  {}", self.address);
+        let changes = self.changes();
+        if changes.is_empty() {
+            match self.before {
+                Some(_) => println!("    unchanged"),
+                None => println!("    doesn't exist"),
+            }
+        }
+
+        let mut state_shown = false;
+        for change in changes {
+            match change {
+                Change::Created => println!("    created"),
+                Change::Closed => println!("    closed"),
+                Change::Lamports { before, after } => println!("    lamports: {} -> {}", before, after),
+                Change::Field { state, name, before, after } => {
+                    if !state_shown {
+                        println!("    {}", state);
+                        state_shown = true;
+                    }
+                    println!("      {}: {} -> {}", name, before, after);
+                },
+                Change::Data { before, after } => println!("    data: {:?} -> {:?}", before, after),
+            }
+        }
+    }
+}
+
+pub struct Simulation {
+    // the error the transaction would fail with
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: Option<u64>,
+    // the program which set the return data last, and the data
+    pub return_data: Option<(Pubkey, Vec<u8>)>,
+    pub accounts: Vec<AccountChange>,
+}
+
+// Simulates the instructions in a transaction paid by the payer. The payer only needs its address here, the signature
+// isn't verified, and the blockhash is replaced by a recent one. Every writable account of the transaction is
+// compared, the payer too, whose lamports pay the fee.
+pub fn simulate(rpc: &RpcClient, payer: &Pubkey, instructions: &[Instruction]) -> ClientResult<Simulation> {
+    let message = Message::new(instructions, Some(payer));
+    let writable: Vec<Pubkey> = message
+        .account_keys
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| message.is_writable(*index))
+        .map(|(_, address)| *address)
+        .collect();
+
+    let before = rpc.get_multiple_accounts(&writable)?;
+
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(rpc.commitment()),
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: writable.iter().map(Pubkey::to_string).collect(),
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = rpc.simulate_transaction_with_config(&Transaction::new_unsigned(message), config)?.value;
+
+    // the node only returns the accounts if the transaction succeeds
+    let after: Vec<Option<Account>> = match result.accounts {
+        Some(accounts) => accounts.iter().map(|account| account.as_ref().and_then(|account| account.decode())).collect(),
+        None => before.clone(),
+    };
+    let accounts = writable
+        .into_iter()
+        .zip(before.into_iter().zip(after))
+        .map(|(address, (before, after))| AccountChange { address, before, after })
+        .collect();
+
+    let return_data = match result.return_data {
+        Some(return_data) => {
+            let program_id = return_data
+                .program_id
+                .parse()
+                .map_err(|e| send::custom_error(format!("Invalid program id in the return data: {}", e)))?;
+            let data = STANDARD
+                .decode(&return_data.data.0)
+                .map_err(|e| send::custom_error(format!("Invalid return data: {}", e)))?;
+            Some((program_id, data))
+        },
+        None => None,
+    };
+
+    Ok(Simulation {
+        err: result.err,
+        logs: result.logs.unwrap_or_default(),
+        units_consumed: result.units_consumed,
+        return_data,
+        accounts,
+    })
+}
@@ -0,0 +1,154 @@
+// Snapshots of the state accounts of the example programs, for demos and test fixtures which need the same accounts
+// every time. A snapshot is a JSON file with the raw accounts and, for reading it, their decoded state:
+//
+//   {"version": 1, "slot": 1234, "accounts": [{"address": "<pubkey>", "program": "helloworld", "owner": "<id>",
+//    "lamports": 946560, "executable": false, "rent_epoch": 0, "data": "BQAAAA==",
+//    "state": {"name": "GreetingAccount", "fields": {"counter": 5}}}]}
+//
+// The data is what gets restored, the state is only for people and tools reading the file. write_account_files
+// turns a snapshot into the files `solana-test-validator --account <ADDRESS> <FILE>` loads.
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::{client_error::Result as ClientResult, rpc_client::RpcClient};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use crate::state::ProgramState;
+
+// the version of the snapshot file, raised when its format changes
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Snapshot {
+    pub version: u32,
+    // the slot right before the accounts were read
+    pub slot: u64,
+    pub accounts: Vec<SnapshotAccount>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnapshotAccount {
+    pub address: String,
+    // the name of the program in the cluster profiles, like helloworld
+    pub program: String,
+    pub owner: String,
+    pub lamports: u64,
+    pub executable: bool,
+    pub rent_epoch: u64,
+    // base64
+    pub data: String,
+    // None if the data is neither a GreetingAccount nor a CalculatorResult
+    pub state: Option<SnapshotState>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnapshotState {
+    pub name: String,
+    pub fields: BTreeMap<String, u64>,
+}
+
+impl SnapshotAccount {
+    fn new(program: &str, address: &Pubkey, account: &Account) -> Self {
+        let state = ProgramState::detect(&account.data).map(|state| SnapshotState {
+            name: state.name().to_string(),
+            fields: state.fields().into_iter().map(|(name, value)| (name.to_string(), value)).collect(),
+        });
+        Self {
+            address: address.to_string(),
+            program: program.to_string(),
+            owner: account.owner.to_string(),
+            lamports: account.lamports,
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+            data: STANDARD.encode(&account.data),
+            state,
+        }
+    }
+
+    // The account as it was, the decoded state is ignored
+    pub fn to_account(&self) -> Result<(Pubkey, Account), Box<dyn Error>> {
+        let address = self.address.parse().map_err(|e| format!("Invalid address {}: {}", self.address, e))?;
+        let owner = self.owner.parse().map_err(|e| format!("Invalid owner {} of {}: {}", self.owner, self.address, e))?;
+        let data = STANDARD.decode(&self.data).map_err(|e| format!("Invalid data of {}: {}", self.address, e))?;
+        let account = Account {
+            lamports: self.lamports,
+            data,
+            owner,
+            executable: self.executable,
+            rent_epoch: self.rent_epoch,
+        };
+        Ok((address, account))
+    }
+}
+
+impl Snapshot {
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e).into())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let json = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let snapshot: Self =
+            serde_json::from_str(&json).map_err(|e| format!("Invalid snapshot {}: {}", path.display(), e))?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            let message = format!("Snapshot version {} isn't supported, only {}", snapshot.version, SNAPSHOT_VERSION);
+            return Err(message.into());
+        }
+        Ok(snapshot)
+    }
+
+    // Writes every account into <dir>/<address>.json, in the format of `solana account --output json` which
+    // solana-test-validator reads, and returns the address and the file of each, the arguments of --account
+    pub fn write_account_files(&self, dir: &Path) -> Result<Vec<(Pubkey, PathBuf)>, Box<dyn Error>> {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let mut files = Vec::new();
+        for snapshot_account in &self.accounts {
+            let (address, account) = snapshot_account.to_account()?;
+            let file = json_account(&address, &account);
+            let path = dir.join(format!("{}.json", address));
+            fs::write(&path, serde_json::to_string_pretty(&file)?)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            files.push((address, path));
+        }
+        Ok(files)
+    }
+}
+
+// Every account the programs own, the programs are given by their name in the profiles and their id
+pub fn take_snapshot(rpc: &RpcClient, programs: &[(&str, Pubkey)]) -> ClientResult<Snapshot> {
+    let slot = rpc.get_slot()?;
+    let mut accounts = Vec::new();
+    for (program, program_id) in programs {
+        let mut program_accounts = rpc.get_program_accounts(program_id)?;
+        // the node returns them in any order, sorted the snapshots of the same state are the same
+        program_accounts.sort_by_key(|(address, _)| *address);
+        for (address, account) in &program_accounts {
+            accounts.push(SnapshotAccount::new(program, address, account));
+        }
+    }
+    Ok(Snapshot { version: SNAPSHOT_VERSION, slot, accounts })
+}
+
+#[derive(Serialize)]
+struct JsonAccount {
+    pubkey: String,
+    account: UiAccount,
+}
+
+fn json_account(address: &Pubkey, account: &Account) -> JsonAccount {
+    JsonAccount {
+        pubkey: address.to_string(),
+        account: UiAccount::encode(address, account, UiAccountEncoding::Base64, None, None),
+    }
+}
@@ -0,0 +1,51 @@
+// The state accounts of both programs, decoded without knowing which program owns them. The state structs have no
+// discriminator and the program ids depend on the deploy keypair, so the size is all there is to go by. Both
+// programs reject accounts of any other size.
+use borsh::BorshDeserialize;
+
+use crate::{
+    calculator::{self, CalculatorResult},
+    greeting::{self, GreetingAccount},
+};
+
+#[derive(Debug)]
+pub enum ProgramState {
+    Greeting(GreetingAccount),
+    Calculator(CalculatorResult),
+}
+
+impl ProgramState {
+    // None if the size matches neither state
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.len() == greeting::greeting_account_size() {
+            Self::greeting(data).ok()
+        } else if data.len() == calculator::calculator_result_size() {
+            Self::calculator(data).ok()
+        } else {
+            None
+        }
+    }
+
+    pub fn greeting(data: &[u8]) -> std::io::Result<Self> {
+        Ok(Self::Greeting(GreetingAccount::try_from_slice(data)?))
+    }
+
+    pub fn calculator(data: &[u8]) -> std::io::Result<Self> {
+        Ok(Self::Calculator(CalculatorResult::try_from_slice(data)?))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Greeting(_) => "GreetingAccount",
+            Self::Calculator(_) => "CalculatorResult",
+        }
+    }
+
+    // The fields in the order the struct declares them, all of them are integers
+    pub fn fields(&self) -> Vec<(&'static str, u64)> {
+        match self {
+            Self::Greeting(greeting) => vec![("counter", greeting.counter as u64)],
+            Self::Calculator(result) => vec![("result", result.result), ("a", result.a), ("b", result.b)],
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// Client of the streaming program, the side a keeper needs: the streams which can be cranked and the crank
+#[cfg(feature = "rpc")]
+use solana_client::{client_error::Result as ClientResult, rpc_client::RpcClient, rpc_request::TokenAccountsFilter};
+#[cfg(feature = "rpc")]
+use solana_sdk::{clock::UnixTimestamp, sysvar::clock::{self, Clock}};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+pub use streaming::state::{Stream, CRANK_INTERVAL, CRANK_REWARD};
+
+// the tag of Crank, the instruction has no other data
+const CRANK_TAG: u8 = 3;
+
+// Pushes what has been streamed to the recipient's token account and pays the cranker the reward
+pub fn crank_instruction(
+    program_id: &Pubkey,
+    cranker: &Pubkey,
+    stream_address: &Pubkey,
+    stream: &Stream,
+    recipient_token_account: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &[CRANK_TAG],
+        vec![
+            AccountMeta::new(*cranker, true),
+            AccountMeta::new(*stream_address, false),
+            AccountMeta::new(stream.vault, false),
+            AccountMeta::new(*recipient_token_account, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    )
+}
+
+// Every stream of the program with its address
+#[cfg(feature = "rpc")]
+pub fn streams(rpc: &RpcClient, program_id: &Pubkey) -> ClientResult<Vec<(Pubkey, Stream)>> {
+    crate::get_program_states(rpc, program_id, Stream::LEN, vec![])
+}
+
+// The time of the cluster, which is what the program compares the schedule with, not the clock of this machine
+#[cfg(feature = "rpc")]
+pub fn cluster_time(rpc: &RpcClient) -> ClientResult<UnixTimestamp> {
+    let clock: Clock = bincode::deserialize(&rpc.get_account_data(&clock::id())?)
+        .map_err(|e| crate::send::custom_error(format!("Invalid Clock sysvar: {}", e)))?;
+    Ok(clock.unix_timestamp)
+}
+
+// A token account of the recipient for the mint of the stream, the crank may only push tokens there. None if the
+// recipient has none.
+#[cfg(feature = "rpc")]
+pub fn recipient_token_account(rpc: &RpcClient, stream: &Stream) -> ClientResult<Option<Pubkey>> {
+    let accounts = rpc.get_token_accounts_by_owner(&stream.recipient, TokenAccountsFilter::Mint(stream.mint))?;
+    accounts
+        .first()
+        .map(|account| {
+            account
+                .pubkey
+                .parse()
+                .map_err(|e| crate::send::custom_error(format!("Invalid address {}: {}", account.pubkey, e)))
+        })
+        .transpose()
+}
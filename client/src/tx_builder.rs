@@ -0,0 +1,270 @@
+// Collects the instructions of a flow, like creating the greeting account and greeting it, with the signers they
+// need, and packs them into as few transactions as fit. A transaction is at most 1232 bytes and locks at most 64
+// accounts, a flow going over that is split into several transactions, sent one after the other. Instructions added
+// together as a group always end up in the same transaction.
+use std::{error::Error, fmt};
+
+#[cfg(feature = "rpc")]
+use solana_client::{client_error::Result as ClientResult, rpc_client::RpcClient};
+use solana_sdk::{
+    instruction::Instruction, message::Message, packet::PACKET_DATA_SIZE, signature::Signature, signer::Signer,
+    transaction::Transaction,
+};
+
+#[cfg(feature = "rpc")]
+use crate::send::{self, SendOptions};
+
+// the runtime refuses transactions locking more accounts
+pub const MAX_ACCOUNTS: usize = 64;
+
+// The instructions of one transaction and the signers it needs besides the payer
+pub struct Batch<'a> {
+    pub instructions: Vec<Instruction>,
+    pub signers: Vec<&'a dyn Signer>,
+}
+
+// A group which doesn't fit into a transaction on its own
+#[derive(Debug)]
+pub struct GroupTooLarge {
+    // the position of the group among the ones added
+    pub group: usize,
+    pub size: usize,
+    pub accounts: usize,
+}
+
+impl fmt::Display for GroupTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Group {} needs {} bytes and {} accounts, a transaction has room for {} bytes and {} accounts",
+            self.group, self.size, self.accounts, PACKET_DATA_SIZE, MAX_ACCOUNTS
+        )
+    }
+}
+
+impl Error for GroupTooLarge {}
+
+pub struct TxBuilder<'a> {
+    payer: &'a dyn Signer,
+    groups: Vec<Batch<'a>>,
+}
+
+impl<'a> TxBuilder<'a> {
+    pub fn new(payer: &'a dyn Signer) -> Self {
+        Self { payer, groups: Vec::new() }
+    }
+
+    pub fn add(&mut self, instruction: Instruction) -> &mut Self {
+        self.add_group(vec![instruction], &[])
+    }
+
+    // An instruction which needs signatures of other keys than the payer, like a new account signing its creation
+    pub fn add_signed(&mut self, instruction: Instruction, signers: &[&'a dyn Signer]) -> &mut Self {
+        self.add_group(vec![instruction], signers)
+    }
+
+    // Instructions which only work together, like creating an account and initializing it. They are never split.
+    pub fn add_group(&mut self, instructions: Vec<Instruction>, signers: &[&'a dyn Signer]) -> &mut Self {
+        self.groups.push(Batch { instructions, signers: signers.to_vec() });
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.iter().map(|group| group.instructions.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // The size all instructions would take in a single transaction, signatures included
+    pub fn estimated_size(&self) -> usize {
+        let instructions: Vec<Instruction> = self.groups.iter().flat_map(|group| group.instructions.clone()).collect();
+        measure(self.payer, &[], &instructions).0
+    }
+
+    // Packs the groups in order into as few transactions as fit
+    pub fn batches(&self) -> Result<Vec<Batch<'a>>, GroupTooLarge> {
+        self.pack(&[])
+    }
+
+    // Sends the transactions one after the other, each one is confirmed before the next one is sent, so a later
+    // instruction can rely on an earlier one. Returns the signatures in order. If a transaction fails, the ones
+    // before it stay committed.
+    #[cfg(feature = "rpc")]
+    pub fn send(&self, rpc: &RpcClient, options: &SendOptions) -> ClientResult<Vec<Signature>> {
+        // the ComputeBudget instructions go into every transaction, room is left for them
+        let batches = self.pack(&send::budget_placeholder(options)).map_err(|e| send::custom_error(e.to_string()))?;
+
+        let mut signatures = Vec::new();
+        for batch in batches {
+            let instructions = send::with_compute_budget(rpc, self.payer, &batch.instructions, options)?;
+            let signature = send::send_with(rpc, options, |blockhash| {
+                let mut signers = vec![self.payer];
+                add_signers(&mut signers, &batch.signers);
+                Ok(Transaction::new_signed_with_payer(&instructions, Some(&self.payer.pubkey()), &signers, blockhash))
+            })?;
+            signatures.push(signature);
+        }
+        Ok(signatures)
+    }
+
+    // every transaction starts with the prefix instructions
+    fn pack(&self, prefix: &[Instruction]) -> Result<Vec<Batch<'a>>, GroupTooLarge> {
+        let fits = |instructions: &[Instruction]| {
+            let (size, accounts) = measure(self.payer, prefix, instructions);
+            size <= PACKET_DATA_SIZE && accounts <= MAX_ACCOUNTS
+        };
+        let mut batches: Vec<Batch<'a>> = Vec::new();
+        let mut current = Batch { instructions: Vec::new(), signers: Vec::new() };
+
+        for (index, group) in self.groups.iter().enumerate() {
+            let mut candidate = current.instructions.clone();
+            candidate.extend_from_slice(&group.instructions);
+            if fits(&candidate) {
+                current.instructions = candidate;
+                add_signers(&mut current.signers, &group.signers);
+                continue;
+            }
+
+            if !fits(&group.instructions) {
+                let (size, accounts) = measure(self.payer, prefix, &group.instructions);
+                return Err(GroupTooLarge { group: index, size, accounts });
+            }
+            if !current.instructions.is_empty() {
+                batches.push(current);
+            }
+            current = Batch { instructions: group.instructions.clone(), signers: Vec::new() };
+            add_signers(&mut current.signers, &group.signers);
+        }
+
+        if !current.instructions.is_empty() {
+            batches.push(current);
+        }
+        Ok(batches)
+    }
+}
+
+// The serialized size and the number of accounts of the transaction. The signatures are empty, they are as big as
+// real ones.
+fn measure(payer: &dyn Signer, prefix: &[Instruction], instructions: &[Instruction]) -> (usize, usize) {
+    let mut all = prefix.to_vec();
+    all.extend_from_slice(instructions);
+    let message = Message::new(&all, Some(&payer.pubkey()));
+    let accounts = message.account_keys.len();
+    let transaction = Transaction {
+        signatures: vec![Signature::default(); message.header.num_required_signatures as usize],
+        message,
+    };
+    // serializing into a Vec can't fail
+    (bincode::serialized_size(&transaction).unwrap() as usize, accounts)
+}
+
+// a key signing several instructions signs the transaction once
+fn add_signers<'a>(signers: &mut Vec<&'a dyn Signer>, new: &[&'a dyn Signer]) {
+    for signer in new {
+        let pubkey = signer.pubkey();
+        if !signers.iter().any(|signer| signer.pubkey() == pubkey) {
+            signers.push(*signer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+
+    use super::*;
+
+    // An instruction without accounts, only its data makes it bigger
+    fn instruction(program_id: &Pubkey, data_len: usize) -> Instruction {
+        Instruction::new_with_bytes(*program_id, &vec![0; data_len], vec![])
+    }
+
+    // The length of the data which makes the instructions plus one more exactly fill the given size. The length of
+    // the data is encoded in one to three bytes, so it is searched instead of worked out.
+    fn data_len_to_fill(payer: &Keypair, prefix: &[Instruction], instructions: &[Instruction], size: usize) -> usize {
+        let program_id = instructions[0].program_id;
+        (0..size)
+            .find(|&data_len| {
+                let mut all = instructions.to_vec();
+                all.push(instruction(&program_id, data_len));
+                measure(payer, prefix, &all).0 == size
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn exactly_at_the_limit_is_one_transaction() {
+        let (payer, program_id) = (Keypair::new(), Pubkey::new_unique());
+        let first = instruction(&program_id, 100);
+        let data_len = data_len_to_fill(&payer, &[], std::slice::from_ref(&first), PACKET_DATA_SIZE);
+
+        let mut builder = TxBuilder::new(&payer);
+        builder.add(first).add(instruction(&program_id, data_len));
+        assert_eq!(builder.estimated_size(), PACKET_DATA_SIZE);
+
+        let batches = builder.batches().unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].instructions.len(), 2);
+    }
+
+    #[test]
+    fn one_byte_over_the_limit_is_split() {
+        let (payer, program_id) = (Keypair::new(), Pubkey::new_unique());
+        let first = instruction(&program_id, 100);
+        let data_len = data_len_to_fill(&payer, &[], std::slice::from_ref(&first), PACKET_DATA_SIZE) + 1;
+
+        let mut builder = TxBuilder::new(&payer);
+        builder.add(first).add(instruction(&program_id, data_len));
+        assert_eq!(builder.estimated_size(), PACKET_DATA_SIZE + 1);
+
+        let batches = builder.batches().unwrap();
+        assert_eq!(batches.iter().map(|batch| batch.instructions.len()).collect::<Vec<_>>(), vec![1, 1]);
+        assert_eq!(batches[1].instructions[0].data.len(), data_len);
+    }
+
+    #[test]
+    fn group_is_never_split() {
+        let (payer, program_id) = (Keypair::new(), Pubkey::new_unique());
+        let first = instruction(&program_id, 0);
+        // the group fills a transaction on its own, the instruction in front of it doesn't fit with it
+        let data_len = data_len_to_fill(&payer, &[], std::slice::from_ref(&first), PACKET_DATA_SIZE);
+
+        let mut builder = TxBuilder::new(&payer);
+        builder.add(instruction(&program_id, 10)).add_group(vec![first, instruction(&program_id, data_len)], &[]);
+        let batches = builder.batches().unwrap();
+        assert_eq!(batches.iter().map(|batch| batch.instructions.len()).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn oversized_group_is_refused() {
+        let (payer, program_id) = (Keypair::new(), Pubkey::new_unique());
+        let mut builder = TxBuilder::new(&payer);
+        builder.add(instruction(&program_id, 10)).add(instruction(&program_id, PACKET_DATA_SIZE));
+
+        let Err(error) = builder.batches() else { panic!("the group fits") };
+        assert_eq!(error.group, 1);
+        assert!(error.size > PACKET_DATA_SIZE);
+    }
+
+    #[test]
+    fn every_transaction_has_room_for_the_prefix() {
+        let (payer, program_id) = (Keypair::new(), Pubkey::new_unique());
+        let prefix = [instruction(&Pubkey::new_unique(), 8)];
+        let first = instruction(&program_id, 100);
+        // fits exactly without the prefix
+        let data_len = data_len_to_fill(&payer, &[], std::slice::from_ref(&first), PACKET_DATA_SIZE);
+
+        let mut builder = TxBuilder::new(&payer);
+        builder.add(first).add(instruction(&program_id, data_len));
+        assert_eq!(builder.batches().unwrap().len(), 1);
+
+        // the prefix isn't part of the batches, it is only measured with each
+        let batches = builder.pack(&prefix).unwrap();
+        assert_eq!(batches.iter().map(|batch| batch.instructions.len()).collect::<Vec<_>>(), vec![1, 1]);
+        for batch in &batches {
+            assert!(measure(&payer, &prefix, &batch.instructions).0 <= PACKET_DATA_SIZE);
+        }
+    }
+}
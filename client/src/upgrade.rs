@@ -0,0 +1,105 @@
+// The lifecycle of a program deployed with the upgradeable loader. A new version is written into a buffer account
+// first, which takes many transactions, and then swapped in with a single upgrade instruction. The upgrade authority
+// may hand its role to another key or give it up, which makes the program immutable.
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    client_error::Result as ClientResult,
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+use crate::send::{self, SendOptions};
+
+// the bytes of a write instruction a transaction has room for, next to the signature, the accounts and the header
+pub const WRITE_CHUNK_SIZE: usize = 900;
+
+// Creates a buffer with the payer as authority and writes the program into it, one transaction per chunk. Returns
+// the address of the buffer, which upgrade takes.
+pub fn write_buffer(rpc: &RpcClient, payer: &dyn Signer, program: &[u8]) -> ClientResult<Pubkey> {
+    let buffer = Keypair::new();
+    let lamports = rpc.get_minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_buffer(program.len()))?;
+    let create = bpf_loader_upgradeable::create_buffer(
+        &payer.pubkey(),
+        &buffer.pubkey(),
+        &payer.pubkey(),
+        lamports,
+        program.len(),
+    )
+    .map_err(|e| send::custom_error(format!("Failed to build the buffer instructions: {}", e)))?;
+    // the new account signs its creation
+    send_signed(rpc, payer, &[&buffer], &create)?;
+    eprintln!("Created buffer {} for {} bytes", buffer.pubkey(), program.len());
+
+    for (index, chunk) in program.chunks(WRITE_CHUNK_SIZE).enumerate() {
+        let offset = (index * WRITE_CHUNK_SIZE) as u32;
+        let write = bpf_loader_upgradeable::write(&buffer.pubkey(), &payer.pubkey(), offset, chunk.to_vec());
+        crate::send_instructions(rpc, payer, &[write])?;
+    }
+
+    Ok(buffer.pubkey())
+}
+
+// Replaces the program with the one in the buffer. The buffer is closed by the loader, its lamports and the ones the
+// program data account doesn't need anymore go to the spill account.
+pub fn upgrade(
+    rpc: &RpcClient,
+    authority: &dyn Signer,
+    program_id: &Pubkey,
+    buffer: &Pubkey,
+    spill: &Pubkey,
+) -> ClientResult<Signature> {
+    let upgrade = bpf_loader_upgradeable::upgrade(program_id, buffer, &authority.pubkey(), spill);
+    crate::send_instructions(rpc, authority, &[upgrade])
+}
+
+// Hands the upgrade authority to another key. None revokes it for good, nobody can upgrade the program anymore.
+pub fn set_upgrade_authority(
+    rpc: &RpcClient,
+    authority: &dyn Signer,
+    program_id: &Pubkey,
+    new_authority: Option<&Pubkey>,
+) -> ClientResult<Signature> {
+    let set_authority = bpf_loader_upgradeable::set_upgrade_authority(program_id, &authority.pubkey(), new_authority);
+    crate::send_instructions(rpc, authority, &[set_authority])
+}
+
+// Closes a buffer which is no longer needed, for example after a failed write, and returns its lamports
+pub fn close_buffer(rpc: &RpcClient, authority: &dyn Signer, buffer: &Pubkey, recipient: &Pubkey) -> ClientResult<Signature> {
+    let close = bpf_loader_upgradeable::close(buffer, recipient, &authority.pubkey());
+    crate::send_instructions(rpc, authority, &[close])
+}
+
+// The buffers of an authority. A buffer starts with the bincode encoded UpgradeableLoaderState::Buffer, a four byte
+// variant index of 1, then Some as a single 1 byte, then the authority.
+pub fn buffers(rpc: &RpcClient, authority: &Pubkey) -> ClientResult<Vec<(Pubkey, u64)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![1, 0, 0, 0, 1])),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(5, authority.to_bytes().to_vec())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = rpc.get_program_accounts_with_config(&bpf_loader_upgradeable::id(), config)?;
+    Ok(accounts.into_iter().map(|(address, account)| (address, account.lamports)).collect())
+}
+
+fn send_signed(rpc: &RpcClient, payer: &dyn Signer, signers: &[&dyn Signer], instructions: &[Instruction]) -> ClientResult<Signature> {
+    send::send_with(rpc, &SendOptions::default(), |blockhash| {
+        let mut all_signers = vec![payer];
+        all_signers.extend_from_slice(signers);
+        Ok(Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &all_signers, blockhash))
+    })
+}
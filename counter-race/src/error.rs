@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum RaceError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Race Address")]
+    InvalidRaceAddress,
+    #[error("Invalid Team Address")]
+    InvalidTeamAddress,
+    #[error("Invalid Team Count")]
+    InvalidTeamCount,
+    #[error("Deadline In The Past")]
+    DeadlineInPast,
+    #[error("Race Over")]
+    RaceOver,
+    #[error("Race Still Running")]
+    RaceStillRunning,
+    #[error("Already Finalized")]
+    AlreadyFinalized,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<RaceError> for ProgramError {
+    fn from(e: RaceError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,74 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::RaceError::InvalidInstruction;
+
+pub enum RaceInstruction {
+
+    // Creates a race in a PDA derived from ["race", creator, id] together with a counter PDA for every team,
+    // derived from ["team", race, index].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The creator
+    // 1. `[writable]` The race PDA
+    // 2. `[]` The system program
+    // 3.. `[writable]` The team PDAs, in the order of their index
+    CreateRace {
+        id: u64,
+        team_count: u8,
+        fee: u64,
+        deadline: i64,
+    },
+
+    // Pays the fee into the counter of a team and increments it. The race account is only read, so
+    // increments of different teams don't lock the same accounts.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The player
+    // 1. `[]` The race PDA
+    // 2. `[writable]` The team PDA
+    // 3. `[]` The system program
+    Increment,
+
+    // Records the team with the highest count as the winner after the deadline and sends the collected
+    // fees to the creator. Anyone may call it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The race PDA
+    // 1. `[writable]` The creator
+    // 2.. `[writable]` The team PDAs, in the order of their index
+    Finalize,
+}
+
+impl RaceInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateRace {
+                id: Self::unpack_u64(rest, 0)?,
+                team_count: *rest.get(8).ok_or(InvalidInstruction)?,
+                fee: Self::unpack_u64(rest, 9)?,
+                deadline: Self::unpack_u64(rest, 17)? as i64,
+            },
+            1 => Self::Increment,
+            2 => Self::Finalize,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
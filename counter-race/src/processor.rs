@@ -0,0 +1,286 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::RaceInstruction,
+    error::RaceError,
+    state::{Race, Team, MAX_TEAMS},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = RaceInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            RaceInstruction::CreateRace { id, team_count, fee, deadline } => {
+                msg!("Instruction: CreateRace");
+                Self::process_create_race(accounts, id, team_count, fee, deadline, program_id)
+            },
+            RaceInstruction::Increment => {
+                msg!("Instruction: Increment");
+                Self::process_increment(accounts, program_id)
+            },
+            RaceInstruction::Finalize => {
+                msg!("Instruction: Finalize");
+                Self::process_finalize(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_race(
+        accounts: &[AccountInfo],
+        id: u64,
+        team_count: u8,
+        fee: u64,
+        deadline: i64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if !(2..=MAX_TEAMS).contains(&team_count) {
+            return Err(RaceError::InvalidTeamCount.into());
+        }
+        if deadline <= Clock::get()?.unix_timestamp {
+            return Err(RaceError::DeadlineInPast.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let race_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (race_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"race", creator.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if race_pda != *race_account.key {
+            return Err(RaceError::InvalidRaceAddress.into());
+        }
+
+        if !race_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating race account with {} teams...", team_count);
+        Self::create_pda_account(
+            creator,
+            race_account,
+            system_program,
+            Race::LEN,
+            program_id,
+            &[b"race", creator.key.as_ref(), &id_bytes, &[bump_seed]],
+        )?;
+
+        let race_info = Race {
+            is_initialized: true,
+            creator: *creator.key,
+            id,
+            team_count,
+            fee,
+            deadline,
+            finalized: false,
+            winning_team: 0,
+            bump_seed,
+        };
+        race_info.serialize(&mut &mut race_account.data.borrow_mut()[..])?;
+
+        for index in 0..team_count {
+            let team_account = next_account_info(account_info_iter)?;
+            let (team_pda, team_bump_seed) = Pubkey::find_program_address(
+                &[b"team", race_account.key.as_ref(), &[index]],
+                program_id,
+            );
+            if team_pda != *team_account.key {
+                return Err(RaceError::InvalidTeamAddress.into());
+            }
+
+            msg!("Creating counter of team {}...", index);
+            Self::create_pda_account(
+                creator,
+                team_account,
+                system_program,
+                Team::LEN,
+                program_id,
+                &[b"team", race_account.key.as_ref(), &[index], &[team_bump_seed]],
+            )?;
+
+            let team_info = Team {
+                is_initialized: true,
+                race: *race_account.key,
+                index,
+                count: 0,
+                bump_seed: team_bump_seed,
+            };
+            team_info.serialize(&mut &mut team_account.data.borrow_mut()[..])?;
+        }
+
+        Ok(())
+    }
+
+    fn process_increment(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let player = next_account_info(account_info_iter)?;
+
+        if !player.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let race_account = next_account_info(account_info_iter)?;
+        let race_info = Self::unpack_race(race_account, program_id)?;
+        if Clock::get()?.unix_timestamp >= race_info.deadline {
+            return Err(RaceError::RaceOver.into());
+        }
+
+        let team_account = next_account_info(account_info_iter)?;
+        let mut team_info = Self::unpack_team(team_account, race_account, program_id)?;
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        // The fee is collected by the team account instead of the race account. A transaction locks every
+        // account it writes, if all increments wrote to the race they would all wait for each other.
+        msg!("Calling the system program to pay the fee of {} lamports...", race_info.fee);
+        invoke(
+            &system_instruction::transfer(player.key, team_account.key, race_info.fee),
+            &[
+                player.clone(),
+                team_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        team_info.count += 1;
+        msg!("Team {} is at {}", team_info.index, team_info.count);
+        team_info.serialize(&mut &mut team_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_finalize(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let race_account = next_account_info(account_info_iter)?;
+        let mut race_info = Self::unpack_race(race_account, program_id)?;
+
+        if Clock::get()?.unix_timestamp < race_info.deadline {
+            return Err(RaceError::RaceStillRunning.into());
+        }
+        if race_info.finalized {
+            return Err(RaceError::AlreadyFinalized.into());
+        }
+
+        let creator = next_account_info(account_info_iter)?;
+        if race_info.creator != *creator.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let team_rent = Rent::get()?.minimum_balance(Team::LEN);
+        let mut best_count = 0;
+
+        for index in 0..race_info.team_count {
+            let team_account = next_account_info(account_info_iter)?;
+            let team_info = Self::unpack_team(team_account, race_account, program_id)?;
+            if team_info.index != index {
+                return Err(RaceError::InvalidTeamAddress.into());
+            }
+
+            // on a tie the team with the lower index wins, it reached the count first in the account order
+            if team_info.count > best_count {
+                best_count = team_info.count;
+                race_info.winning_team = index;
+            }
+
+            // the team accounts are owned by the program, so the fees are moved out directly
+            let fees = team_account.lamports().saturating_sub(team_rent);
+            **team_account.lamports.borrow_mut() -= fees;
+            **creator.lamports.borrow_mut() = creator.lamports()
+                .checked_add(fees)
+                .ok_or(RaceError::AmountOverflow)?;
+        }
+
+        msg!("Team {} wins with {} increments", race_info.winning_team, best_count);
+        race_info.finalized = true;
+        race_info.serialize(&mut &mut race_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn unpack_race(race_account: &AccountInfo, program_id: &Pubkey) -> Result<Race, ProgramError> {
+        if race_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let race_info = Race::try_from_slice(&race_account.data.borrow())?;
+        if !race_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(race_info)
+    }
+
+    fn unpack_team(team_account: &AccountInfo, race_account: &AccountInfo, program_id: &Pubkey) -> Result<Team, ProgramError> {
+        if team_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let team_info = Team::try_from_slice(&team_account.data.borrow())?;
+        if !team_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if team_info.race != *race_account.key {
+            return Err(RaceError::InvalidTeamAddress.into());
+        }
+
+        Ok(team_info)
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,38 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Most teams a single race can have, Finalize has to read all of them in one transaction
+pub const MAX_TEAMS: u8 = 8;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Race {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub id: u64,
+    pub team_count: u8,
+    // lamports paid for every increment
+    pub fee: u64,
+    // increments are accepted until this unix timestamp
+    pub deadline: i64,
+    pub finalized: bool,
+    // only meaningful once finalized
+    pub winning_team: u8,
+    pub bump_seed: u8,
+}
+
+impl Race {
+    pub const LEN: usize = 1 + 32 + 8 + 1 + 8 + 8 + 1 + 1 + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Team {
+    pub is_initialized: bool,
+    pub race: Pubkey,
+    pub index: u8,
+    pub count: u64,
+    pub bump_seed: u8,
+}
+
+impl Team {
+    pub const LEN: usize = 1 + 32 + 1 + 8 + 1;
+}
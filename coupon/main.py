@@ -0,0 +1,255 @@
+import yaml
+import sys
+import json
+import time
+
+from os import path
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'coupon.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'coupon-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def mint_token(token: Token, minter: Keypair, account: PublicKey, amount: int):
+    print(f"Minting {amount} tokens to {account}")
+    token.mint_to(
+        dest=account,
+        mint_authority=minter,
+        amount=amount,
+        opts=TxOpts(skip_confirmation=False)
+    )
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+def get_campaign_address(merchant: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("campaign", encoding="utf8"), bytes(merchant), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def get_redemption_address(mint: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("redemption", encoding="utf8"), bytes(mint)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def create_campaign(merchant: Keypair, id: int, starts_at: int, expires_at: int) -> PublicKey:
+    campaign = get_campaign_address(merchant.public_key, id)
+    print(f"\nCreating campaign {campaign}, coupons can be redeemed from {starts_at} to {expires_at}")
+    send(
+        merchant,
+        (0).to_bytes(1, byteorder='little') + id.to_bytes(8, byteorder='little')
+            + starts_at.to_bytes(8, byteorder='little', signed=True) + expires_at.to_bytes(8, byteorder='little', signed=True),
+        [
+            AccountMeta(pubkey=merchant.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=campaign, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return campaign
+
+def issue_coupon(merchant: Keypair, campaign: PublicKey, recipient: PublicKey) -> tuple:
+    # every coupon is its own mint, controlled by the campaign PDA
+    coupon = Token.create_mint(
+        conn=client,
+        payer=merchant,
+        mint_authority=campaign,
+        decimals=0,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    recipient_account = create_token_account(coupon, recipient)
+    print(f"\nIssuing coupon {coupon.pubkey} to {recipient}")
+    issue(merchant, campaign, coupon.pubkey, recipient_account)
+    return coupon, recipient_account
+
+def issue(merchant: Keypair, campaign: PublicKey, mint: PublicKey, destination: PublicKey):
+    send(
+        merchant,
+        (1).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=merchant.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=campaign, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=mint, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_redemption_address(mint), is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def redeem(holder: Keypair, campaign: PublicKey, mint: PublicKey, holder_account: PublicKey):
+    print(f"\n{holder.public_key} redeems coupon {mint}")
+    send(
+        holder,
+        (2).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=holder.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=campaign, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=mint, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=holder_account, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_redemption_address(mint), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def is_redeemed(mint: PublicKey) -> bool:
+    # checkout only has to look at the redemption PDA of the coupon
+    return client.get_account_info(get_redemption_address(mint))["result"]["value"] is not None
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    customer = setup_user_keypair()
+    now = int(time.time())
+
+    campaign = create_campaign(payer, now, now - 60, now + 3600)
+    coupon, customer_account = issue_coupon(payer, campaign, customer.public_key)
+    print(f"Customer holds {token_balance(coupon, customer_account)} coupon, redeemed: {is_redeemed(coupon.pubkey)}")
+
+    redeem(customer, campaign, coupon.pubkey, customer_account)
+    print(f"Customer holds {token_balance(coupon, customer_account)} coupon, redeemed: {is_redeemed(coupon.pubkey)}")
+
+    try:
+        issue(payer, campaign, coupon.pubkey, customer_account)
+    except Exception as e:
+        print(f"A redeemed coupon can't be issued again: {e}")
+
+    expired_campaign = create_campaign(payer, now + 1, now - 3600, now - 60)
+    expired_coupon, expired_account = issue_coupon(payer, expired_campaign, customer.public_key)
+    try:
+        redeem(customer, expired_campaign, expired_coupon.pubkey, expired_account)
+    except Exception as e:
+        print(f"The campaign is over, the coupon can't be redeemed anymore: {e}")
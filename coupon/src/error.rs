@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum CouponError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Campaign Address")]
+    InvalidCampaignAddress,
+    #[error("Invalid Redemption Address")]
+    InvalidRedemptionAddress,
+    #[error("Invalid Window")]
+    InvalidWindow,
+    #[error("Invalid Coupon Mint")]
+    InvalidCouponMint,
+    #[error("Not The Merchant")]
+    NotTheMerchant,
+    #[error("Campaign Not Started")]
+    CampaignNotStarted,
+    #[error("Campaign Expired")]
+    CampaignExpired,
+    #[error("Already Redeemed")]
+    AlreadyRedeemed,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<CouponError> for ProgramError {
+    fn from(e: CouponError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
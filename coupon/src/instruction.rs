@@ -0,0 +1,76 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::CouponError::InvalidInstruction;
+
+pub enum CouponInstruction {
+
+    // Creates a campaign in a PDA derived from ["campaign", merchant, id]. Followed by the u64 id and the
+    // i64 start and expiry of the redemption window.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The merchant
+    // 1. `[writable]` The campaign PDA
+    // 2. `[]` The system program
+    CreateCampaign {
+        id: u64,
+        starts_at: i64,
+        expires_at: i64,
+    },
+
+    // Issues a coupon by minting the single token of a fresh mint. The mint must have 0 decimals, no supply
+    // and the campaign PDA as mint authority. The redemption PDA of the mint must not exist yet.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The merchant
+    // 1. `[writable]` The campaign PDA
+    // 2. `[writable]` The coupon mint
+    // 3. `[writable]` The recipient's token account of the coupon mint
+    // 4. `[]` The redemption PDA of the coupon mint
+    // 5. `[]` The token program
+    IssueCoupon,
+
+    // Burns the coupon token and creates the redemption PDA derived from ["redemption", mint].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The holder, pays for the redemption account
+    // 1. `[writable]` The campaign PDA
+    // 2. `[writable]` The coupon mint
+    // 3. `[writable]` The holder's token account of the coupon mint
+    // 4. `[writable]` The redemption PDA
+    // 5. `[]` The system program
+    // 6. `[]` The token program
+    Redeem,
+}
+
+impl CouponInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateCampaign {
+                id: Self::unpack_u64(rest, 0)?,
+                starts_at: Self::unpack_u64(rest, 8)? as i64,
+                expires_at: Self::unpack_u64(rest, 16)? as i64,
+            },
+            1 => Self::IssueCoupon,
+            2 => Self::Redeem,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,329 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::Mint;
+
+use crate::{
+    instruction::CouponInstruction,
+    error::CouponError,
+    state::{Campaign, Redemption},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = CouponInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            CouponInstruction::CreateCampaign { id, starts_at, expires_at } => {
+                msg!("Instruction: CreateCampaign");
+                Self::process_create_campaign(accounts, id, starts_at, expires_at, program_id)
+            },
+            CouponInstruction::IssueCoupon => {
+                msg!("Instruction: IssueCoupon");
+                Self::process_issue_coupon(accounts, program_id)
+            },
+            CouponInstruction::Redeem => {
+                msg!("Instruction: Redeem");
+                Self::process_redeem(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_campaign(
+        accounts: &[AccountInfo],
+        id: u64,
+        starts_at: i64,
+        expires_at: i64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if expires_at <= starts_at {
+            return Err(CouponError::InvalidWindow.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let merchant = next_account_info(account_info_iter)?;
+
+        if !merchant.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let campaign_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (campaign_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"campaign", merchant.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if campaign_pda != *campaign_account.key {
+            return Err(CouponError::InvalidCampaignAddress.into());
+        }
+
+        if !campaign_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating campaign account...");
+        Self::create_pda_account(
+            merchant,
+            campaign_account,
+            system_program,
+            Campaign::LEN,
+            program_id,
+            &[b"campaign", merchant.key.as_ref(), &id_bytes, &[bump_seed]],
+        )?;
+
+        let campaign_info = Campaign {
+            is_initialized: true,
+            merchant: *merchant.key,
+            id,
+            starts_at,
+            expires_at,
+            issued: 0,
+            redeemed: 0,
+            bump_seed,
+        };
+        campaign_info.serialize(&mut &mut campaign_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_issue_coupon(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let merchant = next_account_info(account_info_iter)?;
+
+        if !merchant.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let campaign_account = next_account_info(account_info_iter)?;
+        let mut campaign_info = Self::unpack_campaign(campaign_account, program_id)?;
+        if campaign_info.merchant != *merchant.key {
+            return Err(CouponError::NotTheMerchant.into());
+        }
+
+        // one token per mint, so every coupon has its own mint and can be told apart from the others
+        let mint = next_account_info(account_info_iter)?;
+        let mint_info = Self::unpack_coupon_mint(mint, campaign_account.key)?;
+        if mint_info.supply != 0 || mint_info.decimals != 0 {
+            return Err(CouponError::InvalidCouponMint.into());
+        }
+
+        let destination = next_account_info(account_info_iter)?;
+
+        // the burn brings the supply back to 0, a redeemed mint must not be turned into a coupon again
+        let redemption_account = next_account_info(account_info_iter)?;
+        Self::check_redemption_address(redemption_account, mint.key, program_id)?;
+        if !redemption_account.data_is_empty() {
+            return Err(CouponError::AlreadyRedeemed.into());
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        msg!("Calling the token program to mint coupon {}...", mint.key);
+        invoke_signed(
+            &spl_token::instruction::mint_to(
+                token_program.key,
+                mint.key,
+                destination.key,
+                campaign_account.key,
+                &[campaign_account.key],
+                1,
+            )?,
+            &[
+                mint.clone(),
+                destination.clone(),
+                campaign_account.clone(),
+                token_program.clone(),
+            ],
+            &[&[
+                b"campaign",
+                campaign_info.merchant.as_ref(),
+                &campaign_info.id.to_le_bytes(),
+                &[campaign_info.bump_seed],
+            ]],
+        )?;
+
+        campaign_info.issued = campaign_info.issued
+            .checked_add(1)
+            .ok_or(CouponError::AmountOverflow)?;
+        campaign_info.serialize(&mut &mut campaign_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_redeem(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let holder = next_account_info(account_info_iter)?;
+
+        if !holder.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let campaign_account = next_account_info(account_info_iter)?;
+        let mut campaign_info = Self::unpack_campaign(campaign_account, program_id)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        if now < campaign_info.starts_at {
+            return Err(CouponError::CampaignNotStarted.into());
+        }
+        if now > campaign_info.expires_at {
+            return Err(CouponError::CampaignExpired.into());
+        }
+
+        let mint = next_account_info(account_info_iter)?;
+        Self::unpack_coupon_mint(mint, campaign_account.key)?;
+
+        let holder_token_account = next_account_info(account_info_iter)?;
+
+        let redemption_account = next_account_info(account_info_iter)?;
+        let bump_seed = Self::check_redemption_address(redemption_account, mint.key, program_id)?;
+
+        // the redemption PDA can only be created once, that's what makes a coupon single use
+        if !redemption_account.data_is_empty() {
+            return Err(CouponError::AlreadyRedeemed.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // the holder owns the token and signed the transaction, so they can burn it
+        msg!("Calling the token program to burn coupon {}...", mint.key);
+        invoke(
+            &spl_token::instruction::burn(
+                token_program.key,
+                holder_token_account.key,
+                mint.key,
+                holder.key,
+                &[holder.key],
+                1,
+            )?,
+            &[
+                holder_token_account.clone(),
+                mint.clone(),
+                holder.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        msg!("Creating redemption account...");
+        Self::create_pda_account(
+            holder,
+            redemption_account,
+            system_program,
+            Redemption::LEN,
+            program_id,
+            &[b"redemption", mint.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let redemption_info = Redemption {
+            campaign: *campaign_account.key,
+            mint: *mint.key,
+            redeemer: *holder.key,
+            redeemed_at: now,
+        };
+        redemption_info.serialize(&mut &mut redemption_account.data.borrow_mut()[..])?;
+
+        campaign_info.redeemed = campaign_info.redeemed
+            .checked_add(1)
+            .ok_or(CouponError::AmountOverflow)?;
+        campaign_info.serialize(&mut &mut campaign_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn unpack_campaign(campaign_account: &AccountInfo, program_id: &Pubkey) -> Result<Campaign, ProgramError> {
+        if campaign_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let campaign_info = Campaign::try_from_slice(&campaign_account.data.borrow())?;
+        if !campaign_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(campaign_info)
+    }
+
+    // only mints the campaign PDA is the mint authority of are coupons of the campaign
+    fn unpack_coupon_mint(mint: &AccountInfo, campaign: &Pubkey) -> Result<Mint, ProgramError> {
+        if *mint.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mint_info = Mint::unpack(&mint.try_borrow_data()?)?;
+        if mint_info.mint_authority != Some(*campaign).into() {
+            return Err(CouponError::InvalidCouponMint.into());
+        }
+
+        Ok(mint_info)
+    }
+
+    fn check_redemption_address(
+        redemption_account: &AccountInfo,
+        mint: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        let (redemption_pda, bump_seed) = Pubkey::find_program_address(&[b"redemption", mint.as_ref()], program_id);
+        if redemption_pda != *redemption_account.key {
+            return Err(CouponError::InvalidRedemptionAddress.into());
+        }
+
+        Ok(bump_seed)
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,32 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Campaign {
+    pub is_initialized: bool,
+    pub merchant: Pubkey,
+    pub id: u64,
+    // coupons can only be redeemed between these unix timestamps
+    pub starts_at: i64,
+    pub expires_at: i64,
+    pub issued: u64,
+    pub redeemed: u64,
+    pub bump_seed: u8,
+}
+
+impl Campaign {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 8 + 8 + 1;
+}
+
+// Marks the coupon of a mint as redeemed, the merchant looks it up at checkout
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Redemption {
+    pub campaign: Pubkey,
+    pub mint: Pubkey,
+    pub redeemer: Pubkey,
+    pub redeemed_at: i64,
+}
+
+impl Redemption {
+    pub const LEN: usize = 32 + 32 + 32 + 8;
+}
@@ -0,0 +1,369 @@
+import yaml
+import sys
+import json
+import time
+import base64
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID, TransferParams, transfer
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'dao.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'dao-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_program(payer: Keypair, decimals: int) -> Token:
+    token = Token.create_mint(
+        conn=client,
+        payer=payer,
+        mint_authority=payer.public_key,
+        decimals=decimals,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    print(f"Address of token program: {token.pubkey}")
+    return token
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def mint_token(token: Token, minter: Keypair, account: PublicKey, amount: int):
+    print(f"Minting {amount} tokens to {account}")
+    token.mint_to(
+        dest=account,
+        mint_authority=minter,
+        amount=amount,
+        opts=TxOpts(skip_confirmation=False)
+    )
+
+
+def get_dao_address(creator: PublicKey, mint: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("dao", encoding="utf8"), bytes(creator), bytes(mint)], program_id)[0]
+
+def get_treasury_address(dao: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("treasury", encoding="utf8"), bytes(dao)], program_id)[0]
+
+def get_voter_address(dao: PublicKey, owner: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("voter", encoding="utf8"), bytes(dao), bytes(owner)], program_id)[0]
+
+def get_proposal_address(dao: PublicKey, index: int) -> PublicKey:
+    return PublicKey.find_program_address([bytes("proposal", encoding="utf8"), bytes(dao), index.to_bytes(8, byteorder='little')], program_id)[0]
+
+def get_vote_address(proposal: PublicKey, voter: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("vote", encoding="utf8"), bytes(proposal), bytes(voter)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def serialize_instructions(instructions: list) -> bytes:
+    """
+    Serializes the instructions like borsh serializes a Vec<ProposalInstruction>: every Vec is prefixed with its
+    length as u32, an account is its pubkey followed by the is_signer and is_writable bytes.
+    """
+    data = len(instructions).to_bytes(4, byteorder='little')
+    for ix in instructions:
+        data += bytes(ix.program_id) + len(ix.keys).to_bytes(4, byteorder='little')
+        for key in ix.keys:
+            data += bytes(key.pubkey) + bytes([key.is_signer, key.is_writable])
+        data += len(ix.data).to_bytes(4, byteorder='little') + ix.data
+    return data
+
+def init_dao(creator: Keypair, mint: PublicKey, vault: PublicKey, quorum: int, voting_period: int, timelock: int) -> PublicKey:
+    dao = get_dao_address(creator.public_key, mint)
+    print(f"\nCreating dao {dao}, quorum of {quorum} tokens, {voting_period}s to vote and a timelock of {timelock}s")
+    send(
+        creator,
+        (0).to_bytes(1, byteorder='little') + quorum.to_bytes(8, byteorder='little') + voting_period.to_bytes(8, byteorder='little') + timelock.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=creator.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=dao, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=mint, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return dao
+
+def fund_treasury(funder: Keypair, dao: PublicKey, amount: int):
+    treasury = get_treasury_address(dao)
+    print(f"\nSending {amount} lamports to the treasury {treasury}")
+    tx = client.send_transaction(
+        Transaction().add(transfer(TransferParams(from_pubkey=funder.public_key, to_pubkey=treasury, lamports=amount))),
+        funder,
+    )
+    client.confirm_transaction(tx["result"])
+
+def deposit(owner: Keypair, dao: PublicKey, owner_token_account: PublicKey, vault: PublicKey, amount: int):
+    print(f"\n{owner.public_key} deposits {amount} governance tokens")
+    send(
+        owner,
+        (1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=dao, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_voter_address(dao, owner.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=owner_token_account, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def withdraw(owner: Keypair, dao: PublicKey, vault: PublicKey, destination: PublicKey, amount: int):
+    print(f"\n{owner.public_key} withdraws {amount} governance tokens")
+    send(
+        owner,
+        (2).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=dao, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_voter_address(dao, owner.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def create_proposal(proposer: Keypair, dao: PublicKey, index: int, instructions: list) -> PublicKey:
+    proposal = get_proposal_address(dao, index)
+    print(f"\nCreating proposal {proposal} with {len(instructions)} instructions")
+    send(
+        proposer,
+        (3).to_bytes(1, byteorder='little') + serialize_instructions(instructions),
+        [
+            AccountMeta(pubkey=proposer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=dao, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_voter_address(dao, proposer.public_key), is_signer=False, is_writable=False),
+            AccountMeta(pubkey=proposal, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return proposal
+
+def vote(voter: Keypair, dao: PublicKey, proposal: PublicKey, approve: bool):
+    print(f"\n{voter.public_key} votes {'yes' if approve else 'no'}")
+    send(
+        voter,
+        (4).to_bytes(1, byteorder='little') + bytes([approve]),
+        [
+            AccountMeta(pubkey=voter.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=dao, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=proposal, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_voter_address(dao, voter.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_vote_address(proposal, voter.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def execute(executor: Keypair, dao: PublicKey, proposal: PublicKey, instructions: list):
+    print(f"\nExecuting proposal {proposal}")
+    # every account the instructions use has to be passed along, the treasury only signs inside the program
+    keys = [
+        AccountMeta(pubkey=dao, is_signer=False, is_writable=False),
+        AccountMeta(pubkey=proposal, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=get_treasury_address(dao), is_signer=False, is_writable=True),
+    ]
+    for ix in instructions:
+        keys += [AccountMeta(pubkey=key.pubkey, is_signer=False, is_writable=key.is_writable) for key in ix.keys]
+        keys.append(AccountMeta(pubkey=ix.program_id, is_signer=False, is_writable=False))
+    send(executor, (5).to_bytes(1, byteorder='little'), keys)
+
+def show_proposal(proposal: PublicKey):
+    data = base64.b64decode(client.get_account_info(proposal)["result"]["value"]["data"][0])
+    # is_initialized, dao, index and proposer come first
+    yes_votes = int.from_bytes(data[73:81], byteorder='little')
+    no_votes = int.from_bytes(data[81:89], byteorder='little')
+    executed = data[105] == 1
+    print(f"Proposal {proposal}: {yes_votes} yes, {no_votes} no, executed: {executed}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    member = setup_user_keypair()
+    recipient = Keypair().public_key
+
+    governance_token = create_token_program(payer, 0)
+    payer_token_account = create_token_account(governance_token, payer.public_key)
+    member_token_account = create_token_account(governance_token, member.public_key)
+    mint_token(governance_token, payer, payer_token_account, 100)
+    mint_token(governance_token, payer, member_token_account, 50)
+
+    # the vault belongs to the dao PDA, which can be derived before the dao exists
+    dao_address = get_dao_address(payer.public_key, governance_token.pubkey)
+    vault = create_token_account(governance_token, dao_address)
+
+    voting_period = 15
+    timelock = 5
+    dao = init_dao(payer, governance_token.pubkey, vault, 60, voting_period, timelock)
+    treasury = get_treasury_address(dao)
+    fund_treasury(payer, dao, LAMPORTS_PER_SOL // 2)
+
+    deposit(payer, dao, payer_token_account, vault, 100)
+    deposit(member, dao, member_token_account, vault, 50)
+
+    # the proposal pays the recipient out of the treasury, the treasury signs the transfer when it is executed
+    instructions = [
+        transfer(TransferParams(from_pubkey=treasury, to_pubkey=recipient, lamports=LAMPORTS_PER_SOL // 10)),
+    ]
+    proposal = create_proposal(payer, dao, 0, instructions)
+
+    vote(payer, dao, proposal, True)
+    vote(member, dao, proposal, False)
+    show_proposal(proposal)
+
+    try:
+        vote(member, dao, proposal, True)
+    except Exception as e:
+        print(f"Voting twice fails as expected: {e}")
+
+    try:
+        withdraw(member, dao, vault, member_token_account, 50)
+    except Exception as e:
+        print(f"Withdrawing tokens during the vote fails as expected: {e}")
+
+    try:
+        execute(payer, dao, proposal, instructions)
+    except Exception as e:
+        print(f"Executing before the timelock expired fails as expected: {e}")
+
+    print("\nWaiting for the vote and the timelock to end...")
+    time.sleep(voting_period + timelock + 2)
+    execute(payer, dao, proposal, instructions)
+    show_proposal(proposal)
+    print(f"Recipient balance: {client.get_balance(recipient)['result']['value']} lamports")
+    print(f"Treasury balance: {client.get_balance(treasury)['result']['value']} lamports")
+
+    try:
+        execute(payer, dao, proposal, instructions)
+    except Exception as e:
+        print(f"Executing a proposal twice fails as expected: {e}")
+
+    withdraw(member, dao, vault, member_token_account, 50)
@@ -0,0 +1,53 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum DaoError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Dao Address")]
+    InvalidDaoAddress,
+    #[error("Invalid Voter Address")]
+    InvalidVoterAddress,
+    #[error("Invalid Proposal Address")]
+    InvalidProposalAddress,
+    #[error("Invalid Vote Address")]
+    InvalidVoteAddress,
+    #[error("Invalid Treasury Address")]
+    InvalidTreasuryAddress,
+    #[error("Invalid Vault")]
+    InvalidVault,
+    #[error("Invalid Config")]
+    InvalidConfig,
+    #[error("Too Many Instructions")]
+    TooManyInstructions,
+    #[error("No Voting Weight")]
+    NoVotingWeight,
+    #[error("Tokens Locked")]
+    TokensLocked,
+    #[error("Insufficient Deposit")]
+    InsufficientDeposit,
+    #[error("Already Voted")]
+    AlreadyVoted,
+    #[error("Voting Closed")]
+    VotingClosed,
+    #[error("Proposal Not Passed")]
+    ProposalNotPassed,
+    #[error("Timelock Not Expired")]
+    TimelockNotExpired,
+    #[error("Already Executed")]
+    AlreadyExecuted,
+    #[error("Missing Instruction Account")]
+    MissingInstructionAccount,
+    #[error("Invalid Signer")]
+    InvalidSigner,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<DaoError> for ProgramError {
+    fn from(e: DaoError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
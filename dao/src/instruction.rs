@@ -0,0 +1,140 @@
+use std::convert::TryInto;
+use borsh::BorshDeserialize;
+use solana_program::program_error::ProgramError;
+
+use crate::{error::DaoError::InvalidInstruction, state::ProposalInstruction};
+
+pub enum DaoInstruction {
+
+    // Creates a dao governed by the holders of a mint in a PDA derived from ["dao", creator, mint].
+    // The vault has to be created prior to this instruction and owned by the dao PDA.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The creator
+    // 1. `[writable]` The dao PDA
+    // 2. `[]` The governance mint
+    // 3. `[]` The vault, a token account owned by the dao PDA
+    // 4. `[]` The system program
+    InitDao {
+        quorum: u64,
+        voting_period: i64,
+        timelock: i64,
+    },
+
+    // Deposits governance tokens, they count as voting weight. The voter PDA derived from ["voter", dao, owner]
+    // is created on the first deposit.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the tokens
+    // 1. `[]` The dao PDA
+    // 2. `[writable]` The voter PDA
+    // 3. `[writable]` The owner's token account to take the tokens from
+    // 4. `[writable]` The vault
+    // 5. `[]` The system program
+    // 6. `[]` The token program
+    Deposit {
+        amount: u64,
+    },
+
+    // Withdraws deposited tokens, once the votes the tokens were used in have ended.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner of the tokens
+    // 1. `[]` The dao PDA
+    // 2. `[writable]` The voter PDA
+    // 3. `[writable]` The vault
+    // 4. `[writable]` The token account to send the tokens to
+    // 5. `[]` The token program
+    Withdraw {
+        amount: u64,
+    },
+
+    // Creates a proposal in a PDA derived from ["proposal", dao, index], index being the dao's proposal count.
+    // The instructions are borsh serialized and executed with the treasury as signer once the proposal passed.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The proposer, needs deposited tokens
+    // 1. `[writable]` The dao PDA
+    // 2. `[]` The proposer's voter PDA
+    // 3. `[writable]` The proposal PDA
+    // 4. `[]` The system program
+    CreateProposal {
+        instructions: Vec<ProposalInstruction>,
+    },
+
+    // Votes with all deposited tokens, the vote record PDA derived from ["vote", proposal, voter] prevents voting twice.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The voter
+    // 1. `[]` The dao PDA
+    // 2. `[writable]` The proposal PDA
+    // 3. `[writable]` The voter PDA
+    // 4. `[writable]` The vote record PDA
+    // 5. `[]` The system program
+    Vote {
+        approve: bool,
+    },
+
+    // Executes the instructions of a passed proposal after the timelock. Anyone may call it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[]` The dao PDA
+    // 1. `[writable]` The proposal PDA
+    // 2. `[writable]` The treasury PDA
+    // 3.. Every account and program used by the proposal's instructions
+    Execute,
+}
+
+impl DaoInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitDao {
+                quorum: Self::unpack_u64(rest, 0)?,
+                voting_period: Self::unpack_u64(rest, 8)? as i64,
+                timelock: Self::unpack_u64(rest, 16)? as i64,
+            },
+            1 => Self::Deposit {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::Withdraw {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            // the instructions are nested too deep for a hand written layout, they are sent as a borsh Vec
+            3 => Self::CreateProposal {
+                instructions: Vec::<ProposalInstruction>::try_from_slice(rest).map_err(|_| InvalidInstruction)?,
+            },
+            4 => Self::Vote {
+                approve: match rest.first() {
+                    Some(0) => false,
+                    Some(1) => true,
+                    _ => return Err(InvalidInstruction.into()),
+                },
+            },
+            5 => Self::Execute,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
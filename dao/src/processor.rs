@@ -0,0 +1,603 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::Account as TokenAccount;
+
+use crate::{
+    instruction::DaoInstruction,
+    error::DaoError,
+    state::{Dao, Proposal, ProposalInstruction, VoteRecord, Voter, MAX_PROPOSAL_INSTRUCTIONS},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = DaoInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            DaoInstruction::InitDao { quorum, voting_period, timelock } => {
+                msg!("Instruction: InitDao");
+                Self::process_init_dao(accounts, quorum, voting_period, timelock, program_id)
+            },
+            DaoInstruction::Deposit { amount } => {
+                msg!("Instruction: Deposit");
+                Self::process_deposit(accounts, amount, program_id)
+            },
+            DaoInstruction::Withdraw { amount } => {
+                msg!("Instruction: Withdraw");
+                Self::process_withdraw(accounts, amount, program_id)
+            },
+            DaoInstruction::CreateProposal { instructions } => {
+                msg!("Instruction: CreateProposal");
+                Self::process_create_proposal(accounts, instructions, program_id)
+            },
+            DaoInstruction::Vote { approve } => {
+                msg!("Instruction: Vote");
+                Self::process_vote(accounts, approve, program_id)
+            },
+            DaoInstruction::Execute => {
+                msg!("Instruction: Execute");
+                Self::process_execute(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_init_dao(
+        accounts: &[AccountInfo],
+        quorum: u64,
+        voting_period: i64,
+        timelock: i64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if quorum == 0 || voting_period <= 0 || timelock < 0 {
+            return Err(DaoError::InvalidConfig.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let dao_account = next_account_info(account_info_iter)?;
+        let governance_mint = next_account_info(account_info_iter)?;
+        let vault = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (dao_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"dao", creator.key.as_ref(), governance_mint.key.as_ref()],
+            program_id,
+        );
+        if dao_pda != *dao_account.key {
+            return Err(DaoError::InvalidDaoAddress.into());
+        }
+
+        if !dao_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        if *vault.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let vault_info = TokenAccount::unpack(&vault.try_borrow_data()?)?;
+        if vault_info.mint != *governance_mint.key || vault_info.owner != dao_pda {
+            return Err(DaoError::InvalidVault.into());
+        }
+
+        let (_, treasury_bump_seed) = Pubkey::find_program_address(&[b"treasury", dao_pda.as_ref()], program_id);
+
+        msg!("Creating dao account...");
+        Self::create_pda_account(
+            creator,
+            dao_account,
+            system_program,
+            Dao::LEN,
+            program_id,
+            &[b"dao", creator.key.as_ref(), governance_mint.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let dao_info = Dao {
+            is_initialized: true,
+            creator: *creator.key,
+            governance_mint: *governance_mint.key,
+            vault: *vault.key,
+            quorum,
+            voting_period,
+            timelock,
+            proposal_count: 0,
+            bump_seed,
+            treasury_bump_seed,
+        };
+        dao_info.serialize(&mut &mut dao_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_deposit(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let dao_account = next_account_info(account_info_iter)?;
+        let dao_info = Self::unpack_dao(dao_account, program_id)?;
+
+        let voter_account = next_account_info(account_info_iter)?;
+        let owner_token_account = next_account_info(account_info_iter)?;
+
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != dao_info.vault {
+            return Err(DaoError::InvalidVault.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut voter_info = if voter_account.data_is_empty() {
+            let (voter_pda, bump_seed) = Pubkey::find_program_address(
+                &[b"voter", dao_account.key.as_ref(), owner.key.as_ref()],
+                program_id,
+            );
+            if voter_pda != *voter_account.key {
+                return Err(DaoError::InvalidVoterAddress.into());
+            }
+
+            msg!("Creating voter account...");
+            Self::create_pda_account(
+                owner,
+                voter_account,
+                system_program,
+                Voter::LEN,
+                program_id,
+                &[b"voter", dao_account.key.as_ref(), owner.key.as_ref(), &[bump_seed]],
+            )?;
+
+            Voter {
+                is_initialized: true,
+                dao: *dao_account.key,
+                owner: *owner.key,
+                deposited: 0,
+                locked_until: 0,
+                bump_seed,
+            }
+        } else {
+            Self::unpack_voter(voter_account, dao_account.key, owner.key, program_id)?
+        };
+
+        msg!("Calling the token program to deposit {} tokens into the vault...", amount);
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                owner_token_account.key,
+                vault.key,
+                owner.key,
+                &[owner.key],
+                amount,
+            )?,
+            &[
+                owner_token_account.clone(),
+                vault.clone(),
+                owner.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        voter_info.deposited = voter_info.deposited
+            .checked_add(amount)
+            .ok_or(DaoError::AmountOverflow)?;
+        voter_info.serialize(&mut &mut voter_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_withdraw(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let dao_account = next_account_info(account_info_iter)?;
+        let dao_info = Self::unpack_dao(dao_account, program_id)?;
+
+        let voter_account = next_account_info(account_info_iter)?;
+        let mut voter_info = Self::unpack_voter(voter_account, dao_account.key, owner.key, program_id)?;
+
+        if Clock::get()?.unix_timestamp < voter_info.locked_until {
+            return Err(DaoError::TokensLocked.into());
+        }
+        if amount > voter_info.deposited {
+            return Err(DaoError::InsufficientDeposit.into());
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != dao_info.vault {
+            return Err(DaoError::InvalidVault.into());
+        }
+
+        let destination = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // the dao PDA owns the vault, so the program signs the transfer with the dao's seeds
+        msg!("Calling the token program to withdraw {} tokens out of the vault...", amount);
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                vault.key,
+                destination.key,
+                dao_account.key,
+                &[dao_account.key],
+                amount,
+            )?,
+            &[
+                vault.clone(),
+                destination.clone(),
+                dao_account.clone(),
+                token_program.clone(),
+            ],
+            &[&[
+                b"dao",
+                dao_info.creator.as_ref(),
+                dao_info.governance_mint.as_ref(),
+                &[dao_info.bump_seed],
+            ]],
+        )?;
+
+        voter_info.deposited -= amount;
+        voter_info.serialize(&mut &mut voter_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_create_proposal(
+        accounts: &[AccountInfo],
+        instructions: Vec<ProposalInstruction>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if instructions.is_empty() || instructions.len() > MAX_PROPOSAL_INSTRUCTIONS {
+            return Err(DaoError::TooManyInstructions.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let proposer = next_account_info(account_info_iter)?;
+
+        if !proposer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let dao_account = next_account_info(account_info_iter)?;
+        let mut dao_info = Self::unpack_dao(dao_account, program_id)?;
+
+        // only members with tokens in the dao may propose, so nobody can spam the dao with proposals for free
+        let voter_account = next_account_info(account_info_iter)?;
+        let voter_info = Self::unpack_voter(voter_account, dao_account.key, proposer.key, program_id)?;
+        if voter_info.deposited == 0 {
+            return Err(DaoError::NoVotingWeight.into());
+        }
+
+        let proposal_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let index_bytes = dao_info.proposal_count.to_le_bytes();
+        let (proposal_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"proposal", dao_account.key.as_ref(), &index_bytes],
+            program_id,
+        );
+        if proposal_pda != *proposal_account.key {
+            return Err(DaoError::InvalidProposalAddress.into());
+        }
+
+        let voting_ends_at = Clock::get()?.unix_timestamp
+            .checked_add(dao_info.voting_period)
+            .ok_or(DaoError::AmountOverflow)?;
+        let executable_at = voting_ends_at
+            .checked_add(dao_info.timelock)
+            .ok_or(DaoError::AmountOverflow)?;
+
+        let proposal_info = Proposal {
+            is_initialized: true,
+            dao: *dao_account.key,
+            index: dao_info.proposal_count,
+            proposer: *proposer.key,
+            yes_votes: 0,
+            no_votes: 0,
+            voting_ends_at,
+            executable_at,
+            executed: false,
+            bump_seed,
+            instructions,
+        };
+        // the proposal is sized exactly to its instructions, votes only change fixed size fields later on
+        let data = proposal_info.try_to_vec()?;
+
+        msg!("Creating proposal {} with {} instructions...", proposal_info.index, proposal_info.instructions.len());
+        Self::create_pda_account(
+            proposer,
+            proposal_account,
+            system_program,
+            data.len(),
+            program_id,
+            &[b"proposal", dao_account.key.as_ref(), &index_bytes, &[bump_seed]],
+        )?;
+        proposal_account.data.borrow_mut().copy_from_slice(&data);
+
+        dao_info.proposal_count += 1;
+        dao_info.serialize(&mut &mut dao_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_vote(
+        accounts: &[AccountInfo],
+        approve: bool,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let voter = next_account_info(account_info_iter)?;
+
+        if !voter.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let dao_account = next_account_info(account_info_iter)?;
+        Self::unpack_dao(dao_account, program_id)?;
+
+        let proposal_account = next_account_info(account_info_iter)?;
+        let mut proposal_info = Self::unpack_proposal(proposal_account, dao_account.key, program_id)?;
+        if Clock::get()?.unix_timestamp > proposal_info.voting_ends_at {
+            return Err(DaoError::VotingClosed.into());
+        }
+
+        let voter_account = next_account_info(account_info_iter)?;
+        let mut voter_info = Self::unpack_voter(voter_account, dao_account.key, voter.key, program_id)?;
+        if voter_info.deposited == 0 {
+            return Err(DaoError::NoVotingWeight.into());
+        }
+
+        let vote_account = next_account_info(account_info_iter)?;
+        let (vote_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"vote", proposal_account.key.as_ref(), voter.key.as_ref()],
+            program_id,
+        );
+        if vote_pda != *vote_account.key {
+            return Err(DaoError::InvalidVoteAddress.into());
+        }
+
+        // the vote PDA can only be created once, that's what prevents a member from voting twice
+        if !vote_account.data_is_empty() {
+            return Err(DaoError::AlreadyVoted.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Creating vote record...");
+        Self::create_pda_account(
+            voter,
+            vote_account,
+            system_program,
+            VoteRecord::LEN,
+            program_id,
+            &[b"vote", proposal_account.key.as_ref(), voter.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let weight = voter_info.deposited;
+        let vote_info = VoteRecord {
+            proposal: *proposal_account.key,
+            voter: *voter.key,
+            approve,
+            weight,
+        };
+        vote_info.serialize(&mut &mut vote_account.data.borrow_mut()[..])?;
+
+        let votes = if approve { &mut proposal_info.yes_votes } else { &mut proposal_info.no_votes };
+        *votes = votes
+            .checked_add(weight)
+            .ok_or(DaoError::AmountOverflow)?;
+        msg!("Voted {} with {} tokens", if approve { "yes" } else { "no" }, weight);
+        proposal_info.serialize(&mut &mut proposal_account.data.borrow_mut()[..])?;
+
+        // the tokens counted for this vote can't be withdrawn and used again before the vote ends
+        voter_info.locked_until = voter_info.locked_until.max(proposal_info.voting_ends_at);
+        voter_info.serialize(&mut &mut voter_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_execute(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let dao_account = next_account_info(account_info_iter)?;
+        let dao_info = Self::unpack_dao(dao_account, program_id)?;
+
+        let proposal_account = next_account_info(account_info_iter)?;
+        let mut proposal_info = Self::unpack_proposal(proposal_account, dao_account.key, program_id)?;
+
+        if proposal_info.executed {
+            return Err(DaoError::AlreadyExecuted.into());
+        }
+
+        // votes can still come in until the voting ends, the timelock starts after that
+        let now = Clock::get()?.unix_timestamp;
+        if now < proposal_info.executable_at {
+            return Err(DaoError::TimelockNotExpired.into());
+        }
+        if !proposal_info.has_passed(dao_info.quorum) {
+            msg!("{} yes and {} no votes, the quorum is {}", proposal_info.yes_votes, proposal_info.no_votes, dao_info.quorum);
+            return Err(DaoError::ProposalNotPassed.into());
+        }
+
+        let treasury = next_account_info(account_info_iter)?;
+        let treasury_seeds: &[&[u8]] = &[b"treasury", dao_account.key.as_ref(), &[dao_info.treasury_bump_seed]];
+        let treasury_pda = Pubkey::create_program_address(treasury_seeds, program_id)?;
+        if treasury_pda != *treasury.key {
+            return Err(DaoError::InvalidTreasuryAddress.into());
+        }
+
+        // Marked as executed before any instruction runs. A program may call itself, so an instruction of the
+        // proposal calling Execute again would otherwise run the proposal a second time.
+        proposal_info.executed = true;
+        proposal_info.serialize(&mut &mut proposal_account.data.borrow_mut()[..])?;
+
+        for (i, proposal_instruction) in proposal_info.instructions.iter().enumerate() {
+            let instruction = proposal_instruction.to_instruction();
+
+            // The treasury is the only signer the program can provide. Other signers of the outer transaction
+            // would be passed on as well, a proposal must not be able to borrow the signature of whoever executes it.
+            let mut account_infos = Vec::with_capacity(instruction.accounts.len() + 1);
+            for meta in &instruction.accounts {
+                if meta.is_signer && meta.pubkey != treasury_pda {
+                    return Err(DaoError::InvalidSigner.into());
+                }
+                account_infos.push(Self::find_account(accounts, &meta.pubkey)?);
+            }
+            account_infos.push(Self::find_account(accounts, &instruction.program_id)?);
+
+            msg!("Executing instruction {} of the proposal on program {}...", i, instruction.program_id);
+            invoke_signed(&instruction, &account_infos, &[treasury_seeds])?;
+        }
+
+        Ok(())
+    }
+
+    fn unpack_dao(dao_account: &AccountInfo, program_id: &Pubkey) -> Result<Dao, ProgramError> {
+        if dao_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let dao_info = Dao::try_from_slice(&dao_account.data.borrow())?;
+        if !dao_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(dao_info)
+    }
+
+    fn unpack_voter(
+        voter_account: &AccountInfo,
+        dao: &Pubkey,
+        owner: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<Voter, ProgramError> {
+        if voter_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let voter_info = Voter::try_from_slice(&voter_account.data.borrow())?;
+        if !voter_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if voter_info.dao != *dao || voter_info.owner != *owner {
+            return Err(DaoError::InvalidVoterAddress.into());
+        }
+
+        Ok(voter_info)
+    }
+
+    fn unpack_proposal(proposal_account: &AccountInfo, dao: &Pubkey, program_id: &Pubkey) -> Result<Proposal, ProgramError> {
+        if proposal_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let proposal_info = Proposal::try_from_slice(&proposal_account.data.borrow())?;
+        if !proposal_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if proposal_info.dao != *dao {
+            return Err(DaoError::InvalidProposalAddress.into());
+        }
+
+        Ok(proposal_info)
+    }
+
+    // the accounts of the proposal's instructions can be passed in any order, they are looked up by their key
+    fn find_account<'a>(accounts: &[AccountInfo<'a>], key: &Pubkey) -> Result<AccountInfo<'a>, ProgramError> {
+        let account = accounts
+            .iter()
+            .find(|account| account.key == key)
+            .ok_or(DaoError::MissingInstructionAccount)?;
+
+        Ok(account.clone())
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    //
+    // The address of the next proposal follows from the proposal count, anyone can compute it and send lamports to it.
+    // create_account refuses an account which holds lamports already, one lamport would stop all governance of the DAO.
+    // Such an account is topped up to the rent instead, then allocated and assigned with the signature of the PDA.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let accounts = [
+            payer.clone(),
+            new_account.clone(),
+            system_program.clone(),
+        ];
+
+        if new_account.lamports() == 0 {
+            let create_account_ix = system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            );
+            return invoke_signed(&create_account_ix, &accounts, &[signer_seeds]);
+        }
+
+        let shortfall = lamports.saturating_sub(new_account.lamports());
+        if shortfall > 0 {
+            invoke(&system_instruction::transfer(payer.key, new_account.key, shortfall), &accounts)?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(new_account.key, space as u64),
+            &accounts,
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(new_account.key, program_id),
+            &accounts,
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,114 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+
+// Most instructions a single proposal may carry, they all have to fit into the Execute transaction later on
+pub const MAX_PROPOSAL_INSTRUCTIONS: usize = 4;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Dao {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub governance_mint: Pubkey,
+    // token account owned by the dao PDA, holds the deposited governance tokens
+    pub vault: Pubkey,
+    // the yes votes a proposal needs at least, in the smallest unit of the governance mint
+    pub quorum: u64,
+    // seconds a proposal is open for votes
+    pub voting_period: i64,
+    // seconds between the end of the vote and the earliest execution
+    pub timelock: i64,
+    pub proposal_count: u64,
+    pub bump_seed: u8,
+    // the treasury is a data-less PDA derived from ["treasury", dao], it signs the proposal instructions
+    pub treasury_bump_seed: u8,
+}
+
+impl Dao {
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1;
+}
+
+// Tokens a member has deposited, their voting weight. The tokens stay locked until the last vote they took part in ended,
+// otherwise they could vote, move the tokens to another wallet and vote again.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Voter {
+    pub is_initialized: bool,
+    pub dao: Pubkey,
+    pub owner: Pubkey,
+    pub deposited: u64,
+    pub locked_until: i64,
+    pub bump_seed: u8,
+}
+
+impl Voter {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+// An instruction stored in a proposal, it mirrors solana_program's Instruction which isn't serializable with borsh
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct ProposalInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccount>,
+    pub data: Vec<u8>,
+}
+
+impl ProposalInstruction {
+    pub fn to_instruction(&self) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: self.accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.pubkey,
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Proposal {
+    pub is_initialized: bool,
+    pub dao: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    // unix timestamps, votes are accepted until voting_ends_at and the proposal can be executed from executable_at
+    pub voting_ends_at: i64,
+    pub executable_at: i64,
+    pub executed: bool,
+    pub bump_seed: u8,
+    pub instructions: Vec<ProposalInstruction>,
+}
+
+impl Proposal {
+    pub fn has_passed(&self, quorum: u64) -> bool {
+        self.yes_votes > self.no_votes && self.yes_votes >= quorum
+    }
+}
+
+// Created when a member votes, it can only be created once per proposal and member
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub approve: bool,
+    pub weight: u64,
+}
+
+impl VoteRecord {
+    pub const LEN: usize = 32 + 32 + 1 + 8;
+}
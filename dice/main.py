@@ -0,0 +1,259 @@
+import yaml
+import sys
+import json
+import base64
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+flags_program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'dice.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'dice-keypair.json'))
+# the house is paused through a flag of the flags program, which has to be deployed as well
+FLAGS_PROGRAM_KEYPAIR_PATH = path.normpath(path.join(path.dirname(__file__), '../flags/target/deploy/flags-keypair.json'))
+
+SYSVAR_SLOT_HASHES = PublicKey("SysvarS1otHashes111111111111111111111111111")
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id, flags_program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    with open(FLAGS_PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        flags_program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_house_address(authority: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("house", encoding="utf8"), bytes(authority)], program_id)[0]
+
+def get_paused_flag_address(authority: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("flag", encoding="utf8"), bytes(authority), bytes("paused", encoding="utf8")], flags_program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def init_house(authority: Keypair, max_bet_bps: int) -> PublicKey:
+    house = get_house_address(authority.public_key)
+    print(f"\nCreating house {house}, a bet may win at most {max_bet_bps / 100}% of its funds")
+    send(
+        authority,
+        (0).to_bytes(1, byteorder='little') + max_bet_bps.to_bytes(2, byteorder='little'),
+        [
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=house, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=flags_program_id, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return house
+
+def fund(funder: Keypair, house: PublicKey, amount: int):
+    print(f"\nFunding the house with {amount} lamports")
+    send(
+        funder,
+        (1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=funder.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=house, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def withdraw(authority: Keypair, house: PublicKey, amount: int):
+    print(f"\nWithdrawing {amount} lamports from the house")
+    send(
+        authority,
+        (2).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=house, is_signer=False, is_writable=True),
+        ],
+    )
+
+def set_paused(authority: Keypair, paused: bool):
+    print(f"\n{'Pausing' if paused else 'Unpausing'} the house")
+    flag = get_paused_flag_address(authority.public_key)
+    ix = TransactionInstruction(
+        data=(0).to_bytes(1, byteorder='little') + (0).to_bytes(1, byteorder='little') + int(paused).to_bytes(8, byteorder='little') + bytes("paused", encoding="utf8"),
+        keys=[
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=flag, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=flags_program_id,
+    )
+    tx = client.send_transaction(Transaction().add(ix), authority)
+    client.confirm_transaction(tx["result"])
+
+def roll(player: Keypair, house: PublicKey, authority: PublicKey, bet: int, guess: int):
+    # the seed only makes rolls in the same slot differ, it doesn't make the roll unpredictable
+    seed = Keypair().public_key
+    send(
+        player,
+        (3).to_bytes(1, byteorder='little') + bet.to_bytes(8, byteorder='little') + bytes([guess]) + bytes(seed),
+        [
+            AccountMeta(pubkey=player.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=house, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_paused_flag_address(authority), is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYSVAR_SLOT_HASHES, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def print_balances(player: Keypair, house: PublicKey):
+    print(f"Player balance: {client.get_balance(player.public_key)['result']['value']} lamports")
+    print(f"House balance: {client.get_balance(house)['result']['value']} lamports")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    player = setup_user_keypair()
+
+    house = init_house(payer, 500)
+    fund(payer, house, LAMPORTS_PER_SOL)
+    print_balances(player, house)
+
+    bet = LAMPORTS_PER_SOL // 100
+    for guess in [1, 2, 3, 4, 5, 6]:
+        print(f"\nBetting {bet} lamports on a {guess}")
+        roll(player, house, payer.public_key, bet, guess)
+        print_balances(player, house)
+
+    try:
+        roll(player, house, payer.public_key, LAMPORTS_PER_SOL // 2, 6)
+    except Exception as e:
+        print(f"Betting more than the house allows fails as expected: {e}")
+
+    set_paused(payer, True)
+    try:
+        roll(player, house, payer.public_key, bet, 6)
+    except Exception as e:
+        print(f"Rolling while the house is paused fails as expected: {e}")
+    set_paused(payer, False)
+    roll(player, house, payer.public_key, bet, 6)
+    print_balances(player, house)
+
+    withdraw(payer, house, LAMPORTS_PER_SOL // 2)
+    print_balances(player, house)
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum DiceError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid House Address")]
+    InvalidHouseAddress,
+    #[error("Invalid Max Bet")]
+    InvalidMaxBet,
+    #[error("Invalid Guess")]
+    InvalidGuess,
+    #[error("Bet Too Large")]
+    BetTooLarge,
+    #[error("Not Authority")]
+    NotAuthority,
+    #[error("Insufficient Funds")]
+    InsufficientFunds,
+    #[error("House Paused")]
+    HousePaused,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<DiceError> for ProgramError {
+    fn from(e: DiceError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,102 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::DiceError::InvalidInstruction;
+
+pub enum DiceInstruction {
+
+    // Creates the house in a PDA derived from ["house", authority]. The house account is the vault as well,
+    // the bets are paid into it and the winnings are paid out of it. The house reads its flags from the
+    // given flags program.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The house authority
+    // 1. `[writable]` The house PDA
+    // 2. `[]` The flags program
+    // 3. `[]` The system program
+    InitHouse {
+        max_bet_bps: u16,
+    },
+
+    // Adds lamports to the house. Anyone may fund it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The funder
+    // 1. `[writable]` The house PDA
+    // 2. `[]` The system program
+    Fund {
+        amount: u64,
+    },
+
+    // Takes lamports out of the house, the rent always stays in the account.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The house authority
+    // 1. `[writable]` The house PDA
+    Withdraw {
+        amount: u64,
+    },
+
+    // Bets on a face of the die. The bet is paid into the house, if the roll matches the guess the player
+    // receives PAYOUT_MULTIPLIER times the bet. Fails while the authority's "paused" flag is set.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The player
+    // 1. `[writable]` The house PDA
+    // 2. `[]` The "paused" flag PDA of the house authority, it doesn't have to exist
+    // 3. `[]` The SlotHashes sysvar
+    // 4. `[]` The system program
+    Roll {
+        bet: u64,
+        guess: u8,
+        seed: [u8; 32],
+    },
+}
+
+impl DiceInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitHouse {
+                max_bet_bps: rest
+                    .get(..2)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u16::from_le_bytes)
+                    .ok_or(InvalidInstruction)?,
+            },
+            1 => Self::Fund {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::Withdraw {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            3 => Self::Roll {
+                bet: Self::unpack_u64(rest, 0)?,
+                guess: *rest.get(8).ok_or(InvalidInstruction)?,
+                seed: rest
+                    .get(9..41)
+                    .and_then(|slice| slice.try_into().ok())
+                    .ok_or(InvalidInstruction)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
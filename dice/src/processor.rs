@@ -0,0 +1,266 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::DiceInstruction,
+    error::DiceError,
+    state::{House, FACES, PAUSED_FLAG, PAYOUT_MULTIPLIER},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = DiceInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            DiceInstruction::InitHouse { max_bet_bps } => {
+                msg!("Instruction: InitHouse");
+                Self::process_init_house(accounts, max_bet_bps, program_id)
+            },
+            DiceInstruction::Fund { amount } => {
+                msg!("Instruction: Fund");
+                Self::process_fund(accounts, amount, program_id)
+            },
+            DiceInstruction::Withdraw { amount } => {
+                msg!("Instruction: Withdraw");
+                Self::process_withdraw(accounts, amount, program_id)
+            },
+            DiceInstruction::Roll { bet, guess, seed } => {
+                msg!("Instruction: Roll");
+                Self::process_roll(accounts, bet, guess, seed, program_id)
+            }
+        }
+    }
+
+    fn process_init_house(
+        accounts: &[AccountInfo],
+        max_bet_bps: u16,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if max_bet_bps == 0 || max_bet_bps > 10_000 {
+            return Err(DiceError::InvalidMaxBet.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let house_account = next_account_info(account_info_iter)?;
+        let flags_program = next_account_info(account_info_iter)?;
+        if !flags_program.executable {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (house_pda, bump_seed) = Pubkey::find_program_address(&[b"house", authority.key.as_ref()], program_id);
+        if house_pda != *house_account.key {
+            return Err(DiceError::InvalidHouseAddress.into());
+        }
+
+        if !house_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating house account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                authority.key,
+                house_account.key,
+                Rent::get()?.minimum_balance(House::LEN),
+                House::LEN as u64,
+                program_id,
+            ),
+            &[
+                authority.clone(),
+                house_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"house", authority.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        let house_info = House {
+            is_initialized: true,
+            authority: *authority.key,
+            max_bet_bps,
+            rolls: 0,
+            flags_program: *flags_program.key,
+            bump_seed,
+        };
+        house_info.serialize(&mut &mut house_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_fund(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let funder = next_account_info(account_info_iter)?;
+
+        if !funder.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let house_account = next_account_info(account_info_iter)?;
+        Self::unpack_house(house_account, program_id)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to add {} lamports to the house...", amount);
+        invoke(
+            &system_instruction::transfer(funder.key, house_account.key, amount),
+            &[
+                funder.clone(),
+                house_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn process_withdraw(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let house_account = next_account_info(account_info_iter)?;
+        let house_info = Self::unpack_house(house_account, program_id)?;
+        if house_info.authority != *authority.key {
+            return Err(DiceError::NotAuthority.into());
+        }
+
+        if amount > Self::funds(house_account)? {
+            return Err(DiceError::InsufficientFunds.into());
+        }
+
+        msg!("Withdrawing {} lamports from the house...", amount);
+        Self::pay(house_account, authority, amount)
+    }
+
+    fn process_roll(
+        accounts: &[AccountInfo],
+        bet: u64,
+        guess: u8,
+        seed: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if guess == 0 || guess > FACES {
+            return Err(DiceError::InvalidGuess.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let player = next_account_info(account_info_iter)?;
+
+        if !player.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let house_account = next_account_info(account_info_iter)?;
+        let mut house_info = Self::unpack_house(house_account, program_id)?;
+
+        // The flag is read from the pinned flags program and has to be the authority's "paused" PDA there,
+        // so a player can neither pass a flag of their own nor leave it out
+        let paused_flag = next_account_info(account_info_iter)?;
+        if flags_reader::is_enabled(paused_flag, &house_info.flags_program, &house_info.authority, PAUSED_FLAG)? {
+            return Err(DiceError::HousePaused.into());
+        }
+
+        // the limit is taken from the funds before the bet is added, the house has to be able to cover the win anyway
+        let max_bet = house_info.max_bet(Self::funds(house_account)?);
+        if bet == 0 || bet > max_bet {
+            msg!("The house accepts bets of at most {} lamports", max_bet);
+            return Err(DiceError::BetTooLarge.into());
+        }
+
+        let slot_hashes = next_account_info(account_info_iter)?;
+        // checked before the bet is taken, the roll reads it again
+        entropy::recent_slot_hash(slot_hashes)?;
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to place a bet of {} lamports...", bet);
+        invoke(
+            &system_instruction::transfer(player.key, house_account.key, bet),
+            &[
+                player.clone(),
+                house_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        // See the entropy crate for why this is no real randomness. The player seed and the roll counter only make
+        // sure that rolls in the same slot are different.
+        let roll = entropy::random_below(
+            slot_hashes,
+            &[&seed, player.key.as_ref(), &house_info.rolls.to_le_bytes()],
+            FACES as u64,
+        )? as u8 + 1;
+
+        msg!("Rolled a {}, the guess was {}", roll, guess);
+        if roll == guess {
+            let payout = bet
+                .checked_mul(PAYOUT_MULTIPLIER)
+                .ok_or(DiceError::AmountOverflow)?;
+            msg!("The player wins {} lamports", payout);
+            Self::pay(house_account, player, payout)?;
+        }
+
+        house_info.rolls += 1;
+        house_info.serialize(&mut &mut house_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn unpack_house(house_account: &AccountInfo, program_id: &Pubkey) -> Result<House, ProgramError> {
+        if house_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let house_info = House::try_from_slice(&house_account.data.borrow())?;
+        if !house_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(house_info)
+    }
+
+    // lamports held by the house on top of its rent
+    fn funds(house_account: &AccountInfo) -> Result<u64, ProgramError> {
+        let rent_lamports = Rent::get()?.minimum_balance(house_account.data_len());
+        Ok(house_account.lamports().saturating_sub(rent_lamports))
+    }
+
+    // the house account is owned by the program, so lamports are paid out by moving them directly
+    fn pay(house_account: &AccountInfo, destination: &AccountInfo, amount: u64) -> ProgramResult {
+        **house_account.lamports.borrow_mut() -= amount;
+        **destination.lamports.borrow_mut() = destination.lamports()
+            .checked_add(amount)
+            .ok_or(DiceError::AmountOverflow)?;
+
+        Ok(())
+    }
+}
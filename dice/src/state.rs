@@ -0,0 +1,35 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// the number of faces of the die, a roll is a number in 1..=FACES
+pub const FACES: u8 = 6;
+
+// the flag of the house authority which stops all rolls while it is set to true
+pub const PAUSED_FLAG: &str = "paused";
+
+// a winning bet is paid out five times, which leaves the house an edge of one sixth
+pub const PAYOUT_MULTIPLIER: u64 = 5;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct House {
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+    // the most a single bet may win, in basis points of the house funds
+    pub max_bet_bps: u16,
+    // counts the rolls, it is mixed into the randomness so two rolls in the same slot differ
+    pub rolls: u64,
+    // the flags program the authority keeps the house configuration in, pinned when the house is created
+    pub flags_program: Pubkey,
+    pub bump_seed: u8,
+}
+
+impl House {
+    pub const LEN: usize = 1 + 32 + 2 + 8 + 32 + 1;
+
+    // The winnings of a bet are its payout minus the bet itself. A bet is allowed as long as its winnings
+    // stay within max_bet_bps of the funds, so a lucky streak can't empty the house in one go.
+    pub fn max_bet(&self, funds: u64) -> u64 {
+        let max_winnings = funds as u128 * self.max_bet_bps as u128 / 10_000;
+        (max_winnings / (PAYOUT_MULTIPLIER - 1) as u128) as u64
+    }
+}
@@ -0,0 +1,302 @@
+import yaml
+import sys
+import json
+import time
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'dutch_auction.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'dutch_auction-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_program(payer: Keypair, decimals: int) -> Token:
+    token = Token.create_mint(
+        conn=client,
+        payer=payer,
+        mint_authority=payer.public_key,
+        decimals=decimals,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    print(f"Address of token program: {token.pubkey}")
+    return token
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def mint_token(token: Token, minter: Keypair, account: PublicKey, amount: int):
+    print(f"Minting {amount} tokens to {account}")
+    token.mint_to(
+        dest=account,
+        mint_authority=minter,
+        amount=amount,
+        opts=TxOpts(skip_confirmation=False)
+    )
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+
+def get_auction_address(seller: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("dutch", encoding="utf8"), bytes(seller), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def cluster_time() -> int:
+    return client.get_block_time(client.get_slot()['result'])['result']
+
+def price(start_price: int, end_price: int, start_ts: int, end_ts: int, now: int) -> int:
+    # the same linear decay the program computes
+    if now <= start_ts:
+        return start_price
+    if now >= end_ts:
+        return end_price
+    return start_price - (start_price - end_price) * (now - start_ts) // (end_ts - start_ts)
+
+def create_auction(
+    seller: Keypair, id: int, token: Token, source: PublicKey, payment_account: PublicKey,
+    token_amount: int, start_price: int, end_price: int, start_ts: int, end_ts: int
+) -> (PublicKey, PublicKey):
+    auction = get_auction_address(seller.public_key, id)
+    # the vault is a normal token account owned by the auction PDA
+    vault = create_token_account(token, auction)
+    print(f"\nAuctioning {token_amount} tokens, the price falls from {start_price} at {start_ts} to {end_price} at {end_ts}")
+    send(
+        seller,
+        (0).to_bytes(1, byteorder='little')
+        + id.to_bytes(8, byteorder='little')
+        + token_amount.to_bytes(8, byteorder='little')
+        + start_price.to_bytes(8, byteorder='little')
+        + end_price.to_bytes(8, byteorder='little')
+        + start_ts.to_bytes(8, byteorder='little', signed=True)
+        + end_ts.to_bytes(8, byteorder='little', signed=True),
+        [
+            AccountMeta(pubkey=seller.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=auction, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=source, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=payment_account, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return auction, vault
+
+def buy(
+    buyer: Keypair, auction: PublicKey, vault: PublicKey, destination: PublicKey, buyer_payment_account: PublicKey,
+    payment_account: PublicKey, seller: PublicKey, max_price: int
+):
+    print(f"\n{buyer.public_key} buys for at most {max_price}")
+    send(
+        buyer,
+        (1).to_bytes(1, byteorder='little') + max_price.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=buyer.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=auction, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=buyer_payment_account, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=payment_account, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=seller, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def cancel(seller: Keypair, auction: PublicKey, vault: PublicKey, destination: PublicKey):
+    print("\nCancelling the auction")
+    send(
+        seller,
+        (2).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=seller.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=auction, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+
+    # the payer sells tokens and gets paid in a second token both buyers hold
+    token = create_token_program(payer, decimals=0)
+    seller_account = create_token_account(token, payer.public_key)
+    mint_token(token, payer, seller_account, 20)
+
+    usd = create_token_program(payer, decimals=0)
+    seller_payment_account = create_token_account(usd, payer.public_key)
+    alice_payment_account = create_token_account(usd, alice.public_key)
+    bob_payment_account = create_token_account(usd, bob.public_key)
+    mint_token(usd, payer, alice_payment_account, 1000)
+    mint_token(usd, payer, bob_payment_account, 1000)
+    alice_account = create_token_account(token, alice.public_key)
+    bob_account = create_token_account(token, bob.public_key)
+
+    # an auction nobody buys from can be cancelled
+    now = cluster_time()
+    auction, vault = create_auction(payer, 0, token, seller_account, seller_payment_account, 10, 1000, 100, now, now + 3600)
+    cancel(payer, auction, vault, seller_account)
+    print(f"The seller holds {token_balance(token, seller_account)} tokens again")
+
+    start_price, end_price = 1000, 100
+    start_ts = cluster_time()
+    end_ts = start_ts + 40
+    auction, vault = create_auction(
+        payer, 1, token, seller_account, seller_payment_account, 10, start_price, end_price, start_ts, end_ts
+    )
+
+    try:
+        buy(alice, auction, vault, alice_account, alice_payment_account, seller_payment_account, payer.public_key, 200)
+    except Exception as e:
+        print(f"Buying above the limit fails as expected: {e}")
+
+    print("Waiting for the price to fall...")
+    while price(start_price, end_price, start_ts, end_ts, cluster_time()) > 500:
+        time.sleep(1)
+
+    # the limit leaves some room, the price only falls further until the transaction lands
+    buy(alice, auction, vault, alice_account, alice_payment_account, seller_payment_account, payer.public_key, 500)
+    print(f"Alice holds {token_balance(token, alice_account)} tokens and paid {1000 - token_balance(usd, alice_payment_account)}")
+
+    try:
+        buy(bob, auction, vault, bob_account, bob_payment_account, seller_payment_account, payer.public_key, 1000)
+    except Exception as e:
+        print(f"Buying from a sold out auction fails as expected: {e}")
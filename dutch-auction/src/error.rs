@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum DutchAuctionError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Auction Address")]
+    InvalidAuctionAddress,
+    #[error("Invalid Vault")]
+    InvalidVault,
+    #[error("Invalid Payment Account")]
+    InvalidPaymentAccount,
+    #[error("Invalid Schedule")]
+    InvalidSchedule,
+    #[error("Auction Not Started")]
+    AuctionNotStarted,
+    #[error("Price Above Limit")]
+    PriceAboveLimit,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<DutchAuctionError> for ProgramError {
+    fn from(e: DutchAuctionError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,90 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::DutchAuctionError::InvalidInstruction;
+
+pub enum DutchAuctionInstruction {
+
+    // Lists tokens in a PDA derived from ["dutch", seller, id]. The vault has to be created prior to this
+    // instruction and owned by the auction PDA.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The seller, pays for the auction account
+    // 1. `[writable]` The auction PDA
+    // 2. `[writable]` The seller's token account to take the tokens from
+    // 3. `[writable]` The vault, a token account owned by the auction PDA
+    // 4. `[]` The seller's token account the price is paid into
+    // 5. `[]` The system program
+    // 6. `[]` The token program
+    CreateAuction {
+        id: u64,
+        token_amount: u64,
+        start_price: u64,
+        end_price: u64,
+        start_ts: i64,
+        end_ts: i64,
+    },
+
+    // Buys all tokens at the current price, as long as it isn't above the limit. The first buyer wins, the
+    // auction is closed right away.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The buyer
+    // 1. `[writable]` The auction PDA
+    // 2. `[writable]` The vault
+    // 3. `[writable]` The buyer's token account to receive the tokens
+    // 4. `[writable]` The buyer's token account to pay with
+    // 5. `[writable]` The seller's token account the price is paid into
+    // 6. `[writable]` The seller, receives the rent
+    // 7. `[]` The token program
+    Buy {
+        max_price: u64,
+    },
+
+    // Cancels the auction as long as nobody bought, and gives the tokens back to the seller.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The seller
+    // 1. `[writable]` The auction PDA
+    // 2. `[writable]` The vault
+    // 3. `[writable]` The seller's token account to receive the tokens
+    // 4. `[]` The token program
+    Cancel,
+}
+
+impl DutchAuctionInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateAuction {
+                id: Self::unpack_u64(rest, 0)?,
+                token_amount: Self::unpack_u64(rest, 8)?,
+                start_price: Self::unpack_u64(rest, 16)?,
+                end_price: Self::unpack_u64(rest, 24)?,
+                start_ts: Self::unpack_u64(rest, 32)? as i64,
+                end_ts: Self::unpack_u64(rest, 40)? as i64,
+            },
+            1 => Self::Buy {
+                max_price: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::Cancel,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
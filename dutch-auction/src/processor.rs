@@ -0,0 +1,330 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::Account as TokenAccount;
+
+use crate::{instruction::DutchAuctionInstruction, error::DutchAuctionError, state::Auction};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = DutchAuctionInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            DutchAuctionInstruction::CreateAuction { id, token_amount, start_price, end_price, start_ts, end_ts } => {
+                msg!("Instruction: CreateAuction");
+                Self::process_create_auction(accounts, id, token_amount, start_price, end_price, start_ts, end_ts, program_id)
+            },
+            DutchAuctionInstruction::Buy { max_price } => {
+                msg!("Instruction: Buy");
+                Self::process_buy(accounts, max_price, program_id)
+            },
+            DutchAuctionInstruction::Cancel => {
+                msg!("Instruction: Cancel");
+                Self::process_cancel(accounts, program_id)
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_create_auction(
+        accounts: &[AccountInfo],
+        id: u64,
+        token_amount: u64,
+        start_price: u64,
+        end_price: u64,
+        start_ts: i64,
+        end_ts: i64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if end_ts <= start_ts || end_price > start_price || end_ts <= Clock::get()?.unix_timestamp {
+            return Err(DutchAuctionError::InvalidSchedule.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let seller = next_account_info(account_info_iter)?;
+
+        if !seller.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let auction_account = next_account_info(account_info_iter)?;
+        let seller_token_account = next_account_info(account_info_iter)?;
+        let vault = next_account_info(account_info_iter)?;
+        let payment_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let id_bytes = id.to_le_bytes();
+        let (auction_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"dutch", seller.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if auction_pda != *auction_account.key {
+            return Err(DutchAuctionError::InvalidAuctionAddress.into());
+        }
+
+        let vault_info = TokenAccount::unpack(&vault.try_borrow_data()?)?;
+        if vault_info.owner != auction_pda {
+            return Err(DutchAuctionError::InvalidVault.into());
+        }
+
+        // the seller doesn't sign the purchase, so the price must end up in an account they own
+        let payment_info = TokenAccount::unpack(&payment_account.try_borrow_data()?)?;
+        if payment_info.owner != *seller.key {
+            return Err(DutchAuctionError::InvalidPaymentAccount.into());
+        }
+
+        if !auction_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating auction account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                seller.key,
+                auction_account.key,
+                Rent::get()?.minimum_balance(Auction::LEN),
+                Auction::LEN as u64,
+                program_id,
+            ),
+            &[
+                seller.clone(),
+                auction_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"dutch", seller.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        msg!("Calling the token program to transfer the tokens into the vault...");
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                seller_token_account.key,
+                vault.key,
+                seller.key,
+                &[seller.key],
+                token_amount,
+            )?,
+            &[
+                seller_token_account.clone(),
+                vault.clone(),
+                seller.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        let auction_info = Auction {
+            is_initialized: true,
+            seller: *seller.key,
+            id,
+            mint: vault_info.mint,
+            vault: *vault.key,
+            token_amount,
+            payment_account: *payment_account.key,
+            payment_mint: payment_info.mint,
+            start_price,
+            end_price,
+            start_ts,
+            end_ts,
+            bump_seed,
+        };
+        auction_info.serialize(&mut &mut auction_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_buy(
+        accounts: &[AccountInfo],
+        max_price: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let buyer = next_account_info(account_info_iter)?;
+
+        if !buyer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let auction_account = next_account_info(account_info_iter)?;
+        let auction_info = Self::unpack_auction(auction_account, program_id)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        if now < auction_info.start_ts {
+            return Err(DutchAuctionError::AuctionNotStarted.into());
+        }
+
+        // the price keeps falling while the transaction is on its way, the limit protects the buyer if it
+        // lands earlier than expected
+        let price = auction_info.price(now);
+        if price > max_price {
+            return Err(DutchAuctionError::PriceAboveLimit.into());
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != auction_info.vault {
+            return Err(DutchAuctionError::InvalidVault.into());
+        }
+
+        let buyer_token_account = next_account_info(account_info_iter)?;
+        let buyer_payment_account = next_account_info(account_info_iter)?;
+        let payment_account = next_account_info(account_info_iter)?;
+        if *payment_account.key != auction_info.payment_account {
+            return Err(DutchAuctionError::InvalidPaymentAccount.into());
+        }
+
+        let seller = next_account_info(account_info_iter)?;
+        if *seller.key != auction_info.seller {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        msg!("Calling the token program to pay the price of {}...", price);
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                buyer_payment_account.key,
+                payment_account.key,
+                buyer.key,
+                &[buyer.key],
+                price,
+            )?,
+            &[
+                buyer_payment_account.clone(),
+                payment_account.clone(),
+                buyer.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        Self::release_vault(&auction_info, auction_account, vault, buyer_token_account, seller, token_program)
+    }
+
+    fn process_cancel(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let seller = next_account_info(account_info_iter)?;
+
+        if !seller.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let auction_account = next_account_info(account_info_iter)?;
+        let auction_info = Self::unpack_auction(auction_account, program_id)?;
+        if auction_info.seller != *seller.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != auction_info.vault {
+            return Err(DutchAuctionError::InvalidVault.into());
+        }
+
+        let seller_token_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Self::release_vault(&auction_info, auction_account, vault, seller_token_account, seller, token_program)
+    }
+
+    fn unpack_auction(auction_account: &AccountInfo, program_id: &Pubkey) -> Result<Auction, ProgramError> {
+        if auction_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let auction_info = Auction::try_from_slice(&auction_account.data.borrow())?;
+        if !auction_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(auction_info)
+    }
+
+    // Moves the tokens out of the vault and closes both the vault and the auction account. The auction PDA owns the
+    // vault, so the program signs with the auction's seeds. Closing the auction makes sure there is only ever
+    // one buyer.
+    fn release_vault<'a>(
+        auction_info: &Auction,
+        auction_account: &AccountInfo<'a>,
+        vault: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        seller: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let auction_seeds: &[&[u8]] = &[
+            b"dutch",
+            auction_info.seller.as_ref(),
+            &auction_info.id.to_le_bytes(),
+            &[auction_info.bump_seed],
+        ];
+
+        msg!("Calling the token program to transfer {} tokens out of the vault...", auction_info.token_amount);
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                vault.key,
+                destination.key,
+                auction_account.key,
+                &[auction_account.key],
+                auction_info.token_amount,
+            )?,
+            &[
+                vault.clone(),
+                destination.clone(),
+                auction_account.clone(),
+                token_program.clone(),
+            ],
+            &[auction_seeds],
+        )?;
+
+        msg!("Calling the token program to close the vault...");
+        invoke_signed(
+            &spl_token::instruction::close_account(
+                token_program.key,
+                vault.key,
+                seller.key,
+                auction_account.key,
+                &[auction_account.key],
+            )?,
+            &[
+                vault.clone(),
+                seller.clone(),
+                auction_account.clone(),
+                token_program.clone(),
+            ],
+            &[auction_seeds],
+        )?;
+
+        msg!("Closing the auction account...");
+        **seller.lamports.borrow_mut() = seller.lamports()
+            .checked_add(auction_account.lamports())
+            .ok_or(DutchAuctionError::AmountOverflow)?;
+        **auction_account.lamports.borrow_mut() = 0;
+        auction_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Auction {
+    pub is_initialized: bool,
+    pub seller: Pubkey,
+    pub id: u64,
+    pub mint: Pubkey,
+    // token account owned by the auction PDA, holding the tokens for sale
+    pub vault: Pubkey,
+    pub token_amount: u64,
+    // the seller's token account the price is paid into, its mint is the one the buyer pays with
+    pub payment_account: Pubkey,
+    pub payment_mint: Pubkey,
+    // the price falls from start_price at start_ts down to end_price at end_ts, and stays there afterwards
+    pub start_price: u64,
+    pub end_price: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub bump_seed: u8,
+}
+
+impl Auction {
+    pub const LEN: usize = 1 + 32 + 8 + 32 + 32 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+
+    // The price drops linearly with the time that passed since the start. Rounding down favors the buyer by less
+    // than one unit of the payment token.
+    pub fn price(&self, now: i64) -> u64 {
+        if now <= self.start_ts {
+            return self.start_price;
+        }
+        if now >= self.end_ts {
+            return self.end_price;
+        }
+
+        let drop = (self.start_price - self.end_price) as u128 * (now - self.start_ts) as u128
+            / (self.end_ts - self.start_ts) as u128;
+        self.start_price - drop as u64
+    }
+}
@@ -0,0 +1,231 @@
+import yaml
+import sys
+import json
+import base64
+import struct
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+VERIFIER_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'authority': types.fixed_array(types.u8, 32),
+    'signer': types.fixed_array(types.u8, 32),
+    'verified': types.u64,
+    'bump_seed': types.u8
+})
+
+SYSVAR_INSTRUCTIONS_ID = PublicKey("Sysvar1nstructions1111111111111111111111111")
+ED25519_PROGRAM_ID = PublicKey("Ed25519SigVerify111111111111111111111111111")
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'ed25519_verify.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'ed25519_verify-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def get_verifier_address(authority: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("verifier", encoding="utf8"), bytes(authority)], program_id)[0]
+
+def get_verifier(verifier: PublicKey) -> dict:
+    account_info = client.get_account_info(verifier)["result"]["value"]
+    return borsh.deserialize(VERIFIER_SCHEMA, base64.b64decode(account_info["data"][0]))
+
+def init_verifier(authority: Keypair, signer: PublicKey) -> PublicKey:
+    verifier = get_verifier_address(authority.public_key)
+    if client.get_account_info(verifier)["result"]["value"] is not None:
+        print(f"Verifier {verifier} already exists")
+        return verifier
+
+    print(f"\nCreating verifier {verifier} accepting signatures of {signer}")
+    tx = Transaction().add(
+        TransactionInstruction(
+            data=(0).to_bytes(1, byteorder='little') + bytes(signer),
+            keys=[
+                AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+                AccountMeta(pubkey=verifier, is_signer=False, is_writable=True),
+                AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            ],
+            program_id=program_id,
+        ),
+    )
+    result = client.send_transaction(tx, authority)
+    client.confirm_transaction(result["result"])
+    return verifier
+
+def ed25519_ix(public_key: PublicKey, signature: bytes, message: bytes) -> TransactionInstruction:
+    # one signature, then its offsets, then the key, the signature and the message. u16::MAX as instruction
+    # index tells the Ed25519 program that the bytes are in this instruction
+    public_key_offset = 2 + 14
+    signature_offset = public_key_offset + 32
+    message_offset = signature_offset + 64
+    offsets = struct.pack(
+        '<HHHHHHH',
+        signature_offset, 0xFFFF,
+        public_key_offset, 0xFFFF,
+        message_offset, len(message), 0xFFFF,
+    )
+    return TransactionInstruction(
+        data=bytes([1, 0]) + offsets + bytes(public_key) + signature + message,
+        keys=[],
+        program_id=ED25519_PROGRAM_ID,
+    )
+
+def verify_ix(verifier: PublicKey, message: bytes) -> TransactionInstruction:
+    return TransactionInstruction(
+        data=(1).to_bytes(1, byteorder='little') + message,
+        keys=[
+            AccountMeta(pubkey=verifier, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYSVAR_INSTRUCTIONS_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+
+def verify(verifier: PublicKey, signer: Keypair, message: bytes, claimed: bytes = None):
+    # the signature is made off-chain, the signer doesn't sign the transaction, the payer sends it
+    signature = signer.sign(message).signature
+    tx = Transaction().add(
+        ed25519_ix(signer.public_key, signature, message),
+        verify_ix(verifier, message if claimed is None else claimed),
+    )
+    result = client.send_transaction(tx, payer)
+    client.confirm_transaction(result["result"])
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+
+    # the key of e.g. a backend, which signs messages off-chain
+    backend = Keypair()
+    verifier = init_verifier(payer, backend.public_key)
+
+    message = b"withdrawal of 100 approved"
+    print(f"\nVerifying a message signed by {backend.public_key}")
+    verify(verifier, backend, message)
+    print(f"Verified messages: {get_verifier(verifier)['verified']}")
+
+    print("\nVerifying a message signed by somebody else")
+    try:
+        verify(verifier, Keypair(), message)
+    except Exception as e:
+        print(f"The verify fails, the signer isn't the one of the verifier: {e}")
+
+    print("\nVerifying a message other than the signed one")
+    try:
+        verify(verifier, backend, message, b"withdrawal of 999 approved")
+    except Exception as e:
+        print(f"The verify fails, the signed message is a different one: {e}")
+    print(f"Verified messages: {get_verifier(verifier)['verified']}")
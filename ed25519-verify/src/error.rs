@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum Ed25519VerifyError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Verifier Address")]
+    InvalidVerifierAddress,
+    #[error("Missing Ed25519 Instruction")]
+    MissingEd25519Instruction,
+    #[error("Invalid Ed25519 Instruction")]
+    InvalidEd25519Instruction,
+    #[error("Wrong Signer")]
+    WrongSigner,
+    #[error("Wrong Message")]
+    WrongMessage,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<Ed25519VerifyError> for ProgramError {
+    fn from(e: Ed25519VerifyError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
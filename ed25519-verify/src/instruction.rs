@@ -0,0 +1,52 @@
+use std::convert::TryInto;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::error::Ed25519VerifyError::InvalidInstruction;
+
+pub enum Ed25519VerifyInstruction {
+
+    // Creates the verifier in a PDA derived from ["verifier", authority]. It stores the key whose
+    // signatures Verify accepts. Followed by the 32 bytes of that key.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The authority
+    // 1. `[writable]` The verifier PDA
+    // 2. `[]` The system program
+    InitVerifier {
+        signer: Pubkey,
+    },
+
+    // Confirms that the key of the verifier signed the message, every byte after the tag. The instruction
+    // right before it has to be an instruction of the Ed25519 program verifying exactly that signature.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The verifier PDA
+    // 1. `[]` The instructions sysvar
+    Verify {
+        message: Vec<u8>,
+    },
+}
+
+impl Ed25519VerifyInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match *tag {
+            0 => Self::InitVerifier {
+                signer: rest
+                    .get(..32)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(Pubkey::new_from_array)
+                    .ok_or(InvalidInstruction)?,
+            },
+            1 => Self::Verify {
+                message: rest.to_vec(),
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+}
@@ -0,0 +1,204 @@
+use std::convert::TryInto;
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    ed25519_program,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{
+        self,
+        instructions::get_instruction_relative,
+        rent::Rent,
+        Sysvar,
+    },
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::Ed25519VerifyInstruction,
+    error::Ed25519VerifyError,
+    state::Verifier,
+};
+
+// The data of an Ed25519 program instruction starts with the number of signatures and a padding byte,
+// followed by 14 bytes of offsets per signature. The offsets tell where the signature, the public key and the
+// message are, and in which instruction of the transaction. See solana_sdk::ed25519_instruction.
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+// an instruction index of u16::MAX means the bytes are in the Ed25519 instruction itself
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = Ed25519VerifyInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            Ed25519VerifyInstruction::InitVerifier { signer } => {
+                msg!("Instruction: InitVerifier");
+                Self::process_init_verifier(accounts, signer, program_id)
+            },
+            Ed25519VerifyInstruction::Verify { message } => {
+                msg!("Instruction: Verify");
+                Self::process_verify(accounts, &message, program_id)
+            }
+        }
+    }
+
+    fn process_init_verifier(
+        accounts: &[AccountInfo],
+        signer: Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let verifier_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (verifier_pda, bump_seed) = Pubkey::find_program_address(&[b"verifier", authority.key.as_ref()], program_id);
+        if verifier_pda != *verifier_account.key {
+            return Err(Ed25519VerifyError::InvalidVerifierAddress.into());
+        }
+
+        if !verifier_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating verifier account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                authority.key,
+                verifier_account.key,
+                Rent::get()?.minimum_balance(Verifier::LEN),
+                Verifier::LEN as u64,
+                program_id,
+            ),
+            &[
+                authority.clone(),
+                verifier_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"verifier", authority.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        let verifier_info = Verifier {
+            is_initialized: true,
+            authority: *authority.key,
+            signer,
+            verified: 0,
+            bump_seed,
+        };
+        verifier_info.serialize(&mut &mut verifier_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    // Checking an ed25519 signature in the program itself would cost far more compute units than a transaction
+    // has. The Ed25519 program is a precompile, the runtime verifies the signatures of its instructions before the
+    // transaction runs and rejects the whole transaction if one is wrong. It doesn't store anything though, our program
+    // only learns about the check by looking at the instruction in the Instructions sysvar.
+    fn process_verify(
+        accounts: &[AccountInfo],
+        message: &[u8],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let verifier_account = next_account_info(account_info_iter)?;
+        let mut verifier_info = Self::unpack_verifier(verifier_account, program_id)?;
+
+        let instructions_sysvar = next_account_info(account_info_iter)?;
+        if *instructions_sysvar.key != sysvar::instructions::id() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // the instruction right before this one, the index is relative to the current top level instruction
+        let ed25519_instruction = get_instruction_relative(-1, instructions_sysvar)
+            .map_err(|_| Ed25519VerifyError::MissingEd25519Instruction)?;
+        let (signer, signed_message) = Self::verified_signature(&ed25519_instruction)?;
+
+        if signer != verifier_info.signer {
+            return Err(Ed25519VerifyError::WrongSigner.into());
+        }
+        if signed_message != message {
+            return Err(Ed25519VerifyError::WrongMessage.into());
+        }
+
+        verifier_info.verified = verifier_info.verified
+            .checked_add(1)
+            .ok_or(Ed25519VerifyError::AmountOverflow)?;
+        verifier_info.serialize(&mut &mut verifier_account.data.borrow_mut()[..])?;
+
+        msg!("{} signed a message of {} bytes", signer, message.len());
+        Ok(())
+    }
+
+    // The key and the message the Ed25519 program verified a signature of. Anybody can put an instruction of the
+    // Ed25519 program into the transaction, so everything the runtime checked has to be looked at:
+    // - it has to be the Ed25519 program, otherwise nothing was verified at all
+    // - exactly one signature, so the key and the message read below are the ones of the checked signature
+    // - the offsets have to point into the Ed25519 instruction itself. They may point into any instruction of the
+    //   transaction, the verified key could then be a different one than the bytes read here
+    fn verified_signature(instruction: &Instruction) -> Result<(Pubkey, &[u8]), ProgramError> {
+        if instruction.program_id != ed25519_program::id() {
+            return Err(Ed25519VerifyError::MissingEd25519Instruction.into());
+        }
+
+        let data = &instruction.data;
+        if data.first() != Some(&1) {
+            return Err(Ed25519VerifyError::InvalidEd25519Instruction.into());
+        }
+
+        let offsets = data
+            .get(SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE)
+            .ok_or(Ed25519VerifyError::InvalidEd25519Instruction)?;
+        let field = |index: usize| u16::from_le_bytes([offsets[2 * index], offsets[2 * index + 1]]);
+        let signature_instruction_index = field(1);
+        let public_key_offset = field(2) as usize;
+        let public_key_instruction_index = field(3);
+        let message_data_offset = field(4) as usize;
+        let message_data_size = field(5) as usize;
+        let message_instruction_index = field(6);
+
+        let indexes = [signature_instruction_index, public_key_instruction_index, message_instruction_index];
+        if indexes.iter().any(|index| *index != CURRENT_INSTRUCTION) {
+            return Err(Ed25519VerifyError::InvalidEd25519Instruction.into());
+        }
+
+        let signer = data
+            .get(public_key_offset..public_key_offset + PUBKEY_SIZE)
+            .and_then(|slice| slice.try_into().ok())
+            .map(Pubkey::new_from_array)
+            .ok_or(Ed25519VerifyError::InvalidEd25519Instruction)?;
+        let message = data
+            .get(message_data_offset..message_data_offset + message_data_size)
+            .ok_or(Ed25519VerifyError::InvalidEd25519Instruction)?;
+
+        Ok((signer, message))
+    }
+
+    fn unpack_verifier(verifier_account: &AccountInfo, program_id: &Pubkey) -> Result<Verifier, ProgramError> {
+        if verifier_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let verifier_info = Verifier::try_from_slice(&verifier_account.data.borrow())?;
+        if !verifier_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(verifier_info)
+    }
+}
@@ -0,0 +1,17 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Verifier {
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+    // the key whose signatures are accepted, e.g. the one of a backend signing off-chain
+    pub signer: Pubkey,
+    // messages verified so far
+    pub verified: u64,
+    pub bump_seed: u8,
+}
+
+impl Verifier {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 1;
+}
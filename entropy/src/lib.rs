@@ -0,0 +1,54 @@
+// Pseudo-random numbers for the examples which need one, like dice and raffle. There is no random number generator
+// on-chain, every validator has to come to the same result, so the numbers are derived from the SlotHashes sysvar.
+//
+// This is NOT a safe source of randomness, it only shows where the entropy would come from:
+// - the SlotHashes sysvar contains the hashes of slots that are already finished, so the most recent one is public
+//   by the time a user signs. Anybody can compute the number before sending the transaction, or simulate it and
+//   only send the ones with the outcome they like.
+// - the leader producing the block can reorder or drop transactions depending on the outcome.
+// - the seeds come from the caller. Seeds a user picks add nothing the user doesn't know already, they only make
+//   two numbers taken in the same slot different.
+// Anything with real money needs a commit-reveal scheme like the rps example or a VRF oracle.
+use std::convert::TryInto;
+
+use solana_program::{account_info::AccountInfo, hash::hashv, program_error::ProgramError, sysvar};
+
+// The sysvar is too big to deserialize on-chain, so the most recent hash is read directly from the account data:
+// a u64 length followed by (slot, hash) entries, newest first
+const RECENT_HASH_START: usize = 16;
+const RECENT_HASH_END: usize = RECENT_HASH_START + 32;
+
+// The most recent entry of the SlotHashes sysvar. Any other account is refused, otherwise the caller could pass an
+// account with a hash of their choice.
+pub fn recent_slot_hash(slot_hashes: &AccountInfo) -> Result<[u8; 32], ProgramError> {
+    if *slot_hashes.key != sysvar::slot_hashes::id() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let data = slot_hashes.try_borrow_data()?;
+    let recent_hash = data
+        .get(RECENT_HASH_START..RECENT_HASH_END)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    Ok(recent_hash.try_into().unwrap())
+}
+
+// Hashes the most recent slot hash together with the seeds and takes the first 8 bytes of the result
+pub fn random_u64(slot_hashes: &AccountInfo, seeds: &[&[u8]]) -> Result<u64, ProgramError> {
+    let recent_hash = recent_slot_hash(slot_hashes)?;
+
+    let mut input: Vec<&[u8]> = Vec::with_capacity(seeds.len() + 1);
+    input.push(&recent_hash);
+    input.extend_from_slice(seeds);
+    let hash = hashv(&input);
+
+    Ok(u64::from_le_bytes(hash.to_bytes()[..8].try_into().unwrap()))
+}
+
+// A number between 0 and `bound`, excluded. The modulo favours the small numbers a little, by at most
+// bound / 2^64, which doesn't matter next to the problems above.
+pub fn random_below(slot_hashes: &AccountInfo, seeds: &[&[u8]], bound: u64) -> Result<u64, ProgramError> {
+    if bound == 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(random_u64(slot_hashes, seeds)? % bound)
+}
@@ -0,0 +1,257 @@
+import yaml
+import sys
+import json
+import time
+import base64
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+from spl.token.instructions import AuthorityType
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'faucet.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'faucet-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+
+def get_faucet_address(mint: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("faucet", encoding="utf8"), bytes(mint)], program_id)[0]
+
+def get_claim_address(faucet: PublicKey, requester: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("claim", encoding="utf8"), bytes(faucet), bytes(requester)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def create_dev_token(authority: Keypair) -> Token:
+    token = Token.create_mint(
+        conn=client,
+        payer=authority,
+        mint_authority=authority.public_key,
+        decimals=6,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    # the faucet mints the tokens it hands out, so the mint authority moves to the faucet PDA
+    faucet = get_faucet_address(token.pubkey)
+    print(f"Created dev token {token.pubkey}, handing the mint authority to the faucet {faucet}")
+    token.set_authority(
+        account=token.pubkey,
+        current_authority=authority,
+        authority_type=AuthorityType.MINT_TOKENS,
+        new_authority=faucet,
+        opts=TxOpts(skip_confirmation=False)
+    )
+    return token
+
+def init_faucet(authority: Keypair, mint: PublicKey, amount: int, interval_slots: int) -> PublicKey:
+    faucet = get_faucet_address(mint)
+    print(f"\nCreating faucet {faucet}, handing out {amount} tokens every {interval_slots} slots")
+    send(
+        authority,
+        (0).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little') + interval_slots.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=faucet, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=mint, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return faucet
+
+def claim(requester: Keypair, faucet: PublicKey, mint: PublicKey, destination: PublicKey):
+    print(f"\n{requester.public_key} claims from the faucet")
+    send(
+        requester,
+        (1).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=requester.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=faucet, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_claim_address(faucet, requester.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=mint, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def update_faucet(authority: Keypair, faucet: PublicKey, amount: int, interval_slots: int):
+    print(f"\nThe faucet now hands out {amount} tokens every {interval_slots} slots")
+    send(
+        authority,
+        (2).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little') + interval_slots.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=faucet, is_signer=False, is_writable=True),
+        ],
+    )
+
+def show_claim_record(faucet: PublicKey, requester: PublicKey):
+    account = client.get_account_info(get_claim_address(faucet, requester))["result"]["value"]
+    data = base64.b64decode(account["data"][0])
+    # is_initialized, faucet and requester come first
+    last_claim_slot = int.from_bytes(data[65:73], byteorder='little')
+    claims = int.from_bytes(data[73:81], byteorder='little')
+    print(f"{requester} claimed {claims} times, the last time in slot {last_claim_slot}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    requester = setup_user_keypair()
+
+    interval_slots = 20
+    token = create_dev_token(payer)
+    faucet = init_faucet(payer, token.pubkey, 1_000_000, interval_slots)
+    destination = create_token_account(token, requester.public_key)
+
+    claim(requester, faucet, token.pubkey, destination)
+    print(f"Requester token balance: {token_balance(token, destination)}")
+    show_claim_record(faucet, requester.public_key)
+
+    try:
+        claim(requester, faucet, token.pubkey, destination)
+    except Exception as e:
+        print(f"Claiming again within {interval_slots} slots fails as expected: {e}")
+
+    # a slot takes roughly 400ms
+    print(f"\nWaiting for {interval_slots} slots...")
+    time.sleep(interval_slots * 0.5 + 2)
+    claim(requester, faucet, token.pubkey, destination)
+    print(f"Requester token balance: {token_balance(token, destination)}")
+    show_claim_record(faucet, requester.public_key)
+
+    update_faucet(payer, faucet, 5_000_000, 100)
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum FaucetError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Faucet Address")]
+    InvalidFaucetAddress,
+    #[error("Invalid Claim Address")]
+    InvalidClaimAddress,
+    #[error("Invalid Mint")]
+    InvalidMint,
+    #[error("Not Authority")]
+    NotAuthority,
+    #[error("Claim Too Soon")]
+    ClaimTooSoon,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<FaucetError> for ProgramError {
+    fn from(e: FaucetError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,77 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::FaucetError::InvalidInstruction;
+
+pub enum FaucetInstruction {
+
+    // Creates the faucet of a mint in a PDA derived from ["faucet", mint]. The faucet PDA has to be the mint
+    // authority of the mint already, the faucet mints the tokens it hands out.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The faucet authority
+    // 1. `[writable]` The faucet PDA
+    // 2. `[]` The mint
+    // 3. `[]` The system program
+    InitFaucet {
+        amount: u64,
+        interval_slots: u64,
+    },
+
+    // Mints the faucet amount to the destination. A requester can claim once per interval, the claim record
+    // at ["claim", faucet, requester] is created on the first claim.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The requester, pays for the claim record
+    // 1. `[]` The faucet PDA
+    // 2. `[writable]` The claim record PDA
+    // 3. `[writable]` The mint
+    // 4. `[writable]` The destination token account
+    // 5. `[]` The system program
+    // 6. `[]` The token program
+    Claim,
+
+    // Changes the amount per claim and the interval.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The faucet authority
+    // 1. `[writable]` The faucet PDA
+    UpdateFaucet {
+        amount: u64,
+        interval_slots: u64,
+    },
+}
+
+impl FaucetInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitFaucet {
+                amount: Self::unpack_u64(rest, 0)?,
+                interval_slots: Self::unpack_u64(rest, 8)?,
+            },
+            1 => Self::Claim,
+            2 => Self::UpdateFaucet {
+                amount: Self::unpack_u64(rest, 0)?,
+                interval_slots: Self::unpack_u64(rest, 8)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
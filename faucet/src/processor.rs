@@ -0,0 +1,286 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::Mint;
+
+use crate::{
+    instruction::FaucetInstruction,
+    error::FaucetError,
+    state::{ClaimRecord, Faucet},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = FaucetInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            FaucetInstruction::InitFaucet { amount, interval_slots } => {
+                msg!("Instruction: InitFaucet");
+                Self::process_init_faucet(accounts, amount, interval_slots, program_id)
+            },
+            FaucetInstruction::Claim => {
+                msg!("Instruction: Claim");
+                Self::process_claim(accounts, program_id)
+            },
+            FaucetInstruction::UpdateFaucet { amount, interval_slots } => {
+                msg!("Instruction: UpdateFaucet");
+                Self::process_update_faucet(accounts, amount, interval_slots, program_id)
+            }
+        }
+    }
+
+    fn process_init_faucet(
+        accounts: &[AccountInfo],
+        amount: u64,
+        interval_slots: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let faucet_account = next_account_info(account_info_iter)?;
+        let mint = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (faucet_pda, bump_seed) = Pubkey::find_program_address(&[b"faucet", mint.key.as_ref()], program_id);
+        if faucet_pda != *faucet_account.key {
+            return Err(FaucetError::InvalidFaucetAddress.into());
+        }
+        Self::check_mint(mint, &faucet_pda)?;
+
+        if !faucet_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating faucet account...");
+        Self::create_pda_account(
+            authority,
+            faucet_account,
+            system_program,
+            Faucet::LEN,
+            program_id,
+            &[b"faucet", mint.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let faucet_info = Faucet {
+            is_initialized: true,
+            authority: *authority.key,
+            mint: *mint.key,
+            amount,
+            interval_slots,
+            bump_seed,
+        };
+        faucet_info.serialize(&mut &mut faucet_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_claim(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let requester = next_account_info(account_info_iter)?;
+
+        if !requester.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let faucet_account = next_account_info(account_info_iter)?;
+        let faucet_info = Self::unpack_faucet(faucet_account, program_id)?;
+
+        let claim_account = next_account_info(account_info_iter)?;
+        let (claim_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"claim", faucet_account.key.as_ref(), requester.key.as_ref()],
+            program_id,
+        );
+        if claim_pda != *claim_account.key {
+            return Err(FaucetError::InvalidClaimAddress.into());
+        }
+
+        let mint = next_account_info(account_info_iter)?;
+        if *mint.key != faucet_info.mint {
+            return Err(FaucetError::InvalidMint.into());
+        }
+
+        let destination = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Slots are used instead of the unix timestamp, they are what the validators agree on and can't
+        // drift. The limit is per pubkey only, a requester with many keypairs can still claim many times.
+        let slot = Clock::get()?.slot;
+        let mut claim_info = if claim_account.data_is_empty() {
+            msg!("Creating claim record...");
+            Self::create_pda_account(
+                requester,
+                claim_account,
+                system_program,
+                ClaimRecord::LEN,
+                program_id,
+                &[b"claim", faucet_account.key.as_ref(), requester.key.as_ref(), &[bump_seed]],
+            )?;
+
+            ClaimRecord {
+                is_initialized: true,
+                faucet: *faucet_account.key,
+                requester: *requester.key,
+                last_claim_slot: slot,
+                claims: 0,
+                bump_seed,
+            }
+        } else {
+            let claim_info = Self::unpack_claim_record(claim_account, program_id)?;
+            let next_claim_slot = claim_info.next_claim_slot(faucet_info.interval_slots);
+            if slot < next_claim_slot {
+                msg!("The next claim is possible at slot {}, this is slot {}", next_claim_slot, slot);
+                return Err(FaucetError::ClaimTooSoon.into());
+            }
+            claim_info
+        };
+
+        msg!("Calling the token program to mint {} tokens...", faucet_info.amount);
+        invoke_signed(
+            &spl_token::instruction::mint_to(
+                token_program.key,
+                mint.key,
+                destination.key,
+                faucet_account.key,
+                &[faucet_account.key],
+                faucet_info.amount,
+            )?,
+            &[
+                mint.clone(),
+                destination.clone(),
+                faucet_account.clone(),
+                token_program.clone(),
+            ],
+            &[&[b"faucet", faucet_info.mint.as_ref(), &[faucet_info.bump_seed]]],
+        )?;
+
+        claim_info.last_claim_slot = slot;
+        claim_info.claims = claim_info.claims
+            .checked_add(1)
+            .ok_or(FaucetError::AmountOverflow)?;
+        claim_info.serialize(&mut &mut claim_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_update_faucet(
+        accounts: &[AccountInfo],
+        amount: u64,
+        interval_slots: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let faucet_account = next_account_info(account_info_iter)?;
+        let mut faucet_info = Self::unpack_faucet(faucet_account, program_id)?;
+        if faucet_info.authority != *authority.key {
+            return Err(FaucetError::NotAuthority.into());
+        }
+
+        msg!("The faucet hands out {} tokens every {} slots", amount, interval_slots);
+        faucet_info.amount = amount;
+        faucet_info.interval_slots = interval_slots;
+        faucet_info.serialize(&mut &mut faucet_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn unpack_faucet(faucet_account: &AccountInfo, program_id: &Pubkey) -> Result<Faucet, ProgramError> {
+        if faucet_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let faucet_info = Faucet::try_from_slice(&faucet_account.data.borrow())?;
+        if !faucet_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(faucet_info)
+    }
+
+    fn unpack_claim_record(claim_account: &AccountInfo, program_id: &Pubkey) -> Result<ClaimRecord, ProgramError> {
+        if claim_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let claim_info = ClaimRecord::try_from_slice(&claim_account.data.borrow())?;
+        if !claim_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(claim_info)
+    }
+
+    // the faucet can only hand out tokens if it is the mint authority
+    fn check_mint(mint: &AccountInfo, faucet: &Pubkey) -> ProgramResult {
+        if *mint.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mint_info = Mint::unpack(&mint.try_borrow_data()?)?;
+        if mint_info.mint_authority != Some(*faucet).into() {
+            return Err(FaucetError::InvalidMint.into());
+        }
+
+        Ok(())
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,37 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Faucet {
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    // tokens minted per claim, in the smallest unit of the mint
+    pub amount: u64,
+    // a requester has to wait this many slots between two claims
+    pub interval_slots: u64,
+    pub bump_seed: u8,
+}
+
+impl Faucet {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1;
+}
+
+// One per requester and faucet, remembers when the requester claimed last
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ClaimRecord {
+    pub is_initialized: bool,
+    pub faucet: Pubkey,
+    pub requester: Pubkey,
+    pub last_claim_slot: u64,
+    pub claims: u64,
+    pub bump_seed: u8,
+}
+
+impl ClaimRecord {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1;
+
+    pub fn next_claim_slot(&self, interval_slots: u64) -> u64 {
+        self.last_claim_slot.saturating_add(interval_slots)
+    }
+}
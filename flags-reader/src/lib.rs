@@ -0,0 +1,68 @@
+// Read side of the flags program. Programs which are configured through flags depend on this crate instead of
+// the flags program, it contains the account layout and the checks needed to trust a flag account.
+pub mod state;
+
+use borsh::BorshDeserialize;
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::state::{Flag, FlagValue};
+
+// A flag lives in a PDA of the flags program derived from ["flag", admin, name]
+pub fn flag_address(flags_program_id: &Pubkey, admin: &Pubkey, name: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"flag", admin.as_ref(), name.as_bytes()], flags_program_id)
+}
+
+// Reads the flag `name` of `admin`. The account has to be the flag's PDA, so a caller can't swap in another
+// flag. A flag which was never set or has been deleted has no account and reads as None.
+pub fn read_flag(
+    flag_account: &AccountInfo,
+    flags_program_id: &Pubkey,
+    admin: &Pubkey,
+    name: &str,
+) -> Result<Option<FlagValue>, ProgramError> {
+    let (flag_pda, _) = flag_address(flags_program_id, admin, name);
+    if flag_pda != *flag_account.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if flag_account.data_is_empty() {
+        return Ok(None);
+    }
+
+    // only the flags program can write the data of accounts it owns
+    if flag_account.owner != flags_program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // a name shorter than the longest one leaves zeros at the end, which deserialize doesn't read
+    let flag_info = Flag::deserialize(&mut &flag_account.data.borrow()[..])?;
+    if !flag_info.is_initialized {
+        return Ok(None);
+    }
+
+    Ok(Some(flag_info.value))
+}
+
+// true only if the flag is set to the boolean true, a missing flag counts as false
+pub fn is_enabled(
+    flag_account: &AccountInfo,
+    flags_program_id: &Pubkey,
+    admin: &Pubkey,
+    name: &str,
+) -> Result<bool, ProgramError> {
+    let value = read_flag(flag_account, flags_program_id, admin, name)?;
+    Ok(value == Some(FlagValue::Bool(true)))
+}
+
+pub fn read_u64(
+    flag_account: &AccountInfo,
+    flags_program_id: &Pubkey,
+    admin: &Pubkey,
+    name: &str,
+) -> Result<Option<u64>, ProgramError> {
+    match read_flag(flag_account, flags_program_id, admin, name)? {
+        Some(FlagValue::U64(value)) => Ok(Some(value)),
+        Some(FlagValue::Bool(_)) => Err(ProgramError::InvalidAccountData),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,25 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Longest flag name, in bytes. The name is a seed of the flag PDA, and seeds are at most 32 bytes long.
+pub const MAX_NAME_LEN: usize = 32;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagValue {
+    Bool(bool),
+    U64(u64),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Flag {
+    pub is_initialized: bool,
+    pub admin: Pubkey,
+    pub name: String,
+    pub value: FlagValue,
+    pub bump_seed: u8,
+}
+
+impl Flag {
+    // the account is allocated for the longest name and the bigger value variant
+    pub const LEN: usize = 1 + 32 + (4 + MAX_NAME_LEN) + (1 + 8) + 1;
+}
@@ -0,0 +1,212 @@
+import yaml
+import sys
+import json
+import base64
+
+from os import path
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'flags.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'flags-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_flag_address(admin: PublicKey, name: str) -> PublicKey:
+    return PublicKey.find_program_address([bytes("flag", encoding="utf8"), bytes(admin), bytes(name, encoding="utf8")], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def set_flag(admin: Keypair, name: str, value):
+    # booleans are sent as kind 0, numbers as kind 1, the value is always eight bytes
+    kind = 0 if isinstance(value, bool) else 1
+    print(f"\nSetting flag {name} to {value}")
+    send(
+        admin,
+        (0).to_bytes(1, byteorder='little') + kind.to_bytes(1, byteorder='little') + int(value).to_bytes(8, byteorder='little') + bytes(name, encoding="utf8"),
+        [
+            AccountMeta(pubkey=admin.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_flag_address(admin.public_key, name), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def delete_flag(admin: Keypair, name: str):
+    print(f"\nDeleting flag {name}")
+    send(
+        admin,
+        (1).to_bytes(1, byteorder='little') + bytes(name, encoding="utf8"),
+        [
+            AccountMeta(pubkey=admin.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_flag_address(admin.public_key, name), is_signer=False, is_writable=True),
+        ],
+    )
+
+def read_flag(admin: PublicKey, name: str):
+    """
+    Reads a flag the same way flags-reader does on-chain, a missing account means the flag is unset.
+    """
+    account = client.get_account_info(get_flag_address(admin, name))["result"]["value"]
+    if account is None:
+        return None
+    data = base64.b64decode(account["data"][0])
+    # is_initialized, admin, then the borsh string of the name
+    name_len = int.from_bytes(data[33:37], byteorder='little')
+    offset = 37 + name_len
+    kind = data[offset]
+    if kind == 0:
+        return data[offset + 1] != 0
+    return int.from_bytes(data[offset + 1:offset + 9], byteorder='little')
+
+def show_flag(admin: PublicKey, name: str):
+    print(f"Flag {name}: {read_flag(admin, name)}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    other_admin = setup_user_keypair()
+
+    set_flag(payer, "paused", True)
+    show_flag(payer.public_key, "paused")
+    set_flag(payer, "paused", False)
+    show_flag(payer.public_key, "paused")
+
+    set_flag(payer, "max_entries", 100)
+    show_flag(payer.public_key, "max_entries")
+
+    # the admin is part of the address, so somebody else's flag with the same name is a different account
+    set_flag(other_admin, "paused", True)
+    show_flag(other_admin.public_key, "paused")
+    show_flag(payer.public_key, "paused")
+
+    try:
+        set_flag(payer, "a_name_which_is_longer_than_32_bytes", True)
+    except Exception as e:
+        print(f"Setting a flag with a name longer than 32 bytes fails as expected: {e}")
+
+    delete_flag(payer, "max_entries")
+    show_flag(payer.public_key, "max_entries")
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum FlagsError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Flag Address")]
+    InvalidFlagAddress,
+    #[error("Invalid Flag Name")]
+    InvalidFlagName,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<FlagsError> for ProgramError {
+    fn from(e: FlagsError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,73 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::{error::FlagsError::InvalidInstruction, state::FlagValue};
+
+pub enum FlagsInstruction {
+
+    // Sets the flag `name` of the admin in a PDA derived from ["flag", admin, name]. The account is created
+    // the first time a flag is set, afterwards the value is overwritten.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The admin
+    // 1. `[writable]` The flag PDA
+    // 2. `[]` The system program
+    SetFlag {
+        name: String,
+        value: FlagValue,
+    },
+
+    // Deletes the flag `name`, the rent goes back to the admin. A deleted flag reads as unset.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The admin
+    // 1. `[writable]` The flag PDA
+    DeleteFlag {
+        name: String,
+    },
+}
+
+impl FlagsInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            // the kind of the value, the value as u64 and the name in the remaining bytes
+            0 => {
+                let (kind, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                let value = Self::unpack_u64(rest, 0)?;
+                let value = match kind {
+                    0 => FlagValue::Bool(value != 0),
+                    1 => FlagValue::U64(value),
+                    _ => return Err(InvalidInstruction.into()),
+                };
+                Self::SetFlag {
+                    name: Self::unpack_name(&rest[8..])?,
+                    value,
+                }
+            },
+            1 => Self::DeleteFlag {
+                name: Self::unpack_name(rest)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+
+    fn unpack_name(input: &[u8]) -> Result<String, ProgramError> {
+        let name = String::from_utf8(input.to_vec()).map_err(|_| InvalidInstruction)?;
+        Ok(name)
+    }
+}
@@ -0,0 +1,167 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::FlagsInstruction,
+    error::FlagsError,
+    state::{Flag, FlagValue, MAX_NAME_LEN},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = FlagsInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            FlagsInstruction::SetFlag { name, value } => {
+                msg!("Instruction: SetFlag");
+                Self::process_set_flag(accounts, name, value, program_id)
+            },
+            FlagsInstruction::DeleteFlag { name } => {
+                msg!("Instruction: DeleteFlag");
+                Self::process_delete_flag(accounts, name, program_id)
+            }
+        }
+    }
+
+    fn process_set_flag(
+        accounts: &[AccountInfo],
+        name: String,
+        value: FlagValue,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        // the name is a seed of the PDA, a seed can't be longer than 32 bytes
+        if name.is_empty() || name.len() > MAX_NAME_LEN {
+            return Err(FlagsError::InvalidFlagName.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+
+        if !admin.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let flag_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (flag_pda, bump_seed) = flags_reader::flag_address(program_id, admin.key, &name);
+        if flag_pda != *flag_account.key {
+            return Err(FlagsError::InvalidFlagAddress.into());
+        }
+
+        // the admin is one of the seeds, so only the admin can ever get to this account
+        let flag_info = if flag_account.data_is_empty() {
+            msg!("Creating flag account...");
+            Self::create_pda_account(
+                admin,
+                flag_account,
+                system_program,
+                Flag::LEN,
+                program_id,
+                &[b"flag", admin.key.as_ref(), name.as_bytes(), &[bump_seed]],
+            )?;
+
+            Flag {
+                is_initialized: true,
+                admin: *admin.key,
+                name,
+                value,
+                bump_seed,
+            }
+        } else {
+            let mut flag_info = Self::unpack_flag(flag_account, program_id)?;
+            flag_info.value = value;
+            flag_info
+        };
+
+        msg!("Flag {} is set to {:?}", flag_info.name, flag_info.value);
+        flag_info.serialize(&mut &mut flag_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_delete_flag(
+        accounts: &[AccountInfo],
+        name: String,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+
+        if !admin.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let flag_account = next_account_info(account_info_iter)?;
+        let (flag_pda, _) = flags_reader::flag_address(program_id, admin.key, &name);
+        if flag_pda != *flag_account.key {
+            return Err(FlagsError::InvalidFlagAddress.into());
+        }
+        Self::unpack_flag(flag_account, program_id)?;
+
+        msg!("Closing the flag account...");
+        **admin.lamports.borrow_mut() = admin.lamports()
+            .checked_add(flag_account.lamports())
+            .ok_or(FlagsError::AmountOverflow)?;
+        **flag_account.lamports.borrow_mut() = 0;
+        flag_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_flag(flag_account: &AccountInfo, program_id: &Pubkey) -> Result<Flag, ProgramError> {
+        if flag_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // read up to the end of the name, the rest of the account is room for longer ones
+        let flag_info = Flag::deserialize(&mut &flag_account.data.borrow()[..])?;
+        if !flag_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(flag_info)
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,2 @@
+// The account layout is defined in flags-reader, so the programs reading flags don't have to depend on this program
+pub use flags_reader::state::{Flag, FlagValue, MAX_NAME_LEN};
@@ -0,0 +1,245 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+POOL_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'authority': types.fixed_array(types.u8, 32),
+    'fee_bps': types.u16,
+    'owed': types.u64,
+    'total_fees': types.u64,
+    'bump_seed': types.u8
+})
+
+SYSVAR_INSTRUCTIONS_ID = PublicKey("Sysvar1nstructions1111111111111111111111111")
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'flashloan.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'flashloan-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def get_pool_address(authority: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("pool", encoding="utf8"), bytes(authority)], program_id)[0]
+
+def get_pool(pool: PublicKey) -> dict:
+    account_info = client.get_account_info(pool)["result"]["value"]
+    return borsh.deserialize(POOL_SCHEMA, base64.b64decode(account_info["data"][0]))
+
+def init_pool(authority: Keypair, fee_bps: int, liquidity: int) -> PublicKey:
+    pool = get_pool_address(authority.public_key)
+    if client.get_account_info(pool)["result"]["value"] is not None:
+        print(f"Pool {pool} already exists")
+        return pool
+
+    print(f"\nCreating pool {pool} with a fee of {fee_bps / 100}% and {liquidity} lamports of liquidity")
+    tx = Transaction().add(
+        TransactionInstruction(
+            data=(0).to_bytes(1, byteorder='little') + fee_bps.to_bytes(2, byteorder='little'),
+            keys=[
+                AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+                AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+                AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            ],
+            program_id=program_id,
+        ),
+        TransactionInstruction(
+            data=(1).to_bytes(1, byteorder='little') + liquidity.to_bytes(8, byteorder='little'),
+            keys=[
+                AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+                AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+                AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            ],
+            program_id=program_id,
+        ),
+    )
+    result = client.send_transaction(tx, authority)
+    client.confirm_transaction(result["result"])
+    return pool
+
+def borrow_ix(borrower: PublicKey, pool: PublicKey, amount: int) -> TransactionInstruction:
+    return TransactionInstruction(
+        data=(2).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=borrower, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYSVAR_INSTRUCTIONS_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+
+def repay_ix(borrower: PublicKey, pool: PublicKey, amount: int) -> TransactionInstruction:
+    return TransactionInstruction(
+        data=(3).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=borrower, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+
+def flash_loan(borrower: Keypair, pool: PublicKey, amount: int, repayment: int = None):
+    # everything between the two instructions could use the borrowed lamports, e.g. an arbitrage trade
+    tx = Transaction().add(borrow_ix(borrower.public_key, pool, amount))
+    if repayment is not None:
+        tx.add(repay_ix(borrower.public_key, pool, repayment))
+    result = client.send_transaction(tx, borrower)
+    client.confirm_transaction(result["result"])
+
+def show_pool(pool: PublicKey):
+    pool_info = get_pool(pool)
+    print(f"Pool holds {client.get_balance(pool)['result']['value']} lamports, earned {pool_info['total_fees']} in fees")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    pool = init_pool(payer, 50, LAMPORTS_PER_SOL)
+    show_pool(pool)
+
+    borrower = setup_user_keypair()
+    amount = LAMPORTS_PER_SOL // 2
+    owed = amount + amount * get_pool(pool)['fee_bps'] // 10000
+
+    print(f"\nBorrowing {amount} lamports and paying back {owed} in the same transaction")
+    flash_loan(borrower, pool, amount, owed)
+    show_pool(pool)
+
+    print("\nBorrowing without paying back")
+    try:
+        flash_loan(borrower, pool, amount)
+    except Exception as e:
+        print(f"The borrow fails, there is no repayment later in the transaction: {e}")
+
+    print("\nBorrowing and paying back without the fee")
+    try:
+        flash_loan(borrower, pool, amount, amount)
+    except Exception as e:
+        print(f"The borrow fails, the repayment is too small: {e}")
+    show_pool(pool)
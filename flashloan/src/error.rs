@@ -0,0 +1,35 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum FlashLoanError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Pool Address")]
+    InvalidPoolAddress,
+    #[error("Invalid Fee")]
+    InvalidFee,
+    #[error("Not The Authority")]
+    NotTheAuthority,
+    #[error("Insufficient Liquidity")]
+    InsufficientLiquidity,
+    #[error("Loan Outstanding")]
+    LoanOutstanding,
+    #[error("No Loan Outstanding")]
+    NoLoanOutstanding,
+    #[error("Missing Repayment")]
+    MissingRepayment,
+    #[error("Repayment Too Small")]
+    RepaymentTooSmall,
+    #[error("Borrow Through CPI")]
+    BorrowThroughCpi,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<FlashLoanError> for ProgramError {
+    fn from(e: FlashLoanError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
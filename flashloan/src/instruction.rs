@@ -0,0 +1,109 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::FlashLoanError::InvalidInstruction;
+
+// Tag of the Repay instruction, Borrow looks for it in the rest of the transaction
+pub const REPAY_TAG: u8 = 3;
+
+pub enum FlashLoanInstruction {
+
+    // Creates the pool in a PDA derived from ["pool", authority]. The pool account holds the lamports it lends.
+    // Followed by the u16 fee in basis points.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The authority
+    // 1. `[writable]` The pool PDA
+    // 2. `[]` The system program
+    InitPool {
+        fee_bps: u16,
+    },
+
+    // Adds liquidity to the pool.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The depositor
+    // 1. `[writable]` The pool PDA
+    // 2. `[]` The system program
+    Deposit {
+        amount: u64,
+    },
+
+    // Lends lamports from the pool. Fails unless a later instruction of the same transaction is a Repay of
+    // this program for the same pool, paying back the amount plus the fee.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The borrower
+    // 1. `[writable]` The pool PDA
+    // 2. `[]` The instructions sysvar
+    Borrow {
+        amount: u64,
+    },
+
+    // Pays back the outstanding loan plus the fee.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The borrower
+    // 1. `[writable]` The pool PDA
+    // 2. `[]` The system program
+    Repay {
+        amount: u64,
+    },
+
+    // Takes lamports out of the pool, only while no loan is outstanding.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The authority
+    // 1. `[writable]` The pool PDA
+    Withdraw {
+        amount: u64,
+    },
+}
+
+impl FlashLoanInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match *tag {
+            0 => Self::InitPool {
+                fee_bps: rest
+                    .get(..2)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u16::from_le_bytes)
+                    .ok_or(InvalidInstruction)?,
+            },
+            1 => Self::Deposit {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::Borrow {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            REPAY_TAG => Self::Repay {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            4 => Self::Withdraw {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,322 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{
+        self,
+        instructions::{get_instruction_relative, load_current_index_checked, load_instruction_at_checked},
+        rent::Rent,
+        Sysvar,
+    },
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::{FlashLoanInstruction, REPAY_TAG},
+    error::FlashLoanError,
+    state::Pool,
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = FlashLoanInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            FlashLoanInstruction::InitPool { fee_bps } => {
+                msg!("Instruction: InitPool");
+                Self::process_init_pool(accounts, fee_bps, program_id)
+            },
+            FlashLoanInstruction::Deposit { amount } => {
+                msg!("Instruction: Deposit");
+                Self::process_deposit(accounts, amount, program_id)
+            },
+            FlashLoanInstruction::Borrow { amount } => {
+                msg!("Instruction: Borrow");
+                Self::process_borrow(accounts, amount, program_id)
+            },
+            FlashLoanInstruction::Repay { amount } => {
+                msg!("Instruction: Repay");
+                Self::process_repay(accounts, amount, program_id)
+            },
+            FlashLoanInstruction::Withdraw { amount } => {
+                msg!("Instruction: Withdraw");
+                Self::process_withdraw(accounts, amount, program_id)
+            }
+        }
+    }
+
+    fn process_init_pool(
+        accounts: &[AccountInfo],
+        fee_bps: u16,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if fee_bps > 10_000 {
+            return Err(FlashLoanError::InvalidFee.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (pool_pda, bump_seed) = Pubkey::find_program_address(&[b"pool", authority.key.as_ref()], program_id);
+        if pool_pda != *pool_account.key {
+            return Err(FlashLoanError::InvalidPoolAddress.into());
+        }
+
+        if !pool_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating pool account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                authority.key,
+                pool_account.key,
+                Rent::get()?.minimum_balance(Pool::LEN),
+                Pool::LEN as u64,
+                program_id,
+            ),
+            &[
+                authority.clone(),
+                pool_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"pool", authority.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        let pool_info = Pool {
+            is_initialized: true,
+            authority: *authority.key,
+            fee_bps,
+            owed: 0,
+            total_fees: 0,
+            bump_seed,
+        };
+        pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_deposit(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let depositor = next_account_info(account_info_iter)?;
+
+        if !depositor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        Self::unpack_pool(pool_account, program_id)?;
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to deposit {} lamports...", amount);
+        invoke(
+            &system_instruction::transfer(depositor.key, pool_account.key, amount),
+            &[
+                depositor.clone(),
+                pool_account.clone(),
+                system_program.clone(),
+            ],
+        )
+    }
+
+    fn process_borrow(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let borrower = next_account_info(account_info_iter)?;
+
+        if !borrower.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let mut pool_info = Self::unpack_pool(pool_account, program_id)?;
+
+        // a second borrow in the same transaction could otherwise point at the same repayment
+        if pool_info.owed > 0 {
+            return Err(FlashLoanError::LoanOutstanding.into());
+        }
+        if amount == 0 || amount > Self::funds(pool_account)? {
+            return Err(FlashLoanError::InsufficientLiquidity.into());
+        }
+
+        let instructions_sysvar = next_account_info(account_info_iter)?;
+        if *instructions_sysvar.key != sysvar::instructions::id() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // the sysvar only lists the top level instructions. Through a CPI the current instruction belongs to
+        // the calling program, and the search below would start at the wrong place
+        if get_instruction_relative(0, instructions_sysvar)?.program_id != *program_id {
+            return Err(FlashLoanError::BorrowThroughCpi.into());
+        }
+
+        let fee = pool_info.fee(amount);
+        let owed = amount
+            .checked_add(fee)
+            .ok_or(FlashLoanError::AmountOverflow)?;
+
+        let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+        if !Self::has_repayment(instructions_sysvar, current_index + 1, pool_account.key, owed, program_id) {
+            return Err(FlashLoanError::MissingRepayment.into());
+        }
+
+        // the fee is booked right away, a transaction where the repayment fails is rolled back as a whole
+        pool_info.owed = owed;
+        pool_info.total_fees = pool_info.total_fees
+            .checked_add(fee)
+            .ok_or(FlashLoanError::AmountOverflow)?;
+        pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+
+        msg!("Lending {} lamports, {} have to be paid back", amount, owed);
+        Self::transfer_from_pool(pool_account, borrower, amount)
+    }
+
+    fn process_repay(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let borrower = next_account_info(account_info_iter)?;
+
+        if !borrower.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let mut pool_info = Self::unpack_pool(pool_account, program_id)?;
+
+        if pool_info.owed == 0 {
+            return Err(FlashLoanError::NoLoanOutstanding.into());
+        }
+        if amount < pool_info.owed {
+            return Err(FlashLoanError::RepaymentTooSmall.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to repay {} lamports...", amount);
+        invoke(
+            &system_instruction::transfer(borrower.key, pool_account.key, amount),
+            &[
+                borrower.clone(),
+                pool_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        pool_info.owed = 0;
+        pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_withdraw(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let pool_info = Self::unpack_pool(pool_account, program_id)?;
+        if pool_info.authority != *authority.key {
+            return Err(FlashLoanError::NotTheAuthority.into());
+        }
+        if pool_info.owed > 0 {
+            return Err(FlashLoanError::LoanOutstanding.into());
+        }
+        if amount > Self::funds(pool_account)? {
+            return Err(FlashLoanError::InsufficientLiquidity.into());
+        }
+
+        msg!("Withdrawing {} lamports...", amount);
+        Self::transfer_from_pool(pool_account, authority, amount)
+    }
+
+    // Walks through the instructions following the borrow and looks for a Repay of this program, for the same
+    // pool and for at least the owed amount. Only the instruction data is checked here,
+    // Repay itself makes sure the lamports actually arrive.
+    fn has_repayment(
+        instructions_sysvar: &AccountInfo,
+        start_index: usize,
+        pool: &Pubkey,
+        owed: u64,
+        program_id: &Pubkey,
+    ) -> bool {
+        let mut index = start_index;
+        while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+            index += 1;
+
+            if instruction.program_id != *program_id || instruction.data.first() != Some(&REPAY_TAG) {
+                continue;
+            }
+            if instruction.accounts.get(1).map(|meta| meta.pubkey) != Some(*pool) {
+                continue;
+            }
+            if let Ok(FlashLoanInstruction::Repay { amount }) = FlashLoanInstruction::unpack(&instruction.data) {
+                if amount >= owed {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn unpack_pool(pool_account: &AccountInfo, program_id: &Pubkey) -> Result<Pool, ProgramError> {
+        if pool_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let pool_info = Pool::try_from_slice(&pool_account.data.borrow())?;
+        if !pool_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(pool_info)
+    }
+
+    // lamports held by the pool on top of its rent
+    fn funds(pool_account: &AccountInfo) -> Result<u64, ProgramError> {
+        let rent_lamports = Rent::get()?.minimum_balance(pool_account.data_len());
+        Ok(pool_account.lamports().saturating_sub(rent_lamports))
+    }
+
+    // The pool is owned by the program, so lamports are moved out of it directly
+    fn transfer_from_pool(pool_account: &AccountInfo, destination: &AccountInfo, amount: u64) -> ProgramResult {
+        **pool_account.lamports.borrow_mut() -= amount;
+        **destination.lamports.borrow_mut() = destination.lamports()
+            .checked_add(amount)
+            .ok_or(FlashLoanError::AmountOverflow)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Pool {
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+    // fee on every loan, in basis points
+    pub fee_bps: u16,
+    // lamports the current borrower has to pay back, loan plus fee. 0 while nothing is borrowed
+    pub owed: u64,
+    pub total_fees: u64,
+    pub bump_seed: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 1 + 32 + 2 + 8 + 8 + 1;
+
+    pub fn fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.fee_bps as u128 / 10_000) as u64
+    }
+}
@@ -5,7 +5,7 @@ import json
 import base64
 
 from random import random
-from os import path
+from os import path, environ
 from borsh import types
 from solana.rpc.api import Client
 from solana.keypair import Keypair
@@ -203,8 +203,15 @@ def checkProgram():
 def sayHello():
     print(f"\nGoing to say hello to {greeted_public_key}")
 
+    keys = [AccountMeta(greeted_public_key, False, True)]
+
+    # a program built with the allowlist feature also needs the greeter, who signs, and the allowlist
+    if "ALLOWLIST_ADDRESS" in environ:
+        keys.append(AccountMeta(payer.public_key, True, False))
+        keys.append(AccountMeta(PublicKey(environ["ALLOWLIST_ADDRESS"]), False, False))
+
     instructions = TransactionInstruction(
-        keys=[AccountMeta(greeted_public_key, False, True)],
+        keys=keys,
         program_id=program_id,
         data=bytes(0)    
     )
@@ -4,10 +4,14 @@
 // BorshDeserialize is used for converting bytecode into data.
 // Serializing is necessary because the programs must be parsed in BPF format.
 use borsh::{BorshDeserialize, BorshSerialize};
+// shank reads the derives below to generate an IDL of the program, they don't change the program itself.
+use shank::{ShankAccount, ShankInstruction};
 
 // The next use declaration brings the solana_program crate into the scope.
 // This crate contains a bunch of Solana source code that we'll
 // leverage to write on-chain programs.
+// The entrypoint macro isn't used when the crate is built with the no-entrypoint feature, see below.
+#[cfg_attr(feature = "no-entrypoint", allow(unused_imports))]
 use solana_program::{
     // account_info contains next_account_info, which is a public function
     // that returns the next AccountInfo or a NotEnoughAccountKeys error.
@@ -27,7 +31,17 @@ use solana_program::{
     program_error::ProgramError,
     // PubKey is struct.
     pubkey::Pubkey,
+    // invoke and invoke_signed call other programs, this is called a cross-program invocation or CPI.
+    program::{invoke, invoke_signed},
+    // Rent tells how many lamports an account of a given size needs to never be charged rent.
+    // The Sysvar trait gives us Rent::get() to read it.
+    sysvar::{rent::Rent, Sysvar},
+    // system_instruction builds the instructions of the system program, which creates all accounts.
+    system_instruction,
+    system_program,
 };
+// The result account of the calculator in function_calls, which GreetAbove reads
+use function_calls::processor::CalculatorResult;
 // we also have to add those dependencies to the Cargo.toml file.
 // Have a look into the Cargo.toml file. There you will find 3 dependencies.
 
@@ -44,15 +58,104 @@ use solana_program::{
 // related values. Each field defined within a struct has a name and a type.
 // GreetingAccount has only one field: counter with a type of u32, an
 // unsigned(positive) 32-bit integer.
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
 pub struct GreetingAccount {
     // number of greetings
     pub counter: u32,
 }
 
+// The instructions of the program. The first byte of the instruction data is the index of the variant, like borsh
+// packs an enum. Only Initialize and GreetAbove have to be asked for, any other data greets, so the clients which
+// send no data at all keep working.
+#[derive(Debug, Clone, PartialEq, Eq, ShankInstruction)]
+pub enum GreetingInstruction {
+    // The SPL Memo program may follow as the last account, the program then writes a memo of the greeting. It isn't
+    // listed below, the generated builders would require it.
+    #[account(0, writable, name = "greeting_account", desc = "Account owned by the program storing the GreetingAccount")]
+    Greet,
+    #[account(0, writable, signer, name = "payer", desc = "Pays the rent of the new greeting account")]
+    #[account(1, writable, name = "greeting_account", desc = "The PDA of the payer, or a new keypair which signs")]
+    #[account(2, name = "system_program", desc = "The system program, which creates the account")]
+    Initialize,
+    // Greets like Greet, but only if the calculator stored a result above the threshold, the 8 bytes little endian
+    // after the tag. The memo program may follow here too.
+    #[account(0, writable, name = "greeting_account", desc = "Account owned by the program storing the GreetingAccount")]
+    #[account(1, name = "result_account", desc = "Account of the calculator program storing a CalculatorResult")]
+    GreetAbove { threshold: u64 },
+}
+
+// the first byte of the instruction data of Initialize
+pub const INITIALIZE_TAG: u8 = 1;
+
+// the first byte of the instruction data of GreetAbove
+pub const GREET_ABOVE_TAG: u8 = 2;
+
+// The greeting account Initialize creates without a keypair is the PDA of these seeds and the payer
+pub const GREETING_PDA_SEED: &[u8] = b"greeting";
+
+impl GreetingInstruction {
+    pub fn pack(&self) -> Vec<u8> {
+        match self {
+            GreetingInstruction::Greet => vec![0],
+            GreetingInstruction::Initialize => vec![INITIALIZE_TAG],
+            GreetingInstruction::GreetAbove { threshold } => {
+                let mut data = vec![GREET_ABOVE_TAG];
+                data.extend_from_slice(&threshold.to_le_bytes());
+                data
+            },
+        }
+    }
+
+    // Anything which isn't Initialize or GreetAbove greets, even no data at all
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        match data.split_first() {
+            Some((&INITIALIZE_TAG, _)) => Ok(GreetingInstruction::Initialize),
+            Some((&GREET_ABOVE_TAG, threshold)) => {
+                let threshold = threshold.try_into().map_err(|_| ProgramError::InvalidInstructionData)?;
+                Ok(GreetingInstruction::GreetAbove { threshold: u64::from_le_bytes(threshold) })
+            },
+            _ => Ok(GreetingInstruction::Greet),
+        }
+    }
+}
+
+// The errors of the program besides the ones of ProgramError, the runtime gets them as ProgramError::Custom
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GreetingError {
+    // GreetAbove found a result which isn't above the threshold
+    ResultNotAboveThreshold,
+}
+
+impl From<GreetingError> for ProgramError {
+    fn from(e: GreetingError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+// The calculator doesn't declare its id, it is whatever address it was deployed to. GreetAbove only trusts result
+// accounts owned by the calculator, so its id is baked into the program when it is built:
+// CALCULATOR_PROGRAM_ID=<program id> cargo build-bpf
+// Without it the placeholder below is used, which no program is deployed to, the tests run the calculator there.
+pub const DEFAULT_CALCULATOR_PROGRAM_ID: &str = "Ca1cu1ator1111111111111111111111111111111111";
+
+// A CALCULATOR_PROGRAM_ID which isn't a pubkey fails every GreetAbove
+pub fn calculator_program_id() -> Result<Pubkey, ProgramError> {
+    use std::str::FromStr;
+
+    Pubkey::from_str(option_env!("CALCULATOR_PROGRAM_ID").unwrap_or(DEFAULT_CALCULATOR_PROGRAM_ID))
+        .map_err(|_| ProgramError::InvalidArgument)
+}
+
+// The address of the greeting PDA of the payer and its bump seed
+pub fn greeting_pda(payer: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GREETING_PDA_SEED, payer.as_ref()], program_id)
+}
+
 // All Solana programs must have an entrypoint that the runtime looks up and
 // calls when invoking a program. The entrypoint! macro declares process_instruction
-// as the entry to our program
+// as the entry to our program. Other crates which use GreetingAccount enable the no-entrypoint feature,
+// otherwise there would be two entrypoints.
+#[cfg(not(feature = "no-entrypoint"))]
 entrypoint!(process_instruction);
 
 // We implement process_instruction via a function with visibility set to public.
@@ -67,10 +170,9 @@ pub fn process_instruction(
     // accounts if a reference to an array of accounts to say hello to. It is the list
     // of accounts that will be operated upon in this code
     accounts: &[AccountInfo],
-    // _instruction_data - any additional data passed as u8 array. In this program
-    // we won't be consuming this data because it's just hello. We add the underscore
-    // to tell the compiler to chill.
-    _instruction_data: &[u8],
+    // instruction_data - any additional data passed as u8 array. Saying hello doesn't need
+    // any, only its first byte is looked at to tell Initialize and GreetAbove apart from Greet.
+    instruction_data: &[u8],
 // The function returns ProgramResult which we imported earlier.
 // ProgramResult is of Result type which is an Enum with two variants:
 // Ok representing success and containing a value, and Err representing error and
@@ -80,6 +182,13 @@ pub fn process_instruction(
     // print message on the program log
     msg!("Hello World Rust program entrypoint");
 
+    // Initialize creates the greeting account, everything else greets it. GreetAbove has a threshold to check first.
+    let threshold = match GreetingInstruction::unpack(instruction_data)? {
+        GreetingInstruction::Initialize => return initialize(program_id, accounts),
+        GreetingInstruction::GreetAbove { threshold } => Some(threshold),
+        GreetingInstruction::Greet => None,
+    };
+
     // We create a new variable accounts_iter using the let keyword.
     // We iterate over each account using the iter() method and bind them to the
     // variable as mutable references.
@@ -101,6 +210,18 @@ pub fn process_instruction(
         return Err(ProgramError::IncorrectProgramId);
     }
 
+    // GreetAbove passes the result account of the calculator right after the greeted account, have a look at
+    // check_calculator_result below
+    if let Some(threshold) = threshold {
+        check_calculator_result(accounts_iter, threshold)?;
+    }
+
+    // If the program has been built with `--features allowlist`, not everybody may greet anymore.
+    // The greeter has to sign and has to be a member of the allowlist, have a look at check_allowlist below.
+    // Without the feature, this line doesn't even get compiled.
+    #[cfg(feature = "allowlist")]
+    check_allowlist(accounts_iter)?;
+
     // try_from_slice is a method from the borsh crate that we use to deserialize an instance 
     // from slice of bytes to actual data our program can work with. Under the hood it looks like
     // this: fn try_from_slice(v: &[u8]) -> Result<Self>
@@ -115,5 +236,125 @@ pub fn process_instruction(
     // We log how many time the count has been incremented by using the msg! macro
     msg!("Greeted {} time(s)!", greeting_account.counter);
 
+    // If the client passed the memo program as the last account, have a look at write_memo below
+    write_memo(accounts_iter, &format!("Greeted {} {} time(s)", account.key, greeting_account.counter))
+}
+
+// Calls the SPL Memo program with a short description of what happened. Explorers show the memo next to the
+// transaction, which is easier to read than the bytes of the account. The memo is optional, without another account
+// nothing is written. Any other account than the memo program is refused, otherwise the program would call
+// whatever the client passes.
+fn write_memo(accounts_iter: &mut std::slice::Iter<AccountInfo>, memo: &str) -> ProgramResult {
+    let memo_program = match accounts_iter.next() {
+        Some(memo_program) => memo_program,
+        None => return Ok(()),
+    };
+    if !spl_memo::check_id(memo_program.key) {
+        msg!("Last account is not the memo program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    // no signers, the memo program only checks the ones it is given
+    invoke(&spl_memo::build_memo(memo.as_bytes(), &[]), std::slice::from_ref(memo_program))
+}
+
+// Reads the state of another program. Any program can read any account, but the data only means something if the
+// right program wrote it. Anybody can create an account of 24 bytes which looks like a CalculatorResult with a huge
+// result, owned by some program of their own, so the owner is checked before the data is looked at. The calculator
+// owns other accounts too, a vault state or its caller config. try_from_slice refuses them, it fails unless the data
+// is exactly as long as a CalculatorResult.
+fn check_calculator_result(accounts_iter: &mut std::slice::Iter<AccountInfo>, threshold: u64) -> ProgramResult {
+    let result_account = next_account_info(accounts_iter)?;
+    if *result_account.owner != calculator_program_id()? {
+        msg!("Result account is not owned by the calculator");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let result = CalculatorResult::try_from_slice(&result_account.data.borrow())
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if result.result <= threshold {
+        msg!("Result {} is not above {}", result.result, threshold);
+        return Err(GreetingError::ResultNotAboveThreshold.into());
+    }
+    Ok(())
+}
+
+// Creates the greeting account, so the client doesn't have to create it with the right size and owner first.
+// Only the system program can create accounts, so the program calls it. The new account has to sign its creation,
+// a keypair account signs the transaction itself. A PDA has no private key, instead the program which derived it
+// signs for it by passing its seeds to invoke_signed, and the runtime checks that they lead to the address.
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let payer = next_account_info(accounts_iter)?;
+    let greeting_account = next_account_info(accounts_iter)?;
+    let system_program_account = next_account_info(accounts_iter)?;
+
+    if !payer.is_signer {
+        msg!("Payer has to sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    // invoke would call whichever program is passed here, it has to be the real one
+    if !system_program::check_id(system_program_account.key) {
+        msg!("Wrong system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // The size and the rent are worked out here, on chain, the client can't get them wrong.
+    // A fresh account is zeroed, which is a GreetingAccount with a counter of 0, so nothing has to be written.
+    let size = GreetingAccount { counter: 0 }.try_to_vec()?.len();
+    let lamports = Rent::get()?.minimum_balance(size);
+    let create = system_instruction::create_account(payer.key, greeting_account.key, lamports, size as u64, program_id);
+    let create_accounts = [payer.clone(), greeting_account.clone(), system_program_account.clone()];
+
+    if greeting_account.is_signer {
+        invoke(&create, &create_accounts)?;
+    } else {
+        let (pda, bump) = greeting_pda(payer.key, program_id);
+        if *greeting_account.key != pda {
+            msg!("Greeting account is neither a signer nor the greeting PDA of the payer");
+            return Err(ProgramError::InvalidSeeds);
+        }
+        invoke_signed(&create, &create_accounts, &[&[GREETING_PDA_SEED, payer.key.as_ref(), &[bump]]])?;
+    }
+
+    msg!("Created greeting account {}", greeting_account.key);
+    Ok(())
+}
+
+// The allowlist is read directly from its account instead of calling the allowlist program through a CPI,
+// which is cheaper. Anyone could create an account with the same layout though, that's why the address of the
+// allowlist and the id of the allowlist program are baked into the program when it is built:
+// ALLOWLIST_PROGRAM_ID=<program id> ALLOWLIST_ADDRESS=<allowlist PDA> cargo build-bpf --features allowlist
+#[cfg(feature = "allowlist")]
+fn check_allowlist(accounts_iter: &mut std::slice::Iter<AccountInfo>) -> ProgramResult {
+    use allowlist::{error::AllowlistError, state::Allowlist};
+    use std::str::FromStr;
+
+    // the account which greets and the allowlist follow the greeted account
+    let greeter = next_account_info(accounts_iter)?;
+    let allowlist_account = next_account_info(accounts_iter)?;
+
+    // without checking the signature anybody could just pass the pubkey of a member
+    if !greeter.is_signer {
+        msg!("Greeter has to sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let allowlist_program_id = Pubkey::from_str(env!("ALLOWLIST_PROGRAM_ID"))
+        .map_err(|_| ProgramError::InvalidArgument)?;
+    let allowlist_address = Pubkey::from_str(env!("ALLOWLIST_ADDRESS"))
+        .map_err(|_| ProgramError::InvalidArgument)?;
+
+    if *allowlist_account.key != allowlist_address {
+        msg!("Wrong allowlist account");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // from_account_info checks that the allowlist program owns the account before deserializing it
+    let allowlist = Allowlist::from_account_info(allowlist_account, &allowlist_program_id)?;
+    if !allowlist.contains(greeter.key) {
+        msg!("{} is not on the allowlist", greeter.key);
+        return Err(AllowlistError::NotAMember.into());
+    }
+
     Ok(())
 }
@@ -0,0 +1,265 @@
+// Runs the program in a local bank with solana-program-test. The program isn't built for BPF here, the processor
+// is called natively, so the tests run with a plain `cargo test`.
+use borsh::BorshDeserialize;
+use helloworld::{greeting_pda, process_instruction, GreetingAccount, GreetingInstruction};
+use solana_program_test::{processor, BanksClient, ProgramTest, ProgramTestBanksClientExt};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+// the program expects exactly the borsh packed size of GreetingAccount, a single u32
+const GREETING_ACCOUNT_SIZE: usize = 4;
+
+async fn create_greeting_account(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    owner: &Pubkey,
+) -> Keypair {
+    let greeting_account = Keypair::new();
+    let rent = banks_client.get_rent().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::create_account(
+            &payer.pubkey(),
+            &greeting_account.pubkey(),
+            rent.minimum_balance(GREETING_ACCOUNT_SIZE),
+            GREETING_ACCOUNT_SIZE as u64,
+            owner,
+        )],
+        Some(&payer.pubkey()),
+        &[payer, &greeting_account],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+    greeting_account
+}
+
+fn greet_transaction(program_id: &Pubkey, greeting_account: &Pubkey, payer: &Keypair, recent_blockhash: Hash) -> Transaction {
+    Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(*program_id, &[], vec![AccountMeta::new(*greeting_account, false)])],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    )
+}
+
+fn initialize_instruction(program_id: &Pubkey, payer: &Pubkey, greeting_account: &Pubkey, signer: bool) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &GreetingInstruction::Initialize.pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*greeting_account, signer),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+async fn counter(banks_client: &mut BanksClient, greeting_account: &Pubkey) -> u32 {
+    let account = banks_client.get_account(*greeting_account).await.unwrap().unwrap();
+    GreetingAccount::try_from_slice(&account.data).unwrap().counter
+}
+
+#[tokio::test]
+async fn greeting_increments_counter() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new("helloworld", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let greeting_account = create_greeting_account(&mut banks_client, &payer, recent_blockhash, &program_id).await;
+    assert_eq!(counter(&mut banks_client, &greeting_account.pubkey()).await, 0);
+
+    banks_client
+        .process_transaction(greet_transaction(&program_id, &greeting_account.pubkey(), &payer, recent_blockhash))
+        .await
+        .unwrap();
+    assert_eq!(counter(&mut banks_client, &greeting_account.pubkey()).await, 1);
+
+    // the same transaction twice would be rejected as a duplicate, a new blockhash makes it a different one
+    let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+    banks_client
+        .process_transaction(greet_transaction(&program_id, &greeting_account.pubkey(), &payer, recent_blockhash))
+        .await
+        .unwrap();
+    assert_eq!(counter(&mut banks_client, &greeting_account.pubkey()).await, 2);
+}
+
+#[tokio::test]
+async fn greeting_account_of_another_program_is_rejected() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new("helloworld", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let greeting_account = create_greeting_account(&mut banks_client, &payer, recent_blockhash, &Pubkey::new_unique()).await;
+
+    let error = banks_client
+        .process_transaction(greet_transaction(&program_id, &greeting_account.pubkey(), &payer, recent_blockhash))
+        .await
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(error, TransactionError::InstructionError(0, InstructionError::IncorrectProgramId));
+    assert_eq!(counter(&mut banks_client, &greeting_account.pubkey()).await, 0);
+}
+
+#[tokio::test]
+async fn initialize_creates_the_greeting_pda() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new("helloworld", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let (greeting_account, _) = greeting_pda(&payer.pubkey(), &program_id);
+    let transaction = Transaction::new_signed_with_payer(
+        &[initialize_instruction(&program_id, &payer.pubkey(), &greeting_account, false)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // sized and funded by the program
+    let account = banks_client.get_account(greeting_account).await.unwrap().unwrap();
+    let rent = banks_client.get_rent().await.unwrap();
+    assert_eq!(account.owner, program_id);
+    assert_eq!(account.data.len(), GREETING_ACCOUNT_SIZE);
+    assert_eq!(account.lamports, rent.minimum_balance(GREETING_ACCOUNT_SIZE));
+    assert_eq!(counter(&mut banks_client, &greeting_account).await, 0);
+
+    banks_client
+        .process_transaction(greet_transaction(&program_id, &greeting_account, &payer, recent_blockhash))
+        .await
+        .unwrap();
+    assert_eq!(counter(&mut banks_client, &greeting_account).await, 1);
+}
+
+#[tokio::test]
+async fn initialize_creates_a_keypair_account() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new("helloworld", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let greeting_account = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[initialize_instruction(&program_id, &payer.pubkey(), &greeting_account.pubkey(), true)],
+        Some(&payer.pubkey()),
+        &[&payer, &greeting_account],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let account = banks_client.get_account(greeting_account.pubkey()).await.unwrap().unwrap();
+    assert_eq!(account.owner, program_id);
+    assert_eq!(counter(&mut banks_client, &greeting_account.pubkey()).await, 0);
+}
+
+#[tokio::test]
+async fn initialize_rejects_an_address_which_is_not_the_pda() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new("helloworld", program_id, processor!(process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[initialize_instruction(&program_id, &payer.pubkey(), &Pubkey::new_unique(), false)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    let error = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+    assert_eq!(error, TransactionError::InstructionError(0, InstructionError::InvalidSeeds));
+}
+
+#[tokio::test]
+async fn greeting_with_the_memo_program_writes_a_memo() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new("helloworld", program_id, processor!(process_instruction));
+    program_test.add_program("spl_memo", spl_memo::id(), processor!(spl_memo::processor::process_instruction));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let greeting_account = create_greeting_account(&mut banks_client, &payer, recent_blockhash, &program_id).await;
+    let greet = |memo_program: Pubkey| {
+        Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &[],
+                vec![AccountMeta::new(greeting_account.pubkey(), false), AccountMeta::new_readonly(memo_program, false)],
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        )
+    };
+
+    banks_client.process_transaction(greet(spl_memo::id())).await.unwrap();
+    assert_eq!(counter(&mut banks_client, &greeting_account.pubkey()).await, 1);
+
+    // any other program in place of the memo program is refused
+    let error = banks_client.process_transaction(greet(Pubkey::new_unique())).await.unwrap_err().unwrap();
+    assert_eq!(error, TransactionError::InstructionError(0, InstructionError::IncorrectProgramId));
+    assert_eq!(counter(&mut banks_client, &greeting_account.pubkey()).await, 1);
+}
+
+#[tokio::test]
+async fn greet_above_checks_the_result_of_the_calculator() {
+    use borsh::BorshSerialize;
+    use function_calls::{instruction::Instruction as CalculatorInstruction, processor::CalculatorResult};
+    use helloworld::{calculator_program_id, GreetingError};
+    use solana_sdk::account::Account;
+
+    let program_id = Pubkey::new_unique();
+    let calculator = calculator_program_id().unwrap();
+    let mut program_test = ProgramTest::new("helloworld", program_id, processor!(process_instruction));
+    program_test.add_program("function_calls", calculator, processor!(function_calls::processor::Processor::process));
+
+    // an account of the calculator, one with the same data owned by another program and one of the calculator which
+    // isn't a result
+    let account = |owner: Pubkey, data: Vec<u8>| Account { lamports: 1_000_000_000, data, owner, ..Account::default() };
+    let forged_result = CalculatorResult { result: u64::MAX, a: u64::MAX, b: 0 }.try_to_vec().unwrap();
+    let result_account = Pubkey::new_unique();
+    let forged_account = Pubkey::new_unique();
+    let other_account = Pubkey::new_unique();
+    program_test.add_account(result_account, account(calculator, vec![0; forged_result.len()]));
+    program_test.add_account(forged_account, account(Pubkey::new_unique(), forged_result));
+    program_test.add_account(other_account, account(calculator, vec![0; 33]));
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let greeting_account = create_greeting_account(&mut banks_client, &payer, recent_blockhash, &program_id).await;
+    let add = Instruction::new_with_bytes(
+        calculator,
+        &CalculatorInstruction::Add { a: 3, b: 5 }.pack(),
+        vec![AccountMeta::new(result_account, false)],
+    );
+    let transaction = Transaction::new_signed_with_payer(&[add], Some(&payer.pubkey()), &[&payer], recent_blockhash);
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // every transaction below differs in the account or the threshold, so none is a duplicate of another
+    let greet_above = |result_account: Pubkey, threshold: u64| {
+        Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &GreetingInstruction::GreetAbove { threshold }.pack(),
+                vec![AccountMeta::new(greeting_account.pubkey(), false), AccountMeta::new_readonly(result_account, false)],
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        )
+    };
+
+    banks_client.process_transaction(greet_above(result_account, 7)).await.unwrap();
+    assert_eq!(counter(&mut banks_client, &greeting_account.pubkey()).await, 1);
+
+    let error = banks_client.process_transaction(greet_above(result_account, 8)).await.unwrap_err().unwrap();
+    let not_above = InstructionError::Custom(GreetingError::ResultNotAboveThreshold as u32);
+    assert_eq!(error, TransactionError::InstructionError(0, not_above));
+
+    let error = banks_client.process_transaction(greet_above(forged_account, 0)).await.unwrap_err().unwrap();
+    assert_eq!(error, TransactionError::InstructionError(0, InstructionError::IncorrectProgramId));
+
+    let error = banks_client.process_transaction(greet_above(other_account, 0)).await.unwrap_err().unwrap();
+    assert_eq!(error, TransactionError::InstructionError(0, InstructionError::InvalidAccountData));
+    assert_eq!(counter(&mut banks_client, &greeting_account.pubkey()).await, 1);
+}
@@ -0,0 +1,23 @@
+// Property based tests, proptest generates the counters and thresholds. If the layout of GreetingAccount or of the
+// instruction data drifts apart between writing and reading, they fail.
+use borsh::{BorshDeserialize, BorshSerialize};
+use helloworld::{GreetingAccount, GreetingInstruction};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn greeting_account_roundtrip(counter in any::<u32>()) {
+        let data = GreetingAccount { counter }.try_to_vec().unwrap();
+        // main.py and the client size the account with this length
+        prop_assert_eq!(data.len(), 4);
+        prop_assert_eq!(GreetingAccount::try_from_slice(&data).unwrap().counter, counter);
+    }
+
+    #[test]
+    fn greet_above_roundtrip(threshold in any::<u64>()) {
+        let data = GreetingInstruction::GreetAbove { threshold }.pack();
+        // the tag and the threshold
+        prop_assert_eq!(data.len(), 9);
+        prop_assert_eq!(GreetingInstruction::unpack(&data).unwrap(), GreetingInstruction::GreetAbove { threshold });
+    }
+}
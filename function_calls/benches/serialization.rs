@@ -0,0 +1,70 @@
+// Benchmarks of the serialization the processor does on every call: unpacking the instruction data, and reading and
+// writing the CalculatorResult. borsh copies the whole struct out of the account and back, the zero-copy variants
+// read and write the fields in place at their offsets. `cargo bench` runs them, the reports end up in
+// target/criterion.
+use std::hint::black_box;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use function_calls::{instruction::Instruction, processor::CalculatorResult};
+
+// the borsh layout of CalculatorResult, three little endian u64
+const RESULT_OFFSET: usize = 0;
+const A_OFFSET: usize = 8;
+const B_OFFSET: usize = 16;
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn write_u64(data: &mut [u8], offset: usize, value: u64) {
+    data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+fn calculator_result(c: &mut Criterion) {
+    let mut data = CalculatorResult { result: 8, a: 3, b: 5 }.try_to_vec().unwrap();
+    assert_eq!(data.len(), CalculatorResult::SIZE);
+
+    let mut group = c.benchmark_group("calculator_result");
+    group.throughput(Throughput::Bytes(CalculatorResult::SIZE as u64));
+
+    group.bench_function("borsh_read", |b| {
+        b.iter(|| CalculatorResult::try_from_slice(black_box(&data)).unwrap().result)
+    });
+    group.bench_function("zero_copy_read", |b| b.iter(|| read_u64(black_box(&data), RESULT_OFFSET)));
+
+    // what Processor::add does with the account: read, change all fields, write back
+    group.bench_function("borsh_update", |b| {
+        b.iter(|| {
+            let mut result = CalculatorResult::try_from_slice(&data).unwrap();
+            result.a = black_box(3);
+            result.b = black_box(5);
+            result.result = result.a + result.b;
+            result.serialize(&mut &mut data[..]).unwrap();
+        })
+    });
+    group.bench_function("zero_copy_update", |b| {
+        b.iter(|| {
+            let (a, b) = (black_box(3), black_box(5));
+            write_u64(&mut data, A_OFFSET, a);
+            write_u64(&mut data, B_OFFSET, b);
+            write_u64(&mut data, RESULT_OFFSET, a + b);
+        })
+    });
+    group.finish();
+}
+
+fn instruction(c: &mut Criterion) {
+    let add = Instruction::Add { a: 3, b: 5 }.pack();
+    let unknown_tag = [7; 17];
+
+    let mut group = c.benchmark_group("instruction");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("unpack", |b| b.iter(|| Instruction::unpack(black_box(&add)).unwrap()));
+    group.bench_function("unpack_invalid", |b| b.iter(|| Instruction::unpack(black_box(&unknown_tag)).is_err()));
+    group.bench_function("pack", |b| b.iter(|| black_box(Instruction::Sub { a: 9, b: 2 }).pack()));
+    group.finish();
+}
+
+criterion_group!(benches, calculator_result, instruction);
+criterion_main!(benches);
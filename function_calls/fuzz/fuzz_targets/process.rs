@@ -0,0 +1,40 @@
+// Runs the whole processor with arbitrary instruction data and an account with arbitrary data, which is owned by
+// the program or by some other program. The runtime would turn a panic into a failed transaction, here it shows
+// up as a crash.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use function_calls::processor::Processor;
+use libfuzzer_sys::fuzz_target;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    owned_by_program: bool,
+    is_signer: bool,
+    is_writable: bool,
+    lamports: u64,
+    account_data: Vec<u8>,
+    instruction_data: Vec<u8>,
+}
+
+fuzz_target!(|input: Input| {
+    let program_id = Pubkey::new_unique();
+    let key = Pubkey::new_unique();
+    let owner = if input.owned_by_program { program_id } else { Pubkey::new_unique() };
+    let mut lamports = input.lamports;
+    let mut account_data = input.account_data;
+
+    let account = AccountInfo::new(
+        &key,
+        input.is_signer,
+        input.is_writable,
+        &mut lamports,
+        &mut account_data,
+        &owner,
+        false,
+        0,
+    );
+
+    let _ = Processor::process(&program_id, &[account], &input.instruction_data);
+});
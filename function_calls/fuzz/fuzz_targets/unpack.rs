@@ -0,0 +1,10 @@
+// Instruction::unpack gets arbitrary bytes. Anything it can't decode has to come back as an error, a panic or an
+// out of bounds read is what the fuzzer looks for.
+#![no_main]
+
+use function_calls::instruction::Instruction;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Instruction::unpack(data);
+});
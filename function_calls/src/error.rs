@@ -7,6 +7,18 @@ pub enum InstructionError {
     // Invalid instruction
     #[error("Invalid Instruction")]
     InvalidInstruction,
+    // Result doesn't fit into a u64
+    #[error("Overflow")]
+    Overflow,
+    // The signer isn't the authority of the vault or the admin of the config
+    #[error("Wrong authority")]
+    WrongAuthority,
+    // The program calling through a CPI isn't on the allowlist of the config
+    #[error("Unknown caller")]
+    UnknownCaller,
+    // More callers than the config has room for
+    #[error("Too many callers")]
+    TooManyCallers,
 }
 
 impl From<InstructionError> for ProgramError {
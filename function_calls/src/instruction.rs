@@ -1,26 +1,59 @@
 // instruction.rs is responsible for decoding instruction_data so
 use std::convert::TryInto;
-use solana_program::program_error::ProgramError;
+use borsh::BorshDeserialize;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use shank::ShankInstruction;
 
 use crate::error::InstructionError::InvalidInstruction;
 
+// ShankInstruction only describes the instructions for the IDL, see the README. The tag and the two little endian
+// operands unpack reads are the layout borsh uses for this enum, so the IDL matches the instruction data.
+#[derive(Debug, Clone, PartialEq, Eq, ShankInstruction)]
 pub enum Instruction {
     // we have two instructions, add -> addition,
     // and sub -> subtraction
+    // The SPL Memo program may follow the result account, the program then writes a memo of the calculation. It isn't
+    // listed below, the generated builders would require it.
+    #[account(0, writable, name = "result_account", desc = "Account owned by the program storing the CalculatorResult")]
     Add {
         a: u64,
         b: u64,
     },
+    #[account(0, writable, name = "result_account", desc = "Account owned by the program storing the CalculatorResult")]
     Sub {
         a: u64,
         b: u64,
     },
+    // the vault instructions, a PDA holding lamports only the program can sign for, see the README
+    #[account(0, writable, signer, name = "authority", desc = "Owner of the vault, pays for the vault state")]
+    #[account(1, writable, name = "vault_state", desc = "PDA of the program storing the VaultState")]
+    #[account(2, name = "system_program", desc = "The system program, which creates the vault state")]
+    InitVault,
+    #[account(0, signer, name = "authority", desc = "Owner of the vault")]
+    #[account(1, name = "vault_state", desc = "PDA of the program storing the VaultState")]
+    #[account(2, writable, name = "vault", desc = "PDA of the system program holding the lamports")]
+    #[account(3, writable, name = "recipient", desc = "Account the lamports are transferred to")]
+    #[account(4, name = "system_program", desc = "The system program, which transfers the lamports")]
+    Withdraw {
+        amount: u64,
+    },
+    // the programs a build with the caller-allowlist feature accepts add and sub from through a CPI, see the README
+    #[account(0, writable, signer, name = "admin", desc = "Pays for the config and may change the callers")]
+    #[account(1, writable, name = "config", desc = "PDA of the program storing the CallerConfig")]
+    #[account(2, name = "system_program", desc = "The system program, which creates the config")]
+    InitConfig,
+    #[account(0, signer, name = "admin", desc = "Admin of the config")]
+    #[account(1, writable, name = "config", desc = "PDA of the program storing the CallerConfig")]
+    SetCallers {
+        callers: Vec<Pubkey>,
+    },
 }
 
 impl Instruction {
     pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
         let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
-        let (a, b) = rest.split_at(8); 
+        // split_at panics if there are fewer than 8 bytes, unpack_amount rejects the short input instead
+        let (a, b) = rest.split_at(rest.len().min(8));
 
         Ok(match tag {
             0 => Self::Add {
@@ -31,10 +64,50 @@ impl Instruction {
                 a: Self::unpack_amount(a)?,
                 b: Self::unpack_amount(b)?,
             },
+            2 => Self::InitVault,
+            3 => Self::Withdraw {
+                amount: Self::unpack_amount(rest)?,
+            },
+            4 => Self::InitConfig,
+            // a u32 count followed by the pubkeys, like borsh packs a Vec
+            5 => Self::SetCallers {
+                callers: Vec::<Pubkey>::try_from_slice(rest).map_err(|_| InvalidInstruction)?,
+            },
             _ => return Err(InvalidInstruction.into()),
         })
     }
 
+    // The reverse of unpack, for clients building the instruction data
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(17);
+        match self {
+            Self::Add { a, b } => {
+                data.push(0);
+                data.extend_from_slice(&a.to_le_bytes());
+                data.extend_from_slice(&b.to_le_bytes());
+            }
+            Self::Sub { a, b } => {
+                data.push(1);
+                data.extend_from_slice(&a.to_le_bytes());
+                data.extend_from_slice(&b.to_le_bytes());
+            }
+            Self::InitVault => data.push(2),
+            Self::Withdraw { amount } => {
+                data.push(3);
+                data.extend_from_slice(&amount.to_le_bytes());
+            }
+            Self::InitConfig => data.push(4),
+            Self::SetCallers { callers } => {
+                data.push(5);
+                data.extend_from_slice(&(callers.len() as u32).to_le_bytes());
+                for caller in callers {
+                    data.extend_from_slice(caller.as_ref());
+                }
+            }
+        }
+        data
+    }
+
     fn unpack_amount(input: &[u8]) -> Result<u64, ProgramError> {
         let amount = input
             .get(..8)
@@ -1,3 +1,4 @@
+#[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
 pub mod instruction;
 pub mod error;
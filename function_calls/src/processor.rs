@@ -2,15 +2,19 @@ use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     msg,
+    program::{invoke, invoke_signed},
     pubkey::Pubkey,
     program_error::ProgramError,
+    system_instruction, system_program,
+    sysvar::{rent::Rent, Sysvar},
 };
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use shank::ShankAccount;
 
-use crate::instruction::Instruction;
+use crate::{error::InstructionError, instruction::Instruction};
 
-#[derive(BorshSerialize, BorshDeserialize, Debug)]
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
 pub struct CalculatorResult {
     // result of the calculation is stored here
     pub result: u64,
@@ -18,44 +22,338 @@ pub struct CalculatorResult {
     pub b: u64,
 }
 
+impl CalculatorResult {
+    // three u64
+    pub const SIZE: usize = 24;
+}
+
+// The vault of an authority is the PDA of VAULT_SEED and the authority. It is a plain system account holding
+// lamports, anybody can send lamports to it, but only the program can sign for it, and only does for the authority.
+// The system program only transfers from accounts it owns which hold no data, so the bump of the vault can't be
+// stored in the vault itself. It is stored in the vault state, the PDA of VAULT_STATE_SEED and the authority.
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const VAULT_STATE_SEED: &[u8] = b"vault_state";
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+pub struct VaultState {
+    // the only one who may withdraw
+    pub authority: Pubkey,
+    // the bump seed of the vault, so withdrawing doesn't have to search for it again
+    pub bump: u8,
+}
+
+// The vault of the authority and its bump seed
+pub fn vault_address(authority: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, authority.as_ref()], program_id)
+}
+
+// The vault state of the authority and its bump seed
+pub fn vault_state_address(authority: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_STATE_SEED, authority.as_ref()], program_id)
+}
+
+// The programs a build with the caller-allowlist feature accepts add and sub from through a CPI. There is a single
+// config, the PDA of CONFIG_SEED, and whoever initializes it first is its admin, so the deployer should right after
+// deploying the program.
+pub const CONFIG_SEED: &[u8] = b"config";
+
+// The config is created with room for this many callers, so it never has to grow
+pub const MAX_CALLERS: usize = 8;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+pub struct CallerConfig {
+    // the only one who may change the callers
+    pub admin: Pubkey,
+    // the bump seed of the config
+    pub bump: u8,
+    pub callers: Vec<Pubkey>,
+}
+
+impl CallerConfig {
+    // admin, bump, the length of callers and MAX_CALLERS pubkeys
+    pub const SIZE: usize = 32 + 1 + 4 + MAX_CALLERS * 32;
+}
+
+// The config of the program and its bump seed
+pub fn config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], program_id)
+}
 
 pub struct Processor;
 impl Processor {
     pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
-        let accounts_iter = &mut accounts.iter();
-        let account = next_account_info(accounts_iter)?;
-        if account.owner != program_id {
-            msg!("Greeted account does not have the correct program id");
-            return Err(ProgramError::IncorrectProgramId);
-        }
-
-        let mut calculation_result_account = CalculatorResult::try_from_slice(&account.data.borrow())?;
-
         let instruction = Instruction::unpack(instruction_data)?;
 
         match instruction {
             Instruction::Add { a, b } => {
                 msg!("Instruction: Add {} {}", a, b);
-                Self::add(&mut calculation_result_account, a, b);
+                Self::calculate(program_id, accounts, '+', |result| Self::add(result, a, b))
             }
             Instruction::Sub { a, b} => {
                 msg!("Instruction: Sub {} {}", a, b);
-                Self::sub(&mut calculation_result_account, a, b);
+                Self::calculate(program_id, accounts, '-', |result| Self::sub(result, a, b))
             }
+            Instruction::InitVault => {
+                msg!("Instruction: InitVault");
+                Self::init_vault(program_id, accounts)
+            }
+            Instruction::Withdraw { amount } => {
+                msg!("Instruction: Withdraw {}", amount);
+                Self::withdraw(program_id, accounts, amount)
+            }
+            Instruction::InitConfig => {
+                msg!("Instruction: InitConfig");
+                Self::init_config(program_id, accounts)
+            }
+            Instruction::SetCallers { callers } => {
+                msg!("Instruction: SetCallers {}", callers.len());
+                Self::set_callers(program_id, accounts, callers)
+            }
+        }
+    }
+
+    // Runs the calculation on the CalculatorResult of the first account and writes it back. If the memo program
+    // follows as the last account, the calculation is written into a memo too, which explorers show. A build with
+    // the caller-allowlist feature expects the config and the Instructions sysvar right after the result account.
+    fn calculate(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        operator: char,
+        calculation: impl FnOnce(&mut CalculatorResult) -> ProgramResult,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let account = next_account_info(accounts_iter)?;
+        if account.owner != program_id {
+            msg!("Greeted account does not have the correct program id");
+            return Err(ProgramError::IncorrectProgramId);
         }
+        #[cfg(feature = "caller-allowlist")]
+        Self::check_caller(program_id, accounts_iter)?;
+
+        let mut calculation_result_account = CalculatorResult::try_from_slice(&account.data.borrow())?;
+        calculation(&mut calculation_result_account)?;
         calculation_result_account.serialize(&mut &mut account.data.borrow_mut()[..])?;
+
+        if let Some(memo_program) = accounts_iter.next() {
+            let CalculatorResult { result, a, b } = calculation_result_account;
+            Self::write_memo(memo_program, &format!("{} {} {} = {}", a, operator, b, result))?;
+        }
         Result::Ok(())
     }
 
-    fn add(account: &mut CalculatorResult, a: u64, b: u64) {
-        account.result = a + b;
+    // Any other account than the memo program is refused, otherwise the program would call whatever it is passed
+    fn write_memo(memo_program: &AccountInfo, memo: &str) -> ProgramResult {
+        if !spl_memo::check_id(memo_program.key) {
+            msg!("Last account is not the memo program");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        // no signers, the memo program only checks the ones it is given
+        invoke(&spl_memo::build_memo(memo.as_bytes(), &[]), std::slice::from_ref(memo_program))
+    }
+
+    // A plain + or - panics in a debug build and silently wraps around in a release build, checked_add and
+    // checked_sub let the instruction fail instead
+    fn add(account: &mut CalculatorResult, a: u64, b: u64) -> ProgramResult {
+        account.result = a.checked_add(b).ok_or(InstructionError::Overflow)?;
         account.a = a;
         account.b = b;
+        Ok(())
     }
 
-    fn sub(account: &mut CalculatorResult, a: u64, b: u64) {
-        account.result = a - b;
+    fn sub(account: &mut CalculatorResult, a: u64, b: u64) -> ProgramResult {
+        account.result = a.checked_sub(b).ok_or(InstructionError::Overflow)?;
         account.a = a;
         account.b = b;
+        Ok(())
     }
-}
\ No newline at end of file
+
+    // Creates the vault state of the authority. The program signs for the new PDA with invoke_signed, the runtime
+    // checks that the seeds and the program id lead to its address.
+    fn init_vault(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let vault_state = next_account_info(accounts_iter)?;
+        let system_program_account = next_account_info(accounts_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        Self::check_system_program(system_program_account)?;
+        let (vault_state_key, vault_state_bump) = vault_state_address(authority.key, program_id);
+        if *vault_state.key != vault_state_key {
+            msg!("Vault state is not the PDA of the authority");
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let (vault, bump) = vault_address(authority.key, program_id);
+        let state = VaultState { authority: *authority.key, bump };
+        let size = state.try_to_vec()?.len();
+        invoke_signed(
+            &system_instruction::create_account(
+                authority.key,
+                vault_state.key,
+                Rent::get()?.minimum_balance(size),
+                size as u64,
+                program_id,
+            ),
+            &[authority.clone(), vault_state.clone(), system_program_account.clone()],
+            &[&[VAULT_STATE_SEED, authority.key.as_ref(), &[vault_state_bump]]],
+        )?;
+        state.serialize(&mut &mut vault_state.data.borrow_mut()[..])?;
+
+        msg!("Vault {} of {}", vault, authority.key);
+        Ok(())
+    }
+
+    // Transfers lamports out of the vault. The vault is owned by the system program, so the program can't just lower
+    // its lamports, it asks the system program to transfer them and signs for the vault with the stored bump.
+    fn withdraw(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let vault_state = next_account_info(accounts_iter)?;
+        let vault = next_account_info(accounts_iter)?;
+        let recipient = next_account_info(accounts_iter)?;
+        let system_program_account = next_account_info(accounts_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        Self::check_system_program(system_program_account)?;
+        // only init_vault writes accounts of this layout, a result account is too short to pass for one
+        if vault_state.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let state = VaultState::try_from_slice(&vault_state.data.borrow())?;
+        if state.authority != *authority.key {
+            msg!("{} is not the authority of the vault", authority.key);
+            return Err(InstructionError::WrongAuthority.into());
+        }
+
+        // create_program_address with the stored bump is much cheaper than find_program_address searching for it
+        let seeds: &[&[u8]] = &[VAULT_SEED, authority.key.as_ref(), &[state.bump]];
+        if *vault.key != Pubkey::create_program_address(seeds, program_id)? {
+            msg!("Vault is not the PDA of the authority");
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        invoke_signed(
+            &system_instruction::transfer(vault.key, recipient.key, amount),
+            &[vault.clone(), recipient.clone(), system_program_account.clone()],
+            &[seeds],
+        )
+    }
+
+    fn init_config(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let admin = next_account_info(accounts_iter)?;
+        let config = next_account_info(accounts_iter)?;
+        let system_program_account = next_account_info(accounts_iter)?;
+
+        if !admin.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        Self::check_system_program(system_program_account)?;
+        let (config_key, bump) = config_address(program_id);
+        if *config.key != config_key {
+            msg!("Config is not the PDA of the program");
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        invoke_signed(
+            &system_instruction::create_account(
+                admin.key,
+                config.key,
+                Rent::get()?.minimum_balance(CallerConfig::SIZE),
+                CallerConfig::SIZE as u64,
+                program_id,
+            ),
+            &[admin.clone(), config.clone(), system_program_account.clone()],
+            &[&[CONFIG_SEED, &[bump]]],
+        )?;
+        let state = CallerConfig { admin: *admin.key, bump, callers: Vec::new() };
+        state.serialize(&mut &mut config.data.borrow_mut()[..])?;
+        Ok(())
+    }
+
+    // Replaces the callers of the config
+    fn set_callers(program_id: &Pubkey, accounts: &[AccountInfo], callers: Vec<Pubkey>) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+        let admin = next_account_info(accounts_iter)?;
+        let config = next_account_info(accounts_iter)?;
+
+        if !admin.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        let mut state = Self::caller_config(program_id, config)?;
+        if state.admin != *admin.key {
+            msg!("{} is not the admin of the config", admin.key);
+            return Err(InstructionError::WrongAuthority.into());
+        }
+        if callers.len() > MAX_CALLERS {
+            return Err(InstructionError::TooManyCallers.into());
+        }
+        state.callers = callers;
+        state.serialize(&mut &mut config.data.borrow_mut()[..])?;
+        Ok(())
+    }
+
+    // The config is only trusted at its own address. It is bigger than the CallerConfig it holds, so it is read with
+    // deserialize, try_from_slice would refuse the unused bytes at the end.
+    fn caller_config(program_id: &Pubkey, config: &AccountInfo) -> Result<CallerConfig, ProgramError> {
+        if config.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let state = CallerConfig::deserialize(&mut &config.data.borrow()[..])?;
+        if *config.key != Pubkey::create_program_address(&[CONFIG_SEED, &[state.bump]], program_id)? {
+            msg!("Config is not the PDA of the program");
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(state)
+    }
+
+    // A transaction may always call the calculator directly, a program calling it through a CPI has to be in the
+    // config. The runtime doesn't tell a program who invoked it. The stack height tells how deep the call is, and in
+    // a CPI one level deep the caller is the program of the top level instruction being executed, which the
+    // Instructions sysvar holds. Deeper down the caller is some program in between, which the sysvar doesn't show,
+    // so those calls are refused.
+    #[cfg(feature = "caller-allowlist")]
+    fn check_caller(program_id: &Pubkey, accounts_iter: &mut std::slice::Iter<AccountInfo>) -> ProgramResult {
+        use solana_program::{
+            instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+            sysvar::instructions,
+        };
+
+        let config = next_account_info(accounts_iter)?;
+        let instructions_sysvar = next_account_info(accounts_iter)?;
+
+        let stack_height = get_stack_height();
+        if stack_height == TRANSACTION_LEVEL_STACK_HEIGHT {
+            return Ok(());
+        }
+        if stack_height > TRANSACTION_LEVEL_STACK_HEIGHT + 1 {
+            msg!("Calls nested deeper than one CPI are refused");
+            return Err(InstructionError::UnknownCaller.into());
+        }
+
+        if !instructions::check_id(instructions_sysvar.key) {
+            msg!("Wrong Instructions sysvar");
+            return Err(ProgramError::InvalidArgument);
+        }
+        let state = Self::caller_config(program_id, config)?;
+        let caller = instructions::get_instruction_relative(0, instructions_sysvar)?.program_id;
+        if !state.callers.contains(&caller) {
+            msg!("{} may not call the calculator", caller);
+            return Err(InstructionError::UnknownCaller.into());
+        }
+        Ok(())
+    }
+
+    // invoke would call whichever program is passed, it has to be the real system program
+    fn check_system_program(account: &AccountInfo) -> ProgramResult {
+        if !system_program::check_id(account.key) {
+            msg!("Wrong system program");
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+// Runs every calculator instruction in a local bank with solana-program-test, the processor is called natively.
+// The calls are the same in a build with the caller allowlist, tests/caller.rs checks the list itself.
+mod common;
+
+use common::{calculator_error, Bank};
+use function_calls::{error::InstructionError as CalculatorError, processor::config_address};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    sysvar,
+    transaction::TransactionError,
+};
+
+// With the caller allowlist the calculator expects its config. The calls here are sent directly, which it accepts
+// from anybody, the calculator itself is the only caller on the list.
+async fn start() -> Bank {
+    let mut bank = Bank::start().await;
+    if cfg!(feature = "caller-allowlist") {
+        let program_id = bank.program_id;
+        bank.create_caller_config(vec![program_id]).await;
+    }
+    bank
+}
+
+async fn call(bank: &mut Bank, result_account: &Pubkey, data: &[u8]) -> Result<(), TransactionError> {
+    call_with_accounts(bank, data, vec![AccountMeta::new(*result_account, false)]).await
+}
+
+// The config and the Instructions sysvar of the allowlist go right after the result account
+async fn call_with_accounts(
+    bank: &mut Bank,
+    data: &[u8],
+    mut accounts: Vec<AccountMeta>,
+) -> Result<(), TransactionError> {
+    if cfg!(feature = "caller-allowlist") {
+        let config = AccountMeta::new_readonly(config_address(&bank.program_id).0, false);
+        accounts.splice(1..1, [config, AccountMeta::new_readonly(sysvar::instructions::id(), false)]);
+    }
+    let instruction = Instruction::new_with_bytes(bank.program_id, data, accounts);
+    bank.send(instruction, &[]).await
+}
+
+fn instruction_data(tag: u8, a: u64, b: u64) -> Vec<u8> {
+    let mut data = vec![tag];
+    data.extend_from_slice(&a.to_le_bytes());
+    data.extend_from_slice(&b.to_le_bytes());
+    data
+}
+
+#[tokio::test]
+async fn add_stores_the_sum() {
+    let mut bank = start().await;
+    let program_id = bank.program_id;
+    let result_account = bank.create_result_account(&program_id).await;
+
+    call(&mut bank, &result_account, &instruction_data(0, 3, 5)).await.unwrap();
+
+    let result = bank.result(&result_account).await;
+    assert_eq!((result.a, result.b, result.result), (3, 5, 8));
+}
+
+#[tokio::test]
+async fn sub_stores_the_difference() {
+    let mut bank = start().await;
+    let program_id = bank.program_id;
+    let result_account = bank.create_result_account(&program_id).await;
+
+    call(&mut bank, &result_account, &instruction_data(1, 9, 4)).await.unwrap();
+
+    let result = bank.result(&result_account).await;
+    assert_eq!((result.a, result.b, result.result), (9, 4, 5));
+}
+
+#[tokio::test]
+async fn result_is_overwritten_by_the_next_call() {
+    let mut bank = start().await;
+    let program_id = bank.program_id;
+    let result_account = bank.create_result_account(&program_id).await;
+
+    call(&mut bank, &result_account, &instruction_data(0, 1, 2)).await.unwrap();
+    call(&mut bank, &result_account, &instruction_data(1, 10, 7)).await.unwrap();
+
+    let result = bank.result(&result_account).await;
+    assert_eq!((result.a, result.b, result.result), (10, 7, 3));
+}
+
+#[tokio::test]
+async fn account_of_another_program_is_rejected() {
+    let mut bank = start().await;
+    let result_account = bank.create_result_account(&Pubkey::new_unique()).await;
+
+    let error = call(&mut bank, &result_account, &instruction_data(0, 3, 5)).await.unwrap_err();
+    assert_eq!(error, TransactionError::InstructionError(0, InstructionError::IncorrectProgramId));
+}
+
+#[tokio::test]
+async fn truncated_instruction_data_is_rejected() {
+    let mut bank = start().await;
+    let program_id = bank.program_id;
+    let result_account = bank.create_result_account(&program_id).await;
+
+    let data = instruction_data(0, 3, 5);
+    // no data at all, only the tag, only part of the first operand, and only part of the second one
+    for length in [0, 1, 5, 9, 16] {
+        let error = call(&mut bank, &result_account, &data[..length]).await.unwrap_err();
+        assert_eq!(error, calculator_error(CalculatorError::InvalidInstruction), "data of {} bytes", length);
+    }
+
+    // nothing has been written
+    let result = bank.result(&result_account).await;
+    assert_eq!((result.a, result.b, result.result), (0, 0, 0));
+}
+
+#[tokio::test]
+async fn unknown_tag_is_rejected() {
+    let mut bank = start().await;
+    let program_id = bank.program_id;
+    let result_account = bank.create_result_account(&program_id).await;
+
+    // 2 and 3 are the vault instructions, 4 and 5 the ones of the caller config
+    for tag in [6, 255] {
+        let error = call(&mut bank, &result_account, &instruction_data(tag, 3, 5)).await.unwrap_err();
+        assert_eq!(error, calculator_error(CalculatorError::InvalidInstruction));
+    }
+}
+
+#[tokio::test]
+async fn overflow_is_rejected() {
+    let mut bank = start().await;
+    let program_id = bank.program_id;
+    let result_account = bank.create_result_account(&program_id).await;
+
+    for data in [instruction_data(0, u64::MAX, 1), instruction_data(1, 3, 5)] {
+        let error = call(&mut bank, &result_account, &data).await.unwrap_err();
+        assert_eq!(error, calculator_error(CalculatorError::Overflow));
+    }
+}
+
+#[tokio::test]
+async fn memo_program_gets_a_memo_of_the_calculation() {
+    let mut bank = start().await;
+    let program_id = bank.program_id;
+    let result_account = bank.create_result_account(&program_id).await;
+
+    let accounts = vec![AccountMeta::new(result_account, false), AccountMeta::new_readonly(spl_memo::id(), false)];
+    call_with_accounts(&mut bank, &instruction_data(0, 3, 5), accounts).await.unwrap();
+    let result = bank.result(&result_account).await;
+    assert_eq!((result.a, result.b, result.result), (3, 5, 8));
+
+    // any other program in place of the memo program is refused
+    let accounts = vec![AccountMeta::new(result_account, false), AccountMeta::new_readonly(Pubkey::new_unique(), false)];
+    let error = call_with_accounts(&mut bank, &instruction_data(1, 9, 4), accounts).await.unwrap_err();
+    assert_eq!(error, TransactionError::InstructionError(0, InstructionError::IncorrectProgramId));
+}
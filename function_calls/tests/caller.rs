@@ -0,0 +1,108 @@
+// The caller allowlist, only built with `cargo test --features caller-allowlist`. A second program, registered
+// natively like the calculator, forwards add to the calculator through a CPI.
+#![cfg(feature = "caller-allowlist")]
+
+mod common;
+
+use common::{calculator_error, set_callers, Bank};
+use function_calls::{
+    error::InstructionError as CalculatorError,
+    instruction::Instruction as CalculatorInstruction,
+    processor::config_address,
+};
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, program::invoke};
+use solana_program_test::processor;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    sysvar,
+};
+
+// Invokes the program of the first account with the other accounts and the instruction data it got
+fn forward(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (callee, rest) = accounts.split_first().unwrap();
+    let metas = rest
+        .iter()
+        .map(|account| AccountMeta { pubkey: *account.key, is_signer: account.is_signer, is_writable: account.is_writable })
+        .collect();
+    invoke(&Instruction { program_id: *callee.key, accounts: metas, data: data.to_vec() }, accounts)
+}
+
+struct Test {
+    bank: Bank,
+    // two programs forwarding to the calculator, only the first one is on the allowlist
+    allowed: Pubkey,
+    unknown: Pubkey,
+    result_account: Pubkey,
+}
+
+impl Test {
+    // Starts the bank with the config holding the allowed caller and creates a result account
+    async fn start() -> Self {
+        let (allowed, unknown) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut bank = Bank::start_with(|program_test| {
+            program_test.add_program("allowed_caller", allowed, processor!(forward));
+            program_test.add_program("unknown_caller", unknown, processor!(forward));
+        })
+        .await;
+        let program_id = bank.program_id;
+        let result_account = bank.create_result_account(&program_id).await;
+
+        bank.create_caller_config(vec![allowed]).await;
+        Self { bank, allowed, unknown, result_account }
+    }
+
+    // add 3 5, sent to the calculator directly or through one of the callers
+    fn add(&self, through: Option<Pubkey>) -> Instruction {
+        let mut accounts = vec![
+            AccountMeta::new(self.result_account, false),
+            AccountMeta::new_readonly(config_address(&self.bank.program_id).0, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ];
+        let data = CalculatorInstruction::Add { a: 3, b: 5 }.pack();
+        match through {
+            Some(caller) => {
+                accounts.insert(0, AccountMeta::new_readonly(self.bank.program_id, false));
+                Instruction::new_with_bytes(caller, &data, accounts)
+            },
+            None => Instruction::new_with_bytes(self.bank.program_id, &data, accounts),
+        }
+    }
+
+    async fn result(&mut self) -> u64 {
+        let result_account = self.result_account;
+        self.bank.result(&result_account).await.result
+    }
+}
+
+#[tokio::test]
+async fn direct_calls_are_allowed() {
+    let mut test = Test::start().await;
+    test.bank.send(test.add(None), &[]).await.unwrap();
+    assert_eq!(test.result().await, 8);
+}
+
+#[tokio::test]
+async fn cpi_of_an_allowed_caller_is_accepted() {
+    let mut test = Test::start().await;
+    test.bank.send(test.add(Some(test.allowed)), &[]).await.unwrap();
+    assert_eq!(test.result().await, 8);
+}
+
+#[tokio::test]
+async fn cpi_of_an_unknown_caller_is_rejected() {
+    let mut test = Test::start().await;
+    let error = test.bank.send(test.add(Some(test.unknown)), &[]).await.unwrap_err();
+    assert_eq!(error, calculator_error(CalculatorError::UnknownCaller));
+    assert_eq!(test.result().await, 0);
+}
+
+#[tokio::test]
+async fn only_the_admin_sets_the_callers() {
+    let mut test = Test::start().await;
+    let other = Keypair::new();
+    let instruction = set_callers(&test.bank.program_id, &other.pubkey(), vec![test.unknown]);
+    let error = test.bank.send(instruction, &[&other]).await.unwrap_err();
+    assert_eq!(error, calculator_error(CalculatorError::WrongAuthority));
+}
@@ -0,0 +1,105 @@
+// The bank the program tests run in. Every test file uses a part of it only, the rest would warn as dead code.
+#![allow(dead_code)]
+
+use borsh::BorshDeserialize;
+use function_calls::{
+    error::InstructionError as CalculatorError,
+    instruction::Instruction as CalculatorInstruction,
+    processor::{config_address, CalculatorResult, Processor},
+};
+use solana_program_test::{processor, BanksClient, ProgramTest, ProgramTestBanksClientExt};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+pub struct Bank {
+    pub banks_client: BanksClient,
+    pub payer: Keypair,
+    pub recent_blockhash: Hash,
+    pub program_id: Pubkey,
+}
+
+impl Bank {
+    // The calculator at a new address and the memo program, both called natively
+    pub async fn start() -> Self {
+        Self::start_with(|_| {}).await
+    }
+
+    // Like start, add_programs registers the programs a test needs besides them
+    pub async fn start_with(add_programs: impl FnOnce(&mut ProgramTest)) -> Self {
+        let program_id = Pubkey::new_unique();
+        let mut program_test = ProgramTest::new("function_calls", program_id, processor!(Processor::process));
+        program_test.add_program("spl_memo", spl_memo::id(), processor!(spl_memo::processor::process_instruction));
+        add_programs(&mut program_test);
+        let (banks_client, payer, recent_blockhash) = program_test.start().await;
+        Self { banks_client, payer, recent_blockhash, program_id }
+    }
+
+    // The payer pays the fees, the signers sign besides it. Every transaction uses a new blockhash, otherwise the
+    // same instruction twice would be rejected as a duplicate transaction.
+    pub async fn send(&mut self, instruction: Instruction, signers: &[&Keypair]) -> Result<(), TransactionError> {
+        self.recent_blockhash = self.banks_client.get_new_latest_blockhash(&self.recent_blockhash).await.unwrap();
+        let mut keypairs = vec![&self.payer];
+        keypairs.extend_from_slice(signers);
+        let payer = self.payer.pubkey();
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&payer), &keypairs, self.recent_blockhash);
+        self.banks_client.process_transaction(transaction).await.map_err(|e| e.unwrap())
+    }
+
+    // An empty result account owned by the owner
+    pub async fn create_result_account(&mut self, owner: &Pubkey) -> Pubkey {
+        let result_account = Keypair::new();
+        let rent = self.banks_client.get_rent().await.unwrap();
+        let create = system_instruction::create_account(
+            &self.payer.pubkey(),
+            &result_account.pubkey(),
+            rent.minimum_balance(CalculatorResult::SIZE),
+            CalculatorResult::SIZE as u64,
+            owner,
+        );
+        self.send(create, &[&result_account]).await.unwrap();
+        result_account.pubkey()
+    }
+
+    // The caller config with the payer as its admin
+    pub async fn create_caller_config(&mut self, callers: Vec<Pubkey>) {
+        let admin = self.payer.pubkey();
+        self.send(init_config(&self.program_id, &admin), &[]).await.unwrap();
+        self.send(set_callers(&self.program_id, &admin, callers), &[]).await.unwrap();
+    }
+
+    pub async fn result(&mut self, result_account: &Pubkey) -> CalculatorResult {
+        let account = self.banks_client.get_account(*result_account).await.unwrap().unwrap();
+        CalculatorResult::try_from_slice(&account.data).unwrap()
+    }
+}
+
+// The error of the first instruction failing with the custom error of the calculator
+pub fn calculator_error(error: CalculatorError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+}
+
+pub fn init_config(program_id: &Pubkey, admin: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &CalculatorInstruction::InitConfig.pack(),
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn set_callers(program_id: &Pubkey, admin: &Pubkey, callers: Vec<Pubkey>) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &CalculatorInstruction::SetCallers { callers }.pack(),
+        vec![AccountMeta::new_readonly(*admin, true), AccountMeta::new(config_address(program_id).0, false)],
+    )
+}
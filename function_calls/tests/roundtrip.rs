@@ -0,0 +1,45 @@
+// Property based tests, proptest generates the values. If the layout of the instruction data or of the result
+// account drifts apart between writing and reading, one of them fails.
+use borsh::{BorshDeserialize, BorshSerialize};
+use function_calls::{instruction::Instruction, processor::CalculatorResult};
+use proptest::prelude::*;
+use solana_program::pubkey::Pubkey;
+
+fn any_instruction() -> impl Strategy<Value = Instruction> {
+    prop_oneof![
+        (any::<u64>(), any::<u64>()).prop_map(|(a, b)| Instruction::Add { a, b }),
+        (any::<u64>(), any::<u64>()).prop_map(|(a, b)| Instruction::Sub { a, b }),
+        Just(Instruction::InitVault),
+        any::<u64>().prop_map(|amount| Instruction::Withdraw { amount }),
+        Just(Instruction::InitConfig),
+        proptest::collection::vec(any::<[u8; 32]>().prop_map(Pubkey::new_from_array), 0..8)
+            .prop_map(|callers| Instruction::SetCallers { callers }),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn calculator_result_roundtrip(result in any::<u64>(), a in any::<u64>(), b in any::<u64>()) {
+        let data = CalculatorResult { result, a, b }.try_to_vec().unwrap();
+        // main.py and the client size the account with this length
+        prop_assert_eq!(data.len(), CalculatorResult::SIZE);
+
+        let decoded = CalculatorResult::try_from_slice(&data).unwrap();
+        prop_assert_eq!((decoded.result, decoded.a, decoded.b), (result, a, b));
+    }
+
+    #[test]
+    fn instruction_pack_unpack_roundtrip(instruction in any_instruction()) {
+        let data = instruction.pack();
+        // the tag, 8 bytes per operand, and for the callers a u32 count and 32 bytes each
+        let size = match instruction {
+            Instruction::Add { .. } | Instruction::Sub { .. } => 17,
+            Instruction::InitVault => 1,
+            Instruction::Withdraw { .. } => 9,
+            Instruction::InitConfig => 1,
+            Instruction::SetCallers { ref callers } => 5 + callers.len() * 32,
+        };
+        prop_assert_eq!(data.len(), size);
+        prop_assert_eq!(Instruction::unpack(&data).unwrap(), instruction);
+    }
+}
@@ -0,0 +1,122 @@
+// Runs the vault instructions in a local bank with solana-program-test. The program signs for the vault with
+// invoke_signed, which works the same when the processor is called natively.
+mod common;
+
+use borsh::BorshDeserialize;
+use common::{calculator_error, Bank};
+use function_calls::{
+    error::InstructionError as CalculatorError,
+    instruction::Instruction as CalculatorInstruction,
+    processor::{vault_address, vault_state_address, VaultState},
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::TransactionError,
+};
+
+struct Vault {
+    bank: Bank,
+}
+
+impl Vault {
+    async fn start() -> Self {
+        Self { bank: Bank::start().await }
+    }
+
+    // Creates the vault state of the payer and sends the lamports into the vault, returns the vault
+    async fn init_and_deposit(&mut self, lamports: u64) -> Pubkey {
+        let authority = self.bank.payer.pubkey();
+        let (vault_state, _) = vault_state_address(&authority, &self.bank.program_id);
+        let init = Instruction::new_with_bytes(
+            self.bank.program_id,
+            &CalculatorInstruction::InitVault.pack(),
+            vec![
+                AccountMeta::new(authority, true),
+                AccountMeta::new(vault_state, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        self.bank.send(init, &[]).await.unwrap();
+
+        // depositing is a plain transfer, the program isn't involved
+        let (vault, _) = vault_address(&authority, &self.bank.program_id);
+        self.bank.send(system_instruction::transfer(&authority, &vault, lamports), &[]).await.unwrap();
+        vault
+    }
+
+    fn withdraw_instruction(&self, authority: &Pubkey, vault: &Pubkey, recipient: &Pubkey, amount: u64) -> Instruction {
+        let (vault_state, _) = vault_state_address(&self.bank.payer.pubkey(), &self.bank.program_id);
+        Instruction::new_with_bytes(
+            self.bank.program_id,
+            &CalculatorInstruction::Withdraw { amount }.pack(),
+            vec![
+                AccountMeta::new_readonly(*authority, true),
+                AccountMeta::new_readonly(vault_state, false),
+                AccountMeta::new(*vault, false),
+                AccountMeta::new(*recipient, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        )
+    }
+
+    async fn balance(&mut self, address: &Pubkey) -> u64 {
+        self.bank.banks_client.get_balance(*address).await.unwrap()
+    }
+}
+
+#[tokio::test]
+async fn init_vault_stores_the_authority_and_the_bump() {
+    let mut vault = Vault::start().await;
+    vault.init_and_deposit(LAMPORTS_PER_SOL).await;
+
+    let authority = vault.bank.payer.pubkey();
+    let (vault_state, _) = vault_state_address(&authority, &vault.bank.program_id);
+    let account = vault.bank.banks_client.get_account(vault_state).await.unwrap().unwrap();
+    assert_eq!(account.owner, vault.bank.program_id);
+    let state = VaultState::try_from_slice(&account.data).unwrap();
+    assert_eq!(state.authority, authority);
+    assert_eq!(state.bump, vault_address(&authority, &vault.bank.program_id).1);
+}
+
+#[tokio::test]
+async fn withdraw_transfers_out_of_the_vault() {
+    let mut vault = Vault::start().await;
+    let vault_key = vault.init_and_deposit(LAMPORTS_PER_SOL).await;
+    let recipient = Pubkey::new_unique();
+
+    let authority = vault.bank.payer.pubkey();
+    let instruction = vault.withdraw_instruction(&authority, &vault_key, &recipient, LAMPORTS_PER_SOL / 4);
+    vault.bank.send(instruction, &[]).await.unwrap();
+
+    assert_eq!(vault.balance(&vault_key).await, LAMPORTS_PER_SOL * 3 / 4);
+    assert_eq!(vault.balance(&recipient).await, LAMPORTS_PER_SOL / 4);
+}
+
+#[tokio::test]
+async fn withdraw_by_another_authority_is_rejected() {
+    let mut vault = Vault::start().await;
+    let vault_key = vault.init_and_deposit(LAMPORTS_PER_SOL).await;
+    let thief = Keypair::new();
+
+    let instruction = vault.withdraw_instruction(&thief.pubkey(), &vault_key, &thief.pubkey(), LAMPORTS_PER_SOL / 4);
+    let error = vault.bank.send(instruction, &[&thief]).await.unwrap_err();
+    assert_eq!(error, calculator_error(CalculatorError::WrongAuthority));
+    assert_eq!(vault.balance(&vault_key).await, LAMPORTS_PER_SOL);
+}
+
+#[tokio::test]
+async fn withdraw_from_another_address_is_rejected() {
+    let mut vault = Vault::start().await;
+    vault.init_and_deposit(LAMPORTS_PER_SOL).await;
+    let recipient = Pubkey::new_unique();
+
+    let other = Pubkey::new_unique();
+    let authority = vault.bank.payer.pubkey();
+    let instruction = vault.withdraw_instruction(&authority, &other, &recipient, LAMPORTS_PER_SOL / 4);
+    let error = vault.bank.send(instruction, &[]).await.unwrap_err();
+    assert_eq!(error, TransactionError::InstructionError(0, InstructionError::InvalidSeeds));
+}
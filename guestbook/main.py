@@ -0,0 +1,227 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+GUESTBOOK_HEAD_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'owner': types.fixed_array(types.u8, 32),
+    'entry_count': types.u64,
+    'bump_seed': types.u8
+})
+
+ENTRY_SCHEMA = borsh.schema({
+    'guestbook': types.fixed_array(types.u8, 32),
+    'visitor': types.fixed_array(types.u8, 32),
+    'index': types.u64,
+    'timestamp': types.i64,
+    'message': types.string
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'guestbook.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'guestbook-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def get_head_address(owner: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("guestbook", encoding="utf8"), bytes(owner)], program_id)[0]
+
+def get_entry_address(head: PublicKey, index: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("entry", encoding="utf8"), bytes(head), index.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def init_guestbook(owner: Keypair) -> PublicKey:
+    head = get_head_address(owner.public_key)
+    if client.get_account_info(head)["result"]["value"] is not None:
+        print(f"Guestbook {head} already exists")
+        return head
+
+    print(f"\nCreating guestbook {head}")
+    # 0. `[signer, writable]` The owner of the guestbook, pays for the head account
+    # 1. `[writable]` The guestbook head PDA
+    # 2. `[]` The system program
+    init_ix = TransactionInstruction(
+        data=bytes(1),
+        keys=[
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=head, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(init_ix), owner)
+    client.confirm_transaction(tx["result"])
+    return head
+
+def get_head(head: PublicKey) -> dict:
+    account_info = client.get_account_info(head)["result"]["value"]
+    return borsh.deserialize(GUESTBOOK_HEAD_SCHEMA, base64.b64decode(account_info["data"][0]))
+
+def sign(visitor: Keypair, head: PublicKey, message: str):
+    # the entry is created at the current entry count, so we have to read the head first
+    index = get_head(head)['entry_count']
+    entry = get_entry_address(head, index)
+    print(f"\n{visitor.public_key} signs the guestbook at entry {index}")
+
+    # 0. `[signer, writable]` The visitor, pays for the entry account
+    # 1. `[writable]` The guestbook head PDA
+    # 2. `[writable]` The entry PDA for the next index
+    # 3. `[]` The system program
+    sign_ix = TransactionInstruction(
+        data=(1).to_bytes(1, byteorder='little') + bytes(message, encoding="utf8"),
+        keys=[
+            AccountMeta(pubkey=visitor.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=head, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=entry, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(sign_ix), visitor)
+    client.confirm_transaction(tx["result"])
+
+def list_entries(head: PublicKey):
+    # since every entry address is derived from the head and an index, we can walk all of them
+    entry_count = get_head(head)['entry_count']
+    print(f"\nGuestbook {head} has {entry_count} entries")
+    for index in range(entry_count):
+        account_info = client.get_account_info(get_entry_address(head, index))["result"]["value"]
+        entry = borsh.deserialize(ENTRY_SCHEMA, base64.b64decode(account_info["data"][0]))
+        print(f"#{entry['index']} {PublicKey(entry['visitor'])} at {entry['timestamp']}: {entry['message']}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    head = init_guestbook(payer)
+    sign(payer, head, "Hello from the owner of this guestbook")
+    visitor = setup_user_keypair()
+    sign(visitor, head, "Greetings from a visitor!")
+    list_entries(head)
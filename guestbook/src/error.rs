@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum GuestbookError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Message Too Long")]
+    MessageTooLong,
+    #[error("Invalid Head Address")]
+    InvalidHeadAddress,
+    #[error("Invalid Entry Address")]
+    InvalidEntryAddress,
+    #[error("Entry Count Overflow")]
+    EntryCountOverflow,
+}
+
+impl From<GuestbookError> for ProgramError {
+    fn from(e: GuestbookError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
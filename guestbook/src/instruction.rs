@@ -0,0 +1,52 @@
+use solana_program::program_error::ProgramError;
+
+use crate::error::GuestbookError::InvalidInstruction;
+
+pub enum GuestbookInstruction {
+
+    // Creates the head account of a guestbook. The head is a PDA derived from ["guestbook", owner]
+    // and keeps track of how many entries have been written so far.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the guestbook, pays for the head account
+    // 1. `[writable]` The guestbook head PDA
+    // 2. `[]` The system program
+    InitGuestbook,
+
+    // Signs the guestbook by creating a new entry PDA derived from ["entry", head, index],
+    // where index is the entry count stored in the head account before this instruction.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The visitor, pays for the entry account
+    // 1. `[writable]` The guestbook head PDA
+    // 2. `[writable]` The entry PDA for the next index
+    // 3. `[]` The system program
+    Sign {
+        // The message the visitor leaves behind, everything after the tag byte, utf-8 encoded
+        message: String,
+    },
+}
+
+impl GuestbookInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitGuestbook,
+            1 => Self::Sign {
+                message: Self::unpack_message(rest)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_message(input: &[u8]) -> Result<String, ProgramError> {
+        let message = std::str::from_utf8(input)
+            .map_err(|_| InvalidInstruction)?;
+        Ok(message.to_string())
+    }
+}
@@ -0,0 +1,197 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::GuestbookInstruction,
+    error::GuestbookError,
+    state::{Entry, GuestbookHead, MAX_MESSAGE_LEN},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = GuestbookInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            GuestbookInstruction::InitGuestbook => {
+                msg!("Instruction: InitGuestbook");
+                Self::process_init_guestbook(accounts, program_id)
+            },
+            GuestbookInstruction::Sign { message } => {
+                msg!("Instruction: Sign");
+                Self::process_sign(accounts, message, program_id)
+            }
+        }
+    }
+
+    fn process_init_guestbook(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let head_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (head_pda, bump_seed) = Pubkey::find_program_address(&[b"guestbook", owner.key.as_ref()], program_id);
+        if head_pda != *head_account.key {
+            return Err(GuestbookError::InvalidHeadAddress.into());
+        }
+
+        if !head_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating guestbook head account...");
+        Self::create_pda_account(
+            owner,
+            head_account,
+            system_program,
+            GuestbookHead::LEN,
+            program_id,
+            &[b"guestbook", owner.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let head_info = GuestbookHead {
+            is_initialized: true,
+            owner: *owner.key,
+            entry_count: 0,
+            bump_seed,
+        };
+        head_info.serialize(&mut &mut head_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_sign(
+        accounts: &[AccountInfo],
+        message: String,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if message.len() > MAX_MESSAGE_LEN {
+            return Err(GuestbookError::MessageTooLong.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let visitor = next_account_info(account_info_iter)?;
+
+        if !visitor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let head_account = next_account_info(account_info_iter)?;
+        if head_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut head_info = GuestbookHead::try_from_slice(&head_account.data.borrow())?;
+        if !head_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        // the entry address is fully determined by the head and the current count, which is what
+        // lets clients walk every entry from 0 to entry_count without any extra index
+        let entry_account = next_account_info(account_info_iter)?;
+        let index_bytes = head_info.entry_count.to_le_bytes();
+        let (entry_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"entry", head_account.key.as_ref(), &index_bytes],
+            program_id,
+        );
+        if entry_pda != *entry_account.key {
+            return Err(GuestbookError::InvalidEntryAddress.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Creating entry {}...", head_info.entry_count);
+        Self::create_pda_account(
+            visitor,
+            entry_account,
+            system_program,
+            Entry::space(message.len()),
+            program_id,
+            &[b"entry", head_account.key.as_ref(), &index_bytes, &[bump_seed]],
+        )?;
+
+        let entry_info = Entry {
+            guestbook: *head_account.key,
+            visitor: *visitor.key,
+            index: head_info.entry_count,
+            timestamp: Clock::get()?.unix_timestamp,
+            message,
+        };
+        entry_info.serialize(&mut &mut entry_account.data.borrow_mut()[..])?;
+
+        head_info.entry_count = head_info.entry_count
+            .checked_add(1)
+            .ok_or(GuestbookError::EntryCountOverflow)?;
+        head_info.serialize(&mut &mut head_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    //
+    // The address of the next entry can be computed by anyone, and anyone can send lamports to it. create_account
+    // refuses an account which holds lamports already, and since the entry count only moves forward one lamport
+    // would close the guestbook for good. Such an account is topped up to the rent instead, then allocated and
+    // assigned to the program, which only needs the signature of the PDA.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let accounts = [
+            payer.clone(),
+            new_account.clone(),
+            system_program.clone(),
+        ];
+
+        if new_account.lamports() == 0 {
+            let create_account_ix = system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            );
+            return invoke_signed(&create_account_ix, &accounts, &[signer_seeds]);
+        }
+
+        let shortfall = lamports.saturating_sub(new_account.lamports());
+        if shortfall > 0 {
+            invoke(&system_instruction::transfer(payer.key, new_account.key, shortfall), &accounts)?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(new_account.key, space as u64),
+            &accounts,
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(new_account.key, program_id),
+            &accounts,
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,35 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Longest message a visitor is allowed to leave, in bytes
+pub const MAX_MESSAGE_LEN: usize = 280;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct GuestbookHead {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    // number of entries written so far, the next entry is created at this index
+    pub entry_count: u64,
+    pub bump_seed: u8,
+}
+
+impl GuestbookHead {
+    pub const LEN: usize = 1 + 32 + 8 + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Entry {
+    pub guestbook: Pubkey,
+    pub visitor: Pubkey,
+    pub index: u64,
+    // unix timestamp taken from the Clock sysvar when the entry was written
+    pub timestamp: i64,
+    pub message: String,
+}
+
+impl Entry {
+    // Entries are sized exactly to their message, borsh stores a string as a u32 length followed by its bytes
+    pub fn space(message_len: usize) -> usize {
+        32 + 32 + 8 + 8 + 4 + message_len
+    }
+}
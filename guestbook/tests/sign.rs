@@ -0,0 +1,117 @@
+// Runs Sign in a local bank with solana-program-test, the guestbook program is called natively
+use borsh::BorshDeserialize;
+use guestbook::{
+    processor::Processor,
+    state::{Entry, GuestbookHead},
+};
+use solana_program_test::{processor, BanksClient, ProgramTest, ProgramTestBanksClientExt};
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+struct Test {
+    banks_client: BanksClient,
+    payer: Keypair,
+    recent_blockhash: Hash,
+    program_id: Pubkey,
+    head: Pubkey,
+}
+
+impl Test {
+    // A guestbook owned by the payer, without any entries
+    async fn start() -> Self {
+        let program_id = Pubkey::new_unique();
+        let program_test = ProgramTest::new("guestbook", program_id, processor!(Processor::process));
+        let (banks_client, payer, recent_blockhash) = program_test.start().await;
+        let head = Pubkey::find_program_address(&[b"guestbook", payer.pubkey().as_ref()], &program_id).0;
+        let mut test = Self { banks_client, payer, recent_blockhash, program_id, head };
+
+        let init_guestbook = Instruction::new_with_bytes(
+            program_id,
+            &[0],
+            vec![
+                AccountMeta::new(test.payer.pubkey(), true),
+                AccountMeta::new(head, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        test.send(init_guestbook).await.unwrap();
+        test
+    }
+
+    // Every transaction uses a new blockhash, otherwise the same instruction twice would be a duplicate transaction
+    async fn send(&mut self, instruction: Instruction) -> Result<(), TransactionError> {
+        self.recent_blockhash = self.banks_client.get_new_latest_blockhash(&self.recent_blockhash).await.unwrap();
+        let payer = self.payer.pubkey();
+        let transaction =
+            Transaction::new_signed_with_payer(&[instruction], Some(&payer), &[&self.payer], self.recent_blockhash);
+        self.banks_client.process_transaction(transaction).await.map_err(|e| e.unwrap())
+    }
+
+    fn entry_address(&self, index: u64) -> Pubkey {
+        let seeds: &[&[u8]] = &[b"entry", self.head.as_ref(), &index.to_le_bytes()];
+        Pubkey::find_program_address(seeds, &self.program_id).0
+    }
+
+    async fn sign(&mut self, message: &str) -> Result<(), TransactionError> {
+        let head = self.banks_client.get_account(self.head).await.unwrap().unwrap();
+        let entry_count = GuestbookHead::try_from_slice(&head.data).unwrap().entry_count;
+        let mut data = vec![1];
+        data.extend_from_slice(message.as_bytes());
+        let sign = Instruction::new_with_bytes(
+            self.program_id,
+            &data,
+            vec![
+                AccountMeta::new(self.payer.pubkey(), true),
+                AccountMeta::new(self.head, false),
+                AccountMeta::new(self.entry_address(entry_count), false),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+        );
+        self.send(sign).await
+    }
+
+    async fn entry(&mut self, index: u64) -> Entry {
+        let account = self.banks_client.get_account(self.entry_address(index)).await.unwrap().unwrap();
+        assert_eq!(account.owner, self.program_id);
+        Entry::try_from_slice(&account.data).unwrap()
+    }
+}
+
+#[tokio::test]
+async fn sign_creates_the_next_entry() {
+    let mut test = Test::start().await;
+    test.sign("first").await.unwrap();
+    test.sign("second").await.unwrap();
+
+    assert_eq!(test.entry(0).await.message, "first");
+    let entry = test.entry(1).await;
+    assert_eq!((entry.index, entry.message.as_str()), (1, "second"));
+}
+
+#[tokio::test]
+async fn prefunded_entry_is_signed_anyway() {
+    let mut test = Test::start().await;
+    test.sign("first").await.unwrap();
+
+    // anybody can send lamports to the address of the next entry, the bank wants them rent exempt for no data
+    let payer = test.payer.pubkey();
+    let next_entry = test.entry_address(1);
+    let lamports = test.banks_client.get_rent().await.unwrap().minimum_balance(0);
+    test.send(system_instruction::transfer(&payer, &next_entry, lamports)).await.unwrap();
+
+    test.sign("second").await.unwrap();
+    let entry = test.entry(1).await;
+    assert_eq!((entry.visitor, entry.message.as_str()), (payer, "second"));
+    let rent = test.banks_client.get_rent().await.unwrap().minimum_balance(Entry::space("second".len()));
+    assert_eq!(test.banks_client.get_balance(next_entry).await.unwrap(), rent);
+
+    // and the guestbook goes on after it
+    test.sign("third").await.unwrap();
+    assert_eq!(test.entry(2).await.message, "third");
+}
@@ -0,0 +1,275 @@
+import yaml
+import sys
+import json
+import time
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+HABIT_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'user': types.fixed_array(types.u8, 32),
+    'charity': types.fixed_array(types.u8, 32),
+    'id': types.u64,
+    'interval': types.i64,
+    'days': types.u8,
+    'slash': types.u64,
+    'start_ts': types.i64,
+    'check_ins': types.u64,
+    'settled_days': types.u8,
+    'bump_seed': types.u8
+})
+
+# a "day" of the demo, real daily check-ins would use 86400
+INTERVAL = 10
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'habits.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'habits-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_habit_address(user: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("habit", encoding="utf8"), bytes(user), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def balance(pubkey: PublicKey) -> int:
+    return client.get_balance(pubkey)["result"]["value"]
+
+def cluster_time() -> int:
+    return client.get_block_time(client.get_slot()['result'])['result']
+
+def load_habit(habit: PublicKey) -> dict:
+    account_info = client.get_account_info(habit)["result"]["value"]
+    return borsh.deserialize(HABIT_SCHEMA, base64.b64decode(account_info["data"][0]))
+
+def wait_for_day(habit: PublicKey, day: int):
+    start = load_habit(habit)['start_ts'] + day * INTERVAL
+    print(f"\nWaiting for day {day}...")
+    while cluster_time() < start:
+        time.sleep(1)
+
+def create_habit(user: Keypair, charity: PublicKey, id: int, days: int, slash: int, stake: int) -> PublicKey:
+    habit = get_habit_address(user.public_key, id)
+    print(f"\nCommitting to a habit for {days} days with a stake of {stake} lamports, {slash} are slashed per missed day")
+    send(
+        user,
+        (0).to_bytes(1, byteorder='little')
+        + id.to_bytes(8, byteorder='little')
+        + INTERVAL.to_bytes(8, byteorder='little', signed=True)
+        + days.to_bytes(1, byteorder='little')
+        + slash.to_bytes(8, byteorder='little')
+        + stake.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=user.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=charity, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=habit, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return habit
+
+def check_in(user: Keypair, habit: PublicKey):
+    print("Checking in")
+    send(
+        user,
+        (1).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=user.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=habit, is_signer=False, is_writable=True),
+        ],
+    )
+
+def slash(caller: Keypair, habit: PublicKey, charity: PublicKey):
+    print("Slashing the missed days")
+    send(
+        caller,
+        (2).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=habit, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=charity, is_signer=False, is_writable=True),
+        ],
+    )
+
+def claim(user: Keypair, habit: PublicKey, charity: PublicKey):
+    print("Claiming the rest of the stake")
+    send(
+        user,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=user.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=habit, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=charity, is_signer=False, is_writable=True),
+        ],
+    )
+
+def show_habit(habit: PublicKey):
+    habit_info = load_habit(habit)
+    days = "".join("x" if habit_info['check_ins'] & (1 << day) else "." for day in range(habit_info['days']))
+    print(f"Check-ins: {days}, {habit_info['settled_days']} days settled, {balance(habit)} lamports in the habit account")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    charity = setup_user_keypair()
+    charity_balance = balance(charity.public_key)
+
+    habit = create_habit(payer, charity.public_key, 0, 4, LAMPORTS_PER_SOL // 10, LAMPORTS_PER_SOL // 2)
+
+    check_in(payer, habit)
+    try:
+        check_in(payer, habit)
+    except Exception as e:
+        print(f"Checking in twice on the same day fails as expected: {e}")
+    try:
+        slash(payer, habit, charity.public_key)
+    except Exception as e:
+        print(f"Slashing without a missed day fails as expected: {e}")
+
+    # day 1 is skipped
+    wait_for_day(habit, 2)
+    check_in(payer, habit)
+    slash(payer, habit, charity.public_key)
+    show_habit(habit)
+
+    try:
+        claim(payer, habit, charity.public_key)
+    except Exception as e:
+        print(f"Claiming before the end fails as expected: {e}")
+
+    # day 3 is skipped too, it is slashed by the claim
+    wait_for_day(habit, 4)
+    show_habit(habit)
+    claim(payer, habit, charity.public_key)
+    print(f"The charity received {balance(charity.public_key) - charity_balance} lamports")
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum HabitError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Habit Address")]
+    InvalidHabitAddress,
+    #[error("Invalid Schedule")]
+    InvalidSchedule,
+    #[error("Invalid Charity")]
+    InvalidCharity,
+    #[error("Already Checked In")]
+    AlreadyCheckedIn,
+    #[error("Habit Over")]
+    HabitOver,
+    #[error("Habit Not Over")]
+    HabitNotOver,
+    #[error("Nothing To Slash")]
+    NothingToSlash,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<HabitError> for ProgramError {
+    fn from(e: HabitError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
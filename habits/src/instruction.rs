@@ -0,0 +1,83 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::HabitError::InvalidInstruction;
+
+pub enum HabitInstruction {
+
+    // Commits to a habit for a number of days. The commitment lives in a PDA derived from ["habit", user, id],
+    // the stake is kept in the PDA on top of its rent. Day 0 starts right away.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The user
+    // 1. `[]` The charity
+    // 2. `[writable]` The habit PDA
+    // 3. `[]` The system program
+    CreateHabit {
+        id: u64,
+        interval: i64,
+        days: u8,
+        slash: u64,
+        stake: u64,
+    },
+
+    // Checks in for the current day.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The user
+    // 1. `[writable]` The habit PDA
+    CheckIn,
+
+    // Slashes every day that is over and has no check-in. Anybody may call it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The habit PDA
+    // 1. `[writable]` The charity
+    Slash,
+
+    // Slashes the missed days which are left once the commitment is over, pays the rest of the stake back
+    // to the user and closes the habit account.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The user
+    // 1. `[writable]` The habit PDA
+    // 2. `[writable]` The charity
+    Claim,
+}
+
+impl HabitInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateHabit {
+                id: Self::unpack_u64(rest, 0)?,
+                interval: Self::unpack_u64(rest, 8)? as i64,
+                days: *rest.get(16).ok_or(InvalidInstruction)?,
+                slash: Self::unpack_u64(rest, 17)?,
+                stake: Self::unpack_u64(rest, 25)?,
+            },
+            1 => Self::CheckIn,
+            2 => Self::Slash,
+            3 => Self::Claim,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
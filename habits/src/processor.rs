@@ -0,0 +1,258 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::HabitInstruction,
+    error::HabitError,
+    state::{Habit, MAX_DAYS},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = HabitInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            HabitInstruction::CreateHabit { id, interval, days, slash, stake } => {
+                msg!("Instruction: CreateHabit");
+                Self::process_create_habit(accounts, id, interval, days, slash, stake, program_id)
+            },
+            HabitInstruction::CheckIn => {
+                msg!("Instruction: CheckIn");
+                Self::process_check_in(accounts, program_id)
+            },
+            HabitInstruction::Slash => {
+                msg!("Instruction: Slash");
+                Self::process_slash(accounts, program_id)
+            },
+            HabitInstruction::Claim => {
+                msg!("Instruction: Claim");
+                Self::process_claim(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_habit(
+        accounts: &[AccountInfo],
+        id: u64,
+        interval: i64,
+        days: u8,
+        slash: u64,
+        stake: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if interval <= 0 || days == 0 || days > MAX_DAYS {
+            return Err(HabitError::InvalidSchedule.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let user = next_account_info(account_info_iter)?;
+
+        if !user.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let charity = next_account_info(account_info_iter)?;
+        let habit_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (habit_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"habit", user.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if habit_pda != *habit_account.key {
+            return Err(HabitError::InvalidHabitAddress.into());
+        }
+
+        if !habit_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the stake is simply added on top of the rent when the account is created
+        let lamports = Rent::get()?.minimum_balance(Habit::LEN)
+            .checked_add(stake)
+            .ok_or(HabitError::AmountOverflow)?;
+
+        msg!("Creating habit account with a stake of {} lamports for {} days...", stake, days);
+        invoke_signed(
+            &system_instruction::create_account(
+                user.key,
+                habit_account.key,
+                lamports,
+                Habit::LEN as u64,
+                program_id,
+            ),
+            &[
+                user.clone(),
+                habit_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"habit", user.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        let habit_info = Habit {
+            is_initialized: true,
+            user: *user.key,
+            charity: *charity.key,
+            id,
+            interval,
+            days,
+            slash,
+            start_ts: Clock::get()?.unix_timestamp,
+            check_ins: 0,
+            settled_days: 0,
+            bump_seed,
+        };
+        habit_info.serialize(&mut &mut habit_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_check_in(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user = next_account_info(account_info_iter)?;
+
+        if !user.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let habit_account = next_account_info(account_info_iter)?;
+        let mut habit_info = Self::unpack_habit(habit_account, program_id)?;
+        if habit_info.user != *user.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // a check-in only counts for the day it happens on, missed days can't be made up later
+        let today = habit_info.day(Clock::get()?.unix_timestamp);
+        if today >= habit_info.days {
+            return Err(HabitError::HabitOver.into());
+        }
+        if habit_info.checked_in(today) {
+            return Err(HabitError::AlreadyCheckedIn.into());
+        }
+
+        msg!("Checking in for day {}", today);
+        habit_info.check_ins |= 1 << today;
+        habit_info.serialize(&mut &mut habit_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_slash(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let habit_account = next_account_info(account_info_iter)?;
+        let mut habit_info = Self::unpack_habit(habit_account, program_id)?;
+
+        let charity = next_account_info(account_info_iter)?;
+        if *charity.key != habit_info.charity {
+            return Err(HabitError::InvalidCharity.into());
+        }
+
+        // only days which are over can be slashed, the user may still check in today
+        let today = habit_info.day(Clock::get()?.unix_timestamp);
+        if habit_info.missed_days(today) == 0 {
+            return Err(HabitError::NothingToSlash.into());
+        }
+
+        Self::settle(&mut habit_info, habit_account, charity, today)?;
+        habit_info.serialize(&mut &mut habit_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_claim(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user = next_account_info(account_info_iter)?;
+
+        if !user.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let habit_account = next_account_info(account_info_iter)?;
+        let mut habit_info = Self::unpack_habit(habit_account, program_id)?;
+        if habit_info.user != *user.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let charity = next_account_info(account_info_iter)?;
+        if *charity.key != habit_info.charity {
+            return Err(HabitError::InvalidCharity.into());
+        }
+
+        if habit_info.day(Clock::get()?.unix_timestamp) < habit_info.days {
+            return Err(HabitError::HabitNotOver.into());
+        }
+
+        // nobody has to call Slash before, the days missed since the last one are settled here
+        let days = habit_info.days;
+        Self::settle(&mut habit_info, habit_account, charity, days)?;
+
+        msg!("Closing the habit account, {} lamports go back to the user...", habit_account.lamports());
+        **user.lamports.borrow_mut() = user.lamports()
+            .checked_add(habit_account.lamports())
+            .ok_or(HabitError::AmountOverflow)?;
+        **habit_account.lamports.borrow_mut() = 0;
+        habit_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_habit(habit_account: &AccountInfo, program_id: &Pubkey) -> Result<Habit, ProgramError> {
+        if habit_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let habit_info = Habit::try_from_slice(&habit_account.data.borrow())?;
+        if !habit_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(habit_info)
+    }
+
+    // Moves the slash of every missed day before `until` to the charity, as far as the stake covers it.
+    // The habit account is owned by the program, so the lamports are moved directly.
+    fn settle(
+        habit_info: &mut Habit,
+        habit_account: &AccountInfo,
+        charity: &AccountInfo,
+        until: u8,
+    ) -> ProgramResult {
+        let missed = habit_info.missed_days(until);
+        let rent_lamports = Rent::get()?.minimum_balance(habit_account.data_len());
+        let funds = habit_account.lamports().saturating_sub(rent_lamports);
+        let amount = missed.saturating_mul(habit_info.slash).min(funds);
+
+        if amount > 0 {
+            msg!("Slashing {} missed days, {} lamports go to the charity...", missed, amount);
+            **habit_account.lamports.borrow_mut() -= amount;
+            **charity.lamports.borrow_mut() = charity.lamports()
+                .checked_add(amount)
+                .ok_or(HabitError::AmountOverflow)?;
+        }
+
+        habit_info.settled_days = until;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Check-ins are kept as the bits of a u64, one per day
+pub const MAX_DAYS: u8 = 64;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Habit {
+    pub is_initialized: bool,
+    pub user: Pubkey,
+    // receives the slashed lamports
+    pub charity: Pubkey,
+    pub id: u64,
+    // length of a "day" in seconds, 86400 for real daily check-ins
+    pub interval: i64,
+    pub days: u8,
+    // lamports lost for every missed day
+    pub slash: u64,
+    // day 0 starts here
+    pub start_ts: i64,
+    // bit n is set once the user checked in on day n
+    pub check_ins: u64,
+    // days before this one have been settled, the missed ones among them are slashed already
+    pub settled_days: u8,
+    pub bump_seed: u8,
+}
+
+impl Habit {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1 + 8 + 8 + 8 + 1 + 1;
+
+    // the day `now` falls into, counted from the start and capped at the end of the commitment
+    pub fn day(&self, now: i64) -> u8 {
+        let day = now.saturating_sub(self.start_ts) / self.interval;
+        day.min(self.days as i64) as u8
+    }
+
+    pub fn checked_in(&self, day: u8) -> bool {
+        self.check_ins & (1 << day) != 0
+    }
+
+    // days between the last settlement and `until` without a check-in
+    pub fn missed_days(&self, until: u8) -> u64 {
+        (self.settled_days..until).filter(|day| !self.checked_in(*day)).count() as u64
+    }
+}
@@ -0,0 +1,252 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+import hashlib
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+IDENTITY_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'id': types.fixed_array(types.u8, 32),
+    'authority': types.fixed_array(types.u8, 32),
+    'display_name': types.string,
+    'services_hash': types.fixed_array(types.u8, 32),
+    'previous_keys': types.dynamic_array(types.fixed_array(types.u8, 32)),
+    'revoked': types.u8,
+    'updated_ts': types.i64,
+    'bump_seed': types.u8
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'identity.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'identity-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def get_identity_address(id: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("identity", encoding="utf8"), bytes(id)], program_id)[0]
+
+def services_hash(services: dict) -> bytes:
+    # only the hash goes on-chain, the document itself can be hosted anywhere
+    return hashlib.sha256(json.dumps(services, sort_keys=True).encode("utf8")).digest()
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def create_identity(creator: Keypair, display_name: str, services: dict) -> PublicKey:
+    identity = get_identity_address(creator.public_key)
+    print(f"\nCreating identity {identity} for '{display_name}'")
+    send(
+        creator,
+        (0).to_bytes(1, byteorder='little') + services_hash(services) + bytes(display_name, encoding="utf8"),
+        [
+            AccountMeta(pubkey=creator.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=identity, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return identity
+
+def update(authority: Keypair, identity: PublicKey, display_name: str, services: dict):
+    print(f"\nUpdating identity {identity} to '{display_name}'")
+    send(
+        authority,
+        (1).to_bytes(1, byteorder='little') + services_hash(services) + bytes(display_name, encoding="utf8"),
+        [
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=identity, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def rotate_key(authority: Keypair, new_authority: PublicKey, identity: PublicKey):
+    print(f"\nRotating the key of {identity} to {new_authority}")
+    send(
+        authority,
+        (2).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=new_authority, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=identity, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def revoke(authority: Keypair, identity: PublicKey):
+    print(f"\nRevoking identity {identity}")
+    send(
+        authority,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=identity, is_signer=False, is_writable=True),
+        ],
+    )
+
+def resolve(identity: PublicKey):
+    account_info = client.get_account_info(identity)["result"]["value"]
+    identity_info = borsh.deserialize(IDENTITY_SCHEMA, base64.b64decode(account_info["data"][0]))
+    print(f"Identity {PublicKey(identity_info['id'])} '{identity_info['display_name']}'"
+          f"{' (revoked)' if identity_info['revoked'] else ''}")
+    print(f" controlled by {PublicKey(identity_info['authority'])}, services hash {bytes(identity_info['services_hash']).hex()}")
+    for key in identity_info['previous_keys']:
+        print(f" previously controlled by {PublicKey(key)}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    user = setup_user_keypair()
+    services = {"messaging": "https://example.com/inbox"}
+    identity = create_identity(user, "alice", services)
+    resolve(identity)
+
+    services["storage"] = "https://example.com/files"
+    update(user, identity, "alice.sol", services)
+    resolve(identity)
+
+    new_key = setup_user_keypair()
+    rotate_key(user, new_key.public_key, identity)
+    resolve(identity)
+
+    try:
+        update(user, identity, "mallory", services)
+    except Exception as e:
+        print(f"The rotated key has no control anymore: {e}")
+
+    try:
+        rotate_key(new_key, user.public_key, identity)
+    except Exception as e:
+        print(f"A rotated key can't come back: {e}")
+
+    revoke(new_key, identity)
+    resolve(identity)
+    try:
+        update(new_key, identity, "alice again", services)
+    except Exception as e:
+        print(f"A revoked identity can't be updated: {e}")
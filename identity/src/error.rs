@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum IdentityError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Identity Address")]
+    InvalidIdentityAddress,
+    #[error("Not The Authority")]
+    NotTheAuthority,
+    #[error("Display Name Too Long")]
+    DisplayNameTooLong,
+    #[error("Key Already Used")]
+    KeyAlreadyUsed,
+    #[error("Key History Full")]
+    KeyHistoryFull,
+    #[error("Identity Revoked")]
+    IdentityRevoked,
+}
+
+impl From<IdentityError> for ProgramError {
+    fn from(e: IdentityError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
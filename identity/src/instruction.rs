@@ -0,0 +1,84 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::IdentityError::InvalidInstruction;
+
+pub enum IdentityInstruction {
+
+    // Anchors an identity in a PDA derived from ["identity", id], where id is the key creating it.
+    // Followed by the 32 byte services hash and the utf-8 display name.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The creating key, becomes the id and the first authority
+    // 1. `[writable]` The identity PDA
+    // 2. `[]` The system program
+    CreateIdentity {
+        services_hash: [u8; 32],
+        display_name: String,
+    },
+
+    // Replaces the services hash and the display name, same data layout as CreateIdentity.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The current authority, pays if the account grows
+    // 1. `[writable]` The identity PDA
+    // 2. `[]` The system program
+    Update {
+        services_hash: [u8; 32],
+        display_name: String,
+    },
+
+    // Hands the identity over to a new key. The current key has to sign, it's moved to the key history.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The current authority, pays for the longer history
+    // 1. `[]` The new authority
+    // 2. `[writable]` The identity PDA
+    // 3. `[]` The system program
+    RotateKey,
+
+    // Revokes the identity for good, a revoked identity can't be updated or rotated anymore.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The current authority
+    // 1. `[writable]` The identity PDA
+    Revoke,
+}
+
+impl IdentityInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => {
+                let (services_hash, display_name) = Self::unpack_profile(rest)?;
+                Self::CreateIdentity { services_hash, display_name }
+            },
+            1 => {
+                let (services_hash, display_name) = Self::unpack_profile(rest)?;
+                Self::Update { services_hash, display_name }
+            },
+            2 => Self::RotateKey,
+            3 => Self::Revoke,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_profile(input: &[u8]) -> Result<([u8; 32], String), ProgramError> {
+        let services_hash = input
+            .get(..32)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(InvalidInstruction)?;
+        let display_name = std::str::from_utf8(&input[32..])
+            .map_err(|_| InvalidInstruction)?;
+        Ok((services_hash, display_name.to_string()))
+    }
+}
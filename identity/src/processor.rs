@@ -0,0 +1,235 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::BorshSerialize;
+
+use crate::{
+    instruction::IdentityInstruction,
+    error::IdentityError,
+    state::{Identity, MAX_DISPLAY_NAME_LEN, MAX_PREVIOUS_KEYS},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = IdentityInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            IdentityInstruction::CreateIdentity { services_hash, display_name } => {
+                msg!("Instruction: CreateIdentity");
+                Self::process_create_identity(accounts, services_hash, display_name, program_id)
+            },
+            IdentityInstruction::Update { services_hash, display_name } => {
+                msg!("Instruction: Update");
+                Self::process_update(accounts, services_hash, display_name, program_id)
+            },
+            IdentityInstruction::RotateKey => {
+                msg!("Instruction: RotateKey");
+                Self::process_rotate_key(accounts, program_id)
+            },
+            IdentityInstruction::Revoke => {
+                msg!("Instruction: Revoke");
+                Self::process_revoke(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_identity(
+        accounts: &[AccountInfo],
+        services_hash: [u8; 32],
+        display_name: String,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if display_name.len() > MAX_DISPLAY_NAME_LEN {
+            return Err(IdentityError::DisplayNameTooLong.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let identity_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (identity_pda, bump_seed) = Pubkey::find_program_address(&[b"identity", creator.key.as_ref()], program_id);
+        if identity_pda != *identity_account.key {
+            return Err(IdentityError::InvalidIdentityAddress.into());
+        }
+
+        if !identity_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let identity_info = Identity {
+            is_initialized: true,
+            id: *creator.key,
+            authority: *creator.key,
+            display_name,
+            services_hash,
+            previous_keys: Vec::new(),
+            revoked: false,
+            updated_ts: Clock::get()?.unix_timestamp,
+            bump_seed,
+        };
+        let data = identity_info.try_to_vec()?;
+
+        msg!("Creating identity account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                creator.key,
+                identity_account.key,
+                Rent::get()?.minimum_balance(data.len()),
+                data.len() as u64,
+                program_id,
+            ),
+            &[
+                creator.clone(),
+                identity_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"identity", creator.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        identity_account.data.borrow_mut().copy_from_slice(&data);
+
+        Ok(())
+    }
+
+    fn process_update(
+        accounts: &[AccountInfo],
+        services_hash: [u8; 32],
+        display_name: String,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if display_name.len() > MAX_DISPLAY_NAME_LEN {
+            return Err(IdentityError::DisplayNameTooLong.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+        let identity_account = next_account_info(account_info_iter)?;
+        let mut identity_info = Self::unpack_as_authority(authority, identity_account, program_id)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        identity_info.display_name = display_name;
+        identity_info.services_hash = services_hash;
+        identity_info.updated_ts = Clock::get()?.unix_timestamp;
+
+        Self::store_identity(&identity_info, authority, identity_account, system_program)
+    }
+
+    fn process_rotate_key(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+        let new_authority = next_account_info(account_info_iter)?;
+        let identity_account = next_account_info(account_info_iter)?;
+        let mut identity_info = Self::unpack_as_authority(authority, identity_account, program_id)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        // a key which was rotated away is considered compromised, it must never control the identity again
+        if identity_info.has_used_key(new_authority.key) {
+            return Err(IdentityError::KeyAlreadyUsed.into());
+        }
+        if identity_info.previous_keys.len() >= MAX_PREVIOUS_KEYS {
+            return Err(IdentityError::KeyHistoryFull.into());
+        }
+
+        msg!("Rotating the key of {} from {} to {}", identity_info.id, authority.key, new_authority.key);
+        identity_info.previous_keys.push(identity_info.authority);
+        identity_info.authority = *new_authority.key;
+        identity_info.updated_ts = Clock::get()?.unix_timestamp;
+
+        Self::store_identity(&identity_info, authority, identity_account, system_program)
+    }
+
+    fn process_revoke(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+        let identity_account = next_account_info(account_info_iter)?;
+        let mut identity_info = Self::unpack_as_authority(authority, identity_account, program_id)?;
+
+        // the account stays, so anybody resolving the identity learns that it's revoked instead of finding nothing
+        msg!("Revoking identity {}", identity_info.id);
+        identity_info.revoked = true;
+        identity_info.updated_ts = Clock::get()?.unix_timestamp;
+        identity_info.serialize(&mut &mut identity_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn unpack_as_authority(
+        authority: &AccountInfo,
+        identity_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<Identity, ProgramError> {
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let identity_info = Identity::from_account_info(identity_account, program_id)?;
+        if identity_info.authority != *authority.key {
+            return Err(IdentityError::NotTheAuthority.into());
+        }
+        if identity_info.revoked {
+            return Err(IdentityError::IdentityRevoked.into());
+        }
+
+        Ok(identity_info)
+    }
+
+    // Writes the identity back and resizes the account to the serialized length first. A bigger account needs
+    // more lamports to stay rent exempt, which the authority pays through the system program. A smaller one
+    // gives the surplus back to the authority.
+    fn store_identity<'a>(
+        identity_info: &Identity,
+        authority: &AccountInfo<'a>,
+        identity_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let data = identity_info.try_to_vec()?;
+
+        let required_lamports = Rent::get()?.minimum_balance(data.len());
+        let current_lamports = identity_account.lamports();
+
+        if required_lamports > current_lamports {
+            invoke(
+                &system_instruction::transfer(authority.key, identity_account.key, required_lamports - current_lamports),
+                &[
+                    authority.clone(),
+                    identity_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        } else if current_lamports > required_lamports {
+            let surplus = current_lamports - required_lamports;
+            **identity_account.lamports.borrow_mut() = required_lamports;
+            **authority.lamports.borrow_mut() = authority.lamports()
+                .checked_add(surplus)
+                .ok_or(ProgramError::InvalidAccountData)?;
+        }
+
+        msg!("Resizing identity from {} to {} bytes...", identity_account.data_len(), data.len());
+        identity_account.realloc(data.len(), false)?;
+        identity_account.data.borrow_mut().copy_from_slice(&data);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+// Longest display name, in bytes
+pub const MAX_DISPLAY_NAME_LEN: usize = 32;
+
+// Number of rotated keys an identity remembers
+pub const MAX_PREVIOUS_KEYS: usize = 16;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Identity {
+    pub is_initialized: bool,
+    // the key the identity was created with, it's part of the seeds and never changes, so the address stays
+    // the same across key rotations
+    pub id: Pubkey,
+    // the key currently controlling the identity
+    pub authority: Pubkey,
+    pub display_name: String,
+    // hash of the off-chain document listing the service endpoints
+    pub services_hash: [u8; 32],
+    // keys which controlled the identity before, oldest first
+    pub previous_keys: Vec<Pubkey>,
+    pub revoked: bool,
+    pub updated_ts: i64,
+    pub bump_seed: u8,
+}
+
+impl Identity {
+    pub fn from_account_info(identity_account: &AccountInfo, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if identity_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let identity_info = Self::try_from_slice(&identity_account.data.borrow())?;
+        if !identity_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(identity_info)
+    }
+
+    // true if the key controls the identity now or did so in the past
+    pub fn has_used_key(&self, key: &Pubkey) -> bool {
+        self.authority == *key || self.previous_keys.contains(key)
+    }
+}
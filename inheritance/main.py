@@ -0,0 +1,268 @@
+import yaml
+import sys
+import json
+import time
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+WILL_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'owner': types.fixed_array(types.u8, 32),
+    'heir': types.fixed_array(types.u8, 32),
+    'window': types.i64,
+    'last_heartbeat': types.i64,
+    'bump_seed': types.u8
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'inheritance.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'inheritance-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_will_address(owner: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("will", encoding="utf8"), bytes(owner)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def create_will(owner: Keypair, heir: PublicKey, window: int, deposit: int) -> PublicKey:
+    will = get_will_address(owner.public_key)
+    print(f"\nCreating will {will} with {deposit} lamports, the heir may sweep after {window}s of silence")
+    send(
+        owner,
+        (0).to_bytes(1, byteorder='little') + window.to_bytes(8, byteorder='little', signed=True) + deposit.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=heir, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=will, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return will
+
+def deposit(depositor: Keypair, will: PublicKey, amount: int):
+    print(f"\nDepositing {amount} lamports")
+    send(
+        depositor,
+        (1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=depositor.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=will, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def heartbeat(owner: Keypair, will: PublicKey):
+    print("\nSending a heartbeat")
+    send(
+        owner,
+        (2).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=will, is_signer=False, is_writable=True),
+        ],
+    )
+
+def withdraw(owner: Keypair, will: PublicKey, amount: int):
+    print(f"\nWithdrawing {amount} lamports")
+    send(
+        owner,
+        (3).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=will, is_signer=False, is_writable=True),
+        ],
+    )
+
+def change_heir(owner: Keypair, will: PublicKey, heir: PublicKey):
+    print(f"\nChanging the heir to {heir}")
+    send(
+        owner,
+        (4).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=will, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=heir, is_signer=False, is_writable=False),
+        ],
+    )
+
+def sweep(heir: Keypair, will: PublicKey):
+    print(f"\n{heir.public_key} tries to sweep the will")
+    send(
+        heir,
+        (5).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=heir.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=will, is_signer=False, is_writable=True),
+        ],
+    )
+
+def show_will(will: PublicKey):
+    account = client.get_account_info(will)["result"]["value"]
+    if account is None:
+        print(f"Will {will} is closed")
+        return
+    will_info = borsh.deserialize(WILL_SCHEMA, base64.b64decode(account["data"][0]))
+    print(f"Will {will}: {account['lamports']} lamports, heir {PublicKey(bytes(will_info['heir']))}, last heartbeat {will_info['last_heartbeat']}, window {will_info['window']}s")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    heir = setup_user_keypair()
+    first_heir = setup_user_keypair()
+
+    window = 10
+    will = create_will(payer, first_heir.public_key, window, LAMPORTS_PER_SOL // 2)
+    deposit(heir, will, LAMPORTS_PER_SOL // 10)
+    change_heir(payer, will, heir.public_key)
+    show_will(will)
+
+    try:
+        sweep(first_heir, will)
+    except Exception as e:
+        print(f"Sweeping as the former heir fails as expected: {e}")
+
+    try:
+        sweep(heir, will)
+    except Exception as e:
+        print(f"Sweeping while the owner is alive fails as expected: {e}")
+
+    time.sleep(window // 2)
+    heartbeat(payer, will)
+    withdraw(payer, will, LAMPORTS_PER_SOL // 10)
+    show_will(will)
+
+    print(f"\nThe owner stays silent for more than {window}s...")
+    time.sleep(window + 2)
+    heir_balance = client.get_balance(heir.public_key)['result']['value']
+    sweep(heir, will)
+    print(f"The heir received {client.get_balance(heir.public_key)['result']['value'] - heir_balance} lamports, minus the transaction fee")
+    show_will(will)
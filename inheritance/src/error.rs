@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum InheritanceError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Will Address")]
+    InvalidWillAddress,
+    #[error("Invalid Window")]
+    InvalidWindow,
+    #[error("Not Owner")]
+    NotOwner,
+    #[error("Not Heir")]
+    NotHeir,
+    #[error("Owner Still Alive")]
+    OwnerStillAlive,
+    #[error("Insufficient Funds")]
+    InsufficientFunds,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<InheritanceError> for ProgramError {
+    fn from(e: InheritanceError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
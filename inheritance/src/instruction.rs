@@ -0,0 +1,105 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::InheritanceError::InvalidInstruction;
+
+pub enum InheritanceInstruction {
+
+    // Creates a will in a PDA derived from ["will", owner]. The will account is the vault as well, the deposit
+    // is added on top of the rent.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner
+    // 1. `[]` The heir
+    // 2. `[writable]` The will PDA
+    // 3. `[]` The system program
+    CreateWill {
+        window: i64,
+        deposit: u64,
+    },
+
+    // Adds lamports to the will. Anyone may deposit, it doesn't count as a heartbeat.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The depositor
+    // 1. `[writable]` The will PDA
+    // 2. `[]` The system program
+    Deposit {
+        amount: u64,
+    },
+
+    // The owner's sign of life, restarts the window.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner
+    // 1. `[writable]` The will PDA
+    Heartbeat,
+
+    // Takes lamports out of the will, counts as a heartbeat.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner
+    // 1. `[writable]` The will PDA
+    Withdraw {
+        amount: u64,
+    },
+
+    // Designates a new heir, counts as a heartbeat.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner
+    // 1. `[writable]` The will PDA
+    // 2. `[]` The new heir
+    ChangeHeir,
+
+    // Sends all lamports of the will, rent included, to the heir once the window has lapsed and closes the will.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The heir
+    // 1. `[writable]` The will PDA
+    Sweep,
+}
+
+impl InheritanceInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateWill {
+                window: Self::unpack_u64(rest, 0)? as i64,
+                deposit: Self::unpack_u64(rest, 8)?,
+            },
+            1 => Self::Deposit {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::Heartbeat,
+            3 => Self::Withdraw {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            4 => Self::ChangeHeir,
+            5 => Self::Sweep,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,273 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{instruction::InheritanceInstruction, error::InheritanceError, state::Will};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = InheritanceInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            InheritanceInstruction::CreateWill { window, deposit } => {
+                msg!("Instruction: CreateWill");
+                Self::process_create_will(accounts, window, deposit, program_id)
+            },
+            InheritanceInstruction::Deposit { amount } => {
+                msg!("Instruction: Deposit");
+                Self::process_deposit(accounts, amount, program_id)
+            },
+            InheritanceInstruction::Heartbeat => {
+                msg!("Instruction: Heartbeat");
+                Self::process_heartbeat(accounts, program_id)
+            },
+            InheritanceInstruction::Withdraw { amount } => {
+                msg!("Instruction: Withdraw");
+                Self::process_withdraw(accounts, amount, program_id)
+            },
+            InheritanceInstruction::ChangeHeir => {
+                msg!("Instruction: ChangeHeir");
+                Self::process_change_heir(accounts, program_id)
+            },
+            InheritanceInstruction::Sweep => {
+                msg!("Instruction: Sweep");
+                Self::process_sweep(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_will(
+        accounts: &[AccountInfo],
+        window: i64,
+        deposit: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if window <= 0 {
+            return Err(InheritanceError::InvalidWindow.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let heir = next_account_info(account_info_iter)?;
+        let will_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (will_pda, bump_seed) = Pubkey::find_program_address(&[b"will", owner.key.as_ref()], program_id);
+        if will_pda != *will_account.key {
+            return Err(InheritanceError::InvalidWillAddress.into());
+        }
+
+        if !will_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the deposit is simply added on top of the rent when the account is created
+        let lamports = Rent::get()?.minimum_balance(Will::LEN)
+            .checked_add(deposit)
+            .ok_or(InheritanceError::AmountOverflow)?;
+
+        msg!("Creating will account holding {} lamports...", deposit);
+        invoke_signed(
+            &system_instruction::create_account(
+                owner.key,
+                will_account.key,
+                lamports,
+                Will::LEN as u64,
+                program_id,
+            ),
+            &[
+                owner.clone(),
+                will_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"will", owner.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        let will_info = Will {
+            is_initialized: true,
+            owner: *owner.key,
+            heir: *heir.key,
+            window,
+            last_heartbeat: Clock::get()?.unix_timestamp,
+            bump_seed,
+        };
+        will_info.serialize(&mut &mut will_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_deposit(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let depositor = next_account_info(account_info_iter)?;
+
+        if !depositor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let will_account = next_account_info(account_info_iter)?;
+        Self::unpack_will(will_account, program_id)?;
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to deposit {} lamports...", amount);
+        invoke(
+            &system_instruction::transfer(depositor.key, will_account.key, amount),
+            &[
+                depositor.clone(),
+                will_account.clone(),
+                system_program.clone(),
+            ],
+        )
+    }
+
+    fn process_heartbeat(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let will_account = next_account_info(account_info_iter)?;
+        let mut will_info = Self::unpack_as_owner(owner, will_account, program_id)?;
+
+        Self::heartbeat(&mut will_info, will_account)
+    }
+
+    fn process_withdraw(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let will_account = next_account_info(account_info_iter)?;
+        let mut will_info = Self::unpack_as_owner(owner, will_account, program_id)?;
+
+        if amount > Self::funds(will_account)? {
+            return Err(InheritanceError::InsufficientFunds.into());
+        }
+
+        // the will account is owned by the program, so lamports are paid out by moving them directly
+        msg!("Withdrawing {} lamports...", amount);
+        **will_account.lamports.borrow_mut() -= amount;
+        **owner.lamports.borrow_mut() = owner.lamports()
+            .checked_add(amount)
+            .ok_or(InheritanceError::AmountOverflow)?;
+
+        Self::heartbeat(&mut will_info, will_account)
+    }
+
+    fn process_change_heir(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let will_account = next_account_info(account_info_iter)?;
+        let mut will_info = Self::unpack_as_owner(owner, will_account, program_id)?;
+
+        let heir = next_account_info(account_info_iter)?;
+        msg!("The new heir is {}", heir.key);
+        will_info.heir = *heir.key;
+
+        Self::heartbeat(&mut will_info, will_account)
+    }
+
+    fn process_sweep(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let heir = next_account_info(account_info_iter)?;
+
+        if !heir.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let will_account = next_account_info(account_info_iter)?;
+        let will_info = Self::unpack_will(will_account, program_id)?;
+        if will_info.heir != *heir.key {
+            return Err(InheritanceError::NotHeir.into());
+        }
+
+        // the owner can still save the funds at the very last second of the window
+        let sweepable_at = will_info.sweepable_at();
+        if Clock::get()?.unix_timestamp <= sweepable_at {
+            msg!("The owner has until {} to send a heartbeat", sweepable_at);
+            return Err(InheritanceError::OwnerStillAlive.into());
+        }
+
+        msg!("Sweeping {} lamports to the heir and closing the will...", will_account.lamports());
+        **heir.lamports.borrow_mut() = heir.lamports()
+            .checked_add(will_account.lamports())
+            .ok_or(InheritanceError::AmountOverflow)?;
+        **will_account.lamports.borrow_mut() = 0;
+        will_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_will(will_account: &AccountInfo, program_id: &Pubkey) -> Result<Will, ProgramError> {
+        if will_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let will_info = Will::try_from_slice(&will_account.data.borrow())?;
+        if !will_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(will_info)
+    }
+
+    fn unpack_as_owner(
+        owner: &AccountInfo,
+        will_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<Will, ProgramError> {
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let will_info = Self::unpack_will(will_account, program_id)?;
+        if will_info.owner != *owner.key {
+            return Err(InheritanceError::NotOwner.into());
+        }
+
+        Ok(will_info)
+    }
+
+    // every instruction signed by the owner proves they are alive, it restarts the window
+    fn heartbeat(will_info: &mut Will, will_account: &AccountInfo) -> ProgramResult {
+        will_info.last_heartbeat = Clock::get()?.unix_timestamp;
+        msg!("Heartbeat at {}, the heir can sweep after {}", will_info.last_heartbeat, will_info.sweepable_at());
+        will_info.serialize(&mut &mut will_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    // lamports held by the will on top of its rent
+    fn funds(will_account: &AccountInfo) -> Result<u64, ProgramError> {
+        let rent_lamports = Rent::get()?.minimum_balance(will_account.data_len());
+        Ok(will_account.lamports().saturating_sub(rent_lamports))
+    }
+}
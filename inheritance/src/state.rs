@@ -0,0 +1,22 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Will {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    pub heir: Pubkey,
+    // seconds the owner may stay silent before the heir can sweep the funds
+    pub window: i64,
+    // unix timestamp of the owner's last sign of life
+    pub last_heartbeat: i64,
+    pub bump_seed: u8,
+}
+
+impl Will {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1;
+
+    pub fn sweepable_at(&self) -> i64 {
+        self.last_heartbeat.saturating_add(self.window)
+    }
+}
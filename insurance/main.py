@@ -0,0 +1,303 @@
+import yaml
+import sys
+import json
+import time
+import hashlib
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+POOL_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'assessor': types.fixed_array(types.u8, 32),
+    'id': types.u64,
+    'premium': types.u64,
+    'period': types.i64,
+    'claim_cap': types.u64,
+    'member_count': types.u64,
+    'bump_seed': types.u8
+})
+
+MEMBER_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'pool': types.fixed_array(types.u8, 32),
+    'member': types.fixed_array(types.u8, 32),
+    'paid_until': types.i64,
+    'claim_count': types.u64,
+    'bump_seed': types.u8
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'insurance.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'insurance-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_pool_address(assessor: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("pool", encoding="utf8"), bytes(assessor), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def get_member_address(pool: PublicKey, member: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("member", encoding="utf8"), bytes(pool), bytes(member)], program_id)[0]
+
+def get_claim_address(pool: PublicKey, member: PublicKey, index: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("claim", encoding="utf8"), bytes(pool), bytes(member), index.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def cluster_time() -> int:
+    return client.get_block_time(client.get_slot()['result'])['result']
+
+def get_member(pool: PublicKey, member: PublicKey) -> dict:
+    account_info = client.get_account_info(get_member_address(pool, member))["result"]["value"]
+    return borsh.deserialize(MEMBER_SCHEMA, base64.b64decode(account_info["data"][0]))
+
+def create_pool(assessor: Keypair, id: int, premium: int, period: int, claim_cap: int) -> PublicKey:
+    pool = get_pool_address(assessor.public_key, id)
+    print(f"\nCreating pool {pool}, {premium} lamports cover {period} seconds, a claim pays at most {claim_cap} lamports")
+    send(
+        assessor,
+        (0).to_bytes(1, byteorder='little')
+        + id.to_bytes(8, byteorder='little')
+        + premium.to_bytes(8, byteorder='little')
+        + period.to_bytes(8, byteorder='little', signed=True)
+        + claim_cap.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=assessor.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return pool
+
+def premium_keys(member: Keypair, pool: PublicKey) -> list:
+    return [
+        AccountMeta(pubkey=member.public_key, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=get_member_address(pool, member.public_key), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+    ]
+
+def join(member: Keypair, pool: PublicKey):
+    print(f"\n{member.public_key} joins the pool")
+    send(member, (1).to_bytes(1, byteorder='little'), premium_keys(member, pool))
+    print(f"Covered until {get_member(pool, member.public_key)['paid_until']}")
+
+def pay_premium(member: Keypair, pool: PublicKey):
+    print(f"\n{member.public_key} pays the premium")
+    send(member, (2).to_bytes(1, byteorder='little'), premium_keys(member, pool))
+    print(f"Covered until {get_member(pool, member.public_key)['paid_until']}")
+
+def file_claim(member: Keypair, pool: PublicKey, amount: int, evidence: bytes) -> PublicKey:
+    # the claim's address depends on the number of claims the member filed before
+    claim = get_claim_address(pool, member.public_key, get_member(pool, member.public_key)['claim_count'])
+    print(f"\n{member.public_key} files claim {claim} over {amount} lamports")
+    send(
+        member,
+        (3).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little') + hashlib.sha256(evidence).digest(),
+        [
+            AccountMeta(pubkey=member.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=pool, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_member_address(pool, member.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=claim, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return claim
+
+def assess_claim(assessor: Keypair, pool: PublicKey, claim: PublicKey, member: PublicKey, approve: bool):
+    print(f"\n{'Approving' if approve else 'Rejecting'} claim {claim}")
+    send(
+        assessor,
+        (4).to_bytes(1, byteorder='little') + (1 if approve else 0).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=assessor.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=claim, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=member, is_signer=False, is_writable=True),
+        ],
+    )
+
+def show_pool(pool: PublicKey):
+    account_info = client.get_account_info(pool)["result"]["value"]
+    pool_info = borsh.deserialize(POOL_SCHEMA, base64.b64decode(account_info["data"][0]))
+    rent = client.get_minimum_balance_for_rent_exemption(len(base64.b64decode(account_info["data"][0])))["result"]
+    print(f"Pool has {pool_info['member_count']} members and {account_info['lamports'] - rent} lamports to pay claims with")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+
+    premium = LAMPORTS_PER_SOL // 10
+    period = 30
+    pool = create_pool(payer, int(time.time()), premium, period, LAMPORTS_PER_SOL // 5)
+
+    join(alice, pool)
+    join(bob, pool)
+    show_pool(pool)
+
+    try:
+        file_claim(alice, pool, LAMPORTS_PER_SOL, b"a scratch on the car")
+    except Exception as e:
+        print(f"Claiming more than the cap fails as expected: {e}")
+
+    claim = file_claim(alice, pool, LAMPORTS_PER_SOL // 8, b"a broken window")
+    try:
+        assess_claim(bob, pool, claim, alice.public_key, True)
+    except Exception as e:
+        print(f"Approving without being the assessor fails as expected: {e}")
+    assess_claim(payer, pool, claim, alice.public_key, True)
+    show_pool(pool)
+
+    claim = file_claim(bob, pool, LAMPORTS_PER_SOL // 8, b"a lost umbrella")
+    assess_claim(payer, pool, claim, bob.public_key, False)
+    show_pool(pool)
+
+    print("Waiting for bob's coverage to run out...")
+    paid_until = get_member(pool, bob.public_key)['paid_until']
+    while cluster_time() <= paid_until:
+        time.sleep(1)
+
+    try:
+        file_claim(bob, pool, LAMPORTS_PER_SOL // 8, b"a flooded basement")
+    except Exception as e:
+        print(f"Claiming without coverage fails as expected: {e}")
+
+    pay_premium(bob, pool)
+    claim = file_claim(bob, pool, LAMPORTS_PER_SOL // 8, b"a flooded basement")
+    assess_claim(payer, pool, claim, bob.public_key, True)
+    show_pool(pool)
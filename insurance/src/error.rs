@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum InsuranceError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Pool Address")]
+    InvalidPoolAddress,
+    #[error("Invalid Member Address")]
+    InvalidMemberAddress,
+    #[error("Invalid Claim Address")]
+    InvalidClaimAddress,
+    #[error("Invalid Terms")]
+    InvalidTerms,
+    #[error("Not Covered")]
+    NotCovered,
+    #[error("Claim Above Cap")]
+    ClaimAboveCap,
+    #[error("Not Assessor")]
+    NotAssessor,
+    #[error("Insufficient Pool Funds")]
+    InsufficientPoolFunds,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<InsuranceError> for ProgramError {
+    fn from(e: InsuranceError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
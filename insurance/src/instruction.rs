@@ -0,0 +1,116 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::InsuranceError::InvalidInstruction;
+
+pub enum InsuranceInstruction {
+
+    // Creates a pool in a PDA derived from ["pool", assessor, id].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The assessor
+    // 1. `[writable]` The pool PDA
+    // 2. `[]` The system program
+    CreatePool {
+        id: u64,
+        premium: u64,
+        period: i64,
+        claim_cap: u64,
+    },
+
+    // Joins a pool and pays the premium of the first period. The membership is stored in a PDA derived from
+    // ["member", pool, member].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The member
+    // 1. `[writable]` The pool PDA
+    // 2. `[writable]` The member PDA
+    // 3. `[]` The system program
+    Join,
+
+    // Pays the premium of one more period. A member whose coverage ran out is covered again from now on.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The member
+    // 1. `[writable]` The pool PDA
+    // 2. `[writable]` The member PDA
+    // 3. `[]` The system program
+    PayPremium,
+
+    // Files a claim while the member is covered. The claim is stored in a PDA derived from
+    // ["claim", pool, member, index], the index is the number of claims the member filed before.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The member
+    // 1. `[]` The pool PDA
+    // 2. `[writable]` The member PDA
+    // 3. `[writable]` The claim PDA
+    // 4. `[]` The system program
+    FileClaim {
+        amount: u64,
+        evidence_hash: [u8; 32],
+    },
+
+    // Approves or rejects a claim. An approved claim is paid out of the pool. Either way the claim is closed and
+    // its rent goes back to the member.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The assessor
+    // 1. `[writable]` The pool PDA
+    // 2. `[writable]` The claim PDA
+    // 3. `[writable]` The member who filed the claim
+    AssessClaim {
+        approve: bool,
+    },
+}
+
+impl InsuranceInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreatePool {
+                id: Self::unpack_u64(rest, 0)?,
+                premium: Self::unpack_u64(rest, 8)?,
+                period: Self::unpack_u64(rest, 16)? as i64,
+                claim_cap: Self::unpack_u64(rest, 24)?,
+            },
+            1 => Self::Join,
+            2 => Self::PayPremium,
+            3 => Self::FileClaim {
+                amount: Self::unpack_u64(rest, 0)?,
+                evidence_hash: rest
+                    .get(8..40)
+                    .and_then(|slice| slice.try_into().ok())
+                    .ok_or(InvalidInstruction)?,
+            },
+            4 => Self::AssessClaim {
+                approve: match rest.first() {
+                    Some(0) => false,
+                    Some(1) => true,
+                    _ => return Err(InvalidInstruction.into()),
+                },
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
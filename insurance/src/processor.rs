@@ -0,0 +1,428 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::InsuranceInstruction,
+    error::InsuranceError,
+    state::{Claim, Member, Pool},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = InsuranceInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            InsuranceInstruction::CreatePool { id, premium, period, claim_cap } => {
+                msg!("Instruction: CreatePool");
+                Self::process_create_pool(accounts, id, premium, period, claim_cap, program_id)
+            },
+            InsuranceInstruction::Join => {
+                msg!("Instruction: Join");
+                Self::process_join(accounts, program_id)
+            },
+            InsuranceInstruction::PayPremium => {
+                msg!("Instruction: PayPremium");
+                Self::process_pay_premium(accounts, program_id)
+            },
+            InsuranceInstruction::FileClaim { amount, evidence_hash } => {
+                msg!("Instruction: FileClaim");
+                Self::process_file_claim(accounts, amount, evidence_hash, program_id)
+            },
+            InsuranceInstruction::AssessClaim { approve } => {
+                msg!("Instruction: AssessClaim");
+                Self::process_assess_claim(accounts, approve, program_id)
+            }
+        }
+    }
+
+    fn process_create_pool(
+        accounts: &[AccountInfo],
+        id: u64,
+        premium: u64,
+        period: i64,
+        claim_cap: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if premium == 0 || period <= 0 || claim_cap == 0 {
+            return Err(InsuranceError::InvalidTerms.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let assessor = next_account_info(account_info_iter)?;
+
+        if !assessor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (pool_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"pool", assessor.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if pool_pda != *pool_account.key {
+            return Err(InsuranceError::InvalidPoolAddress.into());
+        }
+
+        if !pool_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating pool account...");
+        Self::create_pda_account(
+            assessor,
+            pool_account,
+            system_program,
+            Pool::LEN,
+            program_id,
+            &[b"pool", assessor.key.as_ref(), &id_bytes, &[bump_seed]],
+        )?;
+
+        let pool_info = Pool {
+            is_initialized: true,
+            assessor: *assessor.key,
+            id,
+            premium,
+            period,
+            claim_cap,
+            member_count: 0,
+            bump_seed,
+        };
+        pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_join(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let member = next_account_info(account_info_iter)?;
+
+        if !member.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let mut pool_info = Self::unpack_pool(pool_account, program_id)?;
+
+        let member_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (member_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"member", pool_account.key.as_ref(), member.key.as_ref()],
+            program_id,
+        );
+        if member_pda != *member_account.key {
+            return Err(InsuranceError::InvalidMemberAddress.into());
+        }
+
+        if !member_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating member account...");
+        Self::create_pda_account(
+            member,
+            member_account,
+            system_program,
+            Member::LEN,
+            program_id,
+            &[b"member", pool_account.key.as_ref(), member.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let mut member_info = Member {
+            is_initialized: true,
+            pool: *pool_account.key,
+            member: *member.key,
+            paid_until: 0,
+            claim_count: 0,
+            bump_seed,
+        };
+        Self::pay_premium(&pool_info, &mut member_info, member, pool_account, system_program)?;
+
+        pool_info.member_count += 1;
+        pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+        member_info.serialize(&mut &mut member_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_pay_premium(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let member = next_account_info(account_info_iter)?;
+
+        if !member.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let pool_info = Self::unpack_pool(pool_account, program_id)?;
+
+        let member_account = next_account_info(account_info_iter)?;
+        let mut member_info = Self::unpack_member(member_account, pool_account, member, program_id)?;
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        Self::pay_premium(&pool_info, &mut member_info, member, pool_account, system_program)?;
+        member_info.serialize(&mut &mut member_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_file_claim(
+        accounts: &[AccountInfo],
+        amount: u64,
+        evidence_hash: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let member = next_account_info(account_info_iter)?;
+
+        if !member.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let pool_info = Self::unpack_pool(pool_account, program_id)?;
+
+        let member_account = next_account_info(account_info_iter)?;
+        let mut member_info = Self::unpack_member(member_account, pool_account, member, program_id)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        if now >= member_info.paid_until {
+            return Err(InsuranceError::NotCovered.into());
+        }
+        if amount == 0 || amount > pool_info.claim_cap {
+            return Err(InsuranceError::ClaimAboveCap.into());
+        }
+
+        let claim_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let index_bytes = member_info.claim_count.to_le_bytes();
+        let (claim_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"claim", pool_account.key.as_ref(), member.key.as_ref(), &index_bytes],
+            program_id,
+        );
+        if claim_pda != *claim_account.key {
+            return Err(InsuranceError::InvalidClaimAddress.into());
+        }
+
+        msg!("Filing claim {} over {} lamports...", member_info.claim_count, amount);
+        Self::create_pda_account(
+            member,
+            claim_account,
+            system_program,
+            Claim::LEN,
+            program_id,
+            &[b"claim", pool_account.key.as_ref(), member.key.as_ref(), &index_bytes, &[bump_seed]],
+        )?;
+
+        let claim_info = Claim {
+            is_initialized: true,
+            pool: *pool_account.key,
+            member: *member.key,
+            index: member_info.claim_count,
+            amount,
+            evidence_hash,
+            filed_at: now,
+        };
+        claim_info.serialize(&mut &mut claim_account.data.borrow_mut()[..])?;
+
+        member_info.claim_count += 1;
+        member_info.serialize(&mut &mut member_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_assess_claim(
+        accounts: &[AccountInfo],
+        approve: bool,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let assessor = next_account_info(account_info_iter)?;
+
+        if !assessor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let pool_info = Self::unpack_pool(pool_account, program_id)?;
+        if pool_info.assessor != *assessor.key {
+            return Err(InsuranceError::NotAssessor.into());
+        }
+
+        let claim_account = next_account_info(account_info_iter)?;
+        if claim_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let claim_info = Claim::try_from_slice(&claim_account.data.borrow())?;
+        if !claim_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if claim_info.pool != *pool_account.key {
+            return Err(InsuranceError::InvalidClaimAddress.into());
+        }
+
+        let member = next_account_info(account_info_iter)?;
+        if claim_info.member != *member.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if approve {
+            // the rent of the pool must stay, only the premiums can be paid out
+            let funds = pool_account.lamports().saturating_sub(Rent::get()?.minimum_balance(Pool::LEN));
+            if claim_info.amount > funds {
+                return Err(InsuranceError::InsufficientPoolFunds.into());
+            }
+
+            msg!("Claim approved, paying out {} lamports...", claim_info.amount);
+            **pool_account.lamports.borrow_mut() -= claim_info.amount;
+            **member.lamports.borrow_mut() = member.lamports()
+                .checked_add(claim_info.amount)
+                .ok_or(InsuranceError::AmountOverflow)?;
+        } else {
+            msg!("Claim rejected");
+        }
+
+        msg!("Closing the claim account...");
+        **member.lamports.borrow_mut() = member.lamports()
+            .checked_add(claim_account.lamports())
+            .ok_or(InsuranceError::AmountOverflow)?;
+        **claim_account.lamports.borrow_mut() = 0;
+        claim_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_pool(pool_account: &AccountInfo, program_id: &Pubkey) -> Result<Pool, ProgramError> {
+        if pool_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let pool_info = Pool::try_from_slice(&pool_account.data.borrow())?;
+        if !pool_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(pool_info)
+    }
+
+    fn unpack_member(
+        member_account: &AccountInfo,
+        pool_account: &AccountInfo,
+        member: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<Member, ProgramError> {
+        if member_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let member_info = Member::try_from_slice(&member_account.data.borrow())?;
+        if !member_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if member_info.pool != *pool_account.key || member_info.member != *member.key {
+            return Err(InsuranceError::InvalidMemberAddress.into());
+        }
+
+        Ok(member_info)
+    }
+
+    // A period paid ahead is added to the end of the coverage. After a gap the coverage starts again from now,
+    // the time without coverage isn't paid for afterwards.
+    fn pay_premium<'a>(
+        pool_info: &Pool,
+        member_info: &mut Member,
+        member: &AccountInfo<'a>,
+        pool_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        msg!("Calling the system program to pay the premium of {} lamports...", pool_info.premium);
+        invoke(
+            &system_instruction::transfer(member.key, pool_account.key, pool_info.premium),
+            &[
+                member.clone(),
+                pool_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        member_info.paid_until = member_info.paid_until
+            .max(Clock::get()?.unix_timestamp)
+            .checked_add(pool_info.period)
+            .ok_or(InsuranceError::AmountOverflow)?;
+        msg!("Covered until {}", member_info.paid_until);
+
+        Ok(())
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    //
+    // The next claim of a member is at an address anyone can compute, and anyone can send lamports to it.
+    // create_account refuses an account which holds lamports already, which would keep the member from filing any
+    // further claim. Such an account is topped up to the rent instead, then allocated and assigned with the signature
+    // of the PDA.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let accounts = [
+            payer.clone(),
+            new_account.clone(),
+            system_program.clone(),
+        ];
+
+        if new_account.lamports() == 0 {
+            let create_account_ix = system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            );
+            return invoke_signed(&create_account_ix, &accounts, &[signer_seeds]);
+        }
+
+        let shortfall = lamports.saturating_sub(new_account.lamports());
+        if shortfall > 0 {
+            invoke(&system_instruction::transfer(payer.key, new_account.key, shortfall), &accounts)?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(new_account.key, space as u64),
+            &accounts,
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(new_account.key, program_id),
+            &accounts,
+            &[signer_seeds],
+        )
+    }
+}
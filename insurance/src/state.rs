@@ -0,0 +1,58 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// The pool PDA holds the premiums of all members on top of its rent, and pays the approved claims out of them
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Pool {
+    pub is_initialized: bool,
+    // created the pool, and is the only account which can approve or reject claims
+    pub assessor: Pubkey,
+    pub id: u64,
+    // lamports a member pays for every period of coverage
+    pub premium: u64,
+    // length of a period in seconds
+    pub period: i64,
+    // most lamports a single claim can pay out
+    pub claim_cap: u64,
+    pub member_count: u64,
+    pub bump_seed: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 8 + 8 + 1;
+}
+
+// A member of a pool, in a PDA derived from ["member", pool, member]
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Member {
+    pub is_initialized: bool,
+    pub pool: Pubkey,
+    pub member: Pubkey,
+    // unix timestamp, the member is covered until then
+    pub paid_until: i64,
+    // number of claims filed so far, the next claim uses it in its address
+    pub claim_count: u64,
+    pub bump_seed: u8,
+}
+
+impl Member {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1;
+}
+
+// A claim waiting for the assessor, in a PDA derived from ["claim", pool, member, index]. It is closed once it is
+// assessed, whether it is paid or not.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Claim {
+    pub is_initialized: bool,
+    pub pool: Pubkey,
+    pub member: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+    // hash of the evidence the member hands to the assessor off chain, photos of the damage for example
+    pub evidence_hash: [u8; 32],
+    pub filed_at: i64,
+}
+
+impl Claim {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 32 + 8;
+}
@@ -0,0 +1,288 @@
+import yaml
+import sys
+import json
+import time
+import hashlib
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+INVOICE_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'merchant': types.fixed_array(types.u8, 32),
+    'payer': types.fixed_array(types.u8, 32),
+    'id': types.u64,
+    'amount': types.u64,
+    'memo_hash': types.fixed_array(types.u8, 32),
+    'due_ts': types.i64,
+    'status': types.u8,
+    'paid_ts': types.i64,
+    'bump_seed': types.u8
+})
+
+SETTLEMENT_EVENT_SCHEMA = borsh.schema({
+    'invoice': types.fixed_array(types.u8, 32),
+    'merchant': types.fixed_array(types.u8, 32),
+    'payer': types.fixed_array(types.u8, 32),
+    'amount': types.u64,
+    'memo_hash': types.fixed_array(types.u8, 32),
+    'paid_ts': types.i64,
+    'late': types.u8
+})
+
+INVOICE_STATUS = ["Open", "Paid", "Overdue"]
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'invoice.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'invoice-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_invoice_address(merchant: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("invoice", encoding="utf8"), bytes(merchant), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list) -> str:
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+    return tx["result"]
+
+def cluster_time() -> int:
+    return client.get_block_time(client.get_slot()['result'])['result']
+
+def issue_invoice(merchant: Keypair, payer: PublicKey, id: int, amount: int, due_ts: int, memo: str) -> PublicKey:
+    invoice = get_invoice_address(merchant.public_key, id)
+    print(f"\nIssuing invoice {invoice} over {amount} lamports for \"{memo}\"")
+    send(
+        merchant,
+        (0).to_bytes(1, byteorder='little')
+        + id.to_bytes(8, byteorder='little')
+        + amount.to_bytes(8, byteorder='little')
+        + due_ts.to_bytes(8, byteorder='little', signed=True)
+        + hashlib.sha256(memo.encode("utf8")).digest(),
+        [
+            AccountMeta(pubkey=merchant.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=payer, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=invoice, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return invoice
+
+def pay_invoice(payer: Keypair, invoice: PublicKey, merchant: PublicKey, amount: int):
+    print(f"\nPaying {amount} lamports")
+    signature = send(
+        payer,
+        (1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=payer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=invoice, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=merchant, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    print_settlement_events(signature)
+
+def mark_overdue(caller: Keypair, invoice: PublicKey):
+    print("\nMarking the invoice as overdue")
+    send(
+        caller,
+        (2).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=invoice, is_signer=False, is_writable=True),
+        ],
+    )
+
+def close_invoice(merchant: Keypair, invoice: PublicKey):
+    print(f"\nClosing invoice {invoice}")
+    send(
+        merchant,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=merchant.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=invoice, is_signer=False, is_writable=True),
+        ],
+    )
+
+def print_settlement_events(signature: str):
+    # sol_log_data shows up as "Program data: <base64 field> <base64 field>" in the logs
+    logs = client.get_transaction(signature)["result"]["meta"]["logMessages"]
+    for log in logs:
+        if not log.startswith("Program data: "):
+            continue
+        fields = [base64.b64decode(field) for field in log[len("Program data: "):].split(" ")]
+        if fields[0] == b"settlement":
+            event = borsh.deserialize(SETTLEMENT_EVENT_SCHEMA, fields[1])
+            print(f"Settlement event: {PublicKey(event['payer'])} paid {event['amount']} lamports at {event['paid_ts']}, late: {bool(event['late'])}")
+
+def show_invoice(invoice: PublicKey):
+    account_info = client.get_account_info(invoice)["result"]["value"]
+    invoice_info = borsh.deserialize(INVOICE_SCHEMA, base64.b64decode(account_info["data"][0]))
+    print(f"Invoice {invoice_info['id']} over {invoice_info['amount']} lamports is {INVOICE_STATUS[invoice_info['status']]}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    customer = setup_user_keypair()
+
+    now = cluster_time()
+    invoice = issue_invoice(payer, customer.public_key, 0, LAMPORTS_PER_SOL // 10, now + 3600, "10 hours of consulting")
+    show_invoice(invoice)
+
+    try:
+        pay_invoice(customer, invoice, payer.public_key, LAMPORTS_PER_SOL // 20)
+    except Exception as e:
+        print(f"Paying the wrong amount fails as expected: {e}")
+
+    pay_invoice(customer, invoice, payer.public_key, LAMPORTS_PER_SOL // 10)
+    show_invoice(invoice)
+    try:
+        pay_invoice(customer, invoice, payer.public_key, LAMPORTS_PER_SOL // 10)
+    except Exception as e:
+        print(f"Paying twice fails as expected: {e}")
+    close_invoice(payer, invoice)
+
+    # the second invoice is due in a few seconds and paid late
+    due_ts = cluster_time() + 5
+    invoice = issue_invoice(payer, customer.public_key, 1, LAMPORTS_PER_SOL // 20, due_ts, "domain renewal")
+    try:
+        mark_overdue(payer, invoice)
+    except Exception as e:
+        print(f"Marking the invoice overdue before the due date fails as expected: {e}")
+
+    print("Waiting for the due date...")
+    while cluster_time() <= due_ts:
+        time.sleep(1)
+
+    mark_overdue(payer, invoice)
+    show_invoice(invoice)
+    pay_invoice(customer, invoice, payer.public_key, LAMPORTS_PER_SOL // 20)
+    show_invoice(invoice)
+    close_invoice(payer, invoice)
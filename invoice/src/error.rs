@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum InvoiceError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Invoice Address")]
+    InvalidInvoiceAddress,
+    #[error("Invalid Due Date")]
+    InvalidDueDate,
+    #[error("Not Merchant")]
+    NotMerchant,
+    #[error("Not Payer")]
+    NotPayer,
+    #[error("Wrong Amount")]
+    WrongAmount,
+    #[error("Already Paid")]
+    AlreadyPaid,
+    #[error("Not Overdue")]
+    NotOverdue,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<InvoiceError> for ProgramError {
+    fn from(e: InvoiceError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,87 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::InvoiceError::InvalidInstruction;
+
+pub enum InvoiceInstruction {
+
+    // Issues an invoice to a payer in a PDA derived from ["invoice", merchant, id].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The merchant
+    // 1. `[]` The payer
+    // 2. `[writable]` The invoice PDA
+    // 3. `[]` The system program
+    IssueInvoice {
+        id: u64,
+        amount: u64,
+        due_ts: i64,
+        memo_hash: [u8; 32],
+    },
+
+    // Pays an open or overdue invoice. The amount has to be the amount of the invoice, it is transferred
+    // straight to the merchant.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The payer
+    // 1. `[writable]` The invoice PDA
+    // 2. `[writable]` The merchant
+    // 3. `[]` The system program
+    PayInvoice {
+        amount: u64,
+    },
+
+    // Marks an open invoice as overdue once its due date has passed. Anybody may call it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The invoice PDA
+    MarkOverdue,
+
+    // Closes an invoice and returns the rent to the merchant. Closing an unpaid invoice cancels it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The merchant
+    // 1. `[writable]` The invoice PDA
+    CloseInvoice,
+}
+
+impl InvoiceInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::IssueInvoice {
+                id: Self::unpack_u64(rest, 0)?,
+                amount: Self::unpack_u64(rest, 8)?,
+                due_ts: Self::unpack_u64(rest, 16)? as i64,
+                memo_hash: rest
+                    .get(24..56)
+                    .and_then(|slice| slice.try_into().ok())
+                    .ok_or(InvalidInstruction)?,
+            },
+            1 => Self::PayInvoice {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::MarkOverdue,
+            3 => Self::CloseInvoice,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,238 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    log::sol_log_data,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::InvoiceInstruction,
+    error::InvoiceError,
+    state::{Invoice, InvoiceStatus, SettlementEvent},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = InvoiceInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            InvoiceInstruction::IssueInvoice { id, amount, due_ts, memo_hash } => {
+                msg!("Instruction: IssueInvoice");
+                Self::process_issue_invoice(accounts, id, amount, due_ts, memo_hash, program_id)
+            },
+            InvoiceInstruction::PayInvoice { amount } => {
+                msg!("Instruction: PayInvoice");
+                Self::process_pay_invoice(accounts, amount, program_id)
+            },
+            InvoiceInstruction::MarkOverdue => {
+                msg!("Instruction: MarkOverdue");
+                Self::process_mark_overdue(accounts, program_id)
+            },
+            InvoiceInstruction::CloseInvoice => {
+                msg!("Instruction: CloseInvoice");
+                Self::process_close_invoice(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_issue_invoice(
+        accounts: &[AccountInfo],
+        id: u64,
+        amount: u64,
+        due_ts: i64,
+        memo_hash: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if due_ts <= Clock::get()?.unix_timestamp {
+            return Err(InvoiceError::InvalidDueDate.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let merchant = next_account_info(account_info_iter)?;
+
+        if !merchant.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let payer = next_account_info(account_info_iter)?;
+        let invoice_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (invoice_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"invoice", merchant.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if invoice_pda != *invoice_account.key {
+            return Err(InvoiceError::InvalidInvoiceAddress.into());
+        }
+
+        if !invoice_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating invoice account for {} lamports...", amount);
+        invoke_signed(
+            &system_instruction::create_account(
+                merchant.key,
+                invoice_account.key,
+                Rent::get()?.minimum_balance(Invoice::LEN),
+                Invoice::LEN as u64,
+                program_id,
+            ),
+            &[
+                merchant.clone(),
+                invoice_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"invoice", merchant.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        let invoice_info = Invoice {
+            is_initialized: true,
+            merchant: *merchant.key,
+            payer: *payer.key,
+            id,
+            amount,
+            memo_hash,
+            due_ts,
+            status: InvoiceStatus::Open,
+            paid_ts: 0,
+            bump_seed,
+        };
+        invoice_info.serialize(&mut &mut invoice_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_pay_invoice(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer = next_account_info(account_info_iter)?;
+
+        if !payer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let invoice_account = next_account_info(account_info_iter)?;
+        let mut invoice_info = Self::unpack_invoice(invoice_account, program_id)?;
+        if invoice_info.payer != *payer.key {
+            return Err(InvoiceError::NotPayer.into());
+        }
+        if invoice_info.status == InvoiceStatus::Paid {
+            return Err(InvoiceError::AlreadyPaid.into());
+        }
+        // the payer states the amount they agree to pay, so an invoice can't be paid by accident
+        if amount != invoice_info.amount {
+            return Err(InvoiceError::WrongAmount.into());
+        }
+
+        let merchant = next_account_info(account_info_iter)?;
+        if *merchant.key != invoice_info.merchant {
+            return Err(InvoiceError::NotMerchant.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to pay {} lamports to the merchant...", amount);
+        invoke(
+            &system_instruction::transfer(payer.key, merchant.key, amount),
+            &[
+                payer.clone(),
+                merchant.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        // an invoice which hasn't been marked overdue yet can still be late
+        let now = Clock::get()?.unix_timestamp;
+        let late = invoice_info.status == InvoiceStatus::Overdue || now > invoice_info.due_ts;
+        invoice_info.status = InvoiceStatus::Paid;
+        invoice_info.paid_ts = now;
+        invoice_info.serialize(&mut &mut invoice_account.data.borrow_mut()[..])?;
+
+        let event = SettlementEvent {
+            invoice: *invoice_account.key,
+            merchant: invoice_info.merchant,
+            payer: *payer.key,
+            amount,
+            memo_hash: invoice_info.memo_hash,
+            paid_ts: now,
+            late,
+        };
+        sol_log_data(&[b"settlement", &event.try_to_vec()?]);
+
+        Ok(())
+    }
+
+    fn process_mark_overdue(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let invoice_account = next_account_info(account_info_iter)?;
+        let mut invoice_info = Self::unpack_invoice(invoice_account, program_id)?;
+
+        if invoice_info.status != InvoiceStatus::Open || Clock::get()?.unix_timestamp <= invoice_info.due_ts {
+            return Err(InvoiceError::NotOverdue.into());
+        }
+
+        msg!("Invoice {} is overdue", invoice_info.id);
+        invoice_info.status = InvoiceStatus::Overdue;
+        invoice_info.serialize(&mut &mut invoice_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_close_invoice(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let merchant = next_account_info(account_info_iter)?;
+
+        if !merchant.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let invoice_account = next_account_info(account_info_iter)?;
+        let invoice_info = Self::unpack_invoice(invoice_account, program_id)?;
+        if invoice_info.merchant != *merchant.key {
+            return Err(InvoiceError::NotMerchant.into());
+        }
+
+        msg!("Closing the invoice account, status: {:?}...", invoice_info.status);
+        **merchant.lamports.borrow_mut() = merchant.lamports()
+            .checked_add(invoice_account.lamports())
+            .ok_or(InvoiceError::AmountOverflow)?;
+        **invoice_account.lamports.borrow_mut() = 0;
+        invoice_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_invoice(invoice_account: &AccountInfo, program_id: &Pubkey) -> Result<Invoice, ProgramError> {
+        if invoice_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let invoice_info = Invoice::try_from_slice(&invoice_account.data.borrow())?;
+        if !invoice_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(invoice_info)
+    }
+}
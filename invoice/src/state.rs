@@ -0,0 +1,44 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvoiceStatus {
+    Open,
+    Paid,
+    Overdue,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Invoice {
+    pub is_initialized: bool,
+    pub merchant: Pubkey,
+    // the only account which can settle the invoice
+    pub payer: Pubkey,
+    pub id: u64,
+    // lamports, the payment has to match exactly
+    pub amount: u64,
+    // hash of the memo the merchant keeps off chain, the line items for example
+    pub memo_hash: [u8; 32],
+    pub due_ts: i64,
+    pub status: InvoiceStatus,
+    // unix timestamp of the payment, 0 while it is unpaid
+    pub paid_ts: i64,
+    pub bump_seed: u8,
+}
+
+impl Invoice {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 32 + 8 + 1 + 8 + 1;
+}
+
+// Emitted with sol_log_data when an invoice is paid
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SettlementEvent {
+    pub invoice: Pubkey,
+    pub merchant: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub memo_hash: [u8; 32],
+    pub paid_ts: i64,
+    // paid after the due date
+    pub late: bool,
+}
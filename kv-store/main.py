@@ -0,0 +1,220 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+KV_ENTRY_SCHEMA = borsh.schema({
+    'owner': types.fixed_array(types.u8, 32),
+    'bump_seed': types.u8,
+    'key': types.dynamic_array(types.u8),
+    'value': types.dynamic_array(types.u8)
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'kv_store.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'kv_store-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def get_entry_address(owner: PublicKey, key: bytes) -> PublicKey:
+    return PublicKey.find_program_address([bytes("kv", encoding="utf8"), bytes(owner), key], program_id)[0]
+
+def encode_key(key: bytes) -> bytes:
+    # the key is prefixed with a single length byte
+    return len(key).to_bytes(1, byteorder='little') + key
+
+def put(owner: Keypair, key: bytes, value: bytes):
+    print(f"\nPut {key} => {value}")
+    put_ix = TransactionInstruction(
+        data=bytes(1) + encode_key(key) + len(value).to_bytes(4, byteorder='little') + value,
+        keys=[
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_entry_address(owner.public_key, key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(put_ix), owner)
+    client.confirm_transaction(tx["result"])
+
+def get(owner: PublicKey, key: bytes):
+    # Get doesn't change any state, so a simulation is enough to receive the return data
+    get_ix = TransactionInstruction(
+        data=(1).to_bytes(1, byteorder='little') + encode_key(key),
+        keys=[
+            AccountMeta(pubkey=owner, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_entry_address(owner, key), is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    block_hash = client.get_recent_blockhash()["result"]["value"]["blockhash"]
+    tx = Transaction(recent_blockhash=block_hash, fee_payer=payer.public_key).add(get_ix)
+    tx.sign(payer)
+    result = client.simulate_transaction(tx)["result"]["value"]
+    if result["err"] is not None:
+        print(f"Get {key} failed: {result['err']}")
+        return None
+    return_data = result.get("returnData")
+    if return_data is None:
+        print("RPC node doesn't report return data, reading the account instead")
+        account_info = client.get_account_info(get_entry_address(owner, key))["result"]["value"]
+        return bytes(borsh.deserialize(KV_ENTRY_SCHEMA, base64.b64decode(account_info["data"][0]))['value'])
+    return base64.b64decode(return_data["data"][0])
+
+def delete(owner: Keypair, key: bytes):
+    print(f"\nDelete {key}")
+    delete_ix = TransactionInstruction(
+        data=(2).to_bytes(1, byteorder='little') + encode_key(key),
+        keys=[
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_entry_address(owner.public_key, key), is_signer=False, is_writable=True),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(delete_ix), owner)
+    client.confirm_transaction(tx["result"])
+
+def top_up_rent(payer: Keypair, owner: PublicKey, key: bytes):
+    print(f"\nTop up the rent of {key}")
+    top_up_ix = TransactionInstruction(
+        data=(3).to_bytes(1, byteorder='little') + encode_key(key),
+        keys=[
+            AccountMeta(pubkey=payer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=owner, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_entry_address(owner, key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(top_up_ix), payer)
+    client.confirm_transaction(tx["result"])
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    key = b"greeting"
+    put(payer, key, b"hello")
+    print(f"Get {key} => {get(payer.public_key, key)}")
+    # a longer value makes the program realloc the entry
+    put(payer, key, b"hello, this value is a lot longer than the first one")
+    print(f"Get {key} => {get(payer.public_key, key)}")
+    # Put keeps the entry rent exempt, so this transfers nothing, but anybody may pay for an entry which isn't
+    top_up_rent(payer, payer.public_key, key)
+    delete(payer, key)
+    print(f"Entry account after delete: {client.get_account_info(get_entry_address(payer.public_key, key))['result']['value']}")
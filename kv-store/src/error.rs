@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum KvError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Key Too Long")]
+    KeyTooLong,
+    #[error("Value Too Long")]
+    ValueTooLong,
+    #[error("Invalid Entry Address")]
+    InvalidEntryAddress,
+    #[error("Entry Not Found")]
+    EntryNotFound,
+}
+
+impl From<KvError> for ProgramError {
+    fn from(e: KvError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,100 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::KvError::InvalidInstruction;
+
+pub enum KvInstruction {
+
+    // Stores a value under a key. The entry lives in a PDA derived from ["kv", owner, key].
+    // If the entry doesn't exist yet it is created, otherwise it is resized to the new value
+    // and the rent difference is paid by or refunded to the owner.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the entry
+    // 1. `[writable]` The entry PDA
+    // 2. `[]` The system program
+    Put {
+        key: Vec<u8>,
+        value: Vec<u8>,
+    },
+
+    // Reads the value stored under a key and hands it back as return data.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[]` The owner of the entry
+    // 1. `[]` The entry PDA
+    Get {
+        key: Vec<u8>,
+    },
+
+    // Removes an entry and sends its lamports back to the owner.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the entry
+    // 1. `[writable]` The entry PDA
+    Delete {
+        key: Vec<u8>,
+    },
+
+    // Tops up the lamports of an entry to the rent exempt minimum of its current size. Anybody can pay,
+    // the entry stays untouched otherwise. Nothing is transferred if it is rent exempt already.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The payer of the missing lamports
+    // 1. `[]` The owner of the entry
+    // 2. `[writable]` The entry PDA
+    // 3. `[]` The system program
+    TopUpRent {
+        key: Vec<u8>,
+    },
+}
+
+impl KvInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+        let (key, rest) = Self::unpack_key(rest)?;
+
+        Ok(match tag {
+            0 => Self::Put {
+                key,
+                value: Self::unpack_value(rest)?,
+            },
+            1 => Self::Get { key },
+            2 => Self::Delete { key },
+            3 => Self::TopUpRent { key },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    // the key is prefixed with a single length byte, since it can't be longer than a seed anyway
+    fn unpack_key(input: &[u8]) -> Result<(Vec<u8>, &[u8]), ProgramError> {
+        let (len, rest) = input.split_first().ok_or(InvalidInstruction)?;
+        let len = *len as usize;
+        if rest.len() < len {
+            return Err(InvalidInstruction.into());
+        }
+        let (key, rest) = rest.split_at(len);
+        Ok((key.to_vec(), rest))
+    }
+
+    // the value is prefixed with a u32 little endian length
+    fn unpack_value(input: &[u8]) -> Result<Vec<u8>, ProgramError> {
+        let len = input
+            .get(..4)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u32::from_le_bytes)
+            .ok_or(InvalidInstruction)? as usize;
+        let value = input
+            .get(4..4 + len)
+            .ok_or(InvalidInstruction)?;
+        Ok(value.to_vec())
+    }
+}
@@ -0,0 +1,275 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed, set_return_data},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::KvInstruction,
+    error::KvError,
+    state::{KvEntry, MAX_KEY_LEN, MAX_VALUE_LEN},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = KvInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            KvInstruction::Put { key, value } => {
+                msg!("Instruction: Put");
+                Self::process_put(accounts, key, value, program_id)
+            },
+            KvInstruction::Get { key } => {
+                msg!("Instruction: Get");
+                Self::process_get(accounts, key, program_id)
+            },
+            KvInstruction::Delete { key } => {
+                msg!("Instruction: Delete");
+                Self::process_delete(accounts, key, program_id)
+            },
+            KvInstruction::TopUpRent { key } => {
+                msg!("Instruction: TopUpRent");
+                Self::process_top_up_rent(accounts, key, program_id)
+            }
+        }
+    }
+
+    fn process_put(
+        accounts: &[AccountInfo],
+        key: Vec<u8>,
+        value: Vec<u8>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if key.len() > MAX_KEY_LEN {
+            return Err(KvError::KeyTooLong.into());
+        }
+        if value.len() > MAX_VALUE_LEN {
+            return Err(KvError::ValueTooLong.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let entry_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        // the owner is part of the seeds, so a signer can only ever write to their own entries
+        let bump_seed = Self::check_entry_address(owner.key, &key, entry_account, program_id)?;
+        let space = KvEntry::space(key.len(), value.len());
+
+        if entry_account.data_is_empty() {
+            msg!("Creating entry...");
+            Self::create_pda_account(
+                owner,
+                entry_account,
+                system_program,
+                space,
+                program_id,
+                &[b"kv", owner.key.as_ref(), &key, &[bump_seed]],
+            )?;
+        } else {
+            if entry_account.owner != program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+
+            msg!("Resizing entry from {} to {} bytes...", entry_account.data_len(), space);
+            Self::resize_account(owner, entry_account, system_program, space)?;
+        }
+
+        let entry_info = KvEntry {
+            owner: *owner.key,
+            bump_seed,
+            key,
+            value,
+        };
+        entry_info.serialize(&mut &mut entry_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_get(
+        accounts: &[AccountInfo],
+        key: Vec<u8>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let entry_account = next_account_info(account_info_iter)?;
+
+        Self::check_entry_address(owner.key, &key, entry_account, program_id)?;
+        if entry_account.data_is_empty() {
+            return Err(KvError::EntryNotFound.into());
+        }
+        if entry_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let entry_info = KvEntry::try_from_slice(&entry_account.data.borrow())?;
+        msg!("Value has {} bytes", entry_info.value.len());
+        set_return_data(&entry_info.value);
+
+        Ok(())
+    }
+
+    fn process_delete(
+        accounts: &[AccountInfo],
+        key: Vec<u8>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let entry_account = next_account_info(account_info_iter)?;
+
+        Self::check_entry_address(owner.key, &key, entry_account, program_id)?;
+        if entry_account.data_is_empty() {
+            return Err(KvError::EntryNotFound.into());
+        }
+        if entry_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        msg!("Closing the entry account...");
+        **owner.lamports.borrow_mut() = owner.lamports()
+            .checked_add(entry_account.lamports())
+            .ok_or(ProgramError::InvalidAccountData)?;
+        **entry_account.lamports.borrow_mut() = 0;
+        entry_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn process_top_up_rent(
+        accounts: &[AccountInfo],
+        key: Vec<u8>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer = next_account_info(account_info_iter)?;
+
+        if !payer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let owner = next_account_info(account_info_iter)?;
+        let entry_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        Self::check_entry_address(owner.key, &key, entry_account, program_id)?;
+        if entry_account.data_is_empty() {
+            return Err(KvError::EntryNotFound.into());
+        }
+        if entry_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // the minimum depends on the size the account has now, which a realloc may have changed
+        let required_lamports = Rent::get()?.minimum_balance(entry_account.data_len());
+        let shortfall = required_lamports.saturating_sub(entry_account.lamports());
+        if shortfall == 0 {
+            msg!("Entry is rent exempt already");
+            return Ok(());
+        }
+
+        msg!("Topping up the entry with {} lamports...", shortfall);
+        invoke(
+            &system_instruction::transfer(payer.key, entry_account.key, shortfall),
+            &[
+                payer.clone(),
+                entry_account.clone(),
+                system_program.clone(),
+            ],
+        )
+    }
+
+    fn check_entry_address(
+        owner: &Pubkey,
+        key: &[u8],
+        entry_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        let (entry_pda, bump_seed) = Pubkey::find_program_address(&[b"kv", owner.as_ref(), key], program_id);
+        if entry_pda != *entry_account.key {
+            return Err(KvError::InvalidEntryAddress.into());
+        }
+        Ok(bump_seed)
+    }
+
+    // Changes the size of an existing entry. A bigger entry needs more lamports to stay rent exempt,
+    // which the owner pays through the system program. A smaller one gives the surplus back to the owner.
+    fn resize_account<'a>(
+        owner: &AccountInfo<'a>,
+        entry_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+    ) -> ProgramResult {
+        let required_lamports = Rent::get()?.minimum_balance(space);
+        let current_lamports = entry_account.lamports();
+
+        if required_lamports > current_lamports {
+            invoke(
+                &system_instruction::transfer(owner.key, entry_account.key, required_lamports - current_lamports),
+                &[
+                    owner.clone(),
+                    entry_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        } else if current_lamports > required_lamports {
+            let surplus = current_lamports - required_lamports;
+            **entry_account.lamports.borrow_mut() = required_lamports;
+            **owner.lamports.borrow_mut() = owner.lamports()
+                .checked_add(surplus)
+                .ok_or(ProgramError::InvalidAccountData)?;
+        }
+
+        entry_account.realloc(space, false)
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
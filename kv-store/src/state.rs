@@ -0,0 +1,22 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// The key is used as a PDA seed and a single seed can't be longer than 32 bytes
+pub const MAX_KEY_LEN: usize = 32;
+// Largest value we are willing to store, in bytes
+pub const MAX_VALUE_LEN: usize = 1024;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct KvEntry {
+    pub owner: Pubkey,
+    pub bump_seed: u8,
+    pub key: Vec<u8>,
+    pub value: Vec<u8>,
+}
+
+impl KvEntry {
+    // Entries are sized exactly to their key and value, borsh prefixes both with a u32 length
+    pub fn space(key_len: usize, value_len: usize) -> usize {
+        32 + 1 + 4 + key_len + 4 + value_len
+    }
+}
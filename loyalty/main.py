@@ -0,0 +1,295 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+LOYALTY_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'merchant': types.fixed_array(types.u8, 32),
+    'coupon_cost': types.u64,
+    'discount_bps': types.u16,
+    'coupon_count': types.u64,
+    'bump_seed': types.u8
+})
+
+POINTS_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'loyalty': types.fixed_array(types.u8, 32),
+    'customer': types.fixed_array(types.u8, 32),
+    'balance': types.u64,
+    'bump_seed': types.u8
+})
+
+ISSUE_EVENT_SCHEMA = borsh.schema({
+    'loyalty': types.fixed_array(types.u8, 32),
+    'customer': types.fixed_array(types.u8, 32),
+    'amount': types.u64,
+    'balance': types.u64
+})
+
+REDEEM_EVENT_SCHEMA = borsh.schema({
+    'loyalty': types.fixed_array(types.u8, 32),
+    'customer': types.fixed_array(types.u8, 32),
+    'coupon': types.fixed_array(types.u8, 32),
+    'points': types.u64,
+    'balance': types.u64
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'loyalty.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'loyalty-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def get_loyalty_address(merchant: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("loyalty", encoding="utf8"), bytes(merchant)], program_id)[0]
+
+def get_points_address(loyalty: PublicKey, customer: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("points", encoding="utf8"), bytes(loyalty), bytes(customer)], program_id)[0]
+
+def get_coupon_address(loyalty: PublicKey, index: int) -> PublicKey:
+    return PublicKey.find_program_address([bytes("coupon", encoding="utf8"), bytes(loyalty), index.to_bytes(8, byteorder='little')], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list) -> str:
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+    return tx["result"]
+
+def print_events(signature: str):
+    # sol_log_data shows up as "Program data: <base64 field> <base64 field>" in the logs
+    logs = client.get_transaction(signature)["result"]["meta"]["logMessages"]
+    for log in logs:
+        if not log.startswith("Program data: "):
+            continue
+        fields = [base64.b64decode(field) for field in log[len("Program data: "):].split(" ")]
+        if fields[0] == b"issue":
+            event = borsh.deserialize(ISSUE_EVENT_SCHEMA, fields[1])
+            print(f"Issue event: {PublicKey(event['customer'])} got {event['amount']} points, balance {event['balance']}")
+        elif fields[0] == b"redeem":
+            event = borsh.deserialize(REDEEM_EVENT_SCHEMA, fields[1])
+            print(f"Redeem event: {PublicKey(event['customer'])} spent {event['points']} points on coupon {PublicKey(event['coupon'])}, balance {event['balance']}")
+
+def init_loyalty(merchant: Keypair, coupon_cost: int, discount_bps: int) -> PublicKey:
+    loyalty = get_loyalty_address(merchant.public_key)
+    if client.get_account_info(loyalty)["result"]["value"] is not None:
+        print(f"Loyalty program {loyalty} already exists")
+        return loyalty
+
+    print(f"\nCreating loyalty program {loyalty}, a coupon costs {coupon_cost} points and grants {discount_bps / 100}% off")
+    send(
+        merchant,
+        (0).to_bytes(1, byteorder='little') + coupon_cost.to_bytes(8, byteorder='little') + discount_bps.to_bytes(2, byteorder='little'),
+        [
+            AccountMeta(pubkey=merchant.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=loyalty, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return loyalty
+
+def issue_points(merchant: Keypair, loyalty: PublicKey, customer: PublicKey, amount: int):
+    print(f"\nIssuing {amount} points to {customer}")
+    signature = send(
+        merchant,
+        (1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=merchant.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=loyalty, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=customer, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_points_address(loyalty, customer), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    print_events(signature)
+
+def redeem_coupon(customer: Keypair, loyalty: PublicKey) -> PublicKey:
+    coupon = get_coupon_address(loyalty, get_loyalty(loyalty)['coupon_count'])
+    print(f"\n{customer.public_key} redeems points for coupon {coupon}")
+    signature = send(
+        customer,
+        (2).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=customer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=loyalty, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_points_address(loyalty, customer.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=coupon, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    print_events(signature)
+    return coupon
+
+def use_coupon(merchant: Keypair, loyalty: PublicKey, coupon: PublicKey):
+    print(f"\nUsing coupon {coupon} at checkout")
+    send(
+        merchant,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=merchant.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=loyalty, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=coupon, is_signer=False, is_writable=True),
+        ],
+    )
+
+def get_loyalty(loyalty: PublicKey) -> dict:
+    account_info = client.get_account_info(loyalty)["result"]["value"]
+    return borsh.deserialize(LOYALTY_SCHEMA, base64.b64decode(account_info["data"][0]))
+
+def show_points(loyalty: PublicKey, customer: PublicKey):
+    account_info = client.get_account_info(get_points_address(loyalty, customer))["result"]["value"]
+    points_info = borsh.deserialize(POINTS_SCHEMA, base64.b64decode(account_info["data"][0]))
+    print(f"{customer} has {points_info['balance']} points")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    loyalty = init_loyalty(payer, 100, 1000)
+    customer = setup_user_keypair()
+    issue_points(payer, loyalty, customer.public_key, 80)
+    issue_points(payer, loyalty, customer.public_key, 50)
+    show_points(loyalty, customer.public_key)
+
+    try:
+        issue_points(customer, loyalty, customer.public_key, 1000)
+    except Exception as e:
+        print(f"Only the merchant can issue points: {e}")
+
+    coupon = redeem_coupon(customer, loyalty)
+    show_points(loyalty, customer.public_key)
+
+    try:
+        redeem_coupon(customer, loyalty)
+    except Exception as e:
+        print(f"Not enough points left for another coupon: {e}")
+
+    use_coupon(payer, loyalty, coupon)
+    try:
+        use_coupon(payer, loyalty, coupon)
+    except Exception as e:
+        print(f"A coupon can only be used once: {e}")
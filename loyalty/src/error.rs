@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum LoyaltyError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Loyalty Address")]
+    InvalidLoyaltyAddress,
+    #[error("Invalid Points Address")]
+    InvalidPointsAddress,
+    #[error("Invalid Coupon Address")]
+    InvalidCouponAddress,
+    #[error("Invalid Discount")]
+    InvalidDiscount,
+    #[error("Not The Merchant")]
+    NotTheMerchant,
+    #[error("Not Enough Points")]
+    NotEnoughPoints,
+    #[error("Coupon Already Used")]
+    CouponAlreadyUsed,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<LoyaltyError> for ProgramError {
+    fn from(e: LoyaltyError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
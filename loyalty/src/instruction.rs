@@ -0,0 +1,90 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::LoyaltyError::InvalidInstruction;
+
+pub enum LoyaltyInstruction {
+
+    // Creates the loyalty program of a merchant in a PDA derived from ["loyalty", merchant].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The merchant
+    // 1. `[writable]` The loyalty PDA
+    // 2. `[]` The system program
+    InitLoyalty {
+        coupon_cost: u64,
+        discount_bps: u16,
+    },
+
+    // Issues points to a customer. Only the merchant can issue points, the points of a customer are kept in
+    // a PDA derived from ["points", loyalty, customer], which the merchant creates on the first issuance.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The merchant
+    // 1. `[]` The loyalty PDA
+    // 2. `[]` The customer
+    // 3. `[writable]` The points PDA of the customer
+    // 4. `[]` The system program
+    IssuePoints {
+        amount: u64,
+    },
+
+    // Spends coupon_cost points on a coupon, a PDA derived from ["coupon", loyalty, index], where index is
+    // the coupon count stored in the loyalty account before this instruction.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The customer, pays for the coupon account
+    // 1. `[writable]` The loyalty PDA
+    // 2. `[writable]` The points PDA of the customer
+    // 3. `[writable]` The coupon PDA for the next index
+    // 4. `[]` The system program
+    RedeemCoupon,
+
+    // Marks a coupon as used at checkout.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The merchant
+    // 1. `[]` The loyalty PDA
+    // 2. `[writable]` The coupon PDA
+    UseCoupon,
+}
+
+impl LoyaltyInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitLoyalty {
+                coupon_cost: Self::unpack_u64(rest, 0)?,
+                discount_bps: rest
+                    .get(8..10)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u16::from_le_bytes)
+                    .ok_or(InvalidInstruction)?,
+            },
+            1 => Self::IssuePoints {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::RedeemCoupon,
+            3 => Self::UseCoupon,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
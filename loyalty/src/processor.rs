@@ -0,0 +1,360 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    log::sol_log_data,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::LoyaltyInstruction,
+    error::LoyaltyError,
+    state::{Coupon, IssueEvent, Loyalty, Points, RedeemEvent},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = LoyaltyInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            LoyaltyInstruction::InitLoyalty { coupon_cost, discount_bps } => {
+                msg!("Instruction: InitLoyalty");
+                Self::process_init_loyalty(accounts, coupon_cost, discount_bps, program_id)
+            },
+            LoyaltyInstruction::IssuePoints { amount } => {
+                msg!("Instruction: IssuePoints");
+                Self::process_issue_points(accounts, amount, program_id)
+            },
+            LoyaltyInstruction::RedeemCoupon => {
+                msg!("Instruction: RedeemCoupon");
+                Self::process_redeem_coupon(accounts, program_id)
+            },
+            LoyaltyInstruction::UseCoupon => {
+                msg!("Instruction: UseCoupon");
+                Self::process_use_coupon(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_init_loyalty(
+        accounts: &[AccountInfo],
+        coupon_cost: u64,
+        discount_bps: u16,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if discount_bps == 0 || discount_bps > 10_000 {
+            return Err(LoyaltyError::InvalidDiscount.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let merchant = next_account_info(account_info_iter)?;
+
+        if !merchant.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let loyalty_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (loyalty_pda, bump_seed) = Pubkey::find_program_address(&[b"loyalty", merchant.key.as_ref()], program_id);
+        if loyalty_pda != *loyalty_account.key {
+            return Err(LoyaltyError::InvalidLoyaltyAddress.into());
+        }
+
+        if !loyalty_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating loyalty account...");
+        Self::create_pda_account(
+            merchant,
+            loyalty_account,
+            system_program,
+            Loyalty::LEN,
+            program_id,
+            &[b"loyalty", merchant.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let loyalty_info = Loyalty {
+            is_initialized: true,
+            merchant: *merchant.key,
+            coupon_cost,
+            discount_bps,
+            coupon_count: 0,
+            bump_seed,
+        };
+        loyalty_info.serialize(&mut &mut loyalty_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_issue_points(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let merchant = next_account_info(account_info_iter)?;
+        let loyalty_account = next_account_info(account_info_iter)?;
+        Self::unpack_as_merchant(merchant, loyalty_account, program_id)?;
+
+        let customer = next_account_info(account_info_iter)?;
+        let points_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (points_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"points", loyalty_account.key.as_ref(), customer.key.as_ref()],
+            program_id,
+        );
+        if points_pda != *points_account.key {
+            return Err(LoyaltyError::InvalidPointsAddress.into());
+        }
+
+        let mut points_info = if points_account.data_is_empty() {
+            msg!("Creating points account of {}...", customer.key);
+            Self::create_pda_account(
+                merchant,
+                points_account,
+                system_program,
+                Points::LEN,
+                program_id,
+                &[b"points", loyalty_account.key.as_ref(), customer.key.as_ref(), &[bump_seed]],
+            )?;
+
+            Points {
+                is_initialized: true,
+                loyalty: *loyalty_account.key,
+                customer: *customer.key,
+                balance: 0,
+                bump_seed,
+            }
+        } else {
+            Self::unpack_points(points_account, program_id)?
+        };
+
+        points_info.balance = points_info.balance
+            .checked_add(amount)
+            .ok_or(LoyaltyError::AmountOverflow)?;
+        points_info.serialize(&mut &mut points_account.data.borrow_mut()[..])?;
+
+        msg!("Issued {} points to {}, balance {}", amount, customer.key, points_info.balance);
+        let event = IssueEvent {
+            loyalty: *loyalty_account.key,
+            customer: *customer.key,
+            amount,
+            balance: points_info.balance,
+        };
+        sol_log_data(&[b"issue", &event.try_to_vec()?]);
+
+        Ok(())
+    }
+
+    fn process_redeem_coupon(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let customer = next_account_info(account_info_iter)?;
+
+        if !customer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let loyalty_account = next_account_info(account_info_iter)?;
+        let mut loyalty_info = Self::unpack_loyalty(loyalty_account, program_id)?;
+
+        let points_account = next_account_info(account_info_iter)?;
+        let mut points_info = Self::unpack_points(points_account, program_id)?;
+        if points_info.loyalty != *loyalty_account.key || points_info.customer != *customer.key {
+            return Err(LoyaltyError::InvalidPointsAddress.into());
+        }
+
+        points_info.balance = points_info.balance
+            .checked_sub(loyalty_info.coupon_cost)
+            .ok_or(LoyaltyError::NotEnoughPoints)?;
+
+        let coupon_account = next_account_info(account_info_iter)?;
+        let index_bytes = loyalty_info.coupon_count.to_le_bytes();
+        let (coupon_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"coupon", loyalty_account.key.as_ref(), &index_bytes],
+            program_id,
+        );
+        if coupon_pda != *coupon_account.key {
+            return Err(LoyaltyError::InvalidCouponAddress.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Creating coupon {}...", loyalty_info.coupon_count);
+        Self::create_pda_account(
+            customer,
+            coupon_account,
+            system_program,
+            Coupon::LEN,
+            program_id,
+            &[b"coupon", loyalty_account.key.as_ref(), &index_bytes, &[bump_seed]],
+        )?;
+
+        let coupon_info = Coupon {
+            is_initialized: true,
+            loyalty: *loyalty_account.key,
+            customer: *customer.key,
+            index: loyalty_info.coupon_count,
+            discount_bps: loyalty_info.discount_bps,
+            used: false,
+            bump_seed,
+        };
+        coupon_info.serialize(&mut &mut coupon_account.data.borrow_mut()[..])?;
+
+        points_info.serialize(&mut &mut points_account.data.borrow_mut()[..])?;
+
+        loyalty_info.coupon_count = loyalty_info.coupon_count
+            .checked_add(1)
+            .ok_or(LoyaltyError::AmountOverflow)?;
+        loyalty_info.serialize(&mut &mut loyalty_account.data.borrow_mut()[..])?;
+
+        msg!("{} redeemed {} points for coupon {}", customer.key, loyalty_info.coupon_cost, coupon_account.key);
+        let event = RedeemEvent {
+            loyalty: *loyalty_account.key,
+            customer: *customer.key,
+            coupon: *coupon_account.key,
+            points: loyalty_info.coupon_cost,
+            balance: points_info.balance,
+        };
+        sol_log_data(&[b"redeem", &event.try_to_vec()?]);
+
+        Ok(())
+    }
+
+    fn process_use_coupon(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let merchant = next_account_info(account_info_iter)?;
+        let loyalty_account = next_account_info(account_info_iter)?;
+        Self::unpack_as_merchant(merchant, loyalty_account, program_id)?;
+
+        let coupon_account = next_account_info(account_info_iter)?;
+        if coupon_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut coupon_info = Coupon::try_from_slice(&coupon_account.data.borrow())?;
+        if !coupon_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if coupon_info.loyalty != *loyalty_account.key {
+            return Err(LoyaltyError::InvalidCouponAddress.into());
+        }
+        if coupon_info.used {
+            return Err(LoyaltyError::CouponAlreadyUsed.into());
+        }
+
+        msg!("Coupon {} of {} grants {} basis points off", coupon_info.index, coupon_info.customer, coupon_info.discount_bps);
+        coupon_info.used = true;
+        coupon_info.serialize(&mut &mut coupon_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn unpack_loyalty(loyalty_account: &AccountInfo, program_id: &Pubkey) -> Result<Loyalty, ProgramError> {
+        if loyalty_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let loyalty_info = Loyalty::try_from_slice(&loyalty_account.data.borrow())?;
+        if !loyalty_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(loyalty_info)
+    }
+
+    // points are only issued and coupons only used by the merchant who created the loyalty program
+    fn unpack_as_merchant(
+        merchant: &AccountInfo,
+        loyalty_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<Loyalty, ProgramError> {
+        if !merchant.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let loyalty_info = Self::unpack_loyalty(loyalty_account, program_id)?;
+        if loyalty_info.merchant != *merchant.key {
+            return Err(LoyaltyError::NotTheMerchant.into());
+        }
+
+        Ok(loyalty_info)
+    }
+
+    fn unpack_points(points_account: &AccountInfo, program_id: &Pubkey) -> Result<Points, ProgramError> {
+        if points_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let points_info = Points::try_from_slice(&points_account.data.borrow())?;
+        if !points_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(points_info)
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    //
+    // Coupons are numbered by a single counter of the program, anyone can compute the next address and send lamports
+    // to it. create_account refuses an account which holds lamports already, so one lamport would keep every customer
+    // from redeeming. Such an account is topped up to the rent instead, then allocated and assigned with the PDA seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let accounts = [
+            payer.clone(),
+            new_account.clone(),
+            system_program.clone(),
+        ];
+
+        if new_account.lamports() == 0 {
+            let create_account_ix = system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            );
+            return invoke_signed(&create_account_ix, &accounts, &[signer_seeds]);
+        }
+
+        let shortfall = lamports.saturating_sub(new_account.lamports());
+        if shortfall > 0 {
+            invoke(&system_instruction::transfer(payer.key, new_account.key, shortfall), &accounts)?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(new_account.key, space as u64),
+            &accounts,
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(new_account.key, program_id),
+            &accounts,
+            &[signer_seeds],
+        )
+    }
+}
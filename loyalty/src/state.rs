@@ -0,0 +1,67 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Loyalty {
+    pub is_initialized: bool,
+    pub merchant: Pubkey,
+    // points a customer pays for a coupon
+    pub coupon_cost: u64,
+    // discount granted by a coupon, in basis points
+    pub discount_bps: u16,
+    // number of coupons redeemed so far, the next coupon is created at this index
+    pub coupon_count: u64,
+    pub bump_seed: u8,
+}
+
+impl Loyalty {
+    pub const LEN: usize = 1 + 32 + 8 + 2 + 8 + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Points {
+    pub is_initialized: bool,
+    pub loyalty: Pubkey,
+    pub customer: Pubkey,
+    pub balance: u64,
+    pub bump_seed: u8,
+}
+
+impl Points {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Coupon {
+    pub is_initialized: bool,
+    pub loyalty: Pubkey,
+    pub customer: Pubkey,
+    pub index: u64,
+    pub discount_bps: u16,
+    // set by the merchant at checkout
+    pub used: bool,
+    pub bump_seed: u8,
+}
+
+impl Coupon {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 2 + 1 + 1;
+}
+
+// Emitted with sol_log_data when points are issued
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct IssueEvent {
+    pub loyalty: Pubkey,
+    pub customer: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+// Emitted with sol_log_data when a customer redeems points for a coupon
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct RedeemEvent {
+    pub loyalty: Pubkey,
+    pub customer: Pubkey,
+    pub coupon: Pubkey,
+    pub points: u64,
+    pub balance: u64,
+}
@@ -0,0 +1,279 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+CAUSE_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'beneficiary': types.fixed_array(types.u8, 32),
+    'id': types.u64,
+    'total_donated': types.u64,
+    'total_matched': types.u64,
+    'bump_seed': types.u8
+})
+
+DONATION_EVENT_SCHEMA = borsh.schema({
+    'cause': types.fixed_array(types.u8, 32),
+    'donor': types.fixed_array(types.u8, 32),
+    'amount': types.u64
+})
+
+MATCH_EVENT_SCHEMA = borsh.schema({
+    'cause': types.fixed_array(types.u8, 32),
+    'pool': types.fixed_array(types.u8, 32),
+    'sponsor': types.fixed_array(types.u8, 32),
+    'donor': types.fixed_array(types.u8, 32),
+    'amount': types.u64,
+    'remaining': types.u64
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'matching.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'matching-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_cause_address(beneficiary: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("cause", encoding="utf8"), bytes(beneficiary), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def get_pool_address(sponsor: PublicKey, cause: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("pool", encoding="utf8"), bytes(sponsor), bytes(cause)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list) -> str:
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+    return tx["result"]
+
+def create_cause(beneficiary: Keypair, id: int) -> PublicKey:
+    cause = get_cause_address(beneficiary.public_key, id)
+    print(f"\nCreating cause {cause}")
+    send(
+        beneficiary,
+        (0).to_bytes(1, byteorder='little') + id.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=beneficiary.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=cause, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return cause
+
+def fund_pool(sponsor: Keypair, cause: PublicKey, amount: int) -> PublicKey:
+    pool = get_pool_address(sponsor.public_key, cause)
+    print(f"\n{sponsor.public_key} puts {amount} lamports into the matching pool {pool}")
+    send(
+        sponsor,
+        (1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=sponsor.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=cause, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return pool
+
+def donate(donor: Keypair, cause: PublicKey, pool: PublicKey, amount: int):
+    print(f"\n{donor.public_key} donates {amount} lamports")
+    signature = send(
+        donor,
+        (2).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=donor.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=cause, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    print_events(signature)
+
+def withdraw(beneficiary: Keypair, cause: PublicKey, amount: int):
+    print(f"\nWithdrawing {amount} lamports from the cause")
+    send(
+        beneficiary,
+        (3).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=beneficiary.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=cause, is_signer=False, is_writable=True),
+        ],
+    )
+
+def close_pool(sponsor: Keypair, pool: PublicKey):
+    print(f"\nClosing the matching pool {pool}")
+    send(
+        sponsor,
+        (4).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=sponsor.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+        ],
+    )
+
+def print_events(signature: str):
+    # sol_log_data shows up as "Program data: <base64 field> <base64 field>" in the logs
+    logs = client.get_transaction(signature)["result"]["meta"]["logMessages"]
+    for log in logs:
+        if not log.startswith("Program data: "):
+            continue
+        fields = [base64.b64decode(field) for field in log[len("Program data: "):].split(" ")]
+        if fields[0] == b"donation":
+            event = borsh.deserialize(DONATION_EVENT_SCHEMA, fields[1])
+            print(f"Donation event: {PublicKey(event['donor'])} donated {event['amount']} lamports")
+        elif fields[0] == b"match":
+            event = borsh.deserialize(MATCH_EVENT_SCHEMA, fields[1])
+            print(f"Match event: {PublicKey(event['sponsor'])} matched {event['amount']} lamports, {event['remaining']} are left in the pool")
+
+def show_cause(cause: PublicKey):
+    account_info = client.get_account_info(cause)["result"]["value"]
+    cause_info = borsh.deserialize(CAUSE_SCHEMA, base64.b64decode(account_info["data"][0]))
+    print(f"Cause holds {account_info['lamports']} lamports, {cause_info['total_donated']} were donated and {cause_info['total_matched']} matched")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    sponsor = setup_user_keypair()
+    donor = setup_user_keypair()
+
+    cause = create_cause(payer, 0)
+    pool = fund_pool(sponsor, cause, LAMPORTS_PER_SOL // 4)
+
+    # the first donation is matched in full, the second one only by what is left in the pool
+    donate(donor, cause, pool, LAMPORTS_PER_SOL // 5)
+    donate(donor, cause, pool, LAMPORTS_PER_SOL // 5)
+    # the pool is empty now, the donation still goes through
+    donate(donor, cause, pool, LAMPORTS_PER_SOL // 10)
+    show_cause(cause)
+
+    withdraw(payer, cause, LAMPORTS_PER_SOL // 2)
+    show_cause(cause)
+    close_pool(sponsor, pool)
@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum MatchingError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Cause Address")]
+    InvalidCauseAddress,
+    #[error("Invalid Pool Address")]
+    InvalidPoolAddress,
+    #[error("Invalid Amount")]
+    InvalidAmount,
+    #[error("Not Beneficiary")]
+    NotBeneficiary,
+    #[error("Not Sponsor")]
+    NotSponsor,
+    #[error("Insufficient Funds")]
+    InsufficientFunds,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<MatchingError> for ProgramError {
+    fn from(e: MatchingError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,98 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::MatchingError::InvalidInstruction;
+
+pub enum MatchingInstruction {
+
+    // Creates a cause in a PDA derived from ["cause", beneficiary, id].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The beneficiary
+    // 1. `[writable]` The cause PDA
+    // 2. `[]` The system program
+    CreateCause {
+        id: u64,
+    },
+
+    // Adds lamports to the sponsor's matching pool for a cause, a PDA derived from ["pool", sponsor, cause].
+    // The pool is created by the first call.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The sponsor
+    // 1. `[]` The cause PDA
+    // 2. `[writable]` The pool PDA
+    // 3. `[]` The system program
+    FundPool {
+        amount: u64,
+    },
+
+    // Donates lamports to a cause. The pool matches the donation 1:1, as far as its lamports go.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The donor
+    // 1. `[writable]` The cause PDA
+    // 2. `[writable]` The pool PDA
+    // 3. `[]` The system program
+    Donate {
+        amount: u64,
+    },
+
+    // Pays donated lamports out to the beneficiary.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The beneficiary
+    // 1. `[writable]` The cause PDA
+    Withdraw {
+        amount: u64,
+    },
+
+    // Closes the pool, the unmatched lamports and the rent go back to the sponsor.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The sponsor
+    // 1. `[writable]` The pool PDA
+    ClosePool,
+}
+
+impl MatchingInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateCause {
+                id: Self::unpack_u64(rest, 0)?,
+            },
+            1 => Self::FundPool {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::Donate {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            3 => Self::Withdraw {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            4 => Self::ClosePool,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,374 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    log::sol_log_data,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::MatchingInstruction,
+    error::MatchingError,
+    state::{Cause, DonationEvent, MatchEvent, MatchingPool},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = MatchingInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            MatchingInstruction::CreateCause { id } => {
+                msg!("Instruction: CreateCause");
+                Self::process_create_cause(accounts, id, program_id)
+            },
+            MatchingInstruction::FundPool { amount } => {
+                msg!("Instruction: FundPool");
+                Self::process_fund_pool(accounts, amount, program_id)
+            },
+            MatchingInstruction::Donate { amount } => {
+                msg!("Instruction: Donate");
+                Self::process_donate(accounts, amount, program_id)
+            },
+            MatchingInstruction::Withdraw { amount } => {
+                msg!("Instruction: Withdraw");
+                Self::process_withdraw(accounts, amount, program_id)
+            },
+            MatchingInstruction::ClosePool => {
+                msg!("Instruction: ClosePool");
+                Self::process_close_pool(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_cause(
+        accounts: &[AccountInfo],
+        id: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let beneficiary = next_account_info(account_info_iter)?;
+
+        if !beneficiary.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let cause_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (cause_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"cause", beneficiary.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if cause_pda != *cause_account.key {
+            return Err(MatchingError::InvalidCauseAddress.into());
+        }
+
+        if !cause_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating cause account...");
+        Self::create_pda_account(
+            beneficiary,
+            cause_account,
+            system_program,
+            Cause::LEN,
+            program_id,
+            &[b"cause", beneficiary.key.as_ref(), &id_bytes, &[bump_seed]],
+        )?;
+
+        let cause_info = Cause {
+            is_initialized: true,
+            beneficiary: *beneficiary.key,
+            id,
+            total_donated: 0,
+            total_matched: 0,
+            bump_seed,
+        };
+        cause_info.serialize(&mut &mut cause_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_fund_pool(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sponsor = next_account_info(account_info_iter)?;
+
+        if !sponsor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let cause_account = next_account_info(account_info_iter)?;
+        Self::unpack_cause(cause_account, program_id)?;
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        if pool_account.data_is_empty() {
+            let (pool_pda, bump_seed) = Pubkey::find_program_address(
+                &[b"pool", sponsor.key.as_ref(), cause_account.key.as_ref()],
+                program_id,
+            );
+            if pool_pda != *pool_account.key {
+                return Err(MatchingError::InvalidPoolAddress.into());
+            }
+
+            msg!("Creating matching pool account...");
+            Self::create_pda_account(
+                sponsor,
+                pool_account,
+                system_program,
+                MatchingPool::LEN,
+                program_id,
+                &[b"pool", sponsor.key.as_ref(), cause_account.key.as_ref(), &[bump_seed]],
+            )?;
+
+            let pool_info = MatchingPool {
+                is_initialized: true,
+                sponsor: *sponsor.key,
+                cause: *cause_account.key,
+                matched: 0,
+                bump_seed,
+            };
+            pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+        } else {
+            let pool_info = Self::unpack_pool(pool_account, cause_account.key, program_id)?;
+            if pool_info.sponsor != *sponsor.key {
+                return Err(MatchingError::NotSponsor.into());
+            }
+        }
+
+        msg!("Calling the system program to add {} lamports to the pool...", amount);
+        invoke(
+            &system_instruction::transfer(sponsor.key, pool_account.key, amount),
+            &[
+                sponsor.clone(),
+                pool_account.clone(),
+                system_program.clone(),
+            ],
+        )
+    }
+
+    fn process_donate(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if amount == 0 {
+            return Err(MatchingError::InvalidAmount.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let donor = next_account_info(account_info_iter)?;
+
+        if !donor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let cause_account = next_account_info(account_info_iter)?;
+        let mut cause_info = Self::unpack_cause(cause_account, program_id)?;
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let mut pool_info = Self::unpack_pool(pool_account, cause_account.key, program_id)?;
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to donate {} lamports...", amount);
+        invoke(
+            &system_instruction::transfer(donor.key, cause_account.key, amount),
+            &[
+                donor.clone(),
+                cause_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        let donation_event = DonationEvent {
+            cause: *cause_account.key,
+            donor: *donor.key,
+            amount,
+        };
+        sol_log_data(&[b"donation", &donation_event.try_to_vec()?]);
+
+        // an exhausted pool doesn't stop the donation, it just isn't matched any more
+        let pool_funds = Self::funds(pool_account)?;
+        let matched = amount.min(pool_funds);
+        if matched > 0 {
+            // the pool account is owned by the program, so the match is moved directly
+            msg!("Matching {} lamports...", matched);
+            **pool_account.lamports.borrow_mut() -= matched;
+            **cause_account.lamports.borrow_mut() = cause_account.lamports()
+                .checked_add(matched)
+                .ok_or(MatchingError::AmountOverflow)?;
+
+            pool_info.matched = pool_info.matched
+                .checked_add(matched)
+                .ok_or(MatchingError::AmountOverflow)?;
+            pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+
+            let match_event = MatchEvent {
+                cause: *cause_account.key,
+                pool: *pool_account.key,
+                sponsor: pool_info.sponsor,
+                donor: *donor.key,
+                amount: matched,
+                remaining: pool_funds - matched,
+            };
+            sol_log_data(&[b"match", &match_event.try_to_vec()?]);
+        }
+
+        cause_info.total_donated = cause_info.total_donated
+            .checked_add(amount)
+            .ok_or(MatchingError::AmountOverflow)?;
+        cause_info.total_matched = cause_info.total_matched
+            .checked_add(matched)
+            .ok_or(MatchingError::AmountOverflow)?;
+        cause_info.serialize(&mut &mut cause_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_withdraw(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let beneficiary = next_account_info(account_info_iter)?;
+
+        if !beneficiary.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let cause_account = next_account_info(account_info_iter)?;
+        let cause_info = Self::unpack_cause(cause_account, program_id)?;
+        if cause_info.beneficiary != *beneficiary.key {
+            return Err(MatchingError::NotBeneficiary.into());
+        }
+
+        if amount > Self::funds(cause_account)? {
+            return Err(MatchingError::InsufficientFunds.into());
+        }
+
+        msg!("Withdrawing {} lamports...", amount);
+        **cause_account.lamports.borrow_mut() -= amount;
+        **beneficiary.lamports.borrow_mut() = beneficiary.lamports()
+            .checked_add(amount)
+            .ok_or(MatchingError::AmountOverflow)?;
+
+        Ok(())
+    }
+
+    fn process_close_pool(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sponsor = next_account_info(account_info_iter)?;
+
+        if !sponsor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        if pool_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let pool_info = MatchingPool::try_from_slice(&pool_account.data.borrow())?;
+        if !pool_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if pool_info.sponsor != *sponsor.key {
+            return Err(MatchingError::NotSponsor.into());
+        }
+
+        msg!("Closing the pool, {} lamports were matched...", pool_info.matched);
+        **sponsor.lamports.borrow_mut() = sponsor.lamports()
+            .checked_add(pool_account.lamports())
+            .ok_or(MatchingError::AmountOverflow)?;
+        **pool_account.lamports.borrow_mut() = 0;
+        pool_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_cause(cause_account: &AccountInfo, program_id: &Pubkey) -> Result<Cause, ProgramError> {
+        if cause_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let cause_info = Cause::try_from_slice(&cause_account.data.borrow())?;
+        if !cause_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(cause_info)
+    }
+
+    // a pool only matches donations to the cause it was funded for
+    fn unpack_pool(pool_account: &AccountInfo, cause: &Pubkey, program_id: &Pubkey) -> Result<MatchingPool, ProgramError> {
+        if pool_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let pool_info = MatchingPool::try_from_slice(&pool_account.data.borrow())?;
+        if !pool_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if pool_info.cause != *cause {
+            return Err(MatchingError::InvalidPoolAddress.into());
+        }
+
+        Ok(pool_info)
+    }
+
+    // lamports held by a cause or a pool on top of its rent
+    fn funds(account: &AccountInfo) -> Result<u64, ProgramError> {
+        let rent_lamports = Rent::get()?.minimum_balance(account.data_len());
+        Ok(account.lamports().saturating_sub(rent_lamports))
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
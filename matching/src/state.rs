@@ -0,0 +1,52 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Cause {
+    pub is_initialized: bool,
+    // receives the donations, they are held by the cause account on top of its rent until then
+    pub beneficiary: Pubkey,
+    pub id: u64,
+    // lamports donated directly and lamports added by matching pools, withdrawals don't reduce them
+    pub total_donated: u64,
+    pub total_matched: u64,
+    pub bump_seed: u8,
+}
+
+impl Cause {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 1;
+}
+
+// A sponsor's pledge to match donations to one cause, the pool holds the remaining lamports on top of its rent
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MatchingPool {
+    pub is_initialized: bool,
+    pub sponsor: Pubkey,
+    pub cause: Pubkey,
+    pub matched: u64,
+    pub bump_seed: u8,
+}
+
+impl MatchingPool {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 1;
+}
+
+// Emitted with sol_log_data for the donor's leg of a donation
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct DonationEvent {
+    pub cause: Pubkey,
+    pub donor: Pubkey,
+    pub amount: u64,
+}
+
+// Emitted with sol_log_data for the sponsor's leg of a donation, only if something was matched
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MatchEvent {
+    pub cause: Pubkey,
+    pub pool: Pubkey,
+    pub sponsor: Pubkey,
+    pub donor: Pubkey,
+    pub amount: u64,
+    // what is left in the pool after this match
+    pub remaining: u64,
+}
@@ -0,0 +1,252 @@
+import yaml
+import sys
+import json
+import base64
+import hashlib
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+CONFIG_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'admin': types.fixed_array(types.u8, 32),
+    'fee': types.u64,
+    'bump_seed': types.u8
+})
+
+SEQUENCE_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'emitter': types.fixed_array(types.u8, 32),
+    'next': types.u64,
+    'bump_seed': types.u8
+})
+
+MESSAGE_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'emitter': types.fixed_array(types.u8, 32),
+    'sequence': types.u64,
+    'nonce': types.u32,
+    'payload_hash': types.fixed_array(types.u8, 32),
+    'slot': types.u64,
+    'timestamp': types.i64
+})
+
+# lamports every message pays
+MESSAGE_FEE = 1000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'messenger.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'messenger-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def get_config_address() -> PublicKey:
+    return PublicKey.find_program_address([bytes("config", encoding="utf8")], program_id)[0]
+
+def get_sequence_address(emitter: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("sequence", encoding="utf8"), bytes(emitter)], program_id)[0]
+
+def get_message_address(emitter: PublicKey, sequence: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("message", encoding="utf8"), bytes(emitter), sequence.to_bytes(8, byteorder='little')],
+        program_id,
+    )[0]
+
+def get_account(address: PublicKey, schema) -> dict:
+    account_info = client.get_account_info(address)["result"]["value"]
+    if account_info is None:
+        return None
+    return borsh.deserialize(schema, base64.b64decode(account_info["data"][0]))
+
+def initialize(admin: Keypair, fee: int):
+    config = get_config_address()
+    if get_account(config, CONFIG_SCHEMA) is not None:
+        print(f"Config {config} already exists")
+        return
+
+    print(f"\nCreating config {config} with a fee of {fee} lamports")
+    tx = Transaction().add(
+        TransactionInstruction(
+            data=(0).to_bytes(1, byteorder='little') + fee.to_bytes(8, byteorder='little'),
+            keys=[
+                AccountMeta(pubkey=admin.public_key, is_signer=True, is_writable=True),
+                AccountMeta(pubkey=config, is_signer=False, is_writable=True),
+                AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            ],
+            program_id=program_id,
+        ),
+    )
+    result = client.send_transaction(tx, admin)
+    client.confirm_transaction(result["result"])
+
+def next_sequence(emitter: PublicKey) -> int:
+    sequence = get_account(get_sequence_address(emitter), SEQUENCE_SCHEMA)
+    return 0 if sequence is None else sequence['next']
+
+def post_message(emitter: Keypair, nonce: int, payload: bytes) -> PublicKey:
+    # the message address contains the sequence number, which is read right before. Two messages of the same
+    # emitter sent at once get the same address, the second one fails
+    message = get_message_address(emitter.public_key, next_sequence(emitter.public_key))
+    print(f"\nPosting {payload} as {message}")
+    tx = Transaction().add(
+        TransactionInstruction(
+            data=(1).to_bytes(1, byteorder='little') + nonce.to_bytes(4, byteorder='little') + payload,
+            keys=[
+                AccountMeta(pubkey=payer.public_key, is_signer=True, is_writable=True),
+                AccountMeta(pubkey=emitter.public_key, is_signer=True, is_writable=False),
+                AccountMeta(pubkey=get_config_address(), is_signer=False, is_writable=True),
+                AccountMeta(pubkey=get_sequence_address(emitter.public_key), is_signer=False, is_writable=True),
+                AccountMeta(pubkey=message, is_signer=False, is_writable=True),
+                AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            ],
+            program_id=program_id,
+        ),
+    )
+    signers = [payer] if emitter.public_key == payer.public_key else [payer, emitter]
+    result = client.send_transaction(tx, *signers)
+    client.confirm_transaction(result["result"])
+    return message
+
+def collect_fees(admin: Keypair):
+    print("\nCollecting the fees")
+    tx = Transaction().add(
+        TransactionInstruction(
+            data=(2).to_bytes(1, byteorder='little'),
+            keys=[
+                AccountMeta(pubkey=admin.public_key, is_signer=True, is_writable=True),
+                AccountMeta(pubkey=get_config_address(), is_signer=False, is_writable=True),
+            ],
+            program_id=program_id,
+        ),
+    )
+    result = client.send_transaction(tx, admin)
+    client.confirm_transaction(result["result"])
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+
+    initialize(payer, MESSAGE_FEE)
+
+    payload = b"transfer 100 tokens to 0x52908400098527886E0F7030069857D2E4169EE7"
+    message = post_message(payer, 42, payload)
+    message_info = get_account(message, MESSAGE_SCHEMA)
+    print(f"Message {message_info['sequence']} in slot {message_info['slot']}, nonce {message_info['nonce']}")
+    print(f"The stored hash matches the payload: {bytes(message_info['payload_hash']) == hashlib.sha256(payload).digest()}")
+    print(f"Next sequence of {payer.public_key}: {next_sequence(payer.public_key)}")
+
+    post_message(payer, 43, b"a second message")
+    collect_fees(payer)
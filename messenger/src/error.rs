@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum MessengerError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Config Address")]
+    InvalidConfigAddress,
+    #[error("Invalid Sequence Address")]
+    InvalidSequenceAddress,
+    #[error("Invalid Message Address")]
+    InvalidMessageAddress,
+    #[error("Payload Too Long")]
+    PayloadTooLong,
+    #[error("Not The Admin")]
+    NotAdmin,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<MessengerError> for ProgramError {
+    fn from(e: MessengerError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,81 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::MessengerError::InvalidInstruction;
+
+pub enum MessengerInstruction {
+
+    // Creates the config in a PDA derived from ["config"]. Whoever sends it first becomes the admin.
+    // Followed by the u64 fee every message pays.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The admin
+    // 1. `[writable]` The config PDA
+    // 2. `[]` The system program
+    Initialize {
+        fee: u64,
+    },
+
+    // Publishes a message of the emitter. The message gets the next sequence number of the emitter and lives
+    // in a PDA derived from ["message", emitter, sequence]. The sequence PDA, ["sequence", emitter], is
+    // created with the first message. Followed by the u32 nonce, every byte after it is the payload.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The payer of the fee and the rent
+    // 1. `[signer]` The emitter, a wallet or the PDA of a program calling through a CPI
+    // 2. `[writable]` The config PDA
+    // 3. `[writable]` The sequence PDA of the emitter
+    // 4. `[writable]` The message PDA
+    // 5. `[]` The system program
+    PostMessage {
+        nonce: u32,
+        payload: Vec<u8>,
+    },
+
+    // Moves the fees collected in the config to the admin, the config keeps its rent.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The admin
+    // 1. `[writable]` The config PDA
+    CollectFees,
+}
+
+impl MessengerInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match *tag {
+            0 => Self::Initialize {
+                fee: Self::unpack_u64(rest)?,
+            },
+            1 => {
+                let nonce = rest
+                    .get(..4)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u32::from_le_bytes)
+                    .ok_or(InvalidInstruction)?;
+                Self::PostMessage {
+                    nonce,
+                    payload: rest[4..].to_vec(),
+                }
+            },
+            2 => Self::CollectFees,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8]) -> Result<u64, ProgramError> {
+        let value = input
+            .get(..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,317 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    hash::hash,
+    log::sol_log_data,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::MessengerInstruction,
+    error::MessengerError,
+    state::{Config, Message, Sequence, MAX_PAYLOAD_LEN},
+};
+
+const CONFIG_SEED: &[u8] = b"config";
+const SEQUENCE_SEED: &[u8] = b"sequence";
+const MESSAGE_SEED: &[u8] = b"message";
+
+pub fn config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], program_id)
+}
+
+pub fn sequence_address(emitter: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEQUENCE_SEED, emitter.as_ref()], program_id)
+}
+
+pub fn message_address(emitter: &Pubkey, sequence: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MESSAGE_SEED, emitter.as_ref(), &sequence.to_le_bytes()], program_id)
+}
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = MessengerInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            MessengerInstruction::Initialize { fee } => {
+                msg!("Instruction: Initialize");
+                Self::process_initialize(accounts, fee, program_id)
+            },
+            MessengerInstruction::PostMessage { nonce, payload } => {
+                msg!("Instruction: PostMessage");
+                Self::process_post_message(accounts, nonce, &payload, program_id)
+            },
+            MessengerInstruction::CollectFees => {
+                msg!("Instruction: CollectFees");
+                Self::process_collect_fees(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_initialize(
+        accounts: &[AccountInfo],
+        fee: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+
+        if !admin.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let config_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (config_pda, bump_seed) = config_address(program_id);
+        if config_pda != *config_account.key {
+            return Err(MessengerError::InvalidConfigAddress.into());
+        }
+
+        if !config_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating config account...");
+        Self::create_pda_account(
+            admin,
+            config_account,
+            system_program,
+            Config::LEN,
+            program_id,
+            &[CONFIG_SEED, &[bump_seed]],
+        )?;
+
+        let config_info = Config {
+            is_initialized: true,
+            admin: *admin.key,
+            fee,
+            bump_seed,
+        };
+        config_info.serialize(&mut &mut config_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    // Like a Wormhole core bridge, the program only publishes. The message account is the proof that the emitter
+    // sent it, the sequence numbers let a relayer notice a message it missed, and the hash ties the account to the
+    // payload in the logs. Nothing checks what happens with the message on the other chain.
+    fn process_post_message(
+        accounts: &[AccountInfo],
+        nonce: u32,
+        payload: &[u8],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if payload.len() > MAX_PAYLOAD_LEN {
+            return Err(MessengerError::PayloadTooLong.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let payer = next_account_info(account_info_iter)?;
+        let emitter = next_account_info(account_info_iter)?;
+
+        // the emitter is who the other chain trusts, nobody may post in its name
+        if !payer.is_signer || !emitter.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let config_account = next_account_info(account_info_iter)?;
+        let config_info = Self::unpack_config(config_account, program_id)?;
+        let sequence_account = next_account_info(account_info_iter)?;
+        let message_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let mut sequence_info = Self::sequence(payer, emitter, sequence_account, system_program, program_id)?;
+
+        let (message_pda, bump_seed) = message_address(emitter.key, sequence_info.next, program_id);
+        if message_pda != *message_account.key {
+            return Err(MessengerError::InvalidMessageAddress.into());
+        }
+
+        if config_info.fee > 0 {
+            msg!("Paying the fee of {} lamports...", config_info.fee);
+            invoke(
+                &system_instruction::transfer(payer.key, config_account.key, config_info.fee),
+                &[
+                    payer.clone(),
+                    config_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+
+        msg!("Creating message account...");
+        let sequence_bytes = sequence_info.next.to_le_bytes();
+        Self::create_pda_account(
+            payer,
+            message_account,
+            system_program,
+            Message::LEN,
+            program_id,
+            &[MESSAGE_SEED, emitter.key.as_ref(), &sequence_bytes, &[bump_seed]],
+        )?;
+
+        let clock = Clock::get()?;
+        let message_info = Message {
+            is_initialized: true,
+            emitter: *emitter.key,
+            sequence: sequence_info.next,
+            nonce,
+            payload_hash: hash(payload).to_bytes(),
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        };
+        message_info.serialize(&mut &mut message_account.data.borrow_mut()[..])?;
+
+        // only the hash is stored, the payload itself ends up in the logs as "Program data: <base64>"
+        msg!("Message {} of {}, {} bytes", message_info.sequence, emitter.key, payload.len());
+        sol_log_data(&[payload]);
+
+        sequence_info.next = sequence_info.next
+            .checked_add(1)
+            .ok_or(MessengerError::AmountOverflow)?;
+        sequence_info.serialize(&mut &mut sequence_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_collect_fees(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin = next_account_info(account_info_iter)?;
+
+        if !admin.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let config_account = next_account_info(account_info_iter)?;
+        let config_info = Self::unpack_config(config_account, program_id)?;
+        if config_info.admin != *admin.key {
+            return Err(MessengerError::NotAdmin.into());
+        }
+
+        // the program owns the config, it can take the lamports out directly
+        let rent = Rent::get()?.minimum_balance(config_account.data_len());
+        let fees = config_account.lamports().saturating_sub(rent);
+        msg!("Collecting {} lamports of fees...", fees);
+        **config_account.lamports.borrow_mut() -= fees;
+        **admin.lamports.borrow_mut() = admin.lamports()
+            .checked_add(fees)
+            .ok_or(MessengerError::AmountOverflow)?;
+
+        Ok(())
+    }
+
+    // The sequence of the emitter, created with its first message
+    fn sequence<'a>(
+        payer: &AccountInfo<'a>,
+        emitter: &AccountInfo<'a>,
+        sequence_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        program_id: &Pubkey,
+    ) -> Result<Sequence, ProgramError> {
+        let (sequence_pda, bump_seed) = sequence_address(emitter.key, program_id);
+        if sequence_pda != *sequence_account.key {
+            return Err(MessengerError::InvalidSequenceAddress.into());
+        }
+
+        if sequence_account.data_is_empty() {
+            msg!("Creating sequence account...");
+            Self::create_pda_account(
+                payer,
+                sequence_account,
+                system_program,
+                Sequence::LEN,
+                program_id,
+                &[SEQUENCE_SEED, emitter.key.as_ref(), &[bump_seed]],
+            )?;
+            return Ok(Sequence {
+                is_initialized: true,
+                emitter: *emitter.key,
+                next: 0,
+                bump_seed,
+            });
+        }
+
+        if sequence_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Ok(Sequence::try_from_slice(&sequence_account.data.borrow())?)
+    }
+
+    fn unpack_config(config_account: &AccountInfo, program_id: &Pubkey) -> Result<Config, ProgramError> {
+        if config_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        if config_address(program_id).0 != *config_account.key {
+            return Err(MessengerError::InvalidConfigAddress.into());
+        }
+
+        let config_info = Config::try_from_slice(&config_account.data.borrow())?;
+        if !config_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(config_info)
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    //
+    // The addresses of the next sequence and message can be computed by anyone, and anyone can send lamports to
+    // them. create_account refuses an account which holds lamports already, one lamport would halt the emitter for
+    // good. Such an account is topped up to the rent instead, then allocated and assigned to the program, which
+    // only needs the signature of the PDA.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let accounts = [
+            payer.clone(),
+            new_account.clone(),
+            system_program.clone(),
+        ];
+
+        if new_account.lamports() == 0 {
+            let create_account_ix = system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            );
+            return invoke_signed(&create_account_ix, &accounts, &[signer_seeds]);
+        }
+
+        let shortfall = lamports.saturating_sub(new_account.lamports());
+        if shortfall > 0 {
+            invoke(&system_instruction::transfer(payer.key, new_account.key, shortfall), &accounts)?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(new_account.key, space as u64),
+            &accounts,
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(new_account.key, program_id),
+            &accounts,
+            &[signer_seeds],
+        )
+    }
+}
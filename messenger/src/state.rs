@@ -0,0 +1,52 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{clock::{Slot, UnixTimestamp}, pubkey::Pubkey};
+
+// the payload is logged in full, a transaction has at most 10 KB of logs
+pub const MAX_PAYLOAD_LEN: usize = 512;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Config {
+    pub is_initialized: bool,
+    // may change nothing but collect the fees
+    pub admin: Pubkey,
+    // lamports every message pays into the config account
+    pub fee: u64,
+    pub bump_seed: u8,
+}
+
+impl Config {
+    pub const LEN: usize = 1 + 32 + 8 + 1;
+}
+
+// The sequence of an emitter, one per emitter
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Sequence {
+    pub is_initialized: bool,
+    pub emitter: Pubkey,
+    // the sequence number of the next message
+    pub next: u64,
+    pub bump_seed: u8,
+}
+
+impl Sequence {
+    pub const LEN: usize = 1 + 32 + 8 + 1;
+}
+
+// A published message. The account is never written again, a relayer picks it up, reads the payload from the
+// logs of the transaction which created it and checks it against the hash.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Message {
+    pub is_initialized: bool,
+    pub emitter: Pubkey,
+    pub sequence: u64,
+    // chosen by the emitter, e.g. to tell apart messages with the same payload
+    pub nonce: u32,
+    // sha256 of the payload
+    pub payload_hash: [u8; 32],
+    pub slot: Slot,
+    pub timestamp: UnixTimestamp,
+}
+
+impl Message {
+    pub const LEN: usize = 1 + 32 + 8 + 4 + 32 + 8 + 8;
+}
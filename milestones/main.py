@@ -0,0 +1,283 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+CONTRACT_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'client': types.fixed_array(types.u8, 32),
+    'freelancer': types.fixed_array(types.u8, 32),
+    'arbiter': types.fixed_array(types.u8, 32),
+    'id': types.u64,
+    'bump_seed': types.u8,
+    'milestones': types.dynamic_array(borsh.schema({
+        'amount': types.u64,
+        'status': types.u8
+    }))
+})
+
+MILESTONE_STATUS = ["Pending", "Released", "Claimed", "Disputed", "Refunded"]
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'milestones.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'milestones-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_contract_address(client_key: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("contract", encoding="utf8"), bytes(client_key), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def create_contract(client_keypair: Keypair, freelancer: PublicKey, arbiter: PublicKey, id: int, amounts: list) -> PublicKey:
+    contract = get_contract_address(client_keypair.public_key, id)
+    print(f"\nCreating contract {contract} with milestones {amounts}")
+    data = (0).to_bytes(1, byteorder='little') + id.to_bytes(8, byteorder='little')
+    for amount in amounts:
+        data += amount.to_bytes(8, byteorder='little')
+    send(
+        client_keypair,
+        data,
+        [
+            AccountMeta(pubkey=client_keypair.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=freelancer, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=arbiter, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=contract, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return contract
+
+def release(client_keypair: Keypair, contract: PublicKey, index: int):
+    print(f"\nClient releases milestone {index}")
+    send(
+        client_keypair,
+        (1).to_bytes(1, byteorder='little') + index.to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=client_keypair.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=contract, is_signer=False, is_writable=True),
+        ],
+    )
+
+def claim(freelancer: Keypair, contract: PublicKey):
+    print("\nFreelancer claims the released milestones")
+    send(
+        freelancer,
+        (2).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=freelancer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=contract, is_signer=False, is_writable=True),
+        ],
+    )
+
+def dispute(party: Keypair, contract: PublicKey, index: int):
+    print(f"\n{party.public_key} disputes milestone {index}")
+    send(
+        party,
+        (3).to_bytes(1, byteorder='little') + index.to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=party.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=contract, is_signer=False, is_writable=True),
+        ],
+    )
+
+def resolve(arbiter: Keypair, contract: PublicKey, client_key: PublicKey, index: int, release: bool):
+    print(f"\nArbiter {'releases' if release else 'refunds'} milestone {index}")
+    send(
+        arbiter,
+        (4).to_bytes(1, byteorder='little') + index.to_bytes(1, byteorder='little') + (1 if release else 0).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=arbiter.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=contract, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=client_key, is_signer=False, is_writable=True),
+        ],
+    )
+
+def close_contract(client_keypair: Keypair, contract: PublicKey):
+    print(f"\nClosing contract {contract}")
+    send(
+        client_keypair,
+        (5).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=client_keypair.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=contract, is_signer=False, is_writable=True),
+        ],
+    )
+
+def show_contract(contract: PublicKey):
+    account_info = client.get_account_info(contract)["result"]["value"]
+    contract_info = borsh.deserialize(CONTRACT_SCHEMA, base64.b64decode(account_info["data"][0]))
+    for index, milestone in enumerate(contract_info['milestones']):
+        print(f"Milestone {index}: {milestone['amount']} lamports, {MILESTONE_STATUS[milestone['status']]}")
+    print(f"Contract holds {account_info['lamports']} lamports")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    freelancer = setup_user_keypair()
+    arbiter = setup_user_keypair()
+
+    amounts = [LAMPORTS_PER_SOL // 10, LAMPORTS_PER_SOL // 5, LAMPORTS_PER_SOL // 4, LAMPORTS_PER_SOL // 2]
+    contract = create_contract(payer, freelancer.public_key, arbiter.public_key, 0, amounts)
+
+    try:
+        claim(freelancer, contract)
+    except Exception as e:
+        print(f"Claiming before a release fails as expected: {e}")
+
+    release(payer, contract, 0)
+    release(payer, contract, 1)
+    claim(freelancer, contract)
+    show_contract(contract)
+
+    # the freelancer says milestone 2 is done, the client disagrees, the arbiter sides with the freelancer
+    dispute(payer, contract, 2)
+    try:
+        resolve(freelancer, contract, payer.public_key, 2, True)
+    except Exception as e:
+        print(f"Resolving a dispute without being the arbiter fails as expected: {e}")
+    resolve(arbiter, contract, payer.public_key, 2, True)
+
+    # milestone 3 is never delivered, the arbiter refunds it to the client
+    dispute(payer, contract, 3)
+    resolve(arbiter, contract, payer.public_key, 3, False)
+
+    try:
+        close_contract(payer, contract)
+    except Exception as e:
+        print(f"Closing before everything has been paid out fails as expected: {e}")
+    claim(freelancer, contract)
+    show_contract(contract)
+    close_contract(payer, contract)
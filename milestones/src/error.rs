@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum MilestonesError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Contract Address")]
+    InvalidContractAddress,
+    #[error("Invalid Milestones")]
+    InvalidMilestones,
+    #[error("Invalid Milestone Status")]
+    InvalidMilestoneStatus,
+    #[error("Not Client")]
+    NotClient,
+    #[error("Not Freelancer")]
+    NotFreelancer,
+    #[error("Not Arbiter")]
+    NotArbiter,
+    #[error("Nothing To Claim")]
+    NothingToClaim,
+    #[error("Contract Not Settled")]
+    ContractNotSettled,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<MilestonesError> for ProgramError {
+    fn from(e: MilestonesError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,124 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::MilestonesError::InvalidInstruction;
+
+pub enum MilestonesInstruction {
+
+    // Creates a contract in a PDA derived from ["contract", client, id]. The client deposits the amounts
+    // of all milestones, they are kept in the PDA on top of its rent.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The client
+    // 1. `[]` The freelancer
+    // 2. `[]` The arbiter
+    // 3. `[writable]` The contract PDA
+    // 4. `[]` The system program
+    CreateContract {
+        id: u64,
+        amounts: Vec<u64>,
+    },
+
+    // Releases a pending or disputed milestone to the freelancer.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The client
+    // 1. `[writable]` The contract PDA
+    Release {
+        index: u8,
+    },
+
+    // Pays every released milestone out to the freelancer.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The freelancer
+    // 1. `[writable]` The contract PDA
+    Claim,
+
+    // Disputes a pending milestone, which stops it until the arbiter resolves it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The client or the freelancer
+    // 1. `[writable]` The contract PDA
+    Dispute {
+        index: u8,
+    },
+
+    // Resolves a disputed milestone, either by releasing it to the freelancer or by refunding it to the client.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The arbiter
+    // 1. `[writable]` The contract PDA
+    // 2. `[writable]` The client
+    Resolve {
+        index: u8,
+        release: bool,
+    },
+
+    // Closes a contract once every milestone has been claimed or refunded and returns the rent to the client.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The client
+    // 1. `[writable]` The contract PDA
+    CloseContract,
+}
+
+impl MilestonesInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateContract {
+                id: Self::unpack_u64(rest, 0)?,
+                amounts: Self::unpack_amounts(rest.get(8..).ok_or(InvalidInstruction)?)?,
+            },
+            1 => Self::Release {
+                index: *rest.first().ok_or(InvalidInstruction)?,
+            },
+            2 => Self::Claim,
+            3 => Self::Dispute {
+                index: *rest.first().ok_or(InvalidInstruction)?,
+            },
+            4 => Self::Resolve {
+                index: *rest.first().ok_or(InvalidInstruction)?,
+                release: match rest.get(1) {
+                    Some(0) => false,
+                    Some(1) => true,
+                    _ => return Err(InvalidInstruction.into()),
+                },
+            },
+            5 => Self::CloseContract,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+
+    // the amounts are simply concatenated, the number of milestones follows from the length
+    fn unpack_amounts(input: &[u8]) -> Result<Vec<u64>, ProgramError> {
+        if input.is_empty() || !input.len().is_multiple_of(8) {
+            return Err(InvalidInstruction.into());
+        }
+
+        (0..input.len()).step_by(8).map(|offset| Self::unpack_u64(input, offset)).collect()
+    }
+}
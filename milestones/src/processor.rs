@@ -0,0 +1,330 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::MilestonesInstruction,
+    error::MilestonesError,
+    state::{Contract, Milestone, MilestoneStatus, MAX_MILESTONES},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = MilestonesInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            MilestonesInstruction::CreateContract { id, amounts } => {
+                msg!("Instruction: CreateContract");
+                Self::process_create_contract(accounts, id, amounts, program_id)
+            },
+            MilestonesInstruction::Release { index } => {
+                msg!("Instruction: Release");
+                Self::process_release(accounts, index, program_id)
+            },
+            MilestonesInstruction::Claim => {
+                msg!("Instruction: Claim");
+                Self::process_claim(accounts, program_id)
+            },
+            MilestonesInstruction::Dispute { index } => {
+                msg!("Instruction: Dispute");
+                Self::process_dispute(accounts, index, program_id)
+            },
+            MilestonesInstruction::Resolve { index, release } => {
+                msg!("Instruction: Resolve");
+                Self::process_resolve(accounts, index, release, program_id)
+            },
+            MilestonesInstruction::CloseContract => {
+                msg!("Instruction: CloseContract");
+                Self::process_close_contract(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_contract(
+        accounts: &[AccountInfo],
+        id: u64,
+        amounts: Vec<u64>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if amounts.len() > MAX_MILESTONES || amounts.contains(&0) {
+            return Err(MilestonesError::InvalidMilestones.into());
+        }
+        let total = amounts
+            .iter()
+            .try_fold(0u64, |total, amount| total.checked_add(*amount))
+            .ok_or(MilestonesError::AmountOverflow)?;
+
+        let account_info_iter = &mut accounts.iter();
+        let client = next_account_info(account_info_iter)?;
+
+        if !client.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let freelancer = next_account_info(account_info_iter)?;
+        let arbiter = next_account_info(account_info_iter)?;
+        let contract_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (contract_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"contract", client.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if contract_pda != *contract_account.key {
+            return Err(MilestonesError::InvalidContractAddress.into());
+        }
+
+        if !contract_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the amounts of all milestones are simply added on top of the rent when the account is created
+        let space = Contract::space(amounts.len());
+        let lamports = Rent::get()?.minimum_balance(space)
+            .checked_add(total)
+            .ok_or(MilestonesError::AmountOverflow)?;
+
+        msg!("Creating contract account with {} milestones over {} lamports...", amounts.len(), total);
+        invoke_signed(
+            &system_instruction::create_account(
+                client.key,
+                contract_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            ),
+            &[
+                client.clone(),
+                contract_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"contract", client.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        let contract_info = Contract {
+            is_initialized: true,
+            client: *client.key,
+            freelancer: *freelancer.key,
+            arbiter: *arbiter.key,
+            id,
+            bump_seed,
+            milestones: amounts
+                .into_iter()
+                .map(|amount| Milestone { amount, status: MilestoneStatus::Pending })
+                .collect(),
+        };
+        contract_info.serialize(&mut &mut contract_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_release(
+        accounts: &[AccountInfo],
+        index: u8,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let client = next_account_info(account_info_iter)?;
+
+        if !client.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let contract_account = next_account_info(account_info_iter)?;
+        let mut contract_info = Self::unpack_contract(contract_account, program_id)?;
+        if contract_info.client != *client.key {
+            return Err(MilestonesError::NotClient.into());
+        }
+
+        // the client may also give in on a disputed milestone, without waiting for the arbiter
+        let milestone = Self::milestone_mut(&mut contract_info, index)?;
+        if !matches!(milestone.status, MilestoneStatus::Pending | MilestoneStatus::Disputed) {
+            return Err(MilestonesError::InvalidMilestoneStatus.into());
+        }
+
+        msg!("Releasing milestone {} over {} lamports", index, milestone.amount);
+        milestone.status = MilestoneStatus::Released;
+        contract_info.serialize(&mut &mut contract_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_claim(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let freelancer = next_account_info(account_info_iter)?;
+
+        if !freelancer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let contract_account = next_account_info(account_info_iter)?;
+        let mut contract_info = Self::unpack_contract(contract_account, program_id)?;
+        if contract_info.freelancer != *freelancer.key {
+            return Err(MilestonesError::NotFreelancer.into());
+        }
+
+        let mut amount: u64 = 0;
+        for milestone in contract_info.milestones.iter_mut() {
+            if milestone.status == MilestoneStatus::Released {
+                amount = amount.checked_add(milestone.amount).ok_or(MilestonesError::AmountOverflow)?;
+                milestone.status = MilestoneStatus::Claimed;
+            }
+        }
+        if amount == 0 {
+            return Err(MilestonesError::NothingToClaim.into());
+        }
+
+        // the contract account is owned by the program, so the lamports are moved directly
+        msg!("Paying {} lamports to the freelancer...", amount);
+        **contract_account.lamports.borrow_mut() -= amount;
+        **freelancer.lamports.borrow_mut() = freelancer.lamports()
+            .checked_add(amount)
+            .ok_or(MilestonesError::AmountOverflow)?;
+
+        contract_info.serialize(&mut &mut contract_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_dispute(
+        accounts: &[AccountInfo],
+        index: u8,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let party = next_account_info(account_info_iter)?;
+
+        if !party.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let contract_account = next_account_info(account_info_iter)?;
+        let mut contract_info = Self::unpack_contract(contract_account, program_id)?;
+        if contract_info.client != *party.key && contract_info.freelancer != *party.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // a released milestone belongs to the freelancer already and can't be disputed anymore
+        let milestone = Self::milestone_mut(&mut contract_info, index)?;
+        if milestone.status != MilestoneStatus::Pending {
+            return Err(MilestonesError::InvalidMilestoneStatus.into());
+        }
+
+        msg!("{} disputes milestone {}", party.key, index);
+        milestone.status = MilestoneStatus::Disputed;
+        contract_info.serialize(&mut &mut contract_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_resolve(
+        accounts: &[AccountInfo],
+        index: u8,
+        release: bool,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let arbiter = next_account_info(account_info_iter)?;
+
+        if !arbiter.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let contract_account = next_account_info(account_info_iter)?;
+        let mut contract_info = Self::unpack_contract(contract_account, program_id)?;
+        if contract_info.arbiter != *arbiter.key {
+            return Err(MilestonesError::NotArbiter.into());
+        }
+
+        let client = next_account_info(account_info_iter)?;
+        if contract_info.client != *client.key {
+            return Err(MilestonesError::NotClient.into());
+        }
+
+        let milestone = Self::milestone_mut(&mut contract_info, index)?;
+        if milestone.status != MilestoneStatus::Disputed {
+            return Err(MilestonesError::InvalidMilestoneStatus.into());
+        }
+
+        if release {
+            msg!("Arbiter releases milestone {} to the freelancer", index);
+            milestone.status = MilestoneStatus::Released;
+        } else {
+            msg!("Arbiter refunds milestone {}, {} lamports go back to the client...", index, milestone.amount);
+            milestone.status = MilestoneStatus::Refunded;
+            **contract_account.lamports.borrow_mut() -= milestone.amount;
+            **client.lamports.borrow_mut() = client.lamports()
+                .checked_add(milestone.amount)
+                .ok_or(MilestonesError::AmountOverflow)?;
+        }
+
+        contract_info.serialize(&mut &mut contract_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_close_contract(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let client = next_account_info(account_info_iter)?;
+
+        if !client.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let contract_account = next_account_info(account_info_iter)?;
+        let contract_info = Self::unpack_contract(contract_account, program_id)?;
+        if contract_info.client != *client.key {
+            return Err(MilestonesError::NotClient.into());
+        }
+        if !contract_info.is_settled() {
+            return Err(MilestonesError::ContractNotSettled.into());
+        }
+
+        msg!("Closing the contract account...");
+        **client.lamports.borrow_mut() = client.lamports()
+            .checked_add(contract_account.lamports())
+            .ok_or(MilestonesError::AmountOverflow)?;
+        **contract_account.lamports.borrow_mut() = 0;
+        contract_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_contract(contract_account: &AccountInfo, program_id: &Pubkey) -> Result<Contract, ProgramError> {
+        if contract_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let contract_info = Contract::try_from_slice(&contract_account.data.borrow())?;
+        if !contract_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(contract_info)
+    }
+
+    fn milestone_mut(contract_info: &mut Contract, index: u8) -> Result<&mut Milestone, ProgramError> {
+        contract_info
+            .milestones
+            .get_mut(index as usize)
+            .ok_or_else(|| MilestonesError::InvalidMilestones.into())
+    }
+}
@@ -0,0 +1,53 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Most milestones a single contract can have
+pub const MAX_MILESTONES: usize = 10;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MilestoneStatus {
+    Pending,
+    // the client, or the arbiter, approved the work, the freelancer can claim the amount
+    Released,
+    Claimed,
+    Disputed,
+    // the arbiter decided for the client, the amount went back to them
+    Refunded,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy)]
+pub struct Milestone {
+    pub amount: u64,
+    pub status: MilestoneStatus,
+}
+
+impl Milestone {
+    pub const LEN: usize = 8 + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Contract {
+    pub is_initialized: bool,
+    pub client: Pubkey,
+    pub freelancer: Pubkey,
+    // resolves disputes, neither side can decide one on its own
+    pub arbiter: Pubkey,
+    pub id: u64,
+    pub bump_seed: u8,
+    pub milestones: Vec<Milestone>,
+}
+
+impl Contract {
+    // The milestones are fixed when the contract is created, so the account is sized exactly.
+    // borsh stores a vector as a u32 length followed by its elements.
+    pub fn space(milestone_count: usize) -> usize {
+        1 + 32 + 32 + 32 + 8 + 1 + 4 + milestone_count * Milestone::LEN
+    }
+
+    // a contract is done once every milestone has been paid out to one side
+    pub fn is_settled(&self) -> bool {
+        self.milestones
+            .iter()
+            .all(|milestone| matches!(milestone.status, MilestoneStatus::Claimed | MilestoneStatus::Refunded))
+    }
+}
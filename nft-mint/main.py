@@ -0,0 +1,184 @@
+import yaml
+import sys
+import json
+import base64
+
+from os import path
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.sysvar import SYSVAR_RENT_PUBKEY
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+TOKEN_METADATA_PROGRAM_ID = PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s")
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'nft_mint.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'nft_mint-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def get_metadata_address(mint: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("metadata", encoding="utf8"), bytes(TOKEN_METADATA_PROGRAM_ID), bytes(mint)],
+        TOKEN_METADATA_PROGRAM_ID
+    )[0]
+
+def get_master_edition_address(mint: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("metadata", encoding="utf8"), bytes(TOKEN_METADATA_PROGRAM_ID), bytes(mint), bytes("edition", encoding="utf8")],
+        TOKEN_METADATA_PROGRAM_ID
+    )[0]
+
+def encode_string(value: str) -> bytes:
+    encoded = bytes(value, encoding="utf8")
+    return len(encoded).to_bytes(4, byteorder='little') + encoded
+
+def mint_nft(minter: Keypair, name: str, symbol: str, uri: str):
+    mint = Keypair()
+    token_account = Keypair()
+    authority = PublicKey.find_program_address([bytes("authority", encoding="utf8")], program_id)[0]
+    print(f"\nMinting NFT {mint.public_key}")
+
+    mint_nft_ix = TransactionInstruction(
+        data=bytes(1) + encode_string(name) + encode_string(symbol) + encode_string(uri),
+        keys=[
+            AccountMeta(pubkey=minter.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=mint.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=token_account.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_metadata_address(mint.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_master_edition_address(mint.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=authority, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYSVAR_RENT_PUBKEY, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_METADATA_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    # the metadata CPIs need more compute units than a simple transfer, but still fit the default budget
+    tx = client.send_transaction(Transaction().add(mint_nft_ix), minter, mint, token_account)
+    client.confirm_transaction(tx["result"])
+
+    token = Token(client, mint.public_key, TOKEN_PROGRAM_ID, minter)
+    mint_info = token.get_mint_info()
+    print(f"Supply: {mint_info.supply}, decimals: {mint_info.decimals}, mint authority: {mint_info.mint_authority}")
+    print(f"Token account {token_account.public_key} holds {token.get_balance(token_account.public_key)['result']['value']['amount']}")
+    metadata = client.get_account_info(get_metadata_address(mint.public_key))["result"]["value"]
+    print(f"Metadata account is owned by {metadata['owner']}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    mint_nft(payer, "Solana Intro #1", "INTRO", "https://example.com/nft/1.json")
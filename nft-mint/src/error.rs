@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum NftError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Authority Address")]
+    InvalidAuthorityAddress,
+    #[error("Invalid Metadata Address")]
+    InvalidMetadataAddress,
+    #[error("Invalid Master Edition Address")]
+    InvalidMasterEditionAddress,
+}
+
+impl From<NftError> for ProgramError {
+    fn from(e: NftError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
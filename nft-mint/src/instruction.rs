@@ -0,0 +1,59 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::NftError::InvalidInstruction;
+
+pub enum NftInstruction {
+
+    // Mints a new NFT: a mint with 0 decimals and a supply of 1, its metadata and its master edition.
+    // The PDA ["authority"] is the mint authority and the update authority of the metadata.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The minter, pays for all accounts and receives the NFT
+    // 1. `[signer, writable]` The new mint account
+    // 2. `[signer, writable]` The new token account of the minter holding the NFT
+    // 3. `[writable]` The metadata PDA of the token metadata program
+    // 4. `[writable]` The master edition PDA of the token metadata program
+    // 5. `[]` The authority PDA of this program
+    // 6. `[]` The rent sysvar
+    // 7. `[]` The system program
+    // 8. `[]` The token program
+    // 9. `[]` The token metadata program
+    MintNft {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+}
+
+impl NftInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => {
+                let (name, rest) = Self::unpack_string(rest)?;
+                let (symbol, rest) = Self::unpack_string(rest)?;
+                let (uri, _) = Self::unpack_string(rest)?;
+                Self::MintNft { name, symbol, uri }
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    // strings are prefixed with a u32 little endian length, exactly how borsh encodes them
+    fn unpack_string(input: &[u8]) -> Result<(String, &[u8]), ProgramError> {
+        let len = input
+            .get(..4)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u32::from_le_bytes)
+            .ok_or(InvalidInstruction)? as usize;
+        let bytes = input.get(4..4 + len).ok_or(InvalidInstruction)?;
+        let string = std::str::from_utf8(bytes)
+            .map_err(|_| InvalidInstruction)?
+            .to_string();
+        Ok((string, &input[4 + len..]))
+    }
+}
@@ -0,0 +1,5 @@
+#[cfg(not(feature = "no-entrypoint"))]
+pub mod entrypoint;
+pub mod instruction;
+pub mod error;
+pub mod processor;
@@ -0,0 +1,197 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use mpl_token_metadata::{
+    accounts::{MasterEdition, Metadata},
+    instructions::{CreateMasterEditionV3CpiBuilder, CreateMetadataAccountV3CpiBuilder},
+    types::DataV2,
+};
+use spl_token::state::{Account as TokenAccount, Mint};
+
+use crate::{instruction::NftInstruction, error::NftError};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = NftInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            NftInstruction::MintNft { name, symbol, uri } => {
+                msg!("Instruction: MintNft");
+                Self::process_mint_nft(accounts, name, symbol, uri, program_id)
+            }
+        }
+    }
+
+    fn process_mint_nft(
+        accounts: &[AccountInfo],
+        name: String,
+        symbol: String,
+        uri: String,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let minter = next_account_info(account_info_iter)?;
+
+        if !minter.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mint_account = next_account_info(account_info_iter)?;
+        let token_account = next_account_info(account_info_iter)?;
+        let metadata_account = next_account_info(account_info_iter)?;
+        let master_edition_account = next_account_info(account_info_iter)?;
+        let authority = next_account_info(account_info_iter)?;
+        let rent_sysvar = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let metadata_program = next_account_info(account_info_iter)?;
+        if *metadata_program.key != mpl_token_metadata::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let (authority_pda, bump_seed) = Pubkey::find_program_address(&[b"authority"], program_id);
+        if authority_pda != *authority.key {
+            return Err(NftError::InvalidAuthorityAddress.into());
+        }
+
+        // both accounts are PDAs of the token metadata program, derived from the mint
+        if Metadata::find_pda(mint_account.key).0 != *metadata_account.key {
+            return Err(NftError::InvalidMetadataAddress.into());
+        }
+        if MasterEdition::find_pda(mint_account.key).0 != *master_edition_account.key {
+            return Err(NftError::InvalidMasterEditionAddress.into());
+        }
+
+        let rent = Rent::get()?;
+        let authority_seeds: &[&[u8]] = &[b"authority", &[bump_seed]];
+
+        msg!("Calling the system program to create the mint account...");
+        invoke(
+            &system_instruction::create_account(
+                minter.key,
+                mint_account.key,
+                rent.minimum_balance(Mint::LEN),
+                Mint::LEN as u64,
+                token_program.key,
+            ),
+            &[minter.clone(), mint_account.clone(), system_program.clone()],
+        )?;
+
+        // an NFT is just a token without decimals. The freeze authority is needed by the master edition,
+        // which takes over both authorities from our PDA.
+        msg!("Calling the token program to initialize the mint...");
+        invoke(
+            &spl_token::instruction::initialize_mint(
+                token_program.key,
+                mint_account.key,
+                &authority_pda,
+                Some(&authority_pda),
+                0,
+            )?,
+            &[mint_account.clone(), rent_sysvar.clone(), token_program.clone()],
+        )?;
+
+        msg!("Calling the system program to create the token account...");
+        invoke(
+            &system_instruction::create_account(
+                minter.key,
+                token_account.key,
+                rent.minimum_balance(TokenAccount::LEN),
+                TokenAccount::LEN as u64,
+                token_program.key,
+            ),
+            &[minter.clone(), token_account.clone(), system_program.clone()],
+        )?;
+
+        msg!("Calling the token program to initialize the token account...");
+        invoke(
+            &spl_token::instruction::initialize_account(
+                token_program.key,
+                token_account.key,
+                mint_account.key,
+                minter.key,
+            )?,
+            &[
+                token_account.clone(),
+                mint_account.clone(),
+                minter.clone(),
+                rent_sysvar.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        msg!("Calling the token program to mint the single token...");
+        invoke_signed(
+            &spl_token::instruction::mint_to(
+                token_program.key,
+                mint_account.key,
+                token_account.key,
+                &authority_pda,
+                &[&authority_pda],
+                1,
+            )?,
+            &[
+                mint_account.clone(),
+                token_account.clone(),
+                authority.clone(),
+                token_program.clone(),
+            ],
+            &[authority_seeds],
+        )?;
+
+        msg!("Calling the token metadata program to create the metadata...");
+        CreateMetadataAccountV3CpiBuilder::new(metadata_program)
+            .metadata(metadata_account)
+            .mint(mint_account)
+            .mint_authority(authority)
+            .payer(minter)
+            .update_authority(authority, true)
+            .system_program(system_program)
+            .rent(Some(rent_sysvar))
+            .data(DataV2 {
+                name,
+                symbol,
+                uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            })
+            .is_mutable(true)
+            .invoke_signed(&[authority_seeds])?;
+
+        // the master edition becomes mint and freeze authority, a max supply of 0 means no prints can
+        // ever be made, so the supply stays at 1 forever
+        msg!("Calling the token metadata program to create the master edition...");
+        CreateMasterEditionV3CpiBuilder::new(metadata_program)
+            .edition(master_edition_account)
+            .mint(mint_account)
+            .update_authority(authority)
+            .mint_authority(authority)
+            .payer(minter)
+            .metadata(metadata_account)
+            .token_program(token_program)
+            .system_program(system_program)
+            .rent(Some(rent_sysvar))
+            .max_supply(0)
+            .invoke_signed(&[authority_seeds])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum NotaryError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Record Address")]
+    InvalidRecordAddress,
+    #[error("Already Anchored")]
+    AlreadyAnchored,
+}
+
+impl From<NotaryError> for ProgramError {
+    fn from(e: NotaryError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
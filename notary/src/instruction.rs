@@ -0,0 +1,33 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::NotaryError::InvalidInstruction;
+
+pub enum NotaryInstruction {
+
+    // Anchors the hash of a document in a PDA derived from ["document", document_hash], together with the
+    // submitter and the current time. Every hash can only be anchored once.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The submitter
+    // 1. `[writable]` The record PDA
+    // 2. `[]` The system program
+    Anchor {
+        document_hash: [u8; 32],
+    },
+}
+
+impl NotaryInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::Anchor {
+                document_hash: rest.try_into().map_err(|_| InvalidInstruction)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+}
@@ -0,0 +1,86 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::BorshSerialize;
+
+use crate::{instruction::NotaryInstruction, error::NotaryError, state::Record};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = NotaryInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            NotaryInstruction::Anchor { document_hash } => {
+                msg!("Instruction: Anchor");
+                Self::process_anchor(accounts, document_hash, program_id)
+            }
+        }
+    }
+
+    fn process_anchor(
+        accounts: &[AccountInfo],
+        document_hash: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let submitter = next_account_info(account_info_iter)?;
+
+        if !submitter.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let record_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        // the PDA only depends on the hash, so whoever anchors a document first owns the record
+        let (record_pda, bump_seed) = Pubkey::find_program_address(&[b"document", &document_hash], program_id);
+        if record_pda != *record_account.key {
+            return Err(NotaryError::InvalidRecordAddress.into());
+        }
+
+        if !record_account.data_is_empty() {
+            return Err(NotaryError::AlreadyAnchored.into());
+        }
+
+        msg!("Creating record account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                submitter.key,
+                record_account.key,
+                Rent::get()?.minimum_balance(Record::LEN),
+                Record::LEN as u64,
+                program_id,
+            ),
+            &[
+                submitter.clone(),
+                record_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"document", &document_hash, &[bump_seed]]],
+        )?;
+
+        let clock = Clock::get()?;
+        let record_info = Record {
+            is_initialized: true,
+            document_hash,
+            submitter: *submitter.key,
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+            bump_seed,
+        };
+        msg!("Anchored at {} in slot {}", record_info.timestamp, record_info.slot);
+        record_info.serialize(&mut &mut record_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+}
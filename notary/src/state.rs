@@ -0,0 +1,18 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Record {
+    pub is_initialized: bool,
+    // sha256 of the document, the document itself never goes on chain
+    pub document_hash: [u8; 32],
+    pub submitter: Pubkey,
+    // taken from the Clock sysvar when the hash was anchored
+    pub timestamp: i64,
+    pub slot: u64,
+    pub bump_seed: u8,
+}
+
+impl Record {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1;
+}
@@ -0,0 +1,195 @@
+import yaml
+import sys
+import json
+import base64
+
+from os import environ, path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'oracle_consumer.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'oracle_consumer-keypair.json'))
+
+# SOL/USD on devnet. Pass another price account with the PRICE_FEED environment variable
+PRICE_FEED = PublicKey(environ.get("PRICE_FEED", "J83w4HKfqxwcq3BEMMkPFSppX3gqekLyLJBexebFVkix"))
+
+# accept prices at most a minute old, with a confidence interval of at most 1% of the price
+MAX_AGE = 60
+MAX_CONF_BPS = 100
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+
+def get_consumer_address(authority: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("consumer", encoding="utf8"), bytes(authority)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def init_consumer(authority: Keypair) -> PublicKey:
+    consumer = get_consumer_address(authority.public_key)
+    if client.get_account_info(consumer)["result"]["value"] is not None:
+        print(f"Consumer {consumer} already exists")
+        return consumer
+
+    print(f"\nCreating consumer {consumer} for the price feed {PRICE_FEED}")
+    send(
+        authority,
+        (0).to_bytes(1, byteorder='little') + MAX_AGE.to_bytes(8, byteorder='little') + MAX_CONF_BPS.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=consumer, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=PRICE_FEED, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return consumer
+
+def update_price(signer: Keypair, consumer: PublicKey):
+    print("\nUpdating the price")
+    try:
+        send(
+            signer,
+            (1).to_bytes(1, byteorder='little'),
+            [
+                AccountMeta(pubkey=consumer, is_signer=False, is_writable=True),
+                AccountMeta(pubkey=PRICE_FEED, is_signer=False, is_writable=False),
+            ],
+        )
+    except Exception as e:
+        # a feed cloned into a local validator never gets updated, it becomes stale after MAX_AGE seconds
+        print(f"Updating the price failed: {e}")
+
+def print_price(consumer: PublicKey):
+    data = base64.b64decode(client.get_account_info(consumer)['result']['value']['data'][0])
+    # is_initialized, authority, price feed, max age and max confidence come before the price
+    offset = 1 + 32 + 32 + 8 + 8
+    price = int.from_bytes(data[offset:offset + 8], byteorder='little', signed=True)
+    conf = int.from_bytes(data[offset + 8:offset + 16], byteorder='little')
+    expo = int.from_bytes(data[offset + 16:offset + 20], byteorder='little', signed=True)
+    publish_time = int.from_bytes(data[offset + 20:offset + 28], byteorder='little', signed=True)
+    if publish_time == 0:
+        print("No price stored yet")
+        return
+    print(f"Stored price: {price * 10 ** expo} +- {conf * 10 ** expo}, published at {publish_time}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    consumer = init_consumer(payer)
+    update_price(payer, consumer)
+    print_price(consumer)
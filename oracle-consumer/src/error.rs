@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum OracleError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Consumer Address")]
+    InvalidConsumerAddress,
+    #[error("Invalid Price Feed")]
+    InvalidPriceFeed,
+    #[error("Stale Price Feed")]
+    StalePriceFeed,
+    #[error("Confidence Interval Too Wide")]
+    ConfidenceTooWide,
+    #[error("Invalid Price")]
+    InvalidPrice,
+}
+
+impl From<OracleError> for ProgramError {
+    fn from(e: OracleError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
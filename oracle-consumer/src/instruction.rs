@@ -0,0 +1,54 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::OracleError::InvalidInstruction;
+
+pub enum OracleInstruction {
+
+    // Creates a consumer in a PDA derived from ["consumer", authority], bound to a single Pyth price account.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The authority
+    // 1. `[writable]` The consumer PDA
+    // 2. `[]` The Pyth price account
+    // 3. `[]` The system program
+    InitConsumer {
+        max_age: u64,
+        max_conf_bps: u64,
+    },
+
+    // Reads the Pyth price account and stores its price, if it is fresh and precise enough. Anyone may call it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The consumer PDA
+    // 1. `[]` The Pyth price account
+    UpdatePrice,
+}
+
+impl OracleInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitConsumer {
+                max_age: Self::unpack_u64(rest, 0)?,
+                max_conf_bps: Self::unpack_u64(rest, 8)?,
+            },
+            1 => Self::UpdatePrice,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
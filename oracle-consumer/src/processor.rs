@@ -0,0 +1,151 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{instruction::OracleInstruction, error::OracleError, state::Consumer};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = OracleInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            OracleInstruction::InitConsumer { max_age, max_conf_bps } => {
+                msg!("Instruction: InitConsumer");
+                Self::process_init_consumer(accounts, max_age, max_conf_bps, program_id)
+            },
+            OracleInstruction::UpdatePrice => {
+                msg!("Instruction: UpdatePrice");
+                Self::process_update_price(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_init_consumer(
+        accounts: &[AccountInfo],
+        max_age: u64,
+        max_conf_bps: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let consumer_account = next_account_info(account_info_iter)?;
+        let price_feed = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        // only make sure it is a price account, whether it is fresh is up to UpdatePrice
+        pyth_sdk_solana::load_price_feed_from_account_info(price_feed)
+            .map_err(|_| OracleError::InvalidPriceFeed)?;
+
+        let (consumer_pda, bump_seed) = Pubkey::find_program_address(&[b"consumer", authority.key.as_ref()], program_id);
+        if consumer_pda != *consumer_account.key {
+            return Err(OracleError::InvalidConsumerAddress.into());
+        }
+
+        if !consumer_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating consumer account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                authority.key,
+                consumer_account.key,
+                Rent::get()?.minimum_balance(Consumer::LEN),
+                Consumer::LEN as u64,
+                program_id,
+            ),
+            &[
+                authority.clone(),
+                consumer_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"consumer", authority.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        let consumer_info = Consumer {
+            is_initialized: true,
+            authority: *authority.key,
+            price_feed: *price_feed.key,
+            max_age,
+            max_conf_bps,
+            price: 0,
+            conf: 0,
+            expo: 0,
+            publish_time: 0,
+            bump_seed,
+        };
+        consumer_info.serialize(&mut &mut consumer_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_update_price(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let consumer_account = next_account_info(account_info_iter)?;
+        if consumer_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut consumer_info = Consumer::try_from_slice(&consumer_account.data.borrow())?;
+        if !consumer_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        // Anybody can create an account which looks like a Pyth price account. Only the price account chosen
+        // at InitConsumer is trusted, so nobody can feed the consumer a price of their own.
+        let price_feed = next_account_info(account_info_iter)?;
+        if *price_feed.key != consumer_info.price_feed {
+            return Err(OracleError::InvalidPriceFeed.into());
+        }
+
+        let feed = pyth_sdk_solana::load_price_feed_from_account_info(price_feed)
+            .map_err(|_| OracleError::InvalidPriceFeed)?;
+
+        // the publishers stop updating a feed when the market is closed or something is wrong, a price
+        // which hasn't been published for a while must not be used
+        let now = Clock::get()?.unix_timestamp;
+        let price = feed
+            .get_price_no_older_than(now, consumer_info.max_age)
+            .ok_or(OracleError::StalePriceFeed)?;
+
+        if price.price <= 0 {
+            return Err(OracleError::InvalidPrice.into());
+        }
+
+        // the confidence interval says how much the publishers disagree, the real price is somewhere in
+        // price +- conf. A wide interval means the price can't be relied on
+        let max_conf = price.price as u128 * consumer_info.max_conf_bps as u128 / 10_000;
+        if price.conf as u128 > max_conf {
+            msg!("Confidence interval {} is wider than {}", price.conf, max_conf);
+            return Err(OracleError::ConfidenceTooWide.into());
+        }
+
+        msg!("Price: {} +- {} x 10^{}, published at {}", price.price, price.conf, price.expo, price.publish_time);
+        consumer_info.price = price.price;
+        consumer_info.conf = price.conf;
+        consumer_info.expo = price.expo;
+        consumer_info.publish_time = price.publish_time;
+        consumer_info.serialize(&mut &mut consumer_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Consumer {
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+    // the Pyth price account this consumer trusts, any other account is rejected
+    pub price_feed: Pubkey,
+    // a price published longer ago than this many seconds is stale
+    pub max_age: u64,
+    // the widest confidence interval accepted, in basis points of the price
+    pub max_conf_bps: u64,
+    // the latest accepted price, the real price is price * 10^expo, the same goes for conf
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub bump_seed: u8,
+}
+
+impl Consumer {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 + 8 + 4 + 8 + 1;
+}
@@ -0,0 +1,314 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+ORDER_SCHEMA = borsh.schema({
+    'seq': types.u64,
+    'owner': types.fixed_array(types.u8, 32),
+    'price': types.u64,
+    'quantity': types.u64
+})
+
+MARKET_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'base_mint': types.fixed_array(types.u8, 32),
+    'quote_mint': types.fixed_array(types.u8, 32),
+    'base_vault': types.fixed_array(types.u8, 32),
+    'quote_vault': types.fixed_array(types.u8, 32),
+    'next_seq': types.u64,
+    'bids': types.dynamic_array(ORDER_SCHEMA),
+    'asks': types.dynamic_array(ORDER_SCHEMA),
+    'bump_seed': types.u8
+})
+
+BID = 0
+ASK = 1
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'orderbook.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'orderbook-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_program(payer: Keypair, decimals: int) -> Token:
+    token = Token.create_mint(
+        conn=client,
+        payer=payer,
+        mint_authority=payer.public_key,
+        decimals=decimals,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    print(f"Address of token program: {token.pubkey}")
+    return token
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def mint_token(token: Token, minter: Keypair, account: PublicKey, amount: int):
+    print(f"Minting {amount} tokens to {account}")
+    token.mint_to(
+        dest=account,
+        mint_authority=minter,
+        amount=amount,
+        opts=TxOpts(skip_confirmation=False)
+    )
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+def get_market_address(base_mint: PublicKey, quote_mint: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("market", encoding="utf8"), bytes(base_mint), bytes(quote_mint)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def get_market(market: PublicKey) -> dict:
+    account_info = client.get_account_info(market)["result"]["value"]
+    return borsh.deserialize(MARKET_SCHEMA, base64.b64decode(account_info["data"][0]))
+
+def init_market(creator: Keypair, base: Token, quote: Token) -> tuple:
+    market = get_market_address(base.pubkey, quote.pubkey)
+    # the vaults are normal token accounts owned by the market PDA
+    base_vault = create_token_account(base, market)
+    quote_vault = create_token_account(quote, market)
+    print(f"\nCreating market {market}")
+    send(
+        creator,
+        (0).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=creator.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=market, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=base_vault, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=quote_vault, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return market, base_vault, quote_vault
+
+def place_order(owner: Keypair, market: PublicKey, side: int, price: int, quantity: int, source: PublicKey, vault: PublicKey):
+    print(f"\n{owner.public_key} places a {'bid' if side == BID else 'ask'} for {quantity} at {price}")
+    send(
+        owner,
+        (1).to_bytes(1, byteorder='little') + bytes([side]) + price.to_bytes(8, byteorder='little') + quantity.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=market, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=source, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def cancel_order(owner: Keypair, market: PublicKey, seq: int, destination: PublicKey, vault: PublicKey):
+    print(f"\n{owner.public_key} cancels order {seq}")
+    send(
+        owner,
+        (2).to_bytes(1, byteorder='little') + seq.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=market, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def match_orders(cranker: Keypair, market: PublicKey, token_accounts: dict):
+    # a real crank would look up the token accounts of the order owners, here they come from a dict
+    market_info = get_market(market)
+    bidder = PublicKey(market_info['bids'][0]['owner'])
+    asker = PublicKey(market_info['asks'][0]['owner'])
+    print("\nCranking the market")
+    send(
+        cranker,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=market, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=PublicKey(market_info['base_vault']), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=PublicKey(market_info['quote_vault']), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=token_accounts[(bidder, 'base')], is_signer=False, is_writable=True),
+            AccountMeta(pubkey=token_accounts[(bidder, 'quote')], is_signer=False, is_writable=True),
+            AccountMeta(pubkey=token_accounts[(asker, 'quote')], is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def show_book(market: PublicKey):
+    market_info = get_market(market)
+    print("Asks:")
+    for order in reversed(market_info['asks']):
+        print(f"  #{order['seq']} {order['quantity']} at {order['price']} by {PublicKey(order['owner'])}")
+    print("Bids:")
+    for order in market_info['bids']:
+        print(f"  #{order['seq']} {order['quantity']} at {order['price']} by {PublicKey(order['owner'])}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    base = create_token_program(payer, decimals=0)
+    quote = create_token_program(payer, decimals=0)
+    market, base_vault, quote_vault = init_market(payer, base, quote)
+
+    seller = setup_user_keypair()
+    buyer = setup_user_keypair()
+    token_accounts = {}
+    for user in [seller, buyer]:
+        token_accounts[(user.public_key, 'base')] = create_token_account(base, user.public_key)
+        token_accounts[(user.public_key, 'quote')] = create_token_account(quote, user.public_key)
+    mint_token(base, payer, token_accounts[(seller.public_key, 'base')], 100)
+    mint_token(quote, payer, token_accounts[(buyer.public_key, 'quote')], 10000)
+
+    place_order(seller, market, ASK, 50, 10, token_accounts[(seller.public_key, 'base')], base_vault)
+    place_order(buyer, market, BID, 55, 6, token_accounts[(buyer.public_key, 'quote')], quote_vault)
+    place_order(buyer, market, BID, 40, 10, token_accounts[(buyer.public_key, 'quote')], quote_vault)
+    show_book(market)
+
+    # the ask was placed first, so it's the maker and the trade happens at its price of 50
+    match_orders(payer, market, token_accounts)
+    show_book(market)
+
+    try:
+        match_orders(payer, market, token_accounts)
+    except Exception as e:
+        print(f"The best bid doesn't cross the best ask anymore: {e}")
+
+    cancel_order(buyer, market, 2, token_accounts[(buyer.public_key, 'quote')], quote_vault)
+    show_book(market)
+
+    for user, name in [(seller, "Seller"), (buyer, "Buyer")]:
+        print(f"{name} holds {token_balance(base, token_accounts[(user.public_key, 'base')])} base "
+              f"and {token_balance(quote, token_accounts[(user.public_key, 'quote')])} quote tokens")
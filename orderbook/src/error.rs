@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum OrderbookError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Market Address")]
+    InvalidMarketAddress,
+    #[error("Invalid Vault")]
+    InvalidVault,
+    #[error("Invalid Token Account")]
+    InvalidTokenAccount,
+    #[error("Invalid Order")]
+    InvalidOrder,
+    #[error("Order Book Full")]
+    OrderBookFull,
+    #[error("Order Not Found")]
+    OrderNotFound,
+    #[error("No Match")]
+    NoMatch,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<OrderbookError> for ProgramError {
+    fn from(e: OrderbookError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
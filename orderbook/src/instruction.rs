@@ -0,0 +1,100 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::{
+    error::OrderbookError::{InvalidInstruction, InvalidOrder},
+    state::Side,
+};
+
+pub enum OrderbookInstruction {
+
+    // Creates a market in a PDA derived from ["market", base_mint, quote_mint]. Both vaults are token accounts
+    // which have to be owned by the market PDA already.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The creator, pays for the market account
+    // 1. `[writable]` The market PDA
+    // 2. `[]` The base vault
+    // 3. `[]` The quote vault
+    // 4. `[]` The system program
+    InitMarket,
+
+    // Places a limit order and escrows its tokens in the vault of the matching side. Followed by the side,
+    // 0 for a bid and 1 for an ask, the u64 price and the u64 quantity.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner of the order
+    // 1. `[writable]` The market PDA
+    // 2. `[writable]` The owner's token account, quote tokens for a bid, base tokens for an ask
+    // 3. `[writable]` The vault of the same mint
+    // 4. `[]` The token program
+    PlaceOrder {
+        side: Side,
+        price: u64,
+        quantity: u64,
+    },
+
+    // Cancels a resting order and gives back what it still has in escrow. Followed by the u64 sequence number.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner of the order
+    // 1. `[writable]` The market PDA
+    // 2. `[writable]` The owner's token account receiving the refund
+    // 3. `[writable]` The vault of the same mint
+    // 4. `[]` The token program
+    CancelOrder {
+        seq: u64,
+    },
+
+    // Crank, matches the best bid against the best ask if they cross. Anybody can call it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The market PDA
+    // 1. `[writable]` The base vault
+    // 2. `[writable]` The quote vault
+    // 3. `[writable]` The bidder's base token account
+    // 4. `[writable]` The bidder's quote token account, receives the price improvement
+    // 5. `[writable]` The asker's quote token account
+    // 6. `[]` The token program
+    MatchOrders,
+}
+
+impl OrderbookInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitMarket,
+            1 => {
+                let (side_byte, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                Self::PlaceOrder {
+                    side: Side::from_u8(*side_byte).ok_or(InvalidOrder)?,
+                    price: Self::unpack_u64(rest, 0)?,
+                    quantity: Self::unpack_u64(rest, 8)?,
+                }
+            },
+            2 => Self::CancelOrder {
+                seq: Self::unpack_u64(rest, 0)?,
+            },
+            3 => Self::MatchOrders,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,370 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::Account as TokenAccount;
+
+use crate::{
+    instruction::OrderbookInstruction,
+    error::OrderbookError,
+    state::{Market, Order, Side},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = OrderbookInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            OrderbookInstruction::InitMarket => {
+                msg!("Instruction: InitMarket");
+                Self::process_init_market(accounts, program_id)
+            },
+            OrderbookInstruction::PlaceOrder { side, price, quantity } => {
+                msg!("Instruction: PlaceOrder");
+                Self::process_place_order(accounts, side, price, quantity, program_id)
+            },
+            OrderbookInstruction::CancelOrder { seq } => {
+                msg!("Instruction: CancelOrder");
+                Self::process_cancel_order(accounts, seq, program_id)
+            },
+            OrderbookInstruction::MatchOrders => {
+                msg!("Instruction: MatchOrders");
+                Self::process_match_orders(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_init_market(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let market_account = next_account_info(account_info_iter)?;
+        let base_vault = next_account_info(account_info_iter)?;
+        let quote_vault = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let base_vault_info = Self::unpack_token_account(base_vault)?;
+        let quote_vault_info = Self::unpack_token_account(quote_vault)?;
+        if base_vault_info.mint == quote_vault_info.mint {
+            return Err(OrderbookError::InvalidVault.into());
+        }
+
+        let (market_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"market", base_vault_info.mint.as_ref(), quote_vault_info.mint.as_ref()],
+            program_id,
+        );
+        if market_pda != *market_account.key {
+            return Err(OrderbookError::InvalidMarketAddress.into());
+        }
+        if base_vault_info.owner != market_pda || quote_vault_info.owner != market_pda {
+            return Err(OrderbookError::InvalidVault.into());
+        }
+
+        if !market_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating market account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                creator.key,
+                market_account.key,
+                Rent::get()?.minimum_balance(Market::LEN),
+                Market::LEN as u64,
+                program_id,
+            ),
+            &[
+                creator.clone(),
+                market_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"market", base_vault_info.mint.as_ref(), quote_vault_info.mint.as_ref(), &[bump_seed]]],
+        )?;
+
+        let market_info = Market {
+            is_initialized: true,
+            base_mint: base_vault_info.mint,
+            quote_mint: quote_vault_info.mint,
+            base_vault: *base_vault.key,
+            quote_vault: *quote_vault.key,
+            next_seq: 0,
+            bids: Vec::new(),
+            asks: Vec::new(),
+            bump_seed,
+        };
+        market_info.serialize(&mut &mut market_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_place_order(
+        accounts: &[AccountInfo],
+        side: Side,
+        price: u64,
+        quantity: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if price == 0 || quantity == 0 {
+            return Err(OrderbookError::InvalidOrder.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let market_account = next_account_info(account_info_iter)?;
+        let mut market_info = Self::unpack_market(market_account, program_id)?;
+
+        let source = next_account_info(account_info_iter)?;
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != Self::vault_for(&market_info, side) {
+            return Err(OrderbookError::InvalidVault.into());
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let order = Order {
+            seq: market_info.next_seq,
+            owner: *owner.key,
+            price,
+            quantity,
+        };
+        if !market_info.insert(side, order) {
+            return Err(OrderbookError::OrderBookFull.into());
+        }
+        market_info.next_seq += 1;
+
+        let escrow = Self::escrow_amount(side, &order)?;
+
+        msg!("Calling the token program to escrow {} tokens for order {}...", escrow, order.seq);
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                source.key,
+                vault.key,
+                owner.key,
+                &[owner.key],
+                escrow,
+            )?,
+            &[
+                source.clone(),
+                vault.clone(),
+                owner.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        market_info.serialize(&mut &mut market_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_cancel_order(
+        accounts: &[AccountInfo],
+        seq: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let market_account = next_account_info(account_info_iter)?;
+        let mut market_info = Self::unpack_market(market_account, program_id)?;
+
+        let (side, order) = market_info.remove(seq).ok_or(OrderbookError::OrderNotFound)?;
+        if order.owner != *owner.key {
+            return Err(OrderbookError::OrderNotFound.into());
+        }
+
+        let destination = next_account_info(account_info_iter)?;
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != Self::vault_for(&market_info, side) {
+            return Err(OrderbookError::InvalidVault.into());
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let refund = Self::escrow_amount(side, &order)?;
+        Self::transfer_from_vault(&market_info, market_account, vault, destination, token_program, refund)?;
+
+        market_info.serialize(&mut &mut market_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_match_orders(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let market_account = next_account_info(account_info_iter)?;
+        let mut market_info = Self::unpack_market(market_account, program_id)?;
+
+        let (mut bid, mut ask) = market_info.top_of_book().ok_or(OrderbookError::NoMatch)?;
+
+        let base_vault = next_account_info(account_info_iter)?;
+        let quote_vault = next_account_info(account_info_iter)?;
+        if *base_vault.key != market_info.base_vault || *quote_vault.key != market_info.quote_vault {
+            return Err(OrderbookError::InvalidVault.into());
+        }
+
+        // nobody signs for the owners of the orders, so the proceeds must end up in their own token accounts
+        let bidder_base = next_account_info(account_info_iter)?;
+        Self::check_destination(bidder_base, &bid.owner, &market_info.base_mint)?;
+        let bidder_quote = next_account_info(account_info_iter)?;
+        Self::check_destination(bidder_quote, &bid.owner, &market_info.quote_mint)?;
+        let asker_quote = next_account_info(account_info_iter)?;
+        Self::check_destination(asker_quote, &ask.owner, &market_info.quote_mint)?;
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // the order placed first is the maker and sets the price, the later one is the taker
+        let price = if bid.seq < ask.seq { bid.price } else { ask.price };
+        let quantity = bid.quantity.min(ask.quantity);
+        let proceeds = price
+            .checked_mul(quantity)
+            .ok_or(OrderbookError::AmountOverflow)?;
+        // the bidder escrowed their own price, a lower fill price gives them the difference back
+        let improvement = (bid.price - price)
+            .checked_mul(quantity)
+            .ok_or(OrderbookError::AmountOverflow)?;
+
+        msg!("Matching bid {} with ask {}: {} at a price of {}", bid.seq, ask.seq, quantity, price);
+        Self::transfer_from_vault(&market_info, market_account, base_vault, bidder_base, token_program, quantity)?;
+        Self::transfer_from_vault(&market_info, market_account, quote_vault, asker_quote, token_program, proceeds)?;
+        if improvement > 0 {
+            Self::transfer_from_vault(&market_info, market_account, quote_vault, bidder_quote, token_program, improvement)?;
+        }
+
+        bid.quantity -= quantity;
+        ask.quantity -= quantity;
+        for (orders, order) in [(&mut market_info.bids, bid), (&mut market_info.asks, ask)] {
+            if order.quantity == 0 {
+                orders.remove(0);
+            } else {
+                orders[0] = order;
+            }
+        }
+
+        market_info.serialize(&mut &mut market_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn unpack_market(market_account: &AccountInfo, program_id: &Pubkey) -> Result<Market, ProgramError> {
+        if market_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // deserialize stops after the last order, the unused room for more orders is ignored
+        let market_info = Market::deserialize(&mut &market_account.data.borrow()[..])?;
+        if !market_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(market_info)
+    }
+
+    fn unpack_token_account(token_account: &AccountInfo) -> Result<TokenAccount, ProgramError> {
+        if *token_account.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        TokenAccount::unpack(&token_account.try_borrow_data()?)
+    }
+
+    fn check_destination(token_account: &AccountInfo, owner: &Pubkey, mint: &Pubkey) -> ProgramResult {
+        let token_account_info = Self::unpack_token_account(token_account)?;
+        if token_account_info.owner != *owner || token_account_info.mint != *mint {
+            return Err(OrderbookError::InvalidTokenAccount.into());
+        }
+
+        Ok(())
+    }
+
+    // bids escrow quote tokens, asks escrow base tokens
+    fn vault_for(market_info: &Market, side: Side) -> Pubkey {
+        match side {
+            Side::Bid => market_info.quote_vault,
+            Side::Ask => market_info.base_vault,
+        }
+    }
+
+    fn escrow_amount(side: Side, order: &Order) -> Result<u64, ProgramError> {
+        match side {
+            Side::Bid => Ok(order.price
+                .checked_mul(order.quantity)
+                .ok_or(OrderbookError::AmountOverflow)?),
+            Side::Ask => Ok(order.quantity),
+        }
+    }
+
+    // The market PDA owns both vaults, so the program signs the token transfer with the market's seeds
+    fn transfer_from_vault<'a>(
+        market_info: &Market,
+        market_account: &AccountInfo<'a>,
+        vault: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        let transfer_ix = spl_token::instruction::transfer(
+            token_program.key,
+            vault.key,
+            destination.key,
+            market_account.key,
+            &[market_account.key],
+            amount,
+        )?;
+
+        msg!("Calling the token program to transfer {} tokens out of the vault...", amount);
+        invoke_signed(
+            &transfer_ix,
+            &[
+                vault.clone(),
+                destination.clone(),
+                market_account.clone(),
+                token_program.clone(),
+            ],
+            &[&[
+                b"market",
+                market_info.base_mint.as_ref(),
+                market_info.quote_mint.as_ref(),
+                &[market_info.bump_seed],
+            ]],
+        )
+    }
+}
@@ -0,0 +1,98 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Number of resting orders per side of the book
+pub const MAX_ORDERS: usize = 32;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    // buys base tokens, escrows price * quantity quote tokens
+    Bid,
+    // sells base tokens, escrows quantity base tokens
+    Ask,
+}
+
+impl Side {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Side::Bid),
+            1 => Some(Side::Ask),
+            _ => None,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy)]
+pub struct Order {
+    // increases with every order placed on the market, the lower sequence number was placed first
+    pub seq: u64,
+    pub owner: Pubkey,
+    // quote token units per base token unit
+    pub price: u64,
+    // base token units which are still open
+    pub quantity: u64,
+}
+
+impl Order {
+    pub const LEN: usize = 8 + 32 + 8 + 8;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Market {
+    pub is_initialized: bool,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    // token accounts owned by the market PDA, holding everything escrowed by resting orders
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub next_seq: u64,
+    // best price first, orders with the same price in the order they were placed
+    pub bids: Vec<Order>,
+    pub asks: Vec<Order>,
+    pub bump_seed: u8,
+}
+
+impl Market {
+    // the account is allocated for full books, borsh only writes the orders which exist
+    pub const LEN: usize = 1 + 32 + 32 + 32 + 32 + 8 + (4 + MAX_ORDERS * Order::LEN) * 2 + 1;
+
+    // Inserts the order behind all orders with the same or a better price, which keeps both sides sorted by
+    // price and time. Returns false if that side of the book is full.
+    pub fn insert(&mut self, side: Side, order: Order) -> bool {
+        let orders = match side {
+            Side::Bid => &mut self.bids,
+            Side::Ask => &mut self.asks,
+        };
+        if orders.len() >= MAX_ORDERS {
+            return false;
+        }
+
+        let index = match side {
+            Side::Bid => orders.partition_point(|resting| resting.price >= order.price),
+            Side::Ask => orders.partition_point(|resting| resting.price <= order.price),
+        };
+        orders.insert(index, order);
+        true
+    }
+
+    // Removes the order with the given sequence number from whichever side it rests on
+    pub fn remove(&mut self, seq: u64) -> Option<(Side, Order)> {
+        if let Some(index) = self.bids.iter().position(|order| order.seq == seq) {
+            return Some((Side::Bid, self.bids.remove(index)));
+        }
+        if let Some(index) = self.asks.iter().position(|order| order.seq == seq) {
+            return Some((Side::Ask, self.asks.remove(index)));
+        }
+        None
+    }
+
+    // the best bid and the best ask, if they cross
+    pub fn top_of_book(&self) -> Option<(Order, Order)> {
+        let bid = self.bids.first()?;
+        let ask = self.asks.first()?;
+        if bid.price < ask.price {
+            return None;
+        }
+        Some((*bid, *ask))
+    }
+}
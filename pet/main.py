@@ -0,0 +1,251 @@
+import yaml
+import sys
+import json
+import time
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PET_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'owner': types.fixed_array(types.u8, 32),
+    'born_at': types.i64,
+    'last_update': types.i64,
+    'hunger': types.u8,
+    'happiness': types.u8,
+    'energy': types.u8,
+    'asleep': types.u8,
+    'bump_seed': types.u8,
+    'name': types.string
+})
+
+# the stats change once per tick, one tick is a minute
+TICK = 60
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'pet.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'pet-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_pet_address(owner: PublicKey, name: str) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("pet", encoding="utf8"), bytes(owner), bytes(name, encoding="utf8")],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def cluster_time() -> int:
+    return client.get_block_time(client.get_slot()['result'])['result']
+
+def adopt(owner: Keypair, name: str) -> PublicKey:
+    pet = get_pet_address(owner.public_key, name)
+    print(f"\nAdopting {name} at {pet}")
+    send(
+        owner,
+        (0).to_bytes(1, byteorder='little') + bytes(name, encoding="utf8"),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=pet, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return pet
+
+def act(owner: Keypair, pet: PublicKey, tag: int, action: str):
+    # feeding, playing, sleeping and waking up all take the same two accounts
+    print(f"\n{action}...")
+    send(
+        owner,
+        tag.to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=pet, is_signer=False, is_writable=True),
+        ],
+    )
+
+def feed(owner: Keypair, pet: PublicKey):
+    act(owner, pet, 1, "Feeding the pet")
+
+def play(owner: Keypair, pet: PublicKey):
+    act(owner, pet, 2, "Playing with the pet")
+
+def sleep(owner: Keypair, pet: PublicKey):
+    act(owner, pet, 3, "Putting the pet to sleep")
+
+def wake_up(owner: Keypair, pet: PublicKey):
+    act(owner, pet, 4, "Waking the pet up")
+
+def show_pet(pet: PublicKey):
+    account_info = client.get_account_info(pet)["result"]["value"]
+    pet_info = borsh.deserialize(PET_SCHEMA, base64.b64decode(account_info["data"][0]))
+    # the stored stats are only updated by the next instruction, so they may lag behind the clock
+    print(
+        f"{pet_info['name']}: hunger {pet_info['hunger']}, happiness {pet_info['happiness']}, "
+        f"energy {pet_info['energy']}, asleep: {bool(pet_info['asleep'])}, last update at {pet_info['last_update']}"
+    )
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    alice = setup_user_keypair()
+
+    pet = adopt(alice, "Rex")
+    show_pet(pet)
+
+    try:
+        feed(alice, pet)
+    except Exception as e:
+        print(f"Feeding a full pet fails as expected: {e}")
+
+    # every game costs 20 energy, so the fifth one at the latest is too much
+    try:
+        for _ in range(6):
+            play(alice, pet)
+            show_pet(pet)
+    except Exception as e:
+        print(f"Playing with a tired pet fails as expected: {e}")
+
+    sleep(alice, pet)
+    try:
+        play(alice, pet)
+    except Exception as e:
+        print(f"Playing with a sleeping pet fails as expected: {e}")
+
+    print("Letting the pet sleep for two ticks...")
+    start = cluster_time()
+    while cluster_time() < start + 2 * TICK:
+        time.sleep(5)
+
+    wake_up(alice, pet)
+    show_pet(pet)
+    feed(alice, pet)
+    show_pet(pet)
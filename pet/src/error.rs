@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum PetError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Pet Address")]
+    InvalidPetAddress,
+    #[error("Invalid Name")]
+    InvalidName,
+    #[error("Not Owner")]
+    NotOwner,
+    #[error("Pet Starved")]
+    PetStarved,
+    #[error("Pet Asleep")]
+    PetAsleep,
+    #[error("Pet Awake")]
+    PetAwake,
+    #[error("Pet Too Tired")]
+    PetTooTired,
+    #[error("Pet Not Hungry")]
+    PetNotHungry,
+}
+
+impl From<PetError> for ProgramError {
+    fn from(e: PetError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
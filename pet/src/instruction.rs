@@ -0,0 +1,72 @@
+use solana_program::program_error::ProgramError;
+
+use crate::error::PetError::InvalidInstruction;
+
+pub enum PetInstruction {
+
+    // Adopts a pet in a PDA derived from ["pet", owner, name]. It starts out full, happy and rested.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner
+    // 1. `[writable]` The pet PDA
+    // 2. `[]` The system program
+    Adopt {
+        name: String,
+    },
+
+    // Feeds the pet, which lowers its hunger. A pet that isn't hungry refuses the food.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner
+    // 1. `[writable]` The pet PDA
+    Feed,
+
+    // Plays with the pet, which makes it happier but costs energy and makes it hungry.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner
+    // 1. `[writable]` The pet PDA
+    Play,
+
+    // Puts the pet to sleep. While it sleeps it regains energy instead of losing it, and doesn't get bored.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner
+    // 1. `[writable]` The pet PDA
+    Sleep,
+
+    // Wakes the pet up.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner
+    // 1. `[writable]` The pet PDA
+    WakeUp,
+}
+
+impl PetInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            // the name is the rest of the data
+            0 => Self::Adopt {
+                name: String::from_utf8(rest.to_vec()).map_err(|_| InvalidInstruction)?,
+            },
+            1 => Self::Feed,
+            2 => Self::Play,
+            3 => Self::Sleep,
+            4 => Self::WakeUp,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+}
@@ -0,0 +1,223 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::PetInstruction,
+    error::PetError,
+    state::{Pet, FOOD, MAX_NAME_LEN, MAX_STAT, PLAY_EFFORT, PLAY_FUN, PLAY_HUNGER},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = PetInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            PetInstruction::Adopt { name } => {
+                msg!("Instruction: Adopt");
+                Self::process_adopt(accounts, name, program_id)
+            },
+            PetInstruction::Feed => {
+                msg!("Instruction: Feed");
+                Self::process_feed(accounts, program_id)
+            },
+            PetInstruction::Play => {
+                msg!("Instruction: Play");
+                Self::process_play(accounts, program_id)
+            },
+            PetInstruction::Sleep => {
+                msg!("Instruction: Sleep");
+                Self::process_sleep(accounts, program_id, true)
+            },
+            PetInstruction::WakeUp => {
+                msg!("Instruction: WakeUp");
+                Self::process_sleep(accounts, program_id, false)
+            }
+        }
+    }
+
+    fn process_adopt(
+        accounts: &[AccountInfo],
+        name: String,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if name.is_empty() || name.len() > MAX_NAME_LEN {
+            return Err(PetError::InvalidName.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pet_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (pet_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"pet", owner.key.as_ref(), name.as_bytes()],
+            program_id,
+        );
+        if pet_pda != *pet_account.key {
+            return Err(PetError::InvalidPetAddress.into());
+        }
+
+        if !pet_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating pet account for {}...", name);
+        invoke_signed(
+            &system_instruction::create_account(
+                owner.key,
+                pet_account.key,
+                Rent::get()?.minimum_balance(Pet::LEN),
+                Pet::LEN as u64,
+                program_id,
+            ),
+            &[
+                owner.clone(),
+                pet_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"pet", owner.key.as_ref(), name.as_bytes(), &[bump_seed]]],
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let pet_info = Pet {
+            is_initialized: true,
+            owner: *owner.key,
+            born_at: now,
+            last_update: now,
+            hunger: 0,
+            happiness: MAX_STAT,
+            energy: MAX_STAT,
+            asleep: false,
+            bump_seed,
+            name,
+        };
+        pet_info.serialize(&mut &mut pet_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_feed(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let pet_account = next_account_info(account_info_iter)?;
+        let mut pet_info = Self::load_pet(owner, pet_account, program_id)?;
+
+        if pet_info.asleep {
+            return Err(PetError::PetAsleep.into());
+        }
+        if pet_info.hunger == 0 {
+            return Err(PetError::PetNotHungry.into());
+        }
+
+        pet_info.hunger = pet_info.hunger.saturating_sub(FOOD);
+        Self::store_pet(&pet_info, pet_account)
+    }
+
+    fn process_play(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let pet_account = next_account_info(account_info_iter)?;
+        let mut pet_info = Self::load_pet(owner, pet_account, program_id)?;
+
+        if pet_info.asleep {
+            return Err(PetError::PetAsleep.into());
+        }
+        if pet_info.energy < PLAY_EFFORT {
+            return Err(PetError::PetTooTired.into());
+        }
+
+        pet_info.energy -= PLAY_EFFORT;
+        pet_info.happiness = Pet::raise(pet_info.happiness, PLAY_FUN);
+        // playing makes the pet hungrier, a pet that would starve from it refuses to play
+        pet_info.hunger = Pet::raise(pet_info.hunger, PLAY_HUNGER);
+        if !pet_info.is_alive() {
+            return Err(PetError::PetStarved.into());
+        }
+
+        Self::store_pet(&pet_info, pet_account)
+    }
+
+    fn process_sleep(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+        asleep: bool,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let pet_account = next_account_info(account_info_iter)?;
+        let mut pet_info = Self::load_pet(owner, pet_account, program_id)?;
+
+        match (pet_info.asleep, asleep) {
+            (true, true) => return Err(PetError::PetAsleep.into()),
+            (false, false) => return Err(PetError::PetAwake.into()),
+            _ => {},
+        }
+
+        pet_info.asleep = asleep;
+        Self::store_pet(&pet_info, pet_account)
+    }
+
+    // Reads the pet and applies the time that passed since the last instruction. A starved pet stays starved,
+    // no instruction can change it anymore.
+    fn load_pet(owner: &AccountInfo, pet_account: &AccountInfo, program_id: &Pubkey) -> Result<Pet, ProgramError> {
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if pet_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // the name is as long as the owner made it, the account is sized for the longest one
+        let mut pet_info = Pet::deserialize(&mut &pet_account.data.borrow()[..])?;
+        if !pet_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if pet_info.owner != *owner.key {
+            return Err(PetError::NotOwner.into());
+        }
+
+        pet_info.update(Clock::get()?.unix_timestamp);
+        if !pet_info.is_alive() {
+            return Err(PetError::PetStarved.into());
+        }
+
+        Ok(pet_info)
+    }
+
+    fn store_pet(pet_info: &Pet, pet_account: &AccountInfo) -> ProgramResult {
+        msg!(
+            "{}: hunger {}, happiness {}, energy {}, asleep: {}",
+            pet_info.name,
+            pet_info.hunger,
+            pet_info.happiness,
+            pet_info.energy,
+            pet_info.asleep
+        );
+        pet_info.serialize(&mut &mut pet_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+}
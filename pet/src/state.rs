@@ -0,0 +1,72 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Longest name a pet can have, in bytes. The name is one of the seeds of the pet PDA.
+pub const MAX_NAME_LEN: usize = 32;
+
+// Every stat goes from 0 to 100. A pet whose hunger reaches 100 has starved.
+pub const MAX_STAT: u8 = 100;
+
+// The stats change once per tick, one tick is a minute
+pub const TICK: i64 = 60;
+
+// how much the stats change per tick while the pet is awake, and while it sleeps
+const HUNGER_PER_TICK: u8 = 5;
+const BOREDOM_PER_TICK: u8 = 4;
+const TIREDNESS_PER_TICK: u8 = 3;
+const REST_PER_TICK: u8 = 10;
+
+// how much the stats change when the owner feeds or plays with the pet
+pub const FOOD: u8 = 30;
+pub const PLAY_FUN: u8 = 20;
+pub const PLAY_EFFORT: u8 = 20;
+pub const PLAY_HUNGER: u8 = 10;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Pet {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    // unix timestamps taken from the Clock sysvar
+    pub born_at: i64,
+    pub last_update: i64,
+    pub hunger: u8,
+    pub happiness: u8,
+    pub energy: u8,
+    pub asleep: bool,
+    pub bump_seed: u8,
+    pub name: String,
+}
+
+impl Pet {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 1 + 1 + 1 + 1 + 1 + (4 + MAX_NAME_LEN);
+
+    // Nothing happens on chain while nobody sends a transaction, so the time that passed since the last
+    // update is applied at once, right before the next instruction changes the pet.
+    pub fn update(&mut self, now: i64) {
+        let ticks = (now - self.last_update) / TICK;
+        if ticks <= 0 {
+            return;
+        }
+        // the part of a tick that has passed already is kept for the next update
+        self.last_update += ticks * TICK;
+
+        // more than 100 ticks take every stat from one end to the other anyway
+        let ticks = ticks.min(MAX_STAT as i64) as u8;
+        self.hunger = Self::raise(self.hunger, ticks.saturating_mul(HUNGER_PER_TICK));
+        if self.asleep {
+            self.energy = Self::raise(self.energy, ticks.saturating_mul(REST_PER_TICK));
+        } else {
+            self.happiness = self.happiness.saturating_sub(ticks.saturating_mul(BOREDOM_PER_TICK));
+            self.energy = self.energy.saturating_sub(ticks.saturating_mul(TIREDNESS_PER_TICK));
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.hunger < MAX_STAT
+    }
+
+    // adds to a stat without going past 100
+    pub fn raise(stat: u8, amount: u8) -> u8 {
+        stat.saturating_add(amount).min(MAX_STAT)
+    }
+}
@@ -0,0 +1,250 @@
+import yaml
+import sys
+import json
+import time
+import hashlib
+import os
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+EVENT_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'organizer': types.fixed_array(types.u8, 32),
+    'id': types.u64,
+    'secret_hash': types.fixed_array(types.u8, 32),
+    'start_ts': types.i64,
+    'end_ts': types.i64,
+    'attendee_count': types.u64,
+    'bump_seed': types.u8
+})
+
+ATTENDANCE_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'event': types.fixed_array(types.u8, 32),
+    'attendee': types.fixed_array(types.u8, 32),
+    'claimed_at': types.i64,
+    'number': types.u64
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'poa.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'poa-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_event_address(organizer: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("event", encoding="utf8"), bytes(organizer), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def get_attendance_address(event: PublicKey, attendee: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("attendance", encoding="utf8"), bytes(event), bytes(attendee)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def cluster_time() -> int:
+    return client.get_block_time(client.get_slot()['result'])['result']
+
+def create_event(organizer: Keypair, id: int, start_ts: int, end_ts: int, secret: bytes) -> PublicKey:
+    event = get_event_address(organizer.public_key, id)
+    print(f"\nCreating event {event}, attendance can be claimed from {start_ts} to {end_ts}")
+    # only the hash of the secret goes on chain until the first attendee claims
+    send(
+        organizer,
+        (0).to_bytes(1, byteorder='little')
+        + id.to_bytes(8, byteorder='little')
+        + start_ts.to_bytes(8, byteorder='little', signed=True)
+        + end_ts.to_bytes(8, byteorder='little', signed=True)
+        + hashlib.sha256(secret).digest(),
+        [
+            AccountMeta(pubkey=organizer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=event, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return event
+
+def claim(attendee: Keypair, event: PublicKey, secret: bytes):
+    print(f"\n{attendee.public_key} claims attendance")
+    send(
+        attendee,
+        (1).to_bytes(1, byteorder='little') + secret,
+        [
+            AccountMeta(pubkey=attendee.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=event, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_attendance_address(event, attendee.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def show_attendance(event: PublicKey, attendee: PublicKey):
+    account_info = client.get_account_info(get_attendance_address(event, attendee))["result"]["value"]
+    if account_info is None:
+        print(f"{attendee} has no attendance for this event")
+        return
+    attendance = borsh.deserialize(ATTENDANCE_SCHEMA, base64.b64decode(account_info["data"][0]))
+    print(f"{attendee} was attendee number {attendance['number']}, claimed at {attendance['claimed_at']}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+    carol = setup_user_keypair()
+
+    secret = b"solana-meetup-" + os.urandom(8).hex().encode("utf8")
+    now = cluster_time()
+    event = create_event(payer, 0, now, now + 20, secret)
+
+    claim(alice, event, secret)
+    claim(bob, event, secret)
+    show_attendance(event, alice.public_key)
+    show_attendance(event, bob.public_key)
+
+    try:
+        claim(alice, event, secret)
+    except Exception as e:
+        print(f"Claiming twice fails as expected: {e}")
+    try:
+        claim(carol, event, b"guessed-secret")
+    except Exception as e:
+        print(f"Claiming with the wrong secret fails as expected: {e}")
+
+    print("Waiting for the claim window to close...")
+    while cluster_time() <= now + 20:
+        time.sleep(1)
+
+    try:
+        claim(carol, event, secret)
+    except Exception as e:
+        print(f"Claiming after the window fails as expected: {e}")
+    show_attendance(event, carol.public_key)
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum PoaError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Event Address")]
+    InvalidEventAddress,
+    #[error("Invalid Attendance Address")]
+    InvalidAttendanceAddress,
+    #[error("Invalid Claim Window")]
+    InvalidClaimWindow,
+    #[error("Claim Window Closed")]
+    ClaimWindowClosed,
+    #[error("Wrong Secret")]
+    WrongSecret,
+    #[error("Already Claimed")]
+    AlreadyClaimed,
+}
+
+impl From<PoaError> for ProgramError {
+    fn from(e: PoaError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,73 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::{error::PoaError::InvalidInstruction, state::MAX_SECRET_LEN};
+
+pub enum PoaInstruction {
+
+    // Creates an event in a PDA derived from ["event", organizer, id].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The organizer
+    // 1. `[writable]` The event PDA
+    // 2. `[]` The system program
+    CreateEvent {
+        id: u64,
+        start_ts: i64,
+        end_ts: i64,
+        secret_hash: [u8; 32],
+    },
+
+    // Claims attendance with the secret of the event. The attendance is recorded in a PDA derived from
+    // ["attendance", event, attendee].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The attendee
+    // 1. `[writable]` The event PDA
+    // 2. `[writable]` The attendance PDA
+    // 3. `[]` The system program
+    Claim {
+        secret: Vec<u8>,
+    },
+}
+
+impl PoaInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateEvent {
+                id: Self::unpack_u64(rest, 0)?,
+                start_ts: Self::unpack_u64(rest, 8)? as i64,
+                end_ts: Self::unpack_u64(rest, 16)? as i64,
+                secret_hash: rest
+                    .get(24..56)
+                    .and_then(|slice| slice.try_into().ok())
+                    .ok_or(InvalidInstruction)?,
+            },
+            // the secret is the rest of the data
+            1 => {
+                if rest.is_empty() || rest.len() > MAX_SECRET_LEN {
+                    return Err(InvalidInstruction.into());
+                }
+                Self::Claim {
+                    secret: rest.to_vec(),
+                }
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
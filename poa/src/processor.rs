@@ -0,0 +1,200 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    hash::hash,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::PoaInstruction,
+    error::PoaError,
+    state::{Attendance, Event},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = PoaInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            PoaInstruction::CreateEvent { id, start_ts, end_ts, secret_hash } => {
+                msg!("Instruction: CreateEvent");
+                Self::process_create_event(accounts, id, start_ts, end_ts, secret_hash, program_id)
+            },
+            PoaInstruction::Claim { secret } => {
+                msg!("Instruction: Claim");
+                Self::process_claim(accounts, secret, program_id)
+            }
+        }
+    }
+
+    fn process_create_event(
+        accounts: &[AccountInfo],
+        id: u64,
+        start_ts: i64,
+        end_ts: i64,
+        secret_hash: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if end_ts <= start_ts || end_ts <= Clock::get()?.unix_timestamp {
+            return Err(PoaError::InvalidClaimWindow.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let organizer = next_account_info(account_info_iter)?;
+
+        if !organizer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let event_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (event_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"event", organizer.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if event_pda != *event_account.key {
+            return Err(PoaError::InvalidEventAddress.into());
+        }
+
+        if !event_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating event account...");
+        Self::create_pda_account(
+            organizer,
+            event_account,
+            system_program,
+            Event::LEN,
+            program_id,
+            &[b"event", organizer.key.as_ref(), &id_bytes, &[bump_seed]],
+        )?;
+
+        let event_info = Event {
+            is_initialized: true,
+            organizer: *organizer.key,
+            id,
+            secret_hash,
+            start_ts,
+            end_ts,
+            attendee_count: 0,
+            bump_seed,
+        };
+        event_info.serialize(&mut &mut event_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_claim(
+        accounts: &[AccountInfo],
+        secret: Vec<u8>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let attendee = next_account_info(account_info_iter)?;
+
+        if !attendee.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let event_account = next_account_info(account_info_iter)?;
+        if event_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut event_info = Event::try_from_slice(&event_account.data.borrow())?;
+        if !event_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        if now < event_info.start_ts || now > event_info.end_ts {
+            return Err(PoaError::ClaimWindowClosed.into());
+        }
+        if hash(&secret).to_bytes() != event_info.secret_hash {
+            return Err(PoaError::WrongSecret.into());
+        }
+
+        let attendance_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (attendance_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"attendance", event_account.key.as_ref(), attendee.key.as_ref()],
+            program_id,
+        );
+        if attendance_pda != *attendance_account.key {
+            return Err(PoaError::InvalidAttendanceAddress.into());
+        }
+
+        // the attendance PDA can only be created once, so nobody is counted twice
+        if !attendance_account.data_is_empty() {
+            return Err(PoaError::AlreadyClaimed.into());
+        }
+
+        msg!("Creating attendance account...");
+        Self::create_pda_account(
+            attendee,
+            attendance_account,
+            system_program,
+            Attendance::LEN,
+            program_id,
+            &[b"attendance", event_account.key.as_ref(), attendee.key.as_ref(), &[bump_seed]],
+        )?;
+
+        event_info.attendee_count += 1;
+        event_info.serialize(&mut &mut event_account.data.borrow_mut()[..])?;
+
+        let attendance_info = Attendance {
+            is_initialized: true,
+            event: *event_account.key,
+            attendee: *attendee.key,
+            claimed_at: now,
+            number: event_info.attendee_count,
+        };
+        msg!("{} is attendee number {}", attendee.key, attendance_info.number);
+        attendance_info.serialize(&mut &mut attendance_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
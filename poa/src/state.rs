@@ -0,0 +1,39 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Longest secret an organizer can hand out, in bytes
+pub const MAX_SECRET_LEN: usize = 64;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Event {
+    pub is_initialized: bool,
+    pub organizer: Pubkey,
+    pub id: u64,
+    // sha256 of the secret that is announced at the event
+    pub secret_hash: [u8; 32],
+    // unix timestamps, attendance can only be claimed from start_ts until end_ts
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub attendee_count: u64,
+    pub bump_seed: u8,
+}
+
+impl Event {
+    pub const LEN: usize = 1 + 32 + 8 + 32 + 8 + 8 + 8 + 1;
+}
+
+// Proof that an attendee was at an event. There is no instruction to transfer it, and the attendee is part of the
+// address, so it can't be handed to somebody else.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Attendance {
+    pub is_initialized: bool,
+    pub event: Pubkey,
+    pub attendee: Pubkey,
+    pub claimed_at: i64,
+    // 1 for the first attendee to claim, 2 for the second and so on
+    pub number: u64,
+}
+
+impl Attendance {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8;
+}
@@ -0,0 +1,306 @@
+import yaml
+import sys
+import json
+import time
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+MARKET_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'creator': types.fixed_array(types.u8, 32),
+    'oracle': types.fixed_array(types.u8, 32),
+    'id': types.u64,
+    'share_price': types.u64,
+    'close_ts': types.i64,
+    'yes_shares': types.u64,
+    'no_shares': types.u64,
+    'pool': types.u64,
+    'outcome': types.option(types.u8),
+    'bump_seed': types.u8
+})
+
+POSITION_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'market': types.fixed_array(types.u8, 32),
+    'owner': types.fixed_array(types.u8, 32),
+    'yes_shares': types.u64,
+    'no_shares': types.u64,
+    'bump_seed': types.u8
+})
+
+NO = 0
+YES = 1
+OUTCOMES = ["NO", "YES"]
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'prediction.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'prediction-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_market_address(creator: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("market", encoding="utf8"), bytes(creator), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def get_position_address(market: PublicKey, owner: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("position", encoding="utf8"), bytes(market), bytes(owner)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def cluster_time() -> int:
+    return client.get_block_time(client.get_slot()['result'])['result']
+
+def balance(account: PublicKey) -> int:
+    return client.get_balance(account)["result"]["value"]
+
+def create_market(creator: Keypair, oracle: PublicKey, id: int, share_price: int, close_ts: int) -> PublicKey:
+    market = get_market_address(creator.public_key, id)
+    print(f"\nCreating market {market}, a share costs {share_price} lamports until {close_ts}")
+    send(
+        creator,
+        (0).to_bytes(1, byteorder='little')
+        + id.to_bytes(8, byteorder='little')
+        + share_price.to_bytes(8, byteorder='little')
+        + close_ts.to_bytes(8, byteorder='little', signed=True),
+        [
+            AccountMeta(pubkey=creator.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=oracle, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=market, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return market
+
+def buy(buyer: Keypair, market: PublicKey, outcome: int, shares: int):
+    print(f"\n{buyer.public_key} buys {shares} {OUTCOMES[outcome]} shares")
+    send(
+        buyer,
+        (1).to_bytes(1, byteorder='little') + outcome.to_bytes(1, byteorder='little') + shares.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=buyer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=market, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_position_address(market, buyer.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def sell(seller: Keypair, market: PublicKey, outcome: int, shares: int):
+    print(f"\n{seller.public_key} sells {shares} {OUTCOMES[outcome]} shares")
+    send(
+        seller,
+        (2).to_bytes(1, byteorder='little') + outcome.to_bytes(1, byteorder='little') + shares.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=seller.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=market, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_position_address(market, seller.public_key), is_signer=False, is_writable=True),
+        ],
+    )
+
+def resolve(oracle: Keypair, market: PublicKey, outcome: int):
+    print(f"\nResolving the market to {OUTCOMES[outcome]}")
+    send(
+        oracle,
+        (3).to_bytes(1, byteorder='little') + outcome.to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=oracle.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=market, is_signer=False, is_writable=True),
+        ],
+    )
+
+def redeem(owner: Keypair, market: PublicKey):
+    before = balance(owner.public_key)
+    send(
+        owner,
+        (4).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=market, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_position_address(market, owner.public_key), is_signer=False, is_writable=True),
+        ],
+    )
+    # the difference includes the rent of the closed position, minus the fee
+    print(f"{owner.public_key} redeemed, the balance changed by {balance(owner.public_key) - before} lamports")
+
+def show_market(market: PublicKey):
+    account_info = client.get_account_info(market)["result"]["value"]
+    market_info = borsh.deserialize(MARKET_SCHEMA, base64.b64decode(account_info["data"][0]))
+    outcome = "open" if market_info['outcome'] is None else f"resolved to {OUTCOMES[market_info['outcome']]}"
+    print(
+        f"Market {outcome}: {market_info['yes_shares']} YES shares, {market_info['no_shares']} NO shares, "
+        f"pool of {market_info['pool']} lamports"
+    )
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    oracle = setup_user_keypair()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+
+    share_price = LAMPORTS_PER_SOL // 100
+    close_ts = cluster_time() + 20
+    market = create_market(payer, oracle.public_key, int(time.time()), share_price, close_ts)
+
+    buy(alice, market, YES, 30)
+    buy(bob, market, NO, 50)
+    sell(bob, market, NO, 20)
+    show_market(market)
+
+    try:
+        sell(alice, market, NO, 1)
+    except Exception as e:
+        print(f"Selling shares one doesn't hold fails as expected: {e}")
+    try:
+        resolve(oracle, market, YES)
+    except Exception as e:
+        print(f"Resolving an open market fails as expected: {e}")
+
+    print("Waiting for the market to close...")
+    while cluster_time() <= close_ts:
+        time.sleep(1)
+
+    try:
+        buy(bob, market, NO, 10)
+    except Exception as e:
+        print(f"Buying after the close fails as expected: {e}")
+    try:
+        resolve(alice, market, YES)
+    except Exception as e:
+        print(f"Resolving without being the oracle fails as expected: {e}")
+
+    resolve(oracle, market, YES)
+    show_market(market)
+
+    # alice holds all YES shares, so alice gets the whole pool and bob only gets the rent of his position back
+    redeem(alice, market)
+    redeem(bob, market)
@@ -0,0 +1,35 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum PredictionError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Market Address")]
+    InvalidMarketAddress,
+    #[error("Invalid Position Address")]
+    InvalidPositionAddress,
+    #[error("Invalid Market")]
+    InvalidMarket,
+    #[error("Market Closed")]
+    MarketClosed,
+    #[error("Market Still Open")]
+    MarketStillOpen,
+    #[error("Already Resolved")]
+    AlreadyResolved,
+    #[error("Not Resolved")]
+    NotResolved,
+    #[error("Not Oracle")]
+    NotOracle,
+    #[error("Insufficient Shares")]
+    InsufficientShares,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<PredictionError> for ProgramError {
+    fn from(e: PredictionError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,114 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::{error::PredictionError::InvalidInstruction, state::Outcome};
+
+pub enum PredictionInstruction {
+
+    // Creates a market in a PDA derived from ["market", creator, id]. The oracle is the only account which can
+    // resolve it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The creator
+    // 1. `[]` The oracle
+    // 2. `[writable]` The market PDA
+    // 3. `[]` The system program
+    CreateMarket {
+        id: u64,
+        share_price: u64,
+        close_ts: i64,
+    },
+
+    // Buys shares of one side at the share price. The position PDA, derived from ["position", market, buyer],
+    // is created with the first purchase.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The buyer
+    // 1. `[writable]` The market PDA
+    // 2. `[writable]` The position PDA
+    // 3. `[]` The system program
+    Buy {
+        outcome: Outcome,
+        shares: u64,
+    },
+
+    // Sells shares back to the market at the share price, as long as it is open.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The seller
+    // 1. `[writable]` The market PDA
+    // 2. `[writable]` The position PDA
+    Sell {
+        outcome: Outcome,
+        shares: u64,
+    },
+
+    // Resolves the market once it is closed.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The oracle
+    // 1. `[writable]` The market PDA
+    Resolve {
+        outcome: Outcome,
+    },
+
+    // Pays out the share of the pool a position has won, and closes the position.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the position
+    // 1. `[writable]` The market PDA
+    // 2. `[writable]` The position PDA
+    Redeem,
+}
+
+impl PredictionInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateMarket {
+                id: Self::unpack_u64(rest, 0)?,
+                share_price: Self::unpack_u64(rest, 8)?,
+                close_ts: Self::unpack_u64(rest, 16)? as i64,
+            },
+            1 => Self::Buy {
+                outcome: Self::unpack_outcome(rest)?,
+                shares: Self::unpack_u64(rest, 1)?,
+            },
+            2 => Self::Sell {
+                outcome: Self::unpack_outcome(rest)?,
+                shares: Self::unpack_u64(rest, 1)?,
+            },
+            3 => Self::Resolve {
+                outcome: Self::unpack_outcome(rest)?,
+            },
+            4 => Self::Redeem,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    // 0 for NO, 1 for YES
+    fn unpack_outcome(input: &[u8]) -> Result<Outcome, ProgramError> {
+        let value = *input.first().ok_or(InvalidInstruction)?;
+        Ok(Outcome::from_u8(value).ok_or(InvalidInstruction)?)
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,356 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::PredictionInstruction,
+    error::PredictionError,
+    state::{Market, Outcome, Position},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = PredictionInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            PredictionInstruction::CreateMarket { id, share_price, close_ts } => {
+                msg!("Instruction: CreateMarket");
+                Self::process_create_market(accounts, id, share_price, close_ts, program_id)
+            },
+            PredictionInstruction::Buy { outcome, shares } => {
+                msg!("Instruction: Buy");
+                Self::process_buy(accounts, outcome, shares, program_id)
+            },
+            PredictionInstruction::Sell { outcome, shares } => {
+                msg!("Instruction: Sell");
+                Self::process_sell(accounts, outcome, shares, program_id)
+            },
+            PredictionInstruction::Resolve { outcome } => {
+                msg!("Instruction: Resolve");
+                Self::process_resolve(accounts, outcome, program_id)
+            },
+            PredictionInstruction::Redeem => {
+                msg!("Instruction: Redeem");
+                Self::process_redeem(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_market(
+        accounts: &[AccountInfo],
+        id: u64,
+        share_price: u64,
+        close_ts: i64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if share_price == 0 || close_ts <= Clock::get()?.unix_timestamp {
+            return Err(PredictionError::InvalidMarket.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let oracle = next_account_info(account_info_iter)?;
+        let market_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (market_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"market", creator.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if market_pda != *market_account.key {
+            return Err(PredictionError::InvalidMarketAddress.into());
+        }
+
+        if !market_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating market account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                creator.key,
+                market_account.key,
+                Rent::get()?.minimum_balance(Market::LEN),
+                Market::LEN as u64,
+                program_id,
+            ),
+            &[
+                creator.clone(),
+                market_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"market", creator.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        let market_info = Market {
+            is_initialized: true,
+            creator: *creator.key,
+            oracle: *oracle.key,
+            id,
+            share_price,
+            close_ts,
+            yes_shares: 0,
+            no_shares: 0,
+            pool: 0,
+            outcome: None,
+            bump_seed,
+        };
+        market_info.serialize(&mut &mut market_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_buy(
+        accounts: &[AccountInfo],
+        outcome: Outcome,
+        shares: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let buyer = next_account_info(account_info_iter)?;
+
+        if !buyer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let market_account = next_account_info(account_info_iter)?;
+        let mut market_info = Self::unpack_open_market(market_account, program_id)?;
+
+        let position_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (position_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"position", market_account.key.as_ref(), buyer.key.as_ref()],
+            program_id,
+        );
+        if position_pda != *position_account.key {
+            return Err(PredictionError::InvalidPositionAddress.into());
+        }
+
+        let mut position_info = if position_account.data_is_empty() {
+            msg!("Creating position account...");
+            invoke_signed(
+                &system_instruction::create_account(
+                    buyer.key,
+                    position_account.key,
+                    Rent::get()?.minimum_balance(Position::LEN),
+                    Position::LEN as u64,
+                    program_id,
+                ),
+                &[
+                    buyer.clone(),
+                    position_account.clone(),
+                    system_program.clone(),
+                ],
+                &[&[b"position", market_account.key.as_ref(), buyer.key.as_ref(), &[bump_seed]]],
+            )?;
+
+            Position {
+                is_initialized: true,
+                market: *market_account.key,
+                owner: *buyer.key,
+                yes_shares: 0,
+                no_shares: 0,
+                bump_seed,
+            }
+        } else {
+            Self::unpack_position(position_account, program_id)?
+        };
+
+        let cost = shares
+            .checked_mul(market_info.share_price)
+            .ok_or(PredictionError::AmountOverflow)?;
+
+        msg!("Calling the system program to pay {} lamports for {} {:?} shares...", cost, shares, outcome);
+        invoke(
+            &system_instruction::transfer(buyer.key, market_account.key, cost),
+            &[
+                buyer.clone(),
+                market_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        let market_shares = market_info.shares_mut(outcome);
+        *market_shares = market_shares.checked_add(shares).ok_or(PredictionError::AmountOverflow)?;
+        market_info.pool = market_info.pool.checked_add(cost).ok_or(PredictionError::AmountOverflow)?;
+        // the market's total is at least as big as every position, so this can't overflow anymore
+        *position_info.shares_mut(outcome) += shares;
+
+        market_info.serialize(&mut &mut market_account.data.borrow_mut()[..])?;
+        position_info.serialize(&mut &mut position_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_sell(
+        accounts: &[AccountInfo],
+        outcome: Outcome,
+        shares: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let seller = next_account_info(account_info_iter)?;
+
+        if !seller.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let market_account = next_account_info(account_info_iter)?;
+        let mut market_info = Self::unpack_open_market(market_account, program_id)?;
+
+        let position_account = next_account_info(account_info_iter)?;
+        let mut position_info = Self::unpack_position(position_account, program_id)?;
+        if position_info.market != *market_account.key || position_info.owner != *seller.key {
+            return Err(PredictionError::InvalidPositionAddress.into());
+        }
+
+        if shares == 0 || shares > position_info.shares(outcome) {
+            return Err(PredictionError::InsufficientShares.into());
+        }
+
+        // the shares have been bought at the same price, so the pool always holds enough
+        let refund = shares * market_info.share_price;
+
+        msg!("Selling {} {:?} shares for {} lamports...", shares, outcome, refund);
+        **market_account.lamports.borrow_mut() -= refund;
+        **seller.lamports.borrow_mut() = seller.lamports()
+            .checked_add(refund)
+            .ok_or(PredictionError::AmountOverflow)?;
+
+        *market_info.shares_mut(outcome) -= shares;
+        market_info.pool -= refund;
+        *position_info.shares_mut(outcome) -= shares;
+
+        market_info.serialize(&mut &mut market_account.data.borrow_mut()[..])?;
+        position_info.serialize(&mut &mut position_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_resolve(
+        accounts: &[AccountInfo],
+        outcome: Outcome,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let oracle = next_account_info(account_info_iter)?;
+
+        if !oracle.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let market_account = next_account_info(account_info_iter)?;
+        let mut market_info = Self::unpack_market(market_account, program_id)?;
+        if market_info.oracle != *oracle.key {
+            return Err(PredictionError::NotOracle.into());
+        }
+        if market_info.outcome.is_some() {
+            return Err(PredictionError::AlreadyResolved.into());
+        }
+        if Clock::get()?.unix_timestamp < market_info.close_ts {
+            return Err(PredictionError::MarketStillOpen.into());
+        }
+
+        msg!(
+            "Market resolved to {:?}, {} winning shares split a pool of {} lamports",
+            outcome,
+            market_info.shares(outcome),
+            market_info.pool
+        );
+        market_info.outcome = Some(outcome);
+        market_info.serialize(&mut &mut market_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_redeem(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let market_account = next_account_info(account_info_iter)?;
+        let market_info = Self::unpack_market(market_account, program_id)?;
+        let outcome = market_info.outcome.ok_or(PredictionError::NotResolved)?;
+
+        let position_account = next_account_info(account_info_iter)?;
+        let position_info = Self::unpack_position(position_account, program_id)?;
+        if position_info.market != *market_account.key || position_info.owner != *owner.key {
+            return Err(PredictionError::InvalidPositionAddress.into());
+        }
+
+        // the pool doesn't change after the market closed, so every position gets its share of the same pool.
+        // The rounding dust stays in the market.
+        let payout = market_info.payout(outcome, &position_info).ok_or(PredictionError::AmountOverflow)?;
+
+        msg!("Paying out {} lamports and closing the position...", payout);
+        **market_account.lamports.borrow_mut() -= payout;
+        **owner.lamports.borrow_mut() = owner.lamports()
+            .checked_add(payout)
+            .and_then(|lamports| lamports.checked_add(position_account.lamports()))
+            .ok_or(PredictionError::AmountOverflow)?;
+        **position_account.lamports.borrow_mut() = 0;
+        position_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_market(market_account: &AccountInfo, program_id: &Pubkey) -> Result<Market, ProgramError> {
+        if market_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // the outcome is None until the market is resolved, the account is a byte longer than that
+        let market_info = Market::deserialize(&mut &market_account.data.borrow()[..])?;
+        if !market_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(market_info)
+    }
+
+    fn unpack_open_market(market_account: &AccountInfo, program_id: &Pubkey) -> Result<Market, ProgramError> {
+        let market_info = Self::unpack_market(market_account, program_id)?;
+        if Clock::get()?.unix_timestamp >= market_info.close_ts {
+            return Err(PredictionError::MarketClosed.into());
+        }
+
+        Ok(market_info)
+    }
+
+    fn unpack_position(position_account: &AccountInfo, program_id: &Pubkey) -> Result<Position, ProgramError> {
+        if position_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let position_info = Position::try_from_slice(&position_account.data.borrow())?;
+        if !position_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(position_info)
+    }
+}
@@ -0,0 +1,101 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    No,
+    Yes,
+}
+
+impl Outcome {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::No),
+            1 => Some(Self::Yes),
+            _ => None,
+        }
+    }
+}
+
+// A binary market. Every share costs the same fixed price, whichever side it is on. The market PDA itself is the
+// vault, the price of every share bought is added to its lamports on top of the rent.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Market {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    // the only account which can resolve the market
+    pub oracle: Pubkey,
+    pub id: u64,
+    // lamports per share
+    pub share_price: u64,
+    // unix timestamp, shares can only be bought and sold before it, and the market only resolved after it
+    pub close_ts: i64,
+    pub yes_shares: u64,
+    pub no_shares: u64,
+    // lamports paid for all shares that haven't been sold back
+    pub pool: u64,
+    pub outcome: Option<Outcome>,
+    pub bump_seed: u8,
+}
+
+impl Market {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + (1 + 1) + 1;
+
+    pub fn shares(&self, outcome: Outcome) -> u64 {
+        match outcome {
+            Outcome::Yes => self.yes_shares,
+            Outcome::No => self.no_shares,
+        }
+    }
+
+    pub fn shares_mut(&mut self, outcome: Outcome) -> &mut u64 {
+        match outcome {
+            Outcome::Yes => &mut self.yes_shares,
+            Outcome::No => &mut self.no_shares,
+        }
+    }
+
+    // What a position gets out of the pool once the market is resolved. The winning shares split the whole pool,
+    // so the payout per share depends on how many shares the losing side holds. If nobody bet on the winning
+    // side, there is nobody to split the pool between, and every share is refunded at its price instead.
+    pub fn payout(&self, outcome: Outcome, position: &Position) -> Option<u64> {
+        let winning_shares = self.shares(outcome);
+        if winning_shares == 0 {
+            return position.yes_shares
+                .checked_add(position.no_shares)?
+                .checked_mul(self.share_price);
+        }
+
+        let payout = position.shares(outcome) as u128 * self.pool as u128 / winning_shares as u128;
+        u64::try_from(payout).ok()
+    }
+}
+
+// The shares one account holds in a market, in a PDA derived from ["position", market, owner]
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Position {
+    pub is_initialized: bool,
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub yes_shares: u64,
+    pub no_shares: u64,
+    pub bump_seed: u8,
+}
+
+impl Position {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1;
+
+    pub fn shares(&self, outcome: Outcome) -> u64 {
+        match outcome {
+            Outcome::Yes => self.yes_shares,
+            Outcome::No => self.no_shares,
+        }
+    }
+
+    pub fn shares_mut(&mut self, outcome: Outcome) -> &mut u64 {
+        match outcome {
+            Outcome::Yes => &mut self.yes_shares,
+            Outcome::No => &mut self.no_shares,
+        }
+    }
+}
@@ -0,0 +1,282 @@
+import yaml
+import sys
+import json
+import time
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+BALLOT_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'creator': types.fixed_array(types.u8, 32),
+    'id': types.u64,
+    'options': types.u8,
+    'deadline': types.i64,
+    'tally': types.fixed_array(types.u64, 8),
+    'voter_count': types.u64,
+    'bump_seed': types.u8,
+    'finalized': types.u8,
+    'winner': types.option(types.u8)
+})
+
+VOTER_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'ballot': types.fixed_array(types.u8, 32),
+    'voter': types.fixed_array(types.u8, 32),
+    'credits': types.u64,
+    'spent': types.u64,
+    'votes': types.fixed_array(types.u64, 8),
+    'bump_seed': types.u8
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'qv.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'qv-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_ballot_address(creator: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("ballot", encoding="utf8"), bytes(creator), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def get_voter_address(ballot: PublicKey, voter: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("voter", encoding="utf8"), bytes(ballot), bytes(voter)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def cluster_time() -> int:
+    return client.get_block_time(client.get_slot()['result'])['result']
+
+def create_ballot(creator: Keypair, id: int, options: int, deadline: int) -> PublicKey:
+    ballot = get_ballot_address(creator.public_key, id)
+    print(f"\nCreating ballot {ballot} with {options} options")
+    send(
+        creator,
+        (0).to_bytes(1, byteorder='little')
+        + id.to_bytes(8, byteorder='little')
+        + options.to_bytes(1, byteorder='little')
+        + deadline.to_bytes(8, byteorder='little', signed=True),
+        [
+            AccountMeta(pubkey=creator.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=ballot, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return ballot
+
+def register_voter(creator: Keypair, ballot: PublicKey, voter: PublicKey, credits: int):
+    print(f"\nRegistering {voter} with {credits} credits")
+    send(
+        creator,
+        (1).to_bytes(1, byteorder='little') + credits.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=creator.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=ballot, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=voter, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_voter_address(ballot, voter), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def cast_votes(voter: Keypair, ballot: PublicKey, option: int, votes: int):
+    print(f"\n{voter.public_key} puts {votes} votes on option {option}")
+    send(
+        voter,
+        (2).to_bytes(1, byteorder='little') + option.to_bytes(1, byteorder='little') + votes.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=voter.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=ballot, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_voter_address(ballot, voter.public_key), is_signer=False, is_writable=True),
+        ],
+    )
+
+def finalize(caller: Keypair, ballot: PublicKey):
+    print("\nFinalizing the tally")
+    send(
+        caller,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=ballot, is_signer=False, is_writable=True),
+        ],
+    )
+
+def show_voter(ballot: PublicKey, voter: PublicKey):
+    account_info = client.get_account_info(get_voter_address(ballot, voter))["result"]["value"]
+    voter_info = borsh.deserialize(VOTER_SCHEMA, base64.b64decode(account_info["data"][0]))
+    print(f"{voter} spent {voter_info['spent']} of {voter_info['credits']} credits on votes {voter_info['votes']}")
+
+def show_ballot(ballot: PublicKey):
+    account_info = client.get_account_info(ballot)["result"]["value"]
+    ballot_info = borsh.deserialize(BALLOT_SCHEMA, base64.b64decode(account_info["data"][0]))
+    tally = ballot_info['tally'][:ballot_info['options']]
+    print(f"Ballot tally: {tally}, {ballot_info['voter_count']} voters, finalized: {bool(ballot_info['finalized'])}, winner: {ballot_info['winner']}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+    carol = setup_user_keypair()
+
+    deadline = cluster_time() + 30
+    ballot = create_ballot(payer, 0, 3, deadline)
+    for voter in (alice, bob, carol):
+        register_voter(payer, ballot, voter.public_key, 100)
+
+    # Alice cares a lot about option 0, 10 votes use up all of her credits
+    cast_votes(alice, ballot, 0, 10)
+    try:
+        cast_votes(alice, ballot, 1, 1)
+    except Exception as e:
+        print(f"Voting without credits left fails as expected: {e}")
+
+    # Bob and Carol spread their credits, every extra vote on the same option gets more expensive
+    cast_votes(bob, ballot, 1, 5)
+    cast_votes(bob, ballot, 1, 2)
+    cast_votes(bob, ballot, 2, 7)
+    cast_votes(carol, ballot, 1, 6)
+    cast_votes(carol, ballot, 2, 8)
+    for voter in (alice, bob, carol):
+        show_voter(ballot, voter.public_key)
+    show_ballot(ballot)
+
+    try:
+        finalize(payer, ballot)
+    except Exception as e:
+        print(f"Finalizing before the deadline fails as expected: {e}")
+
+    print("Waiting for the deadline...")
+    while cluster_time() <= deadline:
+        time.sleep(1)
+
+    finalize(payer, ballot)
+    show_ballot(ballot)
@@ -0,0 +1,35 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum QvError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Ballot Address")]
+    InvalidBallotAddress,
+    #[error("Invalid Voter Address")]
+    InvalidVoterAddress,
+    #[error("Invalid Option")]
+    InvalidOption,
+    #[error("Invalid Deadline")]
+    InvalidDeadline,
+    #[error("Not Creator")]
+    NotCreator,
+    #[error("Voting Closed")]
+    VotingClosed,
+    #[error("Voting Not Closed")]
+    VotingNotClosed,
+    #[error("Already Finalized")]
+    AlreadyFinalized,
+    #[error("Insufficient Credits")]
+    InsufficientCredits,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<QvError> for ProgramError {
+    fn from(e: QvError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
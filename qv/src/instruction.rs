@@ -0,0 +1,90 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::QvError::InvalidInstruction;
+
+pub enum QvInstruction {
+
+    // Creates a ballot in a PDA derived from ["ballot", creator, id] with 2 to 8 options.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The creator
+    // 1. `[writable]` The ballot PDA
+    // 2. `[]` The system program
+    CreateBallot {
+        id: u64,
+        options: u8,
+        deadline: i64,
+    },
+
+    // Registers a voter with a number of credits in a PDA derived from ["voter", ballot, voter].
+    // The creator pays for the voter account.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The creator
+    // 1. `[writable]` The ballot PDA
+    // 2. `[]` The voter
+    // 3. `[writable]` The voter PDA
+    // 4. `[]` The system program
+    RegisterVoter {
+        credits: u64,
+    },
+
+    // Adds votes to an option, paid with credits. Having n votes on an option costs n² credits.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The voter
+    // 1. `[writable]` The ballot PDA
+    // 2. `[writable]` The voter PDA
+    CastVotes {
+        option: u8,
+        votes: u64,
+    },
+
+    // Finalizes the tally once the deadline has passed and stores the winning option.
+    // Anybody may call it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The ballot PDA
+    Finalize,
+}
+
+impl QvInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateBallot {
+                id: Self::unpack_u64(rest, 0)?,
+                options: *rest.get(8).ok_or(InvalidInstruction)?,
+                deadline: Self::unpack_u64(rest, 9)? as i64,
+            },
+            1 => Self::RegisterVoter {
+                credits: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::CastVotes {
+                option: *rest.first().ok_or(InvalidInstruction)?,
+                votes: Self::unpack_u64(rest, 1)?,
+            },
+            3 => Self::Finalize,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
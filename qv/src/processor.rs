@@ -0,0 +1,299 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::QvInstruction,
+    error::QvError,
+    state::{cost, Ballot, Voter, MAX_OPTIONS},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = QvInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            QvInstruction::CreateBallot { id, options, deadline } => {
+                msg!("Instruction: CreateBallot");
+                Self::process_create_ballot(accounts, id, options, deadline, program_id)
+            },
+            QvInstruction::RegisterVoter { credits } => {
+                msg!("Instruction: RegisterVoter");
+                Self::process_register_voter(accounts, credits, program_id)
+            },
+            QvInstruction::CastVotes { option, votes } => {
+                msg!("Instruction: CastVotes");
+                Self::process_cast_votes(accounts, option, votes, program_id)
+            },
+            QvInstruction::Finalize => {
+                msg!("Instruction: Finalize");
+                Self::process_finalize(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_ballot(
+        accounts: &[AccountInfo],
+        id: u64,
+        options: u8,
+        deadline: i64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if options < 2 || options as usize > MAX_OPTIONS {
+            return Err(QvError::InvalidOption.into());
+        }
+        if deadline <= Clock::get()?.unix_timestamp {
+            return Err(QvError::InvalidDeadline.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let ballot_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (ballot_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"ballot", creator.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if ballot_pda != *ballot_account.key {
+            return Err(QvError::InvalidBallotAddress.into());
+        }
+
+        if !ballot_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating ballot account with {} options...", options);
+        Self::create_pda_account(
+            creator,
+            ballot_account,
+            system_program,
+            Ballot::LEN,
+            program_id,
+            &[b"ballot", creator.key.as_ref(), &id_bytes, &[bump_seed]],
+        )?;
+
+        let ballot_info = Ballot {
+            is_initialized: true,
+            creator: *creator.key,
+            id,
+            options,
+            deadline,
+            tally: [0; MAX_OPTIONS],
+            voter_count: 0,
+            bump_seed,
+            finalized: false,
+            winner: None,
+        };
+        ballot_info.serialize(&mut &mut ballot_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_register_voter(
+        accounts: &[AccountInfo],
+        credits: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let ballot_account = next_account_info(account_info_iter)?;
+        let mut ballot_info = Self::unpack_ballot(ballot_account, program_id)?;
+        if ballot_info.creator != *creator.key {
+            return Err(QvError::NotCreator.into());
+        }
+        if Clock::get()?.unix_timestamp > ballot_info.deadline {
+            return Err(QvError::VotingClosed.into());
+        }
+
+        let voter = next_account_info(account_info_iter)?;
+        let voter_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (voter_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"voter", ballot_account.key.as_ref(), voter.key.as_ref()],
+            program_id,
+        );
+        if voter_pda != *voter_account.key {
+            return Err(QvError::InvalidVoterAddress.into());
+        }
+
+        // the voter PDA can only be created once, so nobody gets their credits twice
+        if !voter_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Registering {} with {} credits...", voter.key, credits);
+        Self::create_pda_account(
+            creator,
+            voter_account,
+            system_program,
+            Voter::LEN,
+            program_id,
+            &[b"voter", ballot_account.key.as_ref(), voter.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let voter_info = Voter {
+            is_initialized: true,
+            ballot: *ballot_account.key,
+            voter: *voter.key,
+            credits,
+            spent: 0,
+            votes: [0; MAX_OPTIONS],
+            bump_seed,
+        };
+        voter_info.serialize(&mut &mut voter_account.data.borrow_mut()[..])?;
+
+        ballot_info.voter_count += 1;
+        ballot_info.serialize(&mut &mut ballot_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_cast_votes(
+        accounts: &[AccountInfo],
+        option: u8,
+        votes: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let voter = next_account_info(account_info_iter)?;
+
+        if !voter.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let ballot_account = next_account_info(account_info_iter)?;
+        let mut ballot_info = Self::unpack_ballot(ballot_account, program_id)?;
+        if Clock::get()?.unix_timestamp > ballot_info.deadline {
+            return Err(QvError::VotingClosed.into());
+        }
+        if votes == 0 || option >= ballot_info.options {
+            return Err(QvError::InvalidOption.into());
+        }
+
+        let voter_account = next_account_info(account_info_iter)?;
+        if voter_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let mut voter_info = Voter::try_from_slice(&voter_account.data.borrow())?;
+        if !voter_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if voter_info.ballot != *ballot_account.key || voter_info.voter != *voter.key {
+            return Err(QvError::InvalidVoterAddress.into());
+        }
+
+        let option = option as usize;
+        let price = cost(voter_info.votes[option], votes).ok_or(QvError::AmountOverflow)?;
+        let spent = voter_info.spent.checked_add(price).ok_or(QvError::AmountOverflow)?;
+        if spent > voter_info.credits {
+            return Err(QvError::InsufficientCredits.into());
+        }
+
+        msg!("Adding {} votes to option {} for {} credits...", votes, option, price);
+        voter_info.spent = spent;
+        voter_info.votes[option] += votes;
+        voter_info.serialize(&mut &mut voter_account.data.borrow_mut()[..])?;
+
+        ballot_info.tally[option] = ballot_info.tally[option]
+            .checked_add(votes)
+            .ok_or(QvError::AmountOverflow)?;
+        ballot_info.serialize(&mut &mut ballot_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_finalize(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let ballot_account = next_account_info(account_info_iter)?;
+        let mut ballot_info = Self::unpack_ballot(ballot_account, program_id)?;
+
+        if Clock::get()?.unix_timestamp <= ballot_info.deadline {
+            return Err(QvError::VotingNotClosed.into());
+        }
+        if ballot_info.finalized {
+            return Err(QvError::AlreadyFinalized.into());
+        }
+
+        msg!("Final tally: {:?}", &ballot_info.tally[..ballot_info.options as usize]);
+        ballot_info.finalized = true;
+        ballot_info.winner = ballot_info.leader();
+        match ballot_info.winner {
+            Some(option) => msg!("Option {} wins", option),
+            None => msg!("No option wins"),
+        }
+        ballot_info.serialize(&mut &mut ballot_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn unpack_ballot(ballot_account: &AccountInfo, program_id: &Pubkey) -> Result<Ballot, ProgramError> {
+        if ballot_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // while the ballot is open the winner is None, a byte short of the account
+        let ballot_info = Ballot::deserialize(&mut &ballot_account.data.borrow()[..])?;
+        if !ballot_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(ballot_info)
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,61 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Most options a ballot can have, the tallies are stored in fixed size arrays
+pub const MAX_OPTIONS: usize = 8;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Ballot {
+    pub is_initialized: bool,
+    // the creator registers the voters and hands out their credits
+    pub creator: Pubkey,
+    pub id: u64,
+    pub options: u8,
+    // unix timestamp after which no more votes are accepted and the tally can be finalized
+    pub deadline: i64,
+    // votes per option, only the first `options` entries are used
+    pub tally: [u64; MAX_OPTIONS],
+    pub voter_count: u64,
+    pub bump_seed: u8,
+    pub finalized: bool,
+    // stays empty on a tie or if nobody voted
+    pub winner: Option<u8>,
+}
+
+impl Ballot {
+    pub const LEN: usize = 1 + 32 + 8 + 1 + 8 + 8 * MAX_OPTIONS + 8 + 1 + 1 + (1 + 1);
+
+    // the option with the most votes, None if several options share the highest count
+    pub fn leader(&self) -> Option<u8> {
+        let tally = &self.tally[..self.options as usize];
+        let highest = tally.iter().copied().max().unwrap_or(0);
+        if highest == 0 || tally.iter().filter(|votes| **votes == highest).count() > 1 {
+            return None;
+        }
+        tally.iter().position(|votes| *votes == highest).map(|option| option as u8)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Voter {
+    pub is_initialized: bool,
+    pub ballot: Pubkey,
+    pub voter: Pubkey,
+    // credits handed out by the creator and credits spent so far
+    pub credits: u64,
+    pub spent: u64,
+    // votes this voter put on each option
+    pub votes: [u64; MAX_OPTIONS],
+    pub bump_seed: u8,
+}
+
+impl Voter {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 * MAX_OPTIONS + 1;
+}
+
+// Quadratic voting: n votes on one option cost n² credits. Adding votes to an option
+// costs the difference, so it doesn't matter whether they are cast at once or one by one.
+pub fn cost(current: u64, additional: u64) -> Option<u64> {
+    let total = current.checked_add(additional)?;
+    total.checked_mul(total)?.checked_sub(current * current)
+}
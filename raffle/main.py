@@ -0,0 +1,261 @@
+import yaml
+import sys
+import json
+import time
+import base64
+import struct
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'raffle.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'raffle-keypair.json'))
+
+SYSVAR_SLOT_HASHES = PublicKey("SysvarS1otHashes111111111111111111111111111")
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_raffle_address(creator: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("raffle", encoding="utf8"), bytes(creator), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def get_vault_address(raffle: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("vault", encoding="utf8"), bytes(raffle)], program_id)[0]
+
+def get_participant_address(raffle: PublicKey, participant: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("participant", encoding="utf8"), bytes(raffle), bytes(participant)],
+        program_id
+    )[0]
+
+def init_raffle(creator: Keypair, id: int, ticket_price: int, deadline: int) -> PublicKey:
+    raffle = get_raffle_address(creator.public_key, id)
+    print(f"\nCreating raffle {raffle} with a ticket price of {ticket_price} lamports until {deadline}")
+    init_ix = TransactionInstruction(
+        data=(
+            (0).to_bytes(1, byteorder='little')
+            + id.to_bytes(8, byteorder='little')
+            + ticket_price.to_bytes(8, byteorder='little')
+            + deadline.to_bytes(8, byteorder='little', signed=True)
+        ),
+        keys=[
+            AccountMeta(pubkey=creator.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=raffle, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_vault_address(raffle), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(init_ix), creator)
+    client.confirm_transaction(tx["result"])
+    return raffle
+
+def buy_tickets(buyer: Keypair, raffle: PublicKey, count: int):
+    print(f"\n{buyer.public_key} buys {count} tickets")
+    buy_ix = TransactionInstruction(
+        data=(1).to_bytes(1, byteorder='little') + count.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=buyer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=raffle, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_participant_address(raffle, buyer.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_vault_address(raffle), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(buy_ix), buyer)
+    client.confirm_transaction(tx["result"])
+
+def draw(caller: Keypair, raffle: PublicKey):
+    print("\nDrawing the winning ticket")
+    draw_ix = TransactionInstruction(
+        data=(2).to_bytes(1, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=raffle, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYSVAR_SLOT_HASHES, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(draw_ix), caller)
+    client.confirm_transaction(tx["result"])
+
+def claim_prize(winner: Keypair, raffle: PublicKey, creator: PublicKey):
+    print(f"\n{winner.public_key} claims the prize")
+    claim_ix = TransactionInstruction(
+        data=(3).to_bytes(1, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=winner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=raffle, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_participant_address(raffle, winner.public_key), is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_vault_address(raffle), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=creator, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(claim_ix), winner)
+    client.confirm_transaction(tx["result"])
+
+def get_winning_ticket(raffle: PublicKey) -> int:
+    data = base64.b64decode(client.get_account_info(raffle)['result']['value']['data'][0])
+    # is_initialized, creator, id, ticket_price, deadline and total_tickets come before the Option tag
+    has_winner, winning_ticket = struct.unpack_from("<?Q", data, 1 + 32 + 8 * 4)
+    return winning_ticket if has_winner else None
+
+def get_ticket_range(raffle: PublicKey, participant: PublicKey) -> range:
+    data = base64.b64decode(client.get_account_info(get_participant_address(raffle, participant))['result']['value']['data'][0])
+    first_ticket, ticket_count = struct.unpack_from("<QQ", data, 1 + 32 + 32)
+    return range(first_ticket, first_ticket + ticket_count)
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+
+    deadline = int(time.time()) + 15
+    raffle = init_raffle(payer, 0, LAMPORTS_PER_SOL // 10, deadline)
+
+    # alice holds three of the four tickets, a 75% chance to win
+    buy_tickets(alice, raffle, 3)
+    buy_tickets(bob, raffle, 1)
+
+    try:
+        draw(payer, raffle)
+    except Exception as e:
+        print(f"Drawing before the deadline fails as expected: {e}")
+
+    print("Waiting for the deadline...")
+    time.sleep(20)
+    draw(payer, raffle)
+    winning_ticket = get_winning_ticket(raffle)
+    print(f"Winning ticket: {winning_ticket}")
+
+    winner = next(user for user in [alice, bob] if winning_ticket in get_ticket_range(raffle, user.public_key))
+    claim_prize(winner, raffle, payer.public_key)
+    print(f"Winner balance: {client.get_balance(winner.public_key)['result']['value']} lamports")
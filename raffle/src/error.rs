@@ -0,0 +1,39 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum RaffleError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Raffle Address")]
+    InvalidRaffleAddress,
+    #[error("Invalid Vault Address")]
+    InvalidVaultAddress,
+    #[error("Invalid Participant Address")]
+    InvalidParticipantAddress,
+    #[error("Deadline In The Past")]
+    DeadlineInPast,
+    #[error("Raffle Closed")]
+    RaffleClosed,
+    #[error("Raffle Still Open")]
+    RaffleStillOpen,
+    #[error("Already Entered")]
+    AlreadyEntered,
+    #[error("No Tickets")]
+    NoTickets,
+    #[error("Already Drawn")]
+    AlreadyDrawn,
+    #[error("Not Drawn")]
+    NotDrawn,
+    #[error("Not The Winner")]
+    NotTheWinner,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<RaffleError> for ProgramError {
+    fn from(e: RaffleError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,89 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::RaffleError::InvalidInstruction;
+
+pub enum RaffleInstruction {
+
+    // Creates a raffle in a PDA derived from ["raffle", creator, id]. The ticket sales are collected in a vault,
+    // a system account PDA derived from ["vault", raffle], which gets funded with its rent by the creator.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The creator
+    // 1. `[writable]` The raffle PDA
+    // 2. `[writable]` The vault PDA
+    // 3. `[]` The system program
+    InitRaffle {
+        id: u64,
+        ticket_price: u64,
+        deadline: i64,
+    },
+
+    // Buys a number of tickets. Every buyer enters once, the tickets are recorded in a participant PDA derived
+    // from ["participant", raffle, buyer].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The buyer
+    // 1. `[writable]` The raffle PDA
+    // 2. `[writable]` The participant PDA
+    // 3. `[writable]` The vault PDA
+    // 4. `[]` The system program
+    BuyTickets {
+        count: u64,
+    },
+
+    // Draws the winning ticket after the deadline. Anyone may call it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The raffle PDA
+    // 1. `[]` The SlotHashes sysvar
+    Draw,
+
+    // Pays the pot to the winner and closes the raffle, the remaining lamports go back to the creator.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The winner
+    // 1. `[writable]` The raffle PDA
+    // 2. `[]` The participant PDA of the winner
+    // 3. `[writable]` The vault PDA
+    // 4. `[writable]` The creator
+    // 5. `[]` The system program
+    ClaimPrize,
+}
+
+impl RaffleInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitRaffle {
+                id: Self::unpack_u64(rest, 0)?,
+                ticket_price: Self::unpack_u64(rest, 8)?,
+                deadline: Self::unpack_u64(rest, 16)? as i64,
+            },
+            1 => Self::BuyTickets {
+                count: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::Draw,
+            3 => Self::ClaimPrize,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,357 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::RaffleInstruction,
+    error::RaffleError,
+    state::{Participant, Raffle},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = RaffleInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            RaffleInstruction::InitRaffle { id, ticket_price, deadline } => {
+                msg!("Instruction: InitRaffle");
+                Self::process_init_raffle(accounts, id, ticket_price, deadline, program_id)
+            },
+            RaffleInstruction::BuyTickets { count } => {
+                msg!("Instruction: BuyTickets");
+                Self::process_buy_tickets(accounts, count, program_id)
+            },
+            RaffleInstruction::Draw => {
+                msg!("Instruction: Draw");
+                Self::process_draw(accounts, program_id)
+            },
+            RaffleInstruction::ClaimPrize => {
+                msg!("Instruction: ClaimPrize");
+                Self::process_claim_prize(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_init_raffle(
+        accounts: &[AccountInfo],
+        id: u64,
+        ticket_price: u64,
+        deadline: i64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if deadline <= Clock::get()?.unix_timestamp {
+            return Err(RaffleError::DeadlineInPast.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let raffle_account = next_account_info(account_info_iter)?;
+        let vault = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (raffle_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"raffle", creator.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if raffle_pda != *raffle_account.key {
+            return Err(RaffleError::InvalidRaffleAddress.into());
+        }
+
+        let (vault_pda, vault_bump_seed) = Pubkey::find_program_address(&[b"vault", raffle_pda.as_ref()], program_id);
+        if vault_pda != *vault.key {
+            return Err(RaffleError::InvalidVaultAddress.into());
+        }
+
+        if !raffle_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let rent = Rent::get()?;
+
+        msg!("Creating raffle account...");
+        Self::create_pda_account(
+            creator,
+            raffle_account,
+            system_program,
+            Raffle::LEN,
+            program_id,
+            &[b"raffle", creator.key.as_ref(), &id_bytes, &[bump_seed]],
+        )?;
+
+        // the vault stays a data-less system account. A system account has to be rent exempt as soon as it
+        // holds lamports, so it is seeded with the rent before the first ticket is sold.
+        msg!("Funding the vault with its rent...");
+        invoke(
+            &system_instruction::transfer(creator.key, vault.key, rent.minimum_balance(0)),
+            &[creator.clone(), vault.clone(), system_program.clone()],
+        )?;
+
+        let raffle_info = Raffle {
+            is_initialized: true,
+            creator: *creator.key,
+            id,
+            ticket_price,
+            deadline,
+            total_tickets: 0,
+            winning_ticket: None,
+            bump_seed,
+            vault_bump_seed,
+        };
+        raffle_info.serialize(&mut &mut raffle_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_buy_tickets(
+        accounts: &[AccountInfo],
+        count: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if count == 0 {
+            return Err(RaffleError::NoTickets.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let buyer = next_account_info(account_info_iter)?;
+
+        if !buyer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let raffle_account = next_account_info(account_info_iter)?;
+        let mut raffle_info = Self::unpack_raffle(raffle_account, program_id)?;
+
+        if Clock::get()?.unix_timestamp >= raffle_info.deadline {
+            return Err(RaffleError::RaffleClosed.into());
+        }
+
+        let participant_account = next_account_info(account_info_iter)?;
+        let (participant_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"participant", raffle_account.key.as_ref(), buyer.key.as_ref()],
+            program_id,
+        );
+        if participant_pda != *participant_account.key {
+            return Err(RaffleError::InvalidParticipantAddress.into());
+        }
+
+        if !participant_account.data_is_empty() {
+            return Err(RaffleError::AlreadyEntered.into());
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        Self::check_vault(vault, raffle_account, &raffle_info, program_id)?;
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        let price = raffle_info.ticket_price
+            .checked_mul(count)
+            .ok_or(RaffleError::AmountOverflow)?;
+
+        msg!("Creating participant account...");
+        Self::create_pda_account(
+            buyer,
+            participant_account,
+            system_program,
+            Participant::LEN,
+            program_id,
+            &[b"participant", raffle_account.key.as_ref(), buyer.key.as_ref(), &[bump_seed]],
+        )?;
+
+        msg!("Calling the system program to pay {} lamports for {} tickets...", price, count);
+        invoke(
+            &system_instruction::transfer(buyer.key, vault.key, price),
+            &[buyer.clone(), vault.clone(), system_program.clone()],
+        )?;
+
+        let participant_info = Participant {
+            is_initialized: true,
+            raffle: *raffle_account.key,
+            participant: *buyer.key,
+            first_ticket: raffle_info.total_tickets,
+            ticket_count: count,
+            bump_seed,
+        };
+        participant_info.serialize(&mut &mut participant_account.data.borrow_mut()[..])?;
+
+        raffle_info.total_tickets = raffle_info.total_tickets
+            .checked_add(count)
+            .ok_or(RaffleError::AmountOverflow)?;
+        raffle_info.serialize(&mut &mut raffle_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_draw(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let raffle_account = next_account_info(account_info_iter)?;
+        let mut raffle_info = Self::unpack_raffle(raffle_account, program_id)?;
+
+        if Clock::get()?.unix_timestamp < raffle_info.deadline {
+            return Err(RaffleError::RaffleStillOpen.into());
+        }
+        if raffle_info.winning_ticket.is_some() {
+            return Err(RaffleError::AlreadyDrawn.into());
+        }
+        if raffle_info.total_tickets == 0 {
+            return Err(RaffleError::NoTickets.into());
+        }
+
+        // The slot hash is known to the validator producing the block, so this is fine for an example but not for a
+        // raffle with real money, see the entropy crate
+        let slot_hashes = next_account_info(account_info_iter)?;
+        let winning_ticket = entropy::random_below(slot_hashes, &[raffle_account.key.as_ref()], raffle_info.total_tickets)?;
+        msg!("Winning ticket: {} of {}", winning_ticket, raffle_info.total_tickets);
+
+        raffle_info.winning_ticket = Some(winning_ticket);
+        raffle_info.serialize(&mut &mut raffle_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_claim_prize(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let winner = next_account_info(account_info_iter)?;
+
+        if !winner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let raffle_account = next_account_info(account_info_iter)?;
+        let raffle_info = Self::unpack_raffle(raffle_account, program_id)?;
+        let winning_ticket = raffle_info.winning_ticket.ok_or(RaffleError::NotDrawn)?;
+
+        let participant_account = next_account_info(account_info_iter)?;
+        if participant_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let participant_info = Participant::try_from_slice(&participant_account.data.borrow())?;
+        if participant_info.raffle != *raffle_account.key || participant_info.participant != *winner.key {
+            return Err(RaffleError::InvalidParticipantAddress.into());
+        }
+        if !participant_info.holds(winning_ticket) {
+            return Err(RaffleError::NotTheWinner.into());
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        Self::check_vault(vault, raffle_account, &raffle_info, program_id)?;
+
+        let creator = next_account_info(account_info_iter)?;
+        if *creator.key != raffle_info.creator {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        let pot = raffle_info.pot().ok_or(RaffleError::AmountOverflow)?;
+        let vault_seeds: &[&[u8]] = &[b"vault", raffle_account.key.as_ref(), &[raffle_info.vault_bump_seed]];
+
+        // the vault is owned by the system program, the program can only move its lamports through a
+        // system transfer signed with the vault seeds
+        msg!("Calling the system program to pay the pot of {} lamports...", pot);
+        invoke_signed(
+            &system_instruction::transfer(vault.key, winner.key, pot),
+            &[vault.clone(), winner.clone(), system_program.clone()],
+            &[vault_seeds],
+        )?;
+
+        msg!("Returning the vault rent to the creator...");
+        invoke_signed(
+            &system_instruction::transfer(vault.key, creator.key, vault.lamports()),
+            &[vault.clone(), creator.clone(), system_program.clone()],
+            &[vault_seeds],
+        )?;
+
+        msg!("Closing the raffle account...");
+        **creator.lamports.borrow_mut() = creator.lamports()
+            .checked_add(raffle_account.lamports())
+            .ok_or(RaffleError::AmountOverflow)?;
+        **raffle_account.lamports.borrow_mut() = 0;
+        raffle_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_raffle(raffle_account: &AccountInfo, program_id: &Pubkey) -> Result<Raffle, ProgramError> {
+        if raffle_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // the winning ticket is None until the draw, the last 8 bytes of the raffle are unused until then
+        let raffle_info = Raffle::deserialize(&mut &raffle_account.data.borrow()[..])?;
+        if !raffle_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(raffle_info)
+    }
+
+    fn check_vault(
+        vault: &AccountInfo,
+        raffle_account: &AccountInfo,
+        raffle_info: &Raffle,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let vault_pda = Pubkey::create_program_address(
+            &[b"vault", raffle_account.key.as_ref(), &[raffle_info.vault_bump_seed]],
+            program_id,
+        )?;
+        if vault_pda != *vault.key {
+            return Err(RaffleError::InvalidVaultAddress.into());
+        }
+
+        Ok(())
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,47 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Raffle {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub id: u64,
+    // price of a single ticket in lamports
+    pub ticket_price: u64,
+    // tickets can be bought until this unix timestamp
+    pub deadline: i64,
+    pub total_tickets: u64,
+    // set by Draw, the ticket number in 0..total_tickets that wins the pot
+    pub winning_ticket: Option<u64>,
+    pub bump_seed: u8,
+    pub vault_bump_seed: u8,
+}
+
+impl Raffle {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 8 + (1 + 8) + 1 + 1;
+
+    // lamports collected from ticket sales
+    pub fn pot(&self) -> Option<u64> {
+        self.ticket_price.checked_mul(self.total_tickets)
+    }
+}
+
+// A participant owns the consecutive ticket numbers first_ticket..first_ticket + ticket_count,
+// the more tickets, the higher the chance that the winning number falls into the range.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Participant {
+    pub is_initialized: bool,
+    pub raffle: Pubkey,
+    pub participant: Pubkey,
+    pub first_ticket: u64,
+    pub ticket_count: u64,
+    pub bump_seed: u8,
+}
+
+impl Participant {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 1;
+
+    pub fn holds(&self, ticket: u64) -> bool {
+        ticket >= self.first_ticket && ticket - self.first_ticket < self.ticket_count
+    }
+}
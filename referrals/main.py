@@ -0,0 +1,242 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+REGISTRATION_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'user': types.fixed_array(types.u8, 32),
+    'referrer': types.option(types.fixed_array(types.u8, 32)),
+    'referral_count': types.u64,
+    'rewards': types.u64,
+    'total_earned': types.u64,
+    'bump_seed': types.u8
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'referrals.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'referrals-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def get_vault_address() -> PublicKey:
+    return PublicKey.find_program_address([bytes("vault", encoding="utf8")], program_id)[0]
+
+def get_registration_address(user: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("registration", encoding="utf8"), bytes(user)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def init_vault():
+    vault = get_vault_address()
+    if client.get_account_info(vault)["result"]["value"] is not None:
+        print(f"Vault {vault} already exists")
+        return
+
+    print(f"\nCreating vault {vault}")
+    send(
+        payer,
+        (0).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=payer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def register(user: Keypair, referrer: PublicKey = None):
+    print(f"\nRegistering {user.public_key}" + (f" referred by {referrer}" if referrer else ""))
+    keys = [
+        AccountMeta(pubkey=user.public_key, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=get_registration_address(user.public_key), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+    ]
+    if referrer:
+        keys.append(AccountMeta(pubkey=get_registration_address(referrer), is_signer=False, is_writable=True))
+    send(user, (1).to_bytes(1, byteorder='little'), keys)
+
+def credit(reward_payer: Keypair, user: PublicKey, referrer: PublicKey, amount: int):
+    # usually another program sends this instruction with a CPI when the user does something worth a reward
+    print(f"\nCrediting {amount} lamports to the referrer of {user}")
+    send(
+        reward_payer,
+        (2).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=reward_payer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_registration_address(user), is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_registration_address(referrer), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_vault_address(), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def claim(referrer: Keypair):
+    print(f"\n{referrer.public_key} claims the rewards")
+    send(
+        referrer,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=referrer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_registration_address(referrer.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_vault_address(), is_signer=False, is_writable=True),
+        ],
+    )
+
+def show_registration(user: PublicKey):
+    account_info = client.get_account_info(get_registration_address(user))["result"]["value"]
+    registration_info = borsh.deserialize(REGISTRATION_SCHEMA, base64.b64decode(account_info["data"][0]))
+    referrer = PublicKey(registration_info['referrer']) if registration_info['referrer'] else "nobody"
+    print(f"{user} was referred by {referrer}, referred {registration_info['referral_count']} users, "
+          f"has {registration_info['rewards']} unclaimed of {registration_info['total_earned']} lamports earned")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    init_vault()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+    register(alice)
+    register(bob, alice.public_key)
+
+    credit(payer, bob.public_key, alice.public_key, LAMPORTS_PER_SOL // 100)
+    credit(payer, bob.public_key, alice.public_key, LAMPORTS_PER_SOL // 50)
+    show_registration(alice.public_key)
+    show_registration(bob.public_key)
+
+    try:
+        credit(payer, alice.public_key, bob.public_key, LAMPORTS_PER_SOL // 100)
+    except Exception as e:
+        print(f"Alice registered without a referrer, nobody can be credited: {e}")
+
+    balance = client.get_balance(alice.public_key)["result"]["value"]
+    claim(alice)
+    print(f"Alice received {client.get_balance(alice.public_key)['result']['value'] - balance} lamports, minus the transaction fee")
+    show_registration(alice.public_key)
@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum ReferralError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Registration Address")]
+    InvalidRegistrationAddress,
+    #[error("Invalid Vault Address")]
+    InvalidVaultAddress,
+    #[error("Self Referral")]
+    SelfReferral,
+    #[error("No Referrer")]
+    NoReferrer,
+    #[error("Wrong Referrer")]
+    WrongReferrer,
+    #[error("Nothing To Claim")]
+    NothingToClaim,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<ReferralError> for ProgramError {
+    fn from(e: ReferralError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,113 @@
+use std::convert::TryInto;
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::error::ReferralError::InvalidInstruction;
+
+pub enum ReferralInstruction {
+
+    // Creates the vault, a data-less PDA derived from ["vault"] holding all rewards which haven't been claimed yet.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The payer
+    // 1. `[writable]` The vault PDA
+    // 2. `[]` The system program
+    InitVault,
+
+    // Registers a user in a PDA derived from ["registration", user]. The referrer is optional and has to be
+    // registered already.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The user
+    // 1. `[writable]` The registration PDA of the user
+    // 2. `[]` The system program
+    // 3. `[writable]` Optional, the registration PDA of the referrer
+    Register,
+
+    // Credits the referrer of a user with a reward. The payer transfers the reward into the vault, so every
+    // credited lamport is backed. Meant to be called by other programs through a CPI, see `credit`.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The payer of the reward
+    // 1. `[]` The registration PDA of the user who performed the action
+    // 2. `[writable]` The registration PDA of the user's referrer
+    // 3. `[writable]` The vault PDA
+    // 4. `[]` The system program
+    Credit {
+        amount: u64,
+    },
+
+    // Pays out all rewards of a referrer from the vault.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The referrer
+    // 1. `[writable]` The registration PDA of the referrer
+    // 2. `[writable]` The vault PDA
+    Claim,
+}
+
+impl ReferralInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitVault,
+            1 => Self::Register,
+            2 => Self::Credit {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            3 => Self::Claim,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
+
+// Builds a Credit instruction, for programs which want to reward the referrer of a user through a CPI
+pub fn credit(
+    referrals_program_id: &Pubkey,
+    payer: &Pubkey,
+    user: &Pubkey,
+    referrer: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut data = vec![2];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    let registration = |user: &Pubkey| {
+        Pubkey::find_program_address(&[b"registration", user.as_ref()], referrals_program_id).0
+    };
+    let (vault, _) = Pubkey::find_program_address(&[b"vault"], referrals_program_id);
+
+    Instruction {
+        program_id: *referrals_program_id,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(registration(user), false),
+            AccountMeta::new(registration(referrer), false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data,
+    }
+}
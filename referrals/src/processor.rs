@@ -0,0 +1,268 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::BorshSerialize;
+
+use crate::{instruction::ReferralInstruction, error::ReferralError, state::Registration};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = ReferralInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            ReferralInstruction::InitVault => {
+                msg!("Instruction: InitVault");
+                Self::process_init_vault(accounts, program_id)
+            },
+            ReferralInstruction::Register => {
+                msg!("Instruction: Register");
+                Self::process_register(accounts, program_id)
+            },
+            ReferralInstruction::Credit { amount } => {
+                msg!("Instruction: Credit");
+                Self::process_credit(accounts, amount, program_id)
+            },
+            ReferralInstruction::Claim => {
+                msg!("Instruction: Claim");
+                Self::process_claim(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_init_vault(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer = next_account_info(account_info_iter)?;
+
+        if !payer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (vault_pda, bump_seed) = Pubkey::find_program_address(&[b"vault"], program_id);
+        if vault_pda != *vault.key {
+            return Err(ReferralError::InvalidVaultAddress.into());
+        }
+
+        if vault.owner == program_id {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the vault has no data, it only has to be owned by the program so that the program can take lamports from it
+        msg!("Creating vault account...");
+        Self::create_pda_account(payer, vault, system_program, 0, program_id, &[b"vault", &[bump_seed]])
+    }
+
+    fn process_register(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user = next_account_info(account_info_iter)?;
+
+        if !user.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let registration_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (registration_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"registration", user.key.as_ref()],
+            program_id,
+        );
+        if registration_pda != *registration_account.key {
+            return Err(ReferralError::InvalidRegistrationAddress.into());
+        }
+
+        if !registration_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the referrer is the last account and may be left out
+        let referrer = match next_account_info(account_info_iter) {
+            Ok(referrer_account) => {
+                let mut referrer_info = Registration::from_account_info(referrer_account, program_id)?;
+                if referrer_info.user == *user.key {
+                    return Err(ReferralError::SelfReferral.into());
+                }
+
+                referrer_info.referral_count = referrer_info.referral_count
+                    .checked_add(1)
+                    .ok_or(ReferralError::AmountOverflow)?;
+                referrer_info.serialize(&mut &mut referrer_account.data.borrow_mut()[..])?;
+
+                msg!("{} was referred by {}", user.key, referrer_info.user);
+                Some(referrer_info.user)
+            },
+            Err(_) => None,
+        };
+
+        msg!("Creating registration account...");
+        Self::create_pda_account(
+            user,
+            registration_account,
+            system_program,
+            Registration::LEN,
+            program_id,
+            &[b"registration", user.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let registration_info = Registration {
+            is_initialized: true,
+            user: *user.key,
+            referrer,
+            referral_count: 0,
+            rewards: 0,
+            total_earned: 0,
+            bump_seed,
+        };
+        registration_info.serialize(&mut &mut registration_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_credit(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer = next_account_info(account_info_iter)?;
+
+        if !payer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let user_registration = next_account_info(account_info_iter)?;
+        let user_info = Registration::from_account_info(user_registration, program_id)?;
+        let referrer_key = user_info.referrer.ok_or(ReferralError::NoReferrer)?;
+
+        let referrer_registration = next_account_info(account_info_iter)?;
+        let mut referrer_info = Registration::from_account_info(referrer_registration, program_id)?;
+        if referrer_info.user != referrer_key {
+            return Err(ReferralError::WrongReferrer.into());
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        Self::check_vault(vault, program_id)?;
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to move a reward of {} lamports into the vault...", amount);
+        invoke(
+            &system_instruction::transfer(payer.key, vault.key, amount),
+            &[
+                payer.clone(),
+                vault.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        referrer_info.rewards = referrer_info.rewards
+            .checked_add(amount)
+            .ok_or(ReferralError::AmountOverflow)?;
+        referrer_info.total_earned = referrer_info.total_earned
+            .checked_add(amount)
+            .ok_or(ReferralError::AmountOverflow)?;
+        referrer_info.serialize(&mut &mut referrer_registration.data.borrow_mut()[..])?;
+
+        msg!("Credited {} lamports to {} for referring {}", amount, referrer_key, user_info.user);
+        Ok(())
+    }
+
+    fn process_claim(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let referrer = next_account_info(account_info_iter)?;
+
+        if !referrer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let registration_account = next_account_info(account_info_iter)?;
+        let mut registration_info = Registration::from_account_info(registration_account, program_id)?;
+        if registration_info.user != *referrer.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if registration_info.rewards == 0 {
+            return Err(ReferralError::NothingToClaim.into());
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        Self::check_vault(vault, program_id)?;
+
+        // the vault is owned by the program, so the rewards are paid out by moving lamports directly
+        let amount = registration_info.rewards;
+        msg!("Paying out {} lamports of rewards...", amount);
+        **vault.lamports.borrow_mut() = vault.lamports()
+            .checked_sub(amount)
+            .ok_or(ReferralError::AmountOverflow)?;
+        **referrer.lamports.borrow_mut() = referrer.lamports()
+            .checked_add(amount)
+            .ok_or(ReferralError::AmountOverflow)?;
+
+        registration_info.rewards = 0;
+        registration_info.serialize(&mut &mut registration_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn check_vault(vault: &AccountInfo, program_id: &Pubkey) -> ProgramResult {
+        let (vault_pda, _) = Pubkey::find_program_address(&[b"vault"], program_id);
+        if vault_pda != *vault.key {
+            return Err(ReferralError::InvalidVaultAddress.into());
+        }
+        if vault.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Ok(())
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
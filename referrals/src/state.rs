@@ -0,0 +1,34 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Registration {
+    pub is_initialized: bool,
+    pub user: Pubkey,
+    // the user who referred this one, fixed at registration
+    pub referrer: Option<Pubkey>,
+    // number of users who registered with this user as referrer
+    pub referral_count: u64,
+    // lamports credited to this user and not claimed yet, they are held by the vault
+    pub rewards: u64,
+    pub total_earned: u64,
+    pub bump_seed: u8,
+}
+
+impl Registration {
+    pub const LEN: usize = 1 + 32 + (1 + 32) + 8 + 8 + 8 + 1;
+
+    pub fn from_account_info(registration_account: &AccountInfo, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if registration_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // a registration without a referrer ends 32 bytes before its account does, deserialize leaves those zeros alone
+        let registration_info = Self::deserialize(&mut &registration_account.data.borrow()[..])?;
+        if !registration_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(registration_info)
+    }
+}
@@ -0,0 +1,272 @@
+import yaml
+import sys
+import json
+import time
+import base64
+import based58
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID, CreateAccountParams, create_account
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'replay_demo.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'replay_demo-keypair.json'))
+
+SYSVAR_RECENT_BLOCKHASHES = PublicKey("SysvarRecentB1ockHashes11111111111111111111")
+SYSVAR_RENT = PublicKey("SysvarRent111111111111111111111111111111111")
+
+# a nonce account stores a version, a state, the authority, the nonce and a fee calculator
+NONCE_ACCOUNT_LEN = 80
+
+# a normal blockhash is only valid for about 150 slots, we wait longer than that before sending the offline transaction
+BLOCKHASH_EXPIRY_WAIT = 90
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_sequence_address(authority: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("sequence", encoding="utf8"), bytes(authority)], program_id)[0]
+
+def init_sequence(authority: Keypair) -> PublicKey:
+    sequence = get_sequence_address(authority.public_key)
+    print(f"\nCreating sequence account {sequence}")
+    ix = TransactionInstruction(
+        data=(0).to_bytes(1, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=sequence, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(ix), authority)
+    client.confirm_transaction(tx["result"])
+    return sequence
+
+def get_next_sequence(sequence: PublicKey) -> int:
+    data = base64.b64decode(client.get_account_info(sequence)['result']['value']['data'][0])
+    offset = 1 + 32
+    return int.from_bytes(data[offset:offset + 8], byteorder='little')
+
+def pay_instruction(authority: PublicKey, recipient: PublicKey, sequence_number: int, amount: int) -> TransactionInstruction:
+    return TransactionInstruction(
+        data=(1).to_bytes(1, byteorder='little') + sequence_number.to_bytes(8, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=authority, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_sequence_address(authority), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=recipient, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+
+def create_nonce_account(authority: Keypair) -> PublicKey:
+    nonce = Keypair()
+    print(f"\nCreating nonce account {nonce.public_key}")
+    rent = client.get_minimum_balance_for_rent_exemption(NONCE_ACCOUNT_LEN)['result']
+    create_ix = create_account(CreateAccountParams(
+        from_pubkey=authority.public_key,
+        new_account_pubkey=nonce.public_key,
+        lamports=rent,
+        space=NONCE_ACCOUNT_LEN,
+        program_id=SYS_PROGRAM_ID,
+    ))
+    # InitializeNonceAccount is instruction 6 of the system program, followed by the nonce authority
+    init_ix = TransactionInstruction(
+        data=(6).to_bytes(4, byteorder='little') + bytes(authority.public_key),
+        keys=[
+            AccountMeta(pubkey=nonce.public_key, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYSVAR_RECENT_BLOCKHASHES, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYSVAR_RENT, is_signer=False, is_writable=False),
+        ],
+        program_id=SYS_PROGRAM_ID,
+    )
+    tx = client.send_transaction(Transaction().add(create_ix).add(init_ix), authority, nonce)
+    client.confirm_transaction(tx["result"])
+    return nonce.public_key
+
+def get_nonce(nonce: PublicKey) -> str:
+    # the stored nonce is a blockhash, it is used instead of a recent blockhash
+    data = base64.b64decode(client.get_account_info(nonce)['result']['value']['data'][0])
+    return based58.b58encode(data[40:72]).decode('utf-8')
+
+def advance_nonce_instruction(nonce: PublicKey, authority: PublicKey) -> TransactionInstruction:
+    # AdvanceNonceAccount is instruction 4 of the system program. It has to be the first instruction of a
+    # durable transaction, it replaces the stored nonce, so the transaction can't be sent again
+    return TransactionInstruction(
+        data=(4).to_bytes(4, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=nonce, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYSVAR_RECENT_BLOCKHASHES, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=authority, is_signer=True, is_writable=False),
+        ],
+        program_id=SYS_PROGRAM_ID,
+    )
+
+def sign_offline(authority: Keypair, nonce: PublicKey, recipient: PublicKey, sequence_number: int, amount: int) -> bytes:
+    # Only reading the nonce needs the network. Everything else could happen on a machine which is never online,
+    # the serialized bytes are carried over to whoever sends them
+    tx = Transaction(recent_blockhash=get_nonce(nonce))
+    tx.add(advance_nonce_instruction(nonce, authority.public_key))
+    tx.add(pay_instruction(authority.public_key, recipient, sequence_number, amount))
+    tx.sign(authority)
+    return tx.serialize()
+
+def send_raw(raw: bytes, what: str):
+    try:
+        tx = client.send_raw_transaction(raw)
+        client.confirm_transaction(tx["result"])
+        print(f"{what}: executed")
+    except Exception as e:
+        print(f"{what}: rejected, {e}")
+
+def print_balance(recipient: PublicKey):
+    print(f"Recipient balance: {client.get_balance(recipient)['result']['value']} lamports")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    authority = setup_user_keypair()
+    recipient = Keypair().public_key
+    amount = LAMPORTS_PER_SOL // 10
+
+    sequence = init_sequence(authority)
+    nonce = create_nonce_account(authority)
+
+    print("\nSigning a payment offline with the durable nonce")
+    raw = sign_offline(authority, nonce, recipient, get_next_sequence(sequence), amount)
+    print(f"Waiting {BLOCKHASH_EXPIRY_WAIT} seconds, a transaction with a recent blockhash would have expired by now...")
+    time.sleep(BLOCKHASH_EXPIRY_WAIT)
+    send_raw(raw, "Offline signed payment")
+    print_balance(recipient)
+
+    # the nonce has been advanced by the first send, the same bytes refer to a nonce that doesn't exist anymore
+    send_raw(raw, "Same transaction again")
+    print_balance(recipient)
+
+    # signing the same payment again gives a new transaction, only the sequence number stops it
+    print("\nSigning the same payment again with a fresh nonce")
+    send_raw(sign_offline(authority, nonce, recipient, 0, amount), "Re-signed payment with sequence 0")
+    send_raw(sign_offline(authority, nonce, recipient, get_next_sequence(sequence), amount), "Next payment with sequence 1")
+    print_balance(recipient)
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum ReplayError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Sequence Address")]
+    InvalidSequenceAddress,
+    #[error("Invalid Sequence Number")]
+    InvalidSequenceNumber,
+    #[error("Not The Authority")]
+    NotTheAuthority,
+}
+
+impl From<ReplayError> for ProgramError {
+    fn from(e: ReplayError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
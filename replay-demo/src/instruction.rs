@@ -0,0 +1,57 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::ReplayError::InvalidInstruction;
+
+pub enum ReplayInstruction {
+
+    // Creates the sequence account of an authority, a PDA derived from ["sequence", authority].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The authority
+    // 1. `[writable]` The sequence PDA
+    // 2. `[]` The system program
+    InitSequence,
+
+    // Pays lamports from the authority to a recipient. The instruction carries a sequence number which has to
+    // match the one stored in the sequence account, afterwards the stored number is incremented. The same
+    // instruction can therefore only be executed once, whatever transaction it is wrapped in.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The authority
+    // 1. `[writable]` The sequence PDA
+    // 2. `[writable]` The recipient
+    // 3. `[]` The system program
+    Pay {
+        sequence: u64,
+        amount: u64,
+    },
+}
+
+impl ReplayInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitSequence,
+            1 => Self::Pay {
+                sequence: Self::unpack_u64(rest, 0)?,
+                amount: Self::unpack_u64(rest, 8)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
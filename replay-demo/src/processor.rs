@@ -0,0 +1,137 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{instruction::ReplayInstruction, error::ReplayError, state::Sequence};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = ReplayInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            ReplayInstruction::InitSequence => {
+                msg!("Instruction: InitSequence");
+                Self::process_init_sequence(accounts, program_id)
+            },
+            ReplayInstruction::Pay { sequence, amount } => {
+                msg!("Instruction: Pay");
+                Self::process_pay(accounts, sequence, amount, program_id)
+            }
+        }
+    }
+
+    fn process_init_sequence(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let sequence_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (sequence_pda, bump_seed) = Pubkey::find_program_address(&[b"sequence", authority.key.as_ref()], program_id);
+        if sequence_pda != *sequence_account.key {
+            return Err(ReplayError::InvalidSequenceAddress.into());
+        }
+
+        if !sequence_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating sequence account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                authority.key,
+                sequence_account.key,
+                Rent::get()?.minimum_balance(Sequence::LEN),
+                Sequence::LEN as u64,
+                program_id,
+            ),
+            &[
+                authority.clone(),
+                sequence_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"sequence", authority.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        let sequence_info = Sequence {
+            is_initialized: true,
+            authority: *authority.key,
+            next_sequence: 0,
+            bump_seed,
+        };
+        sequence_info.serialize(&mut &mut sequence_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_pay(
+        accounts: &[AccountInfo],
+        sequence: u64,
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let sequence_account = next_account_info(account_info_iter)?;
+        if sequence_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut sequence_info = Sequence::try_from_slice(&sequence_account.data.borrow())?;
+        if !sequence_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if sequence_info.authority != *authority.key {
+            return Err(ReplayError::NotTheAuthority.into());
+        }
+
+        // The runtime only rejects a transaction it has seen before, i.e. the very same signature. Signing the
+        // same instruction again with a new blockhash gives a new transaction, which the runtime happily
+        // executes. The sequence number is part of the instruction, so that is where the program stops it.
+        if sequence != sequence_info.next_sequence {
+            msg!("Expected sequence number {}, got {}", sequence_info.next_sequence, sequence);
+            return Err(ReplayError::InvalidSequenceNumber.into());
+        }
+
+        let recipient = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to pay {} lamports...", amount);
+        invoke(
+            &system_instruction::transfer(authority.key, recipient.key, amount),
+            &[
+                authority.clone(),
+                recipient.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        sequence_info.next_sequence += 1;
+        sequence_info.serialize(&mut &mut sequence_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Sequence {
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+    // the sequence number the next payment has to carry
+    pub next_sequence: u64,
+    pub bump_seed: u8,
+}
+
+impl Sequence {
+    pub const LEN: usize = 1 + 32 + 8 + 1;
+}
@@ -0,0 +1,35 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum RpsError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Game Address")]
+    InvalidGameAddress,
+    #[error("Invalid Move")]
+    InvalidMove,
+    #[error("Game Already Joined")]
+    GameAlreadyJoined,
+    #[error("Game Not Joined")]
+    GameNotJoined,
+    #[error("Not A Player")]
+    NotAPlayer,
+    #[error("Already Revealed")]
+    AlreadyRevealed,
+    #[error("Commitment Mismatch")]
+    CommitmentMismatch,
+    #[error("Reveal Deadline Passed")]
+    RevealDeadlinePassed,
+    #[error("Reveal Deadline Not Reached")]
+    RevealDeadlineNotReached,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<RpsError> for ProgramError {
+    fn from(e: RpsError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,114 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::{error::RpsError::{InvalidInstruction, InvalidMove}, state::Move};
+
+pub enum RpsInstruction {
+
+    // Opens a game with the commitment of the first player. The game lives in a PDA derived from
+    // ["rps", player_one, id], which holds the stakes on top of its rent.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The first player
+    // 1. `[writable]` The game PDA
+    // 2. `[]` The system program
+    CreateGame {
+        id: u64,
+        stake: u64,
+        timeout: i64,
+        commitment: [u8; 32],
+    },
+
+    // Joins a game with the commitment of the second player and the same stake. The reveal phase starts.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The second player
+    // 1. `[writable]` The game PDA
+    // 2. `[]` The system program
+    JoinGame {
+        commitment: [u8; 32],
+    },
+
+    // Reveals the move behind a commitment. Once both moves are revealed, the game is paid out and closed.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The revealing player
+    // 1. `[writable]` The game PDA
+    // 2. `[writable]` The first player
+    // 3. `[writable]` The second player
+    Reveal {
+        player_move: Move,
+        salt: [u8; 32],
+    },
+
+    // Closes a game nobody has joined and returns the stake.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The first player
+    // 1. `[writable]` The game PDA
+    Cancel,
+
+    // Ends a game after the reveal deadline. A player who revealed wins against one who didn't, if nobody
+    // revealed both stakes are returned. Anyone may call it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The game PDA
+    // 1. `[writable]` The first player
+    // 2. `[writable]` The second player
+    ClaimTimeout,
+}
+
+impl RpsInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateGame {
+                id: Self::unpack_u64(rest, 0)?,
+                stake: Self::unpack_u64(rest, 8)?,
+                timeout: Self::unpack_u64(rest, 16)? as i64,
+                commitment: Self::unpack_hash(rest, 24)?,
+            },
+            1 => Self::JoinGame {
+                commitment: Self::unpack_hash(rest, 0)?,
+            },
+            2 => {
+                let (move_byte, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                Self::Reveal {
+                    player_move: Move::from_u8(*move_byte).ok_or(InvalidMove)?,
+                    salt: Self::unpack_hash(rest, 0)?,
+                }
+            },
+            3 => Self::Cancel,
+            4 => Self::ClaimTimeout,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+
+    fn unpack_hash(input: &[u8], offset: usize) -> Result<[u8; 32], ProgramError> {
+        let hash = input
+            .get(offset..offset + 32)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(InvalidInstruction)?;
+        Ok(hash)
+    }
+}
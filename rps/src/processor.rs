@@ -0,0 +1,335 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::RpsInstruction,
+    error::RpsError,
+    state::{commitment, Game, Move},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = RpsInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            RpsInstruction::CreateGame { id, stake, timeout, commitment } => {
+                msg!("Instruction: CreateGame");
+                Self::process_create_game(accounts, id, stake, timeout, commitment, program_id)
+            },
+            RpsInstruction::JoinGame { commitment } => {
+                msg!("Instruction: JoinGame");
+                Self::process_join_game(accounts, commitment, program_id)
+            },
+            RpsInstruction::Reveal { player_move, salt } => {
+                msg!("Instruction: Reveal");
+                Self::process_reveal(accounts, player_move, salt, program_id)
+            },
+            RpsInstruction::Cancel => {
+                msg!("Instruction: Cancel");
+                Self::process_cancel(accounts, program_id)
+            },
+            RpsInstruction::ClaimTimeout => {
+                msg!("Instruction: ClaimTimeout");
+                Self::process_claim_timeout(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_game(
+        accounts: &[AccountInfo],
+        id: u64,
+        stake: u64,
+        timeout: i64,
+        commitment: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        // both stakes have to fit into the game account later on
+        stake.checked_mul(2).ok_or(RpsError::AmountOverflow)?;
+
+        let account_info_iter = &mut accounts.iter();
+        let player_one = next_account_info(account_info_iter)?;
+
+        if !player_one.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (game_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"rps", player_one.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if game_pda != *game_account.key {
+            return Err(RpsError::InvalidGameAddress.into());
+        }
+
+        if !game_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the stake is simply added on top of the rent when the account is created
+        let lamports = Rent::get()?.minimum_balance(Game::LEN)
+            .checked_add(stake)
+            .ok_or(RpsError::AmountOverflow)?;
+
+        msg!("Creating game account holding a stake of {} lamports...", stake);
+        invoke_signed(
+            &system_instruction::create_account(
+                player_one.key,
+                game_account.key,
+                lamports,
+                Game::LEN as u64,
+                program_id,
+            ),
+            &[
+                player_one.clone(),
+                game_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"rps", player_one.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        let game_info = Game {
+            is_initialized: true,
+            player_one: *player_one.key,
+            player_two: None,
+            id,
+            stake,
+            timeout,
+            commitment_one: commitment,
+            commitment_two: [0; 32],
+            move_one: None,
+            move_two: None,
+            reveal_deadline: 0,
+            bump_seed,
+        };
+        game_info.serialize(&mut &mut game_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_join_game(
+        accounts: &[AccountInfo],
+        commitment: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let player_two = next_account_info(account_info_iter)?;
+
+        if !player_two.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let mut game_info = Self::unpack_game(game_account, program_id)?;
+
+        if game_info.player_two.is_some() {
+            return Err(RpsError::GameAlreadyJoined.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to add the stake of {} lamports...", game_info.stake);
+        invoke(
+            &system_instruction::transfer(player_two.key, game_account.key, game_info.stake),
+            &[
+                player_two.clone(),
+                game_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        game_info.player_two = Some(*player_two.key);
+        game_info.commitment_two = commitment;
+        game_info.reveal_deadline = Clock::get()?.unix_timestamp
+            .checked_add(game_info.timeout)
+            .ok_or(RpsError::AmountOverflow)?;
+        game_info.serialize(&mut &mut game_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_reveal(
+        accounts: &[AccountInfo],
+        player_move: Move,
+        salt: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let player = next_account_info(account_info_iter)?;
+
+        if !player.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let mut game_info = Self::unpack_game(game_account, program_id)?;
+        let player_two_key = game_info.player_two.ok_or(RpsError::GameNotJoined)?;
+
+        if Clock::get()?.unix_timestamp > game_info.reveal_deadline {
+            return Err(RpsError::RevealDeadlinePassed.into());
+        }
+
+        let (stored_commitment, stored_move) = if *player.key == game_info.player_one {
+            (game_info.commitment_one, &mut game_info.move_one)
+        } else if *player.key == player_two_key {
+            (game_info.commitment_two, &mut game_info.move_two)
+        } else {
+            return Err(RpsError::NotAPlayer.into());
+        };
+
+        if stored_move.is_some() {
+            return Err(RpsError::AlreadyRevealed.into());
+        }
+        if commitment(player_move, &salt, player.key) != stored_commitment {
+            return Err(RpsError::CommitmentMismatch.into());
+        }
+        *stored_move = Some(player_move);
+
+        let (player_one, player_two) = Self::next_players(account_info_iter, &game_info, &player_two_key)?;
+
+        match (game_info.move_one, game_info.move_two) {
+            (Some(move_one), Some(move_two)) => {
+                msg!("Player one played {:?}, player two played {:?}", move_one, move_two);
+                let pot = game_info.stake * 2;
+                let (amount_one, amount_two) = if move_one.beats(move_two) {
+                    (pot, 0)
+                } else if move_two.beats(move_one) {
+                    (0, pot)
+                } else {
+                    (game_info.stake, game_info.stake)
+                };
+                Self::pay_out(game_account, player_one, player_two, amount_one, amount_two)
+            },
+            _ => {
+                msg!("Waiting for the other player to reveal");
+                game_info.serialize(&mut &mut game_account.data.borrow_mut()[..])?;
+                Ok(())
+            }
+        }
+    }
+
+    fn process_cancel(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let player_one = next_account_info(account_info_iter)?;
+
+        if !player_one.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let game_info = Self::unpack_game(game_account, program_id)?;
+
+        if game_info.player_one != *player_one.key {
+            return Err(RpsError::NotAPlayer.into());
+        }
+        if game_info.player_two.is_some() {
+            return Err(RpsError::GameAlreadyJoined.into());
+        }
+
+        msg!("Closing the game account...");
+        Self::close_game(game_account, player_one)
+    }
+
+    fn process_claim_timeout(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let game_account = next_account_info(account_info_iter)?;
+        let game_info = Self::unpack_game(game_account, program_id)?;
+        let player_two_key = game_info.player_two.ok_or(RpsError::GameNotJoined)?;
+
+        if Clock::get()?.unix_timestamp <= game_info.reveal_deadline {
+            return Err(RpsError::RevealDeadlineNotReached.into());
+        }
+
+        let (player_one, player_two) = Self::next_players(account_info_iter, &game_info, &player_two_key)?;
+
+        // a game where both revealed has been paid out already, so at most one move is known here.
+        // Not revealing in time forfeits the stake.
+        let pot = game_info.stake * 2;
+        let (amount_one, amount_two) = match (game_info.move_one, game_info.move_two) {
+            (Some(_), None) => (pot, 0),
+            (None, Some(_)) => (0, pot),
+            _ => (game_info.stake, game_info.stake),
+        };
+        Self::pay_out(game_account, player_one, player_two, amount_one, amount_two)
+    }
+
+    fn unpack_game(game_account: &AccountInfo, program_id: &Pubkey) -> Result<Game, ProgramError> {
+        if game_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // player two and the moves start out as None, so a new game doesn't fill its account
+        let game_info = Game::deserialize(&mut &game_account.data.borrow()[..])?;
+        if !game_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(game_info)
+    }
+
+    fn next_players<'a, 'b>(
+        account_info_iter: &mut std::slice::Iter<'b, AccountInfo<'a>>,
+        game_info: &Game,
+        player_two_key: &Pubkey,
+    ) -> Result<(&'b AccountInfo<'a>, &'b AccountInfo<'a>), ProgramError> {
+        let player_one = next_account_info(account_info_iter)?;
+        let player_two = next_account_info(account_info_iter)?;
+        if *player_one.key != game_info.player_one || player_two.key != player_two_key {
+            return Err(RpsError::NotAPlayer.into());
+        }
+
+        Ok((player_one, player_two))
+    }
+
+    // The game account is owned by the program, so the stakes are paid out by moving lamports directly.
+    // What is left afterwards is the rent, it goes back to the first player, who created the account.
+    fn pay_out<'a>(
+        game_account: &AccountInfo<'a>,
+        player_one: &AccountInfo<'a>,
+        player_two: &AccountInfo<'a>,
+        amount_one: u64,
+        amount_two: u64,
+    ) -> ProgramResult {
+        msg!("Paying {} lamports to player one and {} lamports to player two", amount_one, amount_two);
+        for (player, amount) in [(player_one, amount_one), (player_two, amount_two)] {
+            **game_account.lamports.borrow_mut() -= amount;
+            **player.lamports.borrow_mut() = player.lamports()
+                .checked_add(amount)
+                .ok_or(RpsError::AmountOverflow)?;
+        }
+
+        msg!("Closing the game account...");
+        Self::close_game(game_account, player_one)
+    }
+
+    fn close_game<'a>(game_account: &AccountInfo<'a>, destination: &AccountInfo<'a>) -> ProgramResult {
+        **destination.lamports.borrow_mut() = destination.lamports()
+            .checked_add(game_account.lamports())
+            .ok_or(RpsError::AmountOverflow)?;
+        **game_account.lamports.borrow_mut() = 0;
+        game_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+}
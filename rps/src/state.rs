@@ -0,0 +1,56 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{hash::hashv, pubkey::Pubkey};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Move {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Move::Rock),
+            1 => Some(Move::Paper),
+            2 => Some(Move::Scissors),
+            _ => None,
+        }
+    }
+
+    pub fn beats(self, other: Move) -> bool {
+        matches!(
+            (self, other),
+            (Move::Rock, Move::Scissors) | (Move::Paper, Move::Rock) | (Move::Scissors, Move::Paper)
+        )
+    }
+}
+
+// The commitment hides the move until both players are locked in. The salt keeps the opponent from simply
+// hashing all three moves, the player's pubkey keeps them from copying the commitment.
+pub fn commitment(player_move: Move, salt: &[u8; 32], player: &Pubkey) -> [u8; 32] {
+    hashv(&[&[player_move as u8], salt, player.as_ref()]).to_bytes()
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Game {
+    pub is_initialized: bool,
+    pub player_one: Pubkey,
+    // None until somebody joins
+    pub player_two: Option<Pubkey>,
+    pub id: u64,
+    // lamports each player puts in
+    pub stake: u64,
+    // seconds both players have to reveal once the game has been joined
+    pub timeout: i64,
+    pub commitment_one: [u8; 32],
+    pub commitment_two: [u8; 32],
+    pub move_one: Option<Move>,
+    pub move_two: Option<Move>,
+    pub reveal_deadline: i64,
+    pub bump_seed: u8,
+}
+
+impl Game {
+    pub const LEN: usize = 1 + 32 + (1 + 32) + 8 + 8 + 8 + 32 + 32 + (1 + 1) + (1 + 1) + 8 + 1;
+}
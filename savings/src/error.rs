@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum SavingsError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Pot Address")]
+    InvalidPotAddress,
+    #[error("Invalid Treasury Address")]
+    InvalidTreasuryAddress,
+    #[error("Invalid Cooldown")]
+    InvalidCooldown,
+    #[error("Insufficient Funds")]
+    InsufficientFunds,
+    #[error("No Withdrawal Requested")]
+    NoWithdrawalRequested,
+    #[error("Cooldown Not Over")]
+    CooldownNotOver,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<SavingsError> for ProgramError {
+    fn from(e: SavingsError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
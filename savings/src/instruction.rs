@@ -0,0 +1,107 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::SavingsError::InvalidInstruction;
+
+pub enum SavingsInstruction {
+
+    // Creates the treasury, a data-less PDA derived from ["treasury"] collecting the emergency withdrawal penalties.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The payer
+    // 1. `[writable]` The treasury PDA
+    // 2. `[]` The system program
+    InitTreasury,
+
+    // Opens a savings pot in a PDA derived from ["pot", owner]. Followed by the i64 cooldown in seconds.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner
+    // 1. `[writable]` The pot PDA
+    // 2. `[]` The system program
+    OpenPot {
+        cooldown: i64,
+    },
+
+    // Adds lamports to the pot, takes effect right away.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The depositor
+    // 1. `[writable]` The pot PDA
+    // 2. `[]` The system program
+    Deposit {
+        amount: u64,
+    },
+
+    // Requests a withdrawal, which can be executed once the cooldown is over. A new request replaces the
+    // pending one and starts the cooldown again.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner
+    // 1. `[writable]` The pot PDA
+    RequestWithdrawal {
+        amount: u64,
+    },
+
+    // Executes the pending withdrawal after the cooldown.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner
+    // 1. `[writable]` The pot PDA
+    Withdraw,
+
+    // Withdraws right away, without a request. A penalty of PENALTY_BPS of the amount goes to the treasury.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner
+    // 1. `[writable]` The pot PDA
+    // 2. `[writable]` The treasury PDA
+    EmergencyWithdraw {
+        amount: u64,
+    },
+}
+
+impl SavingsInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitTreasury,
+            1 => Self::OpenPot {
+                cooldown: Self::unpack_u64(rest, 0)? as i64,
+            },
+            2 => Self::Deposit {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            3 => Self::RequestWithdrawal {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            4 => Self::Withdraw,
+            5 => Self::EmergencyWithdraw {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
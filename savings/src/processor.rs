@@ -0,0 +1,308 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::SavingsInstruction,
+    error::SavingsError,
+    state::{penalty, Pot},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = SavingsInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            SavingsInstruction::InitTreasury => {
+                msg!("Instruction: InitTreasury");
+                Self::process_init_treasury(accounts, program_id)
+            },
+            SavingsInstruction::OpenPot { cooldown } => {
+                msg!("Instruction: OpenPot");
+                Self::process_open_pot(accounts, cooldown, program_id)
+            },
+            SavingsInstruction::Deposit { amount } => {
+                msg!("Instruction: Deposit");
+                Self::process_deposit(accounts, amount, program_id)
+            },
+            SavingsInstruction::RequestWithdrawal { amount } => {
+                msg!("Instruction: RequestWithdrawal");
+                Self::process_request_withdrawal(accounts, amount, program_id)
+            },
+            SavingsInstruction::Withdraw => {
+                msg!("Instruction: Withdraw");
+                Self::process_withdraw(accounts, program_id)
+            },
+            SavingsInstruction::EmergencyWithdraw { amount } => {
+                msg!("Instruction: EmergencyWithdraw");
+                Self::process_emergency_withdraw(accounts, amount, program_id)
+            }
+        }
+    }
+
+    fn process_init_treasury(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let payer = next_account_info(account_info_iter)?;
+
+        if !payer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let treasury = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (treasury_pda, bump_seed) = Pubkey::find_program_address(&[b"treasury"], program_id);
+        if treasury_pda != *treasury.key {
+            return Err(SavingsError::InvalidTreasuryAddress.into());
+        }
+
+        if treasury.owner == program_id {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating treasury account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                treasury.key,
+                Rent::get()?.minimum_balance(0),
+                0,
+                program_id,
+            ),
+            &[
+                payer.clone(),
+                treasury.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"treasury", &[bump_seed]]],
+        )
+    }
+
+    fn process_open_pot(
+        accounts: &[AccountInfo],
+        cooldown: i64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if cooldown <= 0 {
+            return Err(SavingsError::InvalidCooldown.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pot_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (pot_pda, bump_seed) = Pubkey::find_program_address(&[b"pot", owner.key.as_ref()], program_id);
+        if pot_pda != *pot_account.key {
+            return Err(SavingsError::InvalidPotAddress.into());
+        }
+
+        if !pot_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating pot account with a cooldown of {} seconds...", cooldown);
+        invoke_signed(
+            &system_instruction::create_account(
+                owner.key,
+                pot_account.key,
+                Rent::get()?.minimum_balance(Pot::LEN),
+                Pot::LEN as u64,
+                program_id,
+            ),
+            &[
+                owner.clone(),
+                pot_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"pot", owner.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        let pot_info = Pot {
+            is_initialized: true,
+            owner: *owner.key,
+            cooldown,
+            pending_amount: 0,
+            available_at: 0,
+            bump_seed,
+        };
+        pot_info.serialize(&mut &mut pot_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_deposit(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let depositor = next_account_info(account_info_iter)?;
+
+        if !depositor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pot_account = next_account_info(account_info_iter)?;
+        Self::unpack_pot(pot_account, program_id)?;
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to deposit {} lamports...", amount);
+        invoke(
+            &system_instruction::transfer(depositor.key, pot_account.key, amount),
+            &[
+                depositor.clone(),
+                pot_account.clone(),
+                system_program.clone(),
+            ],
+        )
+    }
+
+    fn process_request_withdrawal(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let pot_account = next_account_info(account_info_iter)?;
+        let mut pot_info = Self::unpack_as_owner(owner, pot_account, program_id)?;
+
+        if amount == 0 || amount > Self::funds(pot_account)? {
+            return Err(SavingsError::InsufficientFunds.into());
+        }
+
+        pot_info.pending_amount = amount;
+        pot_info.available_at = Clock::get()?.unix_timestamp
+            .checked_add(pot_info.cooldown)
+            .ok_or(SavingsError::AmountOverflow)?;
+        msg!("Withdrawal of {} lamports can be executed from {} on", amount, pot_info.available_at);
+        pot_info.serialize(&mut &mut pot_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_withdraw(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let pot_account = next_account_info(account_info_iter)?;
+        let mut pot_info = Self::unpack_as_owner(owner, pot_account, program_id)?;
+
+        if pot_info.pending_amount == 0 {
+            return Err(SavingsError::NoWithdrawalRequested.into());
+        }
+        if Clock::get()?.unix_timestamp < pot_info.available_at {
+            return Err(SavingsError::CooldownNotOver.into());
+        }
+
+        // an emergency withdrawal may have taken some of the requested lamports in the meantime
+        let amount = pot_info.pending_amount;
+        if amount > Self::funds(pot_account)? {
+            return Err(SavingsError::InsufficientFunds.into());
+        }
+
+        msg!("Withdrawing {} lamports...", amount);
+        Self::transfer_from_pot(pot_account, owner, amount)?;
+
+        pot_info.pending_amount = 0;
+        pot_info.available_at = 0;
+        pot_info.serialize(&mut &mut pot_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_emergency_withdraw(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let pot_account = next_account_info(account_info_iter)?;
+        Self::unpack_as_owner(owner, pot_account, program_id)?;
+
+        let treasury = next_account_info(account_info_iter)?;
+        let (treasury_pda, _) = Pubkey::find_program_address(&[b"treasury"], program_id);
+        if treasury_pda != *treasury.key {
+            return Err(SavingsError::InvalidTreasuryAddress.into());
+        }
+
+        if amount == 0 || amount > Self::funds(pot_account)? {
+            return Err(SavingsError::InsufficientFunds.into());
+        }
+
+        let penalty = penalty(amount);
+        msg!("Withdrawing {} lamports right away, {} of them go to the treasury...", amount, penalty);
+        Self::transfer_from_pot(pot_account, treasury, penalty)?;
+        Self::transfer_from_pot(pot_account, owner, amount - penalty)
+    }
+
+    fn unpack_pot(pot_account: &AccountInfo, program_id: &Pubkey) -> Result<Pot, ProgramError> {
+        if pot_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let pot_info = Pot::try_from_slice(&pot_account.data.borrow())?;
+        if !pot_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(pot_info)
+    }
+
+    fn unpack_as_owner(
+        owner: &AccountInfo,
+        pot_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<Pot, ProgramError> {
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pot_info = Self::unpack_pot(pot_account, program_id)?;
+        if pot_info.owner != *owner.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(pot_info)
+    }
+
+    // lamports held by the pot on top of its rent
+    fn funds(pot_account: &AccountInfo) -> Result<u64, ProgramError> {
+        let rent_lamports = Rent::get()?.minimum_balance(pot_account.data_len());
+        Ok(pot_account.lamports().saturating_sub(rent_lamports))
+    }
+
+    // The pot is owned by the program, so lamports are moved out of it directly
+    fn transfer_from_pot(pot_account: &AccountInfo, destination: &AccountInfo, amount: u64) -> ProgramResult {
+        **pot_account.lamports.borrow_mut() -= amount;
+        **destination.lamports.borrow_mut() = destination.lamports()
+            .checked_add(amount)
+            .ok_or(SavingsError::AmountOverflow)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Share of an emergency withdrawal which goes to the treasury, in basis points
+pub const PENALTY_BPS: u64 = 1_000;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Pot {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    // seconds between requesting and executing a withdrawal
+    pub cooldown: i64,
+    // lamports of the pending withdrawal request, 0 if there is none
+    pub pending_amount: u64,
+    // the pending withdrawal can be executed from this unix timestamp on
+    pub available_at: i64,
+    pub bump_seed: u8,
+}
+
+impl Pot {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 1;
+}
+
+pub fn penalty(amount: u64) -> u64 {
+    (amount as u128 * PENALTY_BPS as u128 / 10_000) as u64
+}
@@ -0,0 +1,213 @@
+import yaml
+import sys
+import json
+import base64
+import struct
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+from eth_account import Account
+from eth_account.messages import encode_defunct
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+IDENTITY_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'user': types.fixed_array(types.u8, 32),
+    'eth_address': types.fixed_array(types.u8, 20),
+    'bump_seed': types.u8
+})
+
+SYSVAR_INSTRUCTIONS_ID = PublicKey("Sysvar1nstructions1111111111111111111111111")
+SECP256K1_PROGRAM_ID = PublicKey("KeccakSecp256k11111111111111111111111111111")
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'secp256k1_verify.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'secp256k1_verify-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def get_identity_address(user: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("eth", encoding="utf8"), bytes(user)], program_id)[0]
+
+def get_identity(identity: PublicKey) -> dict:
+    account_info = client.get_account_info(identity)["result"]["value"]
+    return borsh.deserialize(IDENTITY_SCHEMA, base64.b64decode(account_info["data"][0]))
+
+def link_content(user: PublicKey) -> str:
+    return f"Link {user} on Solana"
+
+def secp256k1_ix(eth_address: bytes, signature: bytes, recovery_id: int, message: bytes) -> TransactionInstruction:
+    # one signature, then its offsets, then the address, the signature with its recovery id and the message.
+    # The instruction indexes are absolute, this is the first instruction of the transaction
+    eth_address_offset = 1 + 11
+    signature_offset = eth_address_offset + 20
+    message_offset = signature_offset + 65
+    offsets = struct.pack(
+        '<HBHBHHB',
+        signature_offset, 0,
+        eth_address_offset, 0,
+        message_offset, len(message), 0,
+    )
+    return TransactionInstruction(
+        data=bytes([1]) + offsets + eth_address + signature + bytes([recovery_id]) + message,
+        keys=[],
+        program_id=SECP256K1_PROGRAM_ID,
+    )
+
+def link_eth_address(user: Keypair, eth_account, content: str = None):
+    identity = get_identity_address(user.public_key)
+    content = link_content(user.public_key) if content is None else content
+    print(f"\nLinking {eth_account.address} to {user.public_key}, signing '{content}'")
+
+    # what personal_sign in an Ethereum wallet does, the prefix and the length go in front of the message
+    signed = Account.sign_message(encode_defunct(text=content), private_key=eth_account.key)
+    message = b"\x19Ethereum Signed Message:\n" + str(len(content)).encode() + content.encode()
+    signature = signed.r.to_bytes(32, byteorder='big') + signed.s.to_bytes(32, byteorder='big')
+    eth_address = bytes.fromhex(eth_account.address[2:])
+
+    tx = Transaction().add(
+        secp256k1_ix(eth_address, signature, signed.v - 27, message),
+        TransactionInstruction(
+            data=(0).to_bytes(1, byteorder='little'),
+            keys=[
+                AccountMeta(pubkey=user.public_key, is_signer=True, is_writable=True),
+                AccountMeta(pubkey=identity, is_signer=False, is_writable=True),
+                AccountMeta(pubkey=SYSVAR_INSTRUCTIONS_ID, is_signer=False, is_writable=False),
+                AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            ],
+            program_id=program_id,
+        ),
+    )
+    result = client.send_transaction(tx, user)
+    client.confirm_transaction(result["result"])
+
+def show_identity(user: PublicKey):
+    identity = get_identity(get_identity_address(user))
+    print(f"{user} is linked to 0x{bytes(identity['eth_address']).hex()}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+
+    eth_account = Account.create()
+    link_eth_address(payer, eth_account)
+    show_identity(payer.public_key)
+
+    print("\nLinking with a signature of a message naming another Solana account")
+    try:
+        link_eth_address(payer, Account.create(), link_content(Keypair().public_key))
+    except Exception as e:
+        print(f"The link fails, the signature was meant for a different account: {e}")
+    show_identity(payer.public_key)
@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum Secp256k1VerifyError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Identity Address")]
+    InvalidIdentityAddress,
+    #[error("Missing Secp256k1 Instruction")]
+    MissingSecp256k1Instruction,
+    #[error("Invalid Secp256k1 Instruction")]
+    InvalidSecp256k1Instruction,
+    #[error("Wrong Message")]
+    WrongMessage,
+}
+
+impl From<Secp256k1VerifyError> for ProgramError {
+    fn from(e: Secp256k1VerifyError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
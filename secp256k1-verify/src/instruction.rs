@@ -0,0 +1,30 @@
+use solana_program::program_error::ProgramError;
+
+use crate::error::Secp256k1VerifyError::InvalidInstruction;
+
+pub enum Secp256k1VerifyInstruction {
+
+    // Links the Ethereum address which signed the message `Link <user> on Solana` to the user, in a PDA derived
+    // from ["eth", user]. Linking again replaces the address. The instruction right before it has to be an
+    // instruction of the secp256k1 program verifying that signature.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The user
+    // 1. `[writable]` The identity PDA
+    // 2. `[]` The instructions sysvar
+    // 3. `[]` The system program
+    LinkEthAddress,
+}
+
+impl Secp256k1VerifyInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, _rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match *tag {
+            0 => Self::LinkEthAddress,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+}
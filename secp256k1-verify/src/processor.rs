@@ -0,0 +1,174 @@
+use std::convert::TryInto;
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    secp256k1_program,
+    system_instruction,
+    sysvar::{
+        self,
+        instructions::{load_current_index_checked, load_instruction_at_checked},
+        rent::Rent,
+        Sysvar,
+    },
+};
+
+use borsh::BorshSerialize;
+
+use crate::{
+    instruction::Secp256k1VerifyInstruction,
+    error::Secp256k1VerifyError,
+    state::Identity,
+};
+
+// The data of a secp256k1 program instruction starts with the number of signatures, followed by 11 bytes of
+// offsets per signature. The offsets tell where the signature, the Ethereum address and the message are, and in
+// which instruction of the transaction. See solana_sdk::secp256k1_instruction.
+const SIGNATURE_OFFSETS_START: usize = 1;
+const SIGNATURE_OFFSETS_SIZE: usize = 11;
+const ETH_ADDRESS_SIZE: usize = 20;
+
+// What wallets like MetaMask put in front of a message before they hash and sign it with personal_sign,
+// followed by the length of the message in decimal. A signed transaction can't be passed off as such a message.
+const ETH_MESSAGE_PREFIX: &str = "\x19Ethereum Signed Message:\n";
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = Secp256k1VerifyInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            Secp256k1VerifyInstruction::LinkEthAddress => {
+                msg!("Instruction: LinkEthAddress");
+                Self::process_link_eth_address(accounts, program_id)
+            }
+        }
+    }
+
+    // The secp256k1 program is a precompile like the Ed25519 program. The runtime recovers the public key from each
+    // signature of its instructions, hashes it into an Ethereum address and compares it with the address in the
+    // instruction, before the transaction runs. So the address in the instruction is the one which signed, and the
+    // program reads it from the Instructions sysvar.
+    fn process_link_eth_address(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user = next_account_info(account_info_iter)?;
+
+        if !user.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let identity_account = next_account_info(account_info_iter)?;
+        let instructions_sysvar = next_account_info(account_info_iter)?;
+        if *instructions_sysvar.key != sysvar::instructions::id() {
+            return Err(ProgramError::InvalidArgument);
+        }
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (identity_pda, bump_seed) = Pubkey::find_program_address(&[b"eth", user.key.as_ref()], program_id);
+        if identity_pda != *identity_account.key {
+            return Err(Secp256k1VerifyError::InvalidIdentityAddress.into());
+        }
+
+        // the instruction right before this one
+        let current_index = load_current_index_checked(instructions_sysvar)?;
+        let secp256k1_index = current_index
+            .checked_sub(1)
+            .ok_or(Secp256k1VerifyError::MissingSecp256k1Instruction)?;
+        let secp256k1_instruction = load_instruction_at_checked(secp256k1_index as usize, instructions_sysvar)?;
+        let (eth_address, signed_message) = Self::verified_signature(&secp256k1_instruction, secp256k1_index)?;
+
+        // The message names the Solana account. Otherwise anybody who got hold of a signature of the Ethereum
+        // address would link it to an account of their own.
+        let content = format!("Link {} on Solana", user.key);
+        let expected_message = format!("{}{}{}", ETH_MESSAGE_PREFIX, content.len(), content);
+        if signed_message != expected_message.as_bytes() {
+            return Err(Secp256k1VerifyError::WrongMessage.into());
+        }
+
+        if identity_account.data_is_empty() {
+            msg!("Creating identity account...");
+            invoke_signed(
+                &system_instruction::create_account(
+                    user.key,
+                    identity_account.key,
+                    Rent::get()?.minimum_balance(Identity::LEN),
+                    Identity::LEN as u64,
+                    program_id,
+                ),
+                &[
+                    user.clone(),
+                    identity_account.clone(),
+                    system_program.clone(),
+                ],
+                &[&[b"eth", user.key.as_ref(), &[bump_seed]]],
+            )?;
+        } else if identity_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let identity_info = Identity {
+            is_initialized: true,
+            user: *user.key,
+            eth_address,
+            bump_seed,
+        };
+        identity_info.serialize(&mut &mut identity_account.data.borrow_mut()[..])?;
+
+        msg!("Linked 0x{} to {}", Self::hex(&eth_address), user.key);
+        Ok(())
+    }
+
+    // The Ethereum address and the message the secp256k1 program verified a signature of. Like for the Ed25519
+    // program, it has to be the secp256k1 program with exactly one signature. Its instruction indexes are absolute,
+    // all three have to point to the secp256k1 instruction itself, otherwise the checked address could be a
+    // different one than the bytes read here.
+    fn verified_signature(instruction: &Instruction, index: u16) -> Result<([u8; 20], &[u8]), ProgramError> {
+        if instruction.program_id != secp256k1_program::id() {
+            return Err(Secp256k1VerifyError::MissingSecp256k1Instruction.into());
+        }
+
+        let data = &instruction.data;
+        if data.first() != Some(&1) {
+            return Err(Secp256k1VerifyError::InvalidSecp256k1Instruction.into());
+        }
+
+        let offsets = data
+            .get(SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE)
+            .ok_or(Secp256k1VerifyError::InvalidSecp256k1Instruction)?;
+        let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]) as usize;
+        let signature_instruction_index = offsets[2];
+        let eth_address_offset = read_u16(3);
+        let eth_address_instruction_index = offsets[5];
+        let message_data_offset = read_u16(6);
+        let message_data_size = read_u16(8);
+        let message_instruction_index = offsets[10];
+
+        let indexes = [signature_instruction_index, eth_address_instruction_index, message_instruction_index];
+        if indexes.iter().any(|instruction_index| *instruction_index as u16 != index) {
+            return Err(Secp256k1VerifyError::InvalidSecp256k1Instruction.into());
+        }
+
+        let eth_address = data
+            .get(eth_address_offset..eth_address_offset + ETH_ADDRESS_SIZE)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(Secp256k1VerifyError::InvalidSecp256k1Instruction)?;
+        let message = data
+            .get(message_data_offset..message_data_offset + message_data_size)
+            .ok_or(Secp256k1VerifyError::InvalidSecp256k1Instruction)?;
+
+        Ok((eth_address, message))
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+}
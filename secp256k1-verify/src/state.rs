@@ -0,0 +1,15 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Identity {
+    pub is_initialized: bool,
+    pub user: Pubkey,
+    // the 20 byte Ethereum address, the last 20 bytes of the keccak hash of the public key
+    pub eth_address: [u8; 20],
+    pub bump_seed: u8,
+}
+
+impl Identity {
+    pub const LEN: usize = 1 + 32 + 20 + 1;
+}
@@ -0,0 +1,285 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.rpc.types import MemcmpOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROFILE_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'owner': types.fixed_array(types.u8, 32),
+    'follower_count': types.u64,
+    'following_count': types.u64,
+    'post_count': types.u64,
+    'bump_seed': types.u8
+})
+
+POST_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'author': types.fixed_array(types.u8, 32),
+    'index': types.u64,
+    'created_ts': types.i64,
+    'like_count': types.u64,
+    'text': types.string
+})
+
+FOLLOW_SIZE = 32 + 32 + 8 + 1
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'social.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'social-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def get_profile_address(owner: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("profile", encoding="utf8"), bytes(owner)], program_id)[0]
+
+def get_follow_address(follower: PublicKey, followee: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("follow", encoding="utf8"), bytes(follower), bytes(followee)], program_id)[0]
+
+def get_post_address(author: PublicKey, index: int) -> PublicKey:
+    return PublicKey.find_program_address([bytes("post", encoding="utf8"), bytes(author), index.to_bytes(8, byteorder='little')], program_id)[0]
+
+def get_like_address(post: PublicKey, user: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("like", encoding="utf8"), bytes(post), bytes(user)], program_id)[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def get_profile(owner: PublicKey) -> dict:
+    account_info = client.get_account_info(get_profile_address(owner))["result"]["value"]
+    return borsh.deserialize(PROFILE_SCHEMA, base64.b64decode(account_info["data"][0]))
+
+def create_profile(owner: Keypair):
+    print(f"\nCreating profile for {owner.public_key}")
+    send(
+        owner,
+        (0).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_profile_address(owner.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def follow(follower: Keypair, followee: PublicKey, tag: int = 1):
+    print(f"\n{follower.public_key} {'follows' if tag == 1 else 'unfollows'} {followee}")
+    keys = [
+        AccountMeta(pubkey=follower.public_key, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=get_profile_address(follower.public_key), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=get_profile_address(followee), is_signer=False, is_writable=True),
+        AccountMeta(pubkey=get_follow_address(follower.public_key, followee), is_signer=False, is_writable=True),
+    ]
+    if tag == 1:
+        keys.append(AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False))
+    send(follower, tag.to_bytes(1, byteorder='little'), keys)
+
+def unfollow(follower: Keypair, followee: PublicKey):
+    follow(follower, followee, 2)
+
+def create_post(author: Keypair, text: str) -> PublicKey:
+    post = get_post_address(author.public_key, get_profile(author.public_key)['post_count'])
+    print(f"\n{author.public_key} posts '{text}' to {post}")
+    send(
+        author,
+        (3).to_bytes(1, byteorder='little') + bytes(text, encoding="utf8"),
+        [
+            AccountMeta(pubkey=author.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_profile_address(author.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=post, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return post
+
+def like(user: Keypair, post: PublicKey, tag: int = 4):
+    print(f"\n{user.public_key} {'likes' if tag == 4 else 'unlikes'} {post}")
+    keys = [
+        AccountMeta(pubkey=user.public_key, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=post, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=get_like_address(post, user.public_key), is_signer=False, is_writable=True),
+    ]
+    if tag == 4:
+        keys.append(AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False))
+    send(user, tag.to_bytes(1, byteorder='little'), keys)
+
+def unlike(user: Keypair, post: PublicKey):
+    like(user, post, 5)
+
+def show_profile(owner: PublicKey):
+    profile_info = get_profile(owner)
+    print(f"{owner} has {profile_info['follower_count']} followers, follows {profile_info['following_count']} "
+          f"and wrote {profile_info['post_count']} posts")
+
+def show_post(post: PublicKey):
+    account_info = client.get_account_info(post)["result"]["value"]
+    post_info = borsh.deserialize(POST_SCHEMA, base64.b64decode(account_info["data"][0]))
+    print(f"Post {post_info['index']} of {PublicKey(post_info['author'])}: '{post_info['text']}' with {post_info['like_count']} likes")
+
+def show_followers(followee: PublicKey):
+    # edges are small fixed size accounts, the RPC node filters them by size and by the followee at offset 32
+    accounts = client.get_program_accounts(
+        program_id,
+        encoding="base64",
+        data_size=FOLLOW_SIZE,
+        memcmp_opts=[MemcmpOpts(offset=32, bytes=str(followee))],
+    )["result"]
+    print(f"Followers of {followee}:")
+    for account in accounts:
+        print(f" * {PublicKey(base64.b64decode(account['account']['data'][0])[:32])}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+    carol = setup_user_keypair()
+    for user in [alice, bob, carol]:
+        create_profile(user)
+
+    follow(bob, alice.public_key)
+    follow(carol, alice.public_key)
+    follow(alice, bob.public_key)
+    try:
+        follow(bob, alice.public_key)
+    except Exception as e:
+        print(f"Following twice fails, the edge exists already: {e}")
+    show_profile(alice.public_key)
+    show_followers(alice.public_key)
+
+    post = create_post(alice, "gm from the social graph")
+    like(bob, post)
+    like(carol, post)
+    show_post(post)
+    unlike(carol, post)
+    show_post(post)
+
+    unfollow(carol, alice.public_key)
+    show_profile(alice.public_key)
+    show_followers(alice.public_key)
@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum SocialError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Profile Address")]
+    InvalidProfileAddress,
+    #[error("Invalid Post Address")]
+    InvalidPostAddress,
+    #[error("Invalid Follow Address")]
+    InvalidFollowAddress,
+    #[error("Invalid Like Address")]
+    InvalidLikeAddress,
+    #[error("Self Follow")]
+    SelfFollow,
+    #[error("Post Too Long")]
+    PostTooLong,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<SocialError> for ProgramError {
+    fn from(e: SocialError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
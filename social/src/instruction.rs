@@ -0,0 +1,98 @@
+use solana_program::program_error::ProgramError;
+
+use crate::error::SocialError::InvalidInstruction;
+
+pub enum SocialInstruction {
+
+    // Creates the profile of a user in a PDA derived from ["profile", owner].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner
+    // 1. `[writable]` The profile PDA
+    // 2. `[]` The system program
+    CreateProfile,
+
+    // Creates the follow edge PDA derived from ["follow", follower, followee] and updates both counters.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The follower, pays for the edge
+    // 1. `[writable]` The profile PDA of the follower
+    // 2. `[writable]` The profile PDA of the followee
+    // 3. `[writable]` The follow edge PDA
+    // 4. `[]` The system program
+    Follow,
+
+    // Closes the follow edge and updates both counters, the rent goes back to the follower.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The follower
+    // 1. `[writable]` The profile PDA of the follower
+    // 2. `[writable]` The profile PDA of the followee
+    // 3. `[writable]` The follow edge PDA
+    Unfollow,
+
+    // Creates a post in a PDA derived from ["post", author, index], where index is the post count stored in
+    // the profile before this instruction. Followed by the utf-8 text of the post.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The author
+    // 1. `[writable]` The profile PDA of the author
+    // 2. `[writable]` The post PDA for the next index
+    // 3. `[]` The system program
+    CreatePost {
+        text: String,
+    },
+
+    // Creates the like PDA derived from ["like", post, user] and increases the like counter of the post.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The user, pays for the like
+    // 1. `[writable]` The post PDA
+    // 2. `[writable]` The like PDA
+    // 3. `[]` The system program
+    Like,
+
+    // Closes the like PDA and decreases the like counter of the post, the rent goes back to the user.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The user
+    // 1. `[writable]` The post PDA
+    // 2. `[writable]` The like PDA
+    Unlike,
+}
+
+impl SocialInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateProfile,
+            1 => Self::Follow,
+            2 => Self::Unfollow,
+            3 => Self::CreatePost {
+                text: Self::unpack_text(rest)?,
+            },
+            4 => Self::Like,
+            5 => Self::Unlike,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_text(input: &[u8]) -> Result<String, ProgramError> {
+        let text = std::str::from_utf8(input)
+            .map_err(|_| InvalidInstruction)?;
+        Ok(text.to_string())
+    }
+}
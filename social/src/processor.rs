@@ -0,0 +1,450 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::SocialInstruction,
+    error::SocialError,
+    state::{Follow, Like, Post, Profile, MAX_POST_LEN},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = SocialInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            SocialInstruction::CreateProfile => {
+                msg!("Instruction: CreateProfile");
+                Self::process_create_profile(accounts, program_id)
+            },
+            SocialInstruction::Follow => {
+                msg!("Instruction: Follow");
+                Self::process_follow(accounts, program_id)
+            },
+            SocialInstruction::Unfollow => {
+                msg!("Instruction: Unfollow");
+                Self::process_unfollow(accounts, program_id)
+            },
+            SocialInstruction::CreatePost { text } => {
+                msg!("Instruction: CreatePost");
+                Self::process_create_post(accounts, text, program_id)
+            },
+            SocialInstruction::Like => {
+                msg!("Instruction: Like");
+                Self::process_like(accounts, program_id)
+            },
+            SocialInstruction::Unlike => {
+                msg!("Instruction: Unlike");
+                Self::process_unlike(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_profile(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let profile_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (profile_pda, bump_seed) = Pubkey::find_program_address(&[b"profile", owner.key.as_ref()], program_id);
+        if profile_pda != *profile_account.key {
+            return Err(SocialError::InvalidProfileAddress.into());
+        }
+
+        if !profile_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating profile account...");
+        Self::create_pda_account(
+            owner,
+            profile_account,
+            system_program,
+            Profile::LEN,
+            program_id,
+            &[b"profile", owner.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let profile_info = Profile {
+            is_initialized: true,
+            owner: *owner.key,
+            follower_count: 0,
+            following_count: 0,
+            post_count: 0,
+            bump_seed,
+        };
+        profile_info.serialize(&mut &mut profile_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_follow(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let follower = next_account_info(account_info_iter)?;
+        let follower_profile = next_account_info(account_info_iter)?;
+        let mut follower_info = Self::unpack_as_owner(follower, follower_profile, program_id)?;
+
+        let followee_profile = next_account_info(account_info_iter)?;
+        let mut followee_info = Self::unpack_profile(followee_profile, program_id)?;
+        if followee_info.owner == follower_info.owner {
+            return Err(SocialError::SelfFollow.into());
+        }
+
+        let follow_account = next_account_info(account_info_iter)?;
+        let (follow_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"follow", follower.key.as_ref(), followee_info.owner.as_ref()],
+            program_id,
+        );
+        if follow_pda != *follow_account.key {
+            return Err(SocialError::InvalidFollowAddress.into());
+        }
+
+        // the edge can only be created once, so nobody is counted twice as a follower
+        if !follow_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Creating follow edge...");
+        Self::create_pda_account(
+            follower,
+            follow_account,
+            system_program,
+            Follow::LEN,
+            program_id,
+            &[b"follow", follower.key.as_ref(), followee_info.owner.as_ref(), &[bump_seed]],
+        )?;
+
+        let follow_info = Follow {
+            follower: *follower.key,
+            followee: followee_info.owner,
+            created_ts: Clock::get()?.unix_timestamp,
+            bump_seed,
+        };
+        follow_info.serialize(&mut &mut follow_account.data.borrow_mut()[..])?;
+
+        follower_info.following_count = follower_info.following_count
+            .checked_add(1)
+            .ok_or(SocialError::AmountOverflow)?;
+        follower_info.serialize(&mut &mut follower_profile.data.borrow_mut()[..])?;
+
+        followee_info.follower_count = followee_info.follower_count
+            .checked_add(1)
+            .ok_or(SocialError::AmountOverflow)?;
+        followee_info.serialize(&mut &mut followee_profile.data.borrow_mut()[..])?;
+
+        msg!("{} follows {}", follower.key, followee_info.owner);
+        Ok(())
+    }
+
+    fn process_unfollow(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let follower = next_account_info(account_info_iter)?;
+        let follower_profile = next_account_info(account_info_iter)?;
+        let mut follower_info = Self::unpack_as_owner(follower, follower_profile, program_id)?;
+
+        let followee_profile = next_account_info(account_info_iter)?;
+        let mut followee_info = Self::unpack_profile(followee_profile, program_id)?;
+
+        let follow_account = next_account_info(account_info_iter)?;
+        let (follow_pda, _) = Pubkey::find_program_address(
+            &[b"follow", follower.key.as_ref(), followee_info.owner.as_ref()],
+            program_id,
+        );
+        if follow_pda != *follow_account.key {
+            return Err(SocialError::InvalidFollowAddress.into());
+        }
+        if follow_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        msg!("Closing follow edge...");
+        Self::close_account(follow_account, follower)?;
+
+        follower_info.following_count = follower_info.following_count
+            .checked_sub(1)
+            .ok_or(SocialError::AmountOverflow)?;
+        follower_info.serialize(&mut &mut follower_profile.data.borrow_mut()[..])?;
+
+        followee_info.follower_count = followee_info.follower_count
+            .checked_sub(1)
+            .ok_or(SocialError::AmountOverflow)?;
+        followee_info.serialize(&mut &mut followee_profile.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_create_post(
+        accounts: &[AccountInfo],
+        text: String,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if text.len() > MAX_POST_LEN {
+            return Err(SocialError::PostTooLong.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let author = next_account_info(account_info_iter)?;
+        let profile_account = next_account_info(account_info_iter)?;
+        let mut profile_info = Self::unpack_as_owner(author, profile_account, program_id)?;
+
+        let post_account = next_account_info(account_info_iter)?;
+        let index_bytes = profile_info.post_count.to_le_bytes();
+        let (post_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"post", author.key.as_ref(), &index_bytes],
+            program_id,
+        );
+        if post_pda != *post_account.key {
+            return Err(SocialError::InvalidPostAddress.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        let post_info = Post {
+            is_initialized: true,
+            author: *author.key,
+            index: profile_info.post_count,
+            created_ts: Clock::get()?.unix_timestamp,
+            like_count: 0,
+            text,
+        };
+
+        msg!("Creating post {}...", post_info.index);
+        Self::create_pda_account(
+            author,
+            post_account,
+            system_program,
+            Post::space(post_info.text.len()),
+            program_id,
+            &[b"post", author.key.as_ref(), &index_bytes, &[bump_seed]],
+        )?;
+        post_info.serialize(&mut &mut post_account.data.borrow_mut()[..])?;
+
+        profile_info.post_count = profile_info.post_count
+            .checked_add(1)
+            .ok_or(SocialError::AmountOverflow)?;
+        profile_info.serialize(&mut &mut profile_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_like(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user = next_account_info(account_info_iter)?;
+
+        if !user.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let post_account = next_account_info(account_info_iter)?;
+        let mut post_info = Self::unpack_post(post_account, program_id)?;
+
+        let like_account = next_account_info(account_info_iter)?;
+        let (like_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"like", post_account.key.as_ref(), user.key.as_ref()],
+            program_id,
+        );
+        if like_pda != *like_account.key {
+            return Err(SocialError::InvalidLikeAddress.into());
+        }
+
+        if !like_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Creating like account...");
+        Self::create_pda_account(
+            user,
+            like_account,
+            system_program,
+            Like::LEN,
+            program_id,
+            &[b"like", post_account.key.as_ref(), user.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let like_info = Like {
+            post: *post_account.key,
+            user: *user.key,
+            bump_seed,
+        };
+        like_info.serialize(&mut &mut like_account.data.borrow_mut()[..])?;
+
+        post_info.like_count = post_info.like_count
+            .checked_add(1)
+            .ok_or(SocialError::AmountOverflow)?;
+        post_info.serialize(&mut &mut post_account.data.borrow_mut()[..])?;
+
+        msg!("Post {} of {} has {} likes", post_info.index, post_info.author, post_info.like_count);
+        Ok(())
+    }
+
+    fn process_unlike(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user = next_account_info(account_info_iter)?;
+
+        if !user.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let post_account = next_account_info(account_info_iter)?;
+        let mut post_info = Self::unpack_post(post_account, program_id)?;
+
+        let like_account = next_account_info(account_info_iter)?;
+        let (like_pda, _) = Pubkey::find_program_address(
+            &[b"like", post_account.key.as_ref(), user.key.as_ref()],
+            program_id,
+        );
+        if like_pda != *like_account.key {
+            return Err(SocialError::InvalidLikeAddress.into());
+        }
+        if like_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        msg!("Closing like account...");
+        Self::close_account(like_account, user)?;
+
+        post_info.like_count = post_info.like_count
+            .checked_sub(1)
+            .ok_or(SocialError::AmountOverflow)?;
+        post_info.serialize(&mut &mut post_account.data.borrow_mut()[..])?;
+
+        msg!("Post {} of {} has {} likes", post_info.index, post_info.author, post_info.like_count);
+        Ok(())
+    }
+
+    fn unpack_profile(profile_account: &AccountInfo, program_id: &Pubkey) -> Result<Profile, ProgramError> {
+        if profile_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let profile_info = Profile::try_from_slice(&profile_account.data.borrow())?;
+        if !profile_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(profile_info)
+    }
+
+    fn unpack_as_owner(
+        owner: &AccountInfo,
+        profile_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<Profile, ProgramError> {
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let profile_info = Self::unpack_profile(profile_account, program_id)?;
+        if profile_info.owner != *owner.key {
+            return Err(SocialError::InvalidProfileAddress.into());
+        }
+
+        Ok(profile_info)
+    }
+
+    fn unpack_post(post_account: &AccountInfo, program_id: &Pubkey) -> Result<Post, ProgramError> {
+        if post_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let post_info = Post::try_from_slice(&post_account.data.borrow())?;
+        if !post_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(post_info)
+    }
+
+    fn close_account<'a>(account: &AccountInfo<'a>, destination: &AccountInfo<'a>) -> ProgramResult {
+        **destination.lamports.borrow_mut() = destination.lamports()
+            .checked_add(account.lamports())
+            .ok_or(SocialError::AmountOverflow)?;
+        **account.lamports.borrow_mut() = 0;
+        account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    //
+    // The next post of a profile lives at an address anyone can compute, and anyone can send lamports there.
+    // create_account refuses an account which holds lamports already, a third party could keep the user from ever
+    // posting again. Such an account is topped up to the rent instead, then allocated and assigned with the PDA seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let accounts = [
+            payer.clone(),
+            new_account.clone(),
+            system_program.clone(),
+        ];
+
+        if new_account.lamports() == 0 {
+            let create_account_ix = system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                lamports,
+                space as u64,
+                program_id,
+            );
+            return invoke_signed(&create_account_ix, &accounts, &[signer_seeds]);
+        }
+
+        let shortfall = lamports.saturating_sub(new_account.lamports());
+        if shortfall > 0 {
+            invoke(&system_instruction::transfer(payer.key, new_account.key, shortfall), &accounts)?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(new_account.key, space as u64),
+            &accounts,
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(new_account.key, program_id),
+            &accounts,
+            &[signer_seeds],
+        )
+    }
+}
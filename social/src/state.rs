@@ -0,0 +1,63 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Longest post a user is allowed to write, in bytes
+pub const MAX_POST_LEN: usize = 280;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Profile {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    pub follower_count: u64,
+    pub following_count: u64,
+    // number of posts written so far, the next post is created at this index
+    pub post_count: u64,
+    pub bump_seed: u8,
+}
+
+impl Profile {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Post {
+    pub is_initialized: bool,
+    pub author: Pubkey,
+    pub index: u64,
+    pub created_ts: i64,
+    pub like_count: u64,
+    pub text: String,
+}
+
+impl Post {
+    // Posts are sized exactly to their text, borsh stores a string as a u32 length followed by its bytes
+    pub fn space(text_len: usize) -> usize {
+        1 + 32 + 8 + 8 + 8 + 4 + text_len
+    }
+}
+
+// An edge of the follow graph. The account only exists while the follower follows the followee,
+// its address alone answers whether one user follows another.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Follow {
+    pub follower: Pubkey,
+    pub followee: Pubkey,
+    pub created_ts: i64,
+    pub bump_seed: u8,
+}
+
+impl Follow {
+    pub const LEN: usize = 32 + 32 + 8 + 1;
+}
+
+// Exists while a user likes a post, which makes liking the same post twice impossible
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Like {
+    pub post: Pubkey,
+    pub user: Pubkey,
+    pub bump_seed: u8,
+}
+
+impl Like {
+    pub const LEN: usize = 32 + 32 + 1;
+}
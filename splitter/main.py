@@ -0,0 +1,230 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID, TransferParams, transfer
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+SPLIT_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'creator': types.fixed_array(types.u8, 32),
+    'id': types.u64,
+    'total_distributed': types.u64,
+    'bump_seed': types.u8,
+    'shares': types.dynamic_array(borsh.schema({
+        'recipient': types.fixed_array(types.u8, 32),
+        'bps': types.u16
+    }))
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'splitter.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'splitter-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_split_address(creator: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("split", encoding="utf8"), bytes(creator), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def balance(pubkey: PublicKey) -> int:
+    return client.get_balance(pubkey)["result"]["value"]
+
+def create_split(creator: Keypair, id: int, shares: list) -> PublicKey:
+    split = get_split_address(creator.public_key, id)
+    print(f"\nCreating split {split}")
+    data = (0).to_bytes(1, byteorder='little') + id.to_bytes(8, byteorder='little')
+    for recipient, bps in shares:
+        print(f"{recipient} gets {bps / 100}%")
+        data += bytes(recipient) + bps.to_bytes(2, byteorder='little')
+    send(
+        creator,
+        data,
+        [
+            AccountMeta(pubkey=creator.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=split, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return split
+
+# the split is an ordinary account that can receive lamports from anybody
+def pay(sender: Keypair, split: PublicKey, amount: int):
+    print(f"\nPaying {amount} lamports into the split")
+    tx = client.send_transaction(
+        Transaction().add(transfer(TransferParams(from_pubkey=sender.public_key, to_pubkey=split, lamports=amount))),
+        sender,
+    )
+    client.confirm_transaction(tx["result"])
+
+def distribute(caller: Keypair, split: PublicKey, recipients: list):
+    print("\nDistributing")
+    keys = [AccountMeta(pubkey=split, is_signer=False, is_writable=True)]
+    keys += [AccountMeta(pubkey=recipient, is_signer=False, is_writable=True) for recipient in recipients]
+    send(caller, (1).to_bytes(1, byteorder='little'), keys)
+
+def show_split(split: PublicKey):
+    account_info = client.get_account_info(split)["result"]["value"]
+    split_info = borsh.deserialize(SPLIT_SCHEMA, base64.b64decode(account_info["data"][0]))
+    print(f"Split holds {account_info['lamports']} lamports, {split_info['total_distributed']} have been distributed")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+    carol = setup_user_keypair()
+    recipients = [alice.public_key, bob.public_key, carol.public_key]
+
+    try:
+        create_split(payer, 0, [(alice.public_key, 5000), (bob.public_key, 4000)])
+    except Exception as e:
+        print(f"Shares not adding up to 100% fail as expected: {e}")
+
+    split = create_split(payer, 0, [(alice.public_key, 5000), (bob.public_key, 3000), (carol.public_key, 2000)])
+    pay(payer, split, LAMPORTS_PER_SOL // 10)
+    pay(payer, split, LAMPORTS_PER_SOL // 20)
+    show_split(split)
+
+    before = [balance(recipient) for recipient in recipients]
+    # anybody can crank the distribution, it doesn't have to be the creator or a recipient
+    distribute(payer, split, recipients)
+    for recipient, previous in zip(recipients, before):
+        print(f"{recipient} received {balance(recipient) - previous} lamports")
+    show_split(split)
+
+    try:
+        distribute(payer, split, recipients)
+    except Exception as e:
+        print(f"Distributing an empty split fails as expected: {e}")
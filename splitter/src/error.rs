@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum SplitterError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Split Address")]
+    InvalidSplitAddress,
+    #[error("Invalid Shares")]
+    InvalidShares,
+    #[error("Invalid Recipient")]
+    InvalidRecipient,
+    #[error("Nothing To Distribute")]
+    NothingToDistribute,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<SplitterError> for ProgramError {
+    fn from(e: SplitterError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
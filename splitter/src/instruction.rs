@@ -0,0 +1,71 @@
+use std::convert::TryInto;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{error::SplitterError::InvalidInstruction, state::Share};
+
+pub enum SplitterInstruction {
+
+    // Creates a split in a PDA derived from ["split", creator, id]. The shares can't be changed afterwards.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The creator
+    // 1. `[writable]` The split PDA
+    // 2. `[]` The system program
+    CreateSplit {
+        id: u64,
+        shares: Vec<Share>,
+    },
+
+    // Pays the lamports the split holds on top of its rent out to the recipients, according to their shares.
+    // Anybody may call it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[writable]` The split PDA
+    // 1. `[writable]` The recipients, one account each, in the order of the shares
+    Distribute,
+}
+
+impl SplitterInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateSplit {
+                id: Self::unpack_u64(rest, 0)?,
+                shares: Self::unpack_shares(rest.get(8..).ok_or(InvalidInstruction)?)?,
+            },
+            1 => Self::Distribute,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+
+    // every share is a recipient pubkey followed by the u16 basis points, the number of shares follows from the length
+    fn unpack_shares(input: &[u8]) -> Result<Vec<Share>, ProgramError> {
+        if input.is_empty() || !input.len().is_multiple_of(Share::LEN) {
+            return Err(InvalidInstruction.into());
+        }
+
+        let shares = input
+            .chunks_exact(Share::LEN)
+            .map(|chunk| {
+                let recipient = chunk[..32].try_into().map(Pubkey::new_from_array)?;
+                Ok(Share { recipient, bps: u16::from_le_bytes([chunk[32], chunk[33]]) })
+            })
+            .collect::<Result<Vec<_>, std::array::TryFromSliceError>>()
+            .map_err(|_| InvalidInstruction)?;
+        Ok(shares)
+    }
+}
@@ -0,0 +1,156 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::invoke_signed,
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::SplitterInstruction,
+    error::SplitterError,
+    state::{Share, Split, MAX_RECIPIENTS, TOTAL_BPS},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = SplitterInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            SplitterInstruction::CreateSplit { id, shares } => {
+                msg!("Instruction: CreateSplit");
+                Self::process_create_split(accounts, id, shares, program_id)
+            },
+            SplitterInstruction::Distribute => {
+                msg!("Instruction: Distribute");
+                Self::process_distribute(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_split(
+        accounts: &[AccountInfo],
+        id: u64,
+        shares: Vec<Share>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        // every recipient appears once and gets something, together they get everything
+        let total_bps = shares.iter().map(|share| share.bps as u32).sum::<u32>();
+        let duplicates = shares.iter().enumerate()
+            .any(|(index, share)| shares[..index].iter().any(|other| other.recipient == share.recipient));
+        if shares.len() > MAX_RECIPIENTS
+            || total_bps != TOTAL_BPS as u32
+            || duplicates
+            || shares.iter().any(|share| share.bps == 0)
+        {
+            return Err(SplitterError::InvalidShares.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let split_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (split_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"split", creator.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if split_pda != *split_account.key {
+            return Err(SplitterError::InvalidSplitAddress.into());
+        }
+
+        if !split_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let space = Split::space(shares.len());
+        msg!("Creating split account with {} recipients...", shares.len());
+        invoke_signed(
+            &system_instruction::create_account(
+                creator.key,
+                split_account.key,
+                Rent::get()?.minimum_balance(space),
+                space as u64,
+                program_id,
+            ),
+            &[
+                creator.clone(),
+                split_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"split", creator.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        let split_info = Split {
+            is_initialized: true,
+            creator: *creator.key,
+            id,
+            total_distributed: 0,
+            bump_seed,
+            shares,
+        };
+        split_info.serialize(&mut &mut split_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_distribute(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let split_account = next_account_info(account_info_iter)?;
+        if split_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut split_info = Split::try_from_slice(&split_account.data.borrow())?;
+        if !split_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        // anybody can send lamports to the split with a plain transfer, everything on top of the rent is distributed
+        let rent_lamports = Rent::get()?.minimum_balance(split_account.data_len());
+        let funds = split_account.lamports().saturating_sub(rent_lamports);
+        if funds == 0 {
+            return Err(SplitterError::NothingToDistribute.into());
+        }
+
+        // the shares are rounded down, whatever is left over stays in the split for the next distribution
+        let mut distributed = 0;
+        for share in &split_info.shares {
+            let recipient = next_account_info(account_info_iter)?;
+            if *recipient.key != share.recipient {
+                return Err(SplitterError::InvalidRecipient.into());
+            }
+
+            let amount = (funds as u128 * share.bps as u128 / TOTAL_BPS as u128) as u64;
+            msg!("Paying {} lamports to {}", amount, recipient.key);
+            **split_account.lamports.borrow_mut() -= amount;
+            **recipient.lamports.borrow_mut() = recipient.lamports()
+                .checked_add(amount)
+                .ok_or(SplitterError::AmountOverflow)?;
+            distributed += amount;
+        }
+
+        split_info.total_distributed = split_info.total_distributed
+            .checked_add(distributed)
+            .ok_or(SplitterError::AmountOverflow)?;
+        split_info.serialize(&mut &mut split_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+}
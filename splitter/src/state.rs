@@ -0,0 +1,37 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Most recipients a single split can pay
+pub const MAX_RECIPIENTS: usize = 16;
+
+// The shares are in basis points, they add up to 100%
+pub const TOTAL_BPS: u16 = 10_000;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Share {
+    pub recipient: Pubkey,
+    pub bps: u16,
+}
+
+impl Share {
+    pub const LEN: usize = 32 + 2;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Split {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub id: u64,
+    // lamports paid out to the recipients so far
+    pub total_distributed: u64,
+    pub bump_seed: u8,
+    pub shares: Vec<Share>,
+}
+
+impl Split {
+    // The shares can't change after the split is created, so the account is sized exactly.
+    // borsh stores a vector as a u32 length followed by its elements.
+    pub fn space(share_count: usize) -> usize {
+        1 + 32 + 8 + 8 + 1 + 4 + share_count * Share::LEN
+    }
+}
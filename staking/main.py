@@ -0,0 +1,287 @@
+import yaml
+import sys
+import json
+import time
+import base64
+import struct
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'staking.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'staking-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_program(payer: Keypair, decimals: int) -> Token:
+    token = Token.create_mint(
+        conn=client,
+        payer=payer,
+        mint_authority=payer.public_key,
+        decimals=decimals,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    print(f"Address of token program: {token.pubkey}")
+    return token
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def mint_token(token: Token, minter: Keypair, account: PublicKey, amount: int):
+    print(f"Minting {amount} tokens to {account}")
+    token.mint_to(
+        dest=account,
+        mint_authority=minter,
+        amount=amount,
+        opts=TxOpts(skip_confirmation=False)
+    )
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+def get_pool_address(authority: PublicKey, stake_mint: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("pool", encoding="utf8"), bytes(authority), bytes(stake_mint)],
+        program_id
+    )[0]
+
+def get_stake_address(pool: PublicKey, owner: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("stake", encoding="utf8"), bytes(pool), bytes(owner)],
+        program_id
+    )[0]
+
+def init_pool(authority: Keypair, stake_token: Token, reward_token: Token, reward_rate: int) -> dict:
+    pool = get_pool_address(authority.public_key, stake_token.pubkey)
+    # the vaults are normal token accounts, their owner is the pool PDA
+    stake_vault = create_token_account(stake_token, pool)
+    reward_vault = create_token_account(reward_token, pool)
+
+    print(f"\nCreating pool {pool} paying {reward_rate} reward tokens per second")
+    init_ix = TransactionInstruction(
+        data=bytes(1) + reward_rate.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=pool, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=stake_token.pubkey, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=reward_token.pubkey, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=stake_vault, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=reward_vault, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(init_ix), authority)
+    client.confirm_transaction(tx["result"])
+    return {'pool': pool, 'stake_vault': stake_vault, 'reward_vault': reward_vault}
+
+def stake(staker: Keypair, pool: dict, token_account: PublicKey, amount: int):
+    print(f"\n{staker.public_key} stakes {amount} tokens")
+    stake_ix = TransactionInstruction(
+        data=(1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=staker.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=pool['pool'], is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_stake_address(pool['pool'], staker.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=token_account, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=pool['stake_vault'], is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(stake_ix), staker)
+    client.confirm_transaction(tx["result"])
+
+def unstake(staker: Keypair, pool: dict, token_account: PublicKey, amount: int):
+    print(f"\n{staker.public_key} unstakes {amount} tokens")
+    unstake_ix = TransactionInstruction(
+        data=(2).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=staker.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=pool['pool'], is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_stake_address(pool['pool'], staker.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=token_account, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=pool['stake_vault'], is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(unstake_ix), staker)
+    client.confirm_transaction(tx["result"])
+
+def claim(staker: Keypair, pool: dict, reward_account: PublicKey):
+    print(f"\n{staker.public_key} claims rewards")
+    claim_ix = TransactionInstruction(
+        data=(3).to_bytes(1, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=staker.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=pool['pool'], is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_stake_address(pool['pool'], staker.public_key), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=reward_account, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=pool['reward_vault'], is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(claim_ix), staker)
+    client.confirm_transaction(tx["result"])
+
+def show_stake(pool: dict, owner: PublicKey):
+    account_info = client.get_account_info(get_stake_address(pool['pool'], owner))["result"]["value"]
+    data = base64.b64decode(account_info["data"][0])
+    # is_initialized (1) + pool (32) + owner (32), followed by amount, reward debt (u128 as two u64) and pending rewards
+    amount, debt_low, debt_high, pending = struct.unpack_from("<QQQQ", data, 1 + 32 + 32)
+    print(f"{owner} has {amount} tokens staked and {pending} settled rewards")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+
+    stake_token = create_token_program(payer, decimals=0)
+    reward_token = create_token_program(payer, decimals=0)
+    pool = init_pool(payer, stake_token, reward_token, reward_rate=10)
+    # funding the pool is just a transfer into the reward vault
+    mint_token(reward_token, payer, pool['reward_vault'], 1_000_000)
+
+    bob = setup_user_keypair()
+    bob_stake_account = create_token_account(stake_token, bob.public_key)
+    bob_reward_account = create_token_account(reward_token, bob.public_key)
+    mint_token(stake_token, payer, bob_stake_account, 1000)
+
+    stake(bob, pool, bob_stake_account, 100)
+    show_stake(pool, bob.public_key)
+    print("\nWaiting a few seconds for rewards to accrue...")
+    time.sleep(5)
+    claim(bob, pool, bob_reward_account)
+    print(f"Bob's reward balance: {token_balance(reward_token, bob_reward_account)}")
+    unstake(bob, pool, bob_stake_account, 100)
+    show_stake(pool, bob.public_key)
+    print(f"Bob's stake token balance: {token_balance(stake_token, bob_stake_account)}")
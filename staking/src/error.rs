@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum StakingError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Pool Address")]
+    InvalidPoolAddress,
+    #[error("Invalid Stake Address")]
+    InvalidStakeAddress,
+    #[error("Invalid Vault")]
+    InvalidVault,
+    #[error("Insufficient Stake")]
+    InsufficientStake,
+    #[error("Nothing To Claim")]
+    NothingToClaim,
+    #[error("Math Overflow")]
+    MathOverflow,
+}
+
+impl From<StakingError> for ProgramError {
+    fn from(e: StakingError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
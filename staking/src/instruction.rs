@@ -0,0 +1,99 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::StakingError::InvalidInstruction;
+
+pub enum StakingInstruction {
+
+    // Creates a staking pool in a PDA derived from ["pool", authority, stake mint]. Both vaults have to be
+    // created prior to this instruction and owned by the pool PDA. Rewards are funded by transferring
+    // reward tokens into the reward vault.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The authority creating the pool, pays for the pool account
+    // 1. `[writable]` The pool PDA
+    // 2. `[]` The mint of the token being staked
+    // 3. `[]` The mint of the reward token
+    // 4. `[]` The stake vault, a token account for the stake mint owned by the pool PDA
+    // 5. `[]` The reward vault, a token account for the reward mint owned by the pool PDA
+    // 6. `[]` The system program
+    InitPool {
+        // reward tokens paid out per second, split between all stakers
+        reward_rate: u64,
+    },
+
+    // Locks tokens in the stake vault. The stake account PDA ["stake", pool, owner] is created on the first stake.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The staker
+    // 1. `[writable]` The pool PDA
+    // 2. `[writable]` The staker's stake account PDA
+    // 3. `[writable]` The staker's token account to take the tokens from
+    // 4. `[writable]` The stake vault
+    // 5. `[]` The token program
+    // 6. `[]` The system program
+    Stake {
+        amount: u64,
+    },
+
+    // Takes tokens out of the stake vault. Rewards earned so far stay claimable.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The staker
+    // 1. `[writable]` The pool PDA
+    // 2. `[writable]` The staker's stake account PDA
+    // 3. `[writable]` The staker's token account to receive the tokens
+    // 4. `[writable]` The stake vault
+    // 5. `[]` The token program
+    Unstake {
+        amount: u64,
+    },
+
+    // Pays out all rewards earned so far from the reward vault.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The staker
+    // 1. `[writable]` The pool PDA
+    // 2. `[writable]` The staker's stake account PDA
+    // 3. `[writable]` The staker's token account for the reward token
+    // 4. `[writable]` The reward vault
+    // 5. `[]` The token program
+    Claim,
+}
+
+impl StakingInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitPool {
+                reward_rate: Self::unpack_amount(rest)?,
+            },
+            1 => Self::Stake {
+                amount: Self::unpack_amount(rest)?,
+            },
+            2 => Self::Unstake {
+                amount: Self::unpack_amount(rest)?,
+            },
+            3 => Self::Claim,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_amount(input: &[u8]) -> Result<u64, ProgramError> {
+        let amount = input
+            .get(..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(amount)
+    }
+}
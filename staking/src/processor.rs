@@ -0,0 +1,441 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::Account as TokenAccount;
+
+use crate::{
+    instruction::StakingInstruction,
+    error::StakingError,
+    state::{Pool, StakeAccount},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = StakingInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            StakingInstruction::InitPool { reward_rate } => {
+                msg!("Instruction: InitPool");
+                Self::process_init_pool(accounts, reward_rate, program_id)
+            },
+            StakingInstruction::Stake { amount } => {
+                msg!("Instruction: Stake");
+                Self::process_stake(accounts, amount, program_id)
+            },
+            StakingInstruction::Unstake { amount } => {
+                msg!("Instruction: Unstake");
+                Self::process_unstake(accounts, amount, program_id)
+            },
+            StakingInstruction::Claim => {
+                msg!("Instruction: Claim");
+                Self::process_claim(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_init_pool(
+        accounts: &[AccountInfo],
+        reward_rate: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let stake_mint = next_account_info(account_info_iter)?;
+        let reward_mint = next_account_info(account_info_iter)?;
+        let stake_vault = next_account_info(account_info_iter)?;
+        let reward_vault = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (pool_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"pool", authority.key.as_ref(), stake_mint.key.as_ref()],
+            program_id,
+        );
+        if pool_pda != *pool_account.key {
+            return Err(StakingError::InvalidPoolAddress.into());
+        }
+
+        if !pool_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the vaults must belong to the pool, otherwise the pool couldn't pay anything out
+        Self::check_vault(stake_vault, stake_mint.key, &pool_pda)?;
+        Self::check_vault(reward_vault, reward_mint.key, &pool_pda)?;
+
+        msg!("Creating pool account...");
+        Self::create_pda_account(
+            authority,
+            pool_account,
+            system_program,
+            Pool::LEN,
+            program_id,
+            &[b"pool", authority.key.as_ref(), stake_mint.key.as_ref(), &[bump_seed]],
+        )?;
+
+        let pool_info = Pool {
+            is_initialized: true,
+            authority: *authority.key,
+            stake_mint: *stake_mint.key,
+            reward_mint: *reward_mint.key,
+            stake_vault: *stake_vault.key,
+            reward_vault: *reward_vault.key,
+            reward_rate,
+            total_staked: 0,
+            acc_reward_per_share: 0,
+            last_update_ts: Clock::get()?.unix_timestamp,
+            bump_seed,
+        };
+        pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_stake(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let staker = next_account_info(account_info_iter)?;
+
+        if !staker.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let mut pool_info = Self::unpack_pool(pool_account, program_id)?;
+
+        let stake_account = next_account_info(account_info_iter)?;
+        let staker_token_account = next_account_info(account_info_iter)?;
+
+        let stake_vault = next_account_info(account_info_iter)?;
+        if pool_info.stake_vault != *stake_vault.key {
+            return Err(StakingError::InvalidVault.into());
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (stake_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"stake", pool_account.key.as_ref(), staker.key.as_ref()],
+            program_id,
+        );
+        if stake_pda != *stake_account.key {
+            return Err(StakingError::InvalidStakeAddress.into());
+        }
+
+        if stake_account.data_is_empty() {
+            msg!("Creating stake account...");
+            Self::create_pda_account(
+                staker,
+                stake_account,
+                system_program,
+                StakeAccount::LEN,
+                program_id,
+                &[b"stake", pool_account.key.as_ref(), staker.key.as_ref(), &[bump_seed]],
+            )?;
+
+            let stake_info = StakeAccount {
+                is_initialized: true,
+                pool: *pool_account.key,
+                owner: *staker.key,
+                amount: 0,
+                reward_debt: 0,
+                pending_rewards: 0,
+                bump_seed,
+            };
+            stake_info.serialize(&mut &mut stake_account.data.borrow_mut()[..])?;
+        }
+
+        let mut stake_info = Self::unpack_stake_account(stake_account, staker.key, program_id)?;
+
+        pool_info.update(Clock::get()?.unix_timestamp)?;
+        stake_info.settle(pool_info.acc_reward_per_share)?;
+
+        let transfer_to_vault_ix = spl_token::instruction::transfer(
+            token_program.key,
+            staker_token_account.key,
+            stake_vault.key,
+            staker.key,
+            &[staker.key],
+            amount,
+        )?;
+        msg!("Calling the token program to transfer tokens into the stake vault...");
+        invoke(
+            &transfer_to_vault_ix,
+            &[
+                staker_token_account.clone(),
+                stake_vault.clone(),
+                staker.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        stake_info.amount = stake_info.amount
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        stake_info.reset_debt(pool_info.acc_reward_per_share)?;
+        pool_info.total_staked = pool_info.total_staked
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+
+        stake_info.serialize(&mut &mut stake_account.data.borrow_mut()[..])?;
+        pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_unstake(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let staker = next_account_info(account_info_iter)?;
+
+        if !staker.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let mut pool_info = Self::unpack_pool(pool_account, program_id)?;
+
+        let stake_account = next_account_info(account_info_iter)?;
+        let mut stake_info = Self::unpack_stake_account(stake_account, staker.key, program_id)?;
+        if stake_info.pool != *pool_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if amount > stake_info.amount {
+            return Err(StakingError::InsufficientStake.into());
+        }
+
+        let staker_token_account = next_account_info(account_info_iter)?;
+
+        let stake_vault = next_account_info(account_info_iter)?;
+        if pool_info.stake_vault != *stake_vault.key {
+            return Err(StakingError::InvalidVault.into());
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        pool_info.update(Clock::get()?.unix_timestamp)?;
+        stake_info.settle(pool_info.acc_reward_per_share)?;
+
+        Self::transfer_from_vault(
+            &pool_info,
+            pool_account,
+            stake_vault,
+            staker_token_account,
+            token_program,
+            amount,
+        )?;
+
+        stake_info.amount -= amount;
+        stake_info.reset_debt(pool_info.acc_reward_per_share)?;
+        pool_info.total_staked -= amount;
+
+        stake_info.serialize(&mut &mut stake_account.data.borrow_mut()[..])?;
+        pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_claim(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let staker = next_account_info(account_info_iter)?;
+
+        if !staker.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let pool_account = next_account_info(account_info_iter)?;
+        let mut pool_info = Self::unpack_pool(pool_account, program_id)?;
+
+        let stake_account = next_account_info(account_info_iter)?;
+        let mut stake_info = Self::unpack_stake_account(stake_account, staker.key, program_id)?;
+        if stake_info.pool != *pool_account.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let staker_reward_account = next_account_info(account_info_iter)?;
+
+        let reward_vault = next_account_info(account_info_iter)?;
+        if pool_info.reward_vault != *reward_vault.key {
+            return Err(StakingError::InvalidVault.into());
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        pool_info.update(Clock::get()?.unix_timestamp)?;
+        stake_info.settle(pool_info.acc_reward_per_share)?;
+        stake_info.reset_debt(pool_info.acc_reward_per_share)?;
+
+        let rewards = stake_info.pending_rewards;
+        if rewards == 0 {
+            return Err(StakingError::NothingToClaim.into());
+        }
+
+        msg!("Paying out {} reward tokens...", rewards);
+        Self::transfer_from_vault(
+            &pool_info,
+            pool_account,
+            reward_vault,
+            staker_reward_account,
+            token_program,
+            rewards,
+        )?;
+
+        stake_info.pending_rewards = 0;
+
+        stake_info.serialize(&mut &mut stake_account.data.borrow_mut()[..])?;
+        pool_info.serialize(&mut &mut pool_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn unpack_pool(pool_account: &AccountInfo, program_id: &Pubkey) -> Result<Pool, ProgramError> {
+        if pool_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let pool_info = Pool::try_from_slice(&pool_account.data.borrow())?;
+        if !pool_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(pool_info)
+    }
+
+    fn unpack_stake_account(
+        stake_account: &AccountInfo,
+        staker: &Pubkey,
+        program_id: &Pubkey,
+    ) -> Result<StakeAccount, ProgramError> {
+        if stake_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let stake_info = StakeAccount::try_from_slice(&stake_account.data.borrow())?;
+        if !stake_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if stake_info.owner != *staker {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(stake_info)
+    }
+
+    fn check_vault(vault: &AccountInfo, mint: &Pubkey, pool: &Pubkey) -> ProgramResult {
+        if *vault.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let vault_info = TokenAccount::unpack(&vault.try_borrow_data()?)?;
+        if vault_info.mint != *mint || vault_info.owner != *pool {
+            return Err(StakingError::InvalidVault.into());
+        }
+
+        Ok(())
+    }
+
+    // The pool PDA owns both vaults, so the program signs the token transfer with the pool's seeds
+    fn transfer_from_vault<'a>(
+        pool_info: &Pool,
+        pool_account: &AccountInfo<'a>,
+        vault: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        let transfer_ix = spl_token::instruction::transfer(
+            token_program.key,
+            vault.key,
+            destination.key,
+            pool_account.key,
+            &[pool_account.key],
+            amount,
+        )?;
+
+        msg!("Calling the token program to transfer tokens out of the vault...");
+        invoke_signed(
+            &transfer_ix,
+            &[
+                vault.clone(),
+                destination.clone(),
+                pool_account.clone(),
+                token_program.clone(),
+            ],
+            &[&[
+                b"pool",
+                pool_info.authority.as_ref(),
+                pool_info.stake_mint.as_ref(),
+                &[pool_info.bump_seed],
+            ]],
+        )
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,94 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+use crate::error::StakingError;
+
+// The accumulated reward per staked token is a fraction, it is kept scaled up by this factor
+pub const PRECISION: u128 = 1_000_000_000_000;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Pool {
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+    pub stake_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    // token accounts owned by the pool PDA
+    pub stake_vault: Pubkey,
+    pub reward_vault: Pubkey,
+    // reward tokens handed out per second, shared by all stakers
+    pub reward_rate: u64,
+    pub total_staked: u64,
+    // rewards earned by a single staked token since the pool was created, scaled by PRECISION
+    pub acc_reward_per_share: u128,
+    pub last_update_ts: i64,
+    pub bump_seed: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 1 + 32 * 5 + 8 + 8 + 16 + 8 + 1;
+
+    // Brings acc_reward_per_share up to date. Every staked token earned reward_rate / total_staked
+    // per second since the last update.
+    pub fn update(&mut self, now: i64) -> Result<(), StakingError> {
+        if now <= self.last_update_ts {
+            return Ok(());
+        }
+
+        if self.total_staked > 0 {
+            let elapsed = (now - self.last_update_ts) as u128;
+            let reward = elapsed
+                .checked_mul(self.reward_rate as u128)
+                .and_then(|reward| reward.checked_mul(PRECISION))
+                .ok_or(StakingError::MathOverflow)?;
+            self.acc_reward_per_share = self.acc_reward_per_share
+                .checked_add(reward / self.total_staked as u128)
+                .ok_or(StakingError::MathOverflow)?;
+        }
+        self.last_update_ts = now;
+
+        Ok(())
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct StakeAccount {
+    pub is_initialized: bool,
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    // amount * acc_reward_per_share at the time of the last settlement, the share the staker is not entitled to
+    pub reward_debt: u128,
+    // rewards settled but not claimed yet
+    pub pending_rewards: u64,
+    pub bump_seed: u8,
+}
+
+impl StakeAccount {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 16 + 8 + 1;
+
+    // Moves everything earned since the last settlement into pending_rewards. Has to be called with an
+    // up to date pool, before the staked amount changes.
+    pub fn settle(&mut self, acc_reward_per_share: u128) -> Result<(), StakingError> {
+        let accumulated = (self.amount as u128)
+            .checked_mul(acc_reward_per_share)
+            .ok_or(StakingError::MathOverflow)?
+            / PRECISION;
+        let earned = accumulated
+            .checked_sub(self.reward_debt)
+            .ok_or(StakingError::MathOverflow)?;
+        self.pending_rewards = (self.pending_rewards as u128)
+            .checked_add(earned)
+            .and_then(|pending| u64::try_from(pending).ok())
+            .ok_or(StakingError::MathOverflow)?;
+        Ok(())
+    }
+
+    // Resets the reward debt after the staked amount changed
+    pub fn reset_debt(&mut self, acc_reward_per_share: u128) -> Result<(), StakingError> {
+        self.reward_debt = (self.amount as u128)
+            .checked_mul(acc_reward_per_share)
+            .ok_or(StakingError::MathOverflow)?
+            / PRECISION;
+        Ok(())
+    }
+}
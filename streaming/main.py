@@ -0,0 +1,299 @@
+import yaml
+import sys
+import json
+import time
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID, TransferParams, transfer
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+# what a crank pays the cranker, CRANK_REWARD of state.rs
+CRANK_REWARD = 10_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'streaming.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'streaming-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_program(payer: Keypair, decimals: int) -> Token:
+    token = Token.create_mint(
+        conn=client,
+        payer=payer,
+        mint_authority=payer.public_key,
+        decimals=decimals,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    print(f"Address of token program: {token.pubkey}")
+    return token
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def mint_token(token: Token, minter: Keypair, account: PublicKey, amount: int):
+    print(f"Minting {amount} tokens to {account}")
+    token.mint_to(
+        dest=account,
+        mint_authority=minter,
+        amount=amount,
+        opts=TxOpts(skip_confirmation=False)
+    )
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+
+def get_stream_address(sender: PublicKey, recipient: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("stream", encoding="utf8"), bytes(sender), bytes(recipient), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def create_stream(sender: Keypair, recipient: PublicKey, token: Token, source: PublicKey, id: int, deposit: int, start_ts: int, end_ts: int) -> PublicKey:
+    stream = get_stream_address(sender.public_key, recipient, id)
+    # the vault is a normal token account owned by the stream PDA
+    vault = create_token_account(token, stream)
+    print(f"\nStreaming {deposit} tokens to {recipient} from {start_ts} to {end_ts}")
+    create_ix = TransactionInstruction(
+        data=(
+            (0).to_bytes(1, byteorder='little')
+            + id.to_bytes(8, byteorder='little')
+            + deposit.to_bytes(8, byteorder='little')
+            + start_ts.to_bytes(8, byteorder='little', signed=True)
+            + end_ts.to_bytes(8, byteorder='little', signed=True)
+        ),
+        keys=[
+            AccountMeta(pubkey=sender.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=recipient, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=stream, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=source, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(create_ix), sender)
+    client.confirm_transaction(tx["result"])
+    return vault
+
+def withdraw(recipient: Keypair, sender: PublicKey, id: int, vault: PublicKey, destination: PublicKey, amount: int = 0):
+    print(f"\nRecipient withdraws {amount if amount else 'everything streamed so far'}")
+    withdraw_ix = TransactionInstruction(
+        data=(1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=recipient.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=get_stream_address(sender, recipient.public_key, id), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(withdraw_ix), recipient)
+    client.confirm_transaction(tx["result"])
+
+def cancel(sender: Keypair, recipient: PublicKey, id: int, vault: PublicKey, sender_account: PublicKey, recipient_account: PublicKey):
+    print("\nSender cancels the stream")
+    cancel_ix = TransactionInstruction(
+        data=(2).to_bytes(1, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=sender.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_stream_address(sender.public_key, recipient, id), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=sender_account, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=recipient_account, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(cancel_ix), sender)
+    client.confirm_transaction(tx["result"])
+
+def fund_cranks(sender: Keypair, stream: PublicKey, cranks: int):
+    # the rewards are paid out of the lamports of the stream account, a plain transfer adds them
+    print(f"\nFunding {cranks} cranks of {stream}")
+    tx = Transaction().add(transfer(TransferParams(
+        from_pubkey=sender.public_key,
+        to_pubkey=stream,
+        lamports=cranks * CRANK_REWARD,
+    )))
+    result = client.send_transaction(tx, sender)
+    client.confirm_transaction(result["result"])
+
+def crank(cranker: Keypair, stream: PublicKey, vault: PublicKey, recipient_account: PublicKey):
+    print(f"\n{cranker.public_key} cranks the stream")
+    crank_ix = TransactionInstruction(
+        data=(3).to_bytes(1, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=cranker.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=stream, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=recipient_account, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(crank_ix), cranker)
+    client.confirm_transaction(tx["result"])
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    recipient = setup_user_keypair()
+
+    token = create_token_program(payer, decimals=0)
+    sender_account = create_token_account(token, payer.public_key)
+    recipient_account = create_token_account(token, recipient.public_key)
+    mint_token(token, payer, sender_account, 6000)
+
+    # 6000 tokens over one minute, 100 tokens per second
+    start_ts = int(time.time())
+    vault = create_stream(payer, recipient.public_key, token, sender_account, 0, 6000, start_ts, start_ts + 60)
+
+    time.sleep(10)
+    withdraw(recipient, payer.public_key, 0, vault, recipient_account)
+    print(f"Recipient token balance: {token_balance(token, recipient_account)}")
+
+    time.sleep(10)
+    cancel(payer, recipient.public_key, 0, vault, sender_account, recipient_account)
+    print(f"Recipient token balance: {token_balance(token, recipient_account)}")
+    print(f"Sender token balance: {token_balance(token, sender_account)}")
+
+    # a second stream of ten seconds, pushed to the recipient by a keeper instead of withdrawn
+    keeper = setup_user_keypair()
+    mint_token(token, payer, sender_account, 1000)
+    start_ts = int(time.time())
+    vault = create_stream(payer, recipient.public_key, token, sender_account, 1, 1000, start_ts, start_ts + 10)
+    stream = get_stream_address(payer.public_key, recipient.public_key, 1)
+    fund_cranks(payer, stream, 1)
+
+    time.sleep(12)
+    before = client.get_balance(keeper.public_key)["result"]["value"]
+    crank(keeper, stream, vault, recipient_account)
+    print(f"Recipient token balance: {token_balance(token, recipient_account)}")
+    print(f"The keeper earned {client.get_balance(keeper.public_key)['result']['value'] - before} lamports after the fee")
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum StreamError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Stream Address")]
+    InvalidStreamAddress,
+    #[error("Invalid Vault")]
+    InvalidVault,
+    #[error("Invalid Schedule")]
+    InvalidSchedule,
+    #[error("Insufficient Streamed Amount")]
+    InsufficientStreamedAmount,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+    #[error("Nothing To Crank")]
+    NothingToCrank,
+}
+
+impl From<StreamError> for ProgramError {
+    fn from(e: StreamError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
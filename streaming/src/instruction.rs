@@ -0,0 +1,100 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::StreamError::InvalidInstruction;
+
+pub enum StreamInstruction {
+
+    // Locks the deposit and starts streaming it to the recipient. The stream lives in a PDA derived from
+    // ["stream", sender, recipient, id], the vault has to be created prior to this instruction and owned by the stream PDA.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The sender
+    // 1. `[]` The recipient
+    // 2. `[writable]` The stream PDA
+    // 3. `[writable]` The sender's token account to take the deposit from
+    // 4. `[writable]` The vault, a token account owned by the stream PDA
+    // 5. `[]` The system program
+    // 6. `[]` The token program
+    CreateStream {
+        id: u64,
+        deposit: u64,
+        start_ts: i64,
+        end_ts: i64,
+    },
+
+    // Withdraws streamed tokens, an amount of 0 withdraws everything streamed so far.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The recipient
+    // 1. `[writable]` The stream PDA
+    // 2. `[writable]` The vault
+    // 3. `[writable]` The recipient's token account
+    // 4. `[]` The token program
+    Withdraw {
+        amount: u64,
+    },
+
+    // Stops the stream. The recipient receives what has been streamed but not withdrawn, the sender gets back the rest.
+    // The vault and the stream account are closed, their rent goes back to the sender.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The sender
+    // 1. `[writable]` The stream PDA
+    // 2. `[writable]` The vault
+    // 3. `[writable]` The sender's token account
+    // 4. `[writable]` The recipient's token account
+    // 5. `[]` The token program
+    Cancel,
+
+    // Pushes everything streamed so far to the recipient. Anybody may call it, e.g. a keeper bot or a Clockwork
+    // thread, and gets CRANK_REWARD lamports out of the stream account for it, as long as the sender funded it.
+    // It only succeeds once at least CRANK_INTERVAL seconds worth of tokens have been streamed since the last
+    // withdrawal, or when the stream is over.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The cranker, receives the reward
+    // 1. `[writable]` The stream PDA
+    // 2. `[writable]` The vault
+    // 3. `[writable]` The recipient's token account
+    // 4. `[]` The token program
+    Crank,
+}
+
+impl StreamInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateStream {
+                id: Self::unpack_u64(rest, 0)?,
+                deposit: Self::unpack_u64(rest, 8)?,
+                start_ts: Self::unpack_u64(rest, 16)? as i64,
+                end_ts: Self::unpack_u64(rest, 24)? as i64,
+            },
+            1 => Self::Withdraw {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::Cancel,
+            3 => Self::Crank,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,381 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::Account as TokenAccount;
+
+use crate::{
+    instruction::StreamInstruction,
+    error::StreamError,
+    state::{Stream, CRANK_REWARD},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = StreamInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            StreamInstruction::CreateStream { id, deposit, start_ts, end_ts } => {
+                msg!("Instruction: CreateStream");
+                Self::process_create_stream(accounts, id, deposit, start_ts, end_ts, program_id)
+            },
+            StreamInstruction::Withdraw { amount } => {
+                msg!("Instruction: Withdraw");
+                Self::process_withdraw(accounts, amount, program_id)
+            },
+            StreamInstruction::Cancel => {
+                msg!("Instruction: Cancel");
+                Self::process_cancel(accounts, program_id)
+            },
+            StreamInstruction::Crank => {
+                msg!("Instruction: Crank");
+                Self::process_crank(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_stream(
+        accounts: &[AccountInfo],
+        id: u64,
+        deposit: u64,
+        start_ts: i64,
+        end_ts: i64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if end_ts <= start_ts || end_ts <= Clock::get()?.unix_timestamp {
+            return Err(StreamError::InvalidSchedule.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let sender = next_account_info(account_info_iter)?;
+
+        if !sender.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let recipient = next_account_info(account_info_iter)?;
+        let stream_account = next_account_info(account_info_iter)?;
+        let sender_token_account = next_account_info(account_info_iter)?;
+        let vault = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let id_bytes = id.to_le_bytes();
+        let (stream_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"stream", sender.key.as_ref(), recipient.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if stream_pda != *stream_account.key {
+            return Err(StreamError::InvalidStreamAddress.into());
+        }
+
+        let vault_info = TokenAccount::unpack(&vault.try_borrow_data()?)?;
+        if vault_info.owner != stream_pda {
+            return Err(StreamError::InvalidVault.into());
+        }
+
+        if !stream_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating stream account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                sender.key,
+                stream_account.key,
+                Rent::get()?.minimum_balance(Stream::LEN),
+                Stream::LEN as u64,
+                program_id,
+            ),
+            &[
+                sender.clone(),
+                stream_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"stream", sender.key.as_ref(), recipient.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        msg!("Calling the token program to transfer the deposit into the vault...");
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                sender_token_account.key,
+                vault.key,
+                sender.key,
+                &[sender.key],
+                deposit,
+            )?,
+            &[
+                sender_token_account.clone(),
+                vault.clone(),
+                sender.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        let stream_info = Stream {
+            is_initialized: true,
+            sender: *sender.key,
+            recipient: *recipient.key,
+            id,
+            mint: vault_info.mint,
+            vault: *vault.key,
+            deposit,
+            start_ts,
+            end_ts,
+            withdrawn: 0,
+            bump_seed,
+        };
+        stream_info.serialize(&mut &mut stream_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_withdraw(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let recipient = next_account_info(account_info_iter)?;
+
+        if !recipient.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let stream_account = next_account_info(account_info_iter)?;
+        let mut stream_info = Self::unpack_stream(stream_account, program_id)?;
+        if stream_info.recipient != *recipient.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != stream_info.vault {
+            return Err(StreamError::InvalidVault.into());
+        }
+
+        let recipient_token_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let withdrawable = stream_info.withdrawable(Clock::get()?.unix_timestamp);
+        let amount = if amount == 0 { withdrawable } else { amount };
+        if amount == 0 || amount > withdrawable {
+            return Err(StreamError::InsufficientStreamedAmount.into());
+        }
+
+        Self::transfer_from_vault(&stream_info, stream_account, vault, recipient_token_account, token_program, amount)?;
+
+        stream_info.withdrawn += amount;
+        stream_info.serialize(&mut &mut stream_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_cancel(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let sender = next_account_info(account_info_iter)?;
+
+        if !sender.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let stream_account = next_account_info(account_info_iter)?;
+        let stream_info = Self::unpack_stream(stream_account, program_id)?;
+        if stream_info.sender != *sender.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != stream_info.vault {
+            return Err(StreamError::InvalidVault.into());
+        }
+
+        let sender_token_account = next_account_info(account_info_iter)?;
+
+        // the recipient doesn't sign the cancellation, so their share must end up in an account they own
+        let recipient_token_account = next_account_info(account_info_iter)?;
+        if TokenAccount::unpack(&recipient_token_account.try_borrow_data()?)?.owner != stream_info.recipient {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // whatever has been streamed until now belongs to the recipient, the rest has never been streamed
+        let now = Clock::get()?.unix_timestamp;
+        let recipient_amount = stream_info.withdrawable(now);
+        let sender_amount = stream_info.deposit - stream_info.streamed(now);
+        msg!("Splitting the stream: {} to the recipient, {} back to the sender", recipient_amount, sender_amount);
+
+        if recipient_amount > 0 {
+            Self::transfer_from_vault(&stream_info, stream_account, vault, recipient_token_account, token_program, recipient_amount)?;
+        }
+        if sender_amount > 0 {
+            Self::transfer_from_vault(&stream_info, stream_account, vault, sender_token_account, token_program, sender_amount)?;
+        }
+
+        msg!("Calling the token program to close the vault...");
+        invoke_signed(
+            &spl_token::instruction::close_account(
+                token_program.key,
+                vault.key,
+                sender.key,
+                stream_account.key,
+                &[stream_account.key],
+            )?,
+            &[
+                vault.clone(),
+                sender.clone(),
+                stream_account.clone(),
+                token_program.clone(),
+            ],
+            &[&[
+                b"stream",
+                stream_info.sender.as_ref(),
+                stream_info.recipient.as_ref(),
+                &stream_info.id.to_le_bytes(),
+                &[stream_info.bump_seed],
+            ]],
+        )?;
+
+        msg!("Closing the stream account...");
+        **sender.lamports.borrow_mut() = sender.lamports()
+            .checked_add(stream_account.lamports())
+            .ok_or(StreamError::AmountOverflow)?;
+        **stream_account.lamports.borrow_mut() = 0;
+        stream_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    // Withdraw on behalf of the recipient. The recipient doesn't sign, so the tokens must end up in an account they
+    // own of the mint of the stream. The reward is taken from the lamports of the stream account above its rent, the
+    // sender funds it with a plain transfer, and Cancel gives back what is left.
+    fn process_crank(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let cranker = next_account_info(account_info_iter)?;
+
+        if !cranker.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let stream_account = next_account_info(account_info_iter)?;
+        let mut stream_info = Self::unpack_stream(stream_account, program_id)?;
+
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != stream_info.vault {
+            return Err(StreamError::InvalidVault.into());
+        }
+
+        let recipient_token_account = next_account_info(account_info_iter)?;
+        let recipient_token_info = TokenAccount::unpack(&recipient_token_account.try_borrow_data()?)?;
+        if recipient_token_info.owner != stream_info.recipient || recipient_token_info.mint != stream_info.mint {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        if !stream_info.crankable(now) {
+            return Err(StreamError::NothingToCrank.into());
+        }
+
+        let amount = stream_info.withdrawable(now);
+        Self::transfer_from_vault(&stream_info, stream_account, vault, recipient_token_account, token_program, amount)?;
+
+        stream_info.withdrawn += amount;
+        stream_info.serialize(&mut &mut stream_account.data.borrow_mut()[..])?;
+
+        // the program owns the stream account, it can move its lamports directly
+        let rent = Rent::get()?.minimum_balance(stream_account.data_len());
+        let reward = CRANK_REWARD.min(stream_account.lamports().saturating_sub(rent));
+        if reward > 0 {
+            msg!("Paying the cranker {} lamports...", reward);
+            **stream_account.lamports.borrow_mut() -= reward;
+            **cranker.lamports.borrow_mut() = cranker.lamports()
+                .checked_add(reward)
+                .ok_or(StreamError::AmountOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    fn unpack_stream(stream_account: &AccountInfo, program_id: &Pubkey) -> Result<Stream, ProgramError> {
+        if stream_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let stream_info = Stream::try_from_slice(&stream_account.data.borrow())?;
+        if !stream_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(stream_info)
+    }
+
+    // The stream PDA owns the vault, so the program signs the token transfer with the stream's seeds
+    fn transfer_from_vault<'a>(
+        stream_info: &Stream,
+        stream_account: &AccountInfo<'a>,
+        vault: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        let transfer_ix = spl_token::instruction::transfer(
+            token_program.key,
+            vault.key,
+            destination.key,
+            stream_account.key,
+            &[stream_account.key],
+            amount,
+        )?;
+
+        msg!("Calling the token program to transfer {} tokens out of the vault...", amount);
+        invoke_signed(
+            &transfer_ix,
+            &[
+                vault.clone(),
+                destination.clone(),
+                stream_account.clone(),
+                token_program.clone(),
+            ],
+            &[&[
+                b"stream",
+                stream_info.sender.as_ref(),
+                stream_info.recipient.as_ref(),
+                &stream_info.id.to_le_bytes(),
+                &[stream_info.bump_seed],
+            ]],
+        )
+    }
+}
@@ -0,0 +1,64 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// lamports a crank pays the cranker, a bit more than the fee of its transaction
+pub const CRANK_REWARD: u64 = 10_000;
+// the stream has to run this many seconds between two cranks, otherwise a cranker could collect the reward every slot
+pub const CRANK_INTERVAL: i64 = 3600;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Stream {
+    pub is_initialized: bool,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub id: u64,
+    pub mint: Pubkey,
+    // token account owned by the stream PDA, holding what hasn't been withdrawn yet
+    pub vault: Pubkey,
+    pub deposit: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub withdrawn: u64,
+    pub bump_seed: u8,
+}
+
+impl Stream {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1;
+
+    // The deposit flows linearly from start_ts to end_ts, nothing before the start, everything after the end.
+    pub fn streamed(&self, now: i64) -> u64 {
+        if now <= self.start_ts {
+            return 0;
+        }
+        if now >= self.end_ts {
+            return self.deposit;
+        }
+
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        // the product of a u64 and an i64 difference always fits into a u128
+        (self.deposit as u128 * elapsed / duration) as u64
+    }
+
+    // what the recipient can withdraw right now
+    pub fn withdrawable(&self, now: i64) -> u64 {
+        self.streamed(now) - self.withdrawn
+    }
+
+    // Whether a crank may push tokens now. The stream doesn't record when it was cranked last, but everything
+    // withdrawn so far has been streamed, so at least CRANK_INTERVAL seconds have passed since the last withdrawal
+    // once another interval worth of tokens is withdrawable.
+    pub fn crankable(&self, now: i64) -> bool {
+        let withdrawable = self.withdrawable(now);
+        if withdrawable == 0 {
+            return false;
+        }
+        if now >= self.end_ts {
+            return true;
+        }
+
+        let duration = (self.end_ts - self.start_ts) as u128;
+        let per_interval = (self.deposit as u128 * CRANK_INTERVAL as u128 / duration) as u64;
+        withdrawable >= per_interval.max(1)
+    }
+}
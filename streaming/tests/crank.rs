@@ -0,0 +1,279 @@
+// Runs Crank in a local bank with solana-program-test. The streaming program is called natively, the token program
+// is the one solana-program-test ships. The clock is set by the tests, a stream doesn't move otherwise.
+use solana_program::{program_pack::Pack, sysvar::clock::Clock};
+use solana_program_test::{processor, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+use spl_token::state::{Account as TokenAccount, Mint};
+use streaming::{
+    error::StreamError,
+    processor::Processor,
+    state::{Stream, CRANK_INTERVAL, CRANK_REWARD},
+};
+
+// one token a second, so an interval worth of tokens is CRANK_INTERVAL tokens
+const DEPOSIT: u64 = 2 * CRANK_INTERVAL as u64;
+
+struct Test {
+    context: ProgramTestContext,
+    program_id: Pubkey,
+    mint: Pubkey,
+    recipient: Pubkey,
+    stream: Pubkey,
+    vault: Pubkey,
+    recipient_token_account: Pubkey,
+    start_ts: i64,
+    // signs the cranks and gets the rewards, the payer pays the fees so its balance only changes by the reward
+    cranker: Keypair,
+}
+
+impl Test {
+    // A stream of DEPOSIT tokens from the payer to a new recipient, starting now and running DEPOSIT seconds
+    async fn start() -> Self {
+        let program_id = Pubkey::new_unique();
+        let program_test = ProgramTest::new("streaming", program_id, processor!(Processor::process));
+        let mut context = program_test.start_with_context().await;
+        let start_ts = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+
+        let mut test = Self {
+            context,
+            program_id,
+            mint: Pubkey::default(),
+            recipient: Pubkey::new_unique(),
+            stream: Pubkey::default(),
+            vault: Pubkey::default(),
+            recipient_token_account: Pubkey::default(),
+            start_ts,
+            cranker: Keypair::new(),
+        };
+        let (sender, recipient) = (test.context.payer.pubkey(), test.recipient);
+        let mint = test.create_mint().await;
+        let sender_token_account = test.create_token_account(&mint, &sender).await;
+        let mint_to =
+            spl_token::instruction::mint_to(&spl_token::id(), &mint, &sender_token_account, &sender, &[], DEPOSIT);
+        test.send(mint_to.unwrap(), &[]).await.unwrap();
+
+        let id: u64 = 0;
+        let seeds: &[&[u8]] = &[b"stream", sender.as_ref(), recipient.as_ref(), &id.to_le_bytes()];
+        let stream = Pubkey::find_program_address(seeds, &program_id).0;
+        test.mint = mint;
+        test.stream = stream;
+        test.vault = test.create_token_account(&mint, &stream).await;
+        test.recipient_token_account = test.create_token_account(&mint, &recipient).await;
+
+        let mut data = vec![0];
+        for value in [id, DEPOSIT, start_ts as u64, (start_ts + DEPOSIT as i64) as u64] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        let create_stream = Instruction::new_with_bytes(
+            program_id,
+            &data,
+            vec![
+                AccountMeta::new(sender, true),
+                AccountMeta::new_readonly(recipient, false),
+                AccountMeta::new(stream, false),
+                AccountMeta::new(sender_token_account, false),
+                AccountMeta::new(test.vault, false),
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
+        );
+        test.send(create_stream, &[]).await.unwrap();
+
+        // the cranker has to stay rent exempt itself after getting a reward
+        let fund_cranker = system_instruction::transfer(&sender, &test.cranker.pubkey(), LAMPORTS_PER_SOL);
+        test.send(fund_cranker, &[]).await.unwrap();
+        test
+    }
+
+    // The payer pays the fees, the signers sign besides it
+    async fn send(&mut self, instruction: Instruction, signers: &[&Keypair]) -> Result<(), TransactionError> {
+        let context = &mut self.context;
+        context.last_blockhash = context.banks_client.get_new_latest_blockhash(&context.last_blockhash).await.unwrap();
+        let mut keypairs = vec![&context.payer];
+        keypairs.extend_from_slice(signers);
+        let payer = context.payer.pubkey();
+        let transaction =
+            Transaction::new_signed_with_payer(&[instruction], Some(&payer), &keypairs, context.last_blockhash);
+        context.banks_client.process_transaction(transaction).await.map_err(|e| e.unwrap())
+    }
+
+    async fn create_mint(&mut self) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.context.payer.pubkey();
+        let rent = self.context.banks_client.get_rent().await.unwrap().minimum_balance(Mint::LEN);
+        let create =
+            system_instruction::create_account(&payer, &mint.pubkey(), rent, Mint::LEN as u64, &spl_token::id());
+        self.send(create, &[&mint]).await.unwrap();
+        let initialize = spl_token::instruction::initialize_mint(&spl_token::id(), &mint.pubkey(), &payer, None, 0);
+        self.send(initialize.unwrap(), &[]).await.unwrap();
+        mint.pubkey()
+    }
+
+    async fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+        let account = Keypair::new();
+        let payer = self.context.payer.pubkey();
+        let rent = self.context.banks_client.get_rent().await.unwrap().minimum_balance(TokenAccount::LEN);
+        let space = TokenAccount::LEN as u64;
+        let create = system_instruction::create_account(&payer, &account.pubkey(), rent, space, &spl_token::id());
+        self.send(create, &[&account]).await.unwrap();
+        let initialize = spl_token::instruction::initialize_account(&spl_token::id(), &account.pubkey(), mint, owner);
+        self.send(initialize.unwrap(), &[]).await.unwrap();
+        account.pubkey()
+    }
+
+    // Sets the clock to the given seconds after the start of the stream
+    async fn warp(&mut self, seconds: i64) {
+        let mut clock = self.context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp = self.start_ts + seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    // Funds the reward with a plain transfer to the stream account, like the sender would
+    async fn fund_reward(&mut self, lamports: u64) {
+        let payer = self.context.payer.pubkey();
+        self.send(system_instruction::transfer(&payer, &self.stream, lamports), &[]).await.unwrap();
+    }
+
+    async fn crank(&mut self, destination: &Pubkey) -> Result<(), TransactionError> {
+        let instruction = Instruction::new_with_bytes(
+            self.program_id,
+            &[3],
+            vec![
+                AccountMeta::new(self.cranker.pubkey(), true),
+                AccountMeta::new(self.stream, false),
+                AccountMeta::new(self.vault, false),
+                AccountMeta::new(*destination, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
+        );
+        let cranker = self.cranker.insecure_clone();
+        self.send(instruction, &[&cranker]).await
+    }
+
+    async fn tokens(&mut self, token_account: &Pubkey) -> u64 {
+        let account = self.context.banks_client.get_account(*token_account).await.unwrap().unwrap();
+        TokenAccount::unpack(&account.data).unwrap().amount
+    }
+
+    async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.context.banks_client.get_balance(*address).await.unwrap()
+    }
+}
+
+fn stream_error(error: StreamError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+}
+
+#[tokio::test]
+async fn crank_before_an_interval_is_refused() {
+    let mut test = Test::start().await;
+    test.warp(CRANK_INTERVAL - 1).await;
+
+    let destination = test.recipient_token_account;
+    assert_eq!(test.crank(&destination).await.unwrap_err(), stream_error(StreamError::NothingToCrank));
+    assert_eq!(test.tokens(&destination).await, 0);
+}
+
+#[tokio::test]
+async fn crank_pushes_the_streamed_tokens_to_the_recipient() {
+    let mut test = Test::start().await;
+    test.warp(CRANK_INTERVAL).await;
+
+    let destination = test.recipient_token_account;
+    test.crank(&destination).await.unwrap();
+    assert_eq!(test.tokens(&destination).await, CRANK_INTERVAL as u64);
+
+    // the next crank has to wait for another interval
+    assert_eq!(test.crank(&destination).await.unwrap_err(), stream_error(StreamError::NothingToCrank));
+}
+
+#[tokio::test]
+async fn token_account_of_somebody_else_is_refused() {
+    let mut test = Test::start().await;
+    test.warp(CRANK_INTERVAL).await;
+
+    let mint = test.mint;
+    let other = test.create_token_account(&mint, &Pubkey::new_unique()).await;
+    let error = test.crank(&other).await.unwrap_err();
+    assert_eq!(error, TransactionError::InstructionError(0, InstructionError::InvalidAccountData));
+    assert_eq!(test.tokens(&other).await, 0);
+}
+
+#[tokio::test]
+async fn token_account_of_another_mint_is_refused() {
+    let mut test = Test::start().await;
+    test.warp(CRANK_INTERVAL).await;
+
+    let other_mint = test.create_mint().await;
+    let recipient = test.recipient;
+    let other = test.create_token_account(&other_mint, &recipient).await;
+    let error = test.crank(&other).await.unwrap_err();
+    assert_eq!(error, TransactionError::InstructionError(0, InstructionError::InvalidAccountData));
+}
+
+#[tokio::test]
+async fn funded_crank_pays_the_reward() {
+    let mut test = Test::start().await;
+    test.fund_reward(10 * CRANK_REWARD).await;
+    test.warp(CRANK_INTERVAL).await;
+
+    let (cranker, stream) = (test.cranker.pubkey(), test.stream);
+    let (cranker_before, stream_before) = (test.lamports(&cranker).await, test.lamports(&stream).await);
+    let destination = test.recipient_token_account;
+    test.crank(&destination).await.unwrap();
+
+    assert_eq!(test.lamports(&cranker).await, cranker_before + CRANK_REWARD);
+    assert_eq!(test.lamports(&stream).await, stream_before - CRANK_REWARD);
+}
+
+#[tokio::test]
+async fn reward_is_capped_at_the_lamports_above_rent() {
+    let mut test = Test::start().await;
+    test.fund_reward(CRANK_REWARD / 4).await;
+    test.warp(CRANK_INTERVAL).await;
+
+    let cranker = test.cranker.pubkey();
+    let cranker_before = test.lamports(&cranker).await;
+    let destination = test.recipient_token_account;
+    test.crank(&destination).await.unwrap();
+
+    assert_eq!(test.lamports(&cranker).await, cranker_before + CRANK_REWARD / 4);
+    // the stream account keeps its rent
+    let rent = test.context.banks_client.get_rent().await.unwrap().minimum_balance(Stream::LEN);
+    let stream = test.stream;
+    assert_eq!(test.lamports(&stream).await, rent);
+
+    // without lamports above the rent the crank still works, it just pays nothing
+    test.warp(2 * CRANK_INTERVAL).await;
+    test.crank(&destination).await.unwrap();
+    assert_eq!(test.lamports(&cranker).await, cranker_before + CRANK_REWARD / 4);
+    assert_eq!(test.lamports(&stream).await, rent);
+}
+
+#[tokio::test]
+async fn ended_stream_is_cranked_whatever_is_left() {
+    let mut test = Test::start().await;
+    let destination = test.recipient_token_account;
+    test.warp(CRANK_INTERVAL * 3 / 2).await;
+    test.crank(&destination).await.unwrap();
+
+    // half an interval worth of tokens is left, only enough once the stream is over
+    test.warp(DEPOSIT as i64 - 1).await;
+    assert_eq!(test.crank(&destination).await.unwrap_err(), stream_error(StreamError::NothingToCrank));
+    test.warp(DEPOSIT as i64).await;
+    test.crank(&destination).await.unwrap();
+    assert_eq!(test.tokens(&destination).await, DEPOSIT);
+    let vault = test.vault;
+    assert_eq!(test.tokens(&vault).await, 0);
+
+    // nothing is left to crank
+    test.warp(DEPOSIT as i64 + CRANK_INTERVAL).await;
+    assert_eq!(test.crank(&destination).await.unwrap_err(), stream_error(StreamError::NothingToCrank));
+}
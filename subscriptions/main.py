@@ -0,0 +1,238 @@
+import yaml
+import sys
+import json
+import time
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'subscriptions.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'subscriptions-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_subscription_address(subscriber: PublicKey, merchant: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("subscription", encoding="utf8"), bytes(subscriber), bytes(merchant)],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def subscribe(subscriber: Keypair, merchant: PublicKey, amount: int, period: int, deposit: int):
+    print(f"\nSubscribing to {merchant}: {amount} lamports every {period} seconds, deposit {deposit} lamports")
+    send(
+        subscriber,
+        (0).to_bytes(1, byteorder='little')
+        + amount.to_bytes(8, byteorder='little')
+        + period.to_bytes(8, byteorder='little', signed=True)
+        + deposit.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=subscriber.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=merchant, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_subscription_address(subscriber.public_key, merchant), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def top_up(subscriber: Keypair, merchant: PublicKey, amount: int):
+    print(f"\nTopping up {amount} lamports")
+    send(
+        subscriber,
+        (1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=subscriber.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_subscription_address(subscriber.public_key, merchant), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def collect(merchant: Keypair, subscriber: PublicKey):
+    print("\nMerchant collects the due periods")
+    send(
+        merchant,
+        (2).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=merchant.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_subscription_address(subscriber, merchant.public_key), is_signer=False, is_writable=True),
+        ],
+    )
+
+def cancel(subscriber: Keypair, merchant: PublicKey):
+    print("\nCancelling the subscription")
+    send(
+        subscriber,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=subscriber.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=get_subscription_address(subscriber.public_key, merchant), is_signer=False, is_writable=True),
+        ],
+    )
+
+def refund_remaining(subscriber: Keypair, merchant: PublicKey):
+    print("\nRefunding the remaining deposit")
+    send(
+        subscriber,
+        (4).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=subscriber.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=get_subscription_address(subscriber.public_key, merchant), is_signer=False, is_writable=True),
+        ],
+    )
+
+def balance(account: PublicKey) -> int:
+    return client.get_balance(account)['result']['value']
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    merchant = setup_user_keypair()
+
+    amount = LAMPORTS_PER_SOL // 100
+    subscribe(payer, merchant.public_key, amount, 10, amount * 5)
+
+    # the first period is due right away
+    collect(merchant, payer.public_key)
+    print(f"Merchant balance: {balance(merchant.public_key)} lamports")
+    try:
+        collect(merchant, payer.public_key)
+    except Exception as e:
+        print(f"Collecting twice in the same period fails as expected: {e}")
+
+    print("Waiting for the next period...")
+    time.sleep(12)
+    top_up(payer, merchant.public_key, amount)
+    collect(merchant, payer.public_key)
+    print(f"Merchant balance: {balance(merchant.public_key)} lamports")
+
+    cancel(payer, merchant.public_key)
+    subscription = get_subscription_address(payer.public_key, merchant.public_key)
+    print(f"Subscription balance before the refund: {balance(subscription)} lamports")
+    refund_remaining(payer, merchant.public_key)
+    print(f"Subscription account after the refund: {client.get_account_info(subscription)['result']['value']}")
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum SubscriptionError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Subscription Address")]
+    InvalidSubscriptionAddress,
+    #[error("Invalid Period")]
+    InvalidPeriod,
+    #[error("Nothing To Collect")]
+    NothingToCollect,
+    #[error("Already Cancelled")]
+    AlreadyCancelled,
+    #[error("Not Cancelled")]
+    NotCancelled,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<SubscriptionError> for ProgramError {
+    fn from(e: SubscriptionError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,93 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::SubscriptionError::InvalidInstruction;
+
+pub enum SubscriptionInstruction {
+
+    // Subscribes to a merchant. The subscription lives in a PDA derived from ["subscription", subscriber, merchant],
+    // the deposit is kept in the PDA on top of its rent. The first period starts right away.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The subscriber
+    // 1. `[]` The merchant
+    // 2. `[writable]` The subscription PDA
+    // 3. `[]` The system program
+    Subscribe {
+        amount: u64,
+        period: i64,
+        deposit: u64,
+    },
+
+    // Adds more lamports to the subscription.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The subscriber
+    // 1. `[writable]` The subscription PDA
+    // 2. `[]` The system program
+    TopUp {
+        amount: u64,
+    },
+
+    // Pulls the amount of every started period which hasn't been collected yet, as far as the deposit covers it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The merchant
+    // 1. `[writable]` The subscription PDA
+    Collect,
+
+    // Ends the subscription, periods which already started can still be collected.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The subscriber
+    // 1. `[writable]` The subscription PDA
+    Cancel,
+
+    // Returns everything the merchant can't claim anymore to the subscriber. Once nothing is owed to the
+    // merchant, the subscription account is closed as well.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The subscriber
+    // 1. `[writable]` The subscription PDA
+    RefundRemaining,
+}
+
+impl SubscriptionInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::Subscribe {
+                amount: Self::unpack_u64(rest, 0)?,
+                period: Self::unpack_u64(rest, 8)? as i64,
+                deposit: Self::unpack_u64(rest, 16)?,
+            },
+            1 => Self::TopUp {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            2 => Self::Collect,
+            3 => Self::Cancel,
+            4 => Self::RefundRemaining,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
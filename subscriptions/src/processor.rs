@@ -0,0 +1,284 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{instruction::SubscriptionInstruction, error::SubscriptionError, state::Subscription};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = SubscriptionInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            SubscriptionInstruction::Subscribe { amount, period, deposit } => {
+                msg!("Instruction: Subscribe");
+                Self::process_subscribe(accounts, amount, period, deposit, program_id)
+            },
+            SubscriptionInstruction::TopUp { amount } => {
+                msg!("Instruction: TopUp");
+                Self::process_top_up(accounts, amount, program_id)
+            },
+            SubscriptionInstruction::Collect => {
+                msg!("Instruction: Collect");
+                Self::process_collect(accounts, program_id)
+            },
+            SubscriptionInstruction::Cancel => {
+                msg!("Instruction: Cancel");
+                Self::process_cancel(accounts, program_id)
+            },
+            SubscriptionInstruction::RefundRemaining => {
+                msg!("Instruction: RefundRemaining");
+                Self::process_refund_remaining(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_subscribe(
+        accounts: &[AccountInfo],
+        amount: u64,
+        period: i64,
+        deposit: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if period <= 0 || amount == 0 {
+            return Err(SubscriptionError::InvalidPeriod.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let subscriber = next_account_info(account_info_iter)?;
+
+        if !subscriber.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let merchant = next_account_info(account_info_iter)?;
+        let subscription_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (subscription_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"subscription", subscriber.key.as_ref(), merchant.key.as_ref()],
+            program_id,
+        );
+        if subscription_pda != *subscription_account.key {
+            return Err(SubscriptionError::InvalidSubscriptionAddress.into());
+        }
+
+        if !subscription_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the deposit is simply added on top of the rent when the account is created
+        let lamports = Rent::get()?.minimum_balance(Subscription::LEN)
+            .checked_add(deposit)
+            .ok_or(SubscriptionError::AmountOverflow)?;
+
+        msg!("Creating subscription account holding {} lamports...", deposit);
+        invoke_signed(
+            &system_instruction::create_account(
+                subscriber.key,
+                subscription_account.key,
+                lamports,
+                Subscription::LEN as u64,
+                program_id,
+            ),
+            &[
+                subscriber.clone(),
+                subscription_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"subscription", subscriber.key.as_ref(), merchant.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        let subscription_info = Subscription {
+            is_initialized: true,
+            subscriber: *subscriber.key,
+            merchant: *merchant.key,
+            amount,
+            period,
+            start_ts: Clock::get()?.unix_timestamp,
+            periods_collected: 0,
+            cancelled_at: None,
+            bump_seed,
+        };
+        subscription_info.serialize(&mut &mut subscription_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_top_up(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let subscriber = next_account_info(account_info_iter)?;
+
+        if !subscriber.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let subscription_account = next_account_info(account_info_iter)?;
+        let subscription_info = Self::unpack_subscription(subscription_account, program_id)?;
+        if subscription_info.subscriber != *subscriber.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if subscription_info.cancelled_at.is_some() {
+            return Err(SubscriptionError::AlreadyCancelled.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to add {} lamports...", amount);
+        invoke(
+            &system_instruction::transfer(subscriber.key, subscription_account.key, amount),
+            &[
+                subscriber.clone(),
+                subscription_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn process_collect(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let merchant = next_account_info(account_info_iter)?;
+
+        if !merchant.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let subscription_account = next_account_info(account_info_iter)?;
+        let mut subscription_info = Self::unpack_subscription(subscription_account, program_id)?;
+        if subscription_info.merchant != *merchant.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // only whole periods are paid, a period the deposit can't cover stays due until the subscriber tops up
+        let periods_due = subscription_info.periods_due(Clock::get()?.unix_timestamp);
+        let periods_covered = Self::funds(subscription_account)? / subscription_info.amount;
+        let periods = periods_due.min(periods_covered);
+        if periods == 0 {
+            return Err(SubscriptionError::NothingToCollect.into());
+        }
+
+        let payment = periods
+            .checked_mul(subscription_info.amount)
+            .ok_or(SubscriptionError::AmountOverflow)?;
+
+        msg!("Collecting {} periods, {} lamports...", periods, payment);
+        **subscription_account.lamports.borrow_mut() -= payment;
+        **merchant.lamports.borrow_mut() = merchant.lamports()
+            .checked_add(payment)
+            .ok_or(SubscriptionError::AmountOverflow)?;
+
+        subscription_info.periods_collected += periods;
+        subscription_info.serialize(&mut &mut subscription_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_cancel(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let subscriber = next_account_info(account_info_iter)?;
+
+        if !subscriber.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let subscription_account = next_account_info(account_info_iter)?;
+        let mut subscription_info = Self::unpack_subscription(subscription_account, program_id)?;
+        if subscription_info.subscriber != *subscriber.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if subscription_info.cancelled_at.is_some() {
+            return Err(SubscriptionError::AlreadyCancelled.into());
+        }
+
+        subscription_info.cancelled_at = Some(Clock::get()?.unix_timestamp);
+        subscription_info.serialize(&mut &mut subscription_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_refund_remaining(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let subscriber = next_account_info(account_info_iter)?;
+
+        if !subscriber.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let subscription_account = next_account_info(account_info_iter)?;
+        let subscription_info = Self::unpack_subscription(subscription_account, program_id)?;
+        if subscription_info.subscriber != *subscriber.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let cancelled_at = subscription_info.cancelled_at.ok_or(SubscriptionError::NotCancelled)?;
+
+        // the periods started before the cancellation still belong to the merchant
+        let funds = Self::funds(subscription_account)?;
+        let owed = subscription_info.periods_due(cancelled_at)
+            .saturating_mul(subscription_info.amount)
+            .min(funds);
+
+        if owed == 0 {
+            msg!("Closing the subscription account...");
+            **subscriber.lamports.borrow_mut() = subscriber.lamports()
+                .checked_add(subscription_account.lamports())
+                .ok_or(SubscriptionError::AmountOverflow)?;
+            **subscription_account.lamports.borrow_mut() = 0;
+            subscription_account.data.borrow_mut().fill(0);
+        } else {
+            let refund = funds - owed;
+            msg!("Refunding {} lamports, {} are left for the merchant...", refund, owed);
+            **subscription_account.lamports.borrow_mut() -= refund;
+            **subscriber.lamports.borrow_mut() = subscriber.lamports()
+                .checked_add(refund)
+                .ok_or(SubscriptionError::AmountOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    fn unpack_subscription(subscription_account: &AccountInfo, program_id: &Pubkey) -> Result<Subscription, ProgramError> {
+        if subscription_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // cancelled_at is None while the subscription runs, 8 bytes of the account stay unused
+        let subscription_info = Subscription::deserialize(&mut &subscription_account.data.borrow()[..])?;
+        if !subscription_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(subscription_info)
+    }
+
+    // lamports held by the subscription on top of its rent
+    fn funds(subscription_account: &AccountInfo) -> Result<u64, ProgramError> {
+        let rent_lamports = Rent::get()?.minimum_balance(subscription_account.data_len());
+        Ok(subscription_account.lamports().saturating_sub(rent_lamports))
+    }
+}
@@ -0,0 +1,39 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Subscription {
+    pub is_initialized: bool,
+    pub subscriber: Pubkey,
+    pub merchant: Pubkey,
+    // lamports the merchant may pull per period
+    pub amount: u64,
+    // length of a period in seconds
+    pub period: i64,
+    // the first period starts here
+    pub start_ts: i64,
+    pub periods_collected: u64,
+    // no period starting after the cancellation is paid
+    pub cancelled_at: Option<i64>,
+    pub bump_seed: u8,
+}
+
+impl Subscription {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 + 8 + (1 + 8) + 1;
+
+    // A period is paid up front, so it counts as soon as it has started.
+    pub fn periods_started(&self, now: i64) -> u64 {
+        let end = match self.cancelled_at {
+            Some(cancelled_at) => cancelled_at.min(now),
+            None => now,
+        };
+        if end < self.start_ts {
+            return 0;
+        }
+        ((end - self.start_ts) / self.period) as u64 + 1
+    }
+
+    pub fn periods_due(&self, now: i64) -> u64 {
+        self.periods_started(now).saturating_sub(self.periods_collected)
+    }
+}
@@ -0,0 +1,275 @@
+import yaml
+import sys
+import json
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'swap_otc.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'swap_otc-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_program(payer: Keypair, decimals: int) -> Token:
+    token = Token.create_mint(
+        conn=client,
+        payer=payer,
+        mint_authority=payer.public_key,
+        decimals=decimals,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    print(f"Address of token program: {token.pubkey}")
+    return token
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def mint_token(token: Token, minter: Keypair, account: PublicKey, amount: int):
+    print(f"Minting {amount} tokens to {account}")
+    token.mint_to(
+        dest=account,
+        mint_authority=minter,
+        amount=amount,
+        opts=TxOpts(skip_confirmation=False)
+    )
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+
+def get_offer_address(maker: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("offer", encoding="utf8"), bytes(maker), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def create_offer(maker: Keypair, id: int, token_a: Token, maker_a: PublicKey, maker_b: PublicKey, amount_a: int, amount_b: int, taker: PublicKey = None) -> tuple:
+    offer = get_offer_address(maker.public_key, id)
+    # the vault belongs to the offer PDA, which can be derived before the offer exists
+    vault = create_token_account(token_a, offer)
+    print(f"\nOffering {amount_a} of token A for {amount_b} of token B in {offer}" + (f", only {taker} may take it" if taker else ""))
+    data = (0).to_bytes(1, byteorder='little') + id.to_bytes(8, byteorder='little') + amount_a.to_bytes(8, byteorder='little') + amount_b.to_bytes(8, byteorder='little')
+    if taker:
+        data += bytes(taker)
+    send(
+        maker,
+        data,
+        [
+            AccountMeta(pubkey=maker.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=offer, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=maker_a, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=maker_b, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return offer, vault
+
+def take_offer(taker: Keypair, maker: PublicKey, offer: PublicKey, vault: PublicKey, taker_b: PublicKey, maker_b: PublicKey, taker_a: PublicKey, amount_a: int, amount_b: int):
+    print(f"\n{taker.public_key} takes offer {offer}")
+    send(
+        taker,
+        (1).to_bytes(1, byteorder='little') + amount_a.to_bytes(8, byteorder='little') + amount_b.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=taker.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=maker, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=offer, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=taker_b, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=maker_b, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=taker_a, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def cancel_offer(maker: Keypair, offer: PublicKey, vault: PublicKey, maker_a: PublicKey):
+    print(f"\nCancelling offer {offer}")
+    send(
+        maker,
+        (2).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=maker.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=offer, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=maker_a, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def print_balances(token_a: Token, token_b: Token, name: str, account_a: PublicKey, account_b: PublicKey):
+    print(f"{name}: {token_balance(token_a, account_a)} of token A, {token_balance(token_b, account_b)} of token B")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    taker = setup_user_keypair()
+    stranger = setup_user_keypair()
+
+    token_a = create_token_program(payer, 0)
+    token_b = create_token_program(payer, 0)
+    maker_a = create_token_account(token_a, payer.public_key)
+    maker_b = create_token_account(token_b, payer.public_key)
+    taker_a = create_token_account(token_a, taker.public_key)
+    taker_b = create_token_account(token_b, taker.public_key)
+    stranger_a = create_token_account(token_a, stranger.public_key)
+    stranger_b = create_token_account(token_b, stranger.public_key)
+    mint_token(token_a, payer, maker_a, 1000)
+    mint_token(token_b, payer, taker_b, 500)
+    mint_token(token_b, payer, stranger_b, 500)
+
+    # an offer only the taker may complete
+    offer, vault = create_offer(payer, 0, token_a, maker_a, maker_b, 100, 250, taker.public_key)
+    print_balances(token_a, token_b, "Maker", maker_a, maker_b)
+
+    try:
+        take_offer(stranger, payer.public_key, offer, vault, stranger_b, maker_b, stranger_a, 100, 250)
+    except Exception as e:
+        print(f"Taking an offer meant for somebody else fails as expected: {e}")
+
+    try:
+        take_offer(taker, payer.public_key, offer, vault, taker_b, maker_b, taker_a, 100, 200)
+    except Exception as e:
+        print(f"Taking an offer with other terms fails as expected: {e}")
+
+    take_offer(taker, payer.public_key, offer, vault, taker_b, maker_b, taker_a, 100, 250)
+    print_balances(token_a, token_b, "Maker", maker_a, maker_b)
+    print_balances(token_a, token_b, "Taker", taker_a, taker_b)
+
+    # an open offer, cancelled before anybody takes it
+    offer, vault = create_offer(payer, 1, token_a, maker_a, maker_b, 300, 50)
+    print_balances(token_a, token_b, "Maker", maker_a, maker_b)
+    cancel_offer(payer, offer, vault, maker_a)
+    print_balances(token_a, token_b, "Maker", maker_a, maker_b)
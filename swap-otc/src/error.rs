@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum SwapError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Offer Address")]
+    InvalidOfferAddress,
+    #[error("Invalid Vault")]
+    InvalidVault,
+    #[error("Invalid Receive Account")]
+    InvalidReceiveAccount,
+    #[error("Invalid Amount")]
+    InvalidAmount,
+    #[error("Not The Maker")]
+    NotTheMaker,
+    #[error("Not The Taker")]
+    NotTheTaker,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<SwapError> for ProgramError {
+    fn from(e: SwapError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
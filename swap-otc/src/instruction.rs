@@ -0,0 +1,94 @@
+use std::convert::TryInto;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::error::SwapError::InvalidInstruction;
+
+pub enum SwapInstruction {
+
+    // Creates an offer in a PDA derived from ["offer", maker, id] and escrows amount_a of token A in the vault.
+    // The vault has to be created prior to this instruction and owned by the offer PDA. Optionally followed by
+    // the pubkey of the only taker allowed to complete the swap.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The maker
+    // 1. `[writable]` The offer PDA
+    // 2. `[writable]` The maker's token A account to take the tokens from
+    // 3. `[writable]` The vault, a token A account owned by the offer PDA
+    // 4. `[]` The maker's token B account to be paid into
+    // 5. `[]` The system program
+    // 6. `[]` The token program
+    CreateOffer {
+        id: u64,
+        amount_a: u64,
+        amount_b: u64,
+        taker: Option<Pubkey>,
+    },
+
+    // Completes the swap: the taker pays amount_b of token B to the maker and receives the escrowed token A.
+    // The vault and the offer are closed, their rent goes back to the maker.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The taker
+    // 1. `[writable]` The maker
+    // 2. `[writable]` The offer PDA
+    // 3. `[writable]` The vault
+    // 4. `[writable]` The taker's token B account to pay from
+    // 5. `[writable]` The maker's token B account
+    // 6. `[writable]` The taker's token A account to receive the escrowed tokens
+    // 7. `[]` The token program
+    TakeOffer {
+        amount_a: u64,
+        amount_b: u64,
+    },
+
+    // Returns the escrowed tokens to the maker and closes the offer.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The maker
+    // 1. `[writable]` The offer PDA
+    // 2. `[writable]` The vault
+    // 3. `[writable]` The maker's token A account to return the tokens to
+    // 4. `[]` The token program
+    CancelOffer,
+}
+
+impl SwapInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateOffer {
+                id: Self::unpack_u64(rest, 0)?,
+                amount_a: Self::unpack_u64(rest, 8)?,
+                amount_b: Self::unpack_u64(rest, 16)?,
+                taker: match rest.get(24..) {
+                    Some([]) => None,
+                    Some(key) => Some(Pubkey::new_from_array(key.try_into().map_err(|_| InvalidInstruction)?)),
+                    None => return Err(InvalidInstruction.into()),
+                },
+            },
+            // the taker repeats the amounts they agreed to, so the maker can't cancel and recreate the offer with other terms in front of them
+            1 => Self::TakeOffer {
+                amount_a: Self::unpack_u64(rest, 0)?,
+                amount_b: Self::unpack_u64(rest, 8)?,
+            },
+            2 => Self::CancelOffer,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
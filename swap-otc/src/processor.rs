@@ -0,0 +1,334 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::Account as TokenAccount;
+
+use crate::{instruction::SwapInstruction, error::SwapError, state::Offer};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = SwapInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            SwapInstruction::CreateOffer { id, amount_a, amount_b, taker } => {
+                msg!("Instruction: CreateOffer");
+                Self::process_create_offer(accounts, id, amount_a, amount_b, taker, program_id)
+            },
+            SwapInstruction::TakeOffer { amount_a, amount_b } => {
+                msg!("Instruction: TakeOffer");
+                Self::process_take_offer(accounts, amount_a, amount_b, program_id)
+            },
+            SwapInstruction::CancelOffer => {
+                msg!("Instruction: CancelOffer");
+                Self::process_cancel_offer(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_offer(
+        accounts: &[AccountInfo],
+        id: u64,
+        amount_a: u64,
+        amount_b: u64,
+        taker: Option<Pubkey>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if amount_a == 0 || amount_b == 0 {
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let maker = next_account_info(account_info_iter)?;
+
+        if !maker.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let offer_account = next_account_info(account_info_iter)?;
+        let maker_token_a_account = next_account_info(account_info_iter)?;
+        let vault = next_account_info(account_info_iter)?;
+        let maker_receive_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let id_bytes = id.to_le_bytes();
+        let (offer_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"offer", maker.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if offer_pda != *offer_account.key {
+            return Err(SwapError::InvalidOfferAddress.into());
+        }
+
+        if !offer_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let vault_info = Self::unpack_token_account(vault)?;
+        if vault_info.owner != offer_pda {
+            return Err(SwapError::InvalidVault.into());
+        }
+
+        // the mint of token B is taken from the account the maker wants to be paid into
+        let receive_info = Self::unpack_token_account(maker_receive_account)?;
+        if receive_info.owner != *maker.key || receive_info.mint == vault_info.mint {
+            return Err(SwapError::InvalidReceiveAccount.into());
+        }
+
+        msg!("Creating offer account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                maker.key,
+                offer_account.key,
+                Rent::get()?.minimum_balance(Offer::LEN),
+                Offer::LEN as u64,
+                program_id,
+            ),
+            &[
+                maker.clone(),
+                offer_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"offer", maker.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        msg!("Calling the token program to escrow {} tokens in the vault...", amount_a);
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                maker_token_a_account.key,
+                vault.key,
+                maker.key,
+                &[maker.key],
+                amount_a,
+            )?,
+            &[
+                maker_token_a_account.clone(),
+                vault.clone(),
+                maker.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        let offer_info = Offer {
+            is_initialized: true,
+            maker: *maker.key,
+            id,
+            mint_a: vault_info.mint,
+            mint_b: receive_info.mint,
+            amount_a,
+            amount_b,
+            vault: *vault.key,
+            maker_receive_account: *maker_receive_account.key,
+            taker,
+            bump_seed,
+        };
+        offer_info.serialize(&mut &mut offer_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_take_offer(
+        accounts: &[AccountInfo],
+        amount_a: u64,
+        amount_b: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let taker = next_account_info(account_info_iter)?;
+
+        if !taker.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let maker = next_account_info(account_info_iter)?;
+        let offer_account = next_account_info(account_info_iter)?;
+        let offer_info = Self::unpack_offer(offer_account, program_id)?;
+        if offer_info.maker != *maker.key {
+            return Err(SwapError::NotTheMaker.into());
+        }
+        if offer_info.taker.is_some_and(|designated| designated != *taker.key) {
+            return Err(SwapError::NotTheTaker.into());
+        }
+        if offer_info.amount_a != amount_a || offer_info.amount_b != amount_b {
+            msg!("The offer is {} for {}", offer_info.amount_a, offer_info.amount_b);
+            return Err(SwapError::InvalidAmount.into());
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != offer_info.vault {
+            return Err(SwapError::InvalidVault.into());
+        }
+
+        let taker_token_b_account = next_account_info(account_info_iter)?;
+        let maker_receive_account = next_account_info(account_info_iter)?;
+        if *maker_receive_account.key != offer_info.maker_receive_account {
+            return Err(SwapError::InvalidReceiveAccount.into());
+        }
+
+        let taker_receive_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Both legs happen in the same instruction, if either transfer fails the whole swap is rolled back.
+        // The token program checks that the mints of both accounts of a transfer match.
+        msg!("Calling the token program to pay {} tokens to the maker...", amount_b);
+        invoke(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                taker_token_b_account.key,
+                maker_receive_account.key,
+                taker.key,
+                &[taker.key],
+                amount_b,
+            )?,
+            &[
+                taker_token_b_account.clone(),
+                maker_receive_account.clone(),
+                taker.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        Self::release_vault(&offer_info, offer_account, vault, taker_receive_account, maker, token_program)?;
+        Self::close_offer(offer_account, maker)
+    }
+
+    fn process_cancel_offer(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let maker = next_account_info(account_info_iter)?;
+
+        if !maker.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let offer_account = next_account_info(account_info_iter)?;
+        let offer_info = Self::unpack_offer(offer_account, program_id)?;
+        if offer_info.maker != *maker.key {
+            return Err(SwapError::NotTheMaker.into());
+        }
+
+        let vault = next_account_info(account_info_iter)?;
+        if *vault.key != offer_info.vault {
+            return Err(SwapError::InvalidVault.into());
+        }
+
+        let maker_token_a_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Self::release_vault(&offer_info, offer_account, vault, maker_token_a_account, maker, token_program)?;
+        Self::close_offer(offer_account, maker)
+    }
+
+    fn unpack_offer(offer_account: &AccountInfo, program_id: &Pubkey) -> Result<Offer, ProgramError> {
+        if offer_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // an offer open to anybody has no taker, and the bytes kept for one stay zero
+        let offer_info = Offer::deserialize(&mut &offer_account.data.borrow()[..])?;
+        if !offer_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(offer_info)
+    }
+
+    fn unpack_token_account(token_account: &AccountInfo) -> Result<TokenAccount, ProgramError> {
+        if *token_account.owner != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        TokenAccount::unpack(&token_account.try_borrow_data()?)
+    }
+
+    // The offer PDA owns the vault, so the program signs with the offer's seeds: once to move the escrowed tokens
+    // out, once to close the now empty vault. The vault's rent goes back to the maker, who paid for it.
+    fn release_vault<'a>(
+        offer_info: &Offer,
+        offer_account: &AccountInfo<'a>,
+        vault: &AccountInfo<'a>,
+        destination: &AccountInfo<'a>,
+        maker: &AccountInfo<'a>,
+        token_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let signer_seeds: &[&[u8]] = &[
+            b"offer",
+            offer_info.maker.as_ref(),
+            &offer_info.id.to_le_bytes(),
+            &[offer_info.bump_seed],
+        ];
+
+        msg!("Calling the token program to transfer {} tokens out of the vault...", offer_info.amount_a);
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                token_program.key,
+                vault.key,
+                destination.key,
+                offer_account.key,
+                &[offer_account.key],
+                offer_info.amount_a,
+            )?,
+            &[
+                vault.clone(),
+                destination.clone(),
+                offer_account.clone(),
+                token_program.clone(),
+            ],
+            &[signer_seeds],
+        )?;
+
+        msg!("Calling the token program to close the vault...");
+        invoke_signed(
+            &spl_token::instruction::close_account(
+                token_program.key,
+                vault.key,
+                maker.key,
+                offer_account.key,
+                &[offer_account.key],
+            )?,
+            &[
+                vault.clone(),
+                maker.clone(),
+                offer_account.clone(),
+                token_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+
+    fn close_offer<'a>(offer_account: &AccountInfo<'a>, maker: &AccountInfo<'a>) -> ProgramResult {
+        msg!("Closing the offer account...");
+        **maker.lamports.borrow_mut() = maker.lamports()
+            .checked_add(offer_account.lamports())
+            .ok_or(SwapError::AmountOverflow)?;
+        **offer_account.lamports.borrow_mut() = 0;
+        offer_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+}
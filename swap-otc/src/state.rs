@@ -0,0 +1,27 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Offer {
+    pub is_initialized: bool,
+    pub maker: Pubkey,
+    // lets a maker keep several offers open at once
+    pub id: u64,
+    // the maker gives amount_a of mint_a and wants amount_b of mint_b in return
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    // token account of mint_a owned by the offer PDA, holds the escrowed tokens
+    pub vault: Pubkey,
+    // token account of mint_b the maker is paid into
+    pub maker_receive_account: Pubkey,
+    // only this counterparty may take the offer, anyone may if it is None
+    pub taker: Option<Pubkey>,
+    pub bump_seed: u8,
+}
+
+impl Offer {
+    // the account is always sized for a designated taker
+    pub const LEN: usize = 1 + 32 + 8 + 32 + 32 + 8 + 8 + 32 + 32 + (1 + 32) + 1;
+}
@@ -0,0 +1,125 @@
+// Fake accounts for calling a processor natively in a unit test. An AccountInfo only borrows its lamports, its data
+// and its keys, TestAccount owns them, so they outlive the call and can be checked afterwards.
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey, system_program};
+
+#[derive(Clone, Debug)]
+pub struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+    is_signer: bool,
+    is_writable: bool,
+    executable: bool,
+    // the bump of pda, None for any other key
+    bump: Option<u8>,
+}
+
+impl Default for TestAccount {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestAccount {
+    // An empty account with a random key, owned by the system program, like a fresh wallet without lamports
+    pub fn new() -> Self {
+        Self {
+            key: Pubkey::new_unique(),
+            owner: system_program::id(),
+            lamports: 0,
+            data: Vec::new(),
+            is_signer: false,
+            is_writable: false,
+            executable: false,
+            bump: None,
+        }
+    }
+
+    pub fn key(mut self, key: &Pubkey) -> Self {
+        self.key = *key;
+        self.bump = None;
+        self
+    }
+
+    // The PDA of the program for the seeds, like the program derives it with find_program_address
+    pub fn pda(mut self, program_id: &Pubkey, seeds: &[&[u8]]) -> Self {
+        let (key, bump) = Pubkey::find_program_address(seeds, program_id);
+        self.key = key;
+        self.bump = Some(bump);
+        self
+    }
+
+    pub fn owner(mut self, owner: &Pubkey) -> Self {
+        self.owner = *owner;
+        self
+    }
+
+    pub fn lamports(mut self, lamports: u64) -> Self {
+        self.lamports = lamports;
+        self
+    }
+
+    pub fn data(mut self, data: &[u8]) -> Self {
+        self.data = data.to_vec();
+        self
+    }
+
+    // size zero bytes, what the system program leaves in a newly created account
+    pub fn zeroed(mut self, size: usize) -> Self {
+        self.data = vec![0; size];
+        self
+    }
+
+    pub fn signer(mut self) -> Self {
+        self.is_signer = true;
+        self
+    }
+
+    pub fn writable(mut self) -> Self {
+        self.is_writable = true;
+        self
+    }
+
+    pub fn executable(mut self) -> Self {
+        self.executable = true;
+        self
+    }
+
+    pub fn address(&self) -> Pubkey {
+        self.key
+    }
+
+    // The bump of the PDA, None if the key wasn't derived with pda
+    pub fn bump(&self) -> Option<u8> {
+        self.bump
+    }
+
+    pub fn current_lamports(&self) -> u64 {
+        self.lamports
+    }
+
+    pub fn current_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    // The AccountInfo the processor gets. It borrows this account mutably, once it is dropped the changes of the
+    // processor can be read with current_lamports and current_data.
+    pub fn account_info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            self.is_signer,
+            self.is_writable,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            self.executable,
+            0,
+        )
+    }
+}
+
+// The AccountInfos of all accounts, in order, for process_instruction(program_id, &infos, data)
+pub fn account_infos(accounts: &mut [TestAccount]) -> Vec<AccountInfo<'_>> {
+    accounts.iter_mut().map(TestAccount::account_info).collect()
+}
@@ -0,0 +1,66 @@
+// Calls the greeting and the calculator processors natively with TestAccounts, no bank is started
+use borsh::{BorshDeserialize, BorshSerialize};
+use function_calls::{
+    instruction::Instruction,
+    processor::{CalculatorResult, Processor},
+};
+use helloworld::{process_instruction, GreetingAccount};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+use test_utils::{account_infos, TestAccount};
+
+#[test]
+fn greet_increments_the_counter() {
+    let program_id = Pubkey::new_unique();
+    let state = GreetingAccount { counter: 4 }.try_to_vec().unwrap();
+    let mut accounts = [TestAccount::new().owner(&program_id).data(&state).writable()];
+
+    process_instruction(&program_id, &account_infos(&mut accounts), &[]).unwrap();
+
+    let greeting = GreetingAccount::try_from_slice(accounts[0].current_data()).unwrap();
+    assert_eq!(greeting.counter, 5);
+}
+
+#[test]
+fn greet_rejects_an_account_of_another_program() {
+    let program_id = Pubkey::new_unique();
+    let mut account = TestAccount::new().owner(&Pubkey::new_unique()).zeroed(4).writable();
+
+    let result = process_instruction(&program_id, &[account.account_info()], &[]);
+
+    assert_eq!(result, Err(ProgramError::IncorrectProgramId));
+    assert_eq!(account.current_data(), &[0; 4]);
+}
+
+#[test]
+fn add_and_sub_store_the_result() {
+    let program_id = Pubkey::new_unique();
+    let mut account = TestAccount::new().owner(&program_id).zeroed(24).writable();
+
+    Processor::process(&program_id, &[account.account_info()], &Instruction::Add { a: 3, b: 5 }.pack()).unwrap();
+    let result = CalculatorResult::try_from_slice(account.current_data()).unwrap();
+    assert_eq!((result.result, result.a, result.b), (8, 3, 5));
+
+    Processor::process(&program_id, &[account.account_info()], &Instruction::Sub { a: 9, b: 2 }.pack()).unwrap();
+    let result = CalculatorResult::try_from_slice(account.current_data()).unwrap();
+    assert_eq!((result.result, result.a, result.b), (7, 9, 2));
+}
+
+#[test]
+fn missing_account_is_rejected() {
+    let program_id = Pubkey::new_unique();
+
+    let result = Processor::process(&program_id, &[], &Instruction::Add { a: 1, b: 1 }.pack());
+
+    assert_eq!(result, Err(ProgramError::NotEnoughAccountKeys));
+}
+
+#[test]
+fn pda_keeps_its_bump() {
+    let program_id = Pubkey::new_unique();
+    let account = TestAccount::new().pda(&program_id, &[b"vault"]);
+
+    let bump = account.bump().unwrap();
+    let expected = Pubkey::create_program_address(&[b"vault", &[bump]], &program_id).unwrap();
+    assert_eq!(account.address(), expected);
+    assert_eq!(TestAccount::new().bump(), None);
+}
@@ -0,0 +1,257 @@
+import yaml
+import sys
+import json
+import base64
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'tickets.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'tickets-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_event_address(organizer: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("event", encoding="utf8"), bytes(organizer), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def get_ticket_address(event: PublicKey, number: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("ticket", encoding="utf8"), bytes(event), number.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def send(data: bytes, keys: list, *signers: Keypair):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), *signers)
+    client.confirm_transaction(tx["result"])
+
+def get_sold(event: PublicKey) -> int:
+    data = base64.b64decode(client.get_account_info(event)['result']['value']['data'][0])
+    # is_initialized, organizer, id, price and capacity come before the number of sold tickets
+    offset = 1 + 32 + 8 + 8 + 8
+    return int.from_bytes(data[offset:offset + 8], byteorder='little')
+
+def get_ticket(ticket: PublicKey) -> tuple:
+    data = base64.b64decode(client.get_account_info(ticket)['result']['value']['data'][0])
+    # is_initialized, event and number come before the owner and the used flag
+    offset = 1 + 32 + 8
+    return PublicKey(data[offset:offset + 32]), data[offset + 32] == 1
+
+def create_event(organizer: Keypair, id: int, price: int, capacity: int, royalty_bps: int) -> PublicKey:
+    event = get_event_address(organizer.public_key, id)
+    print(f"\nCreating event {event} with {capacity} tickets for {price} lamports each")
+    send(
+        (0).to_bytes(1, byteorder='little') + id.to_bytes(8, byteorder='little') + price.to_bytes(8, byteorder='little')
+            + capacity.to_bytes(8, byteorder='little') + royalty_bps.to_bytes(2, byteorder='little'),
+        [
+            AccountMeta(pubkey=organizer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=event, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        organizer,
+    )
+    return event
+
+def buy_ticket(buyer: Keypair, event: PublicKey, organizer: PublicKey) -> PublicKey:
+    # the ticket is created at the number of sold tickets, so we have to read the event first
+    ticket = get_ticket_address(event, get_sold(event))
+    print(f"\n{buyer.public_key} buys ticket {ticket}")
+    send(
+        (1).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=buyer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=event, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=ticket, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=organizer, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        buyer,
+    )
+    return ticket
+
+def transfer_ticket(owner: Keypair, new_owner: Keypair, ticket: PublicKey, event: PublicKey, organizer: PublicKey, price: int):
+    print(f"\nTransferring ticket {ticket} to {new_owner.public_key} for {price} lamports")
+    # a resale needs the signature of the new owner, they pay the price
+    signers = [owner, new_owner] if price > 0 else [owner]
+    send(
+        (2).to_bytes(1, byteorder='little') + price.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=new_owner.public_key, is_signer=price > 0, is_writable=True),
+            AccountMeta(pubkey=ticket, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=event, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=organizer, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        *signers,
+    )
+
+def check_in(organizer: Keypair, event: PublicKey, ticket: PublicKey):
+    print(f"\nChecking in ticket {ticket}")
+    send(
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=organizer.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=event, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=ticket, is_signer=False, is_writable=True),
+        ],
+        organizer,
+    )
+
+def print_ticket(ticket: PublicKey):
+    owner, used = get_ticket(ticket)
+    print(f"Ticket {ticket} belongs to {owner}, {'used' if used else 'not used yet'}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    organizer = setup_user_keypair()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+
+    # two tickets, resales pay 10% to the organizer
+    event = create_event(organizer, 0, LAMPORTS_PER_SOL // 10, 2, 1000)
+    ticket = buy_ticket(alice, event, organizer.public_key)
+    buy_ticket(bob, event, organizer.public_key)
+    try:
+        buy_ticket(alice, event, organizer.public_key)
+    except Exception as e:
+        print(f"Buying a third ticket fails as expected: {e}")
+
+    print(f"Organizer balance: {client.get_balance(organizer.public_key)['result']['value']} lamports")
+    transfer_ticket(alice, bob, ticket, event, organizer.public_key, LAMPORTS_PER_SOL // 5)
+    print(f"Organizer balance: {client.get_balance(organizer.public_key)['result']['value']} lamports")
+    print_ticket(ticket)
+
+    check_in(organizer, event, ticket)
+    print_ticket(ticket)
+    try:
+        transfer_ticket(bob, alice, ticket, event, organizer.public_key, 0)
+    except Exception as e:
+        print(f"Transferring a used ticket fails as expected: {e}")
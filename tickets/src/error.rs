@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum TicketsError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Event Address")]
+    InvalidEventAddress,
+    #[error("Invalid Ticket Address")]
+    InvalidTicketAddress,
+    #[error("Invalid Royalty")]
+    InvalidRoyalty,
+    #[error("Sold Out")]
+    SoldOut,
+    #[error("Not The Owner")]
+    NotTheOwner,
+    #[error("Not The Organizer")]
+    NotTheOrganizer,
+    #[error("Ticket Already Used")]
+    TicketAlreadyUsed,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<TicketsError> for ProgramError {
+    fn from(e: TicketsError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,95 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::TicketsError::InvalidInstruction;
+
+pub enum TicketsInstruction {
+
+    // Creates an event in a PDA derived from ["event", organizer, id].
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The organizer
+    // 1. `[writable]` The event PDA
+    // 2. `[]` The system program
+    CreateEvent {
+        id: u64,
+        price: u64,
+        capacity: u64,
+        royalty_bps: u16,
+    },
+
+    // Buys the next ticket from the organizer, the ticket is a PDA derived from ["ticket", event, number],
+    // where number is the count of tickets sold before this instruction.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The buyer, pays the price and the rent of the ticket
+    // 1. `[writable]` The event PDA
+    // 2. `[writable]` The ticket PDA for the next number
+    // 3. `[writable]` The organizer
+    // 4. `[]` The system program
+    BuyTicket,
+
+    // Hands a ticket over to a new owner. With a price above 0 it is a resale: the new owner has to sign and
+    // pays the price, the royalty goes to the organizer and the rest to the current owner.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The current owner
+    // 1. `[writable]` The new owner, signer when the price is above 0
+    // 2. `[writable]` The ticket PDA
+    // 3. `[]` The event PDA
+    // 4. `[writable]` The organizer
+    // 5. `[]` The system program
+    TransferTicket {
+        price: u64,
+    },
+
+    // Marks a ticket as used at the entrance. Only the organizer may check tickets in.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The organizer
+    // 1. `[]` The event PDA
+    // 2. `[writable]` The ticket PDA
+    CheckIn,
+}
+
+impl TicketsInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateEvent {
+                id: Self::unpack_u64(rest, 0)?,
+                price: Self::unpack_u64(rest, 8)?,
+                capacity: Self::unpack_u64(rest, 16)?,
+                royalty_bps: rest
+                    .get(24..26)
+                    .and_then(|slice| slice.try_into().ok())
+                    .map(u16::from_le_bytes)
+                    .ok_or(InvalidInstruction)?,
+            },
+            1 => Self::BuyTicket,
+            2 => Self::TransferTicket {
+                price: Self::unpack_u64(rest, 0)?,
+            },
+            3 => Self::CheckIn,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
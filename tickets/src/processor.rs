@@ -0,0 +1,322 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::TicketsInstruction,
+    error::TicketsError,
+    state::{Event, Ticket},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = TicketsInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            TicketsInstruction::CreateEvent { id, price, capacity, royalty_bps } => {
+                msg!("Instruction: CreateEvent");
+                Self::process_create_event(accounts, id, price, capacity, royalty_bps, program_id)
+            },
+            TicketsInstruction::BuyTicket => {
+                msg!("Instruction: BuyTicket");
+                Self::process_buy_ticket(accounts, program_id)
+            },
+            TicketsInstruction::TransferTicket { price } => {
+                msg!("Instruction: TransferTicket");
+                Self::process_transfer_ticket(accounts, price, program_id)
+            },
+            TicketsInstruction::CheckIn => {
+                msg!("Instruction: CheckIn");
+                Self::process_check_in(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_event(
+        accounts: &[AccountInfo],
+        id: u64,
+        price: u64,
+        capacity: u64,
+        royalty_bps: u16,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if royalty_bps > 10_000 {
+            return Err(TicketsError::InvalidRoyalty.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let organizer = next_account_info(account_info_iter)?;
+
+        if !organizer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let event_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (event_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"event", organizer.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if event_pda != *event_account.key {
+            return Err(TicketsError::InvalidEventAddress.into());
+        }
+
+        if !event_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating event account with {} tickets...", capacity);
+        Self::create_pda_account(
+            organizer,
+            event_account,
+            system_program,
+            Event::LEN,
+            program_id,
+            &[b"event", organizer.key.as_ref(), &id_bytes, &[bump_seed]],
+        )?;
+
+        let event_info = Event {
+            is_initialized: true,
+            organizer: *organizer.key,
+            id,
+            price,
+            capacity,
+            sold: 0,
+            royalty_bps,
+            bump_seed,
+        };
+        event_info.serialize(&mut &mut event_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_buy_ticket(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let buyer = next_account_info(account_info_iter)?;
+
+        if !buyer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let event_account = next_account_info(account_info_iter)?;
+        let mut event_info = Self::unpack_event(event_account, program_id)?;
+        if event_info.sold >= event_info.capacity {
+            return Err(TicketsError::SoldOut.into());
+        }
+
+        // like the entries of the guestbook, the ticket addresses only depend on the event and the number
+        let ticket_account = next_account_info(account_info_iter)?;
+        let number_bytes = event_info.sold.to_le_bytes();
+        let (ticket_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"ticket", event_account.key.as_ref(), &number_bytes],
+            program_id,
+        );
+        if ticket_pda != *ticket_account.key {
+            return Err(TicketsError::InvalidTicketAddress.into());
+        }
+
+        let organizer = next_account_info(account_info_iter)?;
+        if event_info.organizer != *organizer.key {
+            return Err(TicketsError::NotTheOrganizer.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        msg!("Calling the system program to pay {} lamports to the organizer...", event_info.price);
+        invoke(
+            &system_instruction::transfer(buyer.key, organizer.key, event_info.price),
+            &[buyer.clone(), organizer.clone(), system_program.clone()],
+        )?;
+
+        msg!("Creating ticket {}...", event_info.sold);
+        Self::create_pda_account(
+            buyer,
+            ticket_account,
+            system_program,
+            Ticket::LEN,
+            program_id,
+            &[b"ticket", event_account.key.as_ref(), &number_bytes, &[bump_seed]],
+        )?;
+
+        let ticket_info = Ticket {
+            is_initialized: true,
+            event: *event_account.key,
+            number: event_info.sold,
+            owner: *buyer.key,
+            used: false,
+            bump_seed,
+        };
+        ticket_info.serialize(&mut &mut ticket_account.data.borrow_mut()[..])?;
+
+        event_info.sold += 1;
+        event_info.serialize(&mut &mut event_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_transfer_ticket(
+        accounts: &[AccountInfo],
+        price: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let new_owner = next_account_info(account_info_iter)?;
+        let ticket_account = next_account_info(account_info_iter)?;
+        let mut ticket_info = Self::unpack_ticket(ticket_account, program_id)?;
+        if ticket_info.owner != *owner.key {
+            return Err(TicketsError::NotTheOwner.into());
+        }
+        if ticket_info.used {
+            return Err(TicketsError::TicketAlreadyUsed.into());
+        }
+
+        let event_account = next_account_info(account_info_iter)?;
+        let event_info = Self::unpack_event(event_account, program_id)?;
+        if ticket_info.event != *event_account.key {
+            return Err(TicketsError::InvalidTicketAddress.into());
+        }
+
+        let organizer = next_account_info(account_info_iter)?;
+        if event_info.organizer != *organizer.key {
+            return Err(TicketsError::NotTheOrganizer.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        // a ticket given away for free needs no signature of the new owner, a sold one is paid by them
+        if price > 0 {
+            if !new_owner.is_signer {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+
+            let royalty = event_info.royalty(price);
+            msg!("Paying {} lamports to the seller and a royalty of {} lamports to the organizer...", price - royalty, royalty);
+            if royalty > 0 {
+                invoke(
+                    &system_instruction::transfer(new_owner.key, organizer.key, royalty),
+                    &[new_owner.clone(), organizer.clone(), system_program.clone()],
+                )?;
+            }
+            invoke(
+                &system_instruction::transfer(new_owner.key, owner.key, price - royalty),
+                &[new_owner.clone(), owner.clone(), system_program.clone()],
+            )?;
+        }
+
+        ticket_info.owner = *new_owner.key;
+        ticket_info.serialize(&mut &mut ticket_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_check_in(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let organizer = next_account_info(account_info_iter)?;
+
+        if !organizer.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let event_account = next_account_info(account_info_iter)?;
+        let event_info = Self::unpack_event(event_account, program_id)?;
+        if event_info.organizer != *organizer.key {
+            return Err(TicketsError::NotTheOrganizer.into());
+        }
+
+        let ticket_account = next_account_info(account_info_iter)?;
+        let mut ticket_info = Self::unpack_ticket(ticket_account, program_id)?;
+        if ticket_info.event != *event_account.key {
+            return Err(TicketsError::InvalidTicketAddress.into());
+        }
+        if ticket_info.used {
+            return Err(TicketsError::TicketAlreadyUsed.into());
+        }
+
+        msg!("Checking in ticket {} of {}", ticket_info.number, ticket_info.owner);
+        ticket_info.used = true;
+        ticket_info.serialize(&mut &mut ticket_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn unpack_event(event_account: &AccountInfo, program_id: &Pubkey) -> Result<Event, ProgramError> {
+        if event_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let event_info = Event::try_from_slice(&event_account.data.borrow())?;
+        if !event_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(event_info)
+    }
+
+    fn unpack_ticket(ticket_account: &AccountInfo, program_id: &Pubkey) -> Result<Ticket, ProgramError> {
+        if ticket_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let ticket_info = Ticket::try_from_slice(&ticket_account.data.borrow())?;
+        if !ticket_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(ticket_info)
+    }
+
+    // Creates a rent exempt account at a PDA of this program. Only the program can sign for the PDA,
+    // therefore the system program is invoked with invoke_signed and the PDA's seeds.
+    fn create_pda_account<'a>(
+        payer: &AccountInfo<'a>,
+        new_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        space: usize,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let lamports = Rent::get()?.minimum_balance(space);
+        let create_account_ix = system_instruction::create_account(
+            payer.key,
+            new_account.key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                payer.clone(),
+                new_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,40 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Event {
+    pub is_initialized: bool,
+    pub organizer: Pubkey,
+    pub id: u64,
+    // price of a ticket in lamports when it is bought from the organizer
+    pub price: u64,
+    pub capacity: u64,
+    // number of tickets sold so far, the next ticket is created at this number
+    pub sold: u64,
+    // share of every resale price going to the organizer, in basis points
+    pub royalty_bps: u16,
+    pub bump_seed: u8,
+}
+
+impl Event {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 8 + 2 + 1;
+
+    pub fn royalty(&self, resale_price: u64) -> u64 {
+        (resale_price as u128 * self.royalty_bps as u128 / 10_000) as u64
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Ticket {
+    pub is_initialized: bool,
+    pub event: Pubkey,
+    pub number: u64,
+    pub owner: Pubkey,
+    // set at check-in, a used ticket can't be checked in or transferred again
+    pub used: bool,
+    pub bump_seed: u8,
+}
+
+impl Ticket {
+    pub const LEN: usize = 1 + 32 + 8 + 32 + 1 + 1;
+}
@@ -0,0 +1,233 @@
+import yaml
+import sys
+import json
+import base64
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'tictactoe.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'tictactoe-keypair.json'))
+
+MARKS = [" ", "X", "O"]
+STATUS = ["waiting for player", "in progress", "X won", "O won", "draw"]
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+
+def get_game_address(player_x: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("tictactoe", encoding="utf8"), bytes(player_x), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def send(signer: Keypair, data: bytes, keys: list):
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), signer)
+    client.confirm_transaction(tx["result"])
+
+def create_game(player_x: Keypair, id: int, wager: int) -> PublicKey:
+    game = get_game_address(player_x.public_key, id)
+    print(f"\nCreating game {game} with a wager of {wager} lamports")
+    send(
+        player_x,
+        (0).to_bytes(1, byteorder='little') + id.to_bytes(8, byteorder='little') + wager.to_bytes(8, byteorder='little'),
+        [
+            AccountMeta(pubkey=player_x.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=game, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+    return game
+
+def join_game(player_o: Keypair, game: PublicKey):
+    print(f"\n{player_o.public_key} joins as O")
+    send(
+        player_o,
+        (1).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=player_o.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=game, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+    )
+
+def play_move(player: Keypair, game: PublicKey, player_x: PublicKey, player_o: PublicKey, cell: int):
+    send(
+        player,
+        (2).to_bytes(1, byteorder='little') + bytes([cell]),
+        [
+            AccountMeta(pubkey=player.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=game, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=player_x, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=player_o, is_signer=False, is_writable=True),
+        ],
+    )
+
+def close_game(player_x: Keypair, game: PublicKey):
+    print("\nClosing the game")
+    send(
+        player_x,
+        (3).to_bytes(1, byteorder='little'),
+        [
+            AccountMeta(pubkey=player_x.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=game, is_signer=False, is_writable=True),
+        ],
+    )
+
+def print_game(game: PublicKey):
+    data = base64.b64decode(client.get_account_info(game)['result']['value']['data'][0])
+    # is_initialized, player X, player O (set once joined), id and wager come before the board
+    offset = 1 + 32 + 1 + 32 + 8 + 8
+    board = data[offset:offset + 9]
+    status = data[offset + 10]
+    for row in range(3):
+        print(" " + " | ".join(MARKS[cell] for cell in board[row * 3:row * 3 + 3]))
+    print(f"Status: {STATUS[status]}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    alice = setup_user_keypair()
+    bob = setup_user_keypair()
+
+    game = create_game(alice, 0, LAMPORTS_PER_SOL // 10)
+    join_game(bob, game)
+
+    # X takes the left column while O plays in the middle
+    moves = [(alice, 0), (bob, 4), (alice, 3), (bob, 5), (alice, 6)]
+    for player, cell in moves:
+        print(f"\n{player.public_key} plays cell {cell}")
+        play_move(player, game, alice.public_key, bob.public_key, cell)
+        print_game(game)
+
+    try:
+        play_move(bob, game, alice.public_key, bob.public_key, 8)
+    except Exception as e:
+        print(f"Playing after the game ended fails as expected: {e}")
+
+    close_game(alice, game)
+    print(f"Alice balance: {client.get_balance(alice.public_key)['result']['value']} lamports")
+    print(f"Bob balance: {client.get_balance(bob.public_key)['result']['value']} lamports")
@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum TicTacToeError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Game Address")]
+    InvalidGameAddress,
+    #[error("Game Already Joined")]
+    GameAlreadyJoined,
+    #[error("Game Not In Progress")]
+    GameNotInProgress,
+    #[error("Game In Progress")]
+    GameInProgress,
+    #[error("Not A Player")]
+    NotAPlayer,
+    #[error("Not Your Turn")]
+    NotYourTurn,
+    #[error("Invalid Cell")]
+    InvalidCell,
+    #[error("Cell Taken")]
+    CellTaken,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<TicTacToeError> for ProgramError {
+    fn from(e: TicTacToeError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,81 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::TicTacToeError::InvalidInstruction;
+
+pub enum TicTacToeInstruction {
+
+    // Creates a game in a PDA derived from ["tictactoe", player_x, id]. The wager is kept in the PDA on top of its rent.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` Player X
+    // 1. `[writable]` The game PDA
+    // 2. `[]` The system program
+    CreateGame {
+        id: u64,
+        wager: u64,
+    },
+
+    // Joins a game as O and matches the wager.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` Player O
+    // 1. `[writable]` The game PDA
+    // 2. `[]` The system program
+    JoinGame,
+
+    // Places a mark on one of the nine cells, numbered row by row from 0 to 8. When the move ends the game,
+    // the winner receives both wagers, a draw returns them.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The player whose turn it is
+    // 1. `[writable]` The game PDA
+    // 2. `[writable]` Player X
+    // 3. `[writable]` Player O
+    PlayMove {
+        cell: u8,
+    },
+
+    // Closes a finished game, or a game nobody joined, and returns the rent (and the unmatched wager) to X.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` Player X
+    // 1. `[writable]` The game PDA
+    CloseGame,
+}
+
+impl TicTacToeInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::CreateGame {
+                id: Self::unpack_u64(rest, 0)?,
+                wager: Self::unpack_u64(rest, 8)?,
+            },
+            1 => Self::JoinGame,
+            2 => Self::PlayMove {
+                cell: *rest.first().ok_or(InvalidInstruction)?,
+            },
+            3 => Self::CloseGame,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,271 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::TicTacToeInstruction,
+    error::TicTacToeError,
+    state::{Cell, Game, GameStatus},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = TicTacToeInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            TicTacToeInstruction::CreateGame { id, wager } => {
+                msg!("Instruction: CreateGame");
+                Self::process_create_game(accounts, id, wager, program_id)
+            },
+            TicTacToeInstruction::JoinGame => {
+                msg!("Instruction: JoinGame");
+                Self::process_join_game(accounts, program_id)
+            },
+            TicTacToeInstruction::PlayMove { cell } => {
+                msg!("Instruction: PlayMove");
+                Self::process_play_move(accounts, cell, program_id)
+            },
+            TicTacToeInstruction::CloseGame => {
+                msg!("Instruction: CloseGame");
+                Self::process_close_game(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_create_game(
+        accounts: &[AccountInfo],
+        id: u64,
+        wager: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        // both wagers have to fit into the game account later on
+        wager.checked_mul(2).ok_or(TicTacToeError::AmountOverflow)?;
+
+        let account_info_iter = &mut accounts.iter();
+        let player_x = next_account_info(account_info_iter)?;
+
+        if !player_x.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (game_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"tictactoe", player_x.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if game_pda != *game_account.key {
+            return Err(TicTacToeError::InvalidGameAddress.into());
+        }
+
+        if !game_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // the wager is simply added on top of the rent when the account is created
+        let lamports = Rent::get()?.minimum_balance(Game::LEN)
+            .checked_add(wager)
+            .ok_or(TicTacToeError::AmountOverflow)?;
+
+        msg!("Creating game account with a wager of {} lamports...", wager);
+        invoke_signed(
+            &system_instruction::create_account(
+                player_x.key,
+                game_account.key,
+                lamports,
+                Game::LEN as u64,
+                program_id,
+            ),
+            &[
+                player_x.clone(),
+                game_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"tictactoe", player_x.key.as_ref(), &id_bytes, &[bump_seed]]],
+        )?;
+
+        let game_info = Game {
+            is_initialized: true,
+            player_x: *player_x.key,
+            player_o: None,
+            id,
+            wager,
+            board: [Cell::Empty; 9],
+            turn: Cell::X,
+            status: GameStatus::WaitingForPlayer,
+            bump_seed,
+        };
+        game_info.serialize(&mut &mut game_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_join_game(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let player_o = next_account_info(account_info_iter)?;
+
+        if !player_o.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let mut game_info = Self::unpack_game(game_account, program_id)?;
+
+        if game_info.status != GameStatus::WaitingForPlayer {
+            return Err(TicTacToeError::GameAlreadyJoined.into());
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        if game_info.wager > 0 {
+            msg!("Calling the system program to match the wager of {} lamports...", game_info.wager);
+            invoke(
+                &system_instruction::transfer(player_o.key, game_account.key, game_info.wager),
+                &[
+                    player_o.clone(),
+                    game_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+
+        game_info.player_o = Some(*player_o.key);
+        game_info.status = GameStatus::InProgress;
+        game_info.serialize(&mut &mut game_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_play_move(
+        accounts: &[AccountInfo],
+        cell: u8,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let player = next_account_info(account_info_iter)?;
+
+        if !player.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let mut game_info = Self::unpack_game(game_account, program_id)?;
+
+        if game_info.status != GameStatus::InProgress {
+            return Err(TicTacToeError::GameNotInProgress.into());
+        }
+
+        let player_o_key = game_info.player_o.ok_or(TicTacToeError::GameNotInProgress)?;
+        let expected_player = match game_info.turn {
+            Cell::X => game_info.player_x,
+            _ => player_o_key,
+        };
+        if *player.key != expected_player {
+            return if *player.key == game_info.player_x || *player.key == player_o_key {
+                Err(TicTacToeError::NotYourTurn.into())
+            } else {
+                Err(TicTacToeError::NotAPlayer.into())
+            };
+        }
+
+        let cell = cell as usize;
+        match game_info.board.get(cell) {
+            None => return Err(TicTacToeError::InvalidCell.into()),
+            Some(Cell::Empty) => {},
+            Some(_) => return Err(TicTacToeError::CellTaken.into()),
+        }
+
+        game_info.play(cell);
+        msg!("{:?} played cell {}, game status: {:?}", expected_player, cell, game_info.status);
+
+        let player_x = next_account_info(account_info_iter)?;
+        let player_o = next_account_info(account_info_iter)?;
+        if *player_x.key != game_info.player_x || *player_o.key != player_o_key {
+            return Err(TicTacToeError::NotAPlayer.into());
+        }
+
+        // the game account is owned by the program, so the wagers are paid out by moving lamports directly
+        let pot = game_info.wager * 2;
+        let payouts = match game_info.status {
+            GameStatus::XWon => [(player_x, pot), (player_o, 0)],
+            GameStatus::OWon => [(player_x, 0), (player_o, pot)],
+            GameStatus::Draw => [(player_x, game_info.wager), (player_o, game_info.wager)],
+            _ => [(player_x, 0), (player_o, 0)],
+        };
+        for (destination, amount) in payouts {
+            if amount > 0 {
+                msg!("Paying {} lamports to {}", amount, destination.key);
+                **game_account.lamports.borrow_mut() -= amount;
+                **destination.lamports.borrow_mut() = destination.lamports()
+                    .checked_add(amount)
+                    .ok_or(TicTacToeError::AmountOverflow)?;
+            }
+        }
+
+        game_info.serialize(&mut &mut game_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_close_game(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let player_x = next_account_info(account_info_iter)?;
+
+        if !player_x.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let game_account = next_account_info(account_info_iter)?;
+        let game_info = Self::unpack_game(game_account, program_id)?;
+
+        if game_info.player_x != *player_x.key {
+            return Err(TicTacToeError::NotAPlayer.into());
+        }
+        if game_info.status == GameStatus::InProgress {
+            return Err(TicTacToeError::GameInProgress.into());
+        }
+
+        // a finished game only holds its rent anymore, a game nobody joined still holds the wager of X
+        msg!("Closing the game account...");
+        **player_x.lamports.borrow_mut() = player_x.lamports()
+            .checked_add(game_account.lamports())
+            .ok_or(TicTacToeError::AmountOverflow)?;
+        **game_account.lamports.borrow_mut() = 0;
+        game_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn unpack_game(game_account: &AccountInfo, program_id: &Pubkey) -> Result<Game, ProgramError> {
+        if game_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // player O is None until a second player joins, the game ends before its account does
+        let game_info = Game::deserialize(&mut &game_account.data.borrow()[..])?;
+        if !game_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(game_info)
+    }
+}
@@ -0,0 +1,67 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// borsh needs Default to deserialize arrays
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Cell {
+    #[default]
+    Empty,
+    X,
+    O,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    WaitingForPlayer,
+    InProgress,
+    XWon,
+    OWon,
+    Draw,
+}
+
+// rows, columns and both diagonals, as indices into the board
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2], [3, 4, 5], [6, 7, 8],
+    [0, 3, 6], [1, 4, 7], [2, 5, 8],
+    [0, 4, 8], [2, 4, 6],
+];
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Game {
+    pub is_initialized: bool,
+    // X creates the game and moves first
+    pub player_x: Pubkey,
+    pub player_o: Option<Pubkey>,
+    pub id: u64,
+    // lamports each player puts in, can be 0
+    pub wager: u64,
+    // the cells row by row, starting top left
+    pub board: [Cell; 9],
+    pub turn: Cell,
+    pub status: GameStatus,
+    pub bump_seed: u8,
+}
+
+impl Game {
+    pub const LEN: usize = 1 + 32 + (1 + 32) + 8 + 8 + 9 + 1 + 1 + 1;
+
+    // Places the mark of the player whose turn it is and updates the status. The caller checks the cell is free.
+    pub fn play(&mut self, cell: usize) {
+        let mark = self.turn;
+        self.board[cell] = mark;
+
+        let won = LINES
+            .iter()
+            .any(|line| line.iter().all(|&index| self.board[index] == mark));
+
+        self.status = if won {
+            if mark == Cell::X { GameStatus::XWon } else { GameStatus::OWon }
+        } else if self.board.iter().all(|&cell| cell != Cell::Empty) {
+            GameStatus::Draw
+        } else {
+            GameStatus::InProgress
+        };
+
+        self.turn = if mark == Cell::X { Cell::O } else { Cell::X };
+    }
+}
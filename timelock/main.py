@@ -0,0 +1,273 @@
+import yaml
+import sys
+import json
+import time
+import base64
+
+from os import path
+from solana.rpc.api import Client
+from solana.rpc.types import TxOpts
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'timelock.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'timelock-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def create_token_program(payer: Keypair, decimals: int) -> Token:
+    token = Token.create_mint(
+        conn=client,
+        payer=payer,
+        mint_authority=payer.public_key,
+        decimals=decimals,
+        program_id=TOKEN_PROGRAM_ID
+    )
+    print(f"Address of token program: {token.pubkey}")
+    return token
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    owner_token_account = token.create_account(owner)
+    print(f"Created token account {owner_token_account} for {owner}")
+    return owner_token_account
+
+def mint_token(token: Token, minter: Keypair, account: PublicKey, amount: int):
+    print(f"Minting {amount} tokens to {account}")
+    token.mint_to(
+        dest=account,
+        mint_authority=minter,
+        amount=amount,
+        opts=TxOpts(skip_confirmation=False)
+    )
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+def get_lock_address(depositor: PublicKey, beneficiary: PublicKey, id: int) -> PublicKey:
+    return PublicKey.find_program_address(
+        [bytes("timelock", encoding="utf8"), bytes(depositor), bytes(beneficiary), id.to_bytes(8, byteorder='little')],
+        program_id
+    )[0]
+
+def encode_lock(tag: int, id: int, amount: int, release_ts: int, cancellable: bool) -> bytes:
+    return (
+        tag.to_bytes(1, byteorder='little')
+        + id.to_bytes(8, byteorder='little')
+        + amount.to_bytes(8, byteorder='little')
+        + release_ts.to_bytes(8, byteorder='little', signed=True)
+        + (1 if cancellable else 0).to_bytes(1, byteorder='little')
+    )
+
+def lock_lamports(depositor: Keypair, beneficiary: PublicKey, id: int, amount: int, release_ts: int, cancellable: bool):
+    print(f"\nLocking {amount} lamports for {beneficiary} until {release_ts}")
+    lock_ix = TransactionInstruction(
+        data=encode_lock(0, id, amount, release_ts, cancellable),
+        keys=[
+            AccountMeta(pubkey=depositor.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=beneficiary, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=get_lock_address(depositor.public_key, beneficiary, id), is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(lock_ix), depositor)
+    client.confirm_transaction(tx["result"])
+
+def lock_tokens(depositor: Keypair, beneficiary: PublicKey, token: Token, source: PublicKey, id: int, amount: int, release_ts: int, cancellable: bool) -> PublicKey:
+    lock = get_lock_address(depositor.public_key, beneficiary, id)
+    # the vault is a normal token account owned by the lock PDA
+    vault = create_token_account(token, lock)
+    print(f"\nLocking {amount} tokens for {beneficiary} until {release_ts}")
+    lock_ix = TransactionInstruction(
+        data=encode_lock(1, id, amount, release_ts, cancellable),
+        keys=[
+            AccountMeta(pubkey=depositor.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=beneficiary, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=lock, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=source, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(lock_ix), depositor)
+    client.confirm_transaction(tx["result"])
+    return vault
+
+def release(beneficiary: Keypair, depositor: PublicKey, id: int, token_accounts: list = []):
+    print(f"\n{beneficiary.public_key} releases lock {id}")
+    keys = [
+        AccountMeta(pubkey=beneficiary.public_key, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=depositor, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=get_lock_address(depositor, beneficiary.public_key, id), is_signer=False, is_writable=True),
+    ] + token_accounts
+    release_ix = TransactionInstruction(data=(2).to_bytes(1, byteorder='little'), keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(release_ix), beneficiary)
+    client.confirm_transaction(tx["result"])
+
+def cancel(beneficiary: Keypair, depositor: Keypair, id: int, token_accounts: list = []):
+    print(f"\nCancelling lock {id}, signed by both parties")
+    keys = [
+        AccountMeta(pubkey=beneficiary.public_key, is_signer=True, is_writable=False),
+        AccountMeta(pubkey=depositor.public_key, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=get_lock_address(depositor.public_key, beneficiary.public_key, id), is_signer=False, is_writable=True),
+    ] + token_accounts
+    cancel_ix = TransactionInstruction(data=(3).to_bytes(1, byteorder='little'), keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(cancel_ix), depositor, beneficiary)
+    client.confirm_transaction(tx["result"])
+
+def token_keys(vault: PublicKey, destination: PublicKey) -> list:
+    return [
+        AccountMeta(pubkey=vault, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+        AccountMeta(pubkey=TOKEN_PROGRAM_ID, is_signer=False, is_writable=False),
+    ]
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    beneficiary = setup_user_keypair()
+
+    # lamports, released after the lock expired
+    release_ts = int(time.time()) + 10
+    lock_lamports(payer, beneficiary.public_key, 0, LAMPORTS_PER_SOL // 2, release_ts, cancellable=False)
+    try:
+        release(beneficiary, payer.public_key, 0)
+    except Exception as e:
+        print(f"Releasing too early fails as expected: {e}")
+    print("Waiting for the lock to expire...")
+    time.sleep(15)
+    release(beneficiary, payer.public_key, 0)
+    print(f"Beneficiary balance: {client.get_balance(beneficiary.public_key)['result']['value']} lamports")
+
+    # tokens, cancelled early by both parties
+    token = create_token_program(payer, decimals=0)
+    depositor_account = create_token_account(token, payer.public_key)
+    mint_token(token, payer, depositor_account, 1000)
+    vault = lock_tokens(payer, beneficiary.public_key, token, depositor_account, 1, 400, int(time.time()) + 3600, cancellable=True)
+    print(f"Depositor token balance: {token_balance(token, depositor_account)}")
+    cancel(beneficiary, payer, 1, token_keys(vault, depositor_account))
+    print(f"Depositor token balance after cancel: {token_balance(token, depositor_account)}")
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum TimelockError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Lock Address")]
+    InvalidLockAddress,
+    #[error("Invalid Vault")]
+    InvalidVault,
+    #[error("Release Time In The Past")]
+    ReleaseTimeInPast,
+    #[error("Still Locked")]
+    StillLocked,
+    #[error("Not Cancellable")]
+    NotCancellable,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<TimelockError> for ProgramError {
+    fn from(e: TimelockError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,116 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::TimelockError::InvalidInstruction;
+
+pub enum TimelockInstruction {
+
+    // Locks lamports in a PDA derived from ["timelock", depositor, beneficiary, id]
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The depositor, pays for the lock account and the locked lamports
+    // 1. `[]` The beneficiary
+    // 2. `[writable]` The lock PDA
+    // 3. `[]` The system program
+    LockLamports {
+        id: u64,
+        amount: u64,
+        release_ts: i64,
+        cancellable: bool,
+    },
+
+    // Locks SPL tokens. The vault has to be created prior to this instruction and owned by the lock PDA.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The depositor, pays for the lock account
+    // 1. `[]` The beneficiary
+    // 2. `[writable]` The lock PDA
+    // 3. `[writable]` The depositor's token account to take the tokens from
+    // 4. `[writable]` The vault, a token account owned by the lock PDA
+    // 5. `[]` The system program
+    // 6. `[]` The token program
+    LockTokens {
+        id: u64,
+        amount: u64,
+        release_ts: i64,
+        cancellable: bool,
+    },
+
+    // Pays the locked funds out to the beneficiary once the release time has passed.
+    // The rent of the lock account (and vault) goes back to the depositor.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The beneficiary
+    // 1. `[writable]` The depositor
+    // 2. `[writable]` The lock PDA
+    //
+    // Additionally for token locks:
+    // 3. `[writable]` The vault
+    // 4. `[writable]` The beneficiary's token account
+    // 5. `[]` The token program
+    Release,
+
+    // Returns the locked funds to the depositor before the release time. Only possible if the lock
+    // was created as cancellable and both parties sign.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The beneficiary
+    // 1. `[signer, writable]` The depositor
+    // 2. `[writable]` The lock PDA
+    //
+    // Additionally for token locks:
+    // 3. `[writable]` The vault
+    // 4. `[writable]` The depositor's token account
+    // 5. `[]` The token program
+    Cancel,
+}
+
+impl TimelockInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => {
+                let (id, amount, release_ts, cancellable) = Self::unpack_lock(rest)?;
+                Self::LockLamports { id, amount, release_ts, cancellable }
+            },
+            1 => {
+                let (id, amount, release_ts, cancellable) = Self::unpack_lock(rest)?;
+                Self::LockTokens { id, amount, release_ts, cancellable }
+            },
+            2 => Self::Release,
+            3 => Self::Cancel,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    // id (8 bytes), amount (8 bytes), release timestamp (8 bytes) and the cancellable flag (1 byte)
+    fn unpack_lock(input: &[u8]) -> Result<(u64, u64, i64, bool), ProgramError> {
+        let id = Self::unpack_u64(input, 0)?;
+        let amount = Self::unpack_u64(input, 8)?;
+        let release_ts = Self::unpack_u64(input, 16)? as i64;
+        let cancellable = match input.get(24) {
+            Some(0) => false,
+            Some(1) => true,
+            _ => return Err(InvalidInstruction.into()),
+        };
+        Ok((id, amount, release_ts, cancellable))
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,346 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token::state::Account as TokenAccount;
+
+use crate::{
+    instruction::TimelockInstruction,
+    error::TimelockError,
+    state::{Asset, Timelock},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = TimelockInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            TimelockInstruction::LockLamports { id, amount, release_ts, cancellable } => {
+                msg!("Instruction: LockLamports");
+                Self::process_lock(accounts, id, amount, release_ts, cancellable, false, program_id)
+            },
+            TimelockInstruction::LockTokens { id, amount, release_ts, cancellable } => {
+                msg!("Instruction: LockTokens");
+                Self::process_lock(accounts, id, amount, release_ts, cancellable, true, program_id)
+            },
+            TimelockInstruction::Release => {
+                msg!("Instruction: Release");
+                Self::process_release(accounts, program_id)
+            },
+            TimelockInstruction::Cancel => {
+                msg!("Instruction: Cancel");
+                Self::process_cancel(accounts, program_id)
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_lock(
+        accounts: &[AccountInfo],
+        id: u64,
+        amount: u64,
+        release_ts: i64,
+        cancellable: bool,
+        tokens: bool,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if release_ts <= Clock::get()?.unix_timestamp {
+            return Err(TimelockError::ReleaseTimeInPast.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let depositor = next_account_info(account_info_iter)?;
+
+        if !depositor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let beneficiary = next_account_info(account_info_iter)?;
+        let lock_account = next_account_info(account_info_iter)?;
+
+        let id_bytes = id.to_le_bytes();
+        let (lock_pda, bump_seed) = Pubkey::find_program_address(
+            &[b"timelock", depositor.key.as_ref(), beneficiary.key.as_ref(), &id_bytes],
+            program_id,
+        );
+        if lock_pda != *lock_account.key {
+            return Err(TimelockError::InvalidLockAddress.into());
+        }
+
+        if !lock_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let rent_lamports = Rent::get()?.minimum_balance(Timelock::LEN);
+        let signer_seeds: &[&[u8]] = &[
+            b"timelock",
+            depositor.key.as_ref(),
+            beneficiary.key.as_ref(),
+            &id_bytes,
+            &[bump_seed],
+        ];
+
+        let asset = if tokens {
+            let depositor_token_account = next_account_info(account_info_iter)?;
+            let vault = next_account_info(account_info_iter)?;
+            let system_program = next_account_info(account_info_iter)?;
+            let token_program = next_account_info(account_info_iter)?;
+            if *token_program.key != spl_token::id() {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+
+            let vault_info = TokenAccount::unpack(&vault.try_borrow_data()?)?;
+            if vault_info.owner != lock_pda {
+                return Err(TimelockError::InvalidVault.into());
+            }
+
+            msg!("Creating lock account...");
+            Self::create_lock_account(depositor, lock_account, system_program, rent_lamports, program_id, signer_seeds)?;
+
+            let transfer_to_vault_ix = spl_token::instruction::transfer(
+                token_program.key,
+                depositor_token_account.key,
+                vault.key,
+                depositor.key,
+                &[depositor.key],
+                amount,
+            )?;
+            msg!("Calling the token program to transfer tokens into the vault...");
+            invoke(
+                &transfer_to_vault_ix,
+                &[
+                    depositor_token_account.clone(),
+                    vault.clone(),
+                    depositor.clone(),
+                    token_program.clone(),
+                ],
+            )?;
+
+            Asset::Tokens {
+                mint: vault_info.mint,
+                vault: *vault.key,
+            }
+        } else {
+            let system_program = next_account_info(account_info_iter)?;
+
+            // the locked lamports are simply added on top of the rent when the account is created
+            let lamports = rent_lamports
+                .checked_add(amount)
+                .ok_or(TimelockError::AmountOverflow)?;
+            msg!("Creating lock account holding {} lamports...", amount);
+            Self::create_lock_account(depositor, lock_account, system_program, lamports, program_id, signer_seeds)?;
+
+            Asset::Lamports
+        };
+
+        let lock_info = Timelock {
+            is_initialized: true,
+            depositor: *depositor.key,
+            beneficiary: *beneficiary.key,
+            id,
+            asset,
+            amount,
+            release_ts,
+            cancellable,
+            bump_seed,
+        };
+        lock_info.serialize(&mut &mut lock_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_release(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let beneficiary = next_account_info(account_info_iter)?;
+
+        if !beneficiary.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let depositor = next_account_info(account_info_iter)?;
+        let lock_account = next_account_info(account_info_iter)?;
+        let lock_info = Self::unpack_lock(lock_account, depositor, beneficiary, program_id)?;
+
+        if Clock::get()?.unix_timestamp < lock_info.release_ts {
+            return Err(TimelockError::StillLocked.into());
+        }
+
+        msg!("Releasing {} to the beneficiary...", lock_info.amount);
+        Self::pay_out(&lock_info, account_info_iter, lock_account, beneficiary, depositor)
+    }
+
+    fn process_cancel(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let beneficiary = next_account_info(account_info_iter)?;
+        let depositor = next_account_info(account_info_iter)?;
+
+        if !beneficiary.is_signer || !depositor.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let lock_account = next_account_info(account_info_iter)?;
+        let lock_info = Self::unpack_lock(lock_account, depositor, beneficiary, program_id)?;
+
+        if !lock_info.cancellable {
+            return Err(TimelockError::NotCancellable.into());
+        }
+
+        msg!("Returning {} to the depositor...", lock_info.amount);
+        Self::pay_out(&lock_info, account_info_iter, lock_account, depositor, depositor)
+    }
+
+    fn unpack_lock(
+        lock_account: &AccountInfo,
+        depositor: &AccountInfo,
+        beneficiary: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<Timelock, ProgramError> {
+        if lock_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // a lamport lock is 64 bytes shorter than the token lock the account is sized for
+        let lock_info = Timelock::deserialize(&mut &lock_account.data.borrow()[..])?;
+        if !lock_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if lock_info.depositor != *depositor.key || lock_info.beneficiary != *beneficiary.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(lock_info)
+    }
+
+    // Sends the locked funds to the recipient and closes the lock, its rent always goes back to the depositor.
+    // Token locks expect the vault, the recipient's token account and the token program as remaining accounts.
+    fn pay_out<'a, 'b>(
+        lock_info: &Timelock,
+        account_info_iter: &mut std::slice::Iter<'b, AccountInfo<'a>>,
+        lock_account: &AccountInfo<'a>,
+        recipient: &AccountInfo<'a>,
+        depositor: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        match lock_info.asset {
+            Asset::Lamports => {
+                **lock_account.lamports.borrow_mut() -= lock_info.amount;
+                **recipient.lamports.borrow_mut() = recipient.lamports()
+                    .checked_add(lock_info.amount)
+                    .ok_or(TimelockError::AmountOverflow)?;
+            },
+            Asset::Tokens { vault: vault_key, .. } => {
+                let vault = next_account_info(account_info_iter)?;
+                if *vault.key != vault_key {
+                    return Err(TimelockError::InvalidVault.into());
+                }
+                let recipient_token_account = next_account_info(account_info_iter)?;
+                let token_program = next_account_info(account_info_iter)?;
+                if *token_program.key != spl_token::id() {
+                    return Err(ProgramError::IncorrectProgramId);
+                }
+
+                let id_bytes = lock_info.id.to_le_bytes();
+                let signer_seeds: &[&[u8]] = &[
+                    b"timelock",
+                    lock_info.depositor.as_ref(),
+                    lock_info.beneficiary.as_ref(),
+                    &id_bytes,
+                    &[lock_info.bump_seed],
+                ];
+
+                let transfer_ix = spl_token::instruction::transfer(
+                    token_program.key,
+                    vault.key,
+                    recipient_token_account.key,
+                    lock_account.key,
+                    &[lock_account.key],
+                    lock_info.amount,
+                )?;
+                msg!("Calling the token program to transfer tokens out of the vault...");
+                invoke_signed(
+                    &transfer_ix,
+                    &[
+                        vault.clone(),
+                        recipient_token_account.clone(),
+                        lock_account.clone(),
+                        token_program.clone(),
+                    ],
+                    &[signer_seeds],
+                )?;
+
+                let close_vault_ix = spl_token::instruction::close_account(
+                    token_program.key,
+                    vault.key,
+                    depositor.key,
+                    lock_account.key,
+                    &[lock_account.key],
+                )?;
+                msg!("Calling the token program to close the vault...");
+                invoke_signed(
+                    &close_vault_ix,
+                    &[
+                        vault.clone(),
+                        depositor.clone(),
+                        lock_account.clone(),
+                        token_program.clone(),
+                    ],
+                    &[signer_seeds],
+                )?;
+            }
+        }
+
+        msg!("Closing the lock account...");
+        **depositor.lamports.borrow_mut() = depositor.lamports()
+            .checked_add(lock_account.lamports())
+            .ok_or(TimelockError::AmountOverflow)?;
+        **lock_account.lamports.borrow_mut() = 0;
+        lock_account.data.borrow_mut().fill(0);
+
+        Ok(())
+    }
+
+    fn create_lock_account<'a>(
+        depositor: &AccountInfo<'a>,
+        lock_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+        lamports: u64,
+        program_id: &Pubkey,
+        signer_seeds: &[&[u8]],
+    ) -> ProgramResult {
+        let create_account_ix = system_instruction::create_account(
+            depositor.key,
+            lock_account.key,
+            lamports,
+            Timelock::LEN as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &create_account_ix,
+            &[
+                depositor.clone(),
+                lock_account.clone(),
+                system_program.clone(),
+            ],
+            &[signer_seeds],
+        )
+    }
+}
@@ -0,0 +1,34 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Asset {
+    // the locked lamports are held by the lock account itself, on top of its rent
+    Lamports,
+    // the locked tokens are held by a token account owned by the lock PDA
+    Tokens {
+        mint: Pubkey,
+        vault: Pubkey,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Timelock {
+    pub is_initialized: bool,
+    pub depositor: Pubkey,
+    pub beneficiary: Pubkey,
+    // lets a depositor keep several locks for the same beneficiary
+    pub id: u64,
+    pub asset: Asset,
+    pub amount: u64,
+    // unix timestamp, the beneficiary can release the funds from then on
+    pub release_ts: i64,
+    // whether depositor and beneficiary together may cancel before the release time
+    pub cancellable: bool,
+    pub bump_seed: u8,
+}
+
+impl Timelock {
+    // the account is always sized for the biggest variant of Asset
+    pub const LEN: usize = 1 + 32 + 32 + 8 + (1 + 32 + 32) + 8 + 8 + 1 + 1;
+}
@@ -0,0 +1,229 @@
+import yaml
+import sys
+import json
+import base64
+import borsh
+
+from os import path
+from borsh import types
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+JAR_SCHEMA = borsh.schema({
+    'is_initialized': types.u8,
+    'owner': types.fixed_array(types.u8, 32),
+    'bump_seed': types.u8,
+    'total_tipped': types.u64,
+    'tip_count': types.u64
+})
+
+TIP_EVENT_SCHEMA = borsh.schema({
+    'jar': types.fixed_array(types.u8, 32),
+    'tipper': types.fixed_array(types.u8, 32),
+    'amount': types.u64,
+    'note': types.string
+})
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'tipjar.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'tipjar-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def get_jar_address(owner: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("tipjar", encoding="utf8"), bytes(owner)], program_id)[0]
+
+def init_jar(owner: Keypair) -> PublicKey:
+    jar = get_jar_address(owner.public_key)
+    if client.get_account_info(jar)["result"]["value"] is not None:
+        print(f"Jar {jar} already exists")
+        return jar
+
+    print(f"\nCreating jar {jar}")
+    init_ix = TransactionInstruction(
+        data=bytes(1),
+        keys=[
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=jar, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(init_ix), owner)
+    client.confirm_transaction(tx["result"])
+    return jar
+
+def tip(tipper: Keypair, jar: PublicKey, amount: int, note: str = ""):
+    print(f"\n{tipper.public_key} tips {amount} lamports")
+    tip_ix = TransactionInstruction(
+        data=(1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little') + bytes(note, encoding="utf8"),
+        keys=[
+            AccountMeta(pubkey=tipper.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=jar, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(tip_ix), tipper)
+    client.confirm_transaction(tx["result"])
+    print_tip_events(tx["result"])
+
+def print_tip_events(signature: str):
+    # sol_log_data shows up as "Program data: <base64 field> <base64 field>" in the logs
+    logs = client.get_transaction(signature)["result"]["meta"]["logMessages"]
+    for log in logs:
+        if not log.startswith("Program data: "):
+            continue
+        fields = [base64.b64decode(field) for field in log[len("Program data: "):].split(" ")]
+        if fields[0] != b"tip":
+            continue
+        event = borsh.deserialize(TIP_EVENT_SCHEMA, fields[1])
+        print(f"Tip event: {PublicKey(event['tipper'])} tipped {event['amount']} lamports, note: '{event['note']}'")
+
+def withdraw(owner: Keypair, jar: PublicKey, amount: int = 0):
+    print(f"\nWithdrawing {amount if amount else 'everything'} from the jar")
+    withdraw_ix = TransactionInstruction(
+        data=(2).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=jar, is_signer=False, is_writable=True),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(withdraw_ix), owner)
+    client.confirm_transaction(tx["result"])
+
+def show_jar(jar: PublicKey):
+    account_info = client.get_account_info(jar)["result"]["value"]
+    jar_info = borsh.deserialize(JAR_SCHEMA, base64.b64decode(account_info["data"][0]))
+    print(f"Jar holds {account_info['lamports']} lamports, {jar_info['tip_count']} tips with {jar_info['total_tipped']} lamports in total")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    jar = init_jar(payer)
+    fan = setup_user_keypair()
+    tip(fan, jar, LAMPORTS_PER_SOL // 10, "Thanks for the tutorials!")
+    tip(fan, jar, LAMPORTS_PER_SOL // 20)
+    show_jar(jar)
+    withdraw(payer, jar)
+    show_jar(jar)
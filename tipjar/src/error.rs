@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum TipjarError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Jar Address")]
+    InvalidJarAddress,
+    #[error("Note Too Long")]
+    NoteTooLong,
+    #[error("Insufficient Funds")]
+    InsufficientFunds,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<TipjarError> for ProgramError {
+    fn from(e: TipjarError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
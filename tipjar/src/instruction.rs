@@ -0,0 +1,72 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::TipjarError::InvalidInstruction;
+
+pub enum TipjarInstruction {
+
+    // Creates a jar in a PDA derived from ["tipjar", owner]
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the jar, pays for the jar account
+    // 1. `[writable]` The jar PDA
+    // 2. `[]` The system program
+    InitJar,
+
+    // Tips the jar. The lamports are moved by the system program, since they come from a system account.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The tipper
+    // 1. `[writable]` The jar PDA
+    // 2. `[]` The system program
+    Tip {
+        amount: u64,
+        // optional note, everything after the amount, utf-8 encoded
+        note: String,
+    },
+
+    // Withdraws tips from the jar. The jar always keeps enough lamports to stay rent exempt.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the jar
+    // 1. `[writable]` The jar PDA
+    Withdraw {
+        // 0 withdraws everything above the rent exempt minimum
+        amount: u64,
+    },
+}
+
+impl TipjarInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitJar,
+            1 => Self::Tip {
+                amount: Self::unpack_amount(rest)?,
+                note: std::str::from_utf8(&rest[8..])
+                    .map_err(|_| InvalidInstruction)?
+                    .to_string(),
+            },
+            2 => Self::Withdraw {
+                amount: Self::unpack_amount(rest)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_amount(input: &[u8]) -> Result<u64, ProgramError> {
+        let amount = input
+            .get(..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(amount)
+    }
+}
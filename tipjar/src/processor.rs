@@ -0,0 +1,194 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    log::sol_log_data,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::TipjarInstruction,
+    error::TipjarError,
+    state::{Jar, TipEvent, MAX_NOTE_LEN},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = TipjarInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            TipjarInstruction::InitJar => {
+                msg!("Instruction: InitJar");
+                Self::process_init_jar(accounts, program_id)
+            },
+            TipjarInstruction::Tip { amount, note } => {
+                msg!("Instruction: Tip");
+                Self::process_tip(accounts, amount, note, program_id)
+            },
+            TipjarInstruction::Withdraw { amount } => {
+                msg!("Instruction: Withdraw");
+                Self::process_withdraw(accounts, amount, program_id)
+            }
+        }
+    }
+
+    fn process_init_jar(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let jar_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (jar_pda, bump_seed) = Pubkey::find_program_address(&[b"tipjar", owner.key.as_ref()], program_id);
+        if jar_pda != *jar_account.key {
+            return Err(TipjarError::InvalidJarAddress.into());
+        }
+
+        if !jar_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        msg!("Creating jar account...");
+        let create_account_ix = system_instruction::create_account(
+            owner.key,
+            jar_account.key,
+            Rent::get()?.minimum_balance(Jar::LEN),
+            Jar::LEN as u64,
+            program_id,
+        );
+        invoke_signed(
+            &create_account_ix,
+            &[
+                owner.clone(),
+                jar_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"tipjar", owner.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        let jar_info = Jar {
+            is_initialized: true,
+            owner: *owner.key,
+            bump_seed,
+            total_tipped: 0,
+            tip_count: 0,
+        };
+        jar_info.serialize(&mut &mut jar_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_tip(
+        accounts: &[AccountInfo],
+        amount: u64,
+        note: String,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if note.len() > MAX_NOTE_LEN {
+            return Err(TipjarError::NoteTooLong.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let tipper = next_account_info(account_info_iter)?;
+
+        if !tipper.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let jar_account = next_account_info(account_info_iter)?;
+        if jar_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let mut jar_info = Jar::try_from_slice(&jar_account.data.borrow())?;
+        if !jar_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        let system_program = next_account_info(account_info_iter)?;
+
+        // the tipper is owned by the system program, so only the system program can take lamports from it
+        msg!("Calling the system program to transfer the tip...");
+        invoke(
+            &system_instruction::transfer(tipper.key, jar_account.key, amount),
+            &[
+                tipper.clone(),
+                jar_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        jar_info.total_tipped = jar_info.total_tipped
+            .checked_add(amount)
+            .ok_or(TipjarError::AmountOverflow)?;
+        jar_info.tip_count = jar_info.tip_count
+            .checked_add(1)
+            .ok_or(TipjarError::AmountOverflow)?;
+        jar_info.serialize(&mut &mut jar_account.data.borrow_mut()[..])?;
+
+        msg!("{} tipped {} lamports: {}", tipper.key, amount, note);
+        let event = TipEvent {
+            jar: *jar_account.key,
+            tipper: *tipper.key,
+            amount,
+            note,
+        };
+        sol_log_data(&[b"tip", &event.try_to_vec()?]);
+
+        Ok(())
+    }
+
+    fn process_withdraw(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let jar_account = next_account_info(account_info_iter)?;
+        if jar_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let jar_info = Jar::try_from_slice(&jar_account.data.borrow())?;
+        if jar_info.owner != *owner.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let available = jar_account.lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(jar_account.data_len()));
+        let amount = if amount == 0 { available } else { amount };
+        if amount > available {
+            return Err(TipjarError::InsufficientFunds.into());
+        }
+
+        // the jar is owned by this program and holds data, so the program moves the lamports itself
+        msg!("Withdrawing {} lamports...", amount);
+        **jar_account.lamports.borrow_mut() -= amount;
+        **owner.lamports.borrow_mut() = owner.lamports()
+            .checked_add(amount)
+            .ok_or(TipjarError::AmountOverflow)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Longest note a tipper can attach to a tip, in bytes
+pub const MAX_NOTE_LEN: usize = 64;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Jar {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    pub bump_seed: u8,
+    // lamports tipped over the lifetime of the jar, withdrawals don't reduce it
+    pub total_tipped: u64,
+    pub tip_count: u64,
+}
+
+impl Jar {
+    pub const LEN: usize = 1 + 32 + 1 + 8 + 8;
+}
+
+// Emitted with sol_log_data for every tip, so clients can pick tips up from the transaction logs
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct TipEvent {
+    pub jar: Pubkey,
+    pub tipper: Pubkey,
+    pub amount: u64,
+    pub note: String,
+}
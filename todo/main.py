@@ -0,0 +1,190 @@
+import yaml
+import sys
+import json
+import base64
+import struct
+
+from os import path
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'todo.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'todo-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def get_list_address(owner: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("todo", encoding="utf8"), bytes(owner)], program_id)[0]
+
+def send(owner: Keypair, data: bytes, with_system_program: bool = True):
+    keys = [
+        AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=True),
+        AccountMeta(pubkey=get_list_address(owner.public_key), is_signer=False, is_writable=True),
+    ]
+    if with_system_program:
+        keys.append(AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False))
+    ix = TransactionInstruction(data=data, keys=keys, program_id=program_id)
+    tx = client.send_transaction(Transaction().add(ix), owner)
+    client.confirm_transaction(tx["result"])
+
+def init_list(owner: Keypair):
+    if client.get_account_info(get_list_address(owner.public_key))["result"]["value"] is not None:
+        print("Todo list already exists")
+        return
+    print("\nCreating todo list")
+    send(owner, bytes(1))
+
+def create_item(owner: Keypair, description: str):
+    print(f"\nCreating item '{description}'")
+    send(owner, (1).to_bytes(1, byteorder='little') + bytes(description, encoding="utf8"))
+
+def complete_item(owner: Keypair, id: int):
+    print(f"\nCompleting item {id}")
+    send(owner, (2).to_bytes(1, byteorder='little') + id.to_bytes(4, byteorder='little'), with_system_program=False)
+
+def remove_item(owner: Keypair, id: int):
+    print(f"\nRemoving item {id}")
+    send(owner, (3).to_bytes(1, byteorder='little') + id.to_bytes(4, byteorder='little'))
+
+def decode_list(data: bytes) -> dict:
+    # is_initialized (1) + owner (32) + bump_seed (1) + next_id (4) + number of items (4)
+    is_initialized, owner, bump_seed, next_id, count = struct.unpack_from("<?32sBII", data, 0)
+    offset = 1 + 32 + 1 + 4 + 4
+    items = []
+    for _ in range(count):
+        id, completed, length = struct.unpack_from("<I?I", data, offset)
+        offset += 4 + 1 + 4
+        items.append({'id': id, 'completed': completed, 'description': data[offset:offset + length].decode('utf-8')})
+        offset += length
+    return {'owner': PublicKey(owner), 'next_id': next_id, 'items': items}
+
+def show_list(owner: PublicKey):
+    account_info = client.get_account_info(get_list_address(owner))["result"]["value"]
+    data = base64.b64decode(account_info["data"][0])
+    todo_list = decode_list(data)
+    print(f"Todo list of {todo_list['owner']} ({len(data)} bytes, {account_info['lamports']} lamports):")
+    for item in todo_list['items']:
+        print(f"  [{'x' if item['completed'] else ' '}] {item['id']}: {item['description']}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    init_list(payer)
+    create_item(payer, "Learn how accounts work")
+    create_item(payer, "Write a todo program")
+    create_item(payer, "Deploy it")
+    show_list(payer.public_key)
+    complete_item(payer, 0)
+    remove_item(payer, 1)
+    show_list(payer.public_key)
@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum TodoError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid List Address")]
+    InvalidListAddress,
+    #[error("List Is Full")]
+    ListFull,
+    #[error("Description Too Long")]
+    DescriptionTooLong,
+    #[error("Item Not Found")]
+    ItemNotFound,
+    #[error("Item Already Completed")]
+    AlreadyCompleted,
+}
+
+impl From<TodoError> for ProgramError {
+    fn from(e: TodoError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,84 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::TodoError::InvalidInstruction;
+
+pub enum TodoInstruction {
+
+    // Creates an empty todo list in a PDA derived from ["todo", owner]
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the list, pays for the list account
+    // 1. `[writable]` The list PDA
+    // 2. `[]` The system program
+    InitList,
+
+    // Appends a new item to the list, the list account grows to fit it
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the list, pays for the additional space
+    // 1. `[writable]` The list PDA
+    // 2. `[]` The system program
+    CreateItem {
+        // everything after the tag byte, utf-8 encoded
+        description: String,
+    },
+
+    // Marks an item as completed
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner of the list
+    // 1. `[writable]` The list PDA
+    CompleteItem {
+        id: u32,
+    },
+
+    // Removes an item from the list, the list account shrinks and the freed rent goes back to the owner
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the list
+    // 1. `[writable]` The list PDA
+    // 2. `[]` The system program
+    RemoveItem {
+        id: u32,
+    },
+}
+
+impl TodoInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::InitList,
+            1 => Self::CreateItem {
+                description: std::str::from_utf8(rest)
+                    .map_err(|_| InvalidInstruction)?
+                    .to_string(),
+            },
+            2 => Self::CompleteItem {
+                id: Self::unpack_id(rest)?,
+            },
+            3 => Self::RemoveItem {
+                id: Self::unpack_id(rest)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_id(input: &[u8]) -> Result<u32, ProgramError> {
+        let id = input
+            .get(..4)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u32::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(id)
+    }
+}
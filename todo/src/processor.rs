@@ -0,0 +1,243 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{
+    instruction::TodoInstruction,
+    error::TodoError,
+    state::{TodoItem, TodoList, MAX_DESCRIPTION_LEN, MAX_ITEMS},
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = TodoInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            TodoInstruction::InitList => {
+                msg!("Instruction: InitList");
+                Self::process_init_list(accounts, program_id)
+            },
+            TodoInstruction::CreateItem { description } => {
+                msg!("Instruction: CreateItem");
+                Self::process_create_item(accounts, description, program_id)
+            },
+            TodoInstruction::CompleteItem { id } => {
+                msg!("Instruction: CompleteItem {}", id);
+                Self::process_complete_item(accounts, id, program_id)
+            },
+            TodoInstruction::RemoveItem { id } => {
+                msg!("Instruction: RemoveItem {}", id);
+                Self::process_remove_item(accounts, id, program_id)
+            }
+        }
+    }
+
+    fn process_init_list(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let list_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let (list_pda, bump_seed) = Pubkey::find_program_address(&[b"todo", owner.key.as_ref()], program_id);
+        if list_pda != *list_account.key {
+            return Err(TodoError::InvalidListAddress.into());
+        }
+
+        if !list_account.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let list_info = TodoList {
+            is_initialized: true,
+            owner: *owner.key,
+            bump_seed,
+            next_id: 0,
+            items: Vec::new(),
+        };
+        let data = list_info.try_to_vec()?;
+
+        msg!("Creating todo list...");
+        let create_account_ix = system_instruction::create_account(
+            owner.key,
+            list_account.key,
+            Rent::get()?.minimum_balance(data.len()),
+            data.len() as u64,
+            program_id,
+        );
+        invoke_signed(
+            &create_account_ix,
+            &[
+                owner.clone(),
+                list_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"todo", owner.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        list_account.data.borrow_mut().copy_from_slice(&data);
+
+        Ok(())
+    }
+
+    fn process_create_item(
+        accounts: &[AccountInfo],
+        description: String,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        if description.len() > MAX_DESCRIPTION_LEN {
+            return Err(TodoError::DescriptionTooLong.into());
+        }
+
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let list_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let mut list_info = Self::unpack_list(owner, list_account, program_id)?;
+
+        if list_info.items.len() >= MAX_ITEMS {
+            return Err(TodoError::ListFull.into());
+        }
+
+        list_info.items.push(TodoItem {
+            id: list_info.next_id,
+            completed: false,
+            description,
+        });
+        list_info.next_id = list_info.next_id
+            .checked_add(1)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        Self::store_list(&list_info, owner, list_account, system_program)
+    }
+
+    fn process_complete_item(
+        accounts: &[AccountInfo],
+        id: u32,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let list_account = next_account_info(account_info_iter)?;
+
+        let mut list_info = Self::unpack_list(owner, list_account, program_id)?;
+
+        let item = list_info.items
+            .iter_mut()
+            .find(|item| item.id == id)
+            .ok_or(TodoError::ItemNotFound)?;
+
+        if item.completed {
+            return Err(TodoError::AlreadyCompleted.into());
+        }
+        item.completed = true;
+
+        // completing an item doesn't change the size of the list, so the data can be overwritten in place
+        list_info.serialize(&mut &mut list_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_remove_item(
+        accounts: &[AccountInfo],
+        id: u32,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+        let list_account = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+
+        let mut list_info = Self::unpack_list(owner, list_account, program_id)?;
+
+        let position = list_info.items
+            .iter()
+            .position(|item| item.id == id)
+            .ok_or(TodoError::ItemNotFound)?;
+        list_info.items.remove(position);
+
+        Self::store_list(&list_info, owner, list_account, system_program)
+    }
+
+    fn unpack_list(
+        owner: &AccountInfo,
+        list_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<TodoList, ProgramError> {
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if list_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let list_info = TodoList::try_from_slice(&list_account.data.borrow())?;
+        if !list_info.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        if list_info.owner != *owner.key {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(list_info)
+    }
+
+    // Writes the list back and resizes the account to the serialized length first. A bigger account needs
+    // more lamports to stay rent exempt, which the owner pays through the system program. A smaller one
+    // gives the surplus back to the owner.
+    fn store_list<'a>(
+        list_info: &TodoList,
+        owner: &AccountInfo<'a>,
+        list_account: &AccountInfo<'a>,
+        system_program: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let data = list_info.try_to_vec()?;
+
+        let required_lamports = Rent::get()?.minimum_balance(data.len());
+        let current_lamports = list_account.lamports();
+
+        if required_lamports > current_lamports {
+            invoke(
+                &system_instruction::transfer(owner.key, list_account.key, required_lamports - current_lamports),
+                &[
+                    owner.clone(),
+                    list_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        } else if current_lamports > required_lamports {
+            let surplus = current_lamports - required_lamports;
+            **list_account.lamports.borrow_mut() = required_lamports;
+            **owner.lamports.borrow_mut() = owner.lamports()
+                .checked_add(surplus)
+                .ok_or(ProgramError::InvalidAccountData)?;
+        }
+
+        msg!("Resizing list from {} to {} bytes...", list_account.data_len(), data.len());
+        list_account.realloc(data.len(), false)?;
+        list_account.data.borrow_mut().copy_from_slice(&data);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Most items a single list can hold
+pub const MAX_ITEMS: usize = 20;
+// Longest description of an item, in bytes
+pub const MAX_DESCRIPTION_LEN: usize = 100;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct TodoItem {
+    pub id: u32,
+    pub completed: bool,
+    pub description: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct TodoList {
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    pub bump_seed: u8,
+    // ids are never reused, even after an item has been removed
+    pub next_id: u32,
+    pub items: Vec<TodoItem>,
+}
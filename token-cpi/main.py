@@ -0,0 +1,315 @@
+import yaml
+import sys
+import json
+import base64
+
+from os import path
+from solana.rpc.api import Client
+from solana.keypair import Keypair
+from solana.publickey import PublicKey
+from solana.sysvar import SYSVAR_RENT_PUBKEY
+from solana.transaction import Transaction, TransactionInstruction, AccountMeta
+from solana.system_program import SYS_PROGRAM_ID
+
+from spl.token.client import Token
+from spl.token.constants import TOKEN_PROGRAM_ID
+
+
+client = None
+payer = None
+program_id = None
+
+LAMPORTS_PER_SOL = 1000_000_000
+
+TOKEN_2022_PROGRAM_ID = PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb")
+ASSOCIATED_TOKEN_PROGRAM_ID = PublicKey("ATokenGPvbdQxr7K2Ld5dT7U5RnfHeCbe8r2xPTzgqoA")
+
+PROGRAM_PATH = path.normpath(path.join(path.dirname(__file__), 'target/deploy'))
+PROGRAM_SO_PATH = path.normpath(path.join(PROGRAM_PATH, 'token_cpi.so'))
+PROGRAM_KEYPAIR_PATH = path.normpath(path.join(PROGRAM_PATH, 'token_cpi-keypair.json'))
+
+
+def getConfig():
+    config_path = path.normpath(path.join(path.expanduser('~'), '.config', 'solana', 'cli', 'config.yml'))
+    with open(config_path, 'r') as f:
+        try:
+            config = yaml.load(f, Loader=yaml.FullLoader)
+        except FileNotFoundError as fnfe:
+            print(fnfe)
+            print('Please setup solana-cli config first')
+            sys.exit(1)
+    return config
+
+def getRpcUrl():
+    config = getConfig()
+    if 'json_rpc_url' in config:
+        return config['json_rpc_url']
+    print("Failed to read RPC url from config file. Falling back to localhost")
+    return "http://127.0.0.1:8899"
+
+def getPayer():
+    """
+    Tries to read the secret key from the file referenced in the config file. If no file is found, a new keypair is generated.
+    """
+    config = getConfig()
+    if 'keypair_path' in config:
+        with open(config['keypair_path'], 'rb') as f:
+            # totally weird... We are reading the file as bytes, convert it to string, with utf-8 encoding,
+            # load it with json, which gives us a list. Then, we are going through the list, change the numbers of 
+            # byte and join all together to a byte string. Geez
+            keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+            return keypair
+    print('Failed to create keypair from cli, falling back to new random keypair')
+    keypair = Keypair()
+    return Keypair()
+
+
+def establishConnection():
+    """
+    Establishes a connection to the Solana cluster.
+    """
+    global client
+
+    rpc_url = getRpcUrl()
+    try:
+        client = Client(rpc_url)
+    except Exception as e:
+        print(e)
+        print("Failed to establish connection to RPC server")
+        sys.exit(1)
+
+def establishPayer():
+    global payer
+
+    fees = 0
+
+    # each signature in a transaction on Solana costs an addition 5000 lamports per 
+    feeCalculator = client.get_recent_blockhash()["result"]["value"]["feeCalculator"]
+
+    # if I understand this function correct, it returns the number of lamports which are required in order to
+    # be exempted to pay rent for storing data on-chain
+    # fees += client.get_minimum_balance_for_rent_exemption(GREETING_ACCOUNT_SIZE)["result"]
+
+    # calculate the cost of sending transaction - not sure yet why
+    fees += feeCalculator["lamportsPerSignature"] * 100
+
+    payer = getPayer()
+
+    balance = client.get_balance(payer.public_key)["result"]["value"]
+    print(f"Balance for {payer.public_key} is {balance} lamports")
+
+    if balance < fees:
+        print("Not enough balance on account. Going to request an airdrop")
+        tx = client.request_airdrop(payer.public_key, 2 * LAMPORTS_PER_SOL)
+
+        print('Waiting for transaction to be finalized...')
+        client.confirm_transaction(tx["result"])
+
+        balance = client.get_balance(payer.public_key)["result"]["value"]
+        print(f"Balance for {payer.public_key} is {balance} lamports")
+
+def checkProgram():
+    global program_id
+
+    # loading the keypair of the deployed program in order to get the program id
+    with open(PROGRAM_KEYPAIR_PATH, 'rb') as f:
+        keypair = Keypair.from_secret_key(b"".join(map(lambda x: x.to_bytes(1, "big"), json.loads(f.read().decode('utf-8')))))
+        program_id = keypair.public_key
+
+    if program_id is None:
+        print(f"Failed to read program keypair at {PROGRAM_KEYPAIR_PATH}. Program may need to be deployed first")
+        sys.exit(1)
+
+    print(f"Program id: {program_id}")
+    program_info = client.get_account_info(program_id)["result"]["value"]
+
+    if program_info is None:
+        print(f"Failed to get info for program {program_id}")
+        print("Has the program been deployed?")
+        sys.exit(1)
+
+def setup_user_keypair() -> Keypair:
+    keypair = Keypair()
+    print("Requesting airdrop for Keypair")
+    tx_hash = client.request_airdrop(keypair.public_key, LAMPORTS_PER_SOL * 2)
+    print("Airdrop request submitted. Waiting for confirmation...")
+    client.confirm_transaction(tx_hash['result'])
+    print("Airdrop for Keypair confirmed")
+    return keypair
+
+def get_authority_address(mint: PublicKey) -> PublicKey:
+    return PublicKey.find_program_address([bytes("authority", encoding="utf8"), bytes(mint)], program_id)[0]
+
+def create_mint(creator: Keypair, decimals: int, token_program: PublicKey = TOKEN_PROGRAM_ID, fee: tuple = None) -> Token:
+    mint = Keypair()
+    authority = get_authority_address(mint.public_key)
+    print(f"\nCreating mint {mint.public_key} of {token_program} with mint authority {authority}")
+    data = bytes(1) + decimals.to_bytes(1, byteorder='little')
+    if fee is not None:
+        # only Token-2022 mints can have a transfer fee: the basis points and the maximum fee
+        basis_points, maximum_fee = fee
+        data += basis_points.to_bytes(2, byteorder='little') + maximum_fee.to_bytes(8, byteorder='little')
+    create_mint_ix = TransactionInstruction(
+        data=data,
+        keys=[
+            AccountMeta(pubkey=creator.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=mint.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=authority, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=SYSVAR_RENT_PUBKEY, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=token_program, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    # the mint account is created with a CPI, but it still has to sign the transaction
+    tx = client.send_transaction(Transaction().add(create_mint_ix), creator, mint)
+    client.confirm_transaction(tx["result"])
+    return Token(client, mint.public_key, token_program, creator)
+
+def create_token_account(token: Token, owner: PublicKey) -> PublicKey:
+    # Token.create_account makes the account as big as a SPL Token account, a Token-2022 account of a mint with a
+    # transfer fee needs room for the withheld fee. The associated token account program knows the size of both.
+    account = PublicKey.find_program_address(
+        [bytes(owner), bytes(token.program_id), bytes(token.pubkey)],
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+    )[0]
+    create_ix = TransactionInstruction(
+        data=bytes(),
+        keys=[
+            AccountMeta(pubkey=payer.public_key, is_signer=True, is_writable=True),
+            AccountMeta(pubkey=account, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=owner, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=token.pubkey, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=SYS_PROGRAM_ID, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=token.program_id, is_signer=False, is_writable=False),
+        ],
+        program_id=ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    tx = client.send_transaction(Transaction().add(create_ix), payer)
+    client.confirm_transaction(tx["result"])
+    return account
+
+def mint_to(creator: Keypair, token: Token, destination: PublicKey, amount: int):
+    print(f"\nMinting {amount} tokens to {destination} through the program")
+    mint_to_ix = TransactionInstruction(
+        data=(1).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=creator.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=token.pubkey, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=get_authority_address(token.pubkey), is_signer=False, is_writable=False),
+            AccountMeta(pubkey=token.program_id, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(mint_to_ix), creator)
+    client.confirm_transaction(tx["result"])
+
+def transfer(owner: Keypair, token: Token, source: PublicKey, destination: PublicKey, amount: int):
+    print(f"\nTransferring {amount} tokens from {source} to {destination} through the program")
+    transfer_ix = TransactionInstruction(
+        data=(2).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=source, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=token.pubkey, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=destination, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=token.program_id, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(transfer_ix), owner)
+    client.confirm_transaction(tx["result"])
+
+def approve(owner: Keypair, token: Token, source: PublicKey, delegate: PublicKey, amount: int):
+    print(f"\nApproving {amount} tokens of {source} for {delegate} through the program")
+    approve_ix = TransactionInstruction(
+        data=(3).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=source, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=delegate, is_signer=False, is_writable=False),
+            AccountMeta(pubkey=token.program_id, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(approve_ix), owner)
+    client.confirm_transaction(tx["result"])
+
+def revoke(owner: Keypair, token: Token, source: PublicKey):
+    print(f"\nRevoking the delegate of {source} through the program")
+    revoke_ix = TransactionInstruction(
+        data=(4).to_bytes(1, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=owner.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=source, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=token.program_id, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(revoke_ix), owner)
+    client.confirm_transaction(tx["result"])
+
+def burn(authority: Keypair, token: Token, source: PublicKey, amount: int):
+    print(f"\nBurning {amount} tokens of {source} through the program")
+    burn_ix = TransactionInstruction(
+        data=(5).to_bytes(1, byteorder='little') + amount.to_bytes(8, byteorder='little'),
+        keys=[
+            AccountMeta(pubkey=authority.public_key, is_signer=True, is_writable=False),
+            AccountMeta(pubkey=source, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=token.pubkey, is_signer=False, is_writable=True),
+            AccountMeta(pubkey=token.program_id, is_signer=False, is_writable=False),
+        ],
+        program_id=program_id,
+    )
+    tx = client.send_transaction(Transaction().add(burn_ix), authority)
+    client.confirm_transaction(tx["result"])
+
+def token_balance(token: Token, account: PublicKey) -> int:
+    return int(token.get_balance(account)['result']['value']['amount'])
+
+def token_supply(token: Token) -> int:
+    # get_mint_info of Token only reads mints without extensions
+    return int(client.get_token_supply(token.pubkey)['result']['value']['amount'])
+
+def demo(bob: Keypair, token: Token):
+    alice_account = create_token_account(token, payer.public_key)
+    bob_account = create_token_account(token, bob.public_key)
+
+    mint_to(payer, token, alice_account, 10_000)
+    print(f"Supply: {token_supply(token)}")
+    transfer(payer, token, alice_account, bob_account, 2_500)
+    print(f"Alice: {token_balance(token, alice_account)}, Bob: {token_balance(token, bob_account)}")
+
+    try:
+        mint_to(bob, token, bob_account, 1_000_000)
+    except Exception as e:
+        print(f"Bob isn't the creator, minting fails as expected: {e}")
+
+    # Bob may move 1000 of Alice's tokens, he signs the transfer instead of her
+    approve(payer, token, alice_account, bob.public_key, 1_000)
+    transfer(bob, token, alice_account, bob_account, 600)
+    burn(bob, token, alice_account, 100)
+    print(f"Alice: {token_balance(token, alice_account)}, Bob: {token_balance(token, bob_account)}")
+
+    revoke(payer, token, alice_account)
+    try:
+        transfer(bob, token, alice_account, bob_account, 300)
+    except Exception as e:
+        print(f"Bob isn't the delegate anymore, the transfer fails as expected: {e}")
+
+    burn(payer, token, alice_account, 1_000)
+    print(f"Alice: {token_balance(token, alice_account)}, supply: {token_supply(token)}")
+
+
+if __name__ == '__main__':
+    establishConnection()
+    establishPayer()
+    checkProgram()
+    bob = setup_user_keypair()
+
+    demo(bob, create_mint(payer, decimals=2))
+
+    # the same with Token-2022 and a fee of 1%, at most 20 tokens. Bob receives 2480 of the 2500, the fee is capped,
+    # and 594 of the 600, the fees are withheld in his account
+    demo(bob, create_mint(payer, decimals=2, token_program=TOKEN_2022_PROGRAM_ID, fee=(100, 20)))
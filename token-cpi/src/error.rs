@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum TokenCpiError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Authority Address")]
+    InvalidAuthorityAddress,
+    #[error("Not The Creator")]
+    NotCreator,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<TokenCpiError> for ProgramError {
+    fn from(e: TokenCpiError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,151 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::TokenCpiError::InvalidInstruction;
+
+// The transfer fee extension of a Token-2022 mint: every transfer withholds basis_points / 10000 of the amount
+// in the destination, at most maximum_fee tokens
+pub struct TransferFeeArgs {
+    pub basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+pub enum TokenCpiInstruction {
+
+    // Creates and initializes a new mint of the token program given, the SPL Token or the Token-2022 program.
+    // The mint authority is the PDA ["authority", mint], so tokens can only be minted through this program.
+    // A Token-2022 mint may have a transfer fee, the creator can withdraw the withheld fees.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The creator, pays for the mint and the authority account
+    // 1. `[signer, writable]` The new mint account
+    // 2. `[writable]` The mint authority PDA
+    // 3. `[]` The rent sysvar
+    // 4. `[]` The system program
+    // 5. `[]` The token program
+    CreateMint {
+        decimals: u8,
+        transfer_fee: Option<TransferFeeArgs>,
+    },
+
+    // Mints tokens to a token account, signed by the mint authority PDA
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The creator of the mint
+    // 1. `[writable]` The mint
+    // 2. `[writable]` The token account receiving the tokens
+    // 3. `[]` The mint authority PDA
+    // 4. `[]` The token program
+    MintTo {
+        amount: u64,
+    },
+
+    // Transfers tokens between two token accounts, signed by the owner of the source account or its delegate.
+    // The destination receives the amount minus the transfer fee of the mint, if it has one.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner of the source token account, or its delegate
+    // 1. `[writable]` The source token account
+    // 2. `[]` The mint
+    // 3. `[writable]` The destination token account
+    // 4. `[]` The token program
+    Transfer {
+        amount: u64,
+    },
+
+    // Lets a delegate transfer or burn up to amount tokens of a token account, signed by its owner.
+    // A token account has a single delegate, approving another one replaces it.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner of the token account
+    // 1. `[writable]` The token account
+    // 2. `[]` The delegate
+    // 3. `[]` The token program
+    Approve {
+        amount: u64,
+    },
+
+    // Takes the allowance away from the delegate of a token account, signed by its owner
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner of the token account
+    // 1. `[writable]` The token account
+    // 2. `[]` The token program
+    Revoke,
+
+    // Burns tokens of a token account, which lowers the supply of the mint, signed by the owner or the delegate
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer]` The owner of the token account, or its delegate
+    // 1. `[writable]` The token account
+    // 2. `[writable]` The mint
+    // 3. `[]` The token program
+    Burn {
+        amount: u64,
+    },
+}
+
+impl TokenCpiInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => {
+                let (decimals, rest) = rest.split_first().ok_or(InvalidInstruction)?;
+                // the fee is optional, the basis points and the maximum fee follow the decimals
+                let transfer_fee = if rest.is_empty() {
+                    None
+                } else {
+                    let basis_points = rest
+                        .get(..2)
+                        .and_then(|slice| slice.try_into().ok())
+                        .map(u16::from_le_bytes)
+                        .ok_or(InvalidInstruction)?;
+                    Some(TransferFeeArgs {
+                        basis_points,
+                        maximum_fee: Self::unpack_amount(&rest[2..])?,
+                    })
+                };
+                Self::CreateMint {
+                    decimals: *decimals,
+                    transfer_fee,
+                }
+            },
+            1 => Self::MintTo {
+                amount: Self::unpack_amount(rest)?,
+            },
+            2 => Self::Transfer {
+                amount: Self::unpack_amount(rest)?,
+            },
+            3 => Self::Approve {
+                amount: Self::unpack_amount(rest)?,
+            },
+            4 => Self::Revoke,
+            5 => Self::Burn {
+                amount: Self::unpack_amount(rest)?,
+            },
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_amount(input: &[u8]) -> Result<u64, ProgramError> {
+        let amount = input
+            .get(..8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(amount)
+    }
+}
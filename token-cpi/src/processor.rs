@@ -0,0 +1,436 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{clock::Clock, rent::Rent, Sysvar},
+};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use spl_token_2022::{
+    extension::{
+        transfer_fee::{self, TransferFeeConfig},
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    state::Mint,
+};
+
+use crate::{
+    instruction::{TokenCpiInstruction, TransferFeeArgs},
+    error::TokenCpiError,
+    state::MintAuthority,
+};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = TokenCpiInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            TokenCpiInstruction::CreateMint { decimals, transfer_fee } => {
+                msg!("Instruction: CreateMint");
+                Self::process_create_mint(accounts, decimals, transfer_fee, program_id)
+            },
+            TokenCpiInstruction::MintTo { amount } => {
+                msg!("Instruction: MintTo");
+                Self::process_mint_to(accounts, amount, program_id)
+            },
+            TokenCpiInstruction::Transfer { amount } => {
+                msg!("Instruction: Transfer");
+                Self::process_transfer(accounts, amount)
+            },
+            TokenCpiInstruction::Approve { amount } => {
+                msg!("Instruction: Approve");
+                Self::process_approve(accounts, amount)
+            },
+            TokenCpiInstruction::Revoke => {
+                msg!("Instruction: Revoke");
+                Self::process_revoke(accounts)
+            },
+            TokenCpiInstruction::Burn { amount } => {
+                msg!("Instruction: Burn");
+                Self::process_burn(accounts, amount)
+            }
+        }
+    }
+
+    fn process_create_mint(
+        accounts: &[AccountInfo],
+        decimals: u8,
+        transfer_fee: Option<TransferFeeArgs>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mint_account = next_account_info(account_info_iter)?;
+        let authority_account = next_account_info(account_info_iter)?;
+        let rent_sysvar = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        // the mint doesn't exist yet, the caller picks the token program
+        let token_program = next_account_info(account_info_iter)?;
+        spl_token_2022::check_spl_token_program_account(token_program.key)?;
+
+        let (authority_pda, bump_seed) = Pubkey::find_program_address(&[b"authority", mint_account.key.as_ref()], program_id);
+        if authority_pda != *authority_account.key {
+            return Err(TokenCpiError::InvalidAuthorityAddress.into());
+        }
+
+        let rent = Rent::get()?;
+        // extensions live behind the mint data, the account has to be big enough for them from the start
+        let extensions: &[ExtensionType] = match transfer_fee {
+            Some(_) => &[ExtensionType::TransferFeeConfig],
+            None => &[],
+        };
+        let space = ExtensionType::try_calculate_account_len::<Mint>(extensions)?;
+
+        // the mint is a normal keypair account, it signed the transaction, so a plain invoke is enough.
+        // It has to be owned by the token program, otherwise the token program can't initialize it.
+        msg!("Calling the system program to create the mint account...");
+        invoke(
+            &system_instruction::create_account(
+                creator.key,
+                mint_account.key,
+                rent.minimum_balance(space),
+                space as u64,
+                token_program.key,
+            ),
+            &[
+                creator.clone(),
+                mint_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+
+        // extensions are initialized before the mint, the SPL Token program refuses this instruction
+        if let Some(fee) = transfer_fee {
+            msg!("Calling the token program to initialize a transfer fee of {} basis points...", fee.basis_points);
+            invoke(
+                &transfer_fee::instruction::initialize_transfer_fee_config(
+                    token_program.key,
+                    mint_account.key,
+                    Some(&authority_pda),
+                    Some(creator.key),
+                    fee.basis_points,
+                    fee.maximum_fee,
+                )?,
+                &[
+                    mint_account.clone(),
+                    token_program.clone(),
+                ],
+            )?;
+        }
+
+        msg!("Calling the token program to initialize the mint...");
+        invoke(
+            &spl_token_2022::instruction::initialize_mint(
+                token_program.key,
+                mint_account.key,
+                &authority_pda,
+                None,
+                decimals,
+            )?,
+            &[
+                mint_account.clone(),
+                rent_sysvar.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        // the authority PDA only has to exist as an address to be a mint authority, we give it data
+        // so the program remembers who is allowed to mint
+        msg!("Creating the mint authority account...");
+        invoke_signed(
+            &system_instruction::create_account(
+                creator.key,
+                authority_account.key,
+                rent.minimum_balance(MintAuthority::LEN),
+                MintAuthority::LEN as u64,
+                program_id,
+            ),
+            &[
+                creator.clone(),
+                authority_account.clone(),
+                system_program.clone(),
+            ],
+            &[&[b"authority", mint_account.key.as_ref(), &[bump_seed]]],
+        )?;
+
+        let authority_info = MintAuthority {
+            is_initialized: true,
+            mint: *mint_account.key,
+            creator: *creator.key,
+            bump_seed,
+        };
+        authority_info.serialize(&mut &mut authority_account.data.borrow_mut()[..])?;
+
+        Ok(())
+    }
+
+    fn process_mint_to(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let creator = next_account_info(account_info_iter)?;
+
+        if !creator.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let mint_account = next_account_info(account_info_iter)?;
+        let destination = next_account_info(account_info_iter)?;
+
+        let authority_account = next_account_info(account_info_iter)?;
+        if authority_account.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let authority_info = MintAuthority::try_from_slice(&authority_account.data.borrow())?;
+        if authority_info.mint != *mint_account.key {
+            return Err(TokenCpiError::InvalidAuthorityAddress.into());
+        }
+        if authority_info.creator != *creator.key {
+            return Err(TokenCpiError::NotCreator.into());
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        Self::check_token_program(token_program, mint_account)?;
+
+        let mint_to_ix = spl_token_2022::instruction::mint_to(
+            token_program.key,
+            mint_account.key,
+            destination.key,
+            authority_account.key,
+            &[authority_account.key],
+            amount,
+        )?;
+
+        // nobody holds a private key for the authority PDA, the program signs for it with its seeds
+        msg!("Calling the token program to mint {} tokens...", amount);
+        invoke_signed(
+            &mint_to_ix,
+            &[
+                mint_account.clone(),
+                destination.clone(),
+                authority_account.clone(),
+                token_program.clone(),
+            ],
+            &[&[b"authority", mint_account.key.as_ref(), &[authority_info.bump_seed]]],
+        )?;
+
+        Ok(())
+    }
+
+    fn process_transfer(
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let source = next_account_info(account_info_iter)?;
+        let mint_account = next_account_info(account_info_iter)?;
+        let destination = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        Self::check_token_program(token_program, mint_account)?;
+
+        // transfer_checked makes the token program compare the mint and the decimals with the source account.
+        // Token-2022 refuses the plain transfer for mints with a transfer fee.
+        let mint_data = mint_account.try_borrow_data()?;
+        let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        let decimals = mint.base.decimals;
+
+        // With a transfer fee the destination receives less than the amount, the fee is withheld in the
+        // destination account until the withdraw authority collects it. The fee of the mint may have been changed,
+        // the new one only applies from the epoch it was scheduled for. transfer_checked_with_fee makes the token
+        // program confirm the fee computed here, so the amounts logged are the ones which really move.
+        let transfer_ix = match mint.get_extension::<TransferFeeConfig>() {
+            Ok(fee_config) => {
+                let fee = fee_config
+                    .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                    .ok_or(TokenCpiError::AmountOverflow)?;
+                msg!("The destination receives {} tokens, {} are withheld as fee", amount - fee, fee);
+                transfer_fee::instruction::transfer_checked_with_fee(
+                    token_program.key,
+                    source.key,
+                    mint_account.key,
+                    destination.key,
+                    owner.key,
+                    &[],
+                    amount,
+                    decimals,
+                    fee,
+                )?
+            },
+            Err(_) => spl_token_2022::instruction::transfer_checked(
+                token_program.key,
+                source.key,
+                mint_account.key,
+                destination.key,
+                owner.key,
+                &[],
+                amount,
+                decimals,
+            )?,
+        };
+        drop(mint_data);
+
+        // the owner signed the transaction, the signature is passed on to the token program with a plain invoke
+        msg!("Calling the token program to transfer {} tokens...", amount);
+        invoke(
+            &transfer_ix,
+            &[
+                source.clone(),
+                mint_account.clone(),
+                destination.clone(),
+                owner.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn process_approve(
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let source = next_account_info(account_info_iter)?;
+        let delegate = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        Self::check_token_program(token_program, source)?;
+
+        let approve_ix = spl_token_2022::instruction::approve(
+            token_program.key,
+            source.key,
+            delegate.key,
+            owner.key,
+            &[],
+            amount,
+        )?;
+
+        // the delegate doesn't sign, it is only written into the token account. Afterwards it may sign a
+        // Transfer or a Burn of the account in place of the owner, until the allowance is used up or revoked.
+        msg!("Calling the token program to approve {} tokens for {}...", amount, delegate.key);
+        invoke(
+            &approve_ix,
+            &[
+                source.clone(),
+                delegate.clone(),
+                owner.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn process_revoke(
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let source = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        Self::check_token_program(token_program, source)?;
+
+        let revoke_ix = spl_token_2022::instruction::revoke(
+            token_program.key,
+            source.key,
+            owner.key,
+            &[],
+        )?;
+
+        msg!("Calling the token program to revoke the delegate...");
+        invoke(
+            &revoke_ix,
+            &[
+                source.clone(),
+                owner.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn process_burn(
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority = next_account_info(account_info_iter)?;
+
+        if !authority.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let source = next_account_info(account_info_iter)?;
+        let mint_account = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        Self::check_token_program(token_program, mint_account)?;
+
+        let burn_ix = spl_token_2022::instruction::burn(
+            token_program.key,
+            source.key,
+            mint_account.key,
+            authority.key,
+            &[],
+            amount,
+        )?;
+
+        // the mint is writable because burning lowers its supply. Unlike MintTo this needs no PDA, the tokens
+        // belong to the owner of the token account, who or whose delegate signed.
+        msg!("Calling the token program to burn {} tokens...", amount);
+        invoke(
+            &burn_ix,
+            &[
+                source.clone(),
+                mint_account.clone(),
+                authority.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    // Mints and token accounts are owned by the token program which created them, the SPL Token or the Token-2022
+    // program. Their instructions are the same, the token program to call is the owner of the account. Passing
+    // the other one would make the CPI fail in the token program, checking it here gives a clearer error.
+    fn check_token_program(token_program: &AccountInfo, account: &AccountInfo) -> ProgramResult {
+        spl_token_2022::check_spl_token_program_account(token_program.key)?;
+        if account.owner != token_program.key {
+            msg!("{} is owned by {}, not by {}", account.key, account.owner, token_program.key);
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::pubkey::Pubkey;
+
+// Stored in the mint authority PDA ["authority", mint]. The PDA is the mint authority of the token,
+// the data decides who may ask the program to mint.
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MintAuthority {
+    pub is_initialized: bool,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub bump_seed: u8,
+}
+
+impl MintAuthority {
+    pub const LEN: usize = 1 + 32 + 32 + 1;
+}
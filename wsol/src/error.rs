@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use solana_program::program_error::ProgramError;
+
+#[derive(Error, Debug, Copy, Clone)]
+pub enum WsolError {
+    #[error("Invalid Instruction")]
+    InvalidInstruction,
+    #[error("Invalid Wrapped Account Address")]
+    InvalidWrappedAccountAddress,
+    #[error("Invalid Mint")]
+    InvalidMint,
+    #[error("Amount Overflow")]
+    AmountOverflow,
+}
+
+impl From<WsolError> for ProgramError {
+    fn from(e: WsolError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
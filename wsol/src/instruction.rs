@@ -0,0 +1,58 @@
+use std::convert::TryInto;
+use solana_program::program_error::ProgramError;
+
+use crate::error::WsolError::InvalidInstruction;
+
+pub enum WsolInstruction {
+
+    // Wraps lamports into wrapped SOL. The wrapped SOL is held in a token account of the native mint at a
+    // PDA derived from ["wsol", owner], which is created on the first call. Later calls add lamports to
+    // the same account.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner of the lamports
+    // 1. `[writable]` The wrapped SOL token account PDA
+    // 2. `[]` The native mint
+    // 3. `[]` The rent sysvar
+    // 4. `[]` The system program
+    // 5. `[]` The token program
+    Wrap {
+        amount: u64,
+    },
+
+    // Unwraps everything by closing the wrapped SOL token account. The wrapped lamports and the rent of the
+    // account go back to the owner.
+    //
+    //
+    // Accounts expected:
+    //
+    // 0. `[signer, writable]` The owner
+    // 1. `[writable]` The wrapped SOL token account PDA
+    // 2. `[]` The token program
+    Unwrap,
+}
+
+impl WsolInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (tag, rest) = input.split_first().ok_or(InvalidInstruction)?;
+
+        Ok(match tag {
+            0 => Self::Wrap {
+                amount: Self::unpack_u64(rest, 0)?,
+            },
+            1 => Self::Unwrap,
+            _ => return Err(InvalidInstruction.into()),
+        })
+    }
+
+    fn unpack_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+        let value = input
+            .get(offset..offset + 8)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u64::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(value)
+    }
+}
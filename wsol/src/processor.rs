@@ -0,0 +1,170 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    msg,
+    pubkey::Pubkey,
+    program_pack::Pack,
+    program::{invoke, invoke_signed},
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use spl_token::{native_mint, state::Account as TokenAccount};
+
+use crate::{instruction::WsolInstruction, error::WsolError};
+
+pub struct Processor;
+
+impl Processor {
+    pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+        let instruction = WsolInstruction::unpack(instruction_data)?;
+
+        match instruction {
+            WsolInstruction::Wrap { amount } => {
+                msg!("Instruction: Wrap");
+                Self::process_wrap(accounts, amount, program_id)
+            },
+            WsolInstruction::Unwrap => {
+                msg!("Instruction: Unwrap");
+                Self::process_unwrap(accounts, program_id)
+            }
+        }
+    }
+
+    fn process_wrap(
+        accounts: &[AccountInfo],
+        amount: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let wrapped_account = next_account_info(account_info_iter)?;
+        let mint = next_account_info(account_info_iter)?;
+        if *mint.key != native_mint::id() {
+            return Err(WsolError::InvalidMint.into());
+        }
+
+        let rent_sysvar = next_account_info(account_info_iter)?;
+        let system_program = next_account_info(account_info_iter)?;
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let (wrapped_pda, bump_seed) = Pubkey::find_program_address(&[b"wsol", owner.key.as_ref()], program_id);
+        if wrapped_pda != *wrapped_account.key {
+            return Err(WsolError::InvalidWrappedAccountAddress.into());
+        }
+
+        if wrapped_account.data_is_empty() {
+            // Wrapped SOL has no mint authority, the tokens are the lamports of the account above its rent.
+            // Creating the account with rent plus amount and initializing it is all it takes to wrap.
+            let lamports = Rent::get()?.minimum_balance(TokenAccount::LEN)
+                .checked_add(amount)
+                .ok_or(WsolError::AmountOverflow)?;
+
+            msg!("Calling the system program to create the wrapped SOL account with {} lamports...", amount);
+            invoke_signed(
+                &system_instruction::create_account(
+                    owner.key,
+                    wrapped_account.key,
+                    lamports,
+                    TokenAccount::LEN as u64,
+                    token_program.key,
+                ),
+                &[owner.clone(), wrapped_account.clone(), system_program.clone()],
+                &[&[b"wsol", owner.key.as_ref(), &[bump_seed]]],
+            )?;
+
+            // the owner of the lamports becomes the owner of the token account, our program only
+            // needs the PDA to have an address the client can derive
+            msg!("Calling the token program to initialize the wrapped SOL account...");
+            invoke(
+                &spl_token::instruction::initialize_account(
+                    token_program.key,
+                    wrapped_account.key,
+                    mint.key,
+                    owner.key,
+                )?,
+                &[
+                    wrapped_account.clone(),
+                    mint.clone(),
+                    owner.clone(),
+                    rent_sysvar.clone(),
+                    token_program.clone(),
+                ],
+            )?;
+        } else {
+            let wrapped_info = TokenAccount::unpack(&wrapped_account.try_borrow_data()?)?;
+            if wrapped_info.mint != native_mint::id() {
+                return Err(WsolError::InvalidMint.into());
+            }
+
+            // Lamports sent to an existing account don't show up as tokens on their own. The token
+            // program only updates the token amount when it is told to with SyncNative.
+            msg!("Calling the system program to add {} lamports to the wrapped SOL account...", amount);
+            invoke(
+                &system_instruction::transfer(owner.key, wrapped_account.key, amount),
+                &[owner.clone(), wrapped_account.clone(), system_program.clone()],
+            )?;
+
+            msg!("Calling the token program to sync the wrapped SOL amount...");
+            invoke(
+                &spl_token::instruction::sync_native(token_program.key, wrapped_account.key)?,
+                &[wrapped_account.clone(), token_program.clone()],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn process_unwrap(
+        accounts: &[AccountInfo],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner = next_account_info(account_info_iter)?;
+
+        if !owner.is_signer {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let wrapped_account = next_account_info(account_info_iter)?;
+        let (wrapped_pda, _) = Pubkey::find_program_address(&[b"wsol", owner.key.as_ref()], program_id);
+        if wrapped_pda != *wrapped_account.key {
+            return Err(WsolError::InvalidWrappedAccountAddress.into());
+        }
+
+        let token_program = next_account_info(account_info_iter)?;
+        if *token_program.key != spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // There is no "unwrap" in the token program. Closing a wrapped SOL account is allowed with a balance
+        // above 0 and hands all its lamports, wrapped ones and rent, to the destination.
+        msg!("Calling the token program to close the wrapped SOL account...");
+        invoke(
+            &spl_token::instruction::close_account(
+                token_program.key,
+                wrapped_account.key,
+                owner.key,
+                owner.key,
+                &[owner.key],
+            )?,
+            &[
+                wrapped_account.clone(),
+                owner.clone(),
+                owner.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,232 @@
+// Builds, deploys and verifies the programs of this repository. The build and the deploy are done by
+// `cargo build-sbf` and `solana program deploy`, so the Solana tool suite has to be installed.
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use clap::{Parser, Subcommand};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    hash::hash,
+    pubkey::Pubkey,
+    signature::read_keypair_file,
+    signer::Signer,
+};
+
+use client::{cli, profile::Profile};
+
+#[derive(Parser)]
+#[command(about = "Build, deploy and verify the programs of this repository")]
+struct Args {
+    #[command(subcommand)]
+    task: Task,
+    /// Cluster profile of the client, like devnet. CLIENT_CLUSTER or the default of client.toml if it isn't given
+    #[arg(long, global = true)]
+    cluster: Option<String>,
+    /// RPC url, taken from the cluster profile or the solana cli config if it isn't given
+    #[arg(long, global = true)]
+    url: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum Task {
+    /// Builds the program with cargo build-sbf into <crate>/target/deploy
+    Build {
+        /// Directory of the program crate, like from_scratch
+        program: PathBuf,
+    },
+    /// Builds the program, deploys it or upgrades an existing deployment, records its id and verifies it
+    Deploy {
+        /// Directory of the program crate, like from_scratch
+        program: PathBuf,
+        /// Keypair of the program, its address is the program id. Defaults to the one cargo build-sbf generates
+        #[arg(long)]
+        program_keypair: Option<PathBuf>,
+        /// Keypair paying for the deployment, taken from the cluster profile or the solana cli config if it isn't given
+        #[arg(long)]
+        keypair: Option<String>,
+        /// Rust file the program ids are recorded in
+        #[arg(long, default_value = "program_ids.rs")]
+        out: PathBuf,
+    },
+    /// Checks that the deployed program is the local build
+    Verify {
+        /// Directory of the program crate, like from_scratch
+        program: PathBuf,
+        /// Program id, the address of the default program keypair if it isn't given
+        #[arg(long)]
+        program_id: Option<Pubkey>,
+    },
+}
+
+// the crates are independent, so every one of them has its own target directory
+struct Program {
+    dir: PathBuf,
+    // name of the library, cargo build-sbf names the artifacts after it
+    lib_name: String,
+}
+
+impl Program {
+    fn new(dir: &Path) -> Result<Self, Box<dyn Error>> {
+        let manifest: toml::Value = fs::read_to_string(dir.join("Cargo.toml"))
+            .map_err(|e| format!("{} has no Cargo.toml: {}", dir.display(), e))?
+            .parse()?;
+        let lib_name = manifest
+            .get("lib")
+            .and_then(|lib| lib.get("name"))
+            .or_else(|| manifest.get("package").and_then(|package| package.get("name")))
+            .and_then(|name| name.as_str())
+            .ok_or("Cargo.toml has no package name")?
+            .replace('-', "_");
+
+        Ok(Self { dir: dir.to_path_buf(), lib_name })
+    }
+
+    fn deploy_dir(&self) -> PathBuf {
+        self.dir.join("target").join("deploy")
+    }
+
+    fn so_path(&self) -> PathBuf {
+        self.deploy_dir().join(format!("{}.so", self.lib_name))
+    }
+
+    fn keypair_path(&self) -> PathBuf {
+        self.deploy_dir().join(format!("{}-keypair.json", self.lib_name))
+    }
+
+    // the name of the constant in the program ids file
+    fn const_name(&self) -> String {
+        self.lib_name.to_uppercase()
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let profile = Profile::load(args.cluster)?;
+    // the solana cli falls back to its own config, like the profile does
+    let url = args.url.or_else(|| profile.url.clone());
+
+    match args.task {
+        Task::Build { program } => build(&Program::new(&program)?),
+        Task::Deploy { program, program_keypair, keypair, out } => {
+            let program = Program::new(&program)?;
+            build(&program)?;
+
+            let program_keypair = program_keypair.unwrap_or_else(|| program.keypair_path());
+            let program_id = read_keypair_file(&program_keypair)
+                .map_err(|e| format!("Failed to read program keypair {}: {}", program_keypair.display(), e))?
+                .pubkey();
+
+            let rpc = cli::rpc_client(&profile, url.clone());
+            let upgrade = rpc.get_account(&program_id).is_ok();
+            println!("{} {} as {}...", if upgrade { "Upgrading" } else { "Deploying" }, program.lib_name, program_id);
+
+            let mut deploy = Command::new("solana");
+            deploy.arg("program").arg("deploy").arg(program.so_path()).arg("--program-id").arg(&program_keypair);
+            if let Some(url) = &url {
+                deploy.arg("--url").arg(url);
+            }
+            if let Some(keypair) = keypair.or_else(|| profile.keypair.clone()) {
+                deploy.arg("--keypair").arg(keypair);
+            }
+            run(&mut deploy)?;
+
+            record_program_id(&out, &program.const_name(), &program_id)?;
+            println!("Recorded {} in {}", program_id, out.display());
+
+            verify(&rpc, &program, &program_id)
+        },
+        Task::Verify { program, program_id } => {
+            let program = Program::new(&program)?;
+            let program_id = match program_id {
+                Some(program_id) => program_id,
+                None => read_keypair_file(program.keypair_path())
+                    .map_err(|e| format!("No --program-id and no program keypair: {}", e))?
+                    .pubkey(),
+            };
+            verify(&cli::rpc_client(&profile, url), &program, &program_id)
+        },
+    }
+}
+
+fn build(program: &Program) -> Result<(), Box<dyn Error>> {
+    println!("Building {}...", program.dir.display());
+    run(Command::new("cargo")
+        .arg("build-sbf")
+        .arg("--manifest-path")
+        .arg(program.dir.join("Cargo.toml"))
+        .arg("--sbf-out-dir")
+        .arg(program.deploy_dir()))
+}
+
+fn run(command: &mut Command) -> Result<(), Box<dyn Error>> {
+    let status = command.status().map_err(|e| format!("Failed to run {:?}: {}", command, e))?;
+    if !status.success() {
+        return Err(format!("{:?} failed with {}", command, status).into());
+    }
+    Ok(())
+}
+
+// The upgradeable loader keeps the program in a separate program data account, after a header with the slot of the
+// deployment and the upgrade authority. The account is as big as the largest version deployed so far, so the rest is
+// zeros.
+fn verify(rpc: &RpcClient, program: &Program, program_id: &Pubkey) -> Result<(), Box<dyn Error>> {
+    let local = fs::read(program.so_path())
+        .map_err(|e| format!("Failed to read {}, build it first: {}", program.so_path().display(), e))?;
+
+    let (program_data, _) = Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    let data = rpc.get_account_data(&program_data)?;
+    let deployed = data
+        .get(UpgradeableLoaderState::size_of_programdata_metadata()..)
+        .ok_or("Program data account is too small")?;
+
+    let (code, padding) = deployed.split_at(local.len().min(deployed.len()));
+    if code != local.as_slice() || padding.iter().any(|byte| *byte != 0) {
+        return Err(format!(
+            "{} doesn't match the local build, deployed {} local {}",
+            program_id,
+            hash(code),
+            hash(&local)
+        )
+        .into());
+    }
+
+    println!("{} matches the local build, hash {}", program_id, hash(&local));
+    Ok(())
+}
+
+// The file holds one constant per program. Deploying a program replaces its constant and keeps the others.
+fn record_program_id(out: &Path, name: &str, program_id: &Pubkey) -> Result<(), Box<dyn Error>> {
+    let mut ids = BTreeMap::new();
+    if let Ok(existing) = fs::read_to_string(out) {
+        for line in existing.lines() {
+            if let Some((const_name, id)) = parse_constant(line) {
+                ids.insert(const_name.to_string(), id.to_string());
+            }
+        }
+    }
+    ids.insert(name.to_string(), program_id.to_string());
+
+    let mut file = String::from(
+        "// Generated by `cargo xtask deploy`, the ids of the programs as they were deployed last\n\
+         use solana_sdk::{pubkey, pubkey::Pubkey};\n\n",
+    );
+    for (const_name, id) in ids {
+        file.push_str(&format!("pub const {}: Pubkey = pubkey!(\"{}\");\n", const_name, id));
+    }
+    fs::write(out, file)?;
+    Ok(())
+}
+
+// `pub const NAME: Pubkey = pubkey!("<id>");`
+fn parse_constant(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix("pub const ")?;
+    let (name, rest) = rest.split_once(": Pubkey = pubkey!(\"")?;
+    let id = rest.strip_suffix("\");")?;
+    Some((name, id))
+}